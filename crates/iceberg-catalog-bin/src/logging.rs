@@ -0,0 +1,263 @@
+use iceberg_catalog::{LogFormat, CONFIG};
+use serde_json::{Map, Value};
+use std::fmt::Debug;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::{JsonFields, Writer};
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Version of the Elastic Common Schema the `ecs` format follows.
+const ECS_VERSION: &str = "8.11.0";
+
+/// Install the global subscriber writing logs to stdout in the configured format.
+pub(crate) fn init() {
+    let env_filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env_lossy();
+
+    match CONFIG.log_format {
+        LogFormat::Json => tracing_subscriber::fmt()
+            .json()
+            .flatten_event(true)
+            .with_current_span(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_env_filter(env_filter)
+            .init(),
+        LogFormat::Ecs => init_structured(StructuredFormat::Ecs, env_filter),
+        LogFormat::Gcp => init_structured(StructuredFormat::Gcp, env_filter),
+    }
+}
+
+fn init_structured(format: StructuredFormat, env_filter: EnvFilter) {
+    // Span fields are recorded as JSON, so that they can be merged into the log record.
+    tracing_subscriber::fmt()
+        .fmt_fields(JsonFields::new())
+        .event_format(format)
+        .with_env_filter(env_filter)
+        .init();
+}
+
+/// Formats events as one JSON object per line with the field names
+/// expected by a log aggregator.
+#[derive(Debug, Clone, Copy)]
+enum StructuredFormat {
+    /// Elastic Common Schema
+    Ecs,
+    /// Google Cloud Logging
+    Gcp,
+}
+
+impl<S, N> FormatEvent<S, N> for StructuredFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> std::fmt::Result {
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+        let metadata = event.metadata();
+        let span = ctx.lookup_current();
+
+        // Fields of inner spans take precedence over fields of outer spans.
+        let mut span_fields = Map::new();
+        for scope_span in ctx
+            .event_scope()
+            .into_iter()
+            .flat_map(|scope| scope.from_root())
+        {
+            let extensions = scope_span.extensions();
+            let Some(recorded) = extensions.get::<FormattedFields<N>>() else {
+                continue;
+            };
+            if let Ok(Value::Object(recorded)) = serde_json::from_str(&recorded.fields) {
+                span_fields.extend(recorded);
+            }
+        }
+
+        let line = LogLine {
+            timestamp: chrono::Utc::now(),
+            level: *metadata.level(),
+            target: metadata.target(),
+            file: metadata.file(),
+            line: metadata.line(),
+            span: span.as_ref().map(|s| s.name()),
+            fields: fields.0,
+            span_fields,
+        };
+        let line = serde_json::to_string(&line.to_json(*self)).map_err(|_| std::fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor(Map<String, Value>);
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{value:?}").into());
+    }
+}
+
+struct LogLine<'a> {
+    timestamp: chrono::DateTime<chrono::Utc>,
+    level: Level,
+    target: &'a str,
+    file: Option<&'a str>,
+    line: Option<u32>,
+    span: Option<&'a str>,
+    fields: Map<String, Value>,
+    span_fields: Map<String, Value>,
+}
+
+impl LogLine<'_> {
+    fn to_json(&self, format: StructuredFormat) -> Value {
+        let mut fields = self.fields.clone();
+        let message = fields.remove("message").unwrap_or_default();
+        let timestamp = self
+            .timestamp
+            .to_rfc3339_opts(chrono::SecondsFormat::Micros, true);
+
+        let mut record = match format {
+            StructuredFormat::Ecs => serde_json::json!({
+                "@timestamp": timestamp,
+                "ecs.version": ECS_VERSION,
+                "log.level": self.level.as_str().to_lowercase(),
+                "log.logger": self.target,
+                "log.origin.file.name": self.file,
+                "log.origin.file.line": self.line,
+                "message": message,
+            }),
+            StructuredFormat::Gcp => serde_json::json!({
+                "time": timestamp,
+                "severity": gcp_severity(self.level),
+                "message": message,
+                "target": self.target,
+                "logging.googleapis.com/sourceLocation": {
+                    "file": self.file,
+                    // Cloud Logging expects the line as string
+                    "line": self.line.map(|l| l.to_string()),
+                },
+            }),
+        };
+
+        let object = record
+            .as_object_mut()
+            .expect("Log record is always an object");
+        if let Some(span) = self.span {
+            let key = match format {
+                StructuredFormat::Ecs => "span.name",
+                StructuredFormat::Gcp => "span",
+            };
+            object.insert(key.to_string(), span.into());
+        }
+        // Fields of the event take precedence over fields of its spans.
+        for (key, value) in fields.into_iter().chain(self.span_fields.clone()) {
+            object.entry(key).or_insert(value);
+        }
+        record
+    }
+}
+
+fn gcp_severity(level: Level) -> &'static str {
+    match level {
+        Level::TRACE | Level::DEBUG => "DEBUG",
+        Level::INFO => "INFO",
+        Level::WARN => "WARNING",
+        Level::ERROR => "ERROR",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line() -> LogLine<'static> {
+        LogLine {
+            timestamp: chrono::DateTime::parse_from_rfc3339("2025-02-04T10:00:00Z")
+                .unwrap()
+                .into(),
+            level: Level::WARN,
+            target: "iceberg_catalog::service",
+            file: Some("src/service/mod.rs"),
+            line: Some(42),
+            span: Some("request"),
+            fields: serde_json::json!({"message": "Slow request", "duration_ms": 1200})
+                .as_object()
+                .unwrap()
+                .clone(),
+            span_fields: serde_json::json!({"request_id": "abc", "duration_ms": 1})
+                .as_object()
+                .unwrap()
+                .clone(),
+        }
+    }
+
+    #[test]
+    fn test_ecs_format() {
+        assert_eq!(
+            line().to_json(StructuredFormat::Ecs),
+            serde_json::json!({
+                "@timestamp": "2025-02-04T10:00:00.000000Z",
+                "ecs.version": ECS_VERSION,
+                "log.level": "warn",
+                "log.logger": "iceberg_catalog::service",
+                "log.origin.file.name": "src/service/mod.rs",
+                "log.origin.file.line": 42,
+                "message": "Slow request",
+                "span.name": "request",
+                "duration_ms": 1200,
+                "request_id": "abc",
+            })
+        );
+    }
+
+    #[test]
+    fn test_gcp_format() {
+        assert_eq!(
+            line().to_json(StructuredFormat::Gcp),
+            serde_json::json!({
+                "time": "2025-02-04T10:00:00.000000Z",
+                "severity": "WARNING",
+                "message": "Slow request",
+                "target": "iceberg_catalog::service",
+                "logging.googleapis.com/sourceLocation": {
+                    "file": "src/service/mod.rs",
+                    "line": "42",
+                },
+                "span": "request",
+                "duration_ms": 1200,
+                "request_id": "abc",
+            })
+        );
+    }
+}
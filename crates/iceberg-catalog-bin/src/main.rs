@@ -3,13 +3,15 @@
 use clap::{Parser, Subcommand};
 use iceberg_catalog::api::management::v1::api_doc as v1_api_doc;
 use iceberg_catalog::service::authz::implementations::openfga::UnauthenticatedOpenFGAAuthorizer;
+use iceberg_catalog::service::authz::implementations::webhook::WebhookAuthorizer;
 use iceberg_catalog::service::authz::AllowAllAuthorizer;
 use iceberg_catalog::{AuthZBackend, CONFIG};
-use tracing_subscriber::filter::LevelFilter;
-use tracing_subscriber::EnvFilter;
 
 mod healthcheck;
+mod logging;
 mod serve;
+#[cfg(feature = "synthetic-data")]
+mod synthetic;
 #[cfg(feature = "ui")]
 mod ui;
 mod wait_for_db;
@@ -93,24 +95,52 @@ enum Commands {
     Version {},
     /// Get the OpenAPI specification of the Management API as yaml
     ManagementOpenapi {},
+    /// Generate a synthetic warehouse for benchmarking. For development only.
+    #[cfg(feature = "synthetic-data")]
+    #[command(hide = true)]
+    GenerateSyntheticWarehouse {
+        #[clap(long, default_value = "00000000-0000-0000-0000-000000000000")]
+        project_id: String,
+        #[clap(long, default_value = "synthetic")]
+        warehouse_name: String,
+        #[clap(long, default_value_t = 10)]
+        namespaces: usize,
+        #[clap(long, default_value_t = 100)]
+        tables_per_namespace: usize,
+        #[clap(long, default_value_t = 20)]
+        snapshots_per_table: usize,
+        #[clap(long, default_value_t = 20)]
+        columns_per_table: usize,
+    },
+    /// Benchmark loading the tables of a warehouse with different batch sizes. For development only.
+    #[cfg(feature = "synthetic-data")]
+    #[command(hide = true)]
+    BenchLoadTables {
+        #[clap(long)]
+        warehouse_id: String,
+        #[clap(long, value_delimiter = ',', default_value = "1,10,100")]
+        batch_sizes: Vec<usize>,
+        #[clap(long, default_value_t = 3)]
+        iterations: usize,
+    },
+    /// Benchmark listing the tabulars of a warehouse page by page. For development only.
+    #[cfg(feature = "synthetic-data")]
+    #[command(hide = true)]
+    BenchListTabulars {
+        #[clap(long)]
+        warehouse_id: String,
+        #[clap(long, value_delimiter = ',', default_value = "100,1000")]
+        page_sizes: Vec<usize>,
+        #[clap(long, default_value_t = 3)]
+        iterations: usize,
+    },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    tracing_subscriber::fmt()
-        .json()
-        .flatten_event(true)
-        .with_current_span(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_env_filter(
-            EnvFilter::builder()
-                .with_default_directive(LevelFilter::INFO.into())
-                .from_env_lossy(),
-        )
-        .init();
+    logging::init();
 
     match cli.command {
         Some(Commands::WaitForDB {
@@ -125,27 +155,16 @@ async fn main() -> anyhow::Result<()> {
         }
         Some(Commands::Migrate {}) => {
             print_info();
-            println!("Migrating authorizer...");
-            iceberg_catalog::service::authz::implementations::migrate_default_authorizer().await?;
-            println!("Authorizer migration complete.");
-
-            println!("Migrating database...");
-            let write_pool = iceberg_catalog::implementations::postgres::get_writer_pool(
-                CONFIG
-                    .to_pool_opts()
-                    .acquire_timeout(std::time::Duration::from_secs(1)),
-            )
-            .await?;
-
-            // This embeds database migrations in the application binary so we can ensure the database
-            // is migrated correctly on startup
-            iceberg_catalog::implementations::postgres::migrations::migrate(&write_pool).await?;
-            println!("Database migration complete.");
+            migrate().await?;
         }
         Some(Commands::Serve { force_start }) => {
             print_info();
             tracing::info!("Starting server on 0.0.0.0:{}...", CONFIG.listen_port);
             let bind_addr = std::net::SocketAddr::from(([0, 0, 0, 0], CONFIG.listen_port));
+            if CONFIG.migrate_on_startup {
+                wait_for_db::wait_for_db(false, 15, 2, true).await?;
+                migrate().await?;
+            }
             if !force_start {
                 wait_for_db::wait_for_db(true, 0, 0, true).await?;
             }
@@ -167,9 +186,45 @@ async fn main() -> anyhow::Result<()> {
             let doc = match CONFIG.authz_backend {
                 AuthZBackend::AllowAll => v1_api_doc::<AllowAllAuthorizer>(),
                 AuthZBackend::OpenFGA => v1_api_doc::<UnauthenticatedOpenFGAAuthorizer>(),
+                AuthZBackend::Webhook => v1_api_doc::<WebhookAuthorizer>(),
             };
             println!("{}", doc.to_yaml()?);
         }
+        #[cfg(feature = "synthetic-data")]
+        Some(Commands::GenerateSyntheticWarehouse {
+            project_id,
+            warehouse_name,
+            namespaces,
+            tables_per_namespace,
+            snapshots_per_table,
+            columns_per_table,
+        }) => {
+            synthetic::generate(
+                &project_id,
+                warehouse_name,
+                namespaces,
+                tables_per_namespace,
+                snapshots_per_table,
+                columns_per_table,
+            )
+            .await?;
+        }
+        #[cfg(feature = "synthetic-data")]
+        Some(Commands::BenchLoadTables {
+            warehouse_id,
+            batch_sizes,
+            iterations,
+        }) => {
+            synthetic::bench(&warehouse_id, &batch_sizes, iterations).await?;
+        }
+        #[cfg(feature = "synthetic-data")]
+        Some(Commands::BenchListTabulars {
+            warehouse_id,
+            page_sizes,
+            iterations,
+        }) => {
+            synthetic::bench_list(&warehouse_id, &page_sizes, iterations).await?;
+        }
         None => {
             // Error out if no subcommand is provided.
             eprintln!("No subcommand provided. Use --help for more information.");
@@ -179,6 +234,35 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Migrate the authorizer and the database. Replicas migrating concurrently, for example
+/// with `LAKEKEEPER__MIGRATE_ON_STARTUP`, wait for each other, so migrations run only once.
+async fn migrate() -> anyhow::Result<()> {
+    let write_pool = iceberg_catalog::implementations::postgres::get_writer_pool(
+        CONFIG
+            .to_pool_opts()
+            .acquire_timeout(std::time::Duration::from_secs(1)),
+    )
+    .await?;
+
+    iceberg_catalog::implementations::postgres::leader::with_exclusive_lock(
+        &write_pool,
+        "migrations",
+        async {
+            println!("Migrating authorizer...");
+            iceberg_catalog::service::authz::implementations::migrate_default_authorizer().await?;
+            println!("Authorizer migration complete.");
+
+            println!("Migrating database...");
+            // This embeds database migrations in the application binary so we can ensure the database
+            // is migrated correctly on startup
+            iceberg_catalog::implementations::postgres::migrations::migrate(&write_pool).await?;
+            println!("Database migration complete.");
+            Ok::<_, anyhow::Error>(())
+        },
+    )
+    .await
+}
+
 fn print_info() {
     let console_span = r" _      ___  _   _______ _   _______ ___________ _____ _____ 
 | |    / _ \| | / |  ___| | / |  ___|  ___| ___ |  ___| ___ \
@@ -1,24 +1,24 @@
 use anyhow::{anyhow, Error};
-use iceberg_catalog::api::router::{new_full_router, serve as service_serve, RouterArgs};
-use iceberg_catalog::implementations::postgres::{CatalogState, PostgresCatalog, ReadWrite};
+use futures::FutureExt;
+use iceberg_catalog::api::router::serve as service_serve;
+use iceberg_catalog::embedded::{Duty, LakekeeperBuilder};
+use iceberg_catalog::implementations::postgres::leader::run_as_leader;
+use iceberg_catalog::implementations::postgres::pool_metrics::pool_metrics_task;
+use iceberg_catalog::implementations::postgres::{CatalogState, PostgresCatalog};
 use iceberg_catalog::implementations::Secrets;
 use iceberg_catalog::service::authz::implementations::{
     get_default_authorizer_from_config, Authorizers,
 };
 use iceberg_catalog::service::authz::Authorizer;
+use iceberg_catalog::service::commit_audit::CommitAuditBackend;
 use iceberg_catalog::service::contract_verification::ContractVerifiers;
-use iceberg_catalog::service::event_publisher::{
-    CloudEventBackend, CloudEventsPublisher, CloudEventsPublisherBackgroundTask, Message,
-    NatsBackend, TracingPublisher,
-};
-use iceberg_catalog::service::health::ServiceHealthProvider;
-use iceberg_catalog::service::{Catalog, StartupValidationData};
+use iceberg_catalog::service::event_publisher::{EventPolicy, NatsBackend, TracingPublisher};
+use iceberg_catalog::service::maintenance::HttpMaintenanceExecutor;
+use iceberg_catalog::service::{Catalog, ProjectIdent, StartupValidationData};
 use iceberg_catalog::{SecretBackend, CONFIG};
 use reqwest::Url;
 
-use iceberg_catalog::implementations::postgres::task_queues::{
-    TabularExpirationQueue, TabularPurgeQueue,
-};
+use iceberg_catalog::implementations::postgres::task_queues::task_queues_from_config;
 use iceberg_catalog::service::authn::IdpVerifier;
 use iceberg_catalog::service::authn::K8sVerifier;
 use iceberg_catalog::service::task_queue::TaskQueues;
@@ -45,6 +45,41 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
 
     let catalog_state = CatalogState::from_pools(read_pool.clone(), write_pool.clone());
 
+    // Background tasks get their own pools if configured, so that long-running purges
+    // cannot exhaust the connections needed to serve requests.
+    let task_pools = if CONFIG.pg_task_pool_connections > 0 {
+        Some((
+            iceberg_catalog::implementations::postgres::get_reader_pool(
+                CONFIG
+                    .to_pool_opts()
+                    .max_connections(CONFIG.pg_task_pool_connections),
+            )
+            .await?,
+            iceberg_catalog::implementations::postgres::get_writer_pool(
+                CONFIG
+                    .to_pool_opts()
+                    .max_connections(CONFIG.pg_task_pool_connections),
+            )
+            .await?,
+        ))
+    } else {
+        None
+    };
+    let has_task_pools = task_pools.is_some();
+    let task_read_write = catalog_state.read_write.for_tasks(task_pools);
+    let task_catalog_state = CatalogState {
+        read_write: task_read_write.clone(),
+    };
+
+    let mut monitored_pools = catalog_state.read_write.labeled_pools();
+    if has_task_pools {
+        monitored_pools.extend(task_read_write.labeled_pools());
+    }
+    tokio::task::spawn(pool_metrics_task(
+        monitored_pools,
+        std::time::Duration::from_secs(5),
+    ));
+
     let validation_data = PostgresCatalog::get_server_info(catalog_state.clone()).await?;
     match validation_data {
         StartupValidationData::NotBootstrapped => {
@@ -87,27 +122,16 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
     };
     let authorizer = get_default_authorizer_from_config().await?;
 
-    let health_provider = ServiceHealthProvider::new(
-        vec![
-            ("catalog", Arc::new(catalog_state.clone())),
-            ("secrets", Arc::new(secrets_state.clone())),
-            ("auth", Arc::new(authorizer.clone())),
-        ],
-        CONFIG.health_check_frequency_seconds,
-        CONFIG.health_check_jitter_millis,
-    );
-    health_provider.spawn_health_checks().await;
-
-    let queues = TaskQueues::new(
-        Arc::new(TabularExpirationQueue::from_config(
-            ReadWrite::from_pools(read_pool.clone(), write_pool.clone()),
-            CONFIG.queue_config.clone(),
-        )?),
-        Arc::new(TabularPurgeQueue::from_config(
-            ReadWrite::from_pools(read_pool.clone(), write_pool.clone()),
-            CONFIG.queue_config.clone(),
-        )?),
-    );
+    let queues = task_queues_from_config(&task_read_write, &CONFIG.queue_config)?;
+    let queues = if let Some(url) = CONFIG.maintenance_executor_url.clone() {
+        tracing::info!("Compaction enabled, submitting jobs to {url}");
+        queues.with_maintenance_executor(Arc::new(HttpMaintenanceExecutor::new(
+            url,
+            CONFIG.maintenance_executor_token.clone(),
+        )))
+    } else {
+        queues
+    };
 
     let listener = tokio::net::TcpListener::bind(bind_addr).await?;
 
@@ -116,9 +140,9 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
             serve_inner(
                 a,
                 catalog_state,
+                task_catalog_state,
                 secrets_state,
                 queues,
-                health_provider,
                 listener,
             )
             .await?
@@ -127,9 +151,20 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
             serve_inner(
                 a,
                 catalog_state,
+                task_catalog_state,
+                secrets_state,
+                queues,
+                listener,
+            )
+            .await?
+        }
+        Authorizers::Webhook(a) => {
+            serve_inner(
+                a,
+                catalog_state,
+                task_catalog_state,
                 secrets_state,
                 queues,
-                health_provider,
                 listener,
             )
             .await?
@@ -143,39 +178,74 @@ pub(crate) async fn serve(bind_addr: std::net::SocketAddr) -> Result<(), anyhow:
 async fn serve_inner<A: Authorizer>(
     authorizer: A,
     catalog_state: CatalogState,
+    task_catalog_state: CatalogState,
     secrets_state: Secrets,
     queues: TaskQueues,
-    health_provider: ServiceHealthProvider,
     listener: tokio::net::TcpListener,
 ) -> Result<(), anyhow::Error> {
-    let (tx, rx) = tokio::sync::mpsc::channel(1000);
-
-    let mut cloud_event_sinks = vec![];
+    let write_pool = catalog_state.read_write.write_pool.clone();
+    let mut builder = LakekeeperBuilder::<PostgresCatalog, _, _>::new(
+        authorizer,
+        catalog_state.clone(),
+        secrets_state.clone(),
+        queues,
+    )
+    .with_task_catalog_state(task_catalog_state)
+    .with_leader_election(Arc::new(move |duty: &'static str, task: Duty| {
+        run_as_leader(
+            write_pool.clone(),
+            duty,
+            std::time::Duration::from_secs(30),
+            task,
+        )
+        .boxed()
+    }));
 
     if let Some(nat_addr) = &CONFIG.nats_address {
         let nats_publisher = build_nats_client(nat_addr).await?;
-        cloud_event_sinks
-            .push(Arc::new(nats_publisher) as Arc<dyn CloudEventBackend + Sync + Send>);
+        builder = builder.with_event_backend(Arc::new(nats_publisher));
     } else {
         tracing::info!("Running without NATS publisher.");
     };
 
     if let Some(true) = &CONFIG.log_cloudevents {
-        let tracing_publisher = TracingPublisher;
-        cloud_event_sinks
-            .push(Arc::new(tracing_publisher) as Arc<dyn CloudEventBackend + Sync + Send>);
+        builder = builder.with_event_backend(Arc::new(TracingPublisher {
+            policy: EventPolicy::from_config(),
+        }));
         tracing::info!("Logging Cloudevents.");
     } else {
         tracing::info!("Running without logging Cloudevents.");
     }
 
-    let x: CloudEventsPublisherBackgroundTask = CloudEventsPublisherBackgroundTask {
-        source: rx,
-        sinks: cloud_event_sinks,
+    match (
+        &CONFIG.commit_audit_warehouse_id,
+        &CONFIG.commit_audit_table,
+    ) {
+        (Some(warehouse_id), Some(table)) => {
+            let commit_audit = CommitAuditBackend::<PostgresCatalog, _>::try_new(
+                (*warehouse_id).into(),
+                table,
+                catalog_state,
+                secrets_state,
+            )?;
+            builder = builder.with_commit_audit(
+                Arc::new(commit_audit),
+                std::time::Duration::from_secs(CONFIG.commit_audit_flush_interval_seconds),
+            );
+            tracing::info!("Appending commit facts to audit table {table}.");
+        }
+        (None, None) => {
+            tracing::info!("Running without commit audit.");
+        }
+        _ => {
+            return Err(anyhow!(
+                "Both `LAKEKEEPER__COMMIT_AUDIT_WAREHOUSE_ID` and `LAKEKEEPER__COMMIT_AUDIT_TABLE` must be set to enable the commit audit."
+            ));
+        }
     };
 
-    let k8s_token_verifier = if CONFIG.enable_kubernetes_authentication {
-        Some(
+    if CONFIG.enable_kubernetes_authentication {
+        builder = builder.with_k8s_token_verifier(
             K8sVerifier::try_new()
                 .await
                 .map_err(|e| {
@@ -186,40 +256,64 @@ async fn serve_inner<A: Authorizer>(
                     tracing::info!("K8s authorizer created {:?}", v);
                     v
                 })?,
-        )
-    } else {
-        None
-    };
-    if k8s_token_verifier.is_none() && CONFIG.openid_provider_uri.is_none() {
+        );
+    } else if !CONFIG.authn_enabled() {
         tracing::warn!("Authentication is disabled. This is not suitable for production!");
     }
+    for (project_id, provider) in &CONFIG.openid_project_providers {
+        let project_id = ProjectIdent::from(*project_id);
+        builder = builder.with_project_token_verifier(
+            project_id,
+            IdpVerifier::for_project(project_id, provider).await?,
+        );
+        tracing::info!("OpenID provider for project {project_id} created");
+    }
+    if let Some(uri) = CONFIG.openid_provider_uri.clone() {
+        builder = builder.with_token_verifier(
+            IdpVerifier::new(
+                uri,
+                CONFIG.openid_audience.clone(),
+                CONFIG.openid_additional_issuers.clone(),
+                CONFIG.openid_scope.clone(),
+            )
+            .await?,
+        );
+    }
+    if let Some(cors_origins) = CONFIG.allow_origin.as_deref() {
+        builder = builder.with_cors_origins(cors_origins);
+    }
+
     let (layer, metrics_future) =
         iceberg_catalog::metrics::get_axum_layer_and_install_recorder(CONFIG.metrics_port)?;
-    let router = new_full_router::<PostgresCatalog, _, Secrets>(RouterArgs {
-        authorizer: authorizer.clone(),
-        catalog_state: catalog_state.clone(),
-        secrets_state: secrets_state.clone(),
-        queues: queues.clone(),
-        publisher: CloudEventsPublisher::new(tx.clone()),
-        table_change_checkers: ContractVerifiers::new(vec![]),
-        token_verifier: if let Some(uri) = CONFIG.openid_provider_uri.clone() {
-            Some(
-                IdpVerifier::new(
-                    uri,
-                    CONFIG.openid_audience.clone(),
-                    CONFIG.openid_additional_issuers.clone(),
-                    CONFIG.openid_scope.clone(),
-                )
-                .await?,
-            )
-        } else {
-            None
-        },
-        k8s_token_verifier,
-        service_health_provider: health_provider,
-        cors_origins: CONFIG.allow_origin.as_deref(),
-        metrics_layer: Some(layer),
-    })?;
+    builder = builder.with_metrics_layer(layer);
+
+    let mut contract_verifiers = ContractVerifiers::new(vec![]);
+    #[cfg(feature = "wasm-hooks")]
+    if let Some(dir) = CONFIG.wasm_hook_dir.clone() {
+        tracing::info!("Resolving WASM hooks of warehouses from {}", dir.display());
+        contract_verifiers = contract_verifiers.with_warehouse_verifiers(Arc::new(
+            iceberg_catalog::service::wasm_hooks::WasmHookRegistry::new(
+                dir,
+                iceberg_catalog::service::wasm_hooks::WasmHookLimits {
+                    fuel: CONFIG.wasm_hook_fuel,
+                    memory_bytes: CONFIG.wasm_hook_memory_limit_bytes,
+                },
+            ),
+        ));
+    }
+    #[cfg(not(feature = "wasm-hooks"))]
+    if CONFIG.wasm_hook_dir.is_some() {
+        tracing::warn!("LAKEKEEPER__WASM_HOOK_DIR is set, but this build does not include the `wasm-hooks` feature. WASM hooks are disabled.");
+    }
+    if CONFIG.contract_verification_timeout_ms > 0 {
+        contract_verifiers = contract_verifiers.with_timeout(std::time::Duration::from_millis(
+            CONFIG.contract_verification_timeout_ms,
+        ));
+    }
+    builder = builder.with_contract_verifiers(contract_verifiers);
+
+    let lakekeeper = builder.build()?;
+    let router = lakekeeper.router;
 
     #[cfg(feature = "ui")]
     let router = router
@@ -239,24 +333,24 @@ async fn serve_inner<A: Authorizer>(
         .route("/ui/assets/{*file}", get(ui::static_handler))
         .route("/ui/{*file}", get(ui::index_handler));
 
-    let publisher_handle = tokio::task::spawn(async move {
-        match x.publish().await {
-            Ok(_) => tracing::info!("Exiting publisher task"),
-            Err(e) => tracing::error!("Publisher task failed: {e}"),
-        };
+    // Background tasks publish the remaining events and flush the commit audit on shutdown.
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let background_tasks = lakekeeper.background_tasks.run(async move {
+        let _ = shutdown_rx.await;
     });
+    tokio::pin!(background_tasks);
 
     tokio::select!(
-        _ = queues.spawn_queues::<PostgresCatalog, _, _>(catalog_state, secrets_state, authorizer) => tracing::error!("Tabular queue task failed"),
         err = service_serve(listener, router) => tracing::error!("Service failed: {err:?}"),
         _ = metrics_future => tracing::error!("Metrics server failed"),
+        result = &mut background_tasks => {
+            tracing::error!("Background tasks stopped");
+            return result;
+        },
     );
 
-    tracing::debug!("Sending shutdown signal to event publisher.");
-    tx.send(Message::Shutdown).await?;
-    publisher_handle.await?;
-
-    Ok(())
+    let _ = shutdown_tx.send(());
+    background_tasks.await
 }
 
 async fn build_nats_client(nat_addr: &Url) -> Result<NatsBackend, Error> {
@@ -0,0 +1,97 @@
+use iceberg_catalog::implementations::postgres::synthetic::{
+    bench_list_tabulars, bench_load_tables, generate_synthetic_warehouse, SyntheticWarehouseSpec,
+};
+use iceberg_catalog::implementations::postgres::{get_reader_pool, get_writer_pool, CatalogState};
+use iceberg_catalog::{ProjectIdent, WarehouseIdent, CONFIG};
+use std::str::FromStr;
+
+async fn catalog_state() -> anyhow::Result<CatalogState> {
+    let read_pool = get_reader_pool(CONFIG.to_pool_opts()).await?;
+    let write_pool = get_writer_pool(CONFIG.to_pool_opts()).await?;
+    Ok(CatalogState::from_pools(read_pool, write_pool))
+}
+
+pub(crate) async fn generate(
+    project_id: &str,
+    warehouse_name: String,
+    namespaces: usize,
+    tables_per_namespace: usize,
+    snapshots_per_table: usize,
+    columns_per_table: usize,
+) -> anyhow::Result<()> {
+    let spec = SyntheticWarehouseSpec {
+        project_id: ProjectIdent::from_str(project_id).map_err(|e| e.error)?,
+        warehouse_name,
+        namespaces,
+        tables_per_namespace,
+        snapshots_per_table,
+        columns_per_table,
+    };
+    let start = std::time::Instant::now();
+    let warehouse_id = generate_synthetic_warehouse(catalog_state().await?, &spec)
+        .await
+        .map_err(|e| e.error)?;
+    println!(
+        "Generated warehouse {warehouse_id} with {} tables in {:?}",
+        namespaces * tables_per_namespace,
+        start.elapsed()
+    );
+    Ok(())
+}
+
+pub(crate) async fn bench(
+    warehouse_id: &str,
+    batch_sizes: &[usize],
+    iterations: usize,
+) -> anyhow::Result<()> {
+    let warehouse_id = WarehouseIdent::from_str(warehouse_id).map_err(|e| e.error)?;
+    let results = bench_load_tables(
+        catalog_state().await?,
+        warehouse_id,
+        batch_sizes,
+        iterations,
+    )
+    .await
+    .map_err(|e| e.error)?;
+
+    println!(
+        "{:>10} {:>8} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "batch", "calls", "mean", "p50", "p95", "max", "per table"
+    );
+    for r in results {
+        println!(
+            "{:>10} {:>8} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}",
+            r.batch_size,
+            r.calls,
+            r.mean,
+            r.p50,
+            r.p95,
+            r.max,
+            r.per_table()
+        );
+    }
+    Ok(())
+}
+
+pub(crate) async fn bench_list(
+    warehouse_id: &str,
+    page_sizes: &[usize],
+    iterations: usize,
+) -> anyhow::Result<()> {
+    let warehouse_id = WarehouseIdent::from_str(warehouse_id).map_err(|e| e.error)?;
+    let results = bench_list_tabulars(catalog_state().await?, warehouse_id, page_sizes, iterations)
+        .await
+        .map_err(|e| e.error)?;
+
+    println!(
+        "{:>10} {:>8} {:>8} {:>10} {:>12} {:>12} {:>12} {:>12}",
+        "page size", "deleted", "pages", "tabulars", "mean", "p50", "p95", "max"
+    );
+    for r in results {
+        println!(
+            "{:>10} {:>8} {:>8} {:>10} {:>12.2?} {:>12.2?} {:>12.2?} {:>12.2?}",
+            r.page_size, r.include_deleted, r.pages, r.tabulars, r.mean, r.p50, r.p95, r.max
+        );
+    }
+    Ok(())
+}
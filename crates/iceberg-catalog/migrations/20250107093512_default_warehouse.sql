@@ -0,0 +1,25 @@
+create table user_default_warehouse
+(
+    user_id      text primary key,
+    warehouse_id uuid not null,
+    CONSTRAINT user_default_warehouse_user_id_fkey FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE,
+    CONSTRAINT user_default_warehouse_warehouse_id_fkey FOREIGN KEY (warehouse_id) REFERENCES warehouse (warehouse_id) ON DELETE CASCADE
+);
+
+call add_time_columns('user_default_warehouse');
+select trigger_updated_at('user_default_warehouse');
+
+create index user_default_warehouse_warehouse_id_idx on user_default_warehouse (warehouse_id);
+
+create table role_default_warehouse
+(
+    role_id      uuid primary key,
+    warehouse_id uuid not null,
+    CONSTRAINT role_default_warehouse_role_id_fkey FOREIGN KEY (role_id) REFERENCES role (id) ON DELETE CASCADE,
+    CONSTRAINT role_default_warehouse_warehouse_id_fkey FOREIGN KEY (warehouse_id) REFERENCES warehouse (warehouse_id) ON DELETE CASCADE
+);
+
+call add_time_columns('role_default_warehouse');
+select trigger_updated_at('role_default_warehouse');
+
+create index role_default_warehouse_warehouse_id_idx on role_default_warehouse (warehouse_id);
@@ -0,0 +1,15 @@
+create type storage_health_status as enum ('healthy', 'degraded-storage');
+
+create table warehouse_storage_health
+(
+    warehouse_id   uuid primary key,
+    storage_health storage_health_status not null default 'healthy',
+    checked_at     timestamptz           not null,
+    failed_checks  jsonb,
+    CONSTRAINT warehouse_storage_health_warehouse_id_fkey FOREIGN KEY (warehouse_id) REFERENCES warehouse (warehouse_id) ON DELETE CASCADE
+);
+
+call add_time_columns('warehouse_storage_health');
+select trigger_updated_at('warehouse_storage_health');
+
+create index warehouse_storage_health_checked_at_idx on warehouse_storage_health (checked_at);
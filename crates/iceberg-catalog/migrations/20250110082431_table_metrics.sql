@@ -0,0 +1,17 @@
+create table table_metrics
+(
+    table_id                   uuid primary key,
+    scan_count                 bigint not null default 0,
+    scanned_data_files         bigint not null default 0,
+    scanned_bytes              bigint not null default 0,
+    total_planning_duration_ms bigint not null default 0,
+    commit_count               bigint not null default 0,
+    committed_data_files       bigint not null default 0,
+    committed_bytes            bigint not null default 0,
+    last_scan_at               timestamptz,
+    last_commit_at             timestamptz,
+    CONSTRAINT table_metrics_table_id_fkey FOREIGN KEY (table_id) REFERENCES "table" (table_id) ON DELETE CASCADE
+);
+
+call add_time_columns('table_metrics');
+select trigger_updated_at('table_metrics');
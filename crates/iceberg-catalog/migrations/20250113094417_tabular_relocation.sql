@@ -0,0 +1,11 @@
+create table tabular_relocations
+(
+    tabular_id      uuid not null,
+    warehouse_id    uuid not null,
+    source_location text not null,
+    target_location text not null,
+    task_id         uuid primary key references task (task_id)
+);
+
+call add_time_columns('tabular_relocations');
+select trigger_updated_at('"tabular_relocations"');
@@ -0,0 +1,13 @@
+create table tabular_compactions
+(
+    tabular_id   uuid   not null,
+    warehouse_id uuid   not null,
+    snapshot_id  bigint not null,
+    reason       text   not null,
+    task_id      uuid primary key references task (task_id)
+);
+
+create index tabular_compactions_tabular_id_idx on tabular_compactions (tabular_id);
+
+call add_time_columns('tabular_compactions');
+select trigger_updated_at('"tabular_compactions"');
@@ -0,0 +1,4 @@
+alter table tabular_purges
+    add column object_count           bigint,
+    add column approval_requested_at  timestamptz,
+    add column approved_at            timestamptz;
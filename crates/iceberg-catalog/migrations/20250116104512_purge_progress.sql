@@ -0,0 +1,4 @@
+alter table tabular_purges
+    add column deleted_count        bigint not null default 0,
+    add column checkpoint           text,
+    add column progress_updated_at  timestamptz;
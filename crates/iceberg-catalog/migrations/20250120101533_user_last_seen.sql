@@ -0,0 +1,15 @@
+alter table users
+    add column last_seen_at timestamptz;
+
+-- Recording that a user was seen is not an update of the user.
+drop trigger set_updated_at on users;
+create trigger set_updated_at
+    before update
+    on users
+    for each row
+    when (OLD is distinct from NEW and OLD.last_seen_at is not distinct from NEW.last_seen_at)
+execute function set_updated_at();
+
+create index users_created_at_id_idx on users (created_at, id) where deleted_at is null;
+create index users_last_seen_at_idx on users (last_seen_at) where deleted_at is null;
+create index role_created_at_id_idx on role (created_at, id);
@@ -0,0 +1,3 @@
+alter table users
+    add column flagged_stale_at timestamptz,
+    add column deactivated_at   timestamptz;
@@ -0,0 +1,7 @@
+create table tabular_access
+(
+    tabular_id     uuid primary key,
+    last_read_at   timestamptz,
+    last_commit_at timestamptz,
+    CONSTRAINT tabular_access_tabular_id_fkey FOREIGN KEY (tabular_id) REFERENCES tabular (tabular_id) ON DELETE CASCADE
+);
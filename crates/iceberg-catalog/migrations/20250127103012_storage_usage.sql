@@ -0,0 +1,38 @@
+-- Daily rollup of the storage used by each table and by the tables of each namespace.
+-- Not bound to the tables or namespaces, so that usage of dropped ones can still be attributed.
+create table table_storage_usage
+(
+    warehouse_id   uuid        not null,
+    namespace_id   uuid        not null,
+    namespace_name text[]      not null,
+    table_id       uuid        not null,
+    table_name     text        not null,
+    day            date        not null,
+    -- Null if the snapshot summary does not report a size.
+    total_bytes    bigint,
+    PRIMARY KEY (table_id, day),
+    CONSTRAINT table_storage_usage_warehouse_id_fkey FOREIGN KEY (warehouse_id) REFERENCES warehouse (warehouse_id) ON DELETE CASCADE
+);
+
+create index table_storage_usage_warehouse_id_day_idx on table_storage_usage (warehouse_id, day, namespace_id);
+
+call add_time_columns('table_storage_usage');
+select trigger_updated_at('table_storage_usage');
+
+create table namespace_storage_usage
+(
+    warehouse_id        uuid        not null,
+    namespace_id        uuid        not null,
+    namespace_name      text[]      not null,
+    day                 date        not null,
+    number_of_tables    bigint      not null,
+    total_bytes         bigint      not null,
+    tables_without_size bigint      not null,
+    PRIMARY KEY (namespace_id, day),
+    CONSTRAINT namespace_storage_usage_warehouse_id_fkey FOREIGN KEY (warehouse_id) REFERENCES warehouse (warehouse_id) ON DELETE CASCADE
+);
+
+create index namespace_storage_usage_warehouse_id_day_idx on namespace_storage_usage (warehouse_id, day);
+
+call add_time_columns('namespace_storage_usage');
+select trigger_updated_at('namespace_storage_usage');
@@ -0,0 +1,7 @@
+alter table namespace
+    add column description text;
+alter table tabular
+    add column description text;
+
+CREATE INDEX namespace_description_gist_idx ON namespace USING gist (description gist_trgm_ops(siglen=256)) WHERE description IS NOT NULL;
+CREATE INDEX tabular_description_gist_idx ON tabular USING gist (description gist_trgm_ops(siglen=256)) WHERE description IS NOT NULL;
@@ -0,0 +1,29 @@
+create table domain
+(
+    domain_id   uuid primary key,
+    project_id  uuid not null,
+    name        text not null,
+    description text,
+    tags        text[] not null default '{}',
+    CONSTRAINT domain_project_id_fkey FOREIGN KEY (project_id) REFERENCES project (project_id),
+    CONSTRAINT domain_name_not_empty CHECK (length(name) > 0)
+);
+
+call add_time_columns('domain');
+select trigger_updated_at('domain');
+
+create unique index unique_domain_name_in_project on domain (project_id, (lower(name)));
+create index domain_tags_idx on domain using gin (tags);
+
+create table domain_namespace
+(
+    namespace_id uuid primary key,
+    domain_id    uuid not null,
+    CONSTRAINT domain_namespace_namespace_id_fkey FOREIGN KEY (namespace_id) REFERENCES namespace (namespace_id) ON DELETE CASCADE,
+    CONSTRAINT domain_namespace_domain_id_fkey FOREIGN KEY (domain_id) REFERENCES domain (domain_id) ON DELETE CASCADE
+);
+
+call add_time_columns('domain_namespace');
+select trigger_updated_at('domain_namespace');
+
+create index domain_namespace_domain_id_idx on domain_namespace (domain_id);
@@ -0,0 +1,16 @@
+create type notice_severity as enum ('info', 'warning', 'critical');
+
+create table notice
+(
+    notice_id uuid primary key,
+    message   text            not null,
+    severity  notice_severity not null,
+    starts_at timestamptz,
+    ends_at   timestamptz,
+    CONSTRAINT notice_ends_after_start CHECK (starts_at IS NULL OR ends_at IS NULL OR ends_at > starts_at)
+);
+
+call add_time_columns('notice');
+select trigger_updated_at('notice');
+
+create index notice_ends_at_idx on notice (ends_at);
@@ -0,0 +1,2 @@
+alter table warehouse
+    add column namespace_defaults jsonb not null default '{}'::jsonb;
@@ -0,0 +1,5 @@
+alter table warehouse
+    add column lifecycle jsonb not null default '{"type": "permanent"}'::jsonb;
+
+create index warehouse_ephemeral_idx on warehouse ((lifecycle ->> 'type'))
+    where lifecycle ->> 'type' = 'ephemeral';
@@ -0,0 +1,2 @@
+alter table warehouse
+    add column allowed_client_networks jsonb not null default '[]'::jsonb;
@@ -0,0 +1,2 @@
+alter table warehouse
+    add column table_limits jsonb not null default '{}'::jsonb;
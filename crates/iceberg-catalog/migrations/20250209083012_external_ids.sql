@@ -0,0 +1,12 @@
+alter table namespace
+    add column external_id text;
+
+alter table tabular
+    add column external_id text;
+
+-- External ids identify a single entity in an asset registry. Soft-deleted tabulars
+-- release their external id, so that the asset can be re-created.
+create unique index namespace_external_id_idx on namespace (external_id)
+    where external_id is not null;
+create unique index tabular_external_id_idx on tabular (external_id)
+    where external_id is not null and deleted_at is null;
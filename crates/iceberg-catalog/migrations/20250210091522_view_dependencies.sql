@@ -0,0 +1,10 @@
+create table view_table_dependency
+(
+    view_id  uuid not null,
+    table_id uuid not null,
+    CONSTRAINT view_table_dependency_pkey PRIMARY KEY (view_id, table_id),
+    CONSTRAINT view_table_dependency_view_id_fkey FOREIGN KEY (view_id) REFERENCES tabular (tabular_id) ON DELETE CASCADE,
+    CONSTRAINT view_table_dependency_table_id_fkey FOREIGN KEY (table_id) REFERENCES tabular (tabular_id) ON DELETE CASCADE
+);
+
+create index view_table_dependency_table_id_idx on view_table_dependency (table_id);
@@ -0,0 +1,2 @@
+alter table warehouse
+    add column snapshot_summary_enrichment jsonb not null default '{}'::jsonb;
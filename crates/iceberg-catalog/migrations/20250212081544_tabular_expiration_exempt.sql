@@ -0,0 +1,2 @@
+alter table tabular
+    add column expiration_exempt boolean not null default false;
@@ -0,0 +1,21 @@
+create table namespace_property_history
+(
+    history_id   bigint generated always as identity primary key,
+    namespace_id uuid        not null,
+    properties   jsonb       not null,
+    replaced_at  timestamptz not null default now(),
+    CONSTRAINT namespace_property_history_namespace_id_fkey FOREIGN KEY (namespace_id) REFERENCES namespace (namespace_id) ON DELETE CASCADE
+);
+
+create index namespace_property_history_namespace_id_idx on namespace_property_history (namespace_id, history_id);
+
+create table table_property_history
+(
+    history_id  bigint generated always as identity primary key,
+    table_id    uuid        not null,
+    properties  jsonb       not null,
+    replaced_at timestamptz not null default now(),
+    CONSTRAINT table_property_history_table_id_fkey FOREIGN KEY (table_id) REFERENCES "table" (table_id) ON DELETE CASCADE
+);
+
+create index table_property_history_table_id_idx on table_property_history (table_id, history_id);
@@ -0,0 +1,125 @@
+-- Counts of each warehouse, maintained by triggers in the transaction that changes them,
+-- so that warehouse overviews do not need to count all namespaces and tabulars.
+create table warehouse_aggregates
+(
+    warehouse_id         uuid primary key,
+    number_of_namespaces bigint not null default 0,
+    number_of_tables     bigint not null default 0,
+    number_of_views      bigint not null default 0,
+    last_activity_at     timestamptz,
+    CONSTRAINT warehouse_aggregates_warehouse_id_fkey FOREIGN KEY (warehouse_id) REFERENCES warehouse (warehouse_id) ON DELETE CASCADE
+);
+
+-- Staged and soft-deleted tabulars are not counted.
+create function counted_tabular_type(typ tabular_type, metadata_location text, deleted_at timestamptz)
+    returns tabular_type
+    language sql
+    immutable as
+$$
+select case when metadata_location is not null and deleted_at is null then typ end
+$$;
+
+create function update_warehouse_aggregates_of_warehouse() returns trigger as
+$$
+begin
+    insert into warehouse_aggregates (warehouse_id) values (NEW.warehouse_id);
+    return null;
+end;
+$$ language plpgsql;
+
+create trigger update_warehouse_aggregates
+    after insert
+    on warehouse
+    for each row
+execute function update_warehouse_aggregates_of_warehouse();
+
+create function update_warehouse_aggregates_of_namespace() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        update warehouse_aggregates
+        set number_of_namespaces = number_of_namespaces + 1,
+            last_activity_at     = now()
+        where warehouse_id = NEW.warehouse_id;
+    else
+        update warehouse_aggregates
+        set number_of_namespaces = number_of_namespaces - 1,
+            last_activity_at     = now()
+        where warehouse_id = OLD.warehouse_id;
+    end if;
+    return null;
+end;
+$$ language plpgsql;
+
+create trigger update_warehouse_aggregates
+    after insert or delete
+    on namespace
+    for each row
+execute function update_warehouse_aggregates_of_namespace();
+
+create function update_warehouse_aggregates_of_tabular() returns trigger as
+$$
+declare
+    old_type       tabular_type;
+    new_type       tabular_type;
+    v_namespace_id uuid;
+begin
+    if TG_OP = 'INSERT' then
+        new_type := counted_tabular_type(NEW.typ, NEW.metadata_location, NEW.deleted_at);
+        v_namespace_id := NEW.namespace_id;
+    elsif TG_OP = 'DELETE' then
+        old_type := counted_tabular_type(OLD.typ, OLD.metadata_location, OLD.deleted_at);
+        v_namespace_id := OLD.namespace_id;
+    else
+        old_type := counted_tabular_type(OLD.typ, OLD.metadata_location, OLD.deleted_at);
+        new_type := counted_tabular_type(NEW.typ, NEW.metadata_location, NEW.deleted_at);
+        v_namespace_id := NEW.namespace_id;
+    end if;
+
+    if old_type is distinct from new_type then
+        update warehouse_aggregates a
+        set number_of_tables = number_of_tables
+                + (case when new_type = 'table' then 1 else 0 end)
+                - (case when old_type = 'table' then 1 else 0 end),
+            number_of_views  = number_of_views
+                + (case when new_type = 'view' then 1 else 0 end)
+                - (case when old_type = 'view' then 1 else 0 end),
+            last_activity_at = now()
+        from namespace n
+        where n.namespace_id = v_namespace_id
+          and a.warehouse_id = n.warehouse_id;
+    else
+        -- Commits only refresh the activity. Updating it at most once a minute keeps
+        -- concurrent commits to a warehouse from queueing for the lock of its row.
+        update warehouse_aggregates a
+        set last_activity_at = now()
+        from namespace n
+        where n.namespace_id = v_namespace_id
+          and a.warehouse_id = n.warehouse_id
+          and (a.last_activity_at is null or a.last_activity_at < now() - interval '1 minute');
+    end if;
+    return null;
+end;
+$$ language plpgsql;
+
+create trigger update_warehouse_aggregates
+    after insert or update or delete
+    on tabular
+    for each row
+execute function update_warehouse_aggregates_of_tabular();
+
+insert into warehouse_aggregates (warehouse_id, number_of_namespaces, number_of_tables, number_of_views, last_activity_at)
+select w.warehouse_id,
+       (select count(*) from namespace n where n.warehouse_id = w.warehouse_id),
+       (select count(*)
+        from tabular t
+                 join namespace n on t.namespace_id = n.namespace_id
+        where n.warehouse_id = w.warehouse_id
+          and counted_tabular_type(t.typ, t.metadata_location, t.deleted_at) = 'table'),
+       (select count(*)
+        from tabular t
+                 join namespace n on t.namespace_id = n.namespace_id
+        where n.warehouse_id = w.warehouse_id
+          and counted_tabular_type(t.typ, t.metadata_location, t.deleted_at) = 'view'),
+       null
+from warehouse w;
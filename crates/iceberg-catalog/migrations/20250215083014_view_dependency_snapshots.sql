@@ -0,0 +1,6 @@
+-- Snapshot of the main branch of each table when the dependencies of the view were last
+-- recorded, i.e. on the last commit of the view. Null if the table had no snapshot then.
+-- `recorded_at` is null for dependencies recorded before snapshots were tracked.
+alter table view_table_dependency
+    add column snapshot_id bigint,
+    add column recorded_at timestamptz;
@@ -0,0 +1,17 @@
+create table table_inventory_exports
+(
+    export_id       uuid primary key,
+    task_id         uuid not null unique references task (task_id),
+    warehouse_id    uuid not null,
+    table_id        uuid not null,
+    target_location text not null,
+    format          text not null check (format in ('csv', 'parquet')),
+    -- Set once the export finished
+    data_file_count bigint,
+    inventory_files text[]
+);
+
+create index table_inventory_exports_warehouse_id_table_id_idx on table_inventory_exports (warehouse_id, table_id);
+
+call add_time_columns('table_inventory_exports');
+select trigger_updated_at('"table_inventory_exports"');
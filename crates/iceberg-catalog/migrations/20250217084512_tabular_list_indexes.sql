@@ -0,0 +1,9 @@
+-- Serves `list_tabulars`: rows of a namespace in keyset order, including all columns
+-- the listing filters on, so that pages can be read with an index-only scan.
+create index tabular_namespace_id_created_at_idx on tabular (namespace_id, created_at, tabular_id)
+    include (name, typ, deleted_at, metadata_location, table_migrated, expiration_exempt);
+-- Superseded by the index above and `tabular_namespace_id_name_idx`.
+drop index if exists tabular_namespace_id_idx;
+
+create index namespace_warehouse_id_namespace_id_idx on namespace (warehouse_id, namespace_id)
+    include (namespace_name);
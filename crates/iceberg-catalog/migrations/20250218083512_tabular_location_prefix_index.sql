@@ -0,0 +1,3 @@
+-- Serves the prefix search of the location usage diagnostics (`location LIKE 'prefix/%'`).
+-- `text_pattern_ops` is required for LIKE to use the index with collations other than C.
+create index tabular_location_pattern_idx on tabular (location text_pattern_ops);
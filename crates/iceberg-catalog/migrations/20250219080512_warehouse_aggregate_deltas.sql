@@ -0,0 +1,106 @@
+-- Updating the single row of a warehouse in `warehouse_aggregates` serialized all
+-- transactions that change the warehouse on its row lock. Triggers now write one delta
+-- row per warehouse and transaction instead. Reads sum the deltas with the totals,
+-- housekeeping folds committed deltas into the totals.
+-- Deltas do not reference the warehouse: they may be written while it is deleted and
+-- are dropped by the next fold.
+create table warehouse_aggregate_deltas
+(
+    warehouse_id         uuid        not null,
+    transaction_id       bigint      not null default txid_current(),
+    number_of_namespaces bigint      not null default 0,
+    number_of_tables     bigint      not null default 0,
+    number_of_views      bigint      not null default 0,
+    last_activity_at     timestamptz not null default now(),
+    primary key (warehouse_id, transaction_id)
+);
+
+-- Only the transaction itself writes its row, so the upsert never waits for other
+-- transactions.
+create function record_warehouse_aggregate_delta(p_warehouse_id uuid, p_namespaces bigint,
+                                                 p_tables bigint, p_views bigint) returns void
+    language sql as
+$$
+insert into warehouse_aggregate_deltas (warehouse_id, number_of_namespaces, number_of_tables, number_of_views)
+values (p_warehouse_id, p_namespaces, p_tables, p_views)
+on conflict (warehouse_id, transaction_id) do update
+    set number_of_namespaces = warehouse_aggregate_deltas.number_of_namespaces + excluded.number_of_namespaces,
+        number_of_tables     = warehouse_aggregate_deltas.number_of_tables + excluded.number_of_tables,
+        number_of_views      = warehouse_aggregate_deltas.number_of_views + excluded.number_of_views,
+        last_activity_at     = excluded.last_activity_at
+$$;
+
+create or replace function update_warehouse_aggregates_of_namespace() returns trigger as
+$$
+begin
+    if TG_OP = 'INSERT' then
+        perform record_warehouse_aggregate_delta(NEW.warehouse_id, 1, 0, 0);
+    else
+        perform record_warehouse_aggregate_delta(OLD.warehouse_id, -1, 0, 0);
+    end if;
+    return null;
+end;
+$$ language plpgsql;
+
+create or replace function update_warehouse_aggregates_of_tabular() returns trigger as
+$$
+declare
+    old_type       tabular_type;
+    new_type       tabular_type;
+    v_namespace_id uuid;
+    v_warehouse_id uuid;
+begin
+    if TG_OP = 'INSERT' then
+        new_type := counted_tabular_type(NEW.typ, NEW.metadata_location, NEW.deleted_at);
+        v_namespace_id := NEW.namespace_id;
+    elsif TG_OP = 'DELETE' then
+        old_type := counted_tabular_type(OLD.typ, OLD.metadata_location, OLD.deleted_at);
+        v_namespace_id := OLD.namespace_id;
+    else
+        old_type := counted_tabular_type(OLD.typ, OLD.metadata_location, OLD.deleted_at);
+        new_type := counted_tabular_type(NEW.typ, NEW.metadata_location, NEW.deleted_at);
+        v_namespace_id := NEW.namespace_id;
+    end if;
+
+    select n.warehouse_id into v_warehouse_id from namespace n where n.namespace_id = v_namespace_id;
+    if v_warehouse_id is null then
+        return null;
+    end if;
+
+    -- Commits that do not change the counts only refresh the activity.
+    perform record_warehouse_aggregate_delta(
+            v_warehouse_id,
+            0,
+            (case when new_type = 'table' then 1 else 0 end)
+                - (case when old_type = 'table' then 1 else 0 end),
+            (case when new_type = 'view' then 1 else 0 end)
+                - (case when old_type = 'view' then 1 else 0 end)
+            );
+    return null;
+end;
+$$ language plpgsql;
+
+-- Totals of a warehouse including the deltas that were not folded yet.
+create function warehouse_summary(p_warehouse_id uuid)
+    returns table
+            (
+                number_of_namespaces bigint,
+                number_of_tables     bigint,
+                number_of_views      bigint,
+                last_activity_at     timestamptz
+            )
+    language sql
+    stable as
+$$
+select sum(s.number_of_namespaces)::bigint,
+       sum(s.number_of_tables)::bigint,
+       sum(s.number_of_views)::bigint,
+       max(s.last_activity_at)
+from (select a.number_of_namespaces, a.number_of_tables, a.number_of_views, a.last_activity_at
+      from warehouse_aggregates a
+      where a.warehouse_id = p_warehouse_id
+      union all
+      select d.number_of_namespaces, d.number_of_tables, d.number_of_views, d.last_activity_at
+      from warehouse_aggregate_deltas d
+      where d.warehouse_id = p_warehouse_id) s
+$$;
@@ -0,0 +1,2 @@
+alter table warehouse
+    add column wasm_hooks text[] not null default '{}';
@@ -17,7 +17,9 @@ pub mod v1 {
 
     pub use iceberg_ext::catalog::{NamespaceIdent, TableIdent};
 
-    pub use self::namespace::{ListNamespacesQuery, NamespaceParameters, PaginationQuery};
+    pub use self::namespace::{
+        ListNamespacesQuery, NamespaceParameters, PaginationQuery, ReturnTotal,
+    };
     pub use self::tables::{DataAccess, ListTablesQuery, TableParameters};
     pub use self::views::ViewParameters;
     pub use crate::api::iceberg::types::*;
@@ -206,6 +208,7 @@ pub(crate) fn supported_endpoints() -> Vec<String> {
         "POST /v1/{prefix}/namespaces".into(),
         "GET /v1/{prefix}/namespaces/{namespace}".into(),
         "DELETE /v1/{prefix}/namespaces/{namespace}".into(),
+        "HEAD /v1/{prefix}/namespaces/{namespace}".into(),
         "POST /v1/{prefix}/namespaces/{namespace}/properties".into(),
         "GET /v1/{prefix}/namespaces/{namespace}/tables".into(),
         "POST /v1/{prefix}/namespaces/{namespace}/tables".into(),
@@ -217,7 +220,6 @@ pub(crate) fn supported_endpoints() -> Vec<String> {
         "POST /v1/{prefix}/tables/rename".into(),
         "POST /v1/{prefix}/namespaces/{namespace}/register".into(),
         "POST /v1/{prefix}/namespaces/{namespace}/tables/{table}/metrics".into(),
-        "POST /v1/{prefix}/tables/rename".into(),
         "POST /v1/{prefix}/transactions/commit".into(),
         "GET /v1/{prefix}/namespaces/{namespace}/views".into(),
         "POST /v1/{prefix}/namespaces/{namespace}/views".into(),
@@ -234,6 +236,20 @@ mod test {
     use crate::api::iceberg::v1::PaginatedMapping;
     use uuid::Uuid;
 
+    #[test]
+    fn supported_endpoints_include_existence_checks() {
+        let endpoints = super::supported_endpoints();
+        let unique = endpoints.iter().collect::<std::collections::HashSet<_>>();
+        assert_eq!(unique.len(), endpoints.len());
+        for endpoint in [
+            "HEAD /v1/{prefix}/namespaces/{namespace}",
+            "HEAD /v1/{prefix}/namespaces/{namespace}/tables/{table}",
+            "HEAD /v1/{prefix}/namespaces/{namespace}/views/{view}",
+        ] {
+            assert!(endpoints.contains(&endpoint.to_string()), "{endpoint}");
+        }
+    }
+
     #[test]
     fn iteration_with_page_token_is_in_insertion_order() {
         let mut map = PaginatedMapping::with_capacity(3);
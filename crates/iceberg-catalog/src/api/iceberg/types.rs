@@ -194,6 +194,9 @@ impl<'de> Deserialize<'de> for NextPageToken {
 pub struct DropParams {
     #[serde(deserialize_with = "deserialize_bool", default)]
     pub purge_requested: Option<bool>,
+    /// Drop tables even if views depend on them. Lakekeeper extension, ignored for views.
+    #[serde(deserialize_with = "deserialize_bool", default)]
+    pub force: Option<bool>,
 }
 
 fn deserialize_bool<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
@@ -220,7 +223,8 @@ mod tests {
         assert_eq!(
             params,
             DropParams {
-                purge_requested: Some(true)
+                purge_requested: Some(true),
+                force: None
             }
         );
 
@@ -229,7 +233,18 @@ mod tests {
         assert_eq!(
             params,
             DropParams {
-                purge_requested: Some(true)
+                purge_requested: Some(true),
+                force: None
+            }
+        );
+
+        let query = "purgeRequested=false&force=true";
+        let params: DropParams = serde_urlencoded::from_str(query).unwrap();
+        assert_eq!(
+            params,
+            DropParams {
+                purge_requested: Some(false),
+                force: Some(true)
             }
         );
 
@@ -238,7 +253,8 @@ mod tests {
         assert_eq!(
             empty_params,
             DropParams {
-                purge_requested: None
+                purge_requested: None,
+                force: None
             }
         );
     }
@@ -3,8 +3,10 @@ use crate::api::Result;
 use crate::request_metadata::RequestMetadata;
 use async_trait::async_trait;
 use axum::extract::{Query, State};
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
-use axum::{Extension, Router};
+use axum::{Extension, Json, Router};
+use http::{header, HeaderMap, HeaderValue, StatusCode};
 use iceberg_ext::catalog::rest::{CatalogConfig, IcebergErrorResponse};
 
 #[async_trait]
@@ -32,9 +34,91 @@ pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S
         get(
             |Query(query): Query<GetConfigQueryParams>,
              State(api_context): State<ApiContext<S>>,
-             Extension(metadata): Extension<RequestMetadata>| {
+             Extension(metadata): Extension<RequestMetadata>,
+             headers: HeaderMap| async move {
                 I::get_config(query, api_context, metadata)
+                    .await
+                    .map(|config| config_response(config, &headers))
             },
         ),
     )
 }
+
+/// Add `ETag` and `Cache-Control` headers to the config, or answer with
+/// `304 Not Modified` if the client already holds the current version.
+///
+/// The config depends on the principal, so it must only be cached privately.
+fn config_response(config: CatalogConfig, request_headers: &HeaderMap) -> Response {
+    let Ok(body) = serde_json::to_vec(&config) else {
+        return Json(config).into_response();
+    };
+    let etag = format!("\"{:016x}\"", fxhash::hash64(&body));
+    let not_modified = request_headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        });
+
+    let mut response = if not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        (
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/json"),
+            )],
+            body,
+        )
+            .into_response()
+    };
+    let headers = response.headers_mut();
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        headers.insert(header::ETAG, etag);
+    }
+    if let Ok(cache_control) = HeaderValue::from_str(&format!(
+        "private, max-age={}",
+        crate::CONFIG.config_cache_ttl_seconds
+    )) {
+        headers.insert(header::CACHE_CONTROL, cache_control);
+    }
+    response
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_config_response_etag() {
+        let config = CatalogConfig {
+            overrides: [("prefix".to_string(), "my-warehouse".to_string())].into(),
+            ..Default::default()
+        };
+        let response = config_response(config.clone(), &HeaderMap::new());
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response
+            .headers()
+            .get(header::CACHE_CONTROL)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .starts_with("private, max-age="));
+        let etag = response.headers().get(header::ETAG).unwrap().clone();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let response = config_response(config.clone(), &headers);
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG), Some(&etag));
+
+        let mut changed = config;
+        changed
+            .defaults
+            .insert("key".to_string(), "value".to_string());
+        let response = config_response(changed, &headers);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers().get(header::ETAG), Some(&etag));
+    }
+}
@@ -260,6 +260,30 @@ pub struct ListNamespacesQuery {
     /// Default is false.
     #[serde(default)]
     pub return_uuids: bool,
+    /// Include the total number of namespaces in the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_total: Option<ReturnTotal>,
+    /// Include the description of each namespace in the response.
+    #[serde(default)]
+    pub return_descriptions: bool,
+}
+
+/// How the total number of entries of a list endpoint is determined.
+///
+/// Counting is opt-in, as exact counts scan all matching rows. Exact counts are capped at
+/// [`ReturnTotal::EXACT_COUNT_LIMIT`] entries, above which the estimate is returned instead.
+/// Totals only include entries the caller is allowed to see. For long listings with hidden
+/// entries, the share of visible entries is extrapolated from the first entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReturnTotal {
+    Exact,
+    /// Row estimate of the query planner
+    Estimate,
+}
+
+impl ReturnTotal {
+    pub const EXACT_COUNT_LIMIT: i64 = 10_000;
 }
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
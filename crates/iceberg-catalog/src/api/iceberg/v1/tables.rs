@@ -1,4 +1,4 @@
-use super::{PageToken, PaginationQuery};
+use super::{PageToken, PaginationQuery, ReturnTotal};
 use crate::api::iceberg::types::{DropParams, Prefix};
 use crate::api::iceberg::v1::namespace::{NamespaceIdentUrl, NamespaceParameters};
 use crate::api::{
@@ -30,6 +30,15 @@ pub struct ListTablesQuery {
     /// Default is false.
     #[serde(default)]
     pub return_uuids: bool,
+    /// Include the total number of tables or views in the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_total: Option<ReturnTotal>,
+    /// Include the last read and commit of each table or view in the response.
+    #[serde(default)]
+    pub return_access_times: bool,
+    /// Include the description of each table or view in the response.
+    #[serde(default)]
+    pub return_descriptions: bool,
 }
 
 impl From<ListTablesQuery> for PaginationQuery {
@@ -191,6 +191,7 @@ pub fn router<I: Service<S>, S: crate::api::ThreadSafe>() -> Router<ApiContext<S
                             },
                             DropParams {
                                 purge_requested: None,
+                                force: None,
                             },
                             api_context,
                             metadata,
@@ -1,7 +1,17 @@
+pub mod versioning;
+
 pub mod v1 {
     pub mod bootstrap;
+    pub mod description;
+    pub mod domain;
+    pub mod lineage;
+    pub mod namespace;
+    pub mod notice;
     pub mod project;
+    pub mod property_history;
     pub mod role;
+    pub mod table;
+    pub mod task;
     pub mod user;
     pub mod warehouse;
 
@@ -15,43 +25,86 @@ pub mod v1 {
 
     use crate::api::management::v1::user::{ListUsersQuery, ListUsersResponse};
     use crate::api::management::v1::warehouse::UndropTabularsRequest;
+    use crate::api::management::versioning::{deprecated, ManagementApiVersion};
     use crate::api::IcebergErrorResponse;
     use crate::service::authn::UserId;
     use crate::service::{
-        authz::Authorizer, Actor, Catalog, CreateOrUpdateUserResponse, RoleId, SecretStore, State,
-        TabularIdentUuid,
+        authz::Authorizer, Actor, Catalog, CreateOrUpdateUserResponse, DomainId,
+        NamespaceIdentUuid, RoleId, SecretStore, State, TabularIdentUuid,
     };
     use crate::{ProjectIdent, WarehouseIdent};
     use axum::extract::{Path, Query, State as AxumState};
     use axum::response::{IntoResponse, Response};
-    use axum::routing::{get, post};
+    use axum::routing::{delete, get, post, put};
     use bootstrap::{BootstrapRequest, ServerInfo, Service as _};
+    use description::{
+        SearchDescriptionsRequest, SearchDescriptionsResponse, Service as _, SetDescriptionRequest,
+    };
+    use domain::{
+        CreateDomainRequest, Domain, ListDomainNamespacesResponse, ListDomainsQuery,
+        ListDomainsResponse, Service as _, UpdateDomainRequest,
+    };
     use http::StatusCode;
     use iceberg_ext::catalog::rest::ErrorModel;
+    use lineage::{Service as _, TableLineageResponse, ViewFreshnessResponse, ViewLineageResponse};
+    use namespace::{
+        BulkCreateNamespacesRequest, BulkCreateNamespacesResponse, MoveNamespaceRequest,
+        RenameNamespaceRequest, Service as _,
+    };
+    use notice::{
+        CreateNoticeRequest, ListNoticesQuery, ListNoticesResponse, Notice, Service as _,
+    };
     use project::{
         CreateProjectRequest, CreateProjectResponse, GetProjectResponse, ListProjectsResponse,
         RenameProjectRequest, Service as _,
     };
+    use property_history::{
+        PropertyHistoryResponse, RevertPropertiesRequest, RevertPropertiesResponse, Service as _,
+    };
     use role::{
-        CreateRoleRequest, ListRolesQuery, ListRolesResponse, Role, SearchRoleRequest,
-        SearchRoleResponse, Service as _, UpdateRoleRequest,
+        CreateRoleRequest, ListRoleMembersQuery, ListRoleMembersResponse, ListRolesQuery,
+        ListRolesResponse, Role, SearchRoleRequest, SearchRoleResponse, Service as _,
+        UpdateRoleRequest,
     };
     use serde::Serialize;
+    use table::{
+        ExportTableInventoryRequest, ExportTableInventoryResponse, GetTableInventoryExportResponse,
+        GetTableMetadataAsOfQuery, GetTableMetadataUrlQuery, GetTableOverviewQuery,
+        RelocateTableRequest, RollbackTableRequest, RollbackTableResponse, Service as _,
+        TableCheckResponse, TableMetadataAsOfResponse, TableMetadataUrlResponse, TableOverview,
+        TableStatisticsResponse,
+    };
+    use task::{ApprovePurgeRequest, GetPurgeTaskResponse, ListPurgeTasksResponse, Service as _};
     use user::{
-        CreateUserRequest, SearchUserRequest, SearchUserResponse, Service as _, UpdateUserRequest,
-        User,
+        CreateUserRequest, GetDefaultWarehouseResponse, SearchUserRequest, SearchUserResponse,
+        Service as _, SetDefaultWarehouseRequest, UpdateUserRequest, User,
     };
     use warehouse::{
         CreateWarehouseRequest, CreateWarehouseResponse, GetWarehouseResponse,
         ListDeletedTabularsQuery, ListWarehousesRequest, ListWarehousesResponse,
-        RenameWarehouseRequest, Service as _, UpdateWarehouseCredentialRequest,
-        UpdateWarehouseDeleteProfileRequest, UpdateWarehouseStorageRequest,
+        LocationUsageQuery, RelinkWarehouseCredentialRequest, RenameWarehouseRequest,
+        RescheduleExpirationsRequest, RescheduleExpirationsResponse, Service as _,
+        SetExpirationExemptionRequest, StorageUsageQuery, StorageUsageResponse,
+        StorageValidationReport, TableStorageUsageQuery, TableStorageUsageResponse,
+        UpdateWarehouseCredentialRequest, UpdateWarehouseDeleteProfileRequest,
+        UpdateWarehouseNamespaceDefaultsRequest, UpdateWarehouseNetworkPolicyRequest,
+        UpdateWarehouseSnapshotSummaryEnrichmentRequest, UpdateWarehouseStorageRequest,
+        UpdateWarehouseTableLimitsRequest, UpdateWarehouseWasmHooksRequest, ValidateStorageRequest,
+        WarehouseStatisticsResponse,
     };
 
     pub(crate) fn default_page_size() -> i64 {
         100
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize, utoipa::ToSchema)]
+    #[serde(rename_all = "kebab-case")]
+    pub enum SortOrder {
+        #[default]
+        Asc,
+        Desc,
+    }
+
     #[derive(Debug, OpenApi)]
     #[openapi(
         info(
@@ -63,46 +116,106 @@ pub mod v1 {
             (name = "project", description = "Manage Projects"),
             (name = "warehouse", description = "Manage Warehouses"),
             (name = "user", description = "Manage Users"),
-            (name = "role", description = "Manage Roles")
+            (name = "role", description = "Manage Roles"),
+            (name = "domain", description = "Manage Domains")
         ),
         security(
             ("bearerAuth" = [])
         ),
         paths(
             activate_warehouse,
+            add_domain_namespace,
+            add_role_member,
+            approve_purge,
             bootstrap,
+            bulk_create_namespaces,
+            check_table,
+            create_domain,
+            create_notice,
             create_project,
             create_role,
             create_user,
             create_warehouse,
             deactivate_warehouse,
             delete_default_project,
+            delete_domain,
+            delete_notice,
             delete_project_by_id,
             delete_role,
             delete_user,
             delete_warehouse,
+            export_table_inventory,
             get_default_project,
+            get_domain,
+            get_namespace_property_history,
             get_project_by_id,
+            get_purge_task,
             get_role,
+            get_role_default_warehouse,
             get_server_info,
+            get_storage_usage,
+            get_table_inventory_export,
+            get_table_lineage,
+            get_table_metadata_as_of,
+            get_table_metadata_url,
+            get_table_overview,
+            get_table_property_history,
+            get_table_statistics,
+            get_table_storage_usage,
             get_user,
+            get_user_default_warehouse,
+            get_view_freshness,
+            get_view_lineage,
             get_warehouse,
+            get_warehouse_statistics,
             list_deleted_tabulars,
+            list_domain_namespaces,
+            list_domains,
+            list_location_usage,
+            list_notices,
             list_projects,
+            list_purges_awaiting_approval,
+            list_role_members,
             list_roles,
             list_user,
             list_warehouses,
+            move_namespace,
+            reactivate_user,
+            relink_storage_credential,
+            relocate_table,
+            remove_domain_namespace,
             rename_default_project,
+            rename_namespace,
             rename_project_by_id,
+            remove_role_member,
             rename_warehouse,
+            reschedule_expirations,
+            revert_namespace_properties,
+            revert_table_properties,
+            rollback_table,
+            search_descriptions,
             search_role,
             search_user,
+            set_expiration_exemption,
+            set_namespace_description,
+            set_role_default_warehouse,
+            set_table_description,
+            set_user_default_warehouse,
+            set_view_description,
             undrop_tabulars,
+            update_domain,
             update_role,
             update_storage_credential,
             update_storage_profile,
             update_user,
             update_warehouse_delete_profile,
+            update_warehouse_namespace_defaults,
+            update_warehouse_network_policy,
+            update_warehouse_snapshot_summary_enrichment,
+            update_warehouse_table_limits,
+            update_warehouse_wasm_hooks,
+            validate_storage,
+            validate_warehouse_storage,
             whoami,
         ),
         modifiers(&SecurityAddon)
@@ -176,6 +289,72 @@ pub mod v1 {
         Ok(StatusCode::NO_CONTENT)
     }
 
+    /// List notices
+    ///
+    /// Returns announcements of the operators, such as maintenance windows or deprecations,
+    /// that are currently shown.
+    #[utoipa::path(
+        get,
+        tag = "server",
+        path = "/management/v1/notices",
+        params(ListNoticesQuery),
+        responses(
+            (status = 200, description = "List of notices", body = ListNoticesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_notices<C: Catalog, A: Authorizer, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Query(query): Query<ListNoticesQuery>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListNoticesResponse> {
+        ApiServer::<C, A, S>::list_notices(api_context, query, metadata).await
+    }
+
+    /// Create a notice
+    ///
+    /// Notices are shown to all users of the server between `starts-at` and `ends-at`.
+    #[utoipa::path(
+        post,
+        tag = "server",
+        path = "/management/v1/notices",
+        request_body = CreateNoticeRequest,
+        responses(
+            (status = 201, description = "Notice created successfully", body = Notice),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_notice<C: Catalog, A: Authorizer, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<CreateNoticeRequest>,
+    ) -> Result<(StatusCode, Json<Notice>)> {
+        ApiServer::<C, A, S>::create_notice(request, api_context, metadata)
+            .await
+            .map(|notice| (StatusCode::CREATED, Json(notice)))
+    }
+
+    /// Delete a notice
+    #[utoipa::path(
+        delete,
+        tag = "server",
+        path = "/management/v1/notices/{notice_id}",
+        params(("notice_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Notice deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_notice<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(notice_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::delete_notice(api_context, metadata, notice_id)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
     /// Creates the user in the catalog if it does not exist.
     /// If the user exists, it updates the users' metadata from the token.
     /// The token sent to this endpoint should have "profile" and "email" scopes.
@@ -339,6 +518,77 @@ pub mod v1 {
             .map(|()| (StatusCode::NO_CONTENT, ()))
     }
 
+    /// Reactivate a user
+    ///
+    /// Lifts a deactivation by the stale user policy. Other instances of Lakekeeper
+    /// may keep rejecting the user for up to five minutes.
+    #[utoipa::path(
+        post,
+        tag = "user",
+        path = "/management/v1/user/{id}/reactivate",
+        params(("id" = Uuid,)),
+        responses(
+            (status = 204, description = "User reactivated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn reactivate_user<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(id): Path<UserId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::reactivate_user(api_context, metadata, id)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Get the default warehouse of a user
+    ///
+    /// The default warehouse is used by `GET /catalog/v1/config` if the request
+    /// does not specify a warehouse. If the request specifies a warehouse name
+    /// without a project, the name is looked up in the project of the default
+    /// warehouse instead of the default project.
+    #[utoipa::path(
+        get,
+        tag = "user",
+        path = "/management/v1/user/{user_id}/default-warehouse",
+        params(("user_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Default warehouse of the user", body = GetDefaultWarehouseResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_user_default_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(user_id): Path<UserId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetDefaultWarehouseResponse> {
+        ApiServer::<C, A, S>::get_user_default_warehouse(api_context, metadata, user_id).await
+    }
+
+    /// Set or remove the default warehouse of a user
+    #[utoipa::path(
+        put,
+        tag = "user",
+        path = "/management/v1/user/{user_id}/default-warehouse",
+        params(("user_id" = Uuid,)),
+        request_body = SetDefaultWarehouseRequest,
+        responses(
+            (status = 204, description = "Default warehouse updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_user_default_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(user_id): Path<UserId>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetDefaultWarehouseRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::set_user_default_warehouse(api_context, metadata, user_id, request)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
     /// Create a new role
     #[utoipa::path(
         post,
@@ -466,244 +716,539 @@ pub mod v1 {
             .map(|role| (StatusCode::OK, Json(role)))
     }
 
-    /// Create a new warehouse.
+    /// Get the default warehouse of a role
     ///
-    /// Create a new warehouse in the given project. The project
-    /// of a warehouse cannot be changed after creation.
-    /// The storage configuration is validated by this method.
+    /// If a principal assumes the role, the default warehouse of the role takes
+    /// precedence over the default warehouse of the user.
     #[utoipa::path(
-        post,
-        tag = "warehouse",
-        path = "/management/v1/warehouse",
-        request_body = CreateWarehouseRequest,
+        get,
+        tag = "role",
+        path = "/management/v1/role/{id}/default-warehouse",
+        params(("id" = Uuid,)),
         responses(
-            (status = 201, description = "Warehouse created successfully", body = CreateWarehouseResponse),
+            (status = 200, description = "Default warehouse of the role", body = GetDefaultWarehouseResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn create_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn get_role_default_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(id): Path<RoleId>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<CreateWarehouseRequest>,
-    ) -> Result<CreateWarehouseResponse> {
-        ApiServer::<C, A, S>::create_warehouse(request, api_context, metadata).await
+    ) -> Result<GetDefaultWarehouseResponse> {
+        ApiServer::<C, A, S>::get_role_default_warehouse(api_context, metadata, id).await
     }
 
-    /// List all projects the requesting user has access to
+    /// Set or remove the default warehouse of a role
+    ///
+    /// The warehouse must belong to the project of the role.
     #[utoipa::path(
-        get,
-        tag = "project",
-        path = "/management/v1/project-list",
+        put,
+        tag = "role",
+        path = "/management/v1/role/{id}/default-warehouse",
+        params(("id" = Uuid,)),
+        request_body = SetDefaultWarehouseRequest,
         responses(
-            (status = 200, description = "List of projects", body = ListProjectsResponse),
+            (status = 204, description = "Default warehouse updated successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn list_projects<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    async fn set_role_default_warehouse<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(id): Path<RoleId>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<ListProjectsResponse> {
-        ApiServer::<C, A, S>::list_projects(api_context, metadata).await
+        Json(request): Json<SetDefaultWarehouseRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::set_role_default_warehouse(api_context, metadata, id, request)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
     }
 
-    /// Create a new project
+    /// List the member roles of a role
+    ///
+    /// Assignees of a member role inherit all privileges of the role.
+    /// With `recursive=true`, members of members are included as well.
     #[utoipa::path(
-        post,
-        tag = "project",
-        path = "/management/v1/project",
+        get,
+        tag = "role",
+        path = "/management/v1/role/{id}/members",
+        params(("id" = Uuid,), ListRoleMembersQuery),
         responses(
-            (status = 201, description = "Project created successfully", body = CreateProjectResponse),
+            (status = 200, description = "Member roles of the role", body = ListRoleMembersResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn create_project<C: Catalog, A: Authorizer, S: SecretStore>(
+    async fn list_role_members<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(id): Path<RoleId>,
+        Query(query): Query<ListRoleMembersQuery>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<CreateProjectRequest>,
-    ) -> Result<CreateProjectResponse> {
-        ApiServer::<C, A, S>::create_project(request, api_context, metadata).await
+    ) -> Result<ListRoleMembersResponse> {
+        ApiServer::<C, A, S>::list_role_members(api_context, metadata, id, query).await
     }
 
-    /// Get the default project
+    /// Add a role as a member of another role
+    ///
+    /// Both roles must belong to the same project. Memberships that would
+    /// introduce a cycle are rejected with a 409 Conflict.
     #[utoipa::path(
-        get,
-        tag = "project",
-        path = "/management/v1/default-project",
+        put,
+        tag = "role",
+        path = "/management/v1/role/{id}/members/{member_id}",
+        params(("id" = Uuid,), ("member_id" = Uuid,)),
         responses(
-            (status = 200, description = "Project details", body = GetProjectResponse),
+            (status = 204, description = "Role member added successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_default_project<C: Catalog, A: Authorizer, S: SecretStore>(
+    async fn add_role_member<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path((id, member_id)): Path<(RoleId, RoleId)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<GetProjectResponse> {
-        ApiServer::<C, A, S>::get_project(None, api_context, metadata).await
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::add_role_member(api_context, metadata, id, member_id)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
     }
 
-    /// Get a specific project by id
+    /// Remove a member role from a role
     #[utoipa::path(
-        get,
-        tag = "project",
-        path = "/management/v1/project/{project_id}",
-        params(("project_id" = Uuid,)),
+        delete,
+        tag = "role",
+        path = "/management/v1/role/{id}/members/{member_id}",
+        params(("id" = Uuid,), ("member_id" = Uuid,)),
         responses(
-            (status = 200, description = "Project details", body = GetProjectResponse),
+            (status = 204, description = "Role member removed successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_project_by_id<C: Catalog, A: Authorizer, S: SecretStore>(
-        Path(project_id): Path<ProjectIdent>,
+    async fn remove_role_member<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path((id, member_id)): Path<(RoleId, RoleId)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<GetProjectResponse> {
-        ApiServer::<C, A, S>::get_project(Some(project_id), api_context, metadata).await
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::remove_role_member(api_context, metadata, id, member_id)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
     }
 
-    /// Delete the default project
+    /// Create a new domain
+    ///
+    /// Domains group namespaces of different warehouses of a project,
+    /// for example by the team or data product that owns them.
     #[utoipa::path(
-        delete,
-        tag = "project",
-        path = "/management/v1/default-project",
+        post,
+        tag = "domain",
+        path = "/management/v1/domain",
+        request_body = CreateDomainRequest,
         responses(
-            (status = 204, description = "Project deleted successfully"),
+            (status = 201, description = "Domain successfully created", body = Domain),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn delete_default_project<C: Catalog, A: Authorizer, S: SecretStore>(
+    async fn create_domain<C: Catalog, A: Authorizer, S: SecretStore>(
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<(StatusCode, ())> {
-        ApiServer::<C, A, S>::delete_project(None, api_context, metadata)
-            .await
-            .map(|()| (StatusCode::NO_CONTENT, ()))
+        Json(request): Json<CreateDomainRequest>,
+    ) -> Response {
+        match ApiServer::<C, A, S>::create_domain(request, api_context, metadata).await {
+            Ok(domain) => (StatusCode::CREATED, Json(domain)).into_response(),
+            Err(e) => e.into_response(),
+        }
     }
 
-    /// Delete the default project
+    /// List domains in a project
     #[utoipa::path(
-        delete,
-        tag = "project",
-        path = "/management/v1/project/{project_id}",
-        params(("project_id" = Uuid,)),
+        get,
+        tag = "domain",
+        path = "/management/v1/domain",
+        params(ListDomainsQuery),
         responses(
-            (status = 204, description = "Project deleted successfully"),
+            (status = 200, description = "List of domains", body = ListDomainsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn delete_project_by_id<C: Catalog, A: Authorizer, S: SecretStore>(
-        Path(project_id): Path<ProjectIdent>,
+    async fn list_domains<C: Catalog, A: Authorizer, S: SecretStore>(
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Query(query): Query<ListDomainsQuery>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<(StatusCode, ())> {
-        ApiServer::<C, A, S>::delete_project(Some(project_id), api_context, metadata)
-            .await
-            .map(|()| (StatusCode::NO_CONTENT, ()))
+    ) -> Result<ListDomainsResponse> {
+        ApiServer::<C, A, S>::list_domains(api_context, query, metadata).await
     }
 
-    /// Rename the default project
+    /// Get a domain
     #[utoipa::path(
-        post,
-        tag = "project",
-        path = "/management/v1/default-project/rename",
+        get,
+        tag = "domain",
+        path = "/management/v1/domain/{domain_id}",
+        params(("domain_id" = Uuid,)),
         responses(
-            (status = 200, description = "Project renamed successfully"),
+            (status = 200, description = "Domain details", body = Domain),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn rename_default_project<C: Catalog, A: Authorizer, S: SecretStore>(
+    async fn get_domain<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(domain_id): Path<DomainId>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<RenameProjectRequest>,
-    ) -> Result<()> {
-        ApiServer::<C, A, S>::rename_project(None, request, api_context, metadata).await
+    ) -> Result<Domain> {
+        ApiServer::<C, A, S>::get_domain(api_context, metadata, domain_id).await
     }
 
-    /// Rename project by id
+    /// Update a domain
+    ///
+    /// Replaces name, description and tags of the domain.
     #[utoipa::path(
         post,
-        tag = "project",
-        path = "/management/v1/project/{project_id}/rename",
-        params(("project_id" = Uuid,)),
+        tag = "domain",
+        path = "/management/v1/domain/{domain_id}",
+        params(("domain_id" = Uuid,)),
+        request_body = UpdateDomainRequest,
         responses(
-            (status = 200, description = "Project renamed successfully"),
+            (status = 200, description = "Domain updated successfully", body = Domain),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn rename_project_by_id<C: Catalog, A: Authorizer, S: SecretStore>(
-        Path(project_id): Path<ProjectIdent>,
+    async fn update_domain<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(domain_id): Path<DomainId>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<RenameProjectRequest>,
-    ) -> Result<()> {
-        ApiServer::<C, A, S>::rename_project(Some(project_id), request, api_context, metadata).await
+        Json(request): Json<UpdateDomainRequest>,
+    ) -> Result<Domain> {
+        ApiServer::<C, A, S>::update_domain(api_context, metadata, domain_id, request).await
     }
 
-    /// List all warehouses in a project
+    /// Delete a domain
     ///
-    /// By default, this endpoint does not return deactivated warehouses.
-    /// To include deactivated warehouses, set the `include_deactivated` query parameter to `true`.
+    /// Namespaces of the domain are not deleted, they just no longer belong to a domain.
     #[utoipa::path(
-        get,
-        tag = "warehouse",
-        path = "/management/v1/warehouse",
-        params(ListWarehousesRequest),
+        delete,
+        tag = "domain",
+        path = "/management/v1/domain/{domain_id}",
+        params(("domain_id" = Uuid,)),
         responses(
-            (status = 200, description = "List of warehouses", body = ListWarehousesResponse),
+            (status = 204, description = "Domain deleted successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn list_warehouses<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Query(request): Query<ListWarehousesRequest>,
+    async fn delete_domain<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(domain_id): Path<DomainId>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<ListWarehousesResponse> {
-        ApiServer::<C, A, S>::list_warehouses(request, api_context, metadata).await
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::delete_domain(api_context, metadata, domain_id)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
     }
 
-    /// Get a warehouse by ID
+    /// List namespaces of a domain
+    ///
+    /// Only namespaces you are allowed to see are returned.
     #[utoipa::path(
         get,
-        tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}",
+        tag = "domain",
+        path = "/management/v1/domain/{domain_id}/namespaces",
+        params(("domain_id" = Uuid,)),
         responses(
-            (status = 200, description = "Warehouse details", body = GetWarehouseResponse),
+            (status = 200, description = "Namespaces of the domain", body = ListDomainNamespacesResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn get_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(warehouse_id): Path<uuid::Uuid>,
+    async fn list_domain_namespaces<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(domain_id): Path<DomainId>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<GetWarehouseResponse> {
-        ApiServer::<C, A, S>::get_warehouse(warehouse_id.into(), api_context, metadata).await
+    ) -> Result<ListDomainNamespacesResponse> {
+        ApiServer::<C, A, S>::list_domain_namespaces(api_context, metadata, domain_id).await
     }
 
-    /// Delete a warehouse by ID
+    /// Add a namespace to a domain
+    ///
+    /// The namespace must belong to a warehouse of the domain's project.
+    /// A namespace can be part of at most one domain.
     #[utoipa::path(
-        delete,
-        tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}",
+        put,
+        tag = "domain",
+        path = "/management/v1/domain/{domain_id}/namespaces/{namespace_id}",
+        params(("domain_id" = Uuid,), ("namespace_id" = Uuid,)),
         responses(
-            (status = 204, description = "Warehouse deleted successfully"),
+            (status = 204, description = "Namespace added to domain"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn delete_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(warehouse_id): Path<uuid::Uuid>,
+    async fn add_domain_namespace<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path((domain_id, namespace_id)): Path<(DomainId, NamespaceIdentUuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
     ) -> Result<(StatusCode, ())> {
-        ApiServer::<C, A, S>::delete_warehouse(warehouse_id.into(), api_context, metadata)
+        ApiServer::<C, A, S>::add_domain_namespace(api_context, metadata, domain_id, namespace_id)
             .await
             .map(|()| (StatusCode::NO_CONTENT, ()))
     }
 
-    /// Rename a warehouse
+    /// Remove a namespace from a domain
     #[utoipa::path(
-        post,
-        tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/rename",
-        request_body = RenameWarehouseRequest,
+        delete,
+        tag = "domain",
+        path = "/management/v1/domain/{domain_id}/namespaces/{namespace_id}",
+        params(("domain_id" = Uuid,), ("namespace_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Namespace removed from domain"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn remove_domain_namespace<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path((domain_id, namespace_id)): Path<(DomainId, NamespaceIdentUuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::remove_domain_namespace(
+            api_context,
+            metadata,
+            domain_id,
+            namespace_id,
+        )
+        .await
+        .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Create a new warehouse.
+    ///
+    /// Create a new warehouse in the given project. The project
+    /// of a warehouse cannot be changed after creation.
+    /// The storage configuration is validated by this method.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse",
+        request_body = CreateWarehouseRequest,
+        responses(
+            (status = 201, description = "Warehouse created successfully", body = CreateWarehouseResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<CreateWarehouseRequest>,
+    ) -> Result<CreateWarehouseResponse> {
+        ApiServer::<C, A, S>::create_warehouse(request, api_context, metadata).await
+    }
+
+    /// List all projects the requesting user has access to
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = "/management/v1/project-list",
+        responses(
+            (status = 200, description = "List of projects", body = ListProjectsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_projects<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListProjectsResponse> {
+        ApiServer::<C, A, S>::list_projects(api_context, metadata).await
+    }
+
+    /// Create a new project
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = "/management/v1/project",
+        responses(
+            (status = 201, description = "Project created successfully", body = CreateProjectResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn create_project<C: Catalog, A: Authorizer, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<CreateProjectRequest>,
+    ) -> Result<CreateProjectResponse> {
+        ApiServer::<C, A, S>::create_project(request, api_context, metadata).await
+    }
+
+    /// Get the default project
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = "/management/v1/default-project",
+        responses(
+            (status = 200, description = "Project details", body = GetProjectResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_default_project<C: Catalog, A: Authorizer, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetProjectResponse> {
+        ApiServer::<C, A, S>::get_project(None, api_context, metadata).await
+    }
+
+    /// Get a specific project by id
+    #[utoipa::path(
+        get,
+        tag = "project",
+        path = "/management/v1/project/{project_id}",
+        params(("project_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Project details", body = GetProjectResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_project_by_id<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectIdent>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetProjectResponse> {
+        ApiServer::<C, A, S>::get_project(Some(project_id), api_context, metadata).await
+    }
+
+    /// Delete the default project
+    #[utoipa::path(
+        delete,
+        tag = "project",
+        path = "/management/v1/default-project",
+        responses(
+            (status = 204, description = "Project deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_default_project<C: Catalog, A: Authorizer, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::delete_project(None, api_context, metadata)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Delete the default project
+    #[utoipa::path(
+        delete,
+        tag = "project",
+        path = "/management/v1/project/{project_id}",
+        params(("project_id" = Uuid,)),
+        responses(
+            (status = 204, description = "Project deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_project_by_id<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectIdent>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::delete_project(Some(project_id), api_context, metadata)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Rename the default project
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = "/management/v1/default-project/rename",
+        responses(
+            (status = 200, description = "Project renamed successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rename_default_project<C: Catalog, A: Authorizer, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RenameProjectRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::rename_project(None, request, api_context, metadata).await
+    }
+
+    /// Rename project by id
+    #[utoipa::path(
+        post,
+        tag = "project",
+        path = "/management/v1/project/{project_id}/rename",
+        params(("project_id" = Uuid,)),
+        responses(
+            (status = 200, description = "Project renamed successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rename_project_by_id<C: Catalog, A: Authorizer, S: SecretStore>(
+        Path(project_id): Path<ProjectIdent>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RenameProjectRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::rename_project(Some(project_id), request, api_context, metadata).await
+    }
+
+    /// List all warehouses in a project
+    ///
+    /// By default, this endpoint does not return deactivated warehouses.
+    /// To include deactivated warehouses, set the `include_deactivated` query parameter to `true`.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse",
+        params(ListWarehousesRequest),
+        responses(
+            (status = 200, description = "List of warehouses", body = ListWarehousesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_warehouses<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Query(request): Query<ListWarehousesRequest>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ListWarehousesResponse> {
+        ApiServer::<C, A, S>::list_warehouses(request, api_context, metadata).await
+    }
+
+    /// Get a warehouse by ID
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}",
+        responses(
+            (status = 200, description = "Warehouse details", body = GetWarehouseResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetWarehouseResponse> {
+        ApiServer::<C, A, S>::get_warehouse(warehouse_id.into(), api_context, metadata).await
+    }
+
+    /// Delete a warehouse by ID
+    #[utoipa::path(
+        delete,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}",
+        responses(
+            (status = 204, description = "Warehouse deleted successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn delete_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::delete_warehouse(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(|()| (StatusCode::NO_CONTENT, ()))
+    }
+
+    /// Rename a warehouse
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/rename",
+        request_body = RenameWarehouseRequest,
         responses(
             (status = 200, description = "Warehouse renamed successfully"),
             (status = "4XX", body = IcebergErrorResponse),
@@ -713,30 +1258,1155 @@ pub mod v1 {
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<RenameWarehouseRequest>,
-    ) -> Result<()> {
-        ApiServer::<C, A, S>::rename_warehouse(warehouse_id.into(), request, api_context, metadata)
-            .await
+        Json(request): Json<RenameWarehouseRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::rename_warehouse(warehouse_id.into(), request, api_context, metadata)
+            .await
+    }
+
+    /// Update the Deletion Profile (soft-delete) of a warehouse.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/delete-profile",
+            request_body = UpdateWarehouseDeleteProfileRequest,
+            responses(
+                (status = 200, description = "Deletion Profile updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_warehouse_delete_profile<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseDeleteProfileRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_delete_profile(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Update the Namespace Defaults of a warehouse
+    ///
+    /// Properties that are applied to namespaces created without them, for example `owner`.
+    /// Properties specified when creating a namespace take precedence.
+    /// Existing namespaces are not changed.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/namespace-defaults",
+            request_body = UpdateWarehouseNamespaceDefaultsRequest,
+            responses(
+                (status = 200, description = "Namespace Defaults updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_warehouse_namespace_defaults<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseNamespaceDefaultsRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_namespace_defaults(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Update the Network Policy of a warehouse
+    ///
+    /// Restricts the client networks the catalog API and the management API of the warehouse
+    /// may be accessed from. An empty list removes the restriction.
+    /// Client addresses are determined as configured by `LAKEKEEPER__TRUSTED_PROXIES`.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/network-policy",
+            request_body = UpdateWarehouseNetworkPolicyRequest,
+            responses(
+                (status = 200, description = "Network Policy updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_warehouse_network_policy<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseNetworkPolicyRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_network_policy(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Update the Snapshot Summary Enrichment of a warehouse
+    ///
+    /// Selects the provenance the catalog writes into the summary of every snapshot committed
+    /// to a table of the warehouse: the committing principal (`lakekeeper.committed-by`),
+    /// the `User-Agent` of the engine (`lakekeeper.engine`) and the request id
+    /// (`lakekeeper.request-id`). Applies to commits received after the update.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/snapshot-summary-enrichment",
+            request_body = UpdateWarehouseSnapshotSummaryEnrichmentRequest,
+            responses(
+                (status = 200, description = "Snapshot Summary Enrichment updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_warehouse_snapshot_summary_enrichment<
+        C: Catalog,
+        A: Authorizer + Clone,
+        S: SecretStore,
+    >(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseSnapshotSummaryEnrichmentRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_snapshot_summary_enrichment(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Update the WASM hooks of a warehouse
+    ///
+    /// WASM hooks are WebAssembly modules in the hook directory of the server that are run
+    /// as contract verifiers before tables and views of the warehouse are created, committed to,
+    /// renamed or dropped. Hooks are referenced by their file name without extension and run
+    /// in the given order. Unknown hooks and modules that fail to compile are rejected.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/wasm-hooks",
+            request_body = UpdateWarehouseWasmHooksRequest,
+            responses(
+                (status = 200, description = "WASM hooks updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_warehouse_wasm_hooks<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseWasmHooksRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_wasm_hooks(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Update the Table Limits of a warehouse
+    ///
+    /// Thresholds on the number of snapshots and the metadata size of each table.
+    /// Commits reaching a warning threshold succeed, but are answered with a `Warning` header
+    /// and emit a `tableLimitWarning` event. Commits growing a table beyond a limit are rejected
+    /// until snapshots are expired.
+    #[utoipa::path(
+            post,
+            tag = "warehouse",
+            path = "/management/v1/warehouse/{warehouse_id}/table-limits",
+            request_body = UpdateWarehouseTableLimitsRequest,
+            responses(
+                (status = 200, description = "Table Limits updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+            )
+        )]
+    async fn update_warehouse_table_limits<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseTableLimitsRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_warehouse_table_limits(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Deactivate a warehouse
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/deactivate",
+        responses(
+            (status = 200, description = "Warehouse deactivated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn deactivate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::deactivate_warehouse(warehouse_id.into(), api_context, metadata).await
+    }
+
+    /// Activate a warehouse
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/activate",
+        responses(
+            (status = 200, description = "Warehouse activated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn activate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::activate_warehouse(warehouse_id.into(), api_context, metadata).await
+    }
+
+    /// Update the storage profile of a warehouse including its storage credential.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage",
+        request_body = UpdateWarehouseStorageRequest,
+        responses(
+            (status = 200, description = "Storage profile updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_storage_profile<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseStorageRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_storage(warehouse_id.into(), request, api_context, metadata)
+            .await
+    }
+
+    /// Validate a storage profile and credential
+    ///
+    /// Runs all storage checks (credentials, reachability, read / write / list / delete
+    /// permissions, vended credentials, remote signing) and reports the outcome of each check
+    /// instead of failing on the first error. Use this endpoint to debug storage setups before
+    /// creating a warehouse.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/validate-storage",
+        request_body = ValidateStorageRequest,
+        responses(
+            (status = 200, description = "Validation report", body = StorageValidationReport),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn validate_storage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<ValidateStorageRequest>,
+    ) -> Result<(StatusCode, Json<StorageValidationReport>)> {
+        ApiServer::<C, A, S>::validate_storage(request, api_context, metadata)
+            .await
+            .map(|report| (StatusCode::OK, Json(report)))
+    }
+
+    /// Validate the storage profile and credential of an existing warehouse
+    ///
+    /// Reports the outcome of each storage check for the currently stored configuration.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/validate-storage",
+        responses(
+            (status = 200, description = "Validation report", body = StorageValidationReport),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn validate_warehouse_storage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<(StatusCode, Json<StorageValidationReport>)> {
+        ApiServer::<C, A, S>::validate_warehouse_storage(warehouse_id.into(), api_context, metadata)
+            .await
+            .map(|report| (StatusCode::OK, Json(report)))
+    }
+
+    /// Update the storage credential of a warehouse. The storage profile is not modified.
+    /// This can be used to update credentials before expiration.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage-credential",
+        request_body = UpdateWarehouseCredentialRequest,
+        responses(
+            (status = 200, description = "Storage credential updated successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn update_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UpdateWarehouseCredentialRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::update_storage_credential(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Replace a storage credential that is missing or cannot be read
+    ///
+    /// Attaches a new storage credential to a warehouse whose `credential-status` is
+    /// `missing` or `unreadable`. Readable credentials must be rotated via the
+    /// storage-credential endpoint instead.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage-credential/relink",
+        request_body = RelinkWarehouseCredentialRequest,
+        responses(
+            (status = 200, description = "Storage credential relinked successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn relink_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RelinkWarehouseCredentialRequest>,
+    ) -> Result<()> {
+        ApiServer::<C, A, S>::relink_storage_credential(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get warehouse statistics
+    ///
+    /// Returns the number of tables and views in the warehouse as well as
+    /// the storage still held by soft-deleted tables awaiting expiration.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/statistics",
+        responses(
+            (status = 200, description = "Warehouse statistics", body = WarehouseStatisticsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_warehouse_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<WarehouseStatisticsResponse> {
+        ApiServer::<C, A, S>::get_warehouse_statistics(warehouse_id.into(), api_context, metadata)
+            .await
+    }
+
+    /// Get storage usage per namespace
+    ///
+    /// Returns the daily rollup of the storage used by the tables of each namespace.
+    /// Sizes are estimated from snapshot summaries. Rollups are only computed if
+    /// `LAKEKEEPER__ENABLE_STORAGE_USAGE_ROLLUP` is set.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage-usage",
+        params(StorageUsageQuery),
+        responses(
+            (status = 200, description = "Storage usage per namespace", body = StorageUsageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_storage_usage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<StorageUsageQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<StorageUsageResponse> {
+        ApiServer::<C, A, S>::get_storage_usage(warehouse_id.into(), query, api_context, metadata)
+            .await
+    }
+
+    /// Get storage usage per table
+    ///
+    /// Returns the daily rollup of the storage used by each table of the warehouse.
+    /// Sizes are estimated from snapshot summaries. Rollups are only computed if
+    /// `LAKEKEEPER__ENABLE_STORAGE_USAGE_ROLLUP` is set.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/storage-usage/tables",
+        params(TableStorageUsageQuery),
+        responses(
+            (status = 200, description = "Storage usage per table", body = TableStorageUsageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_storage_usage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<TableStorageUsageQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<TableStorageUsageResponse> {
+        ApiServer::<C, A, S>::get_table_storage_usage(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List soft-deleted tabulars
+    ///
+    /// List all soft-deleted tabulars in the warehouse that are visible to you.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/deleted-tabulars",
+        params(ListDeletedTabularsQuery),
+        responses(
+            (status = 200, description = "List of soft-deleted tabulars", body = ListDeletedTabularsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_deleted_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<ListDeletedTabularsQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListDeletedTabularsResponse>> {
+        ApiServer::<C, A, S>::list_soft_deleted_tabulars(
+            warehouse_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// List tabulars occupying a location
+    ///
+    /// Returns all tabulars (including staged and soft-deleted ones) whose location
+    /// is equal to, a parent of, or a child of the given prefix.
+    /// Useful to find out why a table cannot be created at a certain location.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/location-usage",
+        params(LocationUsageQuery),
+        responses(
+            (status = 200, description = "Tabulars occupying the location", body = LocationUsageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_location_usage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        Query(query): Query<LocationUsageQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<LocationUsageResponse>> {
+        ApiServer::<C, A, S>::list_location_usage(warehouse_id.into(), query, api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Undrop soft-deleted tabulars
+    ///
+    /// Either all tabulars are restored or none. Fails with 409 Conflict if the
+    /// expiration of one of the tabulars is already running.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/deleted_tabulars/undrop",
+        responses(
+            (status = 204, description = "Tabular undropped successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn undrop_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(_warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<UndropTabularsRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::undrop_tabulars(metadata, request, api_context).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Reschedule expirations of soft-deleted tabulars
+    ///
+    /// Changes of the delete profile only apply to tabulars dropped afterwards.
+    /// This endpoint moves the expiration of tabulars that are already soft-deleted,
+    /// either to the expiration of the current delete profile or by a fixed offset.
+    /// With `dry-run`, the new expiration dates are returned without being applied.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/deleted-tabulars/reschedule",
+        request_body = RescheduleExpirationsRequest,
+        responses(
+            (status = 200, description = "Rescheduled expirations", body = RescheduleExpirationsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn reschedule_expirations<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RescheduleExpirationsRequest>,
+    ) -> Result<RescheduleExpirationsResponse> {
+        ApiServer::<C, A, S>::reschedule_expirations(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Exempt a soft-deleted tabular from expiration
+    ///
+    /// While exempt, the tabular is skipped by the expiration worker even after its
+    /// expiration date has passed, for example because it is under a legal hold.
+    /// Clearing the exemption lets the tabular expire at the next check.
+    /// Undropping the tabular also clears its exemption.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/deleted-tabulars/{tabular_id}/expiration-exemption",
+        request_body = SetExpirationExemptionRequest,
+        responses(
+            (status = 204, description = "Expiration exemption set successfully"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn set_expiration_exemption<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, tabular_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<SetExpirationExemptionRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_expiration_exemption(
+            warehouse_id.into(),
+            tabular_id,
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// List purges waiting for approval
+    ///
+    /// Purges of locations containing more objects than `purge_approval_threshold`
+    /// are held back until they are approved.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/task/purge/awaiting-approval",
+        responses(
+            (status = 200, description = "Purges waiting for approval", body = ListPurgeTasksResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn list_purges_awaiting_approval<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<ListPurgeTasksResponse>> {
+        ApiServer::<C, A, S>::list_purges_awaiting_approval(
+            warehouse_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+        .map(Json)
+    }
+
+    /// Get status and progress of a purge
+    ///
+    /// Purges record their progress after every listing page. An interrupted purge
+    /// is resumed from its checkpoint when it is picked up again.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/task/purge/{task_id}",
+        responses(
+            (status = 200, description = "Status and progress of the purge", body = GetPurgeTaskResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_purge_task<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, task_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<Json<GetPurgeTaskResponse>> {
+        ApiServer::<C, A, S>::get_purge_task(warehouse_id.into(), task_id, api_context, metadata)
+            .await
+            .map(Json)
+    }
+
+    /// Approve a purge
+    ///
+    /// Releases a purge that is waiting for approval. The location of the purge
+    /// must be repeated in the request to confirm which data is deleted.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/task/purge/{task_id}/approve",
+        request_body = ApprovePurgeRequest,
+        responses(
+            (status = 204, description = "Purge approved"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn approve_purge<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, task_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<ApprovePurgeRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::approve_purge(
+            warehouse_id.into(),
+            task_id,
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    /// Check the health of a table
+    ///
+    /// Verifies that the metadata file the catalog points to exists in storage, can be parsed
+    /// and describes the same table state as the catalog, and that the manifest list of the
+    /// current snapshot can be read. Checks depending on a failed check are skipped.
+    /// Storage errors are reported as failed checks, not as errors of the request.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v2/warehouse/{warehouse_id}/table/{table_id}/check",
+        responses(
+            (status = 200, description = "Results of the checks", body = TableCheckResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn check_table<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<TableCheckResponse> {
+        ApiServer::<C, A, S>::check_table(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Export an inventory of the data files of a table
+    ///
+    /// Writes the data and delete files referenced by any snapshot of the table to the given
+    /// location, one row per file with its path, content, format, partition, record count,
+    /// size and the snapshot that added it. The inventory can be compared with inventory
+    /// reports of the storage to find orphaned or missing files.
+    /// The export runs in the background, use the returned ID to get its status.
+    /// Inventory files contain up to one million rows each. `inventory.json`, listing all
+    /// inventory files, is written last.
+    /// Requires the permission to update the storage of the warehouse and to read the data
+    /// of the table.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v2/warehouse/{warehouse_id}/table/{table_id}/inventory-export",
+        request_body = ExportTableInventoryRequest,
+        responses(
+            (status = 202, description = "Export scheduled", body = ExportTableInventoryResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn export_table_inventory<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<ExportTableInventoryRequest>,
+    ) -> Result<ExportTableInventoryResponse> {
+        ApiServer::<C, A, S>::export_table_inventory(
+            warehouse_id.into(),
+            table_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get the status of an inventory export
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v2/warehouse/{warehouse_id}/table/{table_id}/inventory-export/{export_id}",
+        responses(
+            (status = 200, description = "Status of the export", body = GetTableInventoryExportResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_inventory_export<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id, export_id)): Path<(uuid::Uuid, uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<GetTableInventoryExportResponse> {
+        ApiServer::<C, A, S>::get_table_inventory_export(
+            warehouse_id.into(),
+            table_id.into(),
+            export_id,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Relocate a table
+    ///
+    /// Moves all files of a table to a new location within the storage of the warehouse.
+    /// Manifests and metadata are rewritten to the new paths and the table is switched to
+    /// the new location once all files are copied. Afterwards the old location is purged.
+    /// The relocation runs in the background, this endpoint only schedules it.
+    /// Tables with delete files cannot be relocated.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/relocate",
+        request_body = RelocateTableRequest,
+        responses(
+            (status = 202, description = "Relocation scheduled"),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn relocate_table<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RelocateTableRequest>,
+    ) -> Result<(StatusCode, ())> {
+        ApiServer::<C, A, S>::relocate_table(
+            warehouse_id.into(),
+            table_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|()| (StatusCode::ACCEPTED, ()))
+    }
+
+    /// Roll back a table
+    ///
+    /// Move a branch of the table back to a previous snapshot, identified either by its ID
+    /// or by a point in time. The rollback is committed like any other table update and
+    /// requires the permission to commit to the table.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/rollback",
+        request_body = RollbackTableRequest,
+        responses(
+            (status = 200, description = "Table rolled back successfully", body = RollbackTableResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn rollback_table<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RollbackTableRequest>,
+    ) -> Result<(StatusCode, Json<RollbackTableResponse>)> {
+        ApiServer::<C, A, S>::rollback_table(
+            warehouse_id.into(),
+            table_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+        .map(|response| (StatusCode::OK, Json(response)))
+    }
+
+    /// Get table metadata as of a point in time
+    ///
+    /// Returns the metadata that was current at the given timestamp, or the metadata of the
+    /// commit that made the given snapshot current, together with the schema of the snapshot and
+    /// the partition spec at that time. Older metadata is read from the metadata log of the table,
+    /// so it is only available as long as the metadata file is part of the log.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/metadata-as-of",
+        params(GetTableMetadataAsOfQuery),
+        responses(
+            (status = 200, description = "Table metadata at the requested point in time", body = TableMetadataAsOfResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_metadata_as_of<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<GetTableMetadataAsOfQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<TableMetadataAsOfResponse> {
+        ApiServer::<C, A, S>::get_table_metadata_as_of(
+            warehouse_id.into(),
+            table_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get presigned URLs for table metadata
+    ///
+    /// Returns short-lived URLs to download the current metadata file and optionally the
+    /// manifest list of the current snapshot directly from object storage,
+    /// without requiring any storage credentials. Requires permission to read the table metadata.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/metadata-url",
+        params(GetTableMetadataUrlQuery),
+        responses(
+            (status = 200, description = "Presigned metadata URLs", body = TableMetadataUrlResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_metadata_url<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<GetTableMetadataUrlQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<TableMetadataUrlResponse> {
+        ApiServer::<C, A, S>::get_table_metadata_url(
+            warehouse_id.into(),
+            table_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get a table overview
+    ///
+    /// Returns a compact representation of a table for browsing: the current schema,
+    /// the default partition spec, the properties and the most recent snapshots.
+    /// Cheaper than loading the table, as the full table metadata is not assembled.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v2/warehouse/{warehouse_id}/table/{table_id}/overview",
+        params(GetTableOverviewQuery),
+        responses(
+            (status = 200, description = "Table overview", body = TableOverview),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_overview<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        Query(query): Query<GetTableOverviewQuery>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<TableOverview> {
+        ApiServer::<C, A, S>::get_table_overview(
+            warehouse_id.into(),
+            table_id.into(),
+            query,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get table statistics
+    ///
+    /// Returns aggregated scan and commit metrics reported by engines
+    /// via the Iceberg REST `metrics` endpoint.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/statistics",
+        responses(
+            (status = 200, description = "Table statistics", body = TableStatisticsResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_statistics<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<TableStatisticsResponse> {
+        ApiServer::<C, A, S>::get_table_statistics(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get views depending on a table
+    ///
+    /// Dependencies are determined on a best effort basis from the SQL of the views
+    /// and the `lakekeeper.view-dependencies` view property when a view is created or replaced.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/lineage",
+        responses(
+            (status = 200, description = "Views depending on the table", body = TableLineageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_lineage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<TableLineageResponse> {
+        ApiServer::<C, A, S>::get_table_lineage(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get tables a view depends on
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/view/{view_id}/lineage",
+        responses(
+            (status = 200, description = "Tables the view depends on", body = ViewLineageResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_view_lineage<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ViewLineageResponse> {
+        ApiServer::<C, A, S>::get_view_lineage(
+            warehouse_id.into(),
+            view_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Get whether the tables a view depends on changed since the view was last committed
+    ///
+    /// For each table, the snapshot of its `main` branch when the view was last created
+    /// or replaced is compared with its current snapshot. Schedulers can use this to
+    /// decide which downstream jobs need to run again.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v2/warehouse/{warehouse_id}/view/{view_id}/freshness",
+        responses(
+            (status = 200, description = "Freshness of the tables the view depends on", body = ViewFreshnessResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_view_freshness<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<ViewFreshnessResponse> {
+        ApiServer::<C, A, S>::get_view_freshness(
+            warehouse_id.into(),
+            view_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List previous properties of a namespace
+    ///
+    /// Each update that changes the properties of the namespace keeps the properties it replaced.
+    /// The number of kept versions is configured by `LAKEKEEPER__PROPERTY_HISTORY_LENGTH`.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/property-history",
+        responses(
+            (status = 200, description = "Previous properties of the namespace", body = PropertyHistoryResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_namespace_property_history<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<PropertyHistoryResponse> {
+        ApiServer::<C, A, S>::get_namespace_property_history(
+            warehouse_id.into(),
+            namespace_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Restore previous properties of a namespace
+    ///
+    /// Replaces the properties of the namespace with a version of its property history.
+    /// `location` and `lakekeeper.external-id` keep their current values.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/property-history/revert",
+        request_body = RevertPropertiesRequest,
+        responses(
+            (status = 200, description = "Properties of the namespace after the revert", body = RevertPropertiesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn revert_namespace_properties<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RevertPropertiesRequest>,
+    ) -> Result<RevertPropertiesResponse> {
+        ApiServer::<C, A, S>::revert_namespace_properties(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// List previous properties of a table
+    ///
+    /// Each commit that changes the properties of the table keeps the properties it replaced.
+    #[utoipa::path(
+        get,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/property-history",
+        responses(
+            (status = 200, description = "Previous properties of the table", body = PropertyHistoryResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn get_table_property_history<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+    ) -> Result<PropertyHistoryResponse> {
+        ApiServer::<C, A, S>::get_table_property_history(
+            warehouse_id.into(),
+            table_id.into(),
+            api_context,
+            metadata,
+        )
+        .await
+    }
+
+    /// Restore previous properties of a table
+    ///
+    /// Commits the properties of a version of the property history to the table,
+    /// which writes a new metadata file. Requires the `can_commit` permission on the table.
+    #[utoipa::path(
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/property-history/revert",
+        request_body = RevertPropertiesRequest,
+        responses(
+            (status = 200, description = "Properties of the table after the revert", body = RevertPropertiesResponse),
+            (status = "4XX", body = IcebergErrorResponse),
+        )
+    )]
+    async fn revert_table_properties<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
+        AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
+        Extension(metadata): Extension<RequestMetadata>,
+        Json(request): Json<RevertPropertiesRequest>,
+    ) -> Result<RevertPropertiesResponse> {
+        ApiServer::<C, A, S>::revert_table_properties(
+            warehouse_id.into(),
+            table_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
-    /// Update the Deletion Profile (soft-delete) of a warehouse.
+    /// Create multiple namespaces
+    ///
+    /// Creates a hierarchy of namespaces in one call. Parents of the listed namespaces are
+    /// created first if they do not exist. Namespaces are created one by one with the same
+    /// validation and permissions as `createNamespace`, and the response reports the result
+    /// of each namespace. Existing namespaces are reported as `exists` and left unchanged,
+    /// so the request can safely be repeated. At most 1000 namespaces can be listed.
     #[utoipa::path(
-            post,
-            tag = "warehouse",
-            path = "/management/v1/warehouse/{warehouse_id}/delete-profile",
-            request_body = UpdateWarehouseDeleteProfileRequest,
-            responses(
-                (status = 200, description = "Deletion Profile updated successfully"),
+        post,
+        tag = "warehouse",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/bulk-create",
+        request_body = BulkCreateNamespacesRequest,
+        responses(
+            (status = 200, description = "Result per namespace", body = BulkCreateNamespacesResponse),
             (status = "4XX", body = IcebergErrorResponse),
-            )
-        )]
-    async fn update_warehouse_delete_profile<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        )
+    )]
+    async fn bulk_create_namespaces<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UpdateWarehouseDeleteProfileRequest>,
-    ) -> Result<()> {
-        ApiServer::<C, A, S>::update_warehouse_delete_profile(
+        Json(request): Json<BulkCreateNamespacesRequest>,
+    ) -> Result<BulkCreateNamespacesResponse> {
+        ApiServer::<C, A, S>::bulk_create_namespaces(
             warehouse_id.into(),
             request,
             api_context,
@@ -745,136 +2415,198 @@ pub mod v1 {
         .await
     }
 
-    /// Deactivate a warehouse
+    /// Rename a namespace
+    ///
+    /// Replaces the last part of the namespace name. Child namespaces, tables and views
+    /// move along with the namespace in a single transaction, so they do not have to be
+    /// renamed individually. The namespace stays in its parent, use the move endpoint to
+    /// place it below a different parent.
+    ///
+    /// Requires the `can_rename` permission on the namespace and permission to create
+    /// namespaces in its parent. Views whose SQL references the old name are not updated.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/deactivate",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/rename",
+        request_body = RenameNamespaceRequest,
         responses(
-            (status = 200, description = "Warehouse deactivated successfully"),
+            (status = 204, description = "Namespace renamed successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn deactivate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(warehouse_id): Path<uuid::Uuid>,
+    async fn rename_namespace<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<()> {
-        ApiServer::<C, A, S>::deactivate_warehouse(warehouse_id.into(), api_context, metadata).await
+        Json(request): Json<RenameNamespaceRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::rename_namespace(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
     }
 
-    /// Activate a warehouse
+    /// Move a namespace
+    ///
+    /// Moves a namespace with all of its child namespaces, tables and views below a different
+    /// parent namespace, or to the top level of the warehouse if `new-parent` is not set.
+    /// The move is a single transaction and fails with a conflict if the new parent already
+    /// contains a namespace with the same name.
+    ///
+    /// Permissions granted on the previous parent are no longer inherited, permissions of the
+    /// new parent apply instead. Requires the `can_rename` and `can_delete` permissions on the
+    /// namespace and permission to create namespaces in the new parent.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/activate",
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/move",
+        request_body = MoveNamespaceRequest,
         responses(
-            (status = 200, description = "Warehouse activated successfully"),
+            (status = 204, description = "Namespace moved successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn activate_warehouse<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(warehouse_id): Path<uuid::Uuid>,
+    async fn move_namespace<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<()> {
-        ApiServer::<C, A, S>::activate_warehouse(warehouse_id.into(), api_context, metadata).await
+        Json(request): Json<MoveNamespaceRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::move_namespace(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
     }
 
-    /// Update the storage profile of a warehouse including its storage credential.
+    /// Set the description of a namespace
+    ///
+    /// Descriptions document namespaces independently of the properties managed by
+    /// engines. They are returned when listing namespaces with `returnDescriptions`.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/storage",
-        request_body = UpdateWarehouseStorageRequest,
+        path = "/management/v1/warehouse/{warehouse_id}/namespace/{namespace_id}/description",
+        request_body = SetDescriptionRequest,
         responses(
-            (status = 200, description = "Storage profile updated successfully"),
+            (status = 204, description = "Description set successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn update_storage_profile<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(warehouse_id): Path<uuid::Uuid>,
+    async fn set_namespace_description<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, namespace_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UpdateWarehouseStorageRequest>,
-    ) -> Result<()> {
-        ApiServer::<C, A, S>::update_storage(warehouse_id.into(), request, api_context, metadata)
-            .await
+        Json(request): Json<SetDescriptionRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_namespace_description(
+            warehouse_id.into(),
+            namespace_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
     }
 
-    /// Update the storage credential of a warehouse. The storage profile is not modified.
-    /// This can be used to update credentials before expiration.
+    /// Set the description of a table
+    ///
+    /// Unlike the `comment` table property, the description is maintained by Lakekeeper only.
+    /// It is returned when listing tables with `returnDescriptions`.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/storage-credential",
-        request_body = UpdateWarehouseCredentialRequest,
+        path = "/management/v1/warehouse/{warehouse_id}/table/{table_id}/description",
+        request_body = SetDescriptionRequest,
         responses(
-            (status = 200, description = "Storage credential updated successfully"),
+            (status = 204, description = "Description set successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn update_storage_credential<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(warehouse_id): Path<uuid::Uuid>,
+    async fn set_table_description<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, table_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UpdateWarehouseCredentialRequest>,
-    ) -> Result<()> {
-        ApiServer::<C, A, S>::update_storage_credential(
+        Json(request): Json<SetDescriptionRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_table_description(
             warehouse_id.into(),
+            table_id.into(),
             request,
             api_context,
             metadata,
         )
-        .await
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
     }
 
-    /// List soft-deleted tabulars
+    /// Set the description of a view
     ///
-    /// List all soft-deleted tabulars in the warehouse that are visible to you.
+    /// The description is returned when listing views with `returnDescriptions`.
     #[utoipa::path(
-        get,
+        post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/deleted-tabulars",
-        params(ListDeletedTabularsQuery),
+        path = "/management/v1/warehouse/{warehouse_id}/view/{view_id}/description",
+        request_body = SetDescriptionRequest,
         responses(
-            (status = 200, description = "List of soft-deleted tabulars", body = ListDeletedTabularsResponse),
+            (status = 204, description = "Description set successfully"),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn list_deleted_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(warehouse_id): Path<uuid::Uuid>,
-        Query(query): Query<ListDeletedTabularsQuery>,
+    async fn set_view_description<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path((warehouse_id, view_id)): Path<(uuid::Uuid, uuid::Uuid)>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-    ) -> Result<Json<ListDeletedTabularsResponse>> {
-        ApiServer::<C, A, S>::list_soft_deleted_tabulars(
+        Json(request): Json<SetDescriptionRequest>,
+    ) -> Result<StatusCode> {
+        ApiServer::<C, A, S>::set_view_description(
             warehouse_id.into(),
-            query,
+            view_id.into(),
+            request,
             api_context,
             metadata,
         )
-        .await
-        .map(Json)
+        .await?;
+        Ok(StatusCode::NO_CONTENT)
     }
 
+    /// Search descriptions (Fuzzy)
+    ///
+    /// Search the descriptions of namespaces, tables and views in the warehouse
+    /// that are visible to you.
     #[utoipa::path(
         post,
         tag = "warehouse",
-        path = "/management/v1/warehouse/{warehouse_id}/deleted_tabulars/undrop",
+        path = "/management/v1/warehouse/{warehouse_id}/search/description",
+        request_body = SearchDescriptionsRequest,
         responses(
-            (status = 204, description = "Tabular undropped successfully"),
+            (status = 200, description = "Matching namespaces, tables and views", body = SearchDescriptionsResponse),
             (status = "4XX", body = IcebergErrorResponse),
         )
     )]
-    async fn undrop_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
-        Path(_warehouse_id): Path<uuid::Uuid>,
+    async fn search_descriptions<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+        Path(warehouse_id): Path<uuid::Uuid>,
         AxumState(api_context): AxumState<ApiContext<State<A, C, S>>>,
         Extension(metadata): Extension<RequestMetadata>,
-        Json(request): Json<UndropTabularsRequest>,
-    ) -> Result<StatusCode> {
-        ApiServer::<C, A, S>::undrop_tabulars(metadata, request, api_context).await?;
-        Ok(StatusCode::NO_CONTENT)
+        Json(request): Json<SearchDescriptionsRequest>,
+    ) -> Result<SearchDescriptionsResponse> {
+        ApiServer::<C, A, S>::search_descriptions(
+            warehouse_id.into(),
+            request,
+            api_context,
+            metadata,
+        )
+        .await
     }
 
     #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -904,6 +2636,34 @@ pub mod v1 {
         pub deleted_at: chrono::DateTime<chrono::Utc>,
         /// Date when the tabular will not be recoverable anymore
         pub expiration_date: chrono::DateTime<chrono::Utc>,
+        /// Whether the tabular is exempt from expiration, for example for a legal hold
+        pub expiration_exempt: bool,
+    }
+
+    #[derive(Debug, Serialize, utoipa::ToSchema)]
+    pub struct LocationUsageResponse {
+        /// Tabulars whose location overlaps with the requested prefix
+        pub tabulars: Vec<LocationUsageTabular>,
+        /// Token to fetch the next page
+        pub next_page_token: Option<String>,
+    }
+
+    #[derive(Debug, Serialize, utoipa::ToSchema)]
+    pub struct LocationUsageTabular {
+        /// Unique identifier of the tabular
+        pub id: uuid::Uuid,
+        /// Name of the tabular
+        pub name: String,
+        /// List of namespace parts the tabular belongs to
+        pub namespace: Vec<String>,
+        /// Type of the tabular
+        pub typ: TabularType,
+        /// Location of the tabular
+        pub location: String,
+        /// Whether the tabular is staged, i.e. has no metadata yet
+        pub staged: bool,
+        /// Date when the tabular was deleted, if it is soft-deleted
+        pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
     }
 
     impl From<TabularIdentUuid> for TabularType {
@@ -939,17 +2699,109 @@ pub mod v1 {
 
     impl<C: Catalog, A: Authorizer, S: SecretStore> ApiServer<C, A, S> {
         pub fn new_v1_router(authorizer: &A) -> Router<ApiContext<State<A, C, S>>> {
+            Self::new_router(ManagementApiVersion::V1, authorizer)
+        }
+
+        /// Serves all routes of `v1` except the deprecated ones.
+        /// New endpoints are added to `v2` only.
+        pub fn new_v2_router(authorizer: &A) -> Router<ApiContext<State<A, C, S>>> {
+            Self::new_router(ManagementApiVersion::V2, authorizer)
+        }
+
+        fn new_router(
+            version: ManagementApiVersion,
+            authorizer: &A,
+        ) -> Router<ApiContext<State<A, C, S>>> {
+            let router = match version {
+                ManagementApiVersion::V1 => Self::v1_routes().merge(Self::deprecated_v1_routes()),
+                ManagementApiVersion::V2 => Self::v1_routes().merge(Self::v2_routes()),
+            };
+            router.merge(authorizer.new_router())
+        }
+
+        /// Routes listed in `versioning::DEPRECATED_ROUTES`, which are not part of `v2`.
+        fn deprecated_v1_routes() -> Router<ApiContext<State<A, C, S>>> {
+            Router::new()
+                .route(
+                    "/project",
+                    deprecated("get_default_project", get(get_default_project)),
+                )
+                .route(
+                    "/project",
+                    deprecated("delete_default_project", delete(delete_default_project)),
+                )
+                .route(
+                    "/project/rename",
+                    deprecated("rename_default_project", post(rename_default_project)),
+                )
+        }
+
+        /// Endpoints added after `v1` was frozen, only served by `v2`.
+        /// Their `OpenAPI` paths start with `/management/v2`.
+        fn v2_routes() -> Router<ApiContext<State<A, C, S>>> {
+            Router::new()
+                .route(
+                    "/warehouse/{warehouse_id}/view/{view_id}/freshness",
+                    get(get_view_freshness),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/inventory-export",
+                    post(export_table_inventory),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/inventory-export/{export_id}",
+                    get(get_table_inventory_export),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/check",
+                    get(check_table),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/overview",
+                    get(get_table_overview),
+                )
+        }
+
+        /// The frozen route set of `v1`, which `v2` serves as well.
+        /// New endpoints belong in [`Self::v2_routes`].
+        #[allow(clippy::too_many_lines)]
+        fn v1_routes() -> Router<ApiContext<State<A, C, S>>> {
             Router::new()
                 // Server
                 .route("/info", get(get_server_info))
                 .route("/bootstrap", post(bootstrap))
+                .route("/notices", get(list_notices).post(create_notice))
+                .route("/notices/{notice_id}", delete(delete_notice))
                 // Role management
                 .route("/role", get(list_roles).post(create_role))
                 .route(
                     "/role/{id}",
                     get(get_role).post(update_role).delete(delete_role),
                 )
+                .route(
+                    "/role/{id}/default-warehouse",
+                    get(get_role_default_warehouse).put(set_role_default_warehouse),
+                )
+                .route("/role/{id}/members", get(list_role_members))
+                .route(
+                    "/role/{id}/members/{member_id}",
+                    put(add_role_member).delete(remove_role_member),
+                )
                 .route("/search/role", post(search_role))
+                // Domain management
+                .route("/domain", get(list_domains).post(create_domain))
+                .route(
+                    "/domain/{domain_id}",
+                    get(get_domain).post(update_domain).delete(delete_domain),
+                )
+                .route(
+                    "/domain/{domain_id}/namespaces",
+                    get(list_domain_namespaces),
+                )
+                .route(
+                    "/domain/{domain_id}/namespaces/{namespace_id}",
+                    put(add_domain_namespace).delete(remove_domain_namespace),
+                )
                 // User management
                 .route("/whoami", get(whoami))
                 .route("/search/user", post(search_user))
@@ -957,15 +2809,14 @@ pub mod v1 {
                     "/user/{user_id}",
                     get(get_user).put(update_user).delete(delete_user),
                 )
-                .route("/user", get(list_user).post(create_user))
-                // Create a new project
                 .route(
-                    "/project",
-                    post(create_project)
-                        .get(get_default_project)
-                        .delete(delete_default_project),
+                    "/user/{user_id}/default-warehouse",
+                    get(get_user_default_warehouse).put(set_user_default_warehouse),
                 )
-                .route("/project/rename", post(rename_default_project))
+                .route("/user/{user_id}/reactivate", post(reactivate_user))
+                .route("/user", get(list_user).post(create_user))
+                // Create a new project
+                .route("/project", post(create_project))
                 .route(
                     "/project/{project_id}",
                     get(get_project_by_id).delete(delete_project_by_id),
@@ -973,6 +2824,7 @@ pub mod v1 {
                 .route("/project/{project_id}/rename", post(rename_project_by_id))
                 // Create a new warehouse
                 .route("/warehouse", post(create_warehouse))
+                .route("/warehouse/validate-storage", post(validate_storage))
                 // List all projects
                 .route("/project-list", get(list_projects))
                 .route(
@@ -1007,19 +2859,154 @@ pub mod v1 {
                     "/warehouse/{warehouse_id}/storage-credential",
                     post(update_storage_credential),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/storage-credential/relink",
+                    post(relink_storage_credential),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/validate-storage",
+                    post(validate_warehouse_storage),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/statistics",
+                    get(get_warehouse_statistics),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/storage-usage",
+                    get(get_storage_usage),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/storage-usage/tables",
+                    get(get_table_storage_usage),
+                )
                 .route(
                     "/warehouse/{warehouse_id}/deleted-tabulars",
                     get(list_deleted_tabulars),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/deleted-tabulars/reschedule",
+                    post(reschedule_expirations),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/deleted-tabulars/{tabular_id}/expiration-exemption",
+                    post(set_expiration_exemption),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/location-usage",
+                    get(list_location_usage),
+                )
                 .route(
                     "/warehouse/{warehouse_id}/deleted_tabulars/undrop",
                     post(undrop_tabulars),
                 )
+                .route(
+                    "/warehouse/{warehouse_id}/task/purge/awaiting-approval",
+                    get(list_purges_awaiting_approval),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/task/purge/{task_id}",
+                    get(get_purge_task),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/task/purge/{task_id}/approve",
+                    post(approve_purge),
+                )
                 .route(
                     "/warehouse/{warehouse_id}/delete-profile",
                     post(update_warehouse_delete_profile),
                 )
-                .merge(authorizer.new_router())
+                .route(
+                    "/warehouse/{warehouse_id}/namespace-defaults",
+                    post(update_warehouse_namespace_defaults),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/network-policy",
+                    post(update_warehouse_network_policy),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table-limits",
+                    post(update_warehouse_table_limits),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/snapshot-summary-enrichment",
+                    post(update_warehouse_snapshot_summary_enrichment),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/wasm-hooks",
+                    post(update_warehouse_wasm_hooks),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/relocate",
+                    post(relocate_table),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/rollback",
+                    post(rollback_table),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/statistics",
+                    get(get_table_statistics),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/metadata-as-of",
+                    get(get_table_metadata_as_of),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/metadata-url",
+                    get(get_table_metadata_url),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/description",
+                    post(set_table_description),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/lineage",
+                    get(get_table_lineage),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/property-history",
+                    get(get_table_property_history),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/table/{table_id}/property-history/revert",
+                    post(revert_table_properties),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/view/{view_id}/lineage",
+                    get(get_view_lineage),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/view/{view_id}/description",
+                    post(set_view_description),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/bulk-create",
+                    post(bulk_create_namespaces),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/description",
+                    post(set_namespace_description),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/move",
+                    post(move_namespace),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/property-history",
+                    get(get_namespace_property_history),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/property-history/revert",
+                    post(revert_namespace_properties),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/namespace/{namespace_id}/rename",
+                    post(rename_namespace),
+                )
+                .route(
+                    "/warehouse/{warehouse_id}/search/description",
+                    post(search_descriptions),
+                )
         }
     }
 }
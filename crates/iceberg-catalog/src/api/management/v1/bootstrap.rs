@@ -11,12 +11,16 @@ use serde::{Deserialize, Serialize};
 
 use super::user::{UserLastUpdatedWith, UserType};
 
-#[derive(Debug, Deserialize, Serialize, utoipa::ToSchema)]
-#[serde(rename_all = "kebab-case")]
-pub enum AuthZBackend {
-    AllowAll,
-    #[serde(rename = "openfga")]
-    OpenFGA,
+pub use iceberg_ext::management::AuthZBackend;
+
+impl From<&config::AuthZBackend> for AuthZBackend {
+    fn from(backend: &config::AuthZBackend) -> Self {
+        match backend {
+            config::AuthZBackend::AllowAll => AuthZBackend::AllowAll,
+            config::AuthZBackend::OpenFGA => AuthZBackend::OpenFGA,
+            config::AuthZBackend::Webhook => AuthZBackend::Webhook,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
@@ -57,6 +61,40 @@ pub struct ServerInfo {
     pub default_project_id: Option<ProjectIdent>,
     /// `AuthZ` backend in use.
     pub authz_backend: AuthZBackend,
+    /// Git commit the server was built from. Null if it was not provided at build time.
+    pub git_sha: Option<String>,
+    /// Optional features the server was compiled with, for example `nats`.
+    pub features: Vec<String>,
+    /// Backend storing the catalog, for example `postgres`.
+    pub catalog_backend: String,
+    /// Task queues processed by this replica of the server.
+    pub task_queues: Vec<String>,
+}
+
+/// Optional cargo features of this crate that are enabled in the build.
+fn enabled_features() -> Vec<String> {
+    [
+        ("sqlx-postgres", cfg!(feature = "sqlx-postgres")),
+        ("s3-signer", cfg!(feature = "s3-signer")),
+        ("router", cfg!(feature = "router")),
+        ("nats", cfg!(feature = "nats")),
+    ]
+    .into_iter()
+    .filter_map(|(feature, enabled)| enabled.then(|| feature.to_string()))
+    .collect()
+}
+
+/// Anonymous requests are only served if authentication is disabled.
+fn require_authenticated(actor: &Actor, authn_enabled: bool) -> Result<()> {
+    match actor {
+        Actor::Anonymous if authn_enabled => {
+            Err(
+                ErrorModel::unauthorized("Authentication required", "AuthenticationRequired", None)
+                    .into(),
+            )
+        }
+        Actor::Anonymous | Actor::Principal(_) | Actor::Role { .. } => Ok(()),
+    }
 }
 
 impl<C: Catalog, A: Authorizer, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
@@ -162,23 +200,20 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         state: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<ServerInfo> {
-        let actor = request_metadata.auth_details.actor();
-        match actor {
-            Actor::Anonymous => {
-                if CONFIG.authn_enabled() {
-                    return Err(ErrorModel::unauthorized(
-                        "Authentication required",
-                        "AuthenticationRequired",
-                        None,
-                    )
-                    .into());
-                }
-            }
-            Actor::Principal(_) | Actor::Role { .. } => (),
-        }
+        require_authenticated(
+            request_metadata.auth_details.actor(),
+            CONFIG.authn_enabled(),
+        )?;
 
         // ------------------- Business Logic -------------------
         let version = env!("CARGO_PKG_VERSION").to_string();
+        let task_queues = state
+            .v1_state
+            .queues
+            .queue_names()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect();
         let server_data = C::get_server_info(state.v1_state.catalog).await?;
 
         Ok(ServerInfo {
@@ -186,10 +221,71 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             bootstrapped: server_data != StartupValidationData::NotBootstrapped,
             server_id: CONFIG.server_id,
             default_project_id: *DEFAULT_PROJECT_ID,
-            authz_backend: match CONFIG.authz_backend {
-                config::AuthZBackend::AllowAll => AuthZBackend::AllowAll,
-                config::AuthZBackend::OpenFGA => AuthZBackend::OpenFGA,
-            },
+            authz_backend: AuthZBackend::from(&CONFIG.authz_backend),
+            git_sha: option_env!("LAKEKEEPER_GIT_SHA")
+                .filter(|sha| !sha.is_empty())
+                .map(ToString::to_string),
+            features: enabled_features(),
+            catalog_backend: C::backend_name().to_string(),
+            task_queues,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::test::{get_api_context, random_request_metadata, setup};
+    use crate::service::authz::AllowAllAuthorizer;
+    use crate::service::UserId;
+
+    #[test]
+    fn test_require_authenticated() {
+        let principal = Actor::Principal(UserId::OIDC("test-user-id".to_string()));
+        let err = require_authenticated(&Actor::Anonymous, true).unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::UNAUTHORIZED);
+        require_authenticated(&Actor::Anonymous, false).unwrap();
+        require_authenticated(&principal, true).unwrap();
+        require_authenticated(&principal, false).unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_server_info(pool: sqlx::PgPool) {
+        let ctx = get_api_context(pool.clone(), AllowAllAuthorizer);
+        let info = ApiServer::server_info(ctx, random_request_metadata())
+            .await
+            .unwrap();
+        assert!(!info.bootstrapped);
+
+        let (ctx, _) = setup(
+            pool,
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            crate::api::management::v1::warehouse::TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let info = ApiServer::server_info(
+            ctx.clone(),
+            RequestMetadata::random_human(UserId::OIDC("test-user-id".to_string())),
+        )
+        .await
+        .unwrap();
+        assert!(info.bootstrapped);
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(info.server_id, CONFIG.server_id);
+        assert_eq!(info.default_project_id, *DEFAULT_PROJECT_ID);
+        assert_eq!(
+            info.authz_backend,
+            AuthZBackend::from(&CONFIG.authz_backend)
+        );
+        assert_eq!(info.catalog_backend, "postgres");
+        assert_eq!(
+            info.features.contains(&"sqlx-postgres".to_string()),
+            cfg!(feature = "sqlx-postgres")
+        );
+        assert_eq!(info.task_queues, ctx.v1_state.queues.queue_names());
+        assert!(info.task_queues.contains(&"tabular_expiration".to_string()));
+    }
+}
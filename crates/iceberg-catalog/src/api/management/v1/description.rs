@@ -0,0 +1,310 @@
+use crate::api::management::v1::ApiServer;
+use crate::api::ApiContext;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogNamespaceAction, CatalogTableAction, CatalogViewAction,
+    CatalogWarehouseAction,
+};
+use crate::service::{
+    Catalog, CatalogSearchMatch, ListFlags, NamespaceIdentUuid, Result, SecretStore, State,
+    TableIdentUuid, TabularIdentUuid, Transaction, ViewIdentUuid,
+};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+/// Maximum length of a description in characters.
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+/// Maximum number of matches considered by a description search, before authorization.
+const SEARCH_LIMIT: i64 = 50;
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetDescriptionRequest {
+    /// Description of the namespace, table or view. At most 4096 characters.
+    /// If not set, the description is removed.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchDescriptionsRequest {
+    /// Search string for fuzzy search of words in descriptions.
+    /// Length is truncated to 64 characters.
+    pub search: String,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SearchDescriptionsResponse {
+    /// Namespaces, tables and views visible to you, best matches first.
+    pub matches: Vec<DescriptionMatch>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct DescriptionMatch {
+    /// Type of the matching entity
+    pub typ: DescribedEntityType,
+    /// ID of the namespace, table or view
+    pub id: uuid::Uuid,
+    /// Namespace, or namespace the table or view belongs to
+    pub namespace: Vec<String>,
+    /// Name of the table or view. Not set for namespaces.
+    pub name: Option<String>,
+    /// Description of the namespace, table or view
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum DescribedEntityType {
+    Namespace,
+    Table,
+    View,
+}
+
+impl axum::response::IntoResponse for SearchDescriptionsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn set_namespace_description(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        request: SetDescriptionRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        let description = validate_description(request.description)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanUpdateProperties,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_namespace_description(
+            warehouse_id,
+            namespace_id,
+            description.as_deref(),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await
+    }
+
+    async fn set_table_description(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: SetDescriptionRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        let description = validate_description(request.description)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(&request_metadata, table, &CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_tabular_description(
+            warehouse_id,
+            TabularIdentUuid::from(table_id),
+            description.as_deref(),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await
+    }
+
+    async fn set_view_description(
+        warehouse_id: WarehouseIdent,
+        view_id: ViewIdentUuid,
+        request: SetDescriptionRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        let description = validate_description(request.description)?;
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_view_action(
+                &request_metadata,
+                Ok(Some(view_id)),
+                &CatalogViewAction::CanCommit,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_tabular_description(
+            warehouse_id,
+            TabularIdentUuid::from(view_id),
+            description.as_deref(),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await
+    }
+
+    async fn search_descriptions(
+        warehouse_id: WarehouseIdent,
+        request: SearchDescriptionsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<SearchDescriptionsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let search = request.search.chars().take(64).collect::<String>();
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let candidates =
+            C::search_descriptions(warehouse_id, &search, SEARCH_LIMIT, t.transaction()).await?;
+        t.commit().await?;
+
+        let allowed = futures::future::try_join_all(candidates.iter().map(|m| match m {
+            CatalogSearchMatch::Namespace { id, .. } => authorizer.is_allowed_namespace_action(
+                &request_metadata,
+                *id,
+                &CatalogNamespaceAction::CanGetMetadata,
+            ),
+            CatalogSearchMatch::Tabular {
+                id: TabularIdentUuid::Table(id),
+                ..
+            } => authorizer.is_allowed_table_action(
+                &request_metadata,
+                (*id).into(),
+                &CatalogTableAction::CanIncludeInList,
+            ),
+            CatalogSearchMatch::Tabular {
+                id: TabularIdentUuid::View(id),
+                ..
+            } => authorizer.is_allowed_view_action(
+                &request_metadata,
+                (*id).into(),
+                &CatalogViewAction::CanIncludeInList,
+            ),
+        }))
+        .await?;
+
+        let matches = candidates
+            .into_iter()
+            .zip(allowed)
+            .filter_map(|(m, allowed)| allowed.then_some(m))
+            .map(|m| match m {
+                CatalogSearchMatch::Namespace {
+                    id,
+                    namespace,
+                    description,
+                } => DescriptionMatch {
+                    typ: DescribedEntityType::Namespace,
+                    id: *id,
+                    namespace: namespace.inner(),
+                    name: None,
+                    description,
+                },
+                CatalogSearchMatch::Tabular {
+                    id,
+                    ident,
+                    description,
+                } => DescriptionMatch {
+                    typ: match id {
+                        TabularIdentUuid::Table(_) => DescribedEntityType::Table,
+                        TabularIdentUuid::View(_) => DescribedEntityType::View,
+                    },
+                    id: *id,
+                    namespace: ident.namespace.inner(),
+                    name: Some(ident.name),
+                    description,
+                },
+            })
+            .collect();
+
+        Ok(SearchDescriptionsResponse { matches })
+    }
+}
+
+/// Empty descriptions are treated as removal of the description.
+fn validate_description(description: Option<String>) -> Result<Option<String>> {
+    let description = description.filter(|d| !d.is_empty());
+    if let Some(description) = &description {
+        if description.chars().count() > MAX_DESCRIPTION_LENGTH {
+            return Err(ErrorModel::bad_request(
+                format!("Description must not be longer than {MAX_DESCRIPTION_LENGTH} characters"),
+                "DescriptionTooLong",
+                None,
+            )
+            .into());
+        }
+    }
+    Ok(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_description() {
+        assert_eq!(validate_description(None).unwrap(), None);
+        assert_eq!(validate_description(Some(String::new())).unwrap(), None);
+        assert_eq!(
+            validate_description(Some("Orders of the web shop".to_string())).unwrap(),
+            Some("Orders of the web shop".to_string())
+        );
+        assert!(validate_description(Some("a".repeat(MAX_DESCRIPTION_LENGTH + 1))).is_err());
+    }
+}
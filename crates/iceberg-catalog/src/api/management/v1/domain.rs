@@ -0,0 +1,461 @@
+use super::role::require_project_id;
+use crate::api::management::v1::ApiServer;
+use crate::api::ApiContext;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogDomainAction, CatalogNamespaceAction, CatalogProjectAction,
+};
+use crate::service::{
+    Catalog, DomainId, NamespaceIdentUuid, Result, SecretStore, State, Transaction,
+};
+use crate::{ProjectIdent, WarehouseIdent};
+use axum::response::IntoResponse;
+use axum::Json;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of tags of a domain.
+const MAX_TAGS: usize = 32;
+/// Maximum length of a single tag in characters.
+const MAX_TAG_LENGTH: usize = 64;
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateDomainRequest {
+    /// Name of the domain to create. Must be unique within the project.
+    pub name: String,
+    /// Description of the domain
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Tags of the domain, for example the business unit it belongs to.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Project ID in which the domain is created.
+    /// Only required if the project ID cannot be inferred and no default project is set.
+    #[serde(default)]
+    #[schema(value_type=uuid::Uuid)]
+    pub project_id: Option<ProjectIdent>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateDomainRequest {
+    /// Name of the domain
+    pub name: String,
+    /// Description of the domain. If not set, the description will be removed.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Tags of the domain. Replaces all existing tags.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct Domain {
+    /// Globally unique id of this domain
+    #[schema(value_type=uuid::Uuid)]
+    pub id: DomainId,
+    /// Name of the domain
+    pub name: String,
+    /// Description of the domain
+    pub description: Option<String>,
+    /// Tags of the domain
+    pub tags: Vec<String>,
+    /// Project ID in which the domain is created.
+    #[schema(value_type=uuid::Uuid)]
+    pub project_id: ProjectIdent,
+    /// Timestamp when the domain was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Timestamp when the domain was last updated
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl IntoResponse for Domain {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDomainsQuery {
+    /// Only return domains with this tag
+    #[serde(default)]
+    pub tag: Option<String>,
+    /// Project ID from which domains should be listed
+    /// Only required if the project ID cannot be inferred from the
+    /// users token and no default project is set.
+    #[serde(default)]
+    #[param(value_type=uuid::Uuid)]
+    pub project_id: Option<ProjectIdent>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListDomainsResponse {
+    /// Domains visible to you, ordered by name.
+    pub domains: Vec<Domain>,
+}
+
+impl IntoResponse for ListDomainsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct DomainNamespace {
+    /// Warehouse the namespace belongs to
+    #[schema(value_type=uuid::Uuid)]
+    pub warehouse_id: WarehouseIdent,
+    /// Name of the warehouse the namespace belongs to
+    pub warehouse_name: String,
+    /// ID of the namespace
+    #[schema(value_type=uuid::Uuid)]
+    pub namespace_id: NamespaceIdentUuid,
+    /// Name of the namespace
+    pub namespace: Vec<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListDomainNamespacesResponse {
+    /// Namespaces of the domain that are visible to you,
+    /// ordered by warehouse and namespace name.
+    pub namespaces: Vec<DomainNamespace>,
+}
+
+impl IntoResponse for ListDomainNamespacesResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn create_domain(
+        request: CreateDomainRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<Domain> {
+        // -------------------- VALIDATIONS --------------------
+        validate_domain_name(&request.name)?;
+        let tags = normalize_tags(request.tags)?;
+        let project_id = require_project_id(request.project_id, &request_metadata)?;
+
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                project_id,
+                &CatalogProjectAction::CanCreateDomain,
+            )
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let description = request.description.filter(|d| !d.is_empty());
+        let domain_id = DomainId::default();
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let domain = C::create_domain(
+            domain_id,
+            project_id,
+            &request.name,
+            description.as_deref(),
+            &tags,
+            t.transaction(),
+        )
+        .await?;
+        authorizer
+            .create_domain(&request_metadata, domain_id, project_id)
+            .await?;
+        t.commit().await?;
+        Ok(domain)
+    }
+
+    async fn list_domains(
+        context: ApiContext<State<A, C, S>>,
+        query: ListDomainsQuery,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListDomainsResponse> {
+        // -------------------- VALIDATIONS --------------------
+        let project_id = require_project_id(query.project_id, &request_metadata)?;
+
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                project_id,
+                &CatalogProjectAction::CanListDomains,
+            )
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let domains =
+            C::list_domains(project_id, query.tag.as_deref(), context.v1_state.catalog).await?;
+
+        let domains = futures::future::try_join_all(domains.iter().map(|d| {
+            authorizer.is_allowed_domain_action(
+                &request_metadata,
+                d.id,
+                &CatalogDomainAction::CanRead,
+            )
+        }))
+        .await?
+        .into_iter()
+        .zip(domains)
+        .filter_map(|(allowed, domain)| allowed.then_some(domain))
+        .collect();
+
+        Ok(ListDomainsResponse { domains })
+    }
+
+    async fn get_domain(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        domain_id: DomainId,
+    ) -> Result<Domain> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_domain_action(&request_metadata, domain_id, &CatalogDomainAction::CanRead)
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        C::get_domain(domain_id, context.v1_state.catalog)
+            .await?
+            .ok_or_else(|| domain_not_found(domain_id).into())
+    }
+
+    async fn update_domain(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        domain_id: DomainId,
+        request: UpdateDomainRequest,
+    ) -> Result<Domain> {
+        // -------------------- VALIDATIONS --------------------
+        validate_domain_name(&request.name)?;
+        let tags = normalize_tags(request.tags)?;
+
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_domain_action(
+                &request_metadata,
+                domain_id,
+                &CatalogDomainAction::CanUpdate,
+            )
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let description = request.description.filter(|d| !d.is_empty());
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let domain = C::update_domain(
+            domain_id,
+            &request.name,
+            description.as_deref(),
+            &tags,
+            t.transaction(),
+        )
+        .await?;
+        if let Some(domain) = domain {
+            t.commit().await?;
+            Ok(domain)
+        } else {
+            t.rollback().await?;
+            Err(domain_not_found(domain_id).into())
+        }
+    }
+
+    async fn delete_domain(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        domain_id: DomainId,
+    ) -> Result<()> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_domain_action(
+                &request_metadata,
+                domain_id,
+                &CatalogDomainAction::CanDelete,
+            )
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let deleted = C::delete_domain(domain_id, t.transaction()).await?;
+        if deleted.is_none() {
+            return Err(domain_not_found(domain_id).into());
+        }
+        authorizer
+            .delete_domain(&request_metadata, domain_id)
+            .await?;
+        t.commit().await
+    }
+
+    async fn list_domain_namespaces(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        domain_id: DomainId,
+    ) -> Result<ListDomainNamespacesResponse> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_domain_action(&request_metadata, domain_id, &CatalogDomainAction::CanRead)
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let namespaces = C::list_domain_namespaces(domain_id, context.v1_state.catalog).await?;
+
+        // Membership in a domain does not grant access to the namespace itself
+        let namespaces = futures::future::try_join_all(namespaces.iter().map(|n| {
+            authorizer.is_allowed_namespace_action(
+                &request_metadata,
+                n.namespace_id,
+                &CatalogNamespaceAction::CanGetMetadata,
+            )
+        }))
+        .await?
+        .into_iter()
+        .zip(namespaces)
+        .filter_map(|(allowed, namespace)| allowed.then_some(namespace))
+        .collect();
+
+        Ok(ListDomainNamespacesResponse { namespaces })
+    }
+
+    async fn add_domain_namespace(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        domain_id: DomainId,
+        namespace_id: NamespaceIdentUuid,
+    ) -> Result<()> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        let (manage_domain, update_namespace) = futures::join!(
+            authorizer.require_domain_action(
+                &request_metadata,
+                domain_id,
+                &CatalogDomainAction::CanManageNamespaces
+            ),
+            authorizer.require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanUpdateProperties
+            )
+        );
+        manage_domain?;
+        update_namespace?;
+
+        // -------------------- Business Logic --------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::add_domain_namespace(domain_id, namespace_id, t.transaction()).await?;
+        t.commit().await
+    }
+
+    async fn remove_domain_namespace(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        domain_id: DomainId,
+        namespace_id: NamespaceIdentUuid,
+    ) -> Result<()> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_domain_action(
+                &request_metadata,
+                domain_id,
+                &CatalogDomainAction::CanManageNamespaces,
+            )
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let removed = C::remove_domain_namespace(domain_id, namespace_id, t.transaction()).await?;
+        if removed.is_none() {
+            return Err(ErrorModel::not_found(
+                format!("Namespace {namespace_id} is not part of domain {domain_id}."),
+                "DomainNamespaceNotFound",
+                None,
+            )
+            .into());
+        }
+        t.commit().await
+    }
+}
+
+fn domain_not_found(domain_id: DomainId) -> ErrorModel {
+    ErrorModel::not_found(
+        format!("Domain with id {domain_id} not found."),
+        "DomainNotFound",
+        None,
+    )
+}
+
+fn validate_domain_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(ErrorModel::bad_request(
+            "Domain name cannot be empty".to_string(),
+            "EmptyDomainName",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Trims tags and removes duplicates, keeping the first occurrence.
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim();
+        if tag.is_empty() || tag.chars().count() > MAX_TAG_LENGTH {
+            return Err(ErrorModel::bad_request(
+                format!("Domain tags must be between 1 and {MAX_TAG_LENGTH} characters long"),
+                "InvalidDomainTag",
+                None,
+            )
+            .into());
+        }
+        if !normalized.iter().any(|t| t == tag) {
+            normalized.push(tag.to_string());
+        }
+    }
+
+    if normalized.len() > MAX_TAGS {
+        return Err(ErrorModel::bad_request(
+            format!("A domain can have at most {MAX_TAGS} tags"),
+            "TooManyDomainTags",
+            None,
+        )
+        .into());
+    }
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_tags() {
+        assert_eq!(
+            normalize_tags(vec![
+                " finance ".to_string(),
+                "pii".to_string(),
+                "finance".to_string()
+            ])
+            .unwrap(),
+            vec!["finance".to_string(), "pii".to_string()]
+        );
+        assert!(normalize_tags(vec!["  ".to_string()]).is_err());
+        assert!(normalize_tags(vec!["a".repeat(MAX_TAG_LENGTH + 1)]).is_err());
+        assert!(normalize_tags((0..=MAX_TAGS).map(|i| i.to_string()).collect()).is_err());
+    }
+}
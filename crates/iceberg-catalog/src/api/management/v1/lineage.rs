@@ -0,0 +1,291 @@
+use crate::api::management::v1::ApiServer;
+use crate::api::ApiContext;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+};
+use crate::service::{
+    Catalog, Result, SecretStore, State, TableIdentUuid, Transaction, ViewIdentUuid,
+};
+use crate::WarehouseIdent;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableLineageResponse {
+    /// Views depending on the table that are visible to you.
+    pub views: Vec<LineageTabular>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ViewLineageResponse {
+    /// Tables the view depends on that are visible to you.
+    pub tables: Vec<LineageTabular>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct LineageTabular {
+    /// ID of the table or view
+    pub id: uuid::Uuid,
+    /// Namespace the table or view belongs to
+    pub namespace: Vec<String>,
+    /// Name of the table or view
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ViewFreshnessResponse {
+    /// Whether any of the tables changed since the view was last committed.
+    /// `null` if some tables the view depends on are not visible to you, or if none
+    /// changed, but the snapshot at the last view commit is unknown for some tables.
+    pub stale: Option<bool>,
+    /// Tables the view depends on that are visible to you.
+    pub tables: Vec<TableFreshness>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableFreshness {
+    /// ID of the table
+    pub id: uuid::Uuid,
+    /// Namespace the table belongs to
+    pub namespace: Vec<String>,
+    /// Name of the table
+    pub name: String,
+    /// Snapshot of the `main` branch when the view was last committed.
+    /// `null` if the branch did not exist or the snapshot is unknown.
+    pub snapshot_id_at_view_commit: Option<i64>,
+    /// Current snapshot of the `main` branch
+    pub current_snapshot_id: Option<i64>,
+    /// Time the current snapshot was created in milliseconds since epoch
+    pub current_snapshot_timestamp_ms: Option<i64>,
+    /// Whether the `main` branch changed since the view was last committed.
+    /// `null` if the dependency was recorded before snapshots were tracked.
+    pub changed: Option<bool>,
+}
+
+impl axum::response::IntoResponse for TableLineageResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl axum::response::IntoResponse for ViewLineageResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl axum::response::IntoResponse for ViewFreshnessResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn get_table_lineage(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableLineageResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                Ok(Some(table_id)),
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let dependents = C::list_view_dependents(warehouse_id, table_id, t.transaction()).await?;
+        t.commit().await?;
+
+        let allowed = futures::future::try_join_all(dependents.iter().map(|dependent| {
+            authorizer.is_allowed_view_action(
+                &request_metadata,
+                dependent.view_id,
+                &CatalogViewAction::CanIncludeInList,
+            )
+        }))
+        .await?;
+
+        let views = dependents
+            .into_iter()
+            .zip(allowed)
+            .filter_map(|(dependent, allowed)| {
+                allowed.then(|| LineageTabular {
+                    id: *dependent.view_id,
+                    namespace: dependent.view.namespace.inner(),
+                    name: dependent.view.name,
+                })
+            })
+            .collect();
+
+        Ok(TableLineageResponse { views })
+    }
+
+    async fn get_view_lineage(
+        warehouse_id: WarehouseIdent,
+        view_id: ViewIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ViewLineageResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_view_action(
+                &request_metadata,
+                Ok(Some(view_id)),
+                &CatalogViewAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let dependencies =
+            C::list_view_dependencies(warehouse_id, view_id, t.transaction()).await?;
+        t.commit().await?;
+
+        let allowed = futures::future::try_join_all(dependencies.iter().map(|dependency| {
+            authorizer.is_allowed_table_action(
+                &request_metadata,
+                dependency.table_id,
+                &CatalogTableAction::CanIncludeInList,
+            )
+        }))
+        .await?;
+
+        // Staleness can't be derived from the visible tables alone, and computing it
+        // over hidden tables would reveal whether they changed.
+        let all_visible = allowed.iter().all(|allowed| *allowed);
+        let tables = dependencies
+            .into_iter()
+            .zip(allowed)
+            .filter_map(|(dependency, allowed)| {
+                allowed.then(|| LineageTabular {
+                    id: *dependency.table_id,
+                    namespace: dependency.table.namespace.inner(),
+                    name: dependency.table.name,
+                })
+            })
+            .collect();
+
+        Ok(ViewLineageResponse { tables })
+    }
+
+    async fn get_view_freshness(
+        warehouse_id: WarehouseIdent,
+        view_id: ViewIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ViewFreshnessResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_view_action(
+                &request_metadata,
+                Ok(Some(view_id)),
+                &CatalogViewAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let dependencies =
+            C::list_view_dependencies(warehouse_id, view_id, t.transaction()).await?;
+        t.commit().await?;
+
+        let allowed = futures::future::try_join_all(dependencies.iter().map(|dependency| {
+            authorizer.is_allowed_table_action(
+                &request_metadata,
+                dependency.table_id,
+                &CatalogTableAction::CanGetMetadata,
+            )
+        }))
+        .await?;
+
+        // Staleness can't be derived from the visible tables alone, and computing it
+        // over hidden tables would reveal whether they changed.
+        let all_visible = allowed.iter().all(|allowed| *allowed);
+        let tables = dependencies
+            .into_iter()
+            .zip(allowed)
+            .filter_map(|(dependency, allowed)| {
+                allowed.then(|| TableFreshness {
+                    id: *dependency.table_id,
+                    changed: dependency.changed_since_view_commit(),
+                    namespace: dependency.table.namespace.inner(),
+                    name: dependency.table.name,
+                    snapshot_id_at_view_commit: dependency.snapshot_id_at_view_commit,
+                    current_snapshot_id: dependency.current_snapshot_id,
+                    current_snapshot_timestamp_ms: dependency.current_snapshot_timestamp_ms,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ViewFreshnessResponse {
+            stale: all_visible
+                .then(|| staleness(tables.iter().map(|table| table.changed)))
+                .flatten(),
+            tables,
+        })
+    }
+}
+
+/// A view is stale if any table changed. If no table is known to have changed,
+/// it is only known to be fresh if the state of all tables is known.
+fn staleness(changed: impl IntoIterator<Item = Option<bool>>) -> Option<bool> {
+    let mut stale = Some(false);
+    for changed in changed {
+        match changed {
+            Some(true) => return Some(true),
+            Some(false) => {}
+            None => stale = None,
+        }
+    }
+    stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_staleness() {
+        assert_eq!(staleness([]), Some(false));
+        assert_eq!(staleness([Some(false), Some(false)]), Some(false));
+        assert_eq!(staleness([Some(false), None]), None);
+        assert_eq!(staleness([None, Some(true), Some(false)]), Some(true));
+    }
+}
@@ -0,0 +1,652 @@
+use crate::api::iceberg::types::Prefix;
+use crate::api::iceberg::v1::namespace::Service as _;
+use crate::api::management::v1::ApiServer;
+use crate::api::ApiContext;
+use crate::catalog::namespace::{validate_namespace_ident, NAMESPACE_ID_PROPERTY};
+use crate::catalog::CatalogServer;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogNamespaceAction, CatalogWarehouseAction, NamespaceParent,
+};
+use crate::service::event_publisher::WarehouseEventMetadata;
+use crate::service::missing_idents::invalidate_missing_idents;
+use crate::service::{Catalog, NamespaceIdentUuid, Result, SecretStore, State, Transaction};
+use crate::{WarehouseIdent, CONFIG};
+use iceberg::NamespaceIdent;
+use iceberg_ext::catalog::rest::{CreateNamespaceRequest, ErrorModel};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// Maximum number of namespaces in a bulk creation request, excluding implicitly created parents.
+const MAX_BULK_CREATE_NAMESPACES: usize = 1000;
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BulkCreateNamespacesRequest {
+    /// Namespaces to create. Parents that do not exist are created first, without properties.
+    pub namespaces: Vec<BulkCreateNamespace>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BulkCreateNamespace {
+    /// Path of the namespace, for example `["sales", "emea"]`.
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    /// Properties of the namespace, as for `createNamespace`.
+    #[serde(default)]
+    pub properties: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BulkCreateNamespacesResponse {
+    /// One result per namespace, including implicitly created parents.
+    /// Parents are listed before their children.
+    pub results: Vec<BulkCreateNamespaceResult>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct BulkCreateNamespaceResult {
+    #[schema(value_type = Vec<String>)]
+    pub namespace: NamespaceIdent,
+    pub status: BulkCreateNamespaceStatus,
+    /// ID of the namespace. Not set for failed namespaces and for namespaces
+    /// that were created concurrently by another request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<uuid::Uuid>,
+    /// Reason the namespace could not be created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorModel>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum BulkCreateNamespaceStatus {
+    Created,
+    /// The namespace already existed. Its properties were not changed.
+    Exists,
+    Failed,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RenameNamespaceRequest {
+    /// New name of the namespace, replacing the last part of its path.
+    /// The namespace keeps its parent.
+    pub new_name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct MoveNamespaceRequest {
+    /// Namespace to move the namespace into, for example `["sales", "archive"]`.
+    /// If not set, the namespace becomes a top-level namespace of the warehouse.
+    #[serde(default)]
+    #[schema(value_type = Option<Vec<String>>)]
+    pub new_parent: Option<NamespaceIdent>,
+}
+
+impl axum::response::IntoResponse for BulkCreateNamespacesResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn bulk_create_namespaces(
+        warehouse_id: WarehouseIdent,
+        request: BulkCreateNamespacesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<BulkCreateNamespacesResponse> {
+        // ------------------- VALIDATIONS -------------------
+        if request.namespaces.len() > MAX_BULK_CREATE_NAMESPACES {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "At most {MAX_BULK_CREATE_NAMESPACES} namespaces can be created in one request"
+                ),
+                "TooManyNamespaces",
+                None,
+            )
+            .into());
+        }
+        let plan = creation_plan(request.namespaces)?;
+
+        // ------------------- AuthZ -------------------
+        // Permissions to create each namespace are checked by `createNamespace`.
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        // Existing parents are skipped, so that callers which may only create namespaces
+        // inside an existing namespace can use the endpoint.
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let mut existing = HashMap::new();
+        for item in &plan {
+            if let Some(id) =
+                C::namespace_to_id(warehouse_id, &item.namespace, t.transaction()).await?
+            {
+                existing.insert(item.namespace.clone(), id);
+            }
+        }
+        t.commit().await?;
+        invalidate_missing_idents(warehouse_id);
+
+        let mut results: Vec<BulkCreateNamespaceResult> = Vec::with_capacity(plan.len());
+        for BulkCreateNamespace {
+            namespace,
+            properties,
+        } in plan
+        {
+            if let Some(id) = existing.get(&namespace) {
+                if authorizer
+                    .is_allowed_namespace_action(
+                        &request_metadata,
+                        *id,
+                        &CatalogNamespaceAction::CanGetMetadata,
+                    )
+                    .await?
+                {
+                    results.push(BulkCreateNamespaceResult {
+                        namespace_id: Some(**id),
+                        ..BulkCreateNamespaceResult::new(
+                            namespace,
+                            BulkCreateNamespaceStatus::Exists,
+                        )
+                    });
+                    continue;
+                }
+            }
+
+            let failed_parent = namespace.parent().filter(|parent| {
+                results.iter().any(|result| {
+                    result.status == BulkCreateNamespaceStatus::Failed
+                        && &result.namespace == parent
+                })
+            });
+            if let Some(parent) = failed_parent {
+                results.push(BulkCreateNamespaceResult {
+                    error: Some(ErrorModel::failed_dependency(
+                        format!(
+                            "Parent namespace {} could not be created",
+                            parent.as_ref().join(".")
+                        ),
+                        "ParentNamespaceNotCreated",
+                        None,
+                    )),
+                    ..BulkCreateNamespaceResult::new(namespace, BulkCreateNamespaceStatus::Failed)
+                });
+                continue;
+            }
+
+            let created = CatalogServer::<C, A, S>::create_namespace(
+                Some(Prefix(warehouse_id.to_string())),
+                CreateNamespaceRequest {
+                    namespace: namespace.clone(),
+                    properties,
+                },
+                context.clone(),
+                request_metadata.clone(),
+            )
+            .await;
+            results.push(match created {
+                Ok(response) => BulkCreateNamespaceResult {
+                    namespace_id: response
+                        .properties
+                        .as_ref()
+                        .and_then(|properties| properties.get(NAMESPACE_ID_PROPERTY))
+                        .and_then(|id| uuid::Uuid::parse_str(id).ok()),
+                    ..BulkCreateNamespaceResult::new(namespace, BulkCreateNamespaceStatus::Created)
+                },
+                // Created concurrently, for example by another provisioning run.
+                Err(e) if e.error.r#type == "NamespaceAlreadyExists" => {
+                    BulkCreateNamespaceResult::new(namespace, BulkCreateNamespaceStatus::Exists)
+                }
+                Err(e) => BulkCreateNamespaceResult {
+                    error: Some(e.error),
+                    ..BulkCreateNamespaceResult::new(namespace, BulkCreateNamespaceStatus::Failed)
+                },
+            });
+        }
+
+        Ok(BulkCreateNamespacesResponse { results })
+    }
+
+    async fn rename_namespace(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        request: RenameNamespaceRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanRename,
+            )
+            .await?;
+
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let source = C::get_namespace(warehouse_id, namespace_id, t.transaction())
+            .await?
+            .namespace;
+
+        // ------------------- VALIDATIONS -------------------
+        let destination = renamed_namespace(&source, request.new_name)?;
+        if destination == source {
+            return t.commit().await;
+        }
+
+        // ------------------- AuthZ -------------------
+        // Renaming creates a new name in the parent, just like creating a namespace.
+        if let Some(parent) = source.parent() {
+            let parent_id = C::namespace_to_id(warehouse_id, &parent, t.transaction()).await;
+            authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    parent_id,
+                    &CatalogNamespaceAction::CanCreateNamespace,
+                )
+                .await?;
+        } else {
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanCreateNamespace,
+                )
+                .await?;
+        }
+
+        // ------------------- Business Logic -------------------
+        C::rename_namespace(warehouse_id, &source, &destination, t.transaction()).await?;
+        t.commit().await?;
+        invalidate_missing_idents(warehouse_id);
+
+        let event_id = uuid::Uuid::now_v7();
+        let _ = context
+            .v1_state
+            .publisher
+            .publish_warehouse_event(
+                event_id,
+                "renameNamespace",
+                serde_json::json!({
+                    "namespace-id": namespace_id,
+                    "source": source,
+                    "destination": destination,
+                }),
+                WarehouseEventMetadata {
+                    warehouse_id,
+                    trace_id: request_metadata.request_id,
+                },
+            )
+            .await;
+
+        Ok(())
+    }
+
+    async fn move_namespace(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        request: MoveNamespaceRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanRename,
+            )
+            .await?;
+        // Moving the namespace removes it from its parent, so we need to be allowed to
+        // delete it there.
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanDelete,
+            )
+            .await?;
+
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog.clone()).await?;
+        let source = C::get_namespace(warehouse_id, namespace_id, t.transaction())
+            .await?
+            .namespace;
+
+        // ------------------- VALIDATIONS -------------------
+        let destination = moved_namespace(&source, request.new_parent.as_ref())?;
+        if destination == source {
+            return t.commit().await;
+        }
+
+        // ------------------- AuthZ -------------------
+        let new_parent = if let Some(parent) = destination.parent() {
+            let parent_id = C::namespace_to_id(warehouse_id, &parent, t.transaction()).await;
+            let parent_id = authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    parent_id,
+                    &CatalogNamespaceAction::CanCreateNamespace,
+                )
+                .await?;
+            NamespaceParent::Namespace(parent_id)
+        } else {
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanCreateNamespace,
+                )
+                .await?;
+            NamespaceParent::Warehouse(warehouse_id)
+        };
+
+        // ------------------- Business Logic -------------------
+        let previous_parent = if let Some(parent) = source.parent() {
+            let parent_id = C::namespace_to_id(warehouse_id, &parent, t.transaction())
+                .await?
+                .ok_or_else(|| {
+                    ErrorModel::internal(
+                        format!("Parent namespace {} not found", parent.as_ref().join(".")),
+                        "ParentNamespaceNotFound",
+                        None,
+                    )
+                })?;
+            NamespaceParent::Namespace(parent_id)
+        } else {
+            NamespaceParent::Warehouse(warehouse_id)
+        };
+
+        C::rename_namespace(warehouse_id, &source, &destination, t.transaction()).await?;
+        t.commit().await?;
+        invalidate_missing_idents(warehouse_id);
+
+        // The authorizer is not part of the transaction: parent relations are only moved
+        // once the move is committed, and the move is reverted if they cannot be moved.
+        if let Err(e) = authorizer
+            .move_namespace(&request_metadata, namespace_id, previous_parent, new_parent)
+            .await
+        {
+            let revert = async {
+                let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+                C::rename_namespace(warehouse_id, &destination, &source, t.transaction()).await?;
+                t.commit().await
+            }
+            .await;
+            invalidate_missing_idents(warehouse_id);
+            if let Err(revert_error) = revert {
+                tracing::error!(
+                    "Failed to revert move of namespace {namespace_id} after its parent relations could not be moved: {}",
+                    revert_error.error
+                );
+            }
+            return Err(e);
+        }
+
+        let _ = context
+            .v1_state
+            .publisher
+            .publish_warehouse_event(
+                uuid::Uuid::now_v7(),
+                "moveNamespace",
+                serde_json::json!({
+                    "namespace-id": namespace_id,
+                    "source": source,
+                    "destination": destination,
+                }),
+                WarehouseEventMetadata {
+                    warehouse_id,
+                    trace_id: request_metadata.request_id,
+                },
+            )
+            .await;
+
+        Ok(())
+    }
+}
+
+impl BulkCreateNamespaceResult {
+    fn new(namespace: NamespaceIdent, status: BulkCreateNamespaceStatus) -> Self {
+        Self {
+            namespace,
+            status,
+            namespace_id: None,
+            error: None,
+        }
+    }
+}
+
+/// Replace the last part of `source` with `new_name`.
+fn renamed_namespace(source: &NamespaceIdent, new_name: String) -> Result<NamespaceIdent> {
+    let mut parts = source.clone().inner();
+    parts.pop();
+    parts.push(new_name);
+    let destination = NamespaceIdent::from_vec(parts).map_err(|e| {
+        ErrorModel::bad_request("Invalid namespace", "InvalidNamespace", Some(Box::new(e)))
+    })?;
+    validate_destination(&destination)?;
+    Ok(destination)
+}
+
+/// Place the last part of `source` below `new_parent`.
+fn moved_namespace(
+    source: &NamespaceIdent,
+    new_parent: Option<&NamespaceIdent>,
+) -> Result<NamespaceIdent> {
+    if let Some(new_parent) = new_parent {
+        // Namespace names are compared case-insensitively by the catalog.
+        let is_descendant = new_parent.len() >= source.len()
+            && source
+                .iter()
+                .zip(new_parent.iter())
+                .all(|(a, b)| a.to_lowercase() == b.to_lowercase());
+        if is_descendant {
+            return Err(ErrorModel::bad_request(
+                "A namespace cannot be moved into itself or one of its children",
+                "NamespaceMovedIntoItself",
+                None,
+            )
+            .into());
+        }
+    }
+
+    let mut parts = new_parent.map(|p| p.clone().inner()).unwrap_or_default();
+    parts.extend(source.as_ref().last().cloned());
+    let destination = NamespaceIdent::from_vec(parts).map_err(|e| {
+        ErrorModel::bad_request("Invalid namespace", "InvalidNamespace", Some(Box::new(e)))
+    })?;
+    validate_destination(&destination)?;
+    Ok(destination)
+}
+
+fn validate_destination(destination: &NamespaceIdent) -> Result<()> {
+    validate_namespace_ident(destination)?;
+
+    if destination.len() == 1
+        && CONFIG
+            .reserved_namespaces
+            .contains(&destination.as_ref()[0].to_lowercase())
+    {
+        return Err(ErrorModel::bad_request(
+            "Namespace is reserved for internal use.",
+            "ReservedNamespace",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Order the requested namespaces so that parents are created before their children,
+/// adding parents that were not requested explicitly.
+fn creation_plan(namespaces: Vec<BulkCreateNamespace>) -> Result<Vec<BulkCreateNamespace>> {
+    let mut plan: Vec<BulkCreateNamespace> = vec![];
+    let mut positions: HashMap<NamespaceIdent, usize> = HashMap::new();
+    let mut requested = HashSet::new();
+
+    for item in namespaces {
+        if !requested.insert(item.namespace.clone()) {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Namespace {} is listed more than once",
+                    item.namespace.as_ref().join(".")
+                ),
+                "DuplicateNamespace",
+                None,
+            )
+            .into());
+        }
+
+        let levels = item.namespace.as_ref().len();
+        for depth in 1..levels {
+            let parent = NamespaceIdent::from_vec(item.namespace.as_ref()[..depth].to_vec())
+                .map_err(|e| {
+                    ErrorModel::bad_request(
+                        "Invalid namespace",
+                        "InvalidNamespace",
+                        Some(Box::new(e)),
+                    )
+                })?;
+            if !positions.contains_key(&parent) {
+                positions.insert(parent.clone(), plan.len());
+                plan.push(BulkCreateNamespace {
+                    namespace: parent,
+                    properties: None,
+                });
+            }
+        }
+
+        // A namespace added as parent of an earlier item keeps its position
+        // and receives the requested properties.
+        if let Some(position) = positions.get(&item.namespace) {
+            plan[*position].properties = item.properties;
+        } else {
+            positions.insert(item.namespace.clone(), plan.len());
+            plan.push(item);
+        }
+    }
+
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn item(parts: &[&str], properties: Option<&[(&str, &str)]>) -> BulkCreateNamespace {
+        BulkCreateNamespace {
+            namespace: NamespaceIdent::from_strs(parts).unwrap(),
+            properties: properties.map(|properties| {
+                properties
+                    .iter()
+                    .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                    .collect()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_creation_plan() {
+        let plan = creation_plan(vec![
+            item(&["sales", "emea", "raw"], None),
+            item(&["sales"], Some(&[("owner", "sales-team")])),
+            item(&["marketing"], None),
+            item(&["sales", "apac"], None),
+        ])
+        .unwrap();
+
+        let order = plan
+            .iter()
+            .map(|item| item.namespace.as_ref().join("."))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            order,
+            vec![
+                "sales",
+                "sales.emea",
+                "sales.emea.raw",
+                "marketing",
+                "sales.apac"
+            ]
+        );
+        assert_eq!(
+            plan[0].properties.as_ref().unwrap()["owner"],
+            "sales-team".to_string()
+        );
+        assert!(plan[1].properties.is_none());
+    }
+
+    #[test]
+    fn test_renamed_namespace() {
+        let source = NamespaceIdent::from_strs(["sales", "emea"]).unwrap();
+        let destination = renamed_namespace(&source, "europe".to_string()).unwrap();
+        assert_eq!(
+            destination,
+            NamespaceIdent::from_strs(["sales", "europe"]).unwrap()
+        );
+
+        let err = renamed_namespace(&source, "eu.west".to_string()).unwrap_err();
+        assert_eq!(err.error.r#type, "NamespacePartContainsDot");
+        let err = renamed_namespace(&source, String::new()).unwrap_err();
+        assert_eq!(err.error.r#type, "NamespacePartEmpty");
+    }
+
+    #[test]
+    fn test_moved_namespace() {
+        let source = NamespaceIdent::from_strs(["sales", "emea"]).unwrap();
+        let new_parent = NamespaceIdent::from_strs(["archive"]).unwrap();
+        assert_eq!(
+            moved_namespace(&source, Some(&new_parent)).unwrap(),
+            NamespaceIdent::from_strs(["archive", "emea"]).unwrap()
+        );
+        assert_eq!(
+            moved_namespace(&source, None).unwrap(),
+            NamespaceIdent::from_strs(["emea"]).unwrap()
+        );
+
+        for new_parent in [
+            source.clone(),
+            NamespaceIdent::from_strs(["Sales", "EMEA", "raw"]).unwrap(),
+        ] {
+            let err = moved_namespace(&source, Some(&new_parent)).unwrap_err();
+            assert_eq!(err.error.r#type, "NamespaceMovedIntoItself");
+        }
+    }
+
+    #[test]
+    fn test_creation_plan_rejects_duplicates() {
+        let err = creation_plan(vec![item(&["sales"], None), item(&["sales"], None)]).unwrap_err();
+        assert_eq!(err.error.r#type, "DuplicateNamespace");
+    }
+}
@@ -0,0 +1,238 @@
+use crate::api::management::v1::ApiServer;
+use crate::api::ApiContext;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{Authorizer, CatalogServerAction};
+use crate::service::{Actor, Catalog, Result, SecretStore, State, Transaction};
+use crate::CONFIG;
+use axum::response::IntoResponse;
+use axum::Json;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Maximum length of a notice message in characters.
+const MAX_MESSAGE_LENGTH: usize = 1024;
+
+/// Severity of a notice. Determines how prominently it is displayed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum NoticeSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct Notice {
+    /// ID of the notice
+    pub id: Uuid,
+    /// Message to display
+    pub message: String,
+    /// Severity of the notice
+    pub severity: NoticeSeverity,
+    /// Time from which on the notice is shown. If not set, the notice is shown immediately.
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time until which the notice is shown. If not set, the notice is shown until deleted.
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Timestamp when the notice was created
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateNoticeRequest {
+    /// Message to display. At most 1024 characters.
+    pub message: String,
+    /// Severity of the notice. Defaults to `info`.
+    #[serde(default)]
+    pub severity: NoticeSeverity,
+    /// Time from which on the notice is shown. If not set, the notice is shown immediately.
+    #[serde(default)]
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time until which the notice is shown. If not set, the notice is shown until deleted.
+    #[serde(default)]
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListNoticesQuery {
+    /// Also return notices that are scheduled or have ended.
+    /// Requires the permission to manage notices.
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListNoticesResponse {
+    /// Notices, most severe first.
+    pub notices: Vec<Notice>,
+}
+
+impl IntoResponse for ListNoticesResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn create_notice(
+        request: CreateNoticeRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<Notice> {
+        // -------------------- VALIDATIONS --------------------
+        validate_notice(&request, chrono::Utc::now())?;
+
+        // -------------------- AUTHZ --------------------
+        context
+            .v1_state
+            .authz
+            .require_server_action(&request_metadata, &CatalogServerAction::CanManageNotices)
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let notice = C::create_notice(
+            Uuid::now_v7(),
+            &request.message,
+            request.severity,
+            request.starts_at,
+            request.ends_at,
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+        Ok(notice)
+    }
+
+    async fn list_notices(
+        context: ApiContext<State<A, C, S>>,
+        query: ListNoticesQuery,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListNoticesResponse> {
+        // -------------------- AUTHZ --------------------
+        // Active notices are visible to everyone who can use the server.
+        if matches!(request_metadata.actor(), Actor::Anonymous) && CONFIG.authn_enabled() {
+            return Err(ErrorModel::unauthorized(
+                "Authentication required",
+                "AuthenticationRequired",
+                None,
+            )
+            .into());
+        }
+        if query.include_inactive {
+            context
+                .v1_state
+                .authz
+                .require_server_action(&request_metadata, &CatalogServerAction::CanManageNotices)
+                .await?;
+        }
+
+        // -------------------- Business Logic --------------------
+        let notices = C::list_notices(query.include_inactive, context.v1_state.catalog).await?;
+        Ok(ListNoticesResponse { notices })
+    }
+
+    async fn delete_notice(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        notice_id: Uuid,
+    ) -> Result<()> {
+        // -------------------- AUTHZ --------------------
+        context
+            .v1_state
+            .authz
+            .require_server_action(&request_metadata, &CatalogServerAction::CanManageNotices)
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let deleted = C::delete_notice(notice_id, t.transaction()).await?;
+        if deleted.is_none() {
+            return Err(ErrorModel::not_found(
+                format!("Notice {notice_id} not found"),
+                "NoticeNotFound",
+                None,
+            )
+            .into());
+        }
+        t.commit().await
+    }
+}
+
+fn validate_notice(
+    request: &CreateNoticeRequest,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    if request.message.trim().is_empty() {
+        return Err(ErrorModel::bad_request(
+            "Notice message cannot be empty",
+            "EmptyNoticeMessage",
+            None,
+        )
+        .into());
+    }
+    if request.message.chars().count() > MAX_MESSAGE_LENGTH {
+        return Err(ErrorModel::bad_request(
+            format!("Notice message must not be longer than {MAX_MESSAGE_LENGTH} characters"),
+            "NoticeMessageTooLong",
+            None,
+        )
+        .into());
+    }
+    if let Some(ends_at) = request.ends_at {
+        if ends_at <= now
+            || request
+                .starts_at
+                .is_some_and(|starts_at| ends_at <= starts_at)
+        {
+            return Err(ErrorModel::bad_request(
+                "End of the notice must be in the future and after its start",
+                "InvalidNoticeSchedule",
+                None,
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_notice() {
+        let now = chrono::Utc::now();
+        let request = |message: &str, starts_at, ends_at| CreateNoticeRequest {
+            message: message.to_string(),
+            severity: NoticeSeverity::Warning,
+            starts_at,
+            ends_at,
+        };
+        let in_an_hour = Some(now + chrono::Duration::hours(1));
+        let in_two_hours = Some(now + chrono::Duration::hours(2));
+
+        validate_notice(&request("Maintenance", None, None), now).unwrap();
+        validate_notice(&request("Maintenance", in_an_hour, in_two_hours), now).unwrap();
+        validate_notice(&request(" ", None, None), now).unwrap_err();
+        validate_notice(&request("Maintenance", in_two_hours, in_an_hour), now).unwrap_err();
+        validate_notice(
+            &request("Maintenance", None, Some(now - chrono::Duration::hours(1))),
+            now,
+        )
+        .unwrap_err();
+        validate_notice(
+            &request(&"a".repeat(MAX_MESSAGE_LENGTH + 1), None, None),
+            now,
+        )
+        .unwrap_err();
+    }
+}
@@ -2,8 +2,8 @@ use crate::api::management::v1::ApiServer;
 use crate::api::{ApiContext, Result};
 use crate::request_metadata::RequestMetadata;
 pub use crate::service::storage::{
-    AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, S3Credential, S3Profile,
-    StorageCredential, StorageProfile,
+    AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, GcsTokenExchange,
+    S3Credential, S3Profile, StorageCredential, StorageProfile,
 };
 use serde::{Deserialize, Serialize};
 
@@ -0,0 +1,621 @@
+use crate::api::iceberg::types::Prefix;
+use crate::api::iceberg::v1::{CommitTableRequest, CommitTransactionRequest};
+use crate::api::management::v1::ApiServer;
+use crate::api::ApiContext;
+use crate::catalog::tables::commit_tables_internal;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{
+    Authorizer, CatalogNamespaceAction, CatalogTableAction, CatalogWarehouseAction,
+};
+use crate::service::id_generation::PROPERTY_EXTERNAL_ID;
+use crate::service::{
+    Catalog, ListFlags, NamespaceIdentUuid, Result, SecretStore, State, TableIdentUuid, Transaction,
+};
+use crate::WarehouseIdent;
+use iceberg::spec::MAIN_BRANCH;
+use iceberg::{TableRequirement, TableUpdate};
+use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::configs::{ConfigProperty as _, Location};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Namespace properties that are kept at their current value when reverting,
+/// as they cannot be changed after creation.
+const IMMUTABLE_NAMESPACE_PROPERTIES: [&str; 2] = [Location::KEY, PROPERTY_EXTERNAL_ID];
+
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PropertyVersion {
+    /// ID of the version. Pass it to the revert endpoint to restore these properties.
+    pub version: i64,
+    /// Properties before they were replaced
+    pub properties: HashMap<String, String>,
+    /// Time the properties were replaced
+    pub replaced_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PropertyHistoryResponse {
+    /// Previous properties, most recently replaced first
+    pub versions: Vec<PropertyVersion>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RevertPropertiesRequest {
+    /// Version of the property history to restore
+    pub version: i64,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RevertPropertiesResponse {
+    /// Properties after the revert
+    pub properties: HashMap<String, String>,
+}
+
+impl axum::response::IntoResponse for PropertyHistoryResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl axum::response::IntoResponse for RevertPropertiesResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn get_namespace_property_history(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<PropertyHistoryResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let versions =
+            C::list_namespace_property_history(warehouse_id, namespace_id, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(PropertyHistoryResponse { versions })
+    }
+
+    async fn revert_namespace_properties(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        request: RevertPropertiesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RevertPropertiesResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_namespace_action(
+                &request_metadata,
+                Ok(Some(namespace_id)),
+                &CatalogNamespaceAction::CanUpdateProperties,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let current = C::get_namespace(warehouse_id, namespace_id, t.transaction())
+            .await?
+            .properties
+            .unwrap_or_default();
+        let history =
+            C::list_namespace_property_history(warehouse_id, namespace_id, t.transaction()).await?;
+        let mut properties = find_version(history, request.version)?.properties;
+        for key in IMMUTABLE_NAMESPACE_PROPERTIES {
+            properties.remove(key);
+            if let Some(value) = current.get(key) {
+                properties.insert(key.to_string(), value.clone());
+            }
+        }
+        // The properties in place before the revert become part of the history,
+        // so that the revert itself can be undone.
+        C::update_namespace_properties(
+            warehouse_id,
+            namespace_id,
+            properties.clone(),
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        tracing::info!(
+            actor = %request_metadata.actor(),
+            %warehouse_id,
+            %namespace_id,
+            version = request.version,
+            "Reverted namespace properties"
+        );
+
+        Ok(RevertPropertiesResponse { properties })
+    }
+
+    async fn get_table_property_history(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<PropertyHistoryResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                Ok(Some(table_id)),
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let versions =
+            C::list_table_property_history(warehouse_id, table_id, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(PropertyHistoryResponse { versions })
+    }
+
+    async fn revert_table_properties(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: RevertPropertiesRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RevertPropertiesResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, &CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let history =
+            C::list_table_property_history(warehouse_id, table_id, t.transaction()).await?;
+        let mut tables = C::load_tables(warehouse_id, [table_id], false, t.transaction()).await?;
+        t.commit().await?;
+        let metadata = tables
+            .remove(&table_id)
+            .ok_or_else(|| {
+                ErrorModel::not_found(format!("Table {table_id} not found"), "TableNotFound", None)
+            })?
+            .table_metadata;
+        let properties = find_version(history, request.version)?.properties;
+
+        // Properties are changed through a regular commit, which writes a new metadata
+        // file and records the current properties in the history.
+        let current = metadata.properties();
+        let removals = current
+            .keys()
+            .filter(|key| !properties.contains_key(*key))
+            .cloned()
+            .collect::<Vec<_>>();
+        let updates = properties
+            .iter()
+            .filter(|(key, value)| current.get(*key) != Some(*value))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<HashMap<_, _>>();
+        let mut table_updates = vec![];
+        if !updates.is_empty() {
+            table_updates.push(TableUpdate::SetProperties { updates });
+        }
+        if !removals.is_empty() {
+            table_updates.push(TableUpdate::RemoveProperties { removals });
+        }
+        if table_updates.is_empty() {
+            return Ok(RevertPropertiesResponse { properties });
+        }
+
+        let commit = CommitTableRequest {
+            identifier: Some(table.table.clone()),
+            requirements: vec![
+                TableRequirement::UuidMatch {
+                    uuid: metadata.uuid(),
+                },
+                // The revert is computed from the loaded metadata. Fail instead of
+                // overwriting a commit that happened in between.
+                TableRequirement::RefSnapshotIdMatch {
+                    r#ref: MAIN_BRANCH.to_string(),
+                    snapshot_id: metadata.current_snapshot_id(),
+                },
+                TableRequirement::CurrentSchemaIdMatch {
+                    current_schema_id: metadata.current_schema_id(),
+                },
+            ],
+            updates: table_updates,
+        };
+        commit_tables_internal(
+            Some(Prefix(warehouse_id.to_string())),
+            CommitTransactionRequest {
+                table_changes: vec![commit],
+            },
+            context,
+            request_metadata.clone(),
+        )
+        .await?;
+
+        tracing::info!(
+            actor = %request_metadata.actor(),
+            %warehouse_id,
+            %table_id,
+            version = request.version,
+            "Reverted properties of table {}",
+            table.table
+        );
+
+        Ok(RevertPropertiesResponse { properties })
+    }
+}
+
+fn find_version(versions: Vec<PropertyVersion>, version: i64) -> Result<PropertyVersion> {
+    versions
+        .into_iter()
+        .find(|v| v.version == version)
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Property version {version} not found"),
+                "PropertyVersionNotFound",
+                None,
+            )
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{RevertPropertiesRequest, Service as _};
+    use crate::api::iceberg::types::Prefix;
+    use crate::api::iceberg::v1::namespace::Service as _;
+    use crate::api::iceberg::v1::tables::TablesService as _;
+    use crate::api::iceberg::v1::{
+        CommitTableRequest, CommitTransactionRequest, CreateTableRequest, DataAccess,
+        NamespaceParameters, TableParameters,
+    };
+    use crate::api::management::v1::warehouse::TabularDeleteProfile;
+    use crate::api::management::v1::ApiServer;
+    use crate::catalog::namespace::NAMESPACE_ID_PROPERTY;
+    use crate::catalog::tables::commit_tables_internal;
+    use crate::catalog::test::random_request_metadata;
+    use crate::catalog::CatalogServer;
+    use crate::service::authz::AllowAllAuthorizer;
+    use crate::service::{NamespaceIdentUuid, TableIdentUuid};
+    use iceberg::spec::{NestedField, PrimitiveType, Schema, Type};
+    use iceberg::{NamespaceIdent, TableIdent, TableRequirement, TableUpdate};
+    use iceberg_ext::catalog::rest::{CreateNamespaceRequest, UpdateNamespacePropertiesRequest};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    #[sqlx::test]
+    async fn test_revert_namespace_properties(pool: sqlx::PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool,
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let warehouse_id = warehouse.warehouse_id;
+        let namespace = NamespaceIdent::new("ns".to_string());
+        let created = CatalogServer::create_namespace(
+            Some(Prefix(warehouse_id.to_string())),
+            CreateNamespaceRequest {
+                namespace: namespace.clone(),
+                properties: Some(HashMap::from_iter([(
+                    "write.target-file-size-bytes".to_string(),
+                    "536870912".to_string(),
+                )])),
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        let created_properties = created.properties.unwrap();
+        let namespace_id =
+            NamespaceIdentUuid::from_str(&created_properties[NAMESPACE_ID_PROPERTY]).unwrap();
+
+        let update = |updates: &[(&str, &str)], removals: &[&str]| {
+            CatalogServer::update_namespace_properties(
+                NamespaceParameters {
+                    prefix: Some(Prefix(warehouse_id.to_string())),
+                    namespace: namespace.clone(),
+                },
+                UpdateNamespacePropertiesRequest {
+                    updates: Some(
+                        updates
+                            .iter()
+                            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                            .collect(),
+                    ),
+                    removals: Some(removals.iter().map(ToString::to_string).collect()),
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+        };
+        update(&[("write.target-file-size-bytes", "1")], &[])
+            .await
+            .unwrap();
+        update(&[("owner", "sales")], &[]).await.unwrap();
+        // Updates that do not change anything are not recorded
+        update(&[("owner", "sales")], &[]).await.unwrap();
+
+        let history = ApiServer::get_namespace_property_history(
+            warehouse_id,
+            namespace_id,
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap()
+        .versions;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].properties["write.target-file-size-bytes"], "1");
+        assert!(!history[0].properties.contains_key("owner"));
+        assert_eq!(
+            history[1].properties["write.target-file-size-bytes"],
+            "536870912"
+        );
+
+        let reverted = ApiServer::revert_namespace_properties(
+            warehouse_id,
+            namespace_id,
+            RevertPropertiesRequest {
+                version: history[1].version,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            reverted.properties["write.target-file-size-bytes"],
+            "536870912"
+        );
+        assert!(!reverted.properties.contains_key("owner"));
+        assert_eq!(
+            reverted.properties.get("location"),
+            created_properties.get("location")
+        );
+
+        // The revert can be undone
+        let history = ApiServer::get_namespace_property_history(
+            warehouse_id,
+            namespace_id,
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap()
+        .versions;
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].properties["owner"], "sales");
+
+        let missing = ApiServer::revert_namespace_properties(
+            warehouse_id,
+            namespace_id,
+            RevertPropertiesRequest { version: -1 },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(missing.error.code, 404);
+    }
+
+    #[sqlx::test]
+    async fn test_revert_table_properties(pool: sqlx::PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool,
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let warehouse_id = warehouse.warehouse_id;
+        let prefix = Some(Prefix(warehouse_id.to_string()));
+        let namespace = NamespaceIdent::new("ns".to_string());
+        CatalogServer::create_namespace(
+            prefix.clone(),
+            CreateNamespaceRequest {
+                namespace: namespace.clone(),
+                properties: None,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        let table = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: prefix.clone(),
+                namespace: namespace.clone(),
+            },
+            CreateTableRequest {
+                name: "tab".to_string(),
+                location: None,
+                schema: Schema::builder()
+                    .with_fields(vec![NestedField::required(
+                        1,
+                        "id",
+                        Type::Primitive(PrimitiveType::Int),
+                    )
+                    .into()])
+                    .build()
+                    .unwrap(),
+                partition_spec: None,
+                write_order: None,
+                stage_create: Some(false),
+                properties: Some(HashMap::from_iter([(
+                    "write.target-file-size-bytes".to_string(),
+                    "536870912".to_string(),
+                )])),
+            },
+            DataAccess::none(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        let table_id = TableIdentUuid::from(table.metadata.uuid());
+        let table_ident = TableIdent::new(namespace.clone(), "tab".to_string());
+
+        let commit = |requirements: Vec<TableRequirement>, updates: Vec<TableUpdate>| {
+            commit_tables_internal(
+                prefix.clone(),
+                CommitTransactionRequest {
+                    table_changes: vec![CommitTableRequest {
+                        identifier: Some(table_ident.clone()),
+                        requirements,
+                        updates,
+                    }],
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+        };
+        commit(
+            vec![],
+            vec![TableUpdate::SetProperties {
+                updates: HashMap::from_iter([
+                    ("write.target-file-size-bytes".to_string(), "1".to_string()),
+                    ("owner".to_string(), "sales".to_string()),
+                ]),
+            }],
+        )
+        .await
+        .unwrap();
+
+        let history = ApiServer::get_table_property_history(
+            warehouse_id,
+            table_id,
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap()
+        .versions;
+        assert_eq!(history.len(), 1);
+        assert_eq!(
+            history[0].properties["write.target-file-size-bytes"],
+            "536870912"
+        );
+
+        let reverted = ApiServer::revert_table_properties(
+            warehouse_id,
+            table_id,
+            RevertPropertiesRequest {
+                version: history[0].version,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            reverted.properties["write.target-file-size-bytes"],
+            "536870912"
+        );
+        assert!(!reverted.properties.contains_key("owner"));
+
+        let loaded = CatalogServer::load_table(
+            TableParameters {
+                prefix: prefix.clone(),
+                table: table_ident.clone(),
+            },
+            DataAccess::none(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            loaded.metadata.properties()["write.target-file-size-bytes"],
+            "536870912"
+        );
+        assert!(!loaded.metadata.properties().contains_key("owner"));
+
+        // The revert was committed and is part of the history itself
+        let history = ApiServer::get_table_property_history(
+            warehouse_id,
+            table_id,
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap()
+        .versions;
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].properties["owner"], "sales");
+
+        // Unknown tables are reported instead of returning an empty history
+        let missing = ApiServer::get_table_property_history(
+            warehouse_id,
+            TableIdentUuid::from(uuid::Uuid::now_v7()),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(missing.error.code, 404);
+    }
+}
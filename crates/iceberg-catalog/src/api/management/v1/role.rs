@@ -1,16 +1,21 @@
-use super::default_page_size;
+use super::{default_page_size, SortOrder};
 use crate::api::iceberg::types::PageToken;
-use crate::api::iceberg::v1::PaginationQuery;
+use crate::api::iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE};
+use crate::api::management::v1::user::{GetDefaultWarehouseResponse, SetDefaultWarehouseRequest};
 use crate::api::management::v1::ApiServer;
 use crate::api::ApiContext;
 use crate::request_metadata::RequestMetadata;
-use crate::service::authz::{Authorizer, CatalogProjectAction, CatalogRoleAction};
+use crate::service::authz::{
+    nested_roles_not_supported, Authorizer, CatalogProjectAction, CatalogRoleAction,
+    CatalogWarehouseAction,
+};
 use crate::service::{Catalog, Result, RoleId, SecretStore, State, Transaction};
 use crate::{ProjectIdent, DEFAULT_PROJECT_ID};
 use axum::response::IntoResponse;
 use axum::Json;
 use iceberg_ext::catalog::rest::ErrorModel;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
@@ -74,6 +79,39 @@ impl IntoResponse for ListRolesResponse {
     }
 }
 
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RoleMember {
+    /// The member role
+    pub role: Role,
+    /// Role the member is a direct member of. For direct members, this is the listed role.
+    #[schema(value_type=uuid::Uuid)]
+    pub parent_role_id: RoleId,
+    /// 1 for direct members, 2 for members of direct members, and so on.
+    pub depth: i32,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListRoleMembersResponse {
+    /// Member roles, ordered by depth and name.
+    pub members: Vec<RoleMember>,
+}
+
+impl IntoResponse for ListRoleMembersResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ListRoleMembersQuery {
+    /// Also list members of member roles. Default: false
+    #[serde(default)]
+    pub recursive: bool,
+}
+
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct SearchRoleRequest {
@@ -93,6 +131,22 @@ pub struct ListRolesQuery {
     /// Search for a specific role name
     #[serde(default)]
     pub name: Option<String>,
+    /// Only return roles created at or after this time
+    #[serde(default)]
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return roles created before this time
+    #[serde(default)]
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return roles that are direct members of this role
+    #[serde(default)]
+    #[param(value_type=Option::<uuid::Uuid>)]
+    pub member_of: Option<RoleId>,
+    /// Field to sort by. Default: `created-at`
+    #[serde(default)]
+    pub sort_by: RoleSortBy,
+    /// Default: `asc`
+    #[serde(default)]
+    pub sort_order: SortOrder,
     /// Next page token
     #[serde(default)]
     pub page_token: Option<String>,
@@ -119,6 +173,33 @@ impl ListRolesQuery {
             page_size: Some(self.page_size),
         }
     }
+
+    #[must_use]
+    pub fn filter(&self) -> RoleListFilter {
+        RoleListFilter {
+            created_after: self.created_after,
+            created_before: self.created_before,
+            sort_by: self.sort_by,
+            sort_order: self.sort_order,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum RoleSortBy {
+    #[default]
+    CreatedAt,
+    Name,
+}
+
+/// Filters and ordering for listing roles, in addition to the project, id and name filters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RoleListFilter {
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub sort_by: RoleSortBy,
+    pub sort_order: SortOrder,
 }
 
 impl IntoResponse for SearchRoleResponse {
@@ -195,13 +276,29 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             )
             .await?;
 
+        if let Some(member_of) = query.member_of {
+            authorizer
+                .require_role_action(&request_metadata, member_of, &CatalogRoleAction::CanRead)
+                .await?;
+        }
+
         // -------------------- Business Logic --------------------
-        let filter_role_id = None;
+        let filter_role_id = match query.member_of {
+            Some(member_of) => Some(
+                authorizer
+                    .list_role_members(member_of)
+                    .await?
+                    .ok_or_else(nested_roles_not_supported)?,
+            ),
+            None => None,
+        };
         let pagination_query = query.pagination_query();
+        let filter = query.filter();
         C::list_roles(
             Some(project_id),
             filter_role_id,
             query.name,
+            filter,
             pagination_query,
             context.v1_state.catalog,
         )
@@ -224,6 +321,7 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             None,
             Some(vec![role_id]),
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(1),
                 page_token: PageToken::NotSpecified,
@@ -338,21 +436,335 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .into())
         }
     }
+
+    async fn get_role_default_warehouse(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        role_id: RoleId,
+    ) -> Result<GetDefaultWarehouseResponse> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_role_action(&request_metadata, role_id, &CatalogRoleAction::CanRead)
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        let default = C::get_role_default_warehouse(role_id, context.v1_state.catalog).await?;
+        Ok(default.into())
+    }
+
+    async fn set_role_default_warehouse(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        role_id: RoleId,
+        request: SetDefaultWarehouseRequest,
+    ) -> Result<()> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_role_action(&request_metadata, role_id, &CatalogRoleAction::CanUpdate)
+            .await?;
+        let warehouse_id = request.warehouse_id.map(Into::into);
+        if let Some(warehouse_id) = warehouse_id {
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanGetMetadata,
+                )
+                .await?;
+        }
+
+        // -------------------- Business Logic --------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog.clone()).await?;
+        if let Some(warehouse_id) = warehouse_id {
+            // Roles are project scoped - the default warehouse must live in the same project
+            let role = C::list_roles(
+                None,
+                Some(vec![role_id]),
+                None,
+                RoleListFilter::default(),
+                PaginationQuery {
+                    page_size: Some(1),
+                    page_token: PageToken::NotSpecified,
+                },
+                context.v1_state.catalog,
+            )
+            .await?
+            .roles
+            .into_iter()
+            .next()
+            .ok_or(ErrorModel::not_found(
+                format!("Role with id {role_id} not found."),
+                "RoleNotFound",
+                None,
+            ))?;
+            let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+            if warehouse.project_id != role.project_id {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Warehouse {warehouse_id} does not belong to project {} of role {role_id}.",
+                        role.project_id
+                    ),
+                    "WarehouseNotInRoleProject",
+                    None,
+                )
+                .into());
+            }
+        }
+        C::set_role_default_warehouse(role_id, warehouse_id, t.transaction()).await?;
+        t.commit().await
+    }
+
+    async fn list_role_members(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        role_id: RoleId,
+        query: ListRoleMembersQuery,
+    ) -> Result<ListRoleMembersResponse> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_role_action(&request_metadata, role_id, &CatalogRoleAction::CanRead)
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        // Memberships live in the authorizer only, the catalog only knows the roles.
+        let mut memberships = Vec::new();
+        let mut visited = HashSet::from([role_id]);
+        let mut parents = vec![role_id];
+        let mut depth = 1;
+        while !parents.is_empty() && depth <= MAX_ROLE_NESTING_DEPTH {
+            let mut next_parents = Vec::new();
+            for parent_role_id in parents {
+                let direct_members = authorizer
+                    .list_role_members(parent_role_id)
+                    .await?
+                    .ok_or_else(nested_roles_not_supported)?;
+                for member in direct_members {
+                    if visited.insert(member) {
+                        memberships.push((member, parent_role_id, depth));
+                        next_parents.push(member);
+                    }
+                }
+            }
+            if !query.recursive {
+                break;
+            }
+            parents = next_parents;
+            depth += 1;
+        }
+
+        let mut roles = load_roles::<C>(
+            memberships.iter().map(|(member, _, _)| *member).collect(),
+            context.v1_state.catalog,
+        )
+        .await?;
+        let mut members = memberships
+            .into_iter()
+            .filter_map(|(member, parent_role_id, depth)| {
+                roles.remove(&member).map(|role| RoleMember {
+                    role,
+                    parent_role_id,
+                    depth,
+                })
+            })
+            .collect::<Vec<_>>();
+        members.sort_by(|a, b| {
+            a.depth
+                .cmp(&b.depth)
+                .then_with(|| a.role.name.cmp(&b.role.name))
+        });
+        Ok(ListRoleMembersResponse { members })
+    }
+
+    async fn add_role_member(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        role_id: RoleId,
+        member_role_id: RoleId,
+    ) -> Result<()> {
+        // -------------------- AUTHZ --------------------
+        // Members of the member role gain all privileges of the role.
+        let authorizer = context.v1_state.authz;
+        let (update_role, read_member) = futures::join!(
+            authorizer.require_role_action(
+                &request_metadata,
+                role_id,
+                &CatalogRoleAction::CanUpdate
+            ),
+            authorizer.require_role_action(
+                &request_metadata,
+                member_role_id,
+                &CatalogRoleAction::CanRead
+            )
+        );
+        update_role?;
+        read_member?;
+
+        // -------------------- Business Logic --------------------
+        if role_id == member_role_id {
+            return Err(ErrorModel::bad_request(
+                format!("Role {role_id} cannot be a member of itself"),
+                "RoleMembershipCycle",
+                None,
+            )
+            .into());
+        }
+        let roles =
+            load_roles::<C>(vec![role_id, member_role_id], context.v1_state.catalog).await?;
+        let project_of = |id: RoleId| {
+            roles.get(&id).map(|r| r.project_id).ok_or_else(|| {
+                ErrorModel::not_found(format!("Role {id} not found"), "RoleNotFound", None)
+            })
+        };
+        let project_id = project_of(role_id)?;
+        if project_of(member_role_id)? != project_id {
+            return Err(ErrorModel::bad_request(
+                format!("Roles {role_id} and {member_role_id} belong to different projects"),
+                "RoleNotInProject",
+                None,
+            )
+            .into());
+        }
+        // The cycle check of the authorizer and the write of the membership must not
+        // interleave with other membership changes of the project.
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::lock_role_members(project_id, t.transaction()).await?;
+        authorizer
+            .add_role_member(&request_metadata, role_id, member_role_id)
+            .await?;
+        t.commit().await
+    }
+
+    async fn remove_role_member(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        role_id: RoleId,
+        member_role_id: RoleId,
+    ) -> Result<()> {
+        // -------------------- AUTHZ --------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_role_action(&request_metadata, role_id, &CatalogRoleAction::CanUpdate)
+            .await?;
+
+        // -------------------- Business Logic --------------------
+        authorizer
+            .remove_role_member(&request_metadata, role_id, member_role_id)
+            .await
+    }
+}
+
+/// Nesting depth after which recursive member listings stop.
+const MAX_ROLE_NESTING_DEPTH: i32 = 32;
+
+/// Roles with the given ids by id. Roles that do not exist are omitted.
+async fn load_roles<C: Catalog>(
+    role_ids: Vec<RoleId>,
+    catalog_state: C::State,
+) -> Result<HashMap<RoleId, Role>> {
+    let mut roles = HashMap::with_capacity(role_ids.len());
+    let chunk_size = usize::try_from(MAX_PAGE_SIZE).expect("MAX_PAGE_SIZE is positive");
+    for chunk in role_ids.chunks(chunk_size) {
+        let response = C::list_roles(
+            None,
+            Some(chunk.to_vec()),
+            None,
+            RoleListFilter::default(),
+            PaginationQuery {
+                page_size: Some(MAX_PAGE_SIZE),
+                page_token: PageToken::NotSpecified,
+            },
+            catalog_state.clone(),
+        )
+        .await?;
+        roles.extend(response.roles.into_iter().map(|r| (r.id, r)));
+    }
+    Ok(roles)
+}
+
+/// Project a request targets: the project specified in the request, the project of the
+/// authenticated principal or, if enabled, the default project - in this order.
+/// Fails if the identity provider of the principal is bound to another project.
+pub(crate) fn require_project_id(
+    specified_project_id: Option<ProjectIdent>,
+    request_metadata: &RequestMetadata,
+) -> Result<ProjectIdent> {
+    require_project_id_or_else(specified_project_id, request_metadata, || {
+        missing_project_id("MissingProjectId")
+    })
 }
 
-pub(super) fn require_project_id(
+/// Like [`require_project_id`], but fails with the error of `missing` if no project is
+/// found, for endpoints that report a missing project with their own error type.
+pub(crate) fn require_project_id_or_else(
     specified_project_id: Option<ProjectIdent>,
     request_metadata: &RequestMetadata,
+    missing: impl FnOnce() -> ErrorModel,
+) -> Result<ProjectIdent> {
+    let project_id = resolve_project_id(
+        specified_project_id,
+        request_metadata,
+        *DEFAULT_PROJECT_ID,
+        missing,
+    )?;
+    request_metadata.require_project(project_id)?;
+    Ok(project_id)
+}
+
+/// Error for requests without a project while the default project is disabled.
+pub(crate) fn missing_project_id(error_type: &'static str) -> ErrorModel {
+    ErrorModel::bad_request(
+        "No project specified. The default project is disabled \
+        (`LAKEKEEPER__ENABLE_DEFAULT_PROJECT=false`), so the project ID \
+        must be provided with the request.",
+        error_type,
+        None,
+    )
+}
+
+fn resolve_project_id(
+    specified_project_id: Option<ProjectIdent>,
+    request_metadata: &RequestMetadata,
+    default_project_id: Option<ProjectIdent>,
+    missing: impl FnOnce() -> ErrorModel,
 ) -> Result<ProjectIdent> {
     specified_project_id
         .or(request_metadata.auth_details.project_id())
-        .or(*DEFAULT_PROJECT_ID)
-        .ok_or_else(|| {
-            ErrorModel::bad_request(
-                "Project ID is required to create a role".to_string(),
-                "MissingProjectId",
-                None,
-            )
-            .into()
+        .or(default_project_id)
+        .ok_or_else(|| missing().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_project_id() {
+        let metadata = RequestMetadata::new_random();
+        let specified = ProjectIdent::from(uuid::Uuid::now_v7());
+        let default = ProjectIdent::from(uuid::Uuid::nil());
+
+        let missing = || missing_project_id("MissingProjectId");
+
+        assert_eq!(
+            resolve_project_id(Some(specified), &metadata, Some(default), missing).unwrap(),
+            specified
+        );
+        assert_eq!(
+            resolve_project_id(None, &metadata, Some(default), missing).unwrap(),
+            default
+        );
+        let err = resolve_project_id(None, &metadata, None, missing).unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::BAD_REQUEST);
+        assert_eq!(err.error.r#type, "MissingProjectId");
+
+        // Endpoints keep their own error type.
+        let err = resolve_project_id(None, &metadata, None, || {
+            missing_project_id("CreateWarehouseProjectIdMissing")
         })
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "CreateWarehouseProjectIdMissing");
+    }
 }
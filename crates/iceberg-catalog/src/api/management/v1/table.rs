@@ -0,0 +1,1802 @@
+use crate::api::iceberg::types::Prefix;
+use crate::api::iceberg::v1::{
+    CommitTableRequest, CommitTransactionRequest, PageToken, PaginationQuery,
+};
+use crate::api::management::v1::ApiServer;
+use crate::api::ApiContext;
+use crate::catalog::io::{file_exists, read_file, read_metadata_file, IoError};
+use crate::catalog::manifest_cache::read_manifest;
+use crate::catalog::maybe_get_secret;
+use crate::catalog::tables::commit_tables_internal;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{Authorizer, CatalogTableAction, CatalogWarehouseAction};
+use crate::service::storage::{StorageCredential, StorageProfile};
+use crate::service::task_queue::table_inventory_export_queue::{
+    InventoryExportDetails, TableInventoryExportInput,
+};
+use crate::service::task_queue::tabular_relocation_queue::TabularRelocationInput;
+use crate::service::{Catalog, ListFlags, Result, SecretStore, State, TableIdentUuid, Transaction};
+use crate::WarehouseIdent;
+use iceberg::spec::{
+    FormatVersion, PartitionSpec, PartitionSpecRef, Schema, SchemaRef, Snapshot, SnapshotReference,
+    SnapshotRetention, TableMetadata, MAIN_BRANCH,
+};
+use iceberg::{TableRequirement, TableUpdate};
+use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::configs::Location;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr as _;
+
+/// Default validity of presigned metadata URLs.
+const DEFAULT_METADATA_URL_EXPIRY_SECONDS: u64 = 300;
+/// Maximum validity of presigned metadata URLs.
+const MAX_METADATA_URL_EXPIRY_SECONDS: u64 = 3600;
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RollbackTableRequest {
+    /// Snapshot to roll back to. Must be an ancestor of the current snapshot of the branch.
+    /// Exactly one of `snapshot-id` and `timestamp-ms` must be specified.
+    #[serde(default)]
+    pub snapshot_id: Option<i64>,
+    /// Roll back to the latest ancestor of the branch that was created at or before
+    /// this point in time (milliseconds since epoch).
+    #[serde(default)]
+    pub timestamp_ms: Option<i64>,
+    /// Branch to roll back. Defaults to `main`.
+    #[serde(default = "default_ref_name")]
+    pub ref_name: String,
+}
+
+fn default_ref_name() -> String {
+    MAIN_BRANCH.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RollbackTableResponse {
+    /// Snapshot the branch pointed to before the rollback
+    pub previous_snapshot_id: i64,
+    /// Snapshot the branch points to after the rollback
+    pub snapshot_id: i64,
+    /// Location of the new metadata file
+    pub metadata_location: String,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableStatisticsResponse {
+    /// ID of the table
+    pub table_id: uuid::Uuid,
+    /// Number of scans reported by engines
+    pub scan_count: i64,
+    /// Total number of data files returned by reported scans
+    pub scanned_data_files: i64,
+    /// Total size of the data files returned by reported scans in bytes
+    pub scanned_bytes: i64,
+    /// Total planning time of reported scans in milliseconds
+    pub total_planning_duration_ms: i64,
+    /// Number of commits reported by engines
+    pub commit_count: i64,
+    /// Total number of data files added by reported commits
+    pub committed_data_files: i64,
+    /// Total size of the data files added by reported commits in bytes
+    pub committed_bytes: i64,
+    /// Time of the last reported scan
+    pub last_scan_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time of the last reported commit
+    pub last_commit_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time the table was last loaded via the catalog. Sampled, may lag behind
+    /// by `LAKEKEEPER__TABULAR_ACCESS_INTERVAL_SECONDS`.
+    pub last_loaded_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time of the last commit via the catalog, including commits of engines
+    /// that do not report metrics. Sampled like `last-loaded-at`.
+    pub last_catalog_commit_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl axum::response::IntoResponse for TableStatisticsResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+/// Default number of snapshots returned by the table overview.
+const DEFAULT_OVERVIEW_SNAPSHOTS: u32 = 10;
+/// Maximum number of snapshots returned by the table overview.
+const MAX_OVERVIEW_SNAPSHOTS: u32 = 100;
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTableOverviewQuery {
+    /// Number of most recent snapshots to return. Default: 10, maximum: 100
+    #[serde(default = "default_overview_snapshots")]
+    pub snapshots: u32,
+    /// Read the manifest list of the current snapshot and return its statistics.
+    /// Default: false
+    #[serde(default)]
+    pub include_manifest_statistics: bool,
+}
+
+fn default_overview_snapshots() -> u32 {
+    DEFAULT_OVERVIEW_SNAPSHOTS
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableOverview {
+    /// ID of the table
+    pub table_id: uuid::Uuid,
+    /// Name of the table
+    pub name: String,
+    /// Namespace of the table
+    pub namespace: Vec<String>,
+    /// Base location of the table
+    pub location: String,
+    /// Location of the current metadata file
+    pub metadata_location: String,
+    /// Iceberg format version of the table
+    #[schema(value_type = i32)]
+    pub format_version: FormatVersion,
+    /// Time of the last update of the table metadata (milliseconds since epoch)
+    pub last_updated_ms: i64,
+    /// Current snapshot of the main branch. Not set if the table has no snapshot yet.
+    pub current_snapshot_id: Option<i64>,
+    /// Total number of snapshots of the table
+    pub snapshot_count: i64,
+    /// Current schema
+    #[schema(value_type = Object)]
+    pub schema: Schema,
+    /// Default partition spec
+    #[schema(value_type = Object)]
+    pub partition_spec: PartitionSpec,
+    /// Table properties
+    pub properties: HashMap<String, String>,
+    /// Most recent snapshots, newest first
+    #[schema(value_type = Vec<Object>)]
+    pub snapshots: Vec<Snapshot>,
+    /// Manifest list of the current snapshot of the main branch
+    pub current_manifest_list: Option<String>,
+    /// Statistics of the manifest list of the current snapshot.
+    /// Only set if requested with `includeManifestStatistics` and the table has a snapshot.
+    pub manifest_statistics: Option<ManifestListStatistics>,
+}
+
+/// Live files and rows of a snapshot, as recorded in its manifest list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ManifestListStatistics {
+    /// Number of manifests tracking data files
+    pub data_manifests: u64,
+    /// Number of manifests tracking delete files
+    pub delete_manifests: u64,
+    /// Number of added and existing data files
+    pub data_files: u64,
+    /// Number of added and existing delete files
+    pub delete_files: u64,
+    /// Number of rows in added and existing data files
+    pub data_rows: u64,
+}
+
+/// Sum the file and row counts of the manifests in a manifest list.
+/// Format version 1 manifest lists have no `content` field and only track data manifests,
+/// they also use `*_data_files_count` instead of `*_files_count`.
+fn manifest_list_statistics(
+    manifest_list: &[u8],
+) -> std::result::Result<ManifestListStatistics, apache_avro::Error> {
+    let mut statistics = ManifestListStatistics::default();
+    for entry in apache_avro::Reader::new(manifest_list)? {
+        let apache_avro::types::Value::Record(fields) = entry? else {
+            continue;
+        };
+        let mut is_delete_manifest = false;
+        let (mut files, mut rows) = (0, 0);
+        for (name, value) in fields {
+            let count = match value {
+                apache_avro::types::Value::Int(v) => u64::try_from(v).unwrap_or_default(),
+                apache_avro::types::Value::Long(v) => u64::try_from(v).unwrap_or_default(),
+                apache_avro::types::Value::Union(_, v) => match *v {
+                    apache_avro::types::Value::Int(v) => u64::try_from(v).unwrap_or_default(),
+                    apache_avro::types::Value::Long(v) => u64::try_from(v).unwrap_or_default(),
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            match name.as_str() {
+                "content" => is_delete_manifest = count != 0,
+                "added_files_count"
+                | "existing_files_count"
+                | "added_data_files_count"
+                | "existing_data_files_count" => files += count,
+                "added_rows_count" | "existing_rows_count" => rows += count,
+                _ => {}
+            }
+        }
+        if is_delete_manifest {
+            statistics.delete_manifests += 1;
+            statistics.delete_files += files;
+        } else {
+            statistics.data_manifests += 1;
+            statistics.data_files += files;
+            statistics.data_rows += rows;
+        }
+    }
+    Ok(statistics)
+}
+
+impl axum::response::IntoResponse for TableOverview {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTableMetadataUrlQuery {
+    /// Validity of the URLs in seconds. Default: 300, maximum: 3600
+    #[serde(default = "default_metadata_url_expiry")]
+    pub expires_in_seconds: u64,
+    /// Also sign the manifest list of the current snapshot. Default: false
+    #[serde(default)]
+    pub include_manifest_list: bool,
+}
+
+fn default_metadata_url_expiry() -> u64 {
+    DEFAULT_METADATA_URL_EXPIRY_SECONDS
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableMetadataUrlResponse {
+    /// Location of the current metadata file
+    pub metadata_location: String,
+    /// Presigned URL to download the current metadata file
+    pub metadata_url: String,
+    /// Location of the manifest list of the current snapshot
+    pub manifest_list_location: Option<String>,
+    /// Presigned URL to download the manifest list of the current snapshot
+    pub manifest_list_url: Option<String>,
+    /// Point in time after which the URLs are no longer valid
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl axum::response::IntoResponse for TableMetadataUrlResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTableMetadataAsOfQuery {
+    /// Return the metadata that was current at this point in time (milliseconds since epoch).
+    /// Exactly one of `timestampMs` and `snapshotId` must be specified.
+    #[serde(default)]
+    pub timestamp_ms: Option<i64>,
+    /// Return the metadata of the commit that made this snapshot the current snapshot.
+    #[serde(default)]
+    pub snapshot_id: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableMetadataAsOfResponse {
+    /// Location of the metadata file that was current at the requested point in time
+    pub metadata_location: String,
+    /// Requested snapshot, or the snapshot that was current at the requested point in time.
+    /// Not set if the table had no snapshot yet.
+    pub snapshot_id: Option<i64>,
+    /// Schema of the snapshot
+    #[schema(value_type = Object)]
+    pub schema: SchemaRef,
+    /// Default partition spec at the requested point in time
+    #[schema(value_type = Object)]
+    pub partition_spec: PartitionSpecRef,
+    /// Full table metadata at the requested point in time
+    #[schema(value_type = Object)]
+    pub metadata: TableMetadata,
+}
+
+impl axum::response::IntoResponse for TableMetadataAsOfResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RelocateTableRequest {
+    /// New location of the table, i.e. `s3://new-bucket/path/to/table`.
+    /// Must be an empty location within the storage of the warehouse.
+    pub location: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, utoipa::ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum InventoryFormat {
+    Csv,
+    Parquet,
+}
+
+impl InventoryFormat {
+    #[must_use]
+    pub fn extension(self) -> &'static str {
+        match self {
+            InventoryFormat::Csv => "csv",
+            InventoryFormat::Parquet => "parquet",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExportTableInventoryRequest {
+    /// Location the inventory is written to, i.e. `s3://bucket/inventories/orders`.
+    /// Must be within the storage of the warehouse and must not overlap with
+    /// the location of a table or view.
+    pub location: String,
+    /// Format of the inventory files. Default: `parquet`
+    #[serde(default = "default_inventory_format")]
+    pub format: InventoryFormat,
+}
+
+fn default_inventory_format() -> InventoryFormat {
+    InventoryFormat::Parquet
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ExportTableInventoryResponse {
+    /// ID of the export, used to get its status
+    pub export_id: uuid::Uuid,
+}
+
+impl axum::response::IntoResponse for ExportTableInventoryResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::ACCEPTED, axum::Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, utoipa::ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum InventoryExportStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetTableInventoryExportResponse {
+    /// ID of the export
+    pub export_id: uuid::Uuid,
+    /// ID of the exported table
+    pub table_id: uuid::Uuid,
+    /// Location the inventory is written to
+    pub location: String,
+    pub format: InventoryFormat,
+    pub status: InventoryExportStatus,
+    /// Number of times the export was started
+    pub attempt: i32,
+    /// Error of the last failed attempt
+    pub last_error: Option<String>,
+    /// Number of data and delete files in the inventory. Set once the export is done.
+    pub data_file_count: Option<i64>,
+    /// Inventory files written by the export. Set once the export is done.
+    pub inventory_files: Option<Vec<String>>,
+    /// Time the export was requested
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl axum::response::IntoResponse for GetTableInventoryExportResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl From<InventoryExportDetails> for GetTableInventoryExportResponse {
+    fn from(export: InventoryExportDetails) -> Self {
+        let (data_file_count, inventory_files) = match export.result {
+            Some(result) => (Some(result.data_file_count), Some(result.inventory_files)),
+            None => (None, None),
+        };
+        Self {
+            export_id: export.export_id,
+            table_id: *export.table_id,
+            location: export.target_location,
+            format: export.format,
+            status: export.status,
+            attempt: export.attempt,
+            last_error: export.last_error,
+            data_file_count,
+            inventory_files,
+            created_at: export.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, utoipa::ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TableCheckName {
+    /// The current metadata file exists in storage
+    MetadataFileExists,
+    /// The current metadata file can be read and parsed
+    MetadataFileParses,
+    /// The metadata file matches the metadata stored in the catalog
+    MetadataMatchesCatalog,
+    /// The manifest list of the current snapshot can be read
+    ManifestListReadable,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, utoipa::ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TableCheckStatus {
+    Passed,
+    Failed,
+    /// The check was not run because a check it depends on failed,
+    /// or because there is nothing to check.
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableCheckResult {
+    pub name: TableCheckName,
+    pub status: TableCheckStatus,
+    /// Details on why the check failed or was skipped
+    pub message: Option<String>,
+}
+
+impl TableCheckResult {
+    fn passed(name: TableCheckName) -> Self {
+        Self {
+            name,
+            status: TableCheckStatus::Passed,
+            message: None,
+        }
+    }
+
+    fn failed(name: TableCheckName, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: TableCheckStatus::Failed,
+            message: Some(message.into()),
+        }
+    }
+
+    fn skipped(name: TableCheckName, message: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: TableCheckStatus::Skipped,
+            message: Some(message.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableCheckResponse {
+    /// ID of the checked table
+    pub table_id: uuid::Uuid,
+    /// Location of the current metadata file according to the catalog
+    pub metadata_location: String,
+    /// True if no check failed
+    pub healthy: bool,
+    /// Results of the individual checks, in the order they were run
+    pub checks: Vec<TableCheckResult>,
+}
+
+impl axum::response::IntoResponse for TableCheckResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, axum::Json(self)).into_response()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn rollback_table(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: RollbackTableRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RollbackTableResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, &CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let mut tables = C::load_tables(warehouse_id, [table_id], false, t.transaction()).await?;
+        t.commit().await?;
+        let metadata = tables
+            .remove(&table_id)
+            .ok_or_else(|| {
+                ErrorModel::not_found(format!("Table {table_id} not found"), "TableNotFound", None)
+            })?
+            .table_metadata;
+
+        let (current_ref, target_snapshot_id) = resolve_rollback_target(&metadata, &request)?;
+
+        let commit = CommitTableRequest {
+            identifier: Some(table.table.clone()),
+            requirements: vec![
+                TableRequirement::UuidMatch {
+                    uuid: metadata.uuid(),
+                },
+                // Fail if the branch moved between loading and committing
+                TableRequirement::RefSnapshotIdMatch {
+                    r#ref: request.ref_name.clone(),
+                    snapshot_id: Some(current_ref.snapshot_id),
+                },
+            ],
+            updates: vec![TableUpdate::SetSnapshotRef {
+                ref_name: request.ref_name.clone(),
+                reference: SnapshotReference {
+                    snapshot_id: target_snapshot_id,
+                    retention: current_ref.retention.clone(),
+                },
+            }],
+        };
+
+        let commits = commit_tables_internal(
+            Some(Prefix(warehouse_id.to_string())),
+            CommitTransactionRequest {
+                table_changes: vec![commit],
+            },
+            context,
+            request_metadata.clone(),
+        )
+        .await?;
+        let Some(commit) = commits.into_iter().next() else {
+            return Err(ErrorModel::internal(
+                "No new metadata returned by backend",
+                "NoNewMetadataReturned",
+                None,
+            )
+            .into());
+        };
+
+        tracing::info!(
+            actor = %request_metadata.actor(),
+            %warehouse_id,
+            %table_id,
+            ref_name = %request.ref_name,
+            previous_snapshot_id = current_ref.snapshot_id,
+            snapshot_id = target_snapshot_id,
+            "Rolled back table {}",
+            table.table
+        );
+
+        Ok(RollbackTableResponse {
+            previous_snapshot_id: current_ref.snapshot_id,
+            snapshot_id: target_snapshot_id,
+            metadata_location: commit.new_metadata_location.to_string(),
+        })
+    }
+
+    async fn relocate_table(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: RelocateTableRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(&request_metadata, table, &CatalogTableAction::CanCommit)
+            .await?;
+
+        // ------------------- Validations -------------------
+        if table.metadata_location.is_none() {
+            return Err(ErrorModel::bad_request(
+                format!("Table {table_id} is staged and cannot be relocated"),
+                "TableStaged",
+                None,
+            )
+            .into());
+        }
+        let target_location = Location::from_str(&request.location).map_err(|e| {
+            ErrorModel::bad_request(
+                format!("Invalid location {}", request.location),
+                "InvalidLocation",
+                Some(Box::new(e)),
+            )
+        })?;
+        table
+            .storage_profile
+            .require_allowed_location(&target_location)?;
+
+        let source = format!("{}/", table.location.trim_end_matches('/'));
+        let target = format!("{}/", target_location.as_str().trim_end_matches('/'));
+        if source.starts_with(&target) || target.starts_with(&source) {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Target location {target_location} overlaps with the current location {}",
+                    table.location
+                ),
+                "InvalidLocation",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let usage = C::list_location_usage(
+            warehouse_id,
+            &target_location,
+            t.transaction(),
+            PaginationQuery {
+                page_token: PageToken::Empty,
+                page_size: Some(1),
+            },
+        )
+        .await?;
+        t.commit().await?;
+        if !usage.is_empty() {
+            return Err(ErrorModel::conflict(
+                format!("Location {target_location} is already used by another tabular"),
+                "LocationAlreadyTaken",
+                None,
+            )
+            .into());
+        }
+
+        context
+            .v1_state
+            .queues
+            .queue_tabular_relocation(TabularRelocationInput {
+                table_id,
+                warehouse_ident: warehouse_id,
+                source_location: table.location.clone(),
+                target_location: target_location.to_string(),
+            })
+            .await?;
+
+        tracing::info!(
+            actor = %request_metadata.actor(),
+            %warehouse_id,
+            %table_id,
+            source_location = %table.location,
+            %target_location,
+            "Scheduled relocation of table {}",
+            table.table
+        );
+
+        Ok(())
+    }
+
+    async fn get_table_metadata_url(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        query: GetTableMetadataUrlQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableMetadataUrlResponse> {
+        // ------------------- Validations -------------------
+        if query.expires_in_seconds == 0
+            || query.expires_in_seconds > MAX_METADATA_URL_EXPIRY_SECONDS
+        {
+            return Err(ErrorModel::bad_request(
+                format!("expiresInSeconds must be between 1 and {MAX_METADATA_URL_EXPIRY_SECONDS}"),
+                "InvalidExpiry",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let metadata_location = table.metadata_location.clone().ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Table {table_id} has no metadata yet"),
+                "TableStaged",
+                None,
+            )
+        })?;
+
+        let manifest_list_location = if query.include_manifest_list {
+            let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+            let mut tables =
+                C::load_tables(warehouse_id, [table_id], false, t.transaction()).await?;
+            t.commit().await?;
+            tables
+                .remove(&table_id)
+                .ok_or_else(|| {
+                    ErrorModel::not_found(
+                        format!("Table {table_id} not found"),
+                        "TableNotFound",
+                        None,
+                    )
+                })?
+                .table_metadata
+                .current_snapshot()
+                .map(|s| s.manifest_list().to_string())
+        } else {
+            None
+        };
+
+        let secret =
+            maybe_get_secret(table.storage_secret_ident, &context.v1_state.secrets).await?;
+        let expires_in = std::time::Duration::from_secs(query.expires_in_seconds);
+        let expires_at = chrono::Utc::now()
+            + chrono::Duration::seconds(
+                i64::try_from(query.expires_in_seconds).unwrap_or(i64::MAX),
+            );
+
+        let metadata_url = presign_location(
+            &table.storage_profile,
+            secret.as_ref(),
+            &metadata_location,
+            expires_in,
+        )
+        .await?;
+        let manifest_list_url = match &manifest_list_location {
+            Some(location) => Some(
+                presign_location(
+                    &table.storage_profile,
+                    secret.as_ref(),
+                    location,
+                    expires_in,
+                )
+                .await?,
+            ),
+            None => None,
+        };
+
+        tracing::info!(
+            actor = %request_metadata.actor(),
+            %warehouse_id,
+            %table_id,
+            expires_in_seconds = query.expires_in_seconds,
+            include_manifest_list = manifest_list_url.is_some(),
+            "Issued presigned metadata URLs for table {}",
+            table.table
+        );
+
+        Ok(TableMetadataUrlResponse {
+            metadata_location,
+            metadata_url: metadata_url.to_string(),
+            manifest_list_location,
+            manifest_list_url: manifest_list_url.map(|u| u.to_string()),
+            expires_at,
+        })
+    }
+
+    async fn get_table_metadata_as_of(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        query: GetTableMetadataAsOfQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableMetadataAsOfResponse> {
+        // ------------------- Validations -------------------
+        let as_of = match (query.timestamp_ms, query.snapshot_id) {
+            (Some(timestamp_ms), None) => AsOf::Timestamp(timestamp_ms),
+            (None, Some(snapshot_id)) => AsOf::Snapshot(snapshot_id),
+            _ => {
+                return Err(ErrorModel::bad_request(
+                    "Exactly one of timestampMs and snapshotId must be specified",
+                    "InvalidTimeTravelTarget",
+                    None,
+                )
+                .into())
+            }
+        };
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let current_metadata_location = table.metadata_location.clone().ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Table {table_id} has no metadata yet"),
+                "TableStaged",
+                None,
+            )
+        })?;
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let mut tables = C::load_tables(warehouse_id, [table_id], false, t.transaction()).await?;
+        t.commit().await?;
+        let current_metadata = tables
+            .remove(&table_id)
+            .ok_or_else(|| {
+                ErrorModel::not_found(format!("Table {table_id} not found"), "TableNotFound", None)
+            })?
+            .table_metadata;
+
+        let metadata_location =
+            metadata_location_as_of(&current_metadata, &current_metadata_location, as_of)?;
+        let metadata = if metadata_location == current_metadata_location.as_str() {
+            current_metadata
+        } else {
+            let location = Location::from_str(&metadata_location).map_err(|e| {
+                ErrorModel::internal(
+                    format!("Invalid location in metadata log: {metadata_location}"),
+                    "InvalidMetadataLocation",
+                    Some(Box::new(e)),
+                )
+            })?;
+            table.storage_profile.require_allowed_location(&location)?;
+            let secret =
+                maybe_get_secret(table.storage_secret_ident, &context.v1_state.secrets).await?;
+            let file_io = table.storage_profile.file_io(secret.as_ref())?;
+            read_metadata_file(&file_io, &location).await?
+        };
+
+        let snapshot = match as_of {
+            AsOf::Timestamp(_) => metadata.current_snapshot(),
+            AsOf::Snapshot(snapshot_id) => metadata.snapshot_by_id(snapshot_id),
+        };
+        let schema = snapshot
+            .and_then(|s| s.schema_id())
+            .and_then(|schema_id| metadata.schema_by_id(schema_id))
+            .unwrap_or_else(|| metadata.current_schema())
+            .clone();
+
+        Ok(TableMetadataAsOfResponse {
+            metadata_location,
+            snapshot_id: snapshot.map(|s| s.snapshot_id()),
+            schema,
+            partition_spec: metadata.default_partition_spec().clone(),
+            metadata,
+        })
+    }
+
+    async fn get_table_statistics(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableStatisticsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let metrics = C::get_table_metrics(table_id, context.v1_state.catalog.clone()).await?;
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let access = C::get_tabular_access(&[*table_id], t.transaction())
+            .await?
+            .remove(&*table_id)
+            .unwrap_or_default();
+        t.commit().await?;
+
+        Ok(TableStatisticsResponse {
+            table_id: *table_id,
+            scan_count: metrics.scan_count,
+            scanned_data_files: metrics.scanned_data_files,
+            scanned_bytes: metrics.scanned_bytes,
+            total_planning_duration_ms: metrics.total_planning_duration_ms,
+            commit_count: metrics.commit_count,
+            committed_data_files: metrics.committed_data_files,
+            committed_bytes: metrics.committed_bytes,
+            last_scan_at: metrics.last_scan_at,
+            last_commit_at: metrics.last_commit_at,
+            last_loaded_at: access.last_read_at,
+            last_catalog_commit_at: access.last_commit_at,
+        })
+    }
+
+    async fn get_table_overview(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        query: GetTableOverviewQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableOverview> {
+        // ------------------- Validations -------------------
+        if query.snapshots > MAX_OVERVIEW_SNAPSHOTS {
+            return Err(ErrorModel::bad_request(
+                format!("snapshots must not exceed {MAX_OVERVIEW_SNAPSHOTS}"),
+                "InvalidSnapshotLimit",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let overview =
+            C::get_table_overview(warehouse_id, table_id, query.snapshots, t.transaction()).await?;
+        t.commit().await?;
+
+        let mut overview = overview.ok_or_else(|| {
+            ErrorModel::not_found(format!("Table {table_id} not found"), "TableNotFound", None)
+        })?;
+
+        if let (true, Some(manifest_list)) = (
+            query.include_manifest_statistics,
+            overview.current_manifest_list.as_deref(),
+        ) {
+            let location = Location::from_str(manifest_list).map_err(|e| {
+                ErrorModel::internal(
+                    format!("Failed to parse manifest list location {manifest_list}"),
+                    "ParseError",
+                    Some(Box::new(e)),
+                )
+            })?;
+            let secret =
+                maybe_get_secret(table.storage_secret_ident, &context.v1_state.secrets).await?;
+            let file_io = table.storage_profile.file_io(secret.as_ref())?;
+            let content = read_manifest(warehouse_id, &file_io, &location, None).await?;
+            let statistics = manifest_list_statistics(&content).map_err(|e| {
+                ErrorModel::internal(
+                    format!("Failed to parse manifest list {manifest_list}"),
+                    "ManifestListParseError",
+                    Some(Box::new(e)),
+                )
+            })?;
+            overview.manifest_statistics = Some(statistics);
+        }
+
+        Ok(overview)
+    }
+
+    async fn export_table_inventory(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        request: ExportTableInventoryRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ExportTableInventoryResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        // The catalog writes the inventory with the storage credential of the warehouse
+        // to a location chosen by the caller, which must be allowed to manage the storage.
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        // Partition values in the inventory are data of the table.
+        let table = authorizer
+            .require_table_action(&request_metadata, table, &CatalogTableAction::CanReadData)
+            .await?;
+
+        // ------------------- Validations -------------------
+        let target_location = Location::from_str(&request.location).map_err(|e| {
+            ErrorModel::bad_request(
+                format!("Invalid location {}", request.location),
+                "InvalidLocation",
+                Some(Box::new(e)),
+            )
+        })?;
+        table
+            .storage_profile
+            .require_allowed_location(&target_location)?;
+
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let usage = C::list_location_usage(
+            warehouse_id,
+            &target_location,
+            t.transaction(),
+            PaginationQuery {
+                page_token: PageToken::Empty,
+                page_size: Some(1),
+            },
+        )
+        .await?;
+        t.commit().await?;
+        if !usage.is_empty() {
+            return Err(ErrorModel::conflict(
+                format!("Location {target_location} overlaps with the location of a tabular"),
+                "LocationAlreadyTaken",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- Business Logic -------------------
+        let export_id = uuid::Uuid::now_v7();
+        context
+            .v1_state
+            .queues
+            .queue_table_inventory_export(TableInventoryExportInput {
+                export_id,
+                table_id,
+                warehouse_ident: warehouse_id,
+                target_location: target_location.to_string(),
+                format: request.format,
+            })
+            .await?;
+
+        tracing::info!(
+            actor = %request_metadata.actor(),
+            %warehouse_id,
+            %table_id,
+            %export_id,
+            %target_location,
+            "Scheduled inventory export of table {}",
+            table.table
+        );
+
+        Ok(ExportTableInventoryResponse { export_id })
+    }
+
+    async fn get_table_inventory_export(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        export_id: uuid::Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetTableInventoryExportResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        authorizer
+            .require_table_action(
+                &request_metadata,
+                Ok(Some(table_id)),
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        context
+            .v1_state
+            .queues
+            .get_table_inventory_export(warehouse_id, table_id, export_id)
+            .await?
+            .map(Into::into)
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Inventory export {export_id} of table {table_id} not found"),
+                    "InventoryExportNotFound",
+                    None,
+                )
+                .into()
+            })
+    }
+
+    async fn check_table(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableCheckResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz.clone();
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUse,
+            )
+            .await?;
+        let table = C::get_table_metadata_by_id(
+            warehouse_id,
+            table_id,
+            ListFlags::default(),
+            context.v1_state.catalog.clone(),
+        )
+        .await;
+        let table = authorizer
+            .require_table_action(
+                &request_metadata,
+                table,
+                &CatalogTableAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog.clone()).await?;
+        let mut tables = C::load_tables(warehouse_id, [table_id], false, t.transaction()).await?;
+        t.commit().await?;
+        let loaded = tables.remove(&table_id).ok_or_else(|| {
+            ErrorModel::not_found(format!("Table {table_id} not found"), "TableNotFound", None)
+        })?;
+        let metadata_location = loaded.metadata_location.ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Table {table_id} has no metadata yet"),
+                "TableStaged",
+                None,
+            )
+        })?;
+        let catalog_metadata = loaded.table_metadata;
+
+        let secret =
+            maybe_get_secret(table.storage_secret_ident, &context.v1_state.secrets).await?;
+        let file_io = table.storage_profile.file_io(secret.as_ref())?;
+
+        let mut checks = check_metadata_file(&file_io, &metadata_location, &catalog_metadata).await;
+        checks.push(check_manifest_list(&file_io, &table.storage_profile, &catalog_metadata).await);
+
+        Ok(TableCheckResponse {
+            table_id: *table_id,
+            metadata_location: metadata_location.to_string(),
+            healthy: checks
+                .iter()
+                .all(|check| check.status != TableCheckStatus::Failed),
+            checks,
+        })
+    }
+}
+
+/// Verify that the metadata file the catalog points to exists, parses and describes
+/// the same table state as the metadata stored in the catalog.
+/// Each check is skipped if the previous one failed.
+async fn check_metadata_file(
+    file_io: &iceberg::io::FileIO,
+    metadata_location: &Location,
+    catalog_metadata: &TableMetadata,
+) -> Vec<TableCheckResult> {
+    let exists = match file_exists(file_io, metadata_location).await {
+        Ok(true) => TableCheckResult::passed(TableCheckName::MetadataFileExists),
+        Ok(false) => TableCheckResult::failed(
+            TableCheckName::MetadataFileExists,
+            format!("Metadata file {metadata_location} does not exist"),
+        ),
+        Err(e) => {
+            TableCheckResult::failed(TableCheckName::MetadataFileExists, io_error_message(&e))
+        }
+    };
+    if exists.status == TableCheckStatus::Failed {
+        return vec![
+            exists,
+            TableCheckResult::skipped(
+                TableCheckName::MetadataFileParses,
+                "Metadata file does not exist",
+            ),
+            TableCheckResult::skipped(
+                TableCheckName::MetadataMatchesCatalog,
+                "Metadata file does not exist",
+            ),
+        ];
+    }
+
+    let file_metadata = match read_metadata_file(file_io, metadata_location).await {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            return vec![
+                exists,
+                TableCheckResult::failed(TableCheckName::MetadataFileParses, io_error_message(&e)),
+                TableCheckResult::skipped(
+                    TableCheckName::MetadataMatchesCatalog,
+                    "Metadata file could not be parsed",
+                ),
+            ]
+        }
+    };
+
+    let mismatches = metadata_mismatches(&file_metadata, catalog_metadata);
+    let matches = if mismatches.is_empty() {
+        TableCheckResult::passed(TableCheckName::MetadataMatchesCatalog)
+    } else {
+        TableCheckResult::failed(
+            TableCheckName::MetadataMatchesCatalog,
+            mismatches.join("; "),
+        )
+    };
+    vec![
+        exists,
+        TableCheckResult::passed(TableCheckName::MetadataFileParses),
+        matches,
+    ]
+}
+
+/// Read the manifest list of the current snapshot as served by the catalog.
+/// The manifest cache is bypassed on purpose, the check is about the storage.
+async fn check_manifest_list(
+    file_io: &iceberg::io::FileIO,
+    storage_profile: &StorageProfile,
+    catalog_metadata: &TableMetadata,
+) -> TableCheckResult {
+    let name = TableCheckName::ManifestListReadable;
+    let Some(snapshot) = catalog_metadata.current_snapshot() else {
+        return TableCheckResult::skipped(name, "Table has no current snapshot");
+    };
+    let manifest_list = snapshot.manifest_list();
+    let location = match Location::from_str(manifest_list) {
+        Ok(location) => location,
+        Err(e) => {
+            return TableCheckResult::failed(
+                name,
+                format!("Invalid manifest list location {manifest_list}: {e}"),
+            )
+        }
+    };
+    if let Err(e) = storage_profile.require_allowed_location(&location) {
+        return TableCheckResult::failed(name, e.to_string());
+    }
+    let content = match read_file(file_io, &location).await {
+        Ok(content) => content,
+        Err(e) => return TableCheckResult::failed(name, io_error_message(&e)),
+    };
+    let manifests = apache_avro::Reader::new(&content[..])
+        .and_then(|reader| reader.collect::<std::result::Result<Vec<_>, _>>());
+    match manifests {
+        Ok(_) => TableCheckResult::passed(name),
+        Err(e) => TableCheckResult::failed(
+            name,
+            format!("Manifest list {manifest_list} is not a valid avro file: {e}"),
+        ),
+    }
+}
+
+/// Storage errors only say which operation failed, the cause is in the source.
+fn io_error_message(e: &IoError) -> String {
+    match std::error::Error::source(e) {
+        Some(source) => format!("{e} {source}"),
+        None => e.to_string(),
+    }
+}
+
+/// Fields of the metadata file that differ from the metadata stored in the catalog.
+fn metadata_mismatches(file: &TableMetadata, catalog: &TableMetadata) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    if file.uuid() != catalog.uuid() {
+        mismatches.push(format!(
+            "table-uuid is {} in the metadata file but {} in the catalog",
+            file.uuid(),
+            catalog.uuid()
+        ));
+    }
+    if file.last_updated_ms() != catalog.last_updated_ms() {
+        mismatches.push(format!(
+            "last-updated-ms is {} in the metadata file but {} in the catalog",
+            file.last_updated_ms(),
+            catalog.last_updated_ms()
+        ));
+    }
+    if file.last_sequence_number() != catalog.last_sequence_number() {
+        mismatches.push(format!(
+            "last-sequence-number is {} in the metadata file but {} in the catalog",
+            file.last_sequence_number(),
+            catalog.last_sequence_number()
+        ));
+    }
+    if file.current_snapshot_id() != catalog.current_snapshot_id() {
+        mismatches.push(format!(
+            "current-snapshot-id is {:?} in the metadata file but {:?} in the catalog",
+            file.current_snapshot_id(),
+            catalog.current_snapshot_id()
+        ));
+    }
+    if file.current_schema_id() != catalog.current_schema_id() {
+        mismatches.push(format!(
+            "current-schema-id is {} in the metadata file but {} in the catalog",
+            file.current_schema_id(),
+            catalog.current_schema_id()
+        ));
+    }
+    mismatches
+}
+
+async fn presign_location(
+    storage_profile: &StorageProfile,
+    secret: Option<&StorageCredential>,
+    location: &str,
+    expires_in: std::time::Duration,
+) -> Result<url::Url> {
+    let location = Location::from_str(location).map_err(|e| {
+        ErrorModel::internal(
+            format!("Invalid location {location}"),
+            "InvalidLocation",
+            Some(Box::new(e)),
+        )
+    })?;
+    // The manifest list location is taken from the table metadata,
+    // only sign locations that belong to the warehouse.
+    storage_profile.require_allowed_location(&location)?;
+    Ok(storage_profile
+        .presign_download_url(secret, &location, expires_in)
+        .await?)
+}
+
+/// Point in time requested by [`Service::get_table_metadata_as_of`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AsOf {
+    Timestamp(i64),
+    Snapshot(i64),
+}
+
+/// Find the metadata file that was current at `as_of` in the metadata log of the table.
+///
+/// For snapshots, this is the first metadata file written after the snapshot became the
+/// current snapshot of the table, or after it was created if it never was.
+fn metadata_location_as_of(
+    metadata: &TableMetadata,
+    metadata_location: &str,
+    as_of: AsOf,
+) -> Result<String> {
+    let files = metadata
+        .metadata_log()
+        .iter()
+        .map(|log| (log.metadata_file.as_str(), log.timestamp_ms))
+        .chain(std::iter::once((
+            metadata_location,
+            metadata.last_updated_ms(),
+        )))
+        .collect::<Vec<_>>();
+
+    let location = match as_of {
+        AsOf::Timestamp(timestamp_ms) => last_file_before(&files, timestamp_ms),
+        AsOf::Snapshot(snapshot_id) => {
+            let snapshot = metadata.snapshot_by_id(snapshot_id).ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Snapshot {snapshot_id} does not exist"),
+                    "SnapshotNotFound",
+                    None,
+                )
+            })?;
+            let became_current_ms = metadata
+                .history()
+                .iter()
+                .find(|log| log.snapshot_id == snapshot_id)
+                .map_or(snapshot.timestamp_ms(), |log| log.timestamp_ms);
+            first_file_after(&files, became_current_ms)
+        }
+    };
+
+    location.map(ToString::to_string).ok_or_else(|| {
+        ErrorModel::not_found(
+            "No metadata file of the table is available for the requested point in time. The table was created later or the metadata file was removed from the metadata log.",
+            "MetadataNotAvailable",
+            None,
+        )
+        .into()
+    })
+}
+
+/// `files` are ordered by the time they were written.
+fn last_file_before<'a>(files: &[(&'a str, i64)], timestamp_ms: i64) -> Option<&'a str> {
+    files
+        .iter()
+        .take_while(|(_, written_ms)| *written_ms <= timestamp_ms)
+        .last()
+        .map(|(file, _)| *file)
+}
+
+/// `files` are ordered by the time they were written.
+fn first_file_after<'a>(files: &[(&'a str, i64)], timestamp_ms: i64) -> Option<&'a str> {
+    files
+        .iter()
+        .find(|(_, written_ms)| *written_ms >= timestamp_ms)
+        .map(|(file, _)| *file)
+}
+
+/// Determine the current state of the branch and the snapshot to roll back to.
+fn resolve_rollback_target(
+    metadata: &TableMetadata,
+    request: &RollbackTableRequest,
+) -> Result<(SnapshotReference, i64)> {
+    let current_ref = metadata
+        .refs()
+        .get(&request.ref_name)
+        .cloned()
+        .ok_or_else(|| {
+            ErrorModel::not_found(
+                format!("Ref {} does not exist", request.ref_name),
+                "RefNotFound",
+                None,
+            )
+        })?;
+    if matches!(current_ref.retention, SnapshotRetention::Tag { .. }) {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Ref {} is a tag, only branches can be rolled back",
+                request.ref_name
+            ),
+            "RefIsNotABranch",
+            None,
+        )
+        .into());
+    }
+
+    let target_snapshot_id = match (request.snapshot_id, request.timestamp_ms) {
+        (Some(snapshot_id), None) => {
+            if metadata.snapshot_by_id(snapshot_id).is_none() {
+                return Err(ErrorModel::not_found(
+                    format!("Snapshot {snapshot_id} does not exist"),
+                    "SnapshotNotFound",
+                    None,
+                )
+                .into());
+            }
+            // Rolling back to a snapshot of a different lineage would silently
+            // replace the branch history instead of rewinding it.
+            if !is_ancestor_of(metadata, snapshot_id, current_ref.snapshot_id) {
+                return Err(ErrorModel::bad_request(
+                    format!(
+                        "Snapshot {snapshot_id} is not an ancestor of the current snapshot {} of ref {}",
+                        current_ref.snapshot_id, request.ref_name
+                    ),
+                    "SnapshotNotAncestor",
+                    None,
+                )
+                .into());
+            }
+            snapshot_id
+        }
+        (None, Some(timestamp_ms)) => {
+            // Walk the ancestors of the branch head and pick the first one old enough
+            let mut snapshot = metadata.snapshot_by_id(current_ref.snapshot_id);
+            loop {
+                match snapshot {
+                    Some(s) if s.timestamp_ms() <= timestamp_ms => break s.snapshot_id(),
+                    Some(s) => {
+                        snapshot = s
+                            .parent_snapshot_id()
+                            .and_then(|id| metadata.snapshot_by_id(id));
+                    }
+                    None => {
+                        return Err(ErrorModel::not_found(
+                            format!(
+                                "No snapshot of ref {} is older than timestamp {timestamp_ms}",
+                                request.ref_name
+                            ),
+                            "SnapshotNotFound",
+                            None,
+                        )
+                        .into())
+                    }
+                }
+            }
+        }
+        _ => {
+            return Err(ErrorModel::bad_request(
+                "Exactly one of snapshot-id and timestamp-ms must be specified",
+                "InvalidRollbackTarget",
+                None,
+            )
+            .into())
+        }
+    };
+
+    Ok((current_ref, target_snapshot_id))
+}
+
+/// Whether `snapshot_id` is `head` or one of its ancestors.
+fn is_ancestor_of(metadata: &TableMetadata, snapshot_id: i64, head: i64) -> bool {
+    let mut snapshot = metadata.snapshot_by_id(head);
+    while let Some(s) = snapshot {
+        if s.snapshot_id() == snapshot_id {
+            return true;
+        }
+        snapshot = s
+            .parent_snapshot_id()
+            .and_then(|id| metadata.snapshot_by_id(id));
+    }
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use iceberg::spec::{
+        FormatVersion, NestedField, Operation, PrimitiveType, Schema, Snapshot, SortOrder, Summary,
+        TableMetadataBuilder, Type, UnboundPartitionSpec,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_manifest_list_statistics() {
+        use apache_avro::types::Value;
+
+        let schema = apache_avro::Schema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "manifest_file",
+                "fields": [
+                    {"name": "manifest_path", "type": "string"},
+                    {"name": "content", "type": "int"},
+                    {"name": "added_files_count", "type": "int"},
+                    {"name": "existing_files_count", "type": "int"},
+                    {"name": "added_rows_count", "type": "long"},
+                    {"name": "existing_rows_count", "type": "long"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, Vec::new());
+        for (path, content, files, rows) in [
+            ("m1.avro", 0, 2, 10),
+            ("m2.avro", 0, 3, 5),
+            ("d1.avro", 1, 4, 7),
+        ] {
+            writer
+                .append(Value::Record(vec![
+                    ("manifest_path".to_string(), Value::String(path.to_string())),
+                    ("content".to_string(), Value::Int(content)),
+                    ("added_files_count".to_string(), Value::Int(files)),
+                    ("existing_files_count".to_string(), Value::Int(1)),
+                    ("added_rows_count".to_string(), Value::Long(rows)),
+                    ("existing_rows_count".to_string(), Value::Long(0)),
+                ]))
+                .unwrap();
+        }
+        let content = writer.into_inner().unwrap();
+
+        assert_eq!(
+            manifest_list_statistics(&content).unwrap(),
+            ManifestListStatistics {
+                data_manifests: 2,
+                delete_manifests: 1,
+                data_files: 7,
+                delete_files: 5,
+                data_rows: 15,
+            }
+        );
+    }
+
+    #[test]
+    fn test_metadata_file_selection() {
+        let files = [("v1", 100), ("v2", 200), ("v3", 300)];
+        assert_eq!(last_file_before(&files, 50), None);
+        assert_eq!(last_file_before(&files, 100), Some("v1"));
+        assert_eq!(last_file_before(&files, 250), Some("v2"));
+        assert_eq!(last_file_before(&files, 1000), Some("v3"));
+
+        assert_eq!(first_file_after(&files, 150), Some("v2"));
+        assert_eq!(first_file_after(&files, 300), Some("v3"));
+        assert_eq!(first_file_after(&files, 301), None);
+    }
+
+    fn new_metadata() -> TableMetadata {
+        let schema = Schema::builder()
+            .with_fields(vec![NestedField::required(
+                1,
+                "id",
+                Type::Primitive(PrimitiveType::Int),
+            )
+            .into()])
+            .build()
+            .unwrap();
+        TableMetadataBuilder::new(
+            schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata
+    }
+
+    fn snapshot(snapshot_id: i64, parent: Option<i64>, timestamp_ms: i64) -> Snapshot {
+        Snapshot::builder()
+            .with_snapshot_id(snapshot_id)
+            .with_parent_snapshot_id(parent)
+            .with_timestamp_ms(timestamp_ms)
+            .with_sequence_number(snapshot_id)
+            .with_schema_id(0)
+            .with_manifest_list(format!("/snap-{snapshot_id}.avro"))
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::new(),
+            })
+            .build()
+    }
+
+    fn branch(snapshot_id: i64) -> SnapshotReference {
+        SnapshotReference {
+            snapshot_id,
+            retention: SnapshotRetention::Branch {
+                min_snapshots_to_keep: None,
+                max_snapshot_age_ms: None,
+                max_ref_age_ms: None,
+            },
+        }
+    }
+
+    /// `main`: 1 <- 2 <- 3, `other`: 1 <- 4
+    fn metadata_with_branches() -> TableMetadata {
+        let metadata = new_metadata();
+        let t = metadata.last_updated_ms();
+        metadata
+            .into_builder(None)
+            .add_snapshot(snapshot(1, None, t + 1))
+            .unwrap()
+            .add_snapshot(snapshot(2, Some(1), t + 2))
+            .unwrap()
+            .add_snapshot(snapshot(3, Some(2), t + 3))
+            .unwrap()
+            .add_snapshot(snapshot(4, Some(1), t + 4))
+            .unwrap()
+            .set_ref(MAIN_BRANCH, branch(3))
+            .unwrap()
+            .set_ref("other", branch(4))
+            .unwrap()
+            .build()
+            .unwrap()
+            .metadata
+    }
+
+    fn rollback_request(
+        snapshot_id: Option<i64>,
+        timestamp_ms: Option<i64>,
+    ) -> RollbackTableRequest {
+        RollbackTableRequest {
+            snapshot_id,
+            timestamp_ms,
+            ref_name: MAIN_BRANCH.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_rollback_to_ancestor() {
+        let metadata = metadata_with_branches();
+        let (current_ref, target) =
+            resolve_rollback_target(&metadata, &rollback_request(Some(1), None)).unwrap();
+        assert_eq!(current_ref.snapshot_id, 3);
+        assert_eq!(target, 1);
+
+        let (_, target) =
+            resolve_rollback_target(&metadata, &rollback_request(Some(3), None)).unwrap();
+        assert_eq!(target, 3);
+
+        let t = metadata.snapshot_by_id(2).unwrap().timestamp_ms();
+        let (_, target) =
+            resolve_rollback_target(&metadata, &rollback_request(None, Some(t))).unwrap();
+        assert_eq!(target, 2);
+    }
+
+    #[test]
+    fn test_rollback_rejects_snapshot_of_other_lineage() {
+        let metadata = metadata_with_branches();
+        let err = resolve_rollback_target(&metadata, &rollback_request(Some(4), None)).unwrap_err();
+        assert_eq!(err.error.code, 400);
+        assert_eq!(err.error.r#type, "SnapshotNotAncestor");
+
+        let err =
+            resolve_rollback_target(&metadata, &rollback_request(Some(99), None)).unwrap_err();
+        assert_eq!(err.error.code, 404);
+        assert_eq!(err.error.r#type, "SnapshotNotFound");
+
+        let err = resolve_rollback_target(&metadata, &rollback_request(None, Some(0))).unwrap_err();
+        assert_eq!(err.error.r#type, "SnapshotNotFound");
+    }
+
+    #[test]
+    fn test_metadata_mismatches() {
+        let metadata = new_metadata();
+        assert!(metadata_mismatches(&metadata, &metadata.clone()).is_empty());
+
+        let other = new_metadata();
+        let mismatches = metadata_mismatches(&metadata, &other);
+        assert!(mismatches[0].starts_with("table-uuid is"), "{mismatches:?}");
+    }
+}
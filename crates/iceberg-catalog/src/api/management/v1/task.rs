@@ -0,0 +1,233 @@
+use crate::api::management::v1::{ApiServer, TabularType};
+use crate::api::ApiContext;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authz::{Authorizer, CatalogWarehouseAction};
+use crate::service::task_queue::tabular_purge_queue::{PurgeAwaitingApproval, PurgeTaskDetails};
+use crate::service::{Catalog, Result, SecretStore, State};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct PurgeTask {
+    /// ID of the purge task
+    pub task_id: uuid::Uuid,
+    /// ID of the dropped tabular whose files are purged
+    pub tabular_id: uuid::Uuid,
+    /// Type of the tabular
+    pub typ: TabularType,
+    /// Location that is deleted by the purge
+    pub location: String,
+    /// Number of objects found below the location. Counting stops once the
+    /// configured threshold is exceeded, so the location may contain more objects.
+    pub object_count: i64,
+    /// Time when the purge started waiting for approval
+    pub approval_requested_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<PurgeAwaitingApproval> for PurgeTask {
+    fn from(purge: PurgeAwaitingApproval) -> Self {
+        Self {
+            task_id: purge.task_id,
+            tabular_id: purge.tabular_id,
+            typ: purge.tabular_type,
+            location: purge.tabular_location,
+            object_count: purge.object_count,
+            approval_requested_at: purge.approval_requested_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListPurgeTasksResponse {
+    /// Purges waiting for approval, oldest first
+    pub tasks: Vec<PurgeTask>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, utoipa::ToSchema, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PurgeTaskStatus {
+    Pending,
+    AwaitingApproval,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetPurgeTaskResponse {
+    /// ID of the purge task
+    pub task_id: uuid::Uuid,
+    /// ID of the dropped tabular whose files are purged
+    pub tabular_id: uuid::Uuid,
+    /// Type of the tabular
+    pub typ: TabularType,
+    /// Location that is deleted by the purge
+    pub location: String,
+    pub status: PurgeTaskStatus,
+    /// Number of times the purge was started
+    pub attempt: i32,
+    /// Number of objects deleted so far, over all attempts
+    pub deleted_count: i64,
+    /// Last object handled by the purge. An interrupted purge resumes from here.
+    pub checkpoint: Option<String>,
+    /// Time when progress was last recorded
+    pub progress_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<PurgeTaskDetails> for GetPurgeTaskResponse {
+    fn from(purge: PurgeTaskDetails) -> Self {
+        Self {
+            task_id: purge.task_id,
+            tabular_id: purge.tabular_id,
+            typ: purge.tabular_type,
+            location: purge.tabular_location,
+            status: purge.status,
+            attempt: purge.attempt,
+            deleted_count: purge.progress.deleted_count,
+            checkpoint: purge.progress.checkpoint,
+            progress_updated_at: purge.progress_updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ApprovePurgeRequest {
+    /// Location of the purge as returned when listing purges.
+    /// Must match exactly to confirm that the correct location is deleted.
+    pub confirm_location: String,
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
+
+#[async_trait::async_trait]
+pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
+    async fn list_purges_awaiting_approval(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<ListPurgeTasksResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanListDeletedTabulars,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let tasks = context
+            .v1_state
+            .queues
+            .list_purges_awaiting_approval(warehouse_id)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        Ok(ListPurgeTasksResponse { tasks })
+    }
+
+    async fn get_purge_task(
+        warehouse_id: WarehouseIdent,
+        task_id: uuid::Uuid,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<GetPurgeTaskResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanListDeletedTabulars,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        context
+            .v1_state
+            .queues
+            .get_tabular_purge(warehouse_id, task_id)
+            .await?
+            .map(Into::into)
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("Purge task {task_id} not found"),
+                    "PurgeTaskNotFound",
+                    None,
+                )
+                .into()
+            })
+    }
+
+    async fn approve_purge(
+        warehouse_id: WarehouseIdent,
+        task_id: uuid::Uuid,
+        request: ApprovePurgeRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifySoftDeletion,
+            )
+            .await?;
+
+        // ------------------- Validations -------------------
+        let queues = context.v1_state.queues;
+        let purge = queues
+            .list_purges_awaiting_approval(warehouse_id)
+            .await?
+            .into_iter()
+            .find(|purge| purge.task_id == task_id)
+            .ok_or_else(|| {
+                ErrorModel::not_found(
+                    format!("No purge task {task_id} is waiting for approval"),
+                    "PurgeTaskNotFound",
+                    None,
+                )
+            })?;
+        if purge.tabular_location != request.confirm_location {
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "`confirm-location` does not match the location {} of purge task {task_id}",
+                    purge.tabular_location
+                ),
+                "PurgeLocationMismatch",
+                None,
+            )
+            .into());
+        }
+
+        // ------------------- Business Logic -------------------
+        if !queues.approve_tabular_purge(warehouse_id, task_id).await? {
+            return Err(ErrorModel::conflict(
+                format!("Purge task {task_id} is no longer waiting for approval"),
+                "PurgeTaskNotAwaitingApproval",
+                None,
+            )
+            .into());
+        }
+
+        tracing::info!(
+            actor = ?request_metadata.actor(),
+            %warehouse_id,
+            %task_id,
+            location = %purge.tabular_location,
+            object_count = purge.object_count,
+            "Approved purge"
+        );
+        Ok(())
+    }
+}
@@ -1,12 +1,15 @@
-use super::default_page_size;
+use super::{default_page_size, SortOrder};
 use crate::api::iceberg::v1::{PageToken, PaginationQuery};
 use crate::api::management::v1::ApiServer;
 use crate::api::ApiContext;
 use crate::request_metadata::RequestMetadata;
-use crate::service::authn::UserId;
-use crate::service::authz::{Authorizer, CatalogServerAction, CatalogUserAction};
+use crate::service::authn::{invalidate_user_activity, UserId};
+use crate::service::authz::{
+    Authorizer, CatalogRoleAction, CatalogServerAction, CatalogUserAction, CatalogWarehouseAction,
+};
 use crate::service::{
-    AuthDetails, Catalog, CreateOrUpdateUserResponse, Result, SecretStore, State, Transaction,
+    AuthDetails, Catalog, CreateOrUpdateUserResponse, PrincipalDefaultWarehouse, Result, RoleId,
+    SecretStore, State, Transaction,
 };
 use axum::response::IntoResponse;
 use axum::Json;
@@ -25,15 +28,7 @@ pub enum UserLastUpdatedWith {
     UpdateEndpoint,
 }
 
-/// Type of a User
-#[derive(Copy, Debug, PartialEq, Deserialize, Serialize, utoipa::ToSchema, Clone)]
-#[serde(rename_all = "kebab-case")]
-pub enum UserType {
-    /// Human User
-    Human,
-    /// Application / Technical User
-    Application,
-}
+pub use iceberg_ext::management::UserType;
 
 /// User of the catalog
 #[derive(Debug, Serialize, utoipa::ToSchema, Clone)]
@@ -55,6 +50,56 @@ pub struct User {
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Timestamp when the user was last updated
     pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Timestamp when the user last sent an authenticated request.
+    /// Recorded with a precision of a few minutes.
+    pub last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Timestamp when the user was flagged by the stale user policy.
+    /// Cleared once the user is seen again.
+    pub flagged_stale_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Timestamp when the user was deactivated by the stale user policy.
+    /// Deactivated users are rejected until they are reactivated.
+    pub deactivated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Identity provider that authenticated a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdentityProvider {
+    Oidc,
+    Kubernetes,
+}
+
+impl IdentityProvider {
+    /// Prefix of the ids of users authenticated by this provider.
+    #[must_use]
+    pub fn user_id_prefix(self) -> &'static str {
+        match self {
+            IdentityProvider::Oidc => "oidc~",
+            IdentityProvider::Kubernetes => "kubernetes~",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum UserSortBy {
+    #[default]
+    CreatedAt,
+    Name,
+    /// Users that were never seen are sorted first in ascending order.
+    LastSeen,
+}
+
+/// Filters and ordering for listing users, in addition to the id and name filters.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserListFilter {
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_seen_after: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_seen_before: Option<chrono::DateTime<chrono::Utc>>,
+    pub idp: Option<IdentityProvider>,
+    pub sort_by: UserSortBy,
+    pub sort_order: SortOrder,
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema, Clone)]
@@ -113,6 +158,32 @@ pub struct ListUsersQuery {
     /// Search for a specific username
     #[serde(default)]
     pub name: Option<String>,
+    /// Only return users created at or after this time
+    #[serde(default)]
+    pub created_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return users created before this time
+    #[serde(default)]
+    pub created_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return users seen at or after this time
+    #[serde(default)]
+    pub last_seen_after: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return users that were not seen since this time, including users that were never seen
+    #[serde(default)]
+    pub last_seen_before: Option<chrono::DateTime<chrono::Utc>>,
+    /// Only return users authenticated by this identity provider
+    #[serde(default)]
+    pub idp: Option<IdentityProvider>,
+    /// Only return users that are directly assigned to this role.
+    /// Requires an authorization backend that stores role assignments.
+    #[serde(default)]
+    #[param(value_type=Option::<uuid::Uuid>)]
+    pub role_id: Option<RoleId>,
+    /// Field to sort by. Default: `created-at`
+    #[serde(default)]
+    pub sort_by: UserSortBy,
+    /// Default: `asc`
+    #[serde(default)]
+    pub sort_order: SortOrder,
     /// Next page token
     #[serde(default)]
     pub page_token: Option<String>,
@@ -133,6 +204,19 @@ impl ListUsersQuery {
             page_size: Some(self.page_size),
         }
     }
+
+    #[must_use]
+    pub fn filter(&self) -> UserListFilter {
+        UserListFilter {
+            created_after: self.created_after,
+            created_before: self.created_before,
+            last_seen_after: self.last_seen_after,
+            last_seen_before: self.last_seen_before,
+            idp: self.idp,
+            sort_by: self.sort_by,
+            sort_order: self.sort_order,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, utoipa::ToSchema)]
@@ -168,6 +252,42 @@ pub struct UpdateUserRequest {
     pub user_type: UserType,
 }
 
+/// Default warehouse of a user or role.
+/// Used by `GET /catalog/v1/config` if no warehouse is specified in the request.
+#[derive(Debug, Serialize, utoipa::ToSchema, Clone, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct GetDefaultWarehouseResponse {
+    /// ID of the default warehouse.
+    /// `null` if no default is configured or the warehouse is not active.
+    pub warehouse_id: Option<uuid::Uuid>,
+    /// ID of the project the default warehouse belongs to.
+    pub project_id: Option<uuid::Uuid>,
+}
+
+impl From<Option<PrincipalDefaultWarehouse>> for GetDefaultWarehouseResponse {
+    fn from(default: Option<PrincipalDefaultWarehouse>) -> Self {
+        Self {
+            warehouse_id: default.map(|d| *d.warehouse_id),
+            project_id: default.map(|d| *d.project_id),
+        }
+    }
+}
+
+impl IntoResponse for GetDefaultWarehouseResponse {
+    fn into_response(self) -> axum::response::Response {
+        (http::StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetDefaultWarehouseRequest {
+    /// ID of the warehouse to use as default.
+    /// `null` removes the default warehouse.
+    #[serde(default)]
+    pub warehouse_id: Option<uuid::Uuid>,
+}
+
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> Service<C, A, S> for ApiServer<C, A, S> {}
 
 #[async_trait::async_trait]
@@ -315,6 +435,7 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         let users = C::list_user(
             filter_user_id,
             filter_name,
+            UserListFilter::default(),
             PaginationQuery {
                 page_size: Some(1),
                 page_token: PageToken::NotSpecified,
@@ -343,12 +464,29 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .require_server_action(&request_metadata, &CatalogServerAction::CanListUsers)
             .await?;
 
+        let filter_user_id = if let Some(role_id) = query.role_id {
+            authorizer
+                .require_role_action(&request_metadata, role_id, &CatalogRoleAction::CanRead)
+                .await?;
+            let assignees = authorizer.list_role_assignee_users(role_id).await?;
+            Some(assignees.ok_or_else(|| {
+                ErrorModel::bad_request(
+                    "Filtering users by role requires an authorization backend that stores role assignments",
+                    "RoleFilterNotSupported",
+                    None,
+                )
+            })?)
+        } else {
+            None
+        };
+
         // ------------------- Business Logic -------------------
-        let filter_user_id = None;
         let pagination_query = query.pagination_query();
+        let filter = query.filter();
         let users = C::list_user(
             filter_user_id,
             query.name,
+            filter,
             pagination_query,
             context.v1_state.catalog,
         )
@@ -418,4 +556,77 @@ pub(crate) trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         authorizer.delete_user(&request_metadata, user_id).await?;
         t.commit().await
     }
+
+    async fn reactivate_user(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        user_id: UserId,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_user_action(&request_metadata, &user_id, &CatalogUserAction::CanUpdate)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        if C::reactivate_user(&user_id, t.transaction())
+            .await?
+            .is_none()
+        {
+            return Err(ErrorModel::not_found(
+                format!("User with id {user_id} not found."),
+                "UserNotFound",
+                None,
+            )
+            .into());
+        }
+        t.commit().await?;
+        invalidate_user_activity(&user_id);
+        Ok(())
+    }
+
+    async fn get_user_default_warehouse(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        user_id: UserId,
+    ) -> Result<GetDefaultWarehouseResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_user_action(&request_metadata, &user_id, &CatalogUserAction::CanRead)
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let default = C::get_user_default_warehouse(&user_id, context.v1_state.catalog).await?;
+        Ok(default.into())
+    }
+
+    async fn set_user_default_warehouse(
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+        user_id: UserId,
+        request: SetDefaultWarehouseRequest,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_user_action(&request_metadata, &user_id, &CatalogUserAction::CanUpdate)
+            .await?;
+        let warehouse_id = request.warehouse_id.map(Into::into);
+        if let Some(warehouse_id) = warehouse_id {
+            authorizer
+                .require_warehouse_action(
+                    &request_metadata,
+                    warehouse_id,
+                    &CatalogWarehouseAction::CanGetMetadata,
+                )
+                .await?;
+        }
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_user_default_warehouse(&user_id, warehouse_id, t.transaction()).await?;
+        t.commit().await
+    }
 }
@@ -1,31 +1,41 @@
 mod undrop;
 
-use crate::api::management::v1::{ApiServer, DeletedTabularResponse, ListDeletedTabularsResponse};
+use crate::api::management::v1::{
+    ApiServer, DeletedTabularResponse, ListDeletedTabularsResponse, LocationUsageResponse,
+    LocationUsageTabular, TabularType,
+};
 use crate::api::{ApiContext, Result};
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{CatalogProjectAction, CatalogWarehouseAction};
 pub use crate::service::storage::{
-    AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, S3Credential, S3Profile,
-    StorageCredential, StorageProfile,
+    AdlsProfile, AzCredential, GcsCredential, GcsProfile, GcsServiceKey, GcsTokenExchange,
+    S3Credential, S3Profile, StorageCredential, StorageProfile, StorageValidationReport,
 };
 use futures::FutureExt;
 use itertools::Itertools;
 
-use crate::api::iceberg::v1::{PageToken, PaginationQuery};
+use crate::api::iceberg::v1::{PageToken, PaginationQuery, MAX_PAGE_SIZE};
 use crate::service::{NamespaceIdentUuid, TableIdentUuid};
 
-use super::default_page_size;
-use crate::api::management::v1::role::require_project_id;
-use crate::catalog::UnfilteredPage;
-use crate::service::task_queue::TaskFilter;
-pub use crate::service::WarehouseStatus;
+use super::{default_page_size, SortOrder};
+use crate::api::management::v1::role::{
+    missing_project_id, require_project_id, require_project_id_or_else,
+};
+use crate::catalog::config::invalidate_warehouse_config;
+use crate::catalog::namespace::validate_namespace_defaults;
+use crate::catalog::{maybe_get_secret, UnfilteredPage};
+use crate::service::missing_idents::{invalidate_all_missing_idents, invalidate_missing_idents};
+use crate::service::network_policy::invalidate_allowed_client_networks;
 use crate::service::{
-    authz::Authorizer, secrets::SecretStore, Catalog, ListFlags, State, TabularIdentUuid,
-    Transaction,
+    authz::Authorizer, secrets::SecretStore, Catalog, State, TabularIdentUuid, Transaction,
 };
-use crate::{ProjectIdent, WarehouseIdent, DEFAULT_PROJECT_ID};
+pub use crate::service::{WarehouseStatus, WarehouseStorageHealth};
+use crate::{ProjectIdent, SecretIdent, WarehouseIdent};
 use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::configs::Location;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr as _;
 use utoipa::ToSchema;
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -35,6 +45,58 @@ pub struct ListDeletedTabularsQuery {
     #[serde(default)]
     #[param(value_type=uuid::Uuid)]
     pub namespace_id: Option<NamespaceIdentUuid>,
+    /// Also return tabulars of Namespaces nested below `namespaceId`.
+    /// Ignored if `namespaceId` is not set. Default: `false`
+    #[serde(default)]
+    pub include_child_namespaces: bool,
+    /// Field to sort by. Default: `created-at`
+    #[serde(default)]
+    pub sort_by: DeletedTabularSortBy,
+    /// Default: `asc`
+    #[serde(default)]
+    pub sort_order: SortOrder,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationUsageQuery {
+    /// Location to inspect, i.e. `s3://bucket/prefix`
+    pub prefix: String,
+    /// Next page token
+    #[serde(default)]
+    pub page_token: Option<String>,
+    /// Signals an upper bound of the number of results that a client will receive.
+    /// Default: 100
+    #[serde(default = "default_page_size")]
+    pub page_size: i64,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageUsageQuery {
+    /// Day of the rollup, i.e. `2025-01-27`. Default: most recent rollup
+    #[serde(default)]
+    pub day: Option<chrono::NaiveDate>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct TableStorageUsageQuery {
+    /// Day of the rollup, i.e. `2025-01-27`. Default: most recent rollup.
+    /// Pass the `day` of the first page when requesting further pages.
+    #[serde(default)]
+    pub day: Option<chrono::NaiveDate>,
+    /// Only return tables of this namespace. Tables of child namespaces are not included.
+    #[serde(default)]
+    #[param(value_type=uuid::Uuid)]
+    pub namespace_id: Option<NamespaceIdentUuid>,
     /// Next page token
     #[serde(default)]
     pub page_token: Option<String>,
@@ -55,6 +117,34 @@ impl ListDeletedTabularsQuery {
             page_size: Some(self.page_size),
         }
     }
+
+    #[must_use]
+    pub fn filter(&self) -> DeletedTabularListFilter {
+        DeletedTabularListFilter {
+            namespace_id: self.namespace_id,
+            include_child_namespaces: self.include_child_namespaces,
+            sort_by: self.sort_by,
+            sort_order: self.sort_order,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeletedTabularSortBy {
+    #[default]
+    CreatedAt,
+    DeletedAt,
+    ExpirationDate,
+}
+
+/// Filters and ordering for listing soft-deleted tabulars.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DeletedTabularListFilter {
+    pub namespace_id: Option<NamespaceIdentUuid>,
+    pub include_child_namespaces: bool,
+    pub sort_by: DeletedTabularSortBy,
+    pub sort_order: SortOrder,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -75,6 +165,160 @@ pub struct CreateWarehouseRequest {
     /// 7 days expiration.
     #[serde(default)]
     pub delete_profile: TabularDeleteProfile,
+    /// Lifecycle of the warehouse. Defaults to `permanent`.
+    #[serde(default)]
+    pub lifecycle: WarehouseLifecycle,
+}
+
+/// Ephemeral warehouses, for example for CI runs or experiments, are deleted
+/// together with all their data once their lifetime or idle period has passed.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize, ToSchema,
+)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum WarehouseLifecycle {
+    #[default]
+    #[schema(title = "WarehouseLifecyclePermanent")]
+    Permanent {},
+    #[schema(title = "WarehouseLifecycleEphemeral")]
+    #[serde(rename_all = "kebab-case")]
+    Ephemeral {
+        /// Delete the warehouse this many seconds after it was created.
+        #[serde(default)]
+        max_lifetime_seconds: Option<i64>,
+        /// Delete the warehouse if no table or view was created, loaded or
+        /// committed to for this many seconds.
+        #[serde(default)]
+        max_idle_seconds: Option<i64>,
+    },
+}
+
+impl WarehouseLifecycle {
+    pub(crate) fn validate(&self) -> Result<()> {
+        match self {
+            Self::Permanent {} => Ok(()),
+            Self::Ephemeral {
+                max_lifetime_seconds,
+                max_idle_seconds,
+            } => {
+                if max_lifetime_seconds.is_none() && max_idle_seconds.is_none() {
+                    return Err(ErrorModel::bad_request(
+                        "Ephemeral warehouses require `max-lifetime-seconds` or `max-idle-seconds`",
+                        "InvalidWarehouseLifecycle",
+                        None,
+                    )
+                    .into());
+                }
+                if [max_lifetime_seconds, max_idle_seconds]
+                    .into_iter()
+                    .flatten()
+                    .any(|seconds| *seconds <= 0)
+                {
+                    return Err(ErrorModel::bad_request(
+                        "Lifetime and idle period of ephemeral warehouses must be positive",
+                        "InvalidWarehouseLifecycle",
+                        None,
+                    )
+                    .into());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Thresholds on the metadata of each table in a warehouse. Commits that reach a
+/// warning threshold succeed with a `Warning` header, commits that grow a table
+/// beyond a limit are rejected until its history is expired.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize, ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableLimits {
+    /// Number of snapshots from which commits are answered with a warning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_count_warning: Option<u64>,
+    /// Maximum number of snapshots of a table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_count_limit: Option<u64>,
+    /// Size of the uncompressed metadata JSON from which commits are answered with a warning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_size_warning_bytes: Option<u64>,
+    /// Maximum size of the uncompressed metadata JSON of a table.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_size_limit_bytes: Option<u64>,
+}
+
+impl TableLimits {
+    pub(crate) fn validate(&self) -> Result<()> {
+        let thresholds = [
+            (
+                "snapshot-count",
+                self.snapshot_count_warning,
+                self.snapshot_count_limit,
+            ),
+            (
+                "metadata-size",
+                self.metadata_size_warning_bytes,
+                self.metadata_size_limit_bytes,
+            ),
+        ];
+        for (name, warning, limit) in thresholds {
+            if warning == Some(0) || limit == Some(0) {
+                return Err(ErrorModel::bad_request(
+                    format!("Thresholds of `{name}` must be positive"),
+                    "InvalidTableLimits",
+                    None,
+                )
+                .into());
+            }
+            if let (Some(warning), Some(limit)) = (warning, limit) {
+                if warning > limit {
+                    return Err(ErrorModel::bad_request(
+                        format!("The warning threshold of `{name}` must not exceed its limit"),
+                        "InvalidTableLimits",
+                        None,
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Provenance the catalog writes into the summary of every snapshot committed to a
+/// table of the warehouse. Values sent by the client for these keys are replaced.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize, ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+pub struct SnapshotSummaryEnrichment {
+    /// Add the principal that committed the snapshot as `lakekeeper.committed-by`.
+    #[serde(default)]
+    pub principal: bool,
+    /// Add the `User-Agent` of the committing engine as `lakekeeper.engine`.
+    #[serde(default)]
+    pub engine: bool,
+    /// Add the id of the commit request as `lakekeeper.request-id`.
+    #[serde(default)]
+    pub request_id: bool,
+}
+
+/// Counts of a warehouse, updated in the same transaction as the change they count.
+/// Unlike the statistics endpoint, they are cheap to load for many warehouses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseSummary {
+    /// Number of namespaces in the warehouse.
+    pub number_of_namespaces: i64,
+    /// Number of active tables. Staged and soft-deleted tables are not counted.
+    pub number_of_tables: i64,
+    /// Number of active views. Soft-deleted views are not counted.
+    pub number_of_views: i64,
+    /// Time of the most recent creation, drop or commit in the warehouse.
+    /// Commits are recorded with a precision of one minute.
+    pub last_activity_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, ToSchema)]
@@ -134,6 +378,21 @@ pub struct CreateWarehouseResponse {
     pub warehouse_id: WarehouseIdent,
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct ValidateStorageRequest {
+    /// Project ID in which the warehouse would be created.
+    /// If no default project is set for this server, this field is required.
+    #[schema(value_type=Option<uuid::Uuid>)]
+    #[serde(default)]
+    pub project_id: Option<ProjectIdent>,
+    /// Storage profile to validate.
+    pub storage_profile: StorageProfile,
+    /// Optional storage credential to validate with the storage profile.
+    #[serde(default)]
+    pub storage_credential: Option<StorageCredential>,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct UpdateWarehouseStorageRequest {
@@ -178,6 +437,110 @@ pub struct UpdateWarehouseDeleteProfileRequest {
     pub delete_profile: TabularDeleteProfile,
 }
 
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseNamespaceDefaultsRequest {
+    /// Properties applied to namespaces created in this warehouse without them.
+    /// Replaces all existing defaults. In values, `{namespace_id}` is replaced
+    /// by the id of the created namespace. A default `location` must contain it.
+    pub namespace_defaults: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseNetworkPolicyRequest {
+    /// Client networks in CIDR notation the warehouse may be accessed from.
+    /// Replaces the existing list. An empty list allows all clients.
+    #[schema(value_type = Vec<String>, example = json!(["10.0.0.0/8", "2001:db8::/32"]))]
+    pub allowed_client_networks: Vec<ipnet::IpNet>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseTableLimitsRequest {
+    /// Limits applied to all tables of the warehouse. Replaces the existing limits.
+    pub table_limits: TableLimits,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseSnapshotSummaryEnrichmentRequest {
+    /// Provenance added to snapshot summaries. Replaces the existing configuration.
+    pub snapshot_summary_enrichment: SnapshotSummaryEnrichment,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateWarehouseWasmHooksRequest {
+    /// Names of the WASM hooks in the hook directory of the server, run in this order.
+    /// Replaces the existing hooks. Empty to remove all hooks.
+    pub wasm_hooks: Vec<String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RescheduleExpirationsRequest {
+    /// How to compute the new expiration date of each pending expiration.
+    pub schedule: ExpirationSchedule,
+    /// Only compute the new expiration dates without applying them. Default: false
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SetExpirationExemptionRequest {
+    /// Whether the soft-deleted tabular should be skipped by the expiration worker.
+    pub exempt: bool,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum ExpirationSchedule {
+    /// Expire tabulars `expiration-seconds` of the current delete profile
+    /// after they were dropped. Fails if the warehouse uses hard deletion.
+    #[schema(title = "ExpirationScheduleDeleteProfile")]
+    DeleteProfile {},
+    /// Move the current expiration date by `seconds`. Negative values expire tabulars earlier.
+    #[schema(title = "ExpirationScheduleShift")]
+    Shift { seconds: i64 },
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RescheduleExpirationsResponse {
+    /// Whether the new expiration dates were only computed but not applied.
+    pub dry_run: bool,
+    /// Soft-deleted tabulars whose expiration was (or would be) rescheduled.
+    pub tabulars: Vec<RescheduledTabular>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RescheduledTabular {
+    /// Unique identifier of the tabular
+    pub id: uuid::Uuid,
+    /// Name of the tabular
+    pub name: String,
+    /// List of namespace parts the tabular belongs to
+    pub namespace: Vec<String>,
+    /// Type of the tabular
+    pub typ: TabularType,
+    /// Date when the tabular was deleted
+    pub deleted_at: chrono::DateTime<chrono::Utc>,
+    /// Expiration date before rescheduling
+    pub previous_expiration_date: chrono::DateTime<chrono::Utc>,
+    /// Expiration date after rescheduling. Tabulars with a date in the past
+    /// expire on the next run of the expiration queue.
+    pub expiration_date: chrono::DateTime<chrono::Utc>,
+}
+
+impl axum::response::IntoResponse for RescheduleExpirationsResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        axum::Json(self).into_response()
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct RenameProjectRequest {
@@ -195,11 +558,65 @@ pub struct GetWarehouseResponse {
     /// Project ID in which the warehouse is created.
     pub project_id: uuid::Uuid,
     /// Storage profile used for the warehouse.
-    pub storage_profile: StorageProfile,
+    /// Only returned if the caller may view the storage profile.
+    pub storage_profile: Option<StorageProfile>,
+    /// ID of the secret holding the storage credential of the warehouse.
+    /// Only returned if the caller may view the storage credential id.
+    pub storage_credential_id: Option<uuid::Uuid>,
     /// Delete profile used for the warehouse.
     pub delete_profile: TabularDeleteProfile,
     /// Whether the warehouse is active.
     pub status: WarehouseStatus,
+    /// Result of the most recent background storage check.
+    pub storage_health: WarehouseStorageHealth,
+    /// Properties applied to namespaces created without them.
+    pub namespace_defaults: HashMap<String, String>,
+    /// Lifecycle of the warehouse.
+    pub lifecycle: WarehouseLifecycle,
+    /// Client networks the warehouse may be accessed from.
+    /// Empty if clients are not restricted.
+    #[schema(value_type = Vec<String>)]
+    pub allowed_client_networks: Vec<ipnet::IpNet>,
+    /// Limits on the metadata of tables in the warehouse.
+    pub table_limits: TableLimits,
+    /// Provenance added to the summaries of committed snapshots.
+    pub snapshot_summary_enrichment: SnapshotSummaryEnrichment,
+    /// WASM hooks run as contract verifiers for tables and views of the warehouse.
+    pub wasm_hooks: Vec<String>,
+    /// Counts of the warehouse and its last activity.
+    pub summary: WarehouseSummary,
+    /// Whether the storage credential can be read from the secret store.
+    /// Only returned when fetching a single warehouse that has a storage credential,
+    /// if the caller may view the storage credential id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<CredentialStatus>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum CredentialStatus {
+    /// The credential can be read.
+    Valid,
+    /// The secret referenced by the warehouse does not exist.
+    Missing,
+    /// The secret exists but cannot be decrypted or parsed, or the secret store failed.
+    Unreadable,
+}
+
+impl CredentialStatus {
+    async fn check<S: SecretStore>(secret_id: SecretIdent, secrets: &S) -> Self {
+        match secrets
+            .get_secret_by_id::<StorageCredential>(&secret_id)
+            .await
+        {
+            Ok(_) => Self::Valid,
+            Err(e) if e.error.code == http::StatusCode::NOT_FOUND.as_u16() => Self::Missing,
+            Err(e) => {
+                tracing::info!(%secret_id, "Storage credential cannot be read: {:?}", e.error);
+                Self::Unreadable
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, serde::Serialize, ToSchema)]
@@ -209,6 +626,109 @@ pub struct ListWarehousesResponse {
     pub warehouses: Vec<GetWarehouseResponse>,
 }
 
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct WarehouseStatisticsResponse {
+    /// ID of the warehouse.
+    pub warehouse_id: uuid::Uuid,
+    /// Number of active tables in the warehouse.
+    pub number_of_tables: i64,
+    /// Number of active views in the warehouse.
+    pub number_of_views: i64,
+    /// Tabulars that have been soft-deleted and are awaiting expiration.
+    pub soft_deleted: SoftDeletedStatistics,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct SoftDeletedStatistics {
+    /// Number of soft-deleted tables.
+    pub number_of_tables: i64,
+    /// Number of soft-deleted views.
+    pub number_of_views: i64,
+    /// Bytes of data files still held by soft-deleted tables.
+    /// Calculated from the snapshot summary of each table's current snapshot,
+    /// files only referenced by older snapshots are not included.
+    pub retained_bytes: i64,
+    /// Number of soft-deleted tables whose snapshot summary does not report
+    /// a size. These are not included in `retained-bytes`.
+    pub number_of_tables_without_size: i64,
+    /// Seconds after which soft-deleted tabulars are removed.
+    /// Not set if the warehouse uses hard deletes.
+    pub expiration_seconds: Option<i64>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageUsageResponse {
+    /// Day of the rollup. Not set if no rollup was requested and none has been computed yet.
+    pub day: Option<chrono::NaiveDate>,
+    /// Storage used by the tables of each namespace. Child namespaces are not included
+    /// in the usage of their parent.
+    pub namespaces: Vec<NamespaceStorageUsage>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct NamespaceStorageUsage {
+    /// ID of the namespace. The namespace might have been dropped since the rollup.
+    pub namespace_id: uuid::Uuid,
+    /// Name of the namespace at the time of the rollup.
+    pub namespace: Vec<String>,
+    /// Number of tables in the namespace, including soft-deleted ones.
+    pub number_of_tables: i64,
+    /// Bytes of data files referenced by the current snapshot of the tables,
+    /// as reported by the snapshot summaries.
+    pub total_bytes: i64,
+    /// Number of tables whose snapshot summary does not report a size.
+    /// These are not included in `total-bytes`.
+    pub number_of_tables_without_size: i64,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableStorageUsageResponse {
+    /// Day of the rollup. Not set if no rollup was requested and none has been computed yet.
+    pub day: Option<chrono::NaiveDate>,
+    /// Storage used by each table, including soft-deleted ones.
+    pub tables: Vec<TableStorageUsage>,
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct TableStorageUsage {
+    /// ID of the table. The table might have been dropped since the rollup.
+    pub table_id: uuid::Uuid,
+    /// Name of the table at the time of the rollup.
+    pub name: String,
+    /// ID of the namespace the table was in at the time of the rollup.
+    pub namespace_id: uuid::Uuid,
+    /// Name of the namespace at the time of the rollup.
+    pub namespace: Vec<String>,
+    /// Bytes of data files referenced by the current snapshot of the table,
+    /// as reported by the snapshot summary. Not set if the summary does not report a size.
+    pub total_bytes: Option<i64>,
+}
+
+impl axum::response::IntoResponse for StorageUsageResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        axum::Json(self).into_response()
+    }
+}
+
+impl axum::response::IntoResponse for TableStorageUsageResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        axum::Json(self).into_response()
+    }
+}
+
+impl axum::response::IntoResponse for WarehouseStatisticsResponse {
+    fn into_response(self) -> axum::http::Response<axum::body::Body> {
+        axum::Json(self).into_response()
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize, ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct UpdateWarehouseCredentialRequest {
@@ -217,6 +737,13 @@ pub struct UpdateWarehouseCredentialRequest {
     pub new_storage_credential: Option<StorageCredential>,
 }
 
+#[derive(Debug, Clone, serde::Deserialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct RelinkWarehouseCredentialRequest {
+    /// Storage credential replacing the missing or unreadable one.
+    pub storage_credential: StorageCredential,
+}
+
 impl axum::response::IntoResponse for CreateWarehouseResponse {
     fn into_response(self) -> axum::http::Response<axum::body::Body> {
         (http::StatusCode::CREATED, axum::Json(self)).into_response()
@@ -245,14 +772,11 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             mut storage_profile,
             storage_credential,
             delete_profile,
+            lifecycle,
         } = request;
-        let project_id = project_id
-            .or(*DEFAULT_PROJECT_ID)
-            .ok_or(ErrorModel::bad_request(
-                "project_id must be specified",
-                "CreateWarehouseProjectIdMissing",
-                None,
-            ))?;
+        let project_id = require_project_id_or_else(project_id, &request_metadata, || {
+            missing_project_id("CreateWarehouseProjectIdMissing")
+        })?;
 
         // ------------------- AuthZ -------------------
         let authorizer = context.v1_state.authz;
@@ -266,6 +790,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         // ------------------- Business Logic -------------------
         validate_warehouse_name(&warehouse_name)?;
+        lifecycle.validate()?;
         storage_profile.normalize()?;
         storage_profile
             .validate_access(storage_credential.as_ref(), None)
@@ -293,6 +818,9 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             transaction.transaction(),
         )
         .await?;
+        if lifecycle != WarehouseLifecycle::default() {
+            C::set_warehouse_lifecycle(warehouse_id, &lifecycle, transaction.transaction()).await?;
+        }
         authorizer
             .create_warehouse(&request_metadata, warehouse_id, project_id)
             .await?;
@@ -336,14 +864,11 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         .await?
         .into_iter()
         .zip(warehouses.into_iter())
-        .filter_map(|(allowed, warehouse)| {
-            if allowed {
-                Some(warehouse.into())
-            } else {
-                None
-            }
-        })
-        .collect();
+        .filter_map(|(allowed, warehouse)| allowed.then_some(warehouse));
+        let warehouses = futures::future::try_join_all(warehouses.map(|warehouse| {
+            GetWarehouseResponse::authorized(&authorizer, &request_metadata, warehouse)
+        }))
+        .await?;
 
         Ok(ListWarehousesResponse { warehouses })
     }
@@ -365,9 +890,19 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         // ------------------- Business Logic -------------------
         let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
-        let warehouses = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
         transaction.commit().await?;
-        Ok(warehouses.into())
+        let storage_secret_id = warehouse.storage_secret_id;
+        let mut response =
+            GetWarehouseResponse::authorized(&authorizer, &request_metadata, warehouse).await?;
+        // Only checked for single warehouses, as it requires a secret store lookup
+        if let Some(secret_id) =
+            storage_secret_id.filter(|_| response.storage_credential_id.is_some())
+        {
+            response.credential_status =
+                Some(CredentialStatus::check(secret_id, &context.v1_state.secrets).await);
+        }
+        Ok(response)
     }
 
     async fn delete_warehouse(
@@ -392,6 +927,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .delete_warehouse(&request_metadata, warehouse_id)
             .await?;
         transaction.commit().await?;
+        invalidate_warehouse_config(warehouse_id);
 
         Ok(())
     }
@@ -451,8 +987,9 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         Ok(())
     }
 
-    async fn deactivate_warehouse(
+    async fn update_warehouse_namespace_defaults(
         warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseNamespaceDefaultsRequest,
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
@@ -462,27 +999,28 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .require_warehouse_action(
                 &request_metadata,
                 warehouse_id,
-                &CatalogWarehouseAction::CanDeactivate,
+                &CatalogWarehouseAction::CanUpdateNamespaceDefaults,
             )
             .await?;
 
         // ------------------- Business Logic -------------------
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
-
-        C::set_warehouse_status(
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        validate_namespace_defaults(&request.namespace_defaults, &warehouse.storage_profile)?;
+        C::set_warehouse_namespace_defaults(
             warehouse_id,
-            WarehouseStatus::Inactive,
+            &request.namespace_defaults,
             transaction.transaction(),
         )
         .await?;
-
         transaction.commit().await?;
 
         Ok(())
     }
 
-    async fn activate_warehouse(
+    async fn update_warehouse_network_policy(
         warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseNetworkPolicyRequest,
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
@@ -492,28 +1030,34 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .require_warehouse_action(
                 &request_metadata,
                 warehouse_id,
-                &CatalogWarehouseAction::CanActivate,
+                &CatalogWarehouseAction::CanUpdateNetworkPolicy,
             )
             .await?;
 
         // ------------------- Business Logic -------------------
-        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let mut allowed_client_networks = request.allowed_client_networks;
+        for network in &mut allowed_client_networks {
+            *network = network.trunc();
+        }
+        allowed_client_networks.sort_unstable();
+        allowed_client_networks.dedup();
 
-        C::set_warehouse_status(
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_allowed_client_networks(
             warehouse_id,
-            WarehouseStatus::Active,
+            &allowed_client_networks,
             transaction.transaction(),
         )
         .await?;
-
         transaction.commit().await?;
+        invalidate_allowed_client_networks(warehouse_id);
 
         Ok(())
     }
 
-    async fn update_storage(
+    async fn update_warehouse_table_limits(
         warehouse_id: WarehouseIdent,
-        request: UpdateWarehouseStorageRequest,
+        request: UpdateWarehouseTableLimitsRequest,
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
@@ -523,74 +1067,299 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .require_warehouse_action(
                 &request_metadata,
                 warehouse_id,
-                &CatalogWarehouseAction::CanUpdateStorage,
+                &CatalogWarehouseAction::CanUpdateTableLimits,
             )
             .await?;
 
         // ------------------- Business Logic -------------------
-        let UpdateWarehouseStorageRequest {
-            mut storage_profile,
-            storage_credential,
-        } = request;
-
-        storage_profile.normalize()?;
-        storage_profile
-            .validate_access(storage_credential.as_ref(), None)
-            .await?;
-
+        request.table_limits.validate()?;
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
-        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
-        warehouse
-            .storage_profile
-            .can_be_updated_with(&storage_profile)?;
-        let old_secret_id = warehouse.storage_secret_id;
-
-        let secret_id = if let Some(storage_credential) = storage_credential {
-            Some(
-                context
-                    .v1_state
-                    .secrets
-                    .create_secret(storage_credential)
-                    .await?,
-            )
-        } else {
-            None
-        };
-
-        C::update_storage_profile(
+        C::set_warehouse_table_limits(
             warehouse_id,
-            storage_profile,
-            secret_id,
+            &request.table_limits,
             transaction.transaction(),
         )
         .await?;
-
         transaction.commit().await?;
 
-        // Delete the old secret if it exists - never fail the request if the deletion fails
-        if let Some(old_secret_id) = old_secret_id {
-            context
-                .v1_state
-                .secrets
-                .delete_secret(&old_secret_id)
-                .await
-                .map_err(|e| {
-                    tracing::warn!("Failed to delete old secret: {:?}", e.error);
+        Ok(())
+    }
+
+    async fn update_warehouse_snapshot_summary_enrichment(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseSnapshotSummaryEnrichmentRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateSnapshotSummaryEnrichment,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_snapshot_summary_enrichment(
+            warehouse_id,
+            &request.snapshot_summary_enrichment,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn update_warehouse_wasm_hooks(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseWasmHooksRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateWasmHooks,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        // Compiles the hooks, so that broken modules are rejected here
+        // instead of failing every change in the warehouse.
+        context
+            .v1_state
+            .contract_verifiers
+            .validate_warehouse_verifiers(&request.wasm_hooks)?;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_warehouse_wasm_hooks(warehouse_id, &request.wasm_hooks, transaction.transaction())
+            .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn reschedule_expirations(
+        warehouse_id: WarehouseIdent,
+        request: RescheduleExpirationsRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<RescheduleExpirationsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanModifySoftDeletion,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        // New dates are relative to either the deletion or the current expiration date.
+        let (from_deleted_at, offset) = match request.schedule {
+            ExpirationSchedule::DeleteProfile {} => {
+                let expiration = warehouse
+                    .tabular_delete_profile
+                    .expiration_seconds()
+                    .ok_or_else(|| {
+                        ErrorModel::bad_request(
+                            "Warehouse does not use soft-deletion, there is no expiration to reschedule to.",
+                            "HardDeletionProfile",
+                            None,
+                        )
+                    })?;
+                (true, expiration)
+            }
+            ExpirationSchedule::Shift { seconds } => {
+                let shift = chrono::Duration::try_seconds(seconds).ok_or_else(|| {
+                    ErrorModel::bad_request(
+                        format!("Shift of {seconds} seconds is out of range."),
+                        "InvalidExpirationShift",
+                        None,
+                    )
+                })?;
+                (false, shift)
+            }
+        };
+
+        let pending = C::list_pending_expirations(warehouse_id, transaction.transaction()).await?;
+        let tabulars = pending
+            .into_iter()
+            .map(|p| {
+                let base = if from_deleted_at {
+                    p.deletion_details.deleted_at
+                } else {
+                    p.deletion_details.expiration_date
+                };
+                let expiration_date = base.checked_add_signed(offset).ok_or_else(|| {
+                    ErrorModel::bad_request(
+                        "New expiration date is out of range.",
+                        "InvalidExpirationShift",
+                        None,
+                    )
+                })?;
+                Ok((p, expiration_date))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if !request.dry_run {
+            let expirations = tabulars
+                .iter()
+                .map(|(p, expiration_date)| {
+                    (p.deletion_details.expiration_task_id, *expiration_date)
                 })
-                .ok();
+                .collect::<Vec<_>>();
+            C::reschedule_expirations(&expirations, transaction.transaction()).await?;
+            tracing::info!(
+                "Rescheduled {} pending expirations of warehouse {warehouse_id}",
+                expirations.len()
+            );
         }
+        transaction.commit().await?;
+
+        Ok(RescheduleExpirationsResponse {
+            dry_run: request.dry_run,
+            tabulars: tabulars
+                .into_iter()
+                .map(|(p, expiration_date)| {
+                    let ident = p.tabular.into_inner();
+                    RescheduledTabular {
+                        id: *p.tabular_id,
+                        name: ident.name,
+                        namespace: ident.namespace.inner(),
+                        typ: p.tabular_id.into(),
+                        deleted_at: p.deletion_details.deleted_at,
+                        previous_expiration_date: p.deletion_details.expiration_date,
+                        expiration_date,
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    async fn set_expiration_exemption(
+        warehouse_id: WarehouseIdent,
+        tabular_id: uuid::Uuid,
+        request: SetExpirationExemptionRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanSetExpirationExemption,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        C::set_tabular_expiration_exempt(
+            warehouse_id,
+            tabular_id,
+            request.exempt,
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        tracing::info!(
+            "Set expiration exemption of tabular {tabular_id} in warehouse {warehouse_id} to {}",
+            request.exempt
+        );
 
         Ok(())
     }
 
-    async fn update_storage_credential(
+    async fn deactivate_warehouse(
         warehouse_id: WarehouseIdent,
-        request: UpdateWarehouseCredentialRequest,
         context: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
         // ------------------- AuthZ -------------------
         let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanDeactivate,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+
+        C::set_warehouse_status(
+            warehouse_id,
+            WarehouseStatus::Inactive,
+            transaction.transaction(),
+        )
+        .await?;
+
+        transaction.commit().await?;
+        invalidate_warehouse_config(warehouse_id);
+
+        Ok(())
+    }
+
+    async fn activate_warehouse(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanActivate,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+
+        C::set_warehouse_status(
+            warehouse_id,
+            WarehouseStatus::Active,
+            transaction.transaction(),
+        )
+        .await?;
+
+        transaction.commit().await?;
+        invalidate_warehouse_config(warehouse_id);
+        invalidate_missing_idents(warehouse_id);
+
+        Ok(())
+    }
+
+    async fn update_storage(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseStorageRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+        // The profile update replaces the credential as well
         authorizer
             .require_warehouse_action(
                 &request_metadata,
@@ -600,6 +1369,69 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .await?;
 
         // ------------------- Business Logic -------------------
+        let UpdateWarehouseStorageRequest {
+            mut storage_profile,
+            storage_credential,
+        } = request;
+
+        storage_profile.normalize()?;
+        storage_profile
+            .validate_access(storage_credential.as_ref(), None)
+            .await?;
+
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        warehouse
+            .storage_profile
+            .can_be_updated_with(&storage_profile)?;
+        let old_secret_id = warehouse.storage_secret_id;
+
+        let secret_id = if let Some(storage_credential) = storage_credential {
+            Some(
+                context
+                    .v1_state
+                    .secrets
+                    .create_secret(storage_credential)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        C::update_storage_profile(
+            warehouse_id,
+            storage_profile,
+            secret_id,
+            transaction.transaction(),
+        )
+        .await?;
+
+        transaction.commit().await?;
+        invalidate_warehouse_config(warehouse_id);
+
+        // Delete the old secret if it exists - never fail the request if the deletion fails
+        if let Some(old_secret_id) = old_secret_id {
+            context
+                .v1_state
+                .secrets
+                .delete_secret(&old_secret_id)
+                .await
+                .map_err(|e| {
+                    tracing::warn!("Failed to delete old secret: {:?}", e.error);
+                })
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn update_storage_credential(
+        warehouse_id: WarehouseIdent,
+        request: UpdateWarehouseCredentialRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
         let UpdateWarehouseCredentialRequest {
             new_storage_credential,
         } = request;
@@ -607,6 +1439,17 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
         let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
         let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
         let old_secret_id = warehouse.storage_secret_id;
+
+        let action = storage_credential_update_action(
+            old_secret_id.is_some(),
+            new_storage_credential.is_some(),
+        );
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(&request_metadata, warehouse_id, &action)
+            .await?;
+
+        // ------------------- Business Logic -------------------
         let storage_profile = warehouse.storage_profile;
 
         storage_profile
@@ -635,20 +1478,198 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         transaction.commit().await?;
 
-        // Delete the old secret if it exists - never fail the request if the deletion fails
-        if let Some(old_secret_id) = old_secret_id {
-            context
-                .v1_state
-                .secrets
-                .delete_secret(&old_secret_id)
-                .await
-                .map_err(|e| {
-                    tracing::warn!("Failed to delete old secret: {:?}", e.error);
-                })
-                .ok();
-        }
-
-        Ok(())
+        // Delete the old secret if it exists - never fail the request if the deletion fails
+        if let Some(old_secret_id) = old_secret_id {
+            context
+                .v1_state
+                .secrets
+                .delete_secret(&old_secret_id)
+                .await
+                .map_err(|e| {
+                    tracing::warn!("Failed to delete old secret: {:?}", e.error);
+                })
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn relink_storage_credential(
+        warehouse_id: WarehouseIdent,
+        request: RelinkWarehouseCredentialRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<()> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorageCredential,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let RelinkWarehouseCredentialRequest { storage_credential } = request;
+        let mut transaction = C::Transaction::begin_write(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let Some(old_secret_id) = warehouse.storage_secret_id else {
+            return Err(ErrorModel::bad_request(
+                "Warehouse has no storage credential to relink. Use the storage-credential endpoint to set one.",
+                "NoStorageCredential",
+                None,
+            )
+            .into());
+        };
+        let status = CredentialStatus::check(old_secret_id, &context.v1_state.secrets).await;
+        if status == CredentialStatus::Valid {
+            return Err(ErrorModel::conflict(
+                "Storage credential of the warehouse is readable. Use the storage-credential endpoint to rotate it.",
+                "StorageCredentialValid",
+                None,
+            )
+            .into());
+        }
+
+        let storage_profile = warehouse.storage_profile;
+        storage_profile
+            .validate_access(Some(&storage_credential), None)
+            .await?;
+        let secret_id = context
+            .v1_state
+            .secrets
+            .create_secret(storage_credential)
+            .await?;
+        C::update_storage_profile(
+            warehouse_id,
+            storage_profile,
+            Some(secret_id),
+            transaction.transaction(),
+        )
+        .await?;
+        transaction.commit().await?;
+        invalidate_warehouse_config(warehouse_id);
+        tracing::info!(
+            %warehouse_id,
+            %old_secret_id,
+            %secret_id,
+            ?status,
+            "Relinked storage credential of warehouse"
+        );
+
+        if status == CredentialStatus::Unreadable {
+            context
+                .v1_state
+                .secrets
+                .delete_secret(&old_secret_id)
+                .await
+                .map_err(|e| {
+                    tracing::warn!("Failed to delete unreadable secret: {:?}", e.error);
+                })
+                .ok();
+        }
+
+        Ok(())
+    }
+
+    async fn validate_storage(
+        request: ValidateStorageRequest,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<StorageValidationReport> {
+        let ValidateStorageRequest {
+            project_id,
+            mut storage_profile,
+            storage_credential,
+        } = request;
+        let project_id = require_project_id_or_else(project_id, &request_metadata, || {
+            missing_project_id("ValidateStorageProjectIdMissing")
+        })?;
+
+        // ------------------- AuthZ -------------------
+        // Validation writes to the storage - require the same permission as creating a warehouse
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_project_action(
+                &request_metadata,
+                project_id,
+                &CatalogProjectAction::CanCreateWarehouse,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        storage_profile.normalize()?;
+        Ok(storage_profile
+            .validate_access_report(storage_credential.as_ref(), None)
+            .await)
+    }
+
+    async fn validate_warehouse_storage(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<StorageValidationReport> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        let storage_credential =
+            maybe_get_secret(warehouse.storage_secret_id, &context.v1_state.secrets).await?;
+        Ok(warehouse
+            .storage_profile
+            .validate_access_report(storage_credential.as_ref(), None)
+            .await)
+    }
+
+    async fn get_warehouse_statistics(
+        warehouse_id: WarehouseIdent,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<WarehouseStatisticsResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut transaction = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let warehouse = C::require_warehouse(warehouse_id, transaction.transaction()).await?;
+        let statistics =
+            C::get_warehouse_statistics(warehouse_id, transaction.transaction()).await?;
+        transaction.commit().await?;
+
+        Ok(WarehouseStatisticsResponse {
+            warehouse_id: *warehouse_id,
+            number_of_tables: statistics.number_of_tables,
+            number_of_views: statistics.number_of_views,
+            soft_deleted: SoftDeletedStatistics {
+                number_of_tables: statistics.number_of_soft_deleted_tables,
+                number_of_views: statistics.number_of_soft_deleted_views,
+                retained_bytes: statistics.soft_deleted_bytes,
+                number_of_tables_without_size: statistics.soft_deleted_tables_without_size,
+                expiration_seconds: warehouse
+                    .tabular_delete_profile
+                    .expiration_seconds()
+                    .map(|d| d.num_seconds()),
+            },
+        })
     }
 
     async fn undrop_tabulars(
@@ -668,13 +1689,12 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             .into_iter()
             .map(|i| TableIdentUuid::from(*i))
             .collect::<Vec<_>>();
-        let tasks_to_cancel = C::undrop_tabulars(&tabs, transaction.transaction()).await?;
-        context
-            .v1_state
-            .queues
-            .cancel_tabular_expiration(TaskFilter::TaskIds(tasks_to_cancel))
-            .await?;
+        // Expiration tasks are cancelled within the transaction
+        let cancelled_tasks = C::undrop_tabulars(&tabs, transaction.transaction()).await?;
         transaction.commit().await?;
+        // Targets are not restricted to a single warehouse.
+        invalidate_all_missing_idents();
+        tracing::debug!("Cancelled expiration tasks {cancelled_tasks:?} of undropped tabulars");
 
         // TODO: emit event
 
@@ -701,7 +1721,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
 
         // ------------------- Business Logic -------------------
         let pagination_query = query.pagination_query();
-        let namespace_id = query.namespace_id;
+        let filter = query.filter();
         let mut t = C::Transaction::begin_read(catalog.clone()).await?;
         let (tabulars, idents, next_page_token) =
             crate::catalog::fetch_until_full_page::<_, _, _, C>(
@@ -716,14 +1736,9 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
                             page_token: page_token.into(),
                         };
 
-                        let page = C::list_tabulars(
-                            warehouse_id,
-                            namespace_id,
-                            ListFlags::only_deleted(),
-                            t.transaction(),
-                            query,
-                        )
-                        .await?;
+                        let page =
+                            C::list_deleted_tabulars(warehouse_id, filter, t.transaction(), query)
+                                .await?;
                         let (ids, idents, tokens): (Vec<_>, Vec<_>, Vec<_>) =
                             page.into_iter_with_page_tokens().multiunzip();
 
@@ -788,6 +1803,7 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
                     created_at: deleted.created_at,
                     deleted_at: deleted.deleted_at,
                     expiration_date: deleted.expiration_date,
+                    expiration_exempt: deleted.expiration_exempt,
                 })
             })
             .collect::<Result<Vec<_>>>()?;
@@ -799,6 +1815,174 @@ pub trait Service<C: Catalog, A: Authorizer, S: SecretStore> {
             next_page_token,
         })
     }
+
+    async fn get_storage_usage(
+        warehouse_id: WarehouseIdent,
+        query: StorageUsageQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<StorageUsageResponse> {
+        // ------------------- AuthZ -------------------
+        // Like the warehouse statistics, usage is metadata of the warehouse.
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        C::require_warehouse(warehouse_id, t.transaction()).await?;
+        let usage = C::list_storage_usage(warehouse_id, query.day, t.transaction()).await?;
+        t.commit().await?;
+
+        Ok(StorageUsageResponse {
+            day: usage.first().map(|u| u.day).or(query.day),
+            namespaces: usage
+                .into_iter()
+                .map(|u| NamespaceStorageUsage {
+                    namespace_id: *u.namespace_id,
+                    namespace: u.namespace.inner(),
+                    number_of_tables: u.number_of_tables,
+                    total_bytes: u.total_bytes,
+                    number_of_tables_without_size: u.tables_without_size,
+                })
+                .collect(),
+        })
+    }
+
+    async fn get_table_storage_usage(
+        warehouse_id: WarehouseIdent,
+        query: TableStorageUsageQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<TableStorageUsageResponse> {
+        // ------------------- AuthZ -------------------
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanGetMetadata,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let page_size = query.page_size.clamp(1, MAX_PAGE_SIZE);
+        let pagination_query = PaginationQuery {
+            page_token: query
+                .page_token
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(page_size),
+        };
+
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        C::require_warehouse(warehouse_id, t.transaction()).await?;
+        let usage = C::list_table_storage_usage(
+            warehouse_id,
+            query.day,
+            query.namespace_id,
+            pagination_query,
+            t.transaction(),
+        )
+        .await?;
+        t.commit().await?;
+
+        let next_page_token = if i64::try_from(usage.len()).unwrap_or(i64::MAX) >= page_size {
+            usage.next_token().map(ToString::to_string)
+        } else {
+            None
+        };
+        let mut day = query.day;
+        let tables = usage
+            .into_iter_with_page_tokens()
+            .map(|(_, u, _)| {
+                day = Some(u.day);
+                TableStorageUsage {
+                    table_id: *u.table_id,
+                    name: u.name,
+                    namespace_id: *u.namespace_id,
+                    namespace: u.namespace.inner(),
+                    total_bytes: u.total_bytes,
+                }
+            })
+            .collect();
+
+        Ok(TableStorageUsageResponse {
+            day,
+            tables,
+            next_page_token,
+        })
+    }
+
+    async fn list_location_usage(
+        warehouse_id: WarehouseIdent,
+        query: LocationUsageQuery,
+        context: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
+    ) -> Result<LocationUsageResponse> {
+        // ------------------- AuthZ -------------------
+        // Operators that manage the storage of the warehouse may inspect all tabulars within it
+        let authorizer = context.v1_state.authz;
+        authorizer
+            .require_warehouse_action(
+                &request_metadata,
+                warehouse_id,
+                &CatalogWarehouseAction::CanUpdateStorage,
+            )
+            .await?;
+
+        // ------------------- Business Logic -------------------
+        let location = Location::from_str(&query.prefix).map_err(|e| {
+            ErrorModel::bad_request(
+                format!("Invalid prefix {}", query.prefix),
+                "InvalidLocation",
+                Some(Box::new(e)),
+            )
+        })?;
+        let page_size = query.page_size.clamp(1, MAX_PAGE_SIZE);
+        let pagination_query = PaginationQuery {
+            page_token: query
+                .page_token
+                .map_or(PageToken::Empty, PageToken::Present),
+            page_size: Some(page_size),
+        };
+
+        let mut t = C::Transaction::begin_read(context.v1_state.catalog).await?;
+        let usages =
+            C::list_location_usage(warehouse_id, &location, t.transaction(), pagination_query)
+                .await?;
+        t.commit().await?;
+
+        let next_page_token = if i64::try_from(usages.len()).unwrap_or(i64::MAX) >= page_size {
+            usages.next_token().map(ToString::to_string)
+        } else {
+            None
+        };
+        let tabulars = usages
+            .into_iter_with_page_tokens()
+            .map(|(id, usage, _)| {
+                let ident = usage.tabular.into_inner();
+                LocationUsageTabular {
+                    id: *id,
+                    name: ident.name,
+                    namespace: ident.namespace.inner(),
+                    typ: id.into(),
+                    location: usage.location,
+                    staged: usage.staged,
+                    deleted_at: usage.deleted_at,
+                }
+            })
+            .collect();
+
+        Ok(LocationUsageResponse {
+            tabulars,
+            next_page_token,
+        })
+    }
 }
 
 impl axum::response::IntoResponse for ListWarehousesResponse {
@@ -813,16 +1997,60 @@ impl axum::response::IntoResponse for GetWarehouseResponse {
     }
 }
 
-impl From<crate::service::GetWarehouseResponse> for GetWarehouseResponse {
-    fn from(warehouse: crate::service::GetWarehouseResponse) -> Self {
-        Self {
+impl GetWarehouseResponse {
+    /// Build the response, only including storage details the caller is allowed to see.
+    async fn authorized<A: Authorizer>(
+        authorizer: &A,
+        request_metadata: &RequestMetadata,
+        warehouse: crate::service::GetWarehouseResponse,
+    ) -> Result<Self> {
+        let (can_get_storage_profile, can_get_storage_credential_id) = futures::try_join!(
+            authorizer.is_allowed_warehouse_action(
+                request_metadata,
+                warehouse.id,
+                &CatalogWarehouseAction::CanGetStorageProfile,
+            ),
+            authorizer.is_allowed_warehouse_action(
+                request_metadata,
+                warehouse.id,
+                &CatalogWarehouseAction::CanGetStorageCredentialId,
+            ),
+        )?;
+
+        Ok(Self {
             id: warehouse.id.to_uuid(),
             name: warehouse.name,
             project_id: *warehouse.project_id,
-            storage_profile: warehouse.storage_profile,
+            storage_profile: can_get_storage_profile.then_some(warehouse.storage_profile),
+            storage_credential_id: warehouse
+                .storage_secret_id
+                .filter(|_| can_get_storage_credential_id)
+                .map(uuid::Uuid::from),
             status: warehouse.status,
             delete_profile: warehouse.tabular_delete_profile,
-        }
+            storage_health: warehouse.storage_health,
+            namespace_defaults: warehouse.namespace_defaults,
+            lifecycle: warehouse.lifecycle,
+            allowed_client_networks: warehouse.allowed_client_networks,
+            table_limits: warehouse.table_limits,
+            snapshot_summary_enrichment: warehouse.snapshot_summary_enrichment,
+            wasm_hooks: warehouse.wasm_hooks,
+            summary: warehouse.summary,
+            credential_status: None,
+        })
+    }
+}
+
+/// Replacing an existing credential with a new one is a rotation. Setting a credential
+/// on a warehouse without one or removing it changes how the storage is accessed.
+fn storage_credential_update_action(
+    has_credential: bool,
+    sets_credential: bool,
+) -> CatalogWarehouseAction {
+    if has_credential && sets_credential {
+        CatalogWarehouseAction::CanRotateStorageCredential
+    } else {
+        CatalogWarehouseAction::CanUpdateStorageCredential
     }
 }
 
@@ -884,24 +2112,116 @@ mod test {
         assert_eq!(s3_profile.bucket, "test");
         assert_eq!(s3_profile.region, "dummy");
         assert_eq!(s3_profile.path_style_access, Some(true));
+        assert_eq!(request.lifecycle, super::WarehouseLifecycle::Permanent {});
+    }
+
+    #[test]
+    fn test_storage_credential_update_action() {
+        use crate::service::authz::CatalogWarehouseAction;
+
+        // Replacing a credential only requires the permission to rotate it
+        assert_eq!(
+            super::storage_credential_update_action(true, true),
+            CatalogWarehouseAction::CanRotateStorageCredential
+        );
+        // Removing a credential changes how the storage is accessed
+        assert_eq!(
+            super::storage_credential_update_action(true, false),
+            CatalogWarehouseAction::CanUpdateStorageCredential
+        );
+        // As does setting one on a warehouse without a credential
+        assert_eq!(
+            super::storage_credential_update_action(false, true),
+            CatalogWarehouseAction::CanUpdateStorageCredential
+        );
+        assert_eq!(
+            super::storage_credential_update_action(false, false),
+            CatalogWarehouseAction::CanUpdateStorageCredential
+        );
+    }
+
+    #[test]
+    fn test_warehouse_lifecycle() {
+        let lifecycle: super::WarehouseLifecycle = serde_json::from_value(serde_json::json!({
+            "type": "ephemeral",
+            "max-idle-seconds": 3600,
+        }))
+        .unwrap();
+        assert_eq!(
+            lifecycle,
+            super::WarehouseLifecycle::Ephemeral {
+                max_lifetime_seconds: None,
+                max_idle_seconds: Some(3600),
+            }
+        );
+        lifecycle.validate().unwrap();
+
+        super::WarehouseLifecycle::Ephemeral {
+            max_lifetime_seconds: None,
+            max_idle_seconds: None,
+        }
+        .validate()
+        .unwrap_err();
+        super::WarehouseLifecycle::Ephemeral {
+            max_lifetime_seconds: Some(0),
+            max_idle_seconds: None,
+        }
+        .validate()
+        .unwrap_err();
+    }
+
+    #[test]
+    fn test_table_limits() {
+        let limits: super::TableLimits = serde_json::from_value(serde_json::json!({
+            "snapshot-count-warning": 10_000,
+            "snapshot-count-limit": 50_000,
+        }))
+        .unwrap();
+        assert_eq!(limits.snapshot_count_limit, Some(50_000));
+        assert_eq!(limits.metadata_size_limit_bytes, None);
+        limits.validate().unwrap();
+
+        super::TableLimits {
+            snapshot_count_warning: Some(100),
+            snapshot_count_limit: Some(10),
+            ..Default::default()
+        }
+        .validate()
+        .unwrap_err();
+        super::TableLimits {
+            metadata_size_limit_bytes: Some(0),
+            ..Default::default()
+        }
+        .validate()
+        .unwrap_err();
     }
 
     use crate::api::iceberg::types::Prefix;
     use crate::api::iceberg::v1::{DataAccess, DropParams, NamespaceParameters, ViewParameters};
+    use crate::catalog::namespace::NAMESPACE_ID_PROPERTY;
     use crate::catalog::test::{impl_pagination_tests, random_request_metadata};
     use crate::catalog::CatalogServer;
     use crate::service::authz::implementations::openfga::tests::ObjectHidingMock;
-    use iceberg::TableIdent;
+    use crate::service::authz::AllowAllAuthorizer;
+    use crate::service::NamespaceIdentUuid;
+    use iceberg::{NamespaceIdent, TableIdent};
+    use iceberg_ext::catalog::rest::CreateNamespaceRequest;
     use sqlx::PgPool;
+    use std::str::FromStr;
 
+    use crate::api::iceberg::v1::namespace::Service as _;
     use crate::api::iceberg::v1::views::Service;
     use crate::api::management::v1::warehouse::{
-        ListDeletedTabularsQuery, Service as _, TabularDeleteProfile,
+        CredentialStatus, DeletedTabularSortBy, ListDeletedTabularsQuery, Service as _,
+        TabularDeleteProfile,
     };
-    use crate::api::management::v1::ApiServer;
+    use crate::api::management::v1::{ApiServer, ListDeletedTabularsResponse, SortOrder};
     use crate::api::ApiContext;
     use crate::implementations::postgres::{PostgresCatalog, SecretsState};
     use crate::service::authz::implementations::openfga::OpenFGAAuthorizer;
+    use crate::service::authz::Authorizer;
+    use crate::service::secrets::{SecretIdent, SecretStore as _};
+    use crate::service::storage::StorageCredential;
     use crate::service::{State, UserId};
     use crate::WarehouseIdent;
     use itertools::Itertools;
@@ -968,6 +2288,7 @@ mod test {
                 },
                 DropParams {
                     purge_requested: None,
+                    force: None,
                 },
                 ctx.clone(),
                 random_request_metadata(),
@@ -1049,6 +2370,7 @@ mod test {
                 },
                 DropParams {
                     purge_requested: None,
+                    force: None,
                 },
                 ctx.clone(),
                 random_request_metadata(),
@@ -1062,6 +2384,9 @@ mod test {
             warehouse.warehouse_id,
             ListDeletedTabularsQuery {
                 namespace_id: None,
+                include_child_namespaces: false,
+                sort_by: DeletedTabularSortBy::default(),
+                sort_order: SortOrder::default(),
                 page_size: 11,
                 page_token: None,
             },
@@ -1077,6 +2402,9 @@ mod test {
             warehouse.warehouse_id,
             ListDeletedTabularsQuery {
                 namespace_id: None,
+                include_child_namespaces: false,
+                sort_by: DeletedTabularSortBy::default(),
+                sort_order: SortOrder::default(),
                 page_size: 10,
                 page_token: None,
             },
@@ -1092,6 +2420,9 @@ mod test {
             warehouse.warehouse_id,
             ListDeletedTabularsQuery {
                 namespace_id: None,
+                include_child_namespaces: false,
+                sort_by: DeletedTabularSortBy::default(),
+                sort_order: SortOrder::default(),
                 page_size: 10,
                 page_token: all.next_page_token,
             },
@@ -1107,6 +2438,9 @@ mod test {
             warehouse.warehouse_id,
             ListDeletedTabularsQuery {
                 namespace_id: None,
+                include_child_namespaces: false,
+                sort_by: DeletedTabularSortBy::default(),
+                sort_order: SortOrder::default(),
                 page_size: 6,
                 page_token: None,
             },
@@ -1132,6 +2466,9 @@ mod test {
             warehouse.warehouse_id,
             ListDeletedTabularsQuery {
                 namespace_id: None,
+                include_child_namespaces: false,
+                sort_by: DeletedTabularSortBy::default(),
+                sort_order: SortOrder::default(),
                 page_size: 6,
                 page_token: first_six.next_page_token,
             },
@@ -1165,6 +2502,9 @@ mod test {
             warehouse.warehouse_id,
             ListDeletedTabularsQuery {
                 namespace_id: None,
+                include_child_namespaces: false,
+                sort_by: DeletedTabularSortBy::default(),
+                sort_order: SortOrder::default(),
                 page_size: 5,
                 page_token: None,
             },
@@ -1191,6 +2531,9 @@ mod test {
             warehouse.warehouse_id,
             ListDeletedTabularsQuery {
                 namespace_id: None,
+                include_child_namespaces: false,
+                sort_by: DeletedTabularSortBy::default(),
+                sort_order: SortOrder::default(),
                 page_size: 6,
                 page_token: page.next_page_token,
             },
@@ -1213,4 +2556,300 @@ mod test {
             assert_eq!(next_page_items[idx], format!("view-{i}"));
         }
     }
+
+    async fn create_ns_with_id(
+        ctx: ApiContext<State<AllowAllAuthorizer, PostgresCatalog, SecretsState>>,
+        warehouse_id: WarehouseIdent,
+        namespace: &[&str],
+    ) -> NamespaceIdentUuid {
+        let response = CatalogServer::create_namespace(
+            Some(Prefix(warehouse_id.to_string())),
+            CreateNamespaceRequest {
+                namespace: NamespaceIdent::from_strs(namespace).unwrap(),
+                properties: None,
+            },
+            ctx,
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        NamespaceIdentUuid::from_str(
+            response
+                .properties
+                .unwrap()
+                .get(NAMESPACE_ID_PROPERTY)
+                .unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_deleted_tabulars_namespace_filter_and_sort(pool: sqlx::PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool,
+            crate::catalog::test::test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Soft {
+                expiration_seconds: chrono::Duration::seconds(10),
+            },
+            Some(UserId::OIDC("test-user-id".to_string())),
+        )
+        .await;
+        let warehouse_id = warehouse.warehouse_id;
+        let parent_id = create_ns_with_id(ctx.clone(), warehouse_id, &["parent"]).await;
+        create_ns_with_id(ctx.clone(), warehouse_id, &["parent", "child"]).await;
+        create_ns_with_id(ctx.clone(), warehouse_id, &["other"]).await;
+
+        let views = [
+            (vec!["parent"], "in-parent"),
+            (vec!["parent", "child"], "in-child"),
+            (vec!["other"], "in-other"),
+        ];
+        for (namespace, name) in &views {
+            CatalogServer::create_view(
+                NamespaceParameters {
+                    prefix: Some(Prefix(warehouse_id.to_string())),
+                    namespace: NamespaceIdent::from_strs(namespace).unwrap(),
+                },
+                crate::catalog::views::create::test::create_view_request(Some(*name), None),
+                ctx.clone(),
+                DataAccess {
+                    vended_credentials: true,
+                    remote_signing: false,
+                },
+                random_request_metadata(),
+            )
+            .await
+            .unwrap();
+        }
+        // Drop in a different order than created, so that sorting by creation and
+        // deletion time can be told apart.
+        for idx in [2, 0, 1] {
+            let (namespace, name) = &views[idx];
+            CatalogServer::drop_view(
+                ViewParameters {
+                    prefix: Some(Prefix(warehouse_id.to_string())),
+                    view: TableIdent::from_strs(namespace.iter().chain([name])).unwrap(),
+                },
+                DropParams {
+                    purge_requested: None,
+                    force: None,
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+            .await
+            .unwrap();
+        }
+
+        let list = |query: serde_json::Value| {
+            let ctx = ctx.clone();
+            async move {
+                ApiServer::list_soft_deleted_tabulars(
+                    warehouse_id,
+                    serde_json::from_value(query).unwrap(),
+                    ctx,
+                    random_request_metadata(),
+                )
+                .await
+                .unwrap()
+            }
+        };
+        let names = |response: &ListDeletedTabularsResponse| {
+            response
+                .tabulars
+                .iter()
+                .map(|t| t.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let only_parent = list(serde_json::json!({"namespaceId": parent_id})).await;
+        assert_eq!(names(&only_parent), vec!["in-parent"]);
+        let subtree = list(serde_json::json!({
+            "namespaceId": parent_id,
+            "includeChildNamespaces": true,
+        }))
+        .await;
+        assert_eq!(names(&subtree), vec!["in-parent", "in-child"]);
+
+        // Page through the warehouse one tabular at a time
+        for (sort_by, sort_order, expected) in [
+            ("created-at", "asc", ["in-parent", "in-child", "in-other"]),
+            ("deleted-at", "desc", ["in-child", "in-parent", "in-other"]),
+            (
+                "expiration-date",
+                "asc",
+                ["in-other", "in-parent", "in-child"],
+            ),
+        ] {
+            let mut page_token = None;
+            let mut listed = vec![];
+            loop {
+                let page = list(serde_json::json!({
+                    "sortBy": sort_by,
+                    "sortOrder": sort_order,
+                    "pageSize": 1,
+                    "pageToken": page_token,
+                }))
+                .await;
+                listed.extend(names(&page));
+                page_token = page.next_page_token;
+                if page_token.is_none() {
+                    break;
+                }
+            }
+            assert_eq!(listed, expected, "sort by {sort_by} {sort_order}");
+        }
+
+        // A token cannot continue a listing with another sort.
+        let page = list(serde_json::json!({
+            "sortBy": "deleted-at",
+            "sortOrder": "desc",
+            "pageSize": 1,
+        }))
+        .await;
+        let err = ApiServer::list_soft_deleted_tabulars(
+            warehouse_id,
+            serde_json::from_value(serde_json::json!({
+                "sortBy": "deleted-at",
+                "sortOrder": "asc",
+                "pageSize": 1,
+                "pageToken": page.next_page_token,
+            }))
+            .unwrap(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "PaginateTokenSortMismatch");
+    }
+
+    fn s3_credential(signing_keys: serde_json::Value) -> StorageCredential {
+        serde_json::from_value(serde_json::json!({
+            "type": "s3",
+            "credential-type": "access-key",
+            "aws-access-key-id": "test-access-key-id",
+            "aws-secret-access-key": "test-secret-access-key",
+            "signing-keys": signing_keys,
+        }))
+        .unwrap()
+    }
+
+    async fn credential_status<A: Authorizer + Clone>(
+        ctx: &ApiContext<State<A, PostgresCatalog, SecretsState>>,
+        warehouse_id: WarehouseIdent,
+    ) -> (Option<CredentialStatus>, SecretIdent) {
+        let warehouse =
+            ApiServer::get_warehouse(warehouse_id, ctx.clone(), random_request_metadata())
+                .await
+                .unwrap();
+        (
+            warehouse.credential_status,
+            SecretIdent::from(warehouse.storage_credential_id.unwrap()),
+        )
+    }
+
+    #[sqlx::test]
+    async fn test_credential_status(pool: PgPool) {
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            Some(s3_credential(serde_json::json!([]))),
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            None,
+        )
+        .await;
+        let warehouse_id = warehouse.warehouse_id;
+
+        let (status, secret_id) = credential_status(&ctx, warehouse_id).await;
+        assert_eq!(status, Some(CredentialStatus::Valid));
+
+        // Encrypted with another key, the secret can no longer be decrypted.
+        sqlx::query(
+            "UPDATE secret SET secret = pgp_sym_encrypt('{}', 'not-the-encryption-key') WHERE secret_id = $1",
+        )
+        .bind(secret_id.into_uuid())
+        .execute(&pool)
+        .await
+        .unwrap();
+        let (status, _) = credential_status(&ctx, warehouse_id).await;
+        assert_eq!(status, Some(CredentialStatus::Unreadable));
+
+        ctx.v1_state
+            .secrets
+            .delete_secret(&secret_id)
+            .await
+            .unwrap();
+        let (status, _) = credential_status(&ctx, warehouse_id).await;
+        assert_eq!(status, Some(CredentialStatus::Missing));
+    }
+
+    #[sqlx::test]
+    async fn test_relink_storage_credential(pool: PgPool) {
+        let hiding_mock = ObjectHidingMock::new();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            crate::catalog::test::test_io_profile(),
+            Some(s3_credential(serde_json::json!([]))),
+            hiding_mock.to_authorizer(),
+            TabularDeleteProfile::Hard {},
+            Some(UserId::OIDC("test-user-id".to_string())),
+        )
+        .await;
+        let warehouse_id = warehouse.warehouse_id;
+        let relink = |storage_credential: StorageCredential| {
+            ApiServer::relink_storage_credential(
+                warehouse_id,
+                super::RelinkWarehouseCredentialRequest { storage_credential },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+        };
+
+        // Readable credentials are rotated, not relinked.
+        let e = relink(s3_credential(serde_json::json!([])))
+            .await
+            .unwrap_err();
+        assert_eq!(e.error.code, http::StatusCode::CONFLICT, "{e:?}");
+        assert_eq!(e.error.r#type, "StorageCredentialValid");
+
+        let (_, old_secret_id) = credential_status(&ctx, warehouse_id).await;
+        ctx.v1_state
+            .secrets
+            .delete_secret(&old_secret_id)
+            .await
+            .unwrap();
+
+        let invalid = s3_credential(serde_json::json!([{
+            "key-id": "",
+            "aws-access-key-id": "signing-access-key-id",
+            "aws-secret-access-key": "signing-secret-access-key",
+        }]));
+        let e = relink(invalid).await.unwrap_err();
+        assert_eq!(e.error.code, http::StatusCode::BAD_REQUEST, "{e:?}");
+        assert_eq!(e.error.r#type, "InvalidSigningKeys");
+
+        hiding_mock.deny(
+            &format!("warehouse:{warehouse_id}"),
+            "can_update_storage_credential",
+        );
+        let e = relink(s3_credential(serde_json::json!([])))
+            .await
+            .unwrap_err();
+        assert_eq!(e.error.code, http::StatusCode::FORBIDDEN, "{e:?}");
+
+        // Failed relinks keep the broken credential.
+        let (status, secret_id) = credential_status(&ctx, warehouse_id).await;
+        assert_eq!(status, Some(CredentialStatus::Missing));
+        assert_eq!(secret_id, old_secret_id);
+
+        hiding_mock.denied.write().unwrap().clear();
+        relink(s3_credential(serde_json::json!([]))).await.unwrap();
+        let (status, secret_id) = credential_status(&ctx, warehouse_id).await;
+        assert_eq!(status, Some(CredentialStatus::Valid));
+        assert_ne!(secret_id, old_secret_id);
+    }
 }
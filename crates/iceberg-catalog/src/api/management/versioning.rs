@@ -0,0 +1,265 @@
+//! Versions of the management API and the deprecation of their routes.
+//!
+//! `v1` is frozen: its routes keep their behavior until they are removed after their
+//! sunset. New endpoints are only added to `v2`, which serves all routes of `v1` that are
+//! not deprecated. Deprecated routes of any version announce their successor with a `Link`
+//! header and, if the operator configured the dates, with the `Deprecation` (RFC 9745) and
+//! `Sunset` (RFC 8594) headers, so that clients can migrate before they are removed.
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::MethodRouter;
+use http::{HeaderName, HeaderValue, Method};
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+
+use crate::CONFIG;
+
+pub const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+pub const SUNSET_HEADER: HeaderName = HeaderName::from_static("sunset");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManagementApiVersion {
+    V1,
+    V2,
+}
+
+impl ManagementApiVersion {
+    /// Path all routes of this version are nested under.
+    #[must_use]
+    pub fn prefix(self) -> &'static str {
+        match self {
+            ManagementApiVersion::V1 => "/management/v1",
+            ManagementApiVersion::V2 => "/management/v2",
+        }
+    }
+}
+
+/// A deprecated route of the management API.
+#[derive(Debug)]
+pub(crate) struct DeprecatedRoute {
+    /// Version the route is deprecated in. Later versions do not serve it.
+    pub(crate) version: ManagementApiVersion,
+    pub(crate) method: Method,
+    /// Route relative to the version prefix.
+    pub(crate) path: &'static str,
+    /// Operation of the route in the `OpenAPI` document.
+    pub(crate) operation_id: &'static str,
+    /// Route to use instead.
+    pub(crate) successor: &'static str,
+}
+
+pub(crate) const DEPRECATED_ROUTES: &[DeprecatedRoute] = &[
+    DeprecatedRoute {
+        version: ManagementApiVersion::V1,
+        method: Method::GET,
+        path: "/project",
+        operation_id: "get_default_project",
+        successor: "/management/v2/project/{project_id}",
+    },
+    DeprecatedRoute {
+        version: ManagementApiVersion::V1,
+        method: Method::DELETE,
+        path: "/project",
+        operation_id: "delete_default_project",
+        successor: "/management/v2/project/{project_id}",
+    },
+    DeprecatedRoute {
+        version: ManagementApiVersion::V1,
+        method: Method::POST,
+        path: "/project/rename",
+        operation_id: "rename_default_project",
+        successor: "/management/v2/project/{project_id}/rename",
+    },
+];
+
+fn find_deprecated_route(operation_id: &str) -> Option<&'static DeprecatedRoute> {
+    DEPRECATED_ROUTES
+        .iter()
+        .find(|route| route.operation_id == operation_id)
+}
+
+/// Serves `method_router` as the deprecated operation `operation_id`.
+///
+/// The headers are attached by a layer of the route itself, so they are sent
+/// wherever the management router is nested.
+///
+/// # Panics
+/// If the operation is not listed in [`DEPRECATED_ROUTES`].
+pub(crate) fn deprecated<S>(operation_id: &str, method_router: MethodRouter<S>) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let route = find_deprecated_route(operation_id)
+        .unwrap_or_else(|| panic!("Operation {operation_id} is not a deprecated route"));
+    method_router.route_layer(axum::middleware::from_fn_with_state(
+        route,
+        deprecation_middleware_fn,
+    ))
+}
+
+/// Whether the operation is deprecated in `version`.
+pub(crate) fn is_deprecated_operation(version: ManagementApiVersion, operation_id: &str) -> bool {
+    DEPRECATED_ROUTES
+        .iter()
+        .any(|route| route.version == version && route.operation_id == operation_id)
+}
+
+impl DeprecatedRoute {
+    /// `Deprecation` and `Sunset` are only sent if the operator configured their dates.
+    fn headers(
+        &self,
+        deprecated_at: Option<chrono::DateTime<chrono::Utc>>,
+        sunset_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Vec<(HeaderName, HeaderValue)> {
+        let mut headers = vec![(
+            http::header::LINK,
+            HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", self.successor))
+                .expect("Route is a valid header value"),
+        )];
+        if let Some(deprecated_at) = deprecated_at {
+            headers.push((
+                DEPRECATION_HEADER,
+                HeaderValue::from_str(&format!("@{}", deprecated_at.timestamp()))
+                    .expect("Timestamp is a valid header value"),
+            ));
+        }
+        if let Some(sunset_at) = sunset_at {
+            headers.push((
+                SUNSET_HEADER,
+                HeaderValue::from_str(&sunset_at.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+                    .expect("HTTP date is a valid header value"),
+            ));
+        }
+        headers
+    }
+}
+
+/// Adds deprecation headers to responses of a deprecated route, or rejects requests
+/// to it with `410 Gone` if deprecated routes are disabled.
+/// Added to the route by [`deprecated`].
+async fn deprecation_middleware_fn(
+    State(route): State<&'static DeprecatedRoute>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = if CONFIG.disable_deprecated_management_routes {
+        IcebergErrorResponse::from(
+            ErrorModel::new(
+                format!(
+                    "{} {}{} is deprecated and disabled on this server.",
+                    route.method,
+                    route.version.prefix(),
+                    route.path
+                ),
+                "DeprecatedRouteDisabled",
+                http::StatusCode::GONE.as_u16(),
+                None,
+            )
+            .append_detail(format!("Use {} instead.", route.successor)),
+        )
+        .into_response()
+    } else {
+        next.run(request).await
+    };
+    response.headers_mut().extend(route.headers(
+        CONFIG.deprecated_management_routes_deprecation_date,
+        CONFIG.deprecated_management_routes_sunset_date,
+    ));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deprecated_route_headers() {
+        let route = find_deprecated_route("get_default_project").unwrap();
+        let headers = route.headers(None, None);
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].0, http::header::LINK);
+        assert_eq!(
+            headers[0].1,
+            "</management/v2/project/{project_id}>; rel=\"successor-version\""
+        );
+
+        let headers = route.headers(
+            chrono::DateTime::from_timestamp(1_739_577_600, 0),
+            chrono::DateTime::from_timestamp(1_755_216_000, 0),
+        );
+        assert_eq!(
+            headers[1],
+            (DEPRECATION_HEADER, "@1739577600".parse().unwrap())
+        );
+        assert_eq!(
+            headers[2],
+            (
+                SUNSET_HEADER,
+                "Fri, 15 Aug 2025 00:00:00 GMT".parse().unwrap()
+            )
+        );
+
+        assert!(find_deprecated_route("create_project").is_none());
+    }
+
+    #[test]
+    fn test_deprecated_routes_are_versioned() {
+        for route in DEPRECATED_ROUTES {
+            assert!(!route.path.starts_with("/management"));
+            assert!(!route.successor.starts_with(route.version.prefix()));
+        }
+        assert!(is_deprecated_operation(
+            ManagementApiVersion::V1,
+            "get_default_project"
+        ));
+        assert!(!is_deprecated_operation(
+            ManagementApiVersion::V2,
+            "get_default_project"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_route_headers_in_nested_router() {
+        use tower::ServiceExt;
+
+        let management_routes = axum::Router::new()
+            .route(
+                "/project",
+                deprecated("get_default_project", axum::routing::get(|| async { "" })),
+            )
+            .route("/project-list", axum::routing::get(|| async { "" }));
+        let router = axum::Router::new().nest(
+            &format!("/lakekeeper{}", ManagementApiVersion::V1.prefix()),
+            management_routes,
+        );
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/lakekeeper/management/v1/project")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::LINK],
+            "</management/v2/project/{project_id}>; rel=\"successor-version\""
+        );
+
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/lakekeeper/management/v1/project-list")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+        assert!(response.headers().get(http::header::LINK).is_none());
+    }
+}
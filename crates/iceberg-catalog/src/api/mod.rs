@@ -1,6 +1,10 @@
 pub mod iceberg;
 pub mod management;
 
+#[cfg(feature = "router")]
+pub mod openapi;
+#[cfg(feature = "router")]
+pub(crate) mod response_validation;
 #[cfg(feature = "router")]
 pub mod router;
 
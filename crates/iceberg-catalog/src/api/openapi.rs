@@ -0,0 +1,313 @@
+//! `OpenAPI` documents tailored to the running deployment.
+//!
+//! The specs in `openapi/` describe every endpoint Lakekeeper may expose. The documents
+//! served here instead point to the configured `base_uri`, only contain endpoints this
+//! deployment actually serves and list the enabled features, so that generated clients
+//! match the server they talk to.
+
+use crate::api::management::v1::api_doc as v1_api_doc;
+use crate::api::management::v1::bootstrap::AuthZBackend;
+use crate::api::management::versioning::{self, ManagementApiVersion};
+use crate::api::router::ICEBERG_OPENAPI_SPEC_YAML;
+use crate::service::authz::Authorizer;
+use crate::CONFIG;
+use axum::response::{IntoResponse, Response};
+use http::{header, HeaderValue, StatusCode};
+use serde::Serialize;
+
+/// Name of the extension holding the [`DeploymentFeatures`] in served documents.
+const FEATURES_EXTENSION: &str = "x-lakekeeper-features";
+
+/// Iceberg REST endpoints that are part of the spec but not served by Lakekeeper.
+const UNSUPPORTED_CATALOG_PATH_SUFFIXES: &[&str] = &["/plan", "/plan/{plan-id}", "/tasks"];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct DeploymentFeatures {
+    /// Version of the server.
+    pub(crate) version: &'static str,
+    /// `AuthZ` backend in use.
+    pub(crate) authz_backend: AuthZBackend,
+    /// Whether tokens of an `OpenID` provider are accepted.
+    pub(crate) openid_authentication: bool,
+    /// Whether Kubernetes service account tokens are accepted.
+    pub(crate) kubernetes_authentication: bool,
+    /// Whether the S3 remote signing endpoints are compiled in.
+    pub(crate) s3_signer: bool,
+    /// Whether change events can be published to NATS.
+    pub(crate) nats: bool,
+}
+
+impl DeploymentFeatures {
+    pub(crate) fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            authz_backend: AuthZBackend::from(&CONFIG.authz_backend),
+            openid_authentication: CONFIG.authn_enabled(),
+            kubernetes_authentication: CONFIG.enable_kubernetes_authentication,
+            s3_signer: cfg!(feature = "s3-signer"),
+            nats: cfg!(feature = "nats"),
+        }
+    }
+}
+
+/// Management API `v1` spec of this deployment.
+///
+/// # Panics
+/// If the generated document cannot be serialized, which would be a bug in the spec.
+#[must_use]
+pub fn management_api_doc<A: Authorizer>() -> serde_json::Value {
+    let doc = serde_json::to_value(v1_api_doc::<A>())
+        .expect("Management OpenAPI document must serialize to JSON");
+    let doc = with_deprecated_operations(
+        without_v2_operations(doc),
+        ManagementApiVersion::V1,
+        !CONFIG.disable_deprecated_management_routes,
+    );
+    for_deployment(doc, &DeploymentFeatures::current(), &base_url())
+}
+
+/// Management API `v2` spec of this deployment.
+///
+/// # Panics
+/// If the generated document cannot be serialized, which would be a bug in the spec.
+#[must_use]
+pub fn management_v2_api_doc<A: Authorizer>() -> serde_json::Value {
+    let doc = serde_json::to_value(v1_api_doc::<A>())
+        .expect("Management OpenAPI document must serialize to JSON");
+    let doc = with_deprecated_operations(
+        v1_doc_into_v2(doc),
+        ManagementApiVersion::V2,
+        !CONFIG.disable_deprecated_management_routes,
+    );
+    for_deployment(doc, &DeploymentFeatures::current(), &base_url())
+}
+
+/// Iceberg REST Catalog spec of this deployment.
+#[must_use]
+pub fn catalog_api_doc() -> serde_json::Value {
+    let mut doc = ICEBERG_OPENAPI_SPEC_YAML.clone();
+    if let Some(paths) = doc
+        .get_mut("paths")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        paths.retain(|path, _| {
+            !UNSUPPORTED_CATALOG_PATH_SUFFIXES
+                .iter()
+                .any(|suffix| path.ends_with(suffix))
+        });
+    }
+    for_deployment(doc, &DeploymentFeatures::current(), &base_url())
+}
+
+/// Paths of all documents start at the root, i.e. `/management/v1/...`.
+fn base_url() -> String {
+    CONFIG.base_uri.as_str().trim_end_matches('/').to_string()
+}
+
+fn is_deprecated_operation(version: ManagementApiVersion, operation: &serde_json::Value) -> bool {
+    operation
+        .get("operationId")
+        .and_then(serde_json::Value::as_str)
+        .is_some_and(|operation_id| versioning::is_deprecated_operation(version, operation_id))
+}
+
+/// Marks operations deprecated in `version`, or removes them if they are not `served`.
+fn with_deprecated_operations(
+    mut doc: serde_json::Value,
+    version: ManagementApiVersion,
+    served: bool,
+) -> serde_json::Value {
+    if let Some(paths) = doc
+        .get_mut("paths")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        for operations in paths
+            .values_mut()
+            .filter_map(serde_json::Value::as_object_mut)
+        {
+            operations
+                .retain(|_, operation| served || !is_deprecated_operation(version, operation));
+            for operation in operations.values_mut() {
+                if is_deprecated_operation(version, operation) {
+                    operation["deprecated"] = true.into();
+                }
+            }
+        }
+        paths.retain(|_, operations| {
+            operations
+                .as_object()
+                .map_or(true, |operations| !operations.is_empty())
+        });
+    }
+    doc
+}
+
+/// Removes the endpoints added after `v1` was frozen, which only `v2` serves.
+fn without_v2_operations(mut doc: serde_json::Value) -> serde_json::Value {
+    if let Some(paths) = doc
+        .get_mut("paths")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        paths.retain(|path, _| !path.starts_with(ManagementApiVersion::V2.prefix()));
+    }
+    doc
+}
+
+/// `v2` serves the routes of `v1` without the ones deprecated in `v1`,
+/// and the endpoints added after `v1` was frozen.
+fn v1_doc_into_v2(doc: serde_json::Value) -> serde_json::Value {
+    let mut doc = with_deprecated_operations(doc, ManagementApiVersion::V1, false);
+    if let Some(paths) = doc
+        .get_mut("paths")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        *paths = std::mem::take(paths)
+            .into_iter()
+            .map(|(path, operations)| {
+                let path = path.replacen(
+                    ManagementApiVersion::V1.prefix(),
+                    ManagementApiVersion::V2.prefix(),
+                    1,
+                );
+                (path, operations)
+            })
+            .collect();
+    }
+    doc
+}
+
+fn for_deployment(
+    mut doc: serde_json::Value,
+    features: &DeploymentFeatures,
+    base_url: &str,
+) -> serde_json::Value {
+    if let Some(doc) = doc.as_object_mut() {
+        doc.insert(
+            "servers".to_string(),
+            serde_json::json!([{ "url": base_url, "description": "This deployment" }]),
+        );
+        doc.insert(
+            FEATURES_EXTENSION.to_string(),
+            serde_json::to_value(features).unwrap_or_default(),
+        );
+        if let Some(info) = doc
+            .get_mut("info")
+            .and_then(serde_json::Value::as_object_mut)
+        {
+            info.insert("version".to_string(), features.version.into());
+        }
+    }
+    doc
+}
+
+/// Serve a document as YAML so that it can be downloaded and fed to client generators.
+pub(crate) fn yaml_response(doc: &serde_json::Value) -> Response {
+    match serde_yml::to_string(doc) {
+        Ok(yaml) => (
+            [(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("application/yaml"),
+            )],
+            yaml,
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to serialize OpenAPI document as YAML: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features() -> DeploymentFeatures {
+        DeploymentFeatures {
+            version: "0.0.0",
+            authz_backend: AuthZBackend::OpenFGA,
+            openid_authentication: true,
+            kubernetes_authentication: false,
+            s3_signer: true,
+            nats: false,
+        }
+    }
+
+    #[test]
+    fn test_for_deployment_sets_server_and_features() {
+        let doc = serde_json::json!({
+            "openapi": "3.1.0",
+            "info": {"title": "Test", "version": "1"},
+            "servers": [{"url": "{scheme}://{host}/{basePath}"}],
+            "paths": {}
+        });
+        let doc = for_deployment(doc, &features(), "https://lakekeeper.example.com/prefix");
+
+        assert_eq!(
+            doc["servers"],
+            serde_json::json!([{"url": "https://lakekeeper.example.com/prefix", "description": "This deployment"}])
+        );
+        assert_eq!(doc["info"]["version"], "0.0.0");
+        assert_eq!(doc[FEATURES_EXTENSION]["authz-backend"], "openfga");
+        assert_eq!(doc[FEATURES_EXTENSION]["s3-signer"], true);
+    }
+
+    #[test]
+    fn test_management_v2_omits_deprecated_operations() {
+        let doc = serde_json::json!({
+            "paths": {
+                "/management/v1/default-project": {
+                    "get": {"operationId": "get_default_project"},
+                    "delete": {"operationId": "delete_default_project"}
+                },
+                "/management/v1/project": {
+                    "post": {"operationId": "create_project"}
+                },
+                "/management/v2/project/{project_id}/overview": {
+                    "get": {"operationId": "get_project_overview"}
+                }
+            }
+        });
+
+        let v1 = with_deprecated_operations(doc.clone(), ManagementApiVersion::V1, true);
+        assert_eq!(
+            v1["paths"]["/management/v1/default-project"]["get"]["deprecated"],
+            true
+        );
+        assert!(v1["paths"]["/management/v1/project"]["post"]
+            .get("deprecated")
+            .is_none());
+
+        let v1_without_deprecated =
+            with_deprecated_operations(doc.clone(), ManagementApiVersion::V1, false);
+        let paths = v1_without_deprecated["paths"].as_object().unwrap();
+        assert!(!paths.contains_key("/management/v1/default-project"));
+
+        let v1 = without_v2_operations(doc.clone());
+        let paths = v1["paths"].as_object().unwrap();
+        assert_eq!(
+            paths.keys().collect::<Vec<_>>(),
+            vec!["/management/v1/default-project", "/management/v1/project"]
+        );
+
+        let v2 = v1_doc_into_v2(doc);
+        let paths = v2["paths"].as_object().unwrap();
+        assert_eq!(
+            paths.keys().collect::<Vec<_>>(),
+            vec![
+                "/management/v2/project",
+                "/management/v2/project/{project_id}/overview"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_catalog_api_doc_omits_unsupported_endpoints() {
+        let doc = catalog_api_doc();
+        let paths = doc["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/catalog/v1/{prefix}/namespaces"));
+        assert!(!paths.keys().any(|p| p.ends_with("/plan")));
+        assert!(!paths.keys().any(|p| p.ends_with("/tasks")));
+    }
+}
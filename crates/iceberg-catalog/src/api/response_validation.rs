@@ -0,0 +1,483 @@
+//! Validation of Iceberg REST responses against the OpenAPI specification.
+//!
+//! Clients such as `DuckDB` fail on responses that omit fields the specification
+//! marks as required, even if other clients accept them. With
+//! [`ResponseValidation::Warn`] every mismatch is logged as structured warning,
+//! with [`ResponseValidation::Strict`] the response is replaced by an internal
+//! server error so that integration tests fail.
+use crate::api::router::ICEBERG_OPENAPI_SPEC_YAML;
+use crate::api::{ErrorModel, IcebergErrorResponse};
+use crate::config::ResponseValidation;
+use crate::CONFIG;
+use axum::body::{Body, HttpBody as _};
+use axum::extract::{OriginalUri, Request};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use http::{header, Method, StatusCode};
+use serde_json::Value;
+
+/// Larger responses are passed through unvalidated with [`ResponseValidation::Warn`] and
+/// rejected with [`ResponseValidation::Strict`].
+const MAX_VALIDATED_BODY_SIZE: usize = 16 * 1024 * 1024;
+/// Guards against cyclic `$ref`s in the specification.
+const MAX_SCHEMA_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SchemaMismatch {
+    /// JSON pointer into the response body
+    pub(crate) pointer: String,
+    pub(crate) message: String,
+}
+
+impl std::fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{pointer}: {}", self.message)
+    }
+}
+
+pub(crate) async fn validate_response_fn(request: Request, next: Next) -> Response {
+    validate_response(CONFIG.response_validation, request, next).await
+}
+
+async fn validate_response(mode: ResponseValidation, request: Request, next: Next) -> Response {
+    if mode == ResponseValidation::Off {
+        return next.run(request).await;
+    }
+
+    let method = request.method().clone();
+    // Nested routers only see the path below their mount point.
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| request.uri().path(), |uri| uri.path())
+        .to_string();
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+    let status = response.status();
+    let Some(schema) = response_schema(&ICEBERG_OPENAPI_SPEC_YAML, &method, &path, status) else {
+        return response;
+    };
+
+    let (parts, body) = response.into_parts();
+    let exceeds_limit = body
+        .size_hint()
+        .upper()
+        .map_or(true, |size| size > MAX_VALIDATED_BODY_SIZE as u64);
+    if exceeds_limit && mode == ResponseValidation::Warn {
+        tracing::debug!(%method, %path, "Skipping validation of large response");
+        return Response::from_parts(parts, body);
+    }
+
+    let bytes = match axum::body::to_bytes(body, MAX_VALIDATED_BODY_SIZE).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!(%method, %path, "Failed to buffer response for validation: {e}");
+            return IcebergErrorResponse::from(ErrorModel::internal(
+                "Failed to buffer response",
+                "ResponseValidationError",
+                None,
+            ))
+            .into_response();
+        }
+    };
+
+    let mismatches = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(body) => validate(&ICEBERG_OPENAPI_SPEC_YAML, schema, &body),
+        Err(e) => vec![SchemaMismatch {
+            pointer: String::new(),
+            message: format!("Response is not valid JSON: {e}"),
+        }],
+    };
+
+    if mismatches.is_empty() {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+
+    for mismatch in &mismatches {
+        tracing::warn!(
+            %method,
+            %path,
+            status = status.as_u16(),
+            pointer = %mismatch.pointer,
+            message = %mismatch.message,
+            "Response does not conform to the Iceberg REST specification"
+        );
+    }
+
+    match mode {
+        ResponseValidation::Strict => IcebergErrorResponse::from(
+            ErrorModel::internal(
+                format!(
+                    "Response of {method} {path} does not conform to the Iceberg REST specification"
+                ),
+                "ResponseValidationError",
+                None,
+            )
+            .append_details(mismatches.iter().map(ToString::to_string)),
+        )
+        .into_response(),
+        ResponseValidation::Warn | ResponseValidation::Off => {
+            Response::from_parts(parts, Body::from(bytes))
+        }
+    }
+}
+
+/// Find the schema of the response of the operation matching `method` and `path`.
+pub(crate) fn response_schema<'a>(
+    spec: &'a Value,
+    method: &Method,
+    path: &str,
+    status: StatusCode,
+) -> Option<&'a Value> {
+    let (_, path_item) = spec
+        .get("paths")?
+        .as_object()?
+        .iter()
+        .find(|(template, _)| path_matches(template, path))?;
+    let responses = path_item
+        .get(method.as_str().to_lowercase())?
+        .get("responses")?;
+    let response = responses
+        .get(status.as_str())
+        .or_else(|| responses.get("default"))?;
+    resolve(spec, response)?
+        .get("content")?
+        .get("application/json")?
+        .get("schema")
+}
+
+fn path_matches(template: &str, path: &str) -> bool {
+    let template = template.trim_end_matches('/').split('/');
+    let path = path.trim_end_matches('/').split('/');
+    template.clone().count() == path.clone().count()
+        && template
+            .zip(path)
+            .all(|(t, p)| (t.starts_with('{') && t.ends_with('}') && !p.is_empty()) || t == p)
+}
+
+fn resolve<'a>(spec: &'a Value, schema: &'a Value) -> Option<&'a Value> {
+    match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => spec.pointer(reference.strip_prefix('#')?),
+        None => Some(schema),
+    }
+}
+
+/// Validate `value` against `schema`.
+///
+/// Supports the subset of JSON schema used by the Iceberg REST specification. Keywords
+/// that are not understood are ignored, so validation never rejects valid responses.
+pub(crate) fn validate(spec: &Value, schema: &Value, value: &Value) -> Vec<SchemaMismatch> {
+    let mut mismatches = vec![];
+    validate_inner(spec, schema, value, String::new(), 0, &mut mismatches);
+    mismatches
+}
+
+fn validate_inner(
+    spec: &Value,
+    schema: &Value,
+    value: &Value,
+    pointer: String,
+    depth: usize,
+    mismatches: &mut Vec<SchemaMismatch>,
+) {
+    if depth > MAX_SCHEMA_DEPTH {
+        return;
+    }
+    let Some(schema) = resolve(spec, schema) else {
+        mismatches.push(SchemaMismatch {
+            pointer,
+            message: format!("Unresolvable schema reference {}", schema["$ref"]),
+        });
+        return;
+    };
+
+    if value.is_null() && schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        return;
+    }
+
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        for sub_schema in all_of {
+            validate_inner(
+                spec,
+                sub_schema,
+                value,
+                pointer.clone(),
+                depth + 1,
+                mismatches,
+            );
+        }
+    }
+    for keyword in ["oneOf", "anyOf"] {
+        if let Some(options) = schema.get(keyword).and_then(Value::as_array) {
+            let matches_any = options.iter().any(|option| {
+                let mut option_mismatches = vec![];
+                validate_inner(
+                    spec,
+                    option,
+                    value,
+                    pointer.clone(),
+                    depth + 1,
+                    &mut option_mismatches,
+                );
+                option_mismatches.is_empty()
+            });
+            if !matches_any {
+                mismatches.push(SchemaMismatch {
+                    pointer: pointer.clone(),
+                    message: format!("Value does not match any schema of `{keyword}`"),
+                });
+            }
+        }
+    }
+
+    if let Some(typ) = schema.get("type").and_then(Value::as_str) {
+        let type_matches = match typ {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            _ => true,
+        };
+        if !type_matches {
+            mismatches.push(SchemaMismatch {
+                pointer,
+                message: format!("Expected {typ}, got {}", json_type(value)),
+            });
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            mismatches.push(SchemaMismatch {
+                pointer: pointer.clone(),
+                message: format!("{value} is not one of {}", Value::Array(allowed.clone())),
+            });
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            for required in schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+            {
+                if !object.contains_key(required) {
+                    mismatches.push(SchemaMismatch {
+                        pointer: pointer.clone(),
+                        message: format!("Missing required field `{required}`"),
+                    });
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (key, field) in object {
+                let field_pointer =
+                    format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"));
+                match (
+                    properties.and_then(|p| p.get(key)),
+                    schema.get("additionalProperties"),
+                ) {
+                    (Some(field_schema), _) => validate_inner(
+                        spec,
+                        field_schema,
+                        field,
+                        field_pointer,
+                        depth + 1,
+                        mismatches,
+                    ),
+                    (None, Some(Value::Bool(false))) => mismatches.push(SchemaMismatch {
+                        pointer: field_pointer,
+                        message: "Unexpected field".to_string(),
+                    }),
+                    (None, Some(additional @ Value::Object(_))) => validate_inner(
+                        spec,
+                        additional,
+                        field,
+                        field_pointer,
+                        depth + 1,
+                        mismatches,
+                    ),
+                    (None, _) => {}
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate_inner(
+                        spec,
+                        item_schema,
+                        item,
+                        format!("{pointer}/{i}"),
+                        depth + 1,
+                        mismatches,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use iceberg_ext::catalog::rest::CatalogConfig;
+
+    fn config_schema() -> &'static Value {
+        response_schema(
+            &ICEBERG_OPENAPI_SPEC_YAML,
+            &Method::GET,
+            "/catalog/v1/config",
+            StatusCode::OK,
+        )
+        .expect("Schema for GET /config should exist")
+    }
+
+    #[test]
+    fn test_empty_catalog_config_conforms() {
+        let body = serde_json::to_value(CatalogConfig::default()).unwrap();
+        assert_eq!(
+            validate(&ICEBERG_OPENAPI_SPEC_YAML, config_schema(), &body),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_missing_defaults_is_reported() {
+        let body = serde_json::json!({"overrides": {}, "endpoints": []});
+        let mismatches = validate(&ICEBERG_OPENAPI_SPEC_YAML, config_schema(), &body);
+        assert_eq!(
+            mismatches,
+            vec![SchemaMismatch {
+                pointer: String::new(),
+                message: "Missing required field `defaults`".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_nested_type_mismatch_is_reported() {
+        let body = serde_json::json!({"overrides": {"prefix": 1}, "defaults": {}});
+        let mismatches = validate(&ICEBERG_OPENAPI_SPEC_YAML, config_schema(), &body);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].pointer, "/overrides/prefix");
+    }
+
+    #[test]
+    fn test_path_templates_are_matched() {
+        let schema = response_schema(
+            &ICEBERG_OPENAPI_SPEC_YAML,
+            &Method::GET,
+            "/catalog/v1/my-prefix/namespaces/ns1%1Fns2/tables",
+            StatusCode::OK,
+        )
+        .unwrap();
+        let body = serde_json::json!({
+            "identifiers": [{"namespace": ["ns1", "ns2"], "name": "tbl"}]
+        });
+        assert_eq!(validate(&ICEBERG_OPENAPI_SPEC_YAML, schema, &body), vec![]);
+
+        assert!(response_schema(
+            &ICEBERG_OPENAPI_SPEC_YAML,
+            &Method::GET,
+            "/catalog/v1/my-prefix/unknown",
+            StatusCode::OK,
+        )
+        .is_none());
+    }
+
+    /// Serve `body` as response of `GET /catalog/v1/config`, mounted like the catalog router.
+    async fn get_config(mode: ResponseValidation, body: impl Into<bytes::Bytes>) -> Response {
+        use tower::ServiceExt;
+
+        let body = body.into();
+        let v1_routes = axum::Router::new()
+            .route(
+                "/config",
+                axum::routing::get(move || {
+                    let body = body.clone();
+                    async move { ([(header::CONTENT_TYPE, "application/json")], body) }
+                }),
+            )
+            .layer(axum::middleware::from_fn(move |request, next| {
+                validate_response(mode, request, next)
+            }));
+        axum::Router::new()
+            .nest("/catalog/v1", v1_routes)
+            .oneshot(
+                Request::builder()
+                    .uri("/catalog/v1/config")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_strict_rejects_nonconforming_response() {
+        let body = r#"{"overrides": {}}"#;
+        let response = get_config(ResponseValidation::Strict, body).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let body = r#"{"overrides": {}, "defaults": {}}"#;
+        let response = get_config(ResponseValidation::Strict, body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_warn_passes_nonconforming_response() {
+        let body = r#"{"overrides": {}}"#;
+        let response = get_config(ResponseValidation::Warn, body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&bytes[..], br#"{"overrides": {}}"#);
+    }
+
+    #[tokio::test]
+    async fn test_warn_skips_large_response() {
+        let body = vec![b' '; MAX_VALIDATED_BODY_SIZE + 1];
+        let response = get_config(ResponseValidation::Warn, body).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(bytes.len(), MAX_VALIDATED_BODY_SIZE + 1);
+
+        let body = vec![b' '; MAX_VALIDATED_BODY_SIZE + 1];
+        let response = get_config(ResponseValidation::Strict, body).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
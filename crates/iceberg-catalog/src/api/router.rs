@@ -1,30 +1,40 @@
 use crate::service::event_publisher::CloudEventsPublisher;
 use crate::tracing::{MakeRequestUuid7, RestMakeSpan};
 
-use crate::api::management::v1::{api_doc as v1_api_doc, ApiServer};
+use crate::api::management::v1::ApiServer;
+use crate::api::management::versioning::{self, ManagementApiVersion};
+use crate::api::openapi;
 use crate::api::{iceberg::v1::new_v1_full_router, shutdown_signal, ApiContext};
+use crate::config::{ResponseCompression, ResponseCompressionLevel};
 use crate::service::authn::IdpVerifier;
 use crate::service::authn::K8sVerifier;
 use crate::service::authn::VerifierChain;
 use crate::service::contract_verification::ContractVerifiers;
 use crate::service::health::ServiceHealthProvider;
 use crate::service::task_queue::TaskQueues;
-use crate::service::{authz::Authorizer, Catalog, SecretStore, State};
+use crate::service::{authz::Authorizer, Catalog, ProjectIdent, SecretStore, State};
+use axum::extract::connect_info::ConnectInfo;
 use axum::response::IntoResponse;
 use axum::{routing::get, Json, Router};
 use axum_extra::middleware::option_layer;
 use axum_prometheus::PrometheusMetricLayer;
-use http::{header, HeaderValue, Method};
-use tower::ServiceBuilder;
+use http::{header, HeaderName, HeaderValue, Method};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::service::TowerToHyperService;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tower::{ServiceBuilder, ServiceExt as _};
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
 use tower_http::cors::AllowOrigin;
 use tower_http::{
     catch_panic::CatchPanicLayer, compression::CompressionLayer,
     sensitive_headers::SetSensitiveHeadersLayer, timeout::TimeoutLayer, trace, trace::TraceLayer,
-    ServiceBuilderExt,
+    CompressionLevel, ServiceBuilderExt,
 };
 
 lazy_static::lazy_static! {
-    static ref ICEBERG_OPENAPI_SPEC_YAML: serde_json::Value = {
+    pub(crate) static ref ICEBERG_OPENAPI_SPEC_YAML: serde_json::Value = {
         let mut yaml_str = include_str!("../../../../openapi/rest-catalog-open-api.yaml").to_string();
         yaml_str = yaml_str.replace("  /v1/", "  /catalog/v1/");
         serde_yml::from_str(&yaml_str).expect("Failed to parse Iceberg API model V1 as JSON")
@@ -40,6 +50,8 @@ pub struct RouterArgs<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
     pub table_change_checkers: ContractVerifiers,
     pub token_verifier: Option<IdpVerifier>,
     pub k8s_token_verifier: Option<K8sVerifier>,
+    /// Verifiers of identity providers that only authenticate requests targeting their project.
+    pub project_token_verifiers: HashMap<ProjectIdent, IdpVerifier>,
     pub service_health_provider: ServiceHealthProvider,
     pub cors_origins: Option<&'static [HeaderValue]>,
     pub metrics_layer: Option<PrometheusMetricLayer<'static>>,
@@ -56,6 +68,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> std::fmt::Debug for Rout
             .field("table_change_checkers", &self.table_change_checkers)
             .field("token_verifier", &self.token_verifier)
             .field("k8s_token_verifier", &self.k8s_token_verifier)
+            .field("project_token_verifiers", &self.project_token_verifiers)
             .field("svhp", &self.service_health_provider)
             .field("cors_origins", &self.cors_origins)
             .field(
@@ -80,14 +93,33 @@ pub fn new_full_router<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         table_change_checkers,
         token_verifier,
         k8s_token_verifier,
+        project_token_verifiers,
         service_health_provider,
         cors_origins,
         metrics_layer,
     }: RouterArgs<C, A, S>,
 ) -> anyhow::Result<Router> {
-    let v1_routes = new_v1_full_router::<crate::catalog::CatalogServer<C, A, S>, State<A, C, S>>();
+    // Route layers see the decoded path parameters of the matched route.
+    let project_scope_layer = axum::middleware::from_fn_with_state(
+        catalog_state.clone(),
+        crate::service::authn::project_scope_middleware_fn::<C>,
+    );
+    let network_policy_layer = axum::middleware::from_fn_with_state(
+        catalog_state.clone(),
+        crate::service::network_policy::network_policy_middleware_fn::<C>,
+    );
+    let v1_routes = new_v1_full_router::<crate::catalog::CatalogServer<C, A, S>, State<A, C, S>>()
+        .route_layer(network_policy_layer.clone())
+        .route_layer(project_scope_layer.clone());
 
-    let management_routes = Router::new().merge(ApiServer::new_v1_router(&authorizer));
+    let management_routes = Router::new()
+        .merge(ApiServer::new_v1_router(&authorizer))
+        .route_layer(network_policy_layer.clone())
+        .route_layer(project_scope_layer.clone());
+    let management_v2_routes = Router::new()
+        .merge(ApiServer::new_v2_router(&authorizer))
+        .route_layer(network_policy_layer)
+        .route_layer(project_scope_layer);
     let maybe_cors_layer = option_layer(cors_origins.map(|origins| {
         let allowed_origin = if origins
             .iter()
@@ -104,6 +136,13 @@ pub fn new_full_router<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
                 header::CONTENT_TYPE,
                 header::ACCEPT,
                 header::USER_AGENT,
+                HeaderName::from_static("x-iceberg-client"),
+                HeaderName::from_static(crate::service::authn::PROJECT_ID_HEADER),
+            ])
+            .expose_headers(vec![
+                versioning::DEPRECATION_HEADER,
+                versioning::SUNSET_HEADER,
+                header::LINK,
             ])
             .allow_methods(vec![
                 Method::GET,
@@ -114,17 +153,40 @@ pub fn new_full_router<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
                 Method::OPTIONS,
             ])
     }));
-    let maybe_auth_layer = match (token_verifier, k8s_token_verifier) {
-        (None, None) => option_layer(None),
-        (idp_verifier, k8s_verifier) => option_layer(Some(axum::middleware::from_fn_with_state(
-            VerifierChain::try_new(idp_verifier, k8s_verifier)?,
+    let maybe_auth_layer = if token_verifier.is_none()
+        && k8s_token_verifier.is_none()
+        && project_token_verifiers.is_empty()
+    {
+        option_layer(None)
+    } else {
+        option_layer(Some(axum::middleware::from_fn_with_state(
+            VerifierChain::try_new(token_verifier, k8s_token_verifier, project_token_verifiers)?,
             crate::service::authn::auth_middleware_fn,
-        ))),
+        )))
     };
 
+    // Built once - the documents only depend on the configuration and compiled features
+    let management_api_doc = Arc::new(openapi::management_api_doc::<A>());
+    let management_v2_api_doc = Arc::new(openapi::management_v2_api_doc::<A>());
+    let catalog_api_doc = Arc::new(openapi::catalog_api_doc());
+
     let router = Router::new()
-        .nest("/catalog/v1", v1_routes)
-        .nest("/management/v1", management_routes)
+        .nest(
+            "/catalog/v1",
+            v1_routes.layer(axum::middleware::from_fn(
+                crate::api::response_validation::validate_response_fn,
+            )),
+        )
+        .nest(ManagementApiVersion::V1.prefix(), management_routes)
+        .nest(ManagementApiVersion::V2.prefix(), management_v2_routes)
+        .layer(axum::middleware::from_fn_with_state(
+            catalog_state.clone(),
+            crate::service::authn::user_activity_middleware_fn::<C>,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            (authorizer.clone(), catalog_state.clone(), publisher.clone()),
+            crate::service::authn::impersonation_middleware_fn::<C, A>,
+        ))
         .layer(maybe_auth_layer)
         .route(
             "/health",
@@ -135,12 +197,35 @@ pub fn new_full_router<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
         )
         .merge(
             utoipa_swagger_ui::SwaggerUi::new("/swagger-ui")
-                .url("/api-docs/management/v1/openapi.json", v1_api_doc::<A>())
+                .external_url_unchecked(
+                    "/api-docs/management/v1/openapi.json",
+                    management_api_doc.as_ref().clone(),
+                )
+                .external_url_unchecked(
+                    "/api-docs/management/v2/openapi.json",
+                    management_v2_api_doc.as_ref().clone(),
+                )
                 .external_url_unchecked(
                     "/api-docs/catalog/v1/openapi.json",
-                    ICEBERG_OPENAPI_SPEC_YAML.clone(),
+                    catalog_api_doc.as_ref().clone(),
                 ),
         )
+        .route(
+            "/api-docs/management/v1/openapi.yaml",
+            get(|| async move { openapi::yaml_response(&management_api_doc) }),
+        )
+        .route(
+            "/api-docs/management/v2/openapi.yaml",
+            get(|| async move { openapi::yaml_response(&management_v2_api_doc) }),
+        )
+        .route(
+            "/api-docs/catalog/v1/openapi.yaml",
+            get(|| async move { openapi::yaml_response(&catalog_api_doc) }),
+        )
+        .layer(axum::extract::DefaultBodyLimit::max(
+            crate::CONFIG.max_request_body_size_bytes,
+        ))
+        .layer(axum::middleware::from_fn(payload_too_large_fn))
         .layer(axum::middleware::from_fn(
             crate::request_metadata::create_request_metadata_with_trace_id_fn,
         ))
@@ -150,7 +235,7 @@ pub fn new_full_router<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
                 .layer(SetSensitiveHeadersLayer::new([
                     axum::http::header::AUTHORIZATION,
                 ]))
-                .layer(CompressionLayer::new())
+                .layer(option_layer(compression_layer()))
                 .layer(
                     TraceLayer::new_for_http()
                         .on_failure(())
@@ -180,15 +265,119 @@ pub fn new_full_router<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
     })
 }
 
-/// Serve the given router on the given listener
+/// Compression of responses as configured. Table metadata compresses well,
+/// so this mostly reduces egress of `loadTable` responses.
+fn compression_layer() -> Option<CompressionLayer<impl Predicate>> {
+    let algorithms = &crate::CONFIG.response_compression;
+    if algorithms.is_empty() {
+        return None;
+    }
+    let quality = match crate::CONFIG.response_compression_level {
+        ResponseCompressionLevel::Fastest => CompressionLevel::Fastest,
+        ResponseCompressionLevel::Default => CompressionLevel::Default,
+        ResponseCompressionLevel::Best => CompressionLevel::Best,
+    };
+    let predicate = SizeAbove::new(crate::CONFIG.response_compression_min_size_bytes)
+        .and(NotForContentType::GRPC)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE);
+    Some(
+        CompressionLayer::new()
+            .zstd(algorithms.contains(&ResponseCompression::Zstd))
+            .gzip(algorithms.contains(&ResponseCompression::Gzip))
+            .br(algorithms.contains(&ResponseCompression::Br))
+            .deflate(algorithms.contains(&ResponseCompression::Deflate))
+            .quality(quality)
+            .compress_when(predicate),
+    )
+}
+
+/// Bodies exceeding the limit are rejected by axum's extractors with a plain-text response.
+/// Replace it with an error model so that clients can show a meaningful message.
+async fn payload_too_large_fn(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    let response = next.run(request).await;
+    if response.status() != http::StatusCode::PAYLOAD_TOO_LARGE
+        || response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .is_some_and(|v| v.as_bytes().starts_with(b"application/json"))
+    {
+        return response;
+    }
+    crate::api::IcebergErrorResponse::from(crate::api::ErrorModel::new(
+        format!(
+            "Request body exceeds the maximum size of {} bytes",
+            crate::CONFIG.max_request_body_size_bytes
+        ),
+        "RequestBodyTooLarge",
+        http::StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+        None,
+    ))
+    .into_response()
+}
+
+/// Serve the given router on the given listener.
+///
+/// Connections are served by hyper directly instead of `axum::serve`,
+/// so that HTTP/2 flow control can be tuned.
 ///
 /// # Errors
 /// Fails if the webserver panics
 pub async fn serve(listener: tokio::net::TcpListener, router: Router) -> anyhow::Result<()> {
-    axum::serve(listener, router)
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .map_err(|e| anyhow::anyhow!(e).context("error running HTTP server"))
+    let mut builder = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new());
+    builder
+        .http2()
+        .initial_stream_window_size(crate::CONFIG.http2_initial_stream_window_size_bytes)
+        .initial_connection_window_size(crate::CONFIG.http2_initial_connection_window_size_bytes)
+        .adaptive_window(crate::CONFIG.http2_adaptive_window);
+    // Passing `None` would lift hyper's default limit instead of keeping it.
+    if let Some(max_concurrent_streams) = crate::CONFIG.http2_max_concurrent_streams {
+        builder
+            .http2()
+            .max_concurrent_streams(max_concurrent_streams);
+    }
+
+    let graceful = GracefulShutdown::new();
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        let (stream, remote_addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // Errors such as running out of file descriptors are usually transient.
+                    tracing::warn!("Failed to accept connection: {e}");
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    continue;
+                }
+            },
+            () = &mut shutdown => break,
+        };
+        // Makes the peer address available to handlers, as `axum::serve` does.
+        let service = TowerToHyperService::new(router.clone().map_request(
+            move |mut request: http::Request<hyper::body::Incoming>| {
+                request.extensions_mut().insert(ConnectInfo(remote_addr));
+                request
+            },
+        ));
+        let connection = builder
+            .serve_connection_with_upgrades(TokioIo::new(stream), service)
+            .into_owned();
+        let connection = graceful.watch(connection);
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::debug!("Error serving connection: {e}");
+            }
+        });
+    }
+
+    tracing::info!("Shutting down HTTP server, waiting for open connections to finish");
+    graceful.shutdown().await;
+    Ok(())
 }
 
 #[cfg(test)]
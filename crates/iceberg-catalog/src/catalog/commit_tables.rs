@@ -8,8 +8,27 @@ use iceberg_ext::{
 
 use crate::service::{ErrorModel, Result};
 
+/// Check `requirements` against the current metadata of a table without applying any updates.
+///
+/// Returns a description of every failed requirement, so that callers can report all
+/// failures of a commit at once.
+pub(super) fn failed_requirements(
+    metadata: &TableMetadata,
+    metadata_location: Option<&Location>,
+    requirements: &[TableRequirement],
+) -> Vec<String> {
+    requirements
+        .iter()
+        .filter_map(|r| {
+            r.check(metadata_location.map(|_| metadata))
+                .err()
+                .map(|e| format!("{r:?}: {}", e.message()))
+        })
+        .collect()
+}
+
 /// Apply the commits to table metadata.
-pub(super) fn apply_commit(
+pub(crate) fn apply_commit(
     metadata: TableMetadata,
     metadata_location: Option<&Location>,
     requirements: &[TableRequirement],
@@ -20,13 +20,42 @@ impl From<UnsupportedCompressionCodec> for IcebergErrorResponse {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+/// Leading bytes of a gzip member.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// Leading bytes of a zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Codecs metadata files are written with. The Iceberg spec only defines `none` and
+/// `gzip` for `write.metadata.compression-codec`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum CompressionCodec {
     None,
     #[default]
     Gzip,
 }
 
+/// Decompress a file with the codec detected from its leading bytes.
+/// Engines do not agree on file extensions for compressed metadata, so the
+/// content is more reliable than the name. Unknown content is treated as uncompressed.
+/// Zstd is only read, to load metadata written by other engines.
+pub async fn decompress_detected(content: Vec<u8>) -> Result<Vec<u8>, IoError> {
+    if content.starts_with(&GZIP_MAGIC) {
+        CompressionCodec::Gzip.decompress(content).await
+    } else if content.starts_with(&ZSTD_MAGIC) {
+        match tokio::task::spawn_blocking(move || {
+            zstd::stream::decode_all(content.as_slice())
+                .map_err(|e| IoError::FileDecompression(Box::new(e)))
+        })
+        .await
+        {
+            Ok(result) => result,
+            Err(e) => Err(IoError::FileDecompression(Box::new(e))),
+        }
+    } else {
+        Ok(content)
+    }
+}
+
 impl CompressionCodec {
     pub async fn compress(self, payload: Vec<u8>) -> Result<Vec<u8>, IoError> {
         match self {
@@ -58,10 +87,11 @@ impl CompressionCodec {
             CompressionCodec::Gzip => {
                 match tokio::task::spawn_blocking(move || {
                     let mut decompressed_metadata = Vec::new();
-                    let mut decoder = flate2::read::GzDecoder::new(payload.as_slice());
+                    // Some writers concatenate multiple gzip members.
+                    let mut decoder = flate2::read::MultiGzDecoder::new(payload.as_slice());
                     decoder
                         .read_to_end(&mut decompressed_metadata)
-                        .map_err(|e| IoError::FileCompression(Box::new(e)))?;
+                        .map_err(|e| IoError::FileDecompression(Box::new(e)))?;
 
                     Ok(decompressed_metadata)
                 })
@@ -2,16 +2,73 @@ use crate::api::iceberg::v1::config::GetConfigQueryParams;
 use crate::api::iceberg::v1::{
     ApiContext, CatalogConfig, ErrorModel, PageToken, PaginationQuery, Result,
 };
-use crate::api::management::v1::user::UserLastUpdatedWith;
+use crate::api::management::v1::role::require_project_id_or_else;
+use crate::api::management::v1::user::{UserLastUpdatedWith, UserListFilter};
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{CatalogProjectAction, CatalogWarehouseAction};
+use crate::service::housekeeping::evict_expired;
+use crate::service::network_policy::require_allowed_client_network;
 use crate::service::{authz::Authorizer, Catalog, ProjectIdent, State};
-use crate::service::{AuthDetails, SecretStore, Transaction};
-use crate::{CONFIG, DEFAULT_PROJECT_ID};
+use crate::service::{Actor, AuthDetails, PrincipalDefaultWarehouse, SecretStore, Transaction};
+use crate::{WarehouseIdent, CONFIG};
 use std::str::FromStr;
+use std::sync::LazyLock;
+use std::time::Duration;
 
 use super::CatalogServer;
 
+/// Key in the `defaults` of the `CatalogConfig` that reports how the warehouse was resolved.
+pub(crate) const WAREHOUSE_RESOLUTION_SOURCE_KEY: &str = "lakekeeper.warehouse-resolution-source";
+
+/// Catalog config of a warehouse as derived from its storage profile, before any per-request
+/// properties are added. Entries are invalidated when the storage profile or status of the
+/// warehouse changes on this instance; other instances pick up changes once the TTL expires.
+static WAREHOUSE_CONFIG_CACHE: LazyLock<moka::sync::Cache<WarehouseIdent, CatalogConfig>> =
+    LazyLock::new(|| {
+        moka::sync::Cache::builder()
+            .max_capacity(10_000)
+            .time_to_live(Duration::from_secs(CONFIG.config_cache_ttl_seconds))
+            .build()
+    });
+
+pub(crate) fn evict_expired_warehouse_configs() -> u64 {
+    evict_expired(&WAREHOUSE_CONFIG_CACHE)
+}
+
+/// Drop the cached config of a warehouse. Must be called after changes to the warehouse are committed.
+pub(crate) fn invalidate_warehouse_config(warehouse_id: WarehouseIdent) {
+    WAREHOUSE_CONFIG_CACHE.invalidate(&warehouse_id);
+}
+
+async fn cached_config_for_warehouse<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    catalog_state: C::State,
+) -> Result<CatalogConfig> {
+    if CONFIG.config_cache_ttl_seconds == 0 {
+        return C::require_config_for_warehouse(warehouse_id, catalog_state).await;
+    }
+    if let Some(config) = WAREHOUSE_CONFIG_CACHE.get(&warehouse_id) {
+        return Ok(config);
+    }
+    let config = C::require_config_for_warehouse(warehouse_id, catalog_state).await?;
+    WAREHOUSE_CONFIG_CACHE.insert(warehouse_id, config.clone());
+    Ok(config)
+}
+
+/// Source from which the warehouse of a `GET /config` request was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum WarehouseResolutionSource {
+    /// The `warehouse` query parameter of the request.
+    RequestParameter,
+    /// The warehouse encoded in the token of the principal.
+    Token,
+    /// The default warehouse of the role assumed by the principal.
+    RoleDefault,
+    /// The default warehouse of the user.
+    UserDefault,
+}
+
 #[async_trait::async_trait]
 impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
     crate::api::iceberg::v1::config::Service<State<A, C, S>> for CatalogServer<C, A, S>
@@ -27,18 +84,35 @@ impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
 
         maybe_register_user::<C>(&request_metadata, api_context.v1_state.catalog.clone()).await?;
 
-        // Arg takes precedence over auth
-        let warehouse_id = if let Some(query_warehouse) = query.warehouse {
+        let catalog_state = api_context.v1_state.catalog;
+
+        // Arg takes precedence over auth, auth over principal defaults
+        let (warehouse_id, resolution_source) = if let Some(query_warehouse) = query.warehouse {
             let (project_from_arg, warehouse_from_arg) = parse_warehouse_arg(&query_warehouse);
-            let project_id = project_from_arg
-                .or(*project_id_from_auth)
-                .or(*DEFAULT_PROJECT_ID)
-                .ok_or_else(|| {
+            // Warehouse names are unique per project. Without a project in the argument or
+            // the token, the name is looked up in the project of the default warehouse of the
+            // principal, so that principals of other projects do not fall back to the
+            // configured default project. The default project is only used without a
+            // principal default.
+            let specified_project_id = match project_from_arg.or(*project_id_from_auth) {
+                Some(project_id) => Some(project_id),
+                None => principal_default_warehouse::<C>(
+                    request_metadata.actor(),
+                    catalog_state.clone(),
+                )
+                .await?
+                .map(|(default, _)| default.project_id),
+            };
+            let project_id = require_project_id_or_else(
+                specified_project_id,
+                &request_metadata,
+                || {
                     // ToDo Christian: Split Project into separate endpoint, Use name
                     ErrorModel::bad_request(
-                        "No project provided. Please provide warehouse as: <project-name>/<warehouse-name>",
+                        "No project provided and the default project is disabled. Please provide warehouse as: <project-id>/<warehouse-name>",
                         "GetConfigNoProjectProvided", None)
-                })?;
+                },
+            )?;
             authorizer
                 .require_project_action(
                     &request_metadata,
@@ -46,16 +120,22 @@ impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
                     &CatalogProjectAction::CanListWarehouses,
                 )
                 .await?;
-            C::require_warehouse_by_name(
+            let warehouse_id = C::require_warehouse_by_name(
                 &warehouse_from_arg,
                 project_id,
-                api_context.v1_state.catalog.clone(),
+                catalog_state.clone(),
             )
-            .await?
+            .await?;
+            (warehouse_id, WarehouseResolutionSource::RequestParameter)
+        } else if let Some(warehouse_id) = *warehouse_id_from_auth {
+            (warehouse_id, WarehouseResolutionSource::Token)
         } else {
-            warehouse_id_from_auth.ok_or_else(|| {
-                ErrorModel::bad_request("No warehouse specified. Please specify the 'warehouse' parameter in the GET /config request.".to_string(), "GetConfigNoWarehouseProvided", None)
-            })?
+            principal_default_warehouse::<C>(request_metadata.actor(), catalog_state.clone())
+                .await?
+                .map(|(default, source)| (default.warehouse_id, source))
+                .ok_or_else(|| {
+                    ErrorModel::bad_request("No warehouse specified. Please specify the 'warehouse' parameter in the GET /config request or configure a default warehouse for the principal.".to_string(), "GetConfigNoWarehouseProvided", None)
+                })?
         };
 
         authorizer
@@ -65,10 +145,10 @@ impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
                 &CatalogWarehouseAction::CanGetConfig,
             )
             .await?;
+        require_allowed_client_network::<C>(&request_metadata, warehouse_id, catalog_state.clone())
+            .await?;
 
-        // Get config from DB and new token from AuthHandler simultaneously
-        let mut config =
-            C::require_config_for_warehouse(warehouse_id, api_context.v1_state.catalog).await?;
+        let mut config = cached_config_for_warehouse::<C>(warehouse_id, catalog_state).await?;
 
         config
             .overrides
@@ -78,10 +158,46 @@ impl<A: Authorizer + Clone, C: Catalog, S: SecretStore>
             .overrides
             .insert("uri".to_string(), CONFIG.base_uri_catalog().to_string());
 
+        config.defaults.insert(
+            WAREHOUSE_RESOLUTION_SOURCE_KEY.to_string(),
+            resolution_source.to_string(),
+        );
+
+        CONFIG
+            .engine_profiles
+            .apply(request_metadata.engine(), &mut config.defaults);
+
         Ok(config)
     }
 }
 
+/// Default warehouse configured for the actor.
+/// The default of an assumed role takes precedence over the default of the user.
+async fn principal_default_warehouse<C: Catalog>(
+    actor: &Actor,
+    catalog_state: C::State,
+) -> Result<Option<(PrincipalDefaultWarehouse, WarehouseResolutionSource)>> {
+    let user_id = match actor {
+        Actor::Anonymous => return Ok(None),
+        Actor::Principal(user_id) => user_id,
+        Actor::Role {
+            principal,
+            assumed_role,
+        } => {
+            if let Some(default) =
+                C::get_role_default_warehouse(*assumed_role, catalog_state.clone()).await?
+            {
+                return Ok(Some((default, WarehouseResolutionSource::RoleDefault)));
+            }
+            principal
+        }
+    };
+
+    Ok(C::get_user_default_warehouse(user_id, catalog_state)
+        .await?
+        .map(|default| (default, WarehouseResolutionSource::UserDefault)))
+}
+
 fn parse_warehouse_arg(arg: &str) -> (Option<ProjectIdent>, String) {
     // structure of the argument is <(optional uuid project_id)>/<warehouse_name>
     // Warehouse names cannot include /
@@ -126,6 +242,7 @@ async fn maybe_register_user<D: Catalog>(
     let user = D::list_user(
         Some(vec![principal.user_id().clone()]),
         None,
+        UserListFilter::default(),
         PaginationQuery {
             page_token: PageToken::Empty,
             page_size: Some(1),
@@ -152,3 +269,113 @@ async fn maybe_register_user<D: Catalog>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::api::iceberg::v1::config::Service as _;
+    use crate::api::management::v1::project::{CreateProjectRequest, Service as _};
+    use crate::api::management::v1::user::{Service as _, SetDefaultWarehouseRequest};
+    use crate::api::management::v1::warehouse::{
+        CreateWarehouseRequest, Service as _, TabularDeleteProfile, WarehouseLifecycle,
+    };
+    use crate::api::management::v1::ApiServer;
+    use crate::catalog::test::{setup, test_io_profile};
+    use crate::service::authz::AllowAllAuthorizer;
+    use crate::service::UserId;
+
+    #[sqlx::test]
+    async fn test_warehouse_name_resolves_in_project_of_principal_default(pool: sqlx::PgPool) {
+        let user_id = UserId::OIDC("test-user-id".to_string());
+        let metadata = || RequestMetadata::random_human(user_id.clone());
+        let (ctx, warehouse) = setup(
+            pool,
+            test_io_profile(),
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Hard {},
+            Some(user_id.clone()),
+        )
+        .await;
+        let warehouse = ApiServer::get_warehouse(warehouse.warehouse_id, ctx.clone(), metadata())
+            .await
+            .unwrap();
+        let project_id = ApiServer::create_project(
+            CreateProjectRequest {
+                project_name: "other-project".to_string(),
+                project_id: None,
+            },
+            ctx.clone(),
+            metadata(),
+        )
+        .await
+        .unwrap()
+        .project_id;
+        // Same name as the warehouse in the default project.
+        let other_warehouse_id = ApiServer::create_warehouse(
+            CreateWarehouseRequest {
+                warehouse_name: warehouse.name.clone(),
+                project_id: Some(project_id),
+                storage_profile: test_io_profile(),
+                storage_credential: None,
+                delete_profile: TabularDeleteProfile::Hard {},
+                lifecycle: WarehouseLifecycle::default(),
+            },
+            ctx.clone(),
+            metadata(),
+        )
+        .await
+        .unwrap()
+        .warehouse_id;
+
+        let resolved_prefix = |warehouse_arg: String| {
+            let ctx = ctx.clone();
+            let metadata = metadata();
+            async move {
+                let mut config = CatalogServer::get_config(
+                    GetConfigQueryParams {
+                        warehouse: Some(warehouse_arg),
+                    },
+                    ctx,
+                    metadata,
+                )
+                .await
+                .unwrap();
+                assert_eq!(
+                    config.defaults[WAREHOUSE_RESOLUTION_SOURCE_KEY],
+                    "request-parameter"
+                );
+                config.overrides.remove("prefix").unwrap()
+            }
+        };
+        let warehouse_prefix = CONFIG.warehouse_prefix(warehouse.id.into());
+        let other_warehouse_prefix = CONFIG.warehouse_prefix(other_warehouse_id);
+
+        // Without a principal default, the name is looked up in the default project.
+        assert_eq!(
+            resolved_prefix(warehouse.name.clone()).await,
+            warehouse_prefix
+        );
+
+        ApiServer::set_user_default_warehouse(
+            ctx.clone(),
+            metadata(),
+            user_id.clone(),
+            SetDefaultWarehouseRequest {
+                warehouse_id: Some(*other_warehouse_id),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            resolved_prefix(warehouse.name.clone()).await,
+            other_warehouse_prefix
+        );
+
+        // A project in the argument takes precedence over the principal default.
+        assert_eq!(
+            resolved_prefix(format!("{}/{}", warehouse.project_id, warehouse.name)).await,
+            warehouse_prefix
+        );
+    }
+}
@@ -1,15 +1,19 @@
-use super::compression_codec::CompressionCodec;
+use super::compression_codec::{decompress_detected, CompressionCodec};
 use crate::api::{ErrorModel, Result};
 use crate::retry::retry_fn;
 use crate::service::storage::path_utils;
+use bytes::Bytes;
 use futures::stream::BoxStream;
 use futures::StreamExt;
-use iceberg::io::FileIO;
+use iceberg::io::{FileIO, FileRead, FileWrite, OutputFile};
 use iceberg::spec::TableMetadata;
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
 use iceberg_ext::configs::Location;
 use serde::Serialize;
 
+/// Size of the chunks in which [`copy_file`] streams files.
+const COPY_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
 pub(crate) async fn write_metadata_file(
     metadata_location: &Location,
     metadata: impl Serialize,
@@ -28,19 +32,111 @@ pub(crate) async fn write_metadata_file(
         .new_output(metadata_location)
         .map_err(IoError::FileCreation)?;
 
+    let metadata_bytes = serialize_metadata(metadata, compression_codec).await?;
+
+    retry_fn(|| async {
+        metadata_file
+            .write(metadata_bytes.clone())
+            .await
+            .map_err(IoError::FileWriterCreation)
+    })
+    .await
+}
+
+/// Serialize and compress metadata the way [`write_metadata_file`] stores it.
+pub(crate) async fn serialize_metadata(
+    metadata: impl Serialize,
+    compression_codec: CompressionCodec,
+) -> Result<Bytes, IoError> {
     let buf = serde_json::to_vec(&metadata).map_err(IoError::Serialization)?;
+    Ok(Bytes::from(compression_codec.compress(buf).await?))
+}
 
-    let metadata_bytes = compression_codec.compress(buf).await?;
+/// Output file at `location`, for writers that write the file themselves.
+pub(crate) fn new_output(file_io: &FileIO, location: &Location) -> Result<OutputFile, IoError> {
+    let location = location.as_str();
+    let location = if location.starts_with("abfs") {
+        path_utils::reduce_scheme_string(location, false)
+    } else {
+        location.to_string()
+    };
+
+    file_io.new_output(location).map_err(IoError::FileCreation)
+}
 
+pub(crate) async fn write_file(
+    file_io: &FileIO,
+    location: &Location,
+    content: Vec<u8>,
+) -> Result<(), IoError> {
+    let file = new_output(file_io, location)?;
+
+    // Cloning `Bytes` for a retry does not copy the content.
+    let content = Bytes::from(content);
     retry_fn(|| async {
-        metadata_file
-            .write(metadata_bytes.clone().into())
+        file.write(content.clone())
             .await
             .map_err(IoError::FileWriterCreation)
     })
     .await
 }
 
+/// Copy a file byte by byte. Unlike [`read_file`], compressed metadata files are not decompressed.
+///
+/// The file is streamed in chunks of [`COPY_CHUNK_SIZE`], so at most one chunk per copy is held
+/// in memory. Failed copies are retried from the start, overwriting partially written targets.
+pub(crate) async fn copy_file(
+    file_io: &FileIO,
+    source: &Location,
+    target: &Location,
+) -> Result<(), IoError> {
+    let source = path_utils::reduce_scheme_string(source.as_str(), false);
+    let target = target.as_str();
+    let target = if target.starts_with("abfs") {
+        path_utils::reduce_scheme_string(target, false)
+    } else {
+        target.to_string()
+    };
+
+    retry_fn(|| async {
+        let input = file_io
+            .new_input(source.clone())
+            .map_err(IoError::FileInput)?;
+        let size = input
+            .metadata()
+            .await
+            .map_err(|e| IoError::FileRead(Box::new(e)))?
+            .size;
+        let reader = input
+            .reader()
+            .await
+            .map_err(|e| IoError::FileRead(Box::new(e)))?;
+        let mut writer = file_io
+            .new_output(target.clone())
+            .map_err(IoError::FileCreation)?
+            .writer()
+            .await
+            .map_err(IoError::FileWriterCreation)?;
+
+        let mut offset = 0;
+        while offset < size {
+            let end = size.min(offset + COPY_CHUNK_SIZE);
+            let chunk = reader
+                .read(offset..end)
+                .await
+                .map_err(|e| IoError::FileRead(Box::new(e)))?;
+            FileWrite::write(&mut *writer, chunk)
+                .await
+                .map_err(|e| IoError::FileWrite(Box::new(e)))?;
+            offset = end;
+        }
+        FileWrite::close(&mut *writer)
+            .await
+            .map_err(|e| IoError::FileWrite(Box::new(e)))
+    })
+    .await
+}
+
 pub(crate) async fn delete_file(file_io: &FileIO, location: &Location) -> Result<(), IoError> {
     let location = location.as_str();
     let location = if location.starts_with("abfs") {
@@ -59,6 +155,24 @@ pub(crate) async fn delete_file(file_io: &FileIO, location: &Location) -> Result
     .await
 }
 
+pub(crate) async fn file_exists(file_io: &FileIO, location: &Location) -> Result<bool, IoError> {
+    let location = location.as_str();
+    let location = if location.starts_with("abfs") {
+        path_utils::reduce_scheme_string(location, false)
+    } else {
+        location.to_string()
+    };
+
+    retry_fn(|| async {
+        file_io
+            .exists(location.clone())
+            .await
+            .map_err(IoError::FileInput)
+    })
+    .await
+}
+
+/// Read a file. Gzip and zstd compressed files are decompressed, independent of their extension.
 pub(crate) async fn read_file(file_io: &FileIO, file: &Location) -> Result<Vec<u8>, IoError> {
     let file = file.as_str();
     let file = if file.starts_with("abfs") {
@@ -80,13 +194,9 @@ pub(crate) async fn read_file(file_io: &FileIO, file: &Location) -> Result<Vec<u
     })
     .await?;
 
-    if file.as_str().ends_with(".gz.metadata.json") {
-        let codec = CompressionCodec::Gzip;
-        let content = codec.decompress(content).await?;
-        Ok(content)
-    } else {
-        Ok(content)
-    }
+    // Decide by content rather than extension: engines write compressed metadata
+    // without `.gz`, and `.zst` files are not covered by the Iceberg naming convention.
+    decompress_detected(content).await
 }
 
 pub(crate) async fn read_metadata_file(
@@ -184,6 +294,14 @@ pub enum IoError {
     FileRemoveAll(#[source] iceberg::Error),
     #[error("Failed to list files in location. Please check the storage credentials.")]
     List(#[source] iceberg::Error),
+    #[error("Storage returned a file with an invalid location.")]
+    InvalidListedLocation(#[source] Box<dyn std::error::Error + Sync + Send + 'static>),
+    #[error("Failed to tag object. Please check the storage credentials.")]
+    ObjectTagging(#[source] Box<dyn std::error::Error + Sync + Send + 'static>),
+    #[error("Failed to read or change the storage class of files. Please check the storage credentials.")]
+    StorageClass(#[source] Box<dyn std::error::Error + Sync + Send + 'static>),
+    #[error("Failed to encrypt file with the configured key. Please check the storage credentials and the permissions on the key.")]
+    EncryptionKey(#[source] Box<dyn std::error::Error + Sync + Send + 'static>),
 }
 
 impl IoError {
@@ -208,8 +326,16 @@ impl From<IoError> for IcebergErrorResponse {
             | IoError::FileWriterCreation(_)
             | IoError::FileCreation(_)
             | IoError::FileDecompression(_)
-            | IoError::List(_) => ErrorModel::failed_dependency(message, typ, Some(boxed)).into(),
-            IoError::FileCompression(_) | IoError::Write(_) | IoError::Serialization(_) => {
+            | IoError::List(_)
+            | IoError::ObjectTagging(_)
+            | IoError::StorageClass(_)
+            | IoError::EncryptionKey(_) => {
+                ErrorModel::failed_dependency(message, typ, Some(boxed)).into()
+            }
+            IoError::FileCompression(_)
+            | IoError::Write(_)
+            | IoError::Serialization(_)
+            | IoError::InvalidListedLocation(_) => {
                 ErrorModel::internal(message, typ, Some(boxed)).into()
             }
             IoError::TableMetadataDeserialization(e) => {
@@ -220,4 +346,71 @@ impl From<IoError> for IcebergErrorResponse {
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_read_file_detects_compression() {
+        let file_io = iceberg::io::FileIOBuilder::new("file").build().unwrap();
+        let dir = std::env::temp_dir().join(format!("lakekeeper-io-{}", uuid::Uuid::now_v7()));
+        let dir = Location::from_str(&format!("file://{}", dir.display())).unwrap();
+        let metadata = serde_json::json!({"format-version": 2});
+
+        // The extension deliberately does not match the codec.
+        for (name, codec) in [
+            ("plain.gz.metadata.json", CompressionCodec::None),
+            ("gzip.metadata.json", CompressionCodec::Gzip),
+        ] {
+            let location = dir.cloning_push(name);
+            write_metadata_file(&location, &metadata, codec, &file_io)
+                .await
+                .unwrap();
+            let content = read_file(&file_io, &location).await.unwrap();
+            assert_eq!(
+                serde_json::from_slice::<serde_json::Value>(&content).unwrap(),
+                metadata,
+                "{name}"
+            );
+        }
+
+        // Zstd is not written by Lakekeeper, but metadata of other engines is readable.
+        let location = dir.cloning_push("zstd.gz.metadata.json");
+        let compressed =
+            zstd::stream::encode_all(serde_json::to_vec(&metadata).unwrap().as_slice(), 0).unwrap();
+        write_file(&file_io, &location, compressed).await.unwrap();
+        let content = read_file(&file_io, &location).await.unwrap();
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&content).unwrap(),
+            metadata
+        );
+
+        remove_all(&file_io, &dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_in_chunks() {
+        let file_io = iceberg::io::FileIOBuilder::new("file").build().unwrap();
+        let dir = std::env::temp_dir().join(format!("lakekeeper-io-{}", uuid::Uuid::now_v7()));
+        let dir = Location::from_str(&format!("file://{}", dir.display())).unwrap();
+
+        let chunk_size = usize::try_from(COPY_CHUNK_SIZE).unwrap();
+        for (name, size) in [("empty", 0), ("small", 10), ("chunks", 2 * chunk_size + 1)] {
+            let content = (0..size)
+                .map(|i| u8::try_from(i % 251).unwrap())
+                .collect::<Vec<_>>();
+            let source = dir.cloning_push(&format!("{name}-source"));
+            let target = dir.cloning_push(&format!("{name}-target"));
+            write_file(&file_io, &source, content.clone())
+                .await
+                .unwrap();
+            copy_file(&file_io, &source, &target).await.unwrap();
+            assert!(
+                read_file(&file_io, &target).await.unwrap() == content,
+                "{name}"
+            );
+        }
+
+        remove_all(&file_io, &dir).await.unwrap();
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::{Arc, LazyLock};
+
+use axum_prometheus::metrics::counter;
+use iceberg::io::FileIO;
+use iceberg_ext::configs::Location;
+
+use super::io::{read_file, IoError};
+use crate::{WarehouseIdent, CONFIG};
+
+const MANIFEST_CACHE_HITS_TOTAL: &str = "lakekeeper_manifest_cache_hits_total";
+const MANIFEST_CACHE_MISSES_TOTAL: &str = "lakekeeper_manifest_cache_misses_total";
+
+/// Manifests and manifest lists are never modified after they are written, so entries
+/// do not expire. Only the capacity bounds the cache.
+static MANIFEST_CACHE: LazyLock<Option<ManifestCache>> = LazyLock::new(|| {
+    (CONFIG.manifest_cache_capacity_bytes > 0)
+        .then(|| ManifestCache::new(CONFIG.manifest_cache_capacity_bytes))
+});
+
+/// Warehouse that read the file, path of the file and its length in bytes, if the
+/// referencing file records it. Manifest lists record the length of their manifests,
+/// snapshots do not record the length of their manifest list.
+/// Warehouses may share a bucket with different credentials, so content read by one
+/// warehouse is never served to another.
+type ManifestKey = (WarehouseIdent, String, Option<u64>);
+
+#[derive(Debug, Clone)]
+pub(crate) struct ManifestCache(moka::sync::Cache<ManifestKey, Arc<[u8]>>);
+
+impl ManifestCache {
+    pub(crate) fn new(capacity_bytes: u64) -> Self {
+        Self(
+            moka::sync::Cache::builder()
+                .max_capacity(capacity_bytes)
+                .weigher(|_, content: &Arc<[u8]>| u32::try_from(content.len()).unwrap_or(u32::MAX))
+                .build(),
+        )
+    }
+
+    pub(crate) async fn read(
+        &self,
+        warehouse_id: WarehouseIdent,
+        file_io: &FileIO,
+        location: &Location,
+        length: Option<u64>,
+    ) -> Result<Arc<[u8]>, IoError> {
+        let key = (warehouse_id, location.to_string(), length);
+        if let Some(content) = self.0.get(&key) {
+            counter!(MANIFEST_CACHE_HITS_TOTAL).increment(1);
+            return Ok(content);
+        }
+        counter!(MANIFEST_CACHE_MISSES_TOTAL).increment(1);
+
+        let content: Arc<[u8]> = read_file(file_io, location).await?.into();
+        // A differing length means the referencing file does not describe this file,
+        // don't let the next reader trust it either.
+        if length.map_or(true, |length| length == content.len() as u64) {
+            self.0.insert(key, content.clone());
+        } else {
+            tracing::debug!(
+                "Not caching {location}: expected {length:?} bytes, read {}",
+                content.len()
+            );
+        }
+        Ok(content)
+    }
+}
+
+/// Read a manifest or manifest list of a table in `warehouse_id`. Served from the
+/// in-memory cache if `manifest_cache_capacity_bytes` is set, so that repeated reads of
+/// the same table do not download the same avro files again.
+pub(crate) async fn read_manifest(
+    warehouse_id: WarehouseIdent,
+    file_io: &FileIO,
+    location: &Location,
+    length: Option<u64>,
+) -> Result<Arc<[u8]>, IoError> {
+    match MANIFEST_CACHE.as_ref() {
+        Some(cache) => cache.read(warehouse_id, file_io, location, length).await,
+        None => Ok(read_file(file_io, location).await?.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::catalog::io::{remove_all, write_file};
+
+    #[tokio::test]
+    async fn test_manifest_cache() {
+        let file_io = iceberg::io::FileIOBuilder::new("file").build().unwrap();
+        let dir = std::env::temp_dir().join(format!("lakekeeper-cache-{}", uuid::Uuid::now_v7()));
+        let dir = Location::from_str(&format!("file://{}", dir.display())).unwrap();
+        let manifest = dir.cloning_push("manifest.avro");
+        write_file(&file_io, &manifest, b"first".to_vec())
+            .await
+            .unwrap();
+
+        let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let cache = ManifestCache::new(1024);
+        let content = cache
+            .read(warehouse_id, &file_io, &manifest, Some(5))
+            .await
+            .unwrap();
+        assert_eq!(&*content, b"first");
+
+        // Cached reads don't touch the storage anymore.
+        write_file(&file_io, &manifest, b"second".to_vec())
+            .await
+            .unwrap();
+        let content = cache
+            .read(warehouse_id, &file_io, &manifest, Some(5))
+            .await
+            .unwrap();
+        assert_eq!(&*content, b"first");
+
+        // Other warehouses don't see the content read by the first one.
+        let other_warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let content = cache
+            .read(other_warehouse_id, &file_io, &manifest, Some(6))
+            .await
+            .unwrap();
+        assert_eq!(&*content, b"second");
+
+        // Content that doesn't match the expected length is returned but not cached.
+        let content = cache
+            .read(warehouse_id, &file_io, &manifest, Some(3))
+            .await
+            .unwrap();
+        assert_eq!(&*content, b"second");
+        cache.0.run_pending_tasks();
+        assert!(!cache
+            .0
+            .contains_key(&(warehouse_id, manifest.to_string(), Some(3))));
+
+        remove_all(&file_io, &dir).await.unwrap();
+    }
+}
@@ -1,20 +1,197 @@
-use crate::api::iceberg::v1::{ApiContext, Result, TableParameters};
+use std::collections::HashMap;
+
+use crate::api::iceberg::v1::{ApiContext, ErrorModel, Result, TableParameters};
 use crate::request_metadata::RequestMetadata;
 
-use crate::service::{authz::Authorizer, secrets::SecretStore, Catalog, State};
+use crate::service::authz::CatalogTableAction;
+use crate::service::{
+    authz::Authorizer, secrets::SecretStore, Catalog, ListFlags, State, TableMetricsReport,
+    Transaction,
+};
 
-use super::CatalogServer;
+use super::tables::{authorized_table_ident_to_id, validate_table_or_view_ident};
+use super::{require_warehouse_id, CatalogServer};
 
 #[async_trait::async_trait]
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     crate::api::iceberg::v1::metrics::Service<State<A, C, S>> for CatalogServer<C, A, S>
 {
     async fn report_metrics(
-        _: TableParameters,
-        _: serde_json::Value,
-        _: ApiContext<State<A, C, S>>,
-        _: RequestMetadata,
+        parameters: TableParameters,
+        request: serde_json::Value,
+        state: ApiContext<State<A, C, S>>,
+        request_metadata: RequestMetadata,
     ) -> Result<()> {
+        // ------------------- VALIDATIONS -------------------
+        let TableParameters { prefix, table } = parameters;
+        let warehouse_id = require_warehouse_id(prefix)?;
+        validate_table_or_view_ident(&table)?;
+        let report = parse_metrics_report(request)?;
+
+        // ------------------- AUTHZ -------------------
+        let authorizer = state.v1_state.authz;
+        let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+        let table_id = authorized_table_ident_to_id::<C, _>(
+            authorizer,
+            &request_metadata,
+            warehouse_id,
+            &table,
+            ListFlags::default(),
+            &CatalogTableAction::CanGetMetadata,
+            t.transaction(),
+        )
+        .await?;
+
+        // ------------------- BUSINESS LOGIC -------------------
+        C::record_table_metrics(table_id, report, t.transaction()).await?;
+        t.commit().await?;
+
         Ok(())
     }
 }
+
+/// Subset of the `ReportMetricsRequest` of the Iceberg REST spec that we aggregate.
+#[derive(Debug, serde::Deserialize)]
+#[serde(tag = "report-type", rename_all = "kebab-case")]
+enum MetricsReport {
+    ScanReport {
+        #[serde(default)]
+        metrics: HashMap<String, MetricResult>,
+    },
+    CommitReport {
+        #[serde(default)]
+        metrics: HashMap<String, MetricResult>,
+    },
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum MetricResult {
+    Counter {
+        value: i64,
+    },
+    Timer {
+        #[serde(rename = "time-unit")]
+        time_unit: String,
+        #[serde(rename = "total-duration")]
+        total_duration: i64,
+    },
+}
+
+impl MetricResult {
+    fn as_counter(&self) -> i64 {
+        match self {
+            MetricResult::Counter { value, .. } => *value,
+            MetricResult::Timer { .. } => 0,
+        }
+    }
+
+    fn as_duration_ms(&self) -> i64 {
+        match self {
+            MetricResult::Counter { .. } => 0,
+            MetricResult::Timer {
+                time_unit,
+                total_duration,
+                ..
+            } => match time_unit.to_lowercase().as_str() {
+                "nanoseconds" => total_duration / 1_000_000,
+                "microseconds" => total_duration / 1_000,
+                "seconds" => total_duration.saturating_mul(1_000),
+                "minutes" => total_duration.saturating_mul(60_000),
+                "hours" => total_duration.saturating_mul(3_600_000),
+                "days" => total_duration.saturating_mul(86_400_000),
+                // milliseconds
+                _ => *total_duration,
+            },
+        }
+    }
+}
+
+fn parse_metrics_report(request: serde_json::Value) -> Result<TableMetricsReport> {
+    let report: MetricsReport = serde_json::from_value(request).map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Invalid metrics report: {e}"),
+            "InvalidMetricsReport",
+            Some(Box::new(e)),
+        )
+    })?;
+
+    let counter = |metrics: &HashMap<String, MetricResult>, name: &str| {
+        metrics.get(name).map_or(0, MetricResult::as_counter)
+    };
+
+    Ok(match report {
+        MetricsReport::ScanReport { metrics } => TableMetricsReport::Scan {
+            data_files: counter(&metrics, "result-data-files"),
+            bytes: counter(&metrics, "total-file-size-in-bytes"),
+            planning_duration_ms: metrics
+                .get("total-planning-duration")
+                .map_or(0, MetricResult::as_duration_ms),
+        },
+        MetricsReport::CommitReport { metrics } => TableMetricsReport::Commit {
+            data_files: counter(&metrics, "added-data-files"),
+            bytes: counter(&metrics, "added-files-size-in-bytes"),
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_scan_report() {
+        let report = parse_metrics_report(serde_json::json!({
+            "report-type": "scan-report",
+            "table-name": "ns.tbl",
+            "snapshot-id": 1,
+            "filter": true,
+            "schema-id": 0,
+            "projected-field-ids": [1],
+            "projected-field-names": ["id"],
+            "metrics": {
+                "result-data-files": {"unit": "count", "value": 3},
+                "total-file-size-in-bytes": {"unit": "bytes", "value": 1024},
+                "total-planning-duration": {"time-unit": "nanoseconds", "count": 1, "total-duration": 25_000_000}
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            report,
+            TableMetricsReport::Scan {
+                data_files: 3,
+                bytes: 1024,
+                planning_duration_ms: 25
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_commit_report() {
+        let report = parse_metrics_report(serde_json::json!({
+            "report-type": "commit-report",
+            "table-name": "ns.tbl",
+            "snapshot-id": 2,
+            "sequence-number": 2,
+            "operation": "append",
+            "metrics": {
+                "added-data-files": {"unit": "count", "value": 2},
+                "added-files-size-in-bytes": {"unit": "bytes", "value": 2048},
+                "total-duration": {"time-unit": "nanoseconds", "count": 1, "total-duration": 1}
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            report,
+            TableMetricsReport::Commit {
+                data_files: 2,
+                bytes: 2048
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_report() {
+        parse_metrics_report(serde_json::json!({"report-type": "foo"})).unwrap_err();
+    }
+}
@@ -1,11 +1,15 @@
 pub(crate) mod commit_tables;
 pub(crate) mod compression_codec;
-mod config;
+pub(crate) mod config;
 pub(crate) mod io;
+pub(crate) mod manifest_cache;
 mod metrics;
 pub(crate) mod namespace;
 #[cfg(feature = "s3-signer")]
 mod s3_signer;
+pub(crate) mod schema_compatibility;
+pub(crate) mod snapshot_summary;
+pub(crate) mod table_limits;
 pub(crate) mod tables;
 pub(crate) mod tabular;
 pub(crate) mod views;
@@ -14,9 +18,10 @@ use iceberg::spec::{TableMetadata, ViewMetadata};
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
 pub use namespace::{MAX_NAMESPACE_DEPTH, NAMESPACE_ID_PROPERTY, UNSUPPORTED_NAMESPACE_PROPERTIES};
 
-use crate::api::iceberg::v1::{PageToken, MAX_PAGE_SIZE};
+use crate::api::iceberg::v1::DataAccess;
+use crate::api::iceberg::v1::{PageToken, ReturnTotal, MAX_PAGE_SIZE};
 use crate::api::{iceberg::v1::Prefix, ErrorModel, Result};
-use crate::service::storage::StorageCredential;
+use crate::service::storage::{StorageCredential, StorageProfile};
 use crate::{
     service::{authz::Authorizer, secrets::SecretStore, Catalog},
     WarehouseIdent,
@@ -77,6 +82,21 @@ pub(crate) async fn maybe_get_secret<S: SecretStore>(
     }
 }
 
+/// Fetch the storage secret only if generating the table config for `data_access` needs it,
+/// so that loads answered with remote signing do not hit the secret store.
+pub(crate) async fn maybe_get_secret_for_table_config<S: SecretStore>(
+    secret: Option<crate::SecretIdent>,
+    storage_profile: &StorageProfile,
+    data_access: &DataAccess,
+    state: &S,
+) -> Result<Option<StorageCredential>, IcebergErrorResponse> {
+    if storage_profile.table_config_requires_secret(data_access) {
+        maybe_get_secret(secret, state).await
+    } else {
+        Ok(None)
+    }
+}
+
 pub const DEFAULT_PAGE_SIZE: i64 = 100;
 
 lazy_static::lazy_static! {
@@ -233,6 +253,58 @@ where
     Ok((entities, entity_ids, next_page_token))
 }
 
+/// Pages of a listing that are authorized for exact counts. Every entry is a separate
+/// authorization check, so longer listings extrapolate instead.
+const EXACT_COUNT_AUTHZ_PAGES: i64 = 3;
+
+/// Count the entries of a listing that the caller is allowed to see.
+///
+/// `total` is the count of the listing before authorization. Entries are authorized page by
+/// page from the start of the listing, up to [`EXACT_COUNT_AUTHZ_PAGES`] pages for
+/// exact counts and a single page for estimates. If the listing is longer, `total` is scaled
+/// by the share of checked entries that passed authorization. The result is still exact if
+/// all checked entries passed.
+pub(crate) async fn count_authorized<'b, 'd: 'b, Entity, EntityId, FetchFun, C: Catalog>(
+    mode: ReturnTotal,
+    total: i64,
+    mut fetch_fn: FetchFun,
+    transaction: &'d mut C::Transaction,
+) -> Result<i64>
+where
+    FetchFun: for<'c> FnMut(
+        i64,
+        Option<String>,
+        &'c mut C::Transaction,
+    ) -> BoxFuture<'c, Result<UnfilteredPage<Entity, EntityId>>>,
+{
+    let check_limit = match mode {
+        ReturnTotal::Exact => EXACT_COUNT_AUTHZ_PAGES * MAX_PAGE_SIZE,
+        ReturnTotal::Estimate => MAX_PAGE_SIZE,
+    };
+
+    let mut n_checked: i64 = 0;
+    let mut n_approved: i64 = 0;
+    let mut page_token = None;
+    loop {
+        let page = fetch_fn(MAX_PAGE_SIZE, page_token, transaction).await?;
+        let n_page = page.authz_approved.len();
+        n_checked += i64::try_from(n_page).unwrap_or(i64::MAX);
+        n_approved += i64::try_from(n_page - page.n_filtered).unwrap_or(i64::MAX);
+
+        page_token = page.page_tokens.last().cloned();
+        if page.is_partial() || page_token.is_none() {
+            return Ok(n_approved);
+        }
+        if n_checked >= check_limit {
+            break;
+        }
+    }
+
+    // The unfiltered total may be an outdated estimate that is below what we already saw.
+    let scaled = i128::from(total.max(n_checked)) * i128::from(n_approved) / i128::from(n_checked);
+    Ok(i64::try_from(scaled).unwrap_or(i64::MAX))
+}
+
 #[cfg(test)]
 #[allow(dead_code)]
 pub(crate) mod test {
@@ -241,6 +313,7 @@ pub(crate) mod test {
     use crate::api::management::v1::bootstrap::{BootstrapRequest, Service as _};
     use crate::api::management::v1::warehouse::{
         CreateWarehouseRequest, CreateWarehouseResponse, Service as _, TabularDeleteProfile,
+        WarehouseLifecycle,
     };
     use crate::api::management::v1::ApiServer;
     use crate::api::ApiContext;
@@ -253,7 +326,8 @@ pub(crate) mod test {
     use crate::service::contract_verification::ContractVerifiers;
     use crate::service::event_publisher::CloudEventsPublisher;
     use crate::service::storage::{
-        S3Credential, S3Flavor, S3Profile, StorageCredential, StorageProfile, TestProfile,
+        LocationPlacement, MetadataNaming, S3Credential, S3Flavor, S3Profile, StorageCredential,
+        StorageProfile, TestProfile,
     };
     use crate::service::task_queue::TaskQueues;
     use crate::service::{AuthDetails, State, UserId};
@@ -282,6 +356,7 @@ pub(crate) mod test {
         let cred: StorageCredential = S3Credential::AccessKey {
             aws_access_key_id,
             aws_secret_access_key,
+            signing_keys: vec![],
         }
         .into();
 
@@ -293,7 +368,18 @@ pub(crate) mod test {
             region,
             path_style_access: Some(true),
             sts_role_arn: None,
+            sts_endpoint: None,
+            sts_base_role_arn: None,
+            sts_external_id: None,
+            sts_session_tags: None,
+            sts_principal_identity: false,
+            object_tags: None,
+            metadata_storage_class: None,
+            skip_archived_on_purge: false,
+            metadata_naming: MetadataNaming::default(),
             flavor: S3Flavor::S3Compat,
+            additional_buckets: vec![],
+            location_placement: LocationPlacement::default(),
             sts_enabled: true,
         }
         .into();
@@ -358,6 +444,7 @@ pub(crate) mod test {
                 storage_profile,
                 storage_credential,
                 delete_profile,
+                lifecycle: WarehouseLifecycle::default(),
             },
             api_context.clone(),
             metadata,
@@ -386,7 +473,16 @@ pub(crate) mod test {
                         crate::implementations::postgres::task_queues::TabularExpirationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap(),
                     ),
                     Arc::new(
-                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
+                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::TabularRelocationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::TabularCompactionQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::TableInventoryExportQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
                     ),
                 ),
             },
@@ -397,6 +493,11 @@ pub(crate) mod test {
         RequestMetadata {
             request_id: Uuid::new_v4(),
             auth_details: AuthDetails::Unauthenticated,
+            engine: None,
+            user_agent: None,
+            engine_query_id: None,
+            client_ip: None,
+            warnings: std::sync::Arc::default(),
         }
     }
 
@@ -8,11 +8,18 @@ use crate::api::iceberg::v1::{
 use crate::api::set_not_found_status_code;
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{CatalogNamespaceAction, CatalogWarehouseAction, NamespaceParent};
+use crate::service::id_generation::{
+    external_id_may_not_change, generate_id, IdKind, IdRequest, PROPERTY_EXTERNAL_ID,
+};
+use crate::service::missing_idents::invalidate_missing_idents;
+use crate::service::storage::StorageProfile;
+use crate::service::table_ttl::{validate_table_ttl_properties, TABLE_TTL_PROPERTY};
 use crate::service::{
     authz::Authorizer, secrets::SecretStore, Catalog, GetWarehouseResponse, NamespaceIdentUuid,
     State, Transaction,
 };
 use crate::{catalog, WarehouseIdent, CONFIG};
+use futures::future::BoxFuture;
 use futures::FutureExt;
 use http::StatusCode;
 use iceberg::NamespaceIdent;
@@ -23,11 +30,12 @@ use std::collections::HashMap;
 use std::ops::Deref;
 
 pub const UNSUPPORTED_NAMESPACE_PROPERTIES: &[&str] = &[];
-// If this is increased, we need to modify namespace creation and deletion
-// to take care of the hierarchical structure.
+/// Default of the configurable maximum namespace depth, see `LAKEKEEPER__MAX_NAMESPACE_DEPTH`.
 pub const MAX_NAMESPACE_DEPTH: i32 = 5;
 pub const NAMESPACE_ID_PROPERTY: &str = "namespace_id";
 pub(crate) const MANAGED_ACCESS_PROPERTY: &str = "managed_access";
+/// Replaced by the id of the created namespace in namespace defaults of a warehouse.
+pub(crate) const NAMESPACE_ID_PLACEHOLDER: &str = "{namespace_id}";
 
 #[async_trait::async_trait]
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
@@ -46,9 +54,12 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             page_size: _,
             parent,
             return_uuids,
+            return_total,
+            return_descriptions,
         } = &query;
         parent.as_ref().map(validate_namespace_ident).transpose()?;
         let return_uuids = *return_uuids;
+        let return_descriptions = *return_descriptions;
 
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz;
@@ -76,64 +87,50 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let (idents, ids, next_page_token) = catalog::fetch_until_full_page::<_, _, _, C>(
             query.page_size,
             query.page_token.clone(),
-            |ps, page_token, trx| {
-                let parent = parent.clone();
-                let authorizer = authorizer.clone();
-                let request_metadata = request_metadata.clone();
-                async move {
-                    let query = ListNamespacesQuery {
-                        page_size: Some(ps),
-                        page_token: page_token.into(),
-                        parent,
-                        return_uuids: true,
-                    };
-
-                    // list_namespaces gives us a HashMap<Id, Ident> and a Vec<(Id, Token)>, in order
-                    // to do sane pagination, we need to rely on the order of the Vec<(Id, Token)> to
-                    // return the correct next page token which is why we do these unholy things here.
-                    let list_namespaces =
-                        C::list_namespaces(warehouse_id, &query, trx.transaction()).await?;
-                    let (ids, idents, tokens): (Vec<_>, Vec<_>, Vec<_>) =
-                        list_namespaces.into_iter_with_page_tokens().multiunzip();
-
-                    let (next_namespaces, next_uuids, next_page_tokens, mask): (
-                        Vec<_>,
-                        Vec<_>,
-                        Vec<_>,
-                        Vec<bool>,
-                    ) = futures::future::try_join_all(ids.iter().map(|n| {
-                        authorizer.is_allowed_namespace_action(
-                            &request_metadata,
-                            *n,
-                            &CatalogNamespaceAction::CanGetMetadata,
-                        )
-                    }))
-                    .await?
-                    .into_iter()
-                    .zip(idents.into_iter().zip(ids.into_iter()))
-                    .zip(tokens.into_iter())
-                    .map(|((allowed, namespace), token)| (namespace.0, namespace.1, token, allowed))
-                    .multiunzip();
-
-                    Ok(UnfilteredPage::new(
-                        next_namespaces,
-                        next_uuids,
-                        next_page_tokens,
-                        mask,
-                        ps.clamp(0, i64::MAX).try_into().expect("We clamped it"),
-                    ))
-                }
-                .boxed()
-            },
+            list_namespaces_page::<C, _>(
+                warehouse_id,
+                parent.clone(),
+                authorizer.clone(),
+                request_metadata.clone(),
+            ),
             &mut t,
         )
         .await?;
+        let total_count = if let Some(mode) = return_total {
+            let total =
+                C::count_namespaces(warehouse_id, parent.as_ref(), *mode, t.transaction()).await?;
+            Some(
+                catalog::count_authorized::<_, _, _, C>(
+                    *mode,
+                    total,
+                    list_namespaces_page::<C, _>(
+                        warehouse_id,
+                        parent.clone(),
+                        authorizer.clone(),
+                        request_metadata.clone(),
+                    ),
+                    &mut t,
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
+        let ids = ids.into_iter().map(|s| *s).collect::<Vec<_>>();
+        let descriptions = if return_descriptions {
+            let mut descriptions = C::get_namespace_descriptions(&ids, t.transaction()).await?;
+            Some(ids.iter().map(|id| descriptions.remove(id)).collect())
+        } else {
+            None
+        };
         t.commit().await?;
 
         Ok(ListNamespacesResponse {
             next_page_token,
             namespaces: idents,
-            namespace_uuids: return_uuids.then_some(ids.into_iter().map(|s| *s).collect()),
+            namespace_uuids: return_uuids.then_some(ids),
+            total_count,
+            descriptions,
         })
     }
 
@@ -156,6 +153,10 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .as_ref()
             .map(|p| validate_namespace_properties_keys(p.keys()))
             .transpose()?;
+        properties
+            .as_ref()
+            .map(validate_table_ttl_properties)
+            .transpose()?;
 
         if CONFIG
             .reserved_namespaces
@@ -208,10 +209,22 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         };
 
         // ------------------- BUSINESS LOGIC -------------------
-        let namespace_id = NamespaceIdentUuid::default();
+        let namespace_id = NamespaceIdentUuid::from(generate_id(IdRequest {
+            kind: IdKind::Namespace,
+            name: namespace.as_ref().last().map_or("", String::as_str),
+            external_id: properties
+                .as_ref()
+                .and_then(|properties| properties.get(PROPERTY_EXTERNAL_ID))
+                .map(String::as_str),
+        }));
         let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
 
-        let mut namespace_props = NamespaceProperties::try_from_maybe_props(properties.clone())
+        let properties = apply_namespace_defaults(
+            properties.clone(),
+            &warehouse.namespace_defaults,
+            namespace_id,
+        );
+        let mut namespace_props = NamespaceProperties::try_from_maybe_props(properties)
             .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
         // Set location if not specified - validate location if specified
         set_namespace_location_property(&mut namespace_props, &warehouse, namespace_id)?;
@@ -231,6 +244,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .create_namespace(&request_metadata, namespace_id, authz_parent)
             .await?;
         t.commit().await?;
+        invalidate_missing_idents(warehouse_id);
         r.properties
             .as_mut()
             .map(|p| p.insert(NAMESPACE_ID_PROPERTY.to_string(), namespace_id.to_string()));
@@ -356,16 +370,31 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let warehouse_id = require_warehouse_id(parameters.prefix)?;
         validate_namespace_ident(&parameters.namespace)?;
         let UpdateNamespacePropertiesRequest { removals, updates } = request;
+        let sets_table_ttl = updates
+            .as_ref()
+            .is_some_and(|u| u.contains_key(TABLE_TTL_PROPERTY));
         updates
             .as_ref()
             .map(|p| validate_namespace_properties_keys(p.keys()))
             .transpose()?;
+        updates
+            .as_ref()
+            .map(validate_table_ttl_properties)
+            .transpose()?;
         removals
             .as_ref()
             .map(validate_namespace_properties_keys)
             .transpose()?;
 
         namespace_location_may_not_change(updates.as_ref(), removals.as_ref())?;
+        updates
+            .as_ref()
+            .map(|p| external_id_may_not_change(p.keys()))
+            .transpose()?;
+        removals
+            .as_ref()
+            .map(external_id_may_not_change)
+            .transpose()?;
         let mut updates = NamespaceProperties::try_from_maybe_props(updates.clone())
             .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
         remove_managed_namespace_properties(&mut updates);
@@ -373,7 +402,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let authorizer = state.v1_state.authz.clone();
         let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
         let namespace_id = authorized_namespace_ident_to_id::<C, _>(
-            authorizer,
+            authorizer.clone(),
             &request_metadata,
             &warehouse_id,
             &parameters.namespace,
@@ -381,6 +410,16 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             t.transaction(),
         )
         .await?;
+        // Tables of the namespace are dropped once they outlive the TTL
+        if sets_table_ttl {
+            authorizer
+                .require_namespace_action(
+                    &request_metadata,
+                    Ok(Some(namespace_id)),
+                    &CatalogNamespaceAction::CanDelete,
+                )
+                .await?;
+        }
 
         //  ------------------- BUSINESS LOGIC -------------------
         let previous_properties =
@@ -394,6 +433,70 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     }
 }
 
+/// One page of the namespaces below `parent`, with those the caller may not see masked out.
+fn list_namespaces_page<C: Catalog, A: Authorizer + Clone>(
+    warehouse_id: WarehouseIdent,
+    parent: Option<NamespaceIdent>,
+    authorizer: A,
+    request_metadata: RequestMetadata,
+) -> impl for<'c> FnMut(
+    i64,
+    Option<String>,
+    &'c mut C::Transaction,
+) -> BoxFuture<'c, Result<UnfilteredPage<NamespaceIdent, NamespaceIdentUuid>>> {
+    move |ps, page_token, trx| {
+        let parent = parent.clone();
+        let authorizer = authorizer.clone();
+        let request_metadata = request_metadata.clone();
+        async move {
+            let query = ListNamespacesQuery {
+                page_size: Some(ps),
+                page_token: page_token.into(),
+                parent,
+                return_uuids: true,
+                return_total: None,
+                return_descriptions: false,
+            };
+
+            // list_namespaces gives us a HashMap<Id, Ident> and a Vec<(Id, Token)>, in order
+            // to do sane pagination, we need to rely on the order of the Vec<(Id, Token)> to
+            // return the correct next page token which is why we do these unholy things here.
+            let list_namespaces =
+                C::list_namespaces(warehouse_id, &query, trx.transaction()).await?;
+            let (ids, idents, tokens): (Vec<_>, Vec<_>, Vec<_>) =
+                list_namespaces.into_iter_with_page_tokens().multiunzip();
+
+            let (next_namespaces, next_uuids, next_page_tokens, mask): (
+                Vec<_>,
+                Vec<_>,
+                Vec<_>,
+                Vec<bool>,
+            ) = futures::future::try_join_all(ids.iter().map(|n| {
+                authorizer.is_allowed_namespace_action(
+                    &request_metadata,
+                    *n,
+                    &CatalogNamespaceAction::CanGetMetadata,
+                )
+            }))
+            .await?
+            .into_iter()
+            .zip(idents.into_iter().zip(ids.into_iter()))
+            .zip(tokens.into_iter())
+            .map(|((allowed, namespace), token)| (namespace.0, namespace.1, token, allowed))
+            .multiunzip();
+
+            Ok(UnfilteredPage::new(
+                next_namespaces,
+                next_uuids,
+                next_page_tokens,
+                mask,
+                ps.clamp(0, i64::MAX).try_into().expect("We clamped it"),
+            ))
+        }
+        .boxed()
+    }
+}
+
 pub(crate) async fn authorized_namespace_ident_to_id<C: Catalog, A: Authorizer + Clone>(
     authorizer: A,
     metadata: &RequestMetadata,
@@ -448,9 +551,12 @@ where
 }
 
 pub(crate) fn validate_namespace_ident(namespace: &NamespaceIdent) -> Result<()> {
-    if namespace.len() > MAX_NAMESPACE_DEPTH as usize {
+    if namespace.len() > CONFIG.max_namespace_depth {
         return Err(ErrorModel::bad_request(
-            format!("Namespace exceeds maximum depth of {MAX_NAMESPACE_DEPTH}",),
+            format!(
+                "Namespace exceeds maximum depth of {}",
+                CONFIG.max_namespace_depth
+            ),
             "NamespaceDepthExceeded".to_string(),
             None,
         )
@@ -477,6 +583,22 @@ pub(crate) fn validate_namespace_ident(namespace: &NamespaceIdent) -> Result<()>
         .into());
     }
 
+    if namespace
+        .iter()
+        .any(|s| s.len() > CONFIG.max_namespace_part_length_bytes)
+    {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Namespace parts cannot be longer than {} bytes",
+                CONFIG.max_namespace_part_length_bytes
+            ),
+            "NamespacePartTooLong".to_string(),
+            None,
+        )
+        .append_detail(format!("Namespace: {namespace:?}"))
+        .into());
+    }
+
     Ok(())
 }
 
@@ -512,6 +634,65 @@ fn set_namespace_location_property(
     Ok(())
 }
 
+/// Add the namespace defaults of the warehouse for all properties not set in the request.
+fn apply_namespace_defaults(
+    properties: Option<HashMap<String, String>>,
+    namespace_defaults: &HashMap<String, String>,
+    namespace_id: NamespaceIdentUuid,
+) -> Option<HashMap<String, String>> {
+    if namespace_defaults.is_empty() {
+        return properties;
+    }
+
+    let mut properties = properties.unwrap_or_default();
+    for (key, value) in namespace_defaults {
+        properties
+            .entry(key.clone())
+            .or_insert_with(|| value.replace(NAMESPACE_ID_PLACEHOLDER, &namespace_id.to_string()));
+    }
+    Some(properties)
+}
+
+/// Validate namespace defaults before they are set for a warehouse.
+pub(crate) fn validate_namespace_defaults(
+    namespace_defaults: &HashMap<String, String>,
+    storage_profile: &StorageProfile,
+) -> Result<()> {
+    validate_namespace_properties_keys(namespace_defaults.keys())?;
+    if let Some(key) = namespace_defaults
+        .keys()
+        .find(|k| [NAMESPACE_ID_PROPERTY, MANAGED_ACCESS_PROPERTY].contains(&k.as_str()))
+    {
+        return Err(ErrorModel::bad_request(
+            format!("The '{key}' property is managed by the catalog and cannot have a default."),
+            "ManagedNamespaceProperty",
+            None,
+        )
+        .into());
+    }
+
+    // Namespaces must not share a location
+    if namespace_defaults
+        .get(Location::KEY)
+        .is_some_and(|location| !location.contains(NAMESPACE_ID_PLACEHOLDER))
+    {
+        return Err(ErrorModel::bad_request(
+            format!("Default namespace location must contain the {NAMESPACE_ID_PLACEHOLDER} placeholder."),
+            "InvalidNamespaceDefaultLocation",
+            None,
+        )
+        .into());
+    }
+
+    let example = apply_namespace_defaults(None, namespace_defaults, NamespaceIdentUuid::default());
+    let example = NamespaceProperties::try_from_maybe_props(example)
+        .map_err(|e| ErrorModel::bad_request(e.to_string(), e.err_type(), None))?;
+    if let Some(location) = example.get_location() {
+        storage_profile.require_allowed_location(&location)?;
+    }
+    Ok(())
+}
+
 fn update_namespace_properties(
     previous_properties: Option<HashMap<String, String>>,
     updates: NamespaceProperties,
@@ -591,6 +772,7 @@ mod tests {
 
     use crate::api::iceberg::types::{PageToken, Prefix};
     use crate::api::iceberg::v1::namespace::Service;
+    use crate::api::iceberg::v1::ReturnTotal;
     use crate::api::management::v1::warehouse::TabularDeleteProfile;
     use crate::api::ApiContext;
     use crate::catalog::test::impl_pagination_tests;
@@ -671,6 +853,30 @@ mod tests {
         |ns| ns.inner()[0].to_string()
     );
 
+    #[sqlx::test]
+    async fn test_namespace_total_count_excludes_hidden(pool: PgPool) {
+        let (ctx, prefix) = ns_paginate_test_setup(pool, 20, &[(5, 15)]).await;
+        for mode in [ReturnTotal::Exact, ReturnTotal::Estimate] {
+            let page = CatalogServer::list_namespaces(
+                prefix.clone(),
+                ListNamespacesQuery {
+                    page_token: PageToken::NotSpecified,
+                    page_size: Some(3),
+                    parent: None,
+                    return_uuids: false,
+                    return_total: Some(mode),
+                    return_descriptions: false,
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(page.namespaces.len(), 3);
+            assert_eq!(page.total_count, Some(10), "{mode:?}");
+        }
+    }
+
     #[sqlx::test]
     async fn test_ns_pagination(pool: sqlx::PgPool) {
         let prof = crate::catalog::test::test_io_profile();
@@ -709,6 +915,8 @@ mod tests {
                 page_size: Some(11),
                 parent: None,
                 return_uuids: true,
+                return_total: None,
+                return_descriptions: false,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -724,6 +932,8 @@ mod tests {
                 page_size: Some(10),
                 parent: None,
                 return_uuids: true,
+                return_total: None,
+                return_descriptions: false,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -739,6 +949,8 @@ mod tests {
                 page_size: Some(6),
                 parent: None,
                 return_uuids: true,
+                return_total: None,
+                return_descriptions: false,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -763,6 +975,8 @@ mod tests {
                 page_size: Some(6),
                 parent: None,
                 return_uuids: true,
+                return_total: None,
+                return_descriptions: false,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -791,6 +1005,8 @@ mod tests {
                 page_size: Some(5),
                 parent: None,
                 return_uuids: true,
+                return_total: None,
+                return_descriptions: false,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -818,6 +1034,8 @@ mod tests {
                 page_size: Some(5),
                 parent: None,
                 return_uuids: true,
+                return_total: None,
+                return_descriptions: false,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -869,6 +1087,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_apply_namespace_defaults() {
+        use super::*;
+        let namespace_id = NamespaceIdentUuid::default();
+        let defaults = HashMap::from_iter(vec![
+            ("owner".to_string(), "data-platform".to_string()),
+            (
+                "location".to_string(),
+                "s3://bucket/lake/{namespace_id}".to_string(),
+            ),
+        ]);
+
+        let properties = apply_namespace_defaults(
+            Some(HashMap::from_iter(vec![(
+                "owner".to_string(),
+                "finance".to_string(),
+            )])),
+            &defaults,
+            namespace_id,
+        );
+        assert_eq!(
+            properties,
+            Some(HashMap::from_iter(vec![
+                ("owner".to_string(), "finance".to_string()),
+                (
+                    "location".to_string(),
+                    format!("s3://bucket/lake/{namespace_id}")
+                ),
+            ]))
+        );
+        assert_eq!(
+            apply_namespace_defaults(None, &HashMap::new(), namespace_id),
+            None
+        );
+    }
+
+    #[test]
+    fn test_validate_namespace_defaults() {
+        use super::*;
+        let profile = crate::catalog::test::test_io_profile();
+        let defaults =
+            |key: &str, value: &str| HashMap::from_iter(vec![(key.to_string(), value.to_string())]);
+
+        validate_namespace_defaults(&defaults("owner", "data-platform"), &profile).unwrap();
+        validate_namespace_defaults(&defaults("Owner", "data-platform"), &profile).unwrap_err();
+        validate_namespace_defaults(&defaults(MANAGED_ACCESS_PROPERTY, "true"), &profile)
+            .unwrap_err();
+        validate_namespace_defaults(&defaults("location", "s3://bucket/lake"), &profile)
+            .unwrap_err();
+    }
+
     #[test]
     fn test_update_ns_properties_empty_removal() {
         use super::*;
@@ -10,6 +10,7 @@ use crate::service::authz::{CatalogTableAction, CatalogWarehouseAction};
 use aws_sigv4::http_request::{sign as aws_sign, SignableBody, SignableRequest, SigningSettings};
 use aws_sigv4::sign::v4;
 use aws_sigv4::{self};
+use axum_prometheus::metrics::counter;
 
 use super::super::CatalogServer;
 use super::error::SignError;
@@ -22,6 +23,11 @@ use crate::service::{GetTableMetadataResponse, TableIdentUuid};
 const READ_METHODS: &[&str] = &["GET", "HEAD"];
 const WRITE_METHODS: &[&str] = &["PUT", "POST", "DELETE"];
 
+/// Counter of signed requests by warehouse and signing key.
+const SIGNED_REQUESTS_TOTAL: &str = "lakekeeper_s3_signer_signed_requests_total";
+/// `key_id` label of requests signed with the storage credential itself.
+const STORAGE_CREDENTIAL_KEY_ID: &str = "storage-credential";
+
 #[async_trait::async_trait]
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     crate::api::iceberg::v1::s3_signer::Service<State<A, C, S>> for CatalogServer<C, A, S>
@@ -161,11 +167,25 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         })
         .transpose()?;
 
-        let credentials: aws_credential_types::Credentials = storage_profile
-            .get_aws_sdk_credentials(storage_secret.as_ref())
-            .map_err(|e| extend_err(IcebergErrorResponse::from(e)))?;
+        let signing_key = storage_secret
+            .as_ref()
+            .map(|secret| secret.active_signing_key(chrono::Utc::now()))
+            .transpose()
+            .map_err(|e| extend_err(IcebergErrorResponse::from(e)))?
+            .flatten();
+        let (credentials, key_id): (aws_credential_types::Credentials, _) =
+            if let Some(signing_key) = signing_key {
+                (signing_key.into(), signing_key.key_id.clone())
+            } else {
+                (
+                    storage_profile
+                        .get_aws_sdk_credentials(storage_secret.as_ref())
+                        .map_err(|e| extend_err(IcebergErrorResponse::from(e)))?,
+                    STORAGE_CREDENTIAL_KEY_ID.to_string(),
+                )
+            };
 
-        sign(
+        let response = sign(
             credentials,
             request_body,
             &request_region,
@@ -173,7 +193,15 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             &request_method,
             request_headers,
         )
-        .map_err(extend_err)
+        .map_err(extend_err)?;
+
+        counter!(
+            SIGNED_REQUESTS_TOTAL,
+            "warehouse_id" => warehouse_id.to_string(),
+            "key_id" => key_id
+        )
+        .increment(1);
+        Ok(response)
     }
 }
 
@@ -470,7 +498,7 @@ pub(super) mod s3_utils {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::service::storage::S3Flavor;
+    use crate::service::storage::{LocationPlacement, MetadataNaming, S3Flavor};
 
     #[derive(Debug)]
     struct TC {
@@ -727,8 +755,19 @@ mod test {
             path_style_access: None,
             key_prefix: None,
             sts_role_arn: None,
+            sts_endpoint: None,
+            sts_base_role_arn: None,
+            sts_external_id: None,
+            sts_session_tags: None,
+            sts_principal_identity: false,
+            object_tags: None,
+            metadata_storage_class: None,
+            skip_archived_on_purge: false,
+            metadata_naming: MetadataNaming::default(),
             sts_enabled: false,
             flavor: S3Flavor::S3Compat,
+            additional_buckets: vec![],
+            location_placement: LocationPlacement::default(),
         };
 
         let result = validate_region("my-region", &storage_profile);
@@ -0,0 +1,117 @@
+use iceberg::spec::{PrimitiveType, Schema, Type};
+
+/// Compare the schema of a table with the schema of the table it replaces by field id.
+///
+/// Readers resolve columns by id, so a field id must keep a compatible type, must not
+/// become required and must not disappear. Renames and new optional fields are fine.
+/// Returns one message per incompatible field.
+pub(crate) fn schema_incompatibilities(previous: &Schema, new: &Schema) -> Vec<String> {
+    let mut incompatibilities = vec![];
+    for id in 0..=previous.highest_field_id() {
+        let Some(previous_field) = previous.field_by_id(id) else {
+            continue;
+        };
+        let name = previous
+            .name_by_field_id(id)
+            .unwrap_or(previous_field.name.as_str());
+
+        let Some(new_field) = new.field_by_id(id) else {
+            incompatibilities.push(format!("Field `{name}` (id {id}) was removed"));
+            continue;
+        };
+        if !is_compatible_type(&previous_field.field_type, &new_field.field_type) {
+            incompatibilities.push(format!(
+                "Field `{name}` (id {id}) changed its type from {} to {}",
+                previous_field.field_type, new_field.field_type
+            ));
+        }
+        if new_field.required && !previous_field.required {
+            incompatibilities.push(format!("Field `{name}` (id {id}) became required"));
+        }
+    }
+    incompatibilities
+}
+
+/// Types are compatible if they are equal or a valid type promotion.
+/// Children of nested types are checked by their own id.
+fn is_compatible_type(previous: &Type, new: &Type) -> bool {
+    match (previous, new) {
+        (Type::Primitive(previous), Type::Primitive(new)) => {
+            previous == new
+                || matches!(
+                    (previous, new),
+                    (PrimitiveType::Int, PrimitiveType::Long)
+                        | (PrimitiveType::Float, PrimitiveType::Double)
+                )
+                || matches!(
+                    (previous, new),
+                    (
+                        PrimitiveType::Decimal { precision: p1, scale: s1 },
+                        PrimitiveType::Decimal { precision: p2, scale: s2 }
+                    ) if s1 == s2 && p2 >= p1
+                )
+        }
+        (Type::Struct(_), Type::Struct(_))
+        | (Type::List(_), Type::List(_))
+        | (Type::Map(_), Type::Map(_)) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use iceberg::spec::{NestedField, StructType};
+
+    fn schema(fields: Vec<NestedField>) -> Schema {
+        Schema::builder()
+            .with_fields(fields.into_iter().map(Into::into))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compatible_schemas() {
+        let previous = schema(vec![
+            NestedField::required(1, "id", Type::Primitive(PrimitiveType::Int)),
+            NestedField::optional(2, "price", Type::Primitive(PrimitiveType::Float)),
+        ]);
+        let new = schema(vec![
+            NestedField::optional(1, "key", Type::Primitive(PrimitiveType::Long)),
+            NestedField::optional(2, "price", Type::Primitive(PrimitiveType::Double)),
+            NestedField::optional(3, "comment", Type::Primitive(PrimitiveType::String)),
+        ]);
+        assert!(schema_incompatibilities(&previous, &new).is_empty());
+    }
+
+    #[test]
+    fn test_incompatible_schemas() {
+        let previous = schema(vec![
+            NestedField::required(1, "id", Type::Primitive(PrimitiveType::Long)),
+            NestedField::optional(2, "name", Type::Primitive(PrimitiveType::String)),
+            NestedField::optional(
+                3,
+                "address",
+                Type::Struct(StructType::new(vec![NestedField::optional(
+                    4,
+                    "city",
+                    Type::Primitive(PrimitiveType::String),
+                )
+                .into()])),
+            ),
+        ]);
+        let new = schema(vec![
+            NestedField::required(1, "id", Type::Primitive(PrimitiveType::String)),
+            NestedField::required(2, "name", Type::Primitive(PrimitiveType::String)),
+            NestedField::optional(3, "address", Type::Struct(StructType::new(vec![]))),
+        ]);
+        assert_eq!(
+            schema_incompatibilities(&previous, &new),
+            vec![
+                "Field `id` (id 1) changed its type from long to string",
+                "Field `name` (id 2) became required",
+                "Field `address.city` (id 4) was removed",
+            ]
+        );
+    }
+}
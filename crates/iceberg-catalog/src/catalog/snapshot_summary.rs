@@ -0,0 +1,173 @@
+use crate::api::management::v1::warehouse::SnapshotSummaryEnrichment;
+use crate::api::Result;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authn::Actor;
+use iceberg::spec::Snapshot;
+use iceberg::TableUpdate;
+use iceberg_ext::catalog::rest::ErrorModel;
+
+/// Snapshot summary key holding the principal that committed the snapshot.
+pub(crate) const SUMMARY_COMMITTED_BY: &str = "lakekeeper.committed-by";
+/// Snapshot summary key holding the `User-Agent` of the engine that committed the snapshot.
+pub(crate) const SUMMARY_ENGINE: &str = "lakekeeper.engine";
+/// Snapshot summary key holding the id of the request that committed the snapshot.
+pub(crate) const SUMMARY_REQUEST_ID: &str = "lakekeeper.request-id";
+
+/// Add the provenance enabled for the warehouse to the summary of every snapshot
+/// added by `updates`. Values the client sent for the same keys are replaced.
+pub(crate) fn enrich_snapshot_summaries(
+    enrichment: SnapshotSummaryEnrichment,
+    request_metadata: &RequestMetadata,
+    updates: &mut [TableUpdate],
+) -> Result<()> {
+    let properties = summary_properties(enrichment, request_metadata);
+    if properties.is_empty() {
+        return Ok(());
+    }
+
+    for update in updates {
+        if let TableUpdate::AddSnapshot { snapshot } = update {
+            *snapshot = with_summary_properties(snapshot, &properties)?;
+        }
+    }
+    Ok(())
+}
+
+fn summary_properties(
+    enrichment: SnapshotSummaryEnrichment,
+    request_metadata: &RequestMetadata,
+) -> Vec<(&'static str, String)> {
+    let mut properties = vec![];
+    if enrichment.principal {
+        match request_metadata.actor() {
+            Actor::Principal(user_id) => {
+                properties.push((SUMMARY_COMMITTED_BY, user_id.to_string()))
+            }
+            Actor::Role { principal, .. } => {
+                properties.push((SUMMARY_COMMITTED_BY, principal.to_string()));
+            }
+            Actor::Anonymous => {}
+        }
+    }
+    if enrichment.engine {
+        if let Some(user_agent) = request_metadata.user_agent() {
+            properties.push((SUMMARY_ENGINE, user_agent.to_string()));
+        }
+    }
+    if enrichment.request_id {
+        properties.push((SUMMARY_REQUEST_ID, request_metadata.request_id.to_string()));
+    }
+    properties
+}
+
+fn with_summary_properties(
+    snapshot: &Snapshot,
+    properties: &[(&'static str, String)],
+) -> Result<Snapshot> {
+    // `Snapshot` cannot be modified in place. Going through its serialized form keeps
+    // all fields other than the summary exactly as the client sent them.
+    let mut value = serde_json::to_value(snapshot).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize snapshot",
+            "SnapshotSummaryEnrichmentFailed",
+            Some(Box::new(e)),
+        )
+    })?;
+    if let Some(summary) = value
+        .get_mut("summary")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        for (key, property) in properties {
+            summary.insert((*key).to_string(), property.clone().into());
+        }
+    }
+    serde_json::from_value(value).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to add provenance to snapshot summary",
+            "SnapshotSummaryEnrichmentFailed",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use iceberg::spec::{Operation, Summary};
+    use std::collections::HashMap;
+
+    fn snapshot() -> Snapshot {
+        Snapshot::builder()
+            .with_snapshot_id(7)
+            .with_parent_snapshot_id(Some(6))
+            .with_sequence_number(3)
+            .with_timestamp_ms(1_700_000_000_000)
+            .with_manifest_list("s3://bucket/table/metadata/snap-7.avro")
+            .with_schema_id(1)
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::from_iter([
+                    ("added-records".to_string(), "10".to_string()),
+                    (SUMMARY_REQUEST_ID.to_string(), "spoofed".to_string()),
+                ]),
+            })
+            .build()
+    }
+
+    fn add_snapshot() -> TableUpdate {
+        TableUpdate::AddSnapshot {
+            snapshot: snapshot(),
+        }
+    }
+
+    #[test]
+    fn test_enrich_snapshot_summaries() {
+        let mut request_metadata = RequestMetadata::new_random();
+        request_metadata.user_agent = Some("PyIceberg/0.8.1".to_string());
+        let enrichment = SnapshotSummaryEnrichment {
+            principal: true,
+            engine: true,
+            request_id: true,
+        };
+
+        let mut updates = vec![
+            add_snapshot(),
+            TableUpdate::RemoveSnapshotRef {
+                ref_name: "tmp".to_string(),
+            },
+        ];
+        enrich_snapshot_summaries(enrichment, &request_metadata, &mut updates).unwrap();
+
+        let TableUpdate::AddSnapshot { snapshot: enriched } = &updates[0] else {
+            panic!("Expected AddSnapshot");
+        };
+        let original = snapshot();
+        assert_eq!(enriched.snapshot_id(), original.snapshot_id());
+        assert_eq!(enriched.parent_snapshot_id(), original.parent_snapshot_id());
+        assert_eq!(enriched.manifest_list(), original.manifest_list());
+        assert_eq!(enriched.schema_id(), original.schema_id());
+
+        let properties = &enriched.summary().additional_properties;
+        assert_eq!(properties["added-records"], "10");
+        assert_eq!(properties[SUMMARY_ENGINE], "PyIceberg/0.8.1");
+        assert_eq!(
+            properties[SUMMARY_REQUEST_ID],
+            request_metadata.request_id.to_string()
+        );
+        // Unauthenticated requests have no principal to record.
+        assert!(!properties.contains_key(SUMMARY_COMMITTED_BY));
+    }
+
+    #[test]
+    fn test_enrichment_disabled() {
+        let mut updates = vec![add_snapshot()];
+        enrich_snapshot_summaries(
+            SnapshotSummaryEnrichment::default(),
+            &RequestMetadata::new_random(),
+            &mut updates,
+        )
+        .unwrap();
+        assert_eq!(updates, vec![add_snapshot()]);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::api::management::v1::warehouse::TableLimits;
+use crate::api::{ErrorModel, Result};
+use iceberg::spec::TableMetadata;
+use iceberg::TableIdent;
+use serde::Serialize;
+
+/// Metric of a table that is limited by [`TableLimits`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, strum_macros::Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub(crate) enum TableLimitMetric {
+    SnapshotCount,
+    MetadataSizeBytes,
+}
+
+/// A table reached the warning threshold of a metric.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct TableLimitWarning {
+    pub(crate) metric: TableLimitMetric,
+    pub(crate) value: u64,
+    pub(crate) threshold: u64,
+    /// The table was below the threshold before the commit.
+    #[serde(skip)]
+    pub(crate) crossed: bool,
+}
+
+/// Check the metadata produced by a commit against the limits of the warehouse.
+///
+/// Commits that grow a metric beyond its limit are rejected. Commits that do not
+/// grow it are accepted, so that snapshots can be expired. Returns the warning
+/// thresholds the new metadata reached.
+pub(crate) fn check_table_limits(
+    limits: &TableLimits,
+    table: &TableIdent,
+    previous: &TableMetadata,
+    new: &TableMetadata,
+) -> Result<Vec<TableLimitWarning>> {
+    let mut warnings = vec![];
+    if limits.snapshot_count_warning.is_some() || limits.snapshot_count_limit.is_some() {
+        check_metric(
+            TableLimitMetric::SnapshotCount,
+            table,
+            || Ok(snapshot_count(previous)),
+            snapshot_count(new),
+            limits.snapshot_count_warning,
+            limits.snapshot_count_limit,
+            &mut warnings,
+        )?;
+    }
+    if limits.metadata_size_warning_bytes.is_some() || limits.metadata_size_limit_bytes.is_some() {
+        check_metric(
+            TableLimitMetric::MetadataSizeBytes,
+            table,
+            || metadata_size(previous),
+            metadata_size(new)?,
+            limits.metadata_size_warning_bytes,
+            limits.metadata_size_limit_bytes,
+            &mut warnings,
+        )?;
+    }
+    Ok(warnings)
+}
+
+fn check_metric(
+    metric: TableLimitMetric,
+    table: &TableIdent,
+    previous: impl FnOnce() -> Result<u64>,
+    value: u64,
+    warning: Option<u64>,
+    limit: Option<u64>,
+    warnings: &mut Vec<TableLimitWarning>,
+) -> Result<()> {
+    let exceeds_limit = limit.is_some_and(|limit| value > limit);
+    let reaches_warning = warning.is_some_and(|warning| value >= warning);
+    if !exceeds_limit && !reaches_warning {
+        return Ok(());
+    }
+    // Only computed if needed, as the metadata size requires serializing the metadata.
+    let previous = previous()?;
+
+    if let Some(limit) = limit.filter(|_| exceeds_limit && value > previous) {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Commit rejected: {metric} of table {} would be {value}, exceeding the limit of {limit} of the warehouse. Expire snapshots to reduce it.",
+                table_display(table)
+            ),
+            "TableLimitExceeded",
+            None,
+        )
+        .into());
+    }
+    if let Some(threshold) = warning.filter(|_| reaches_warning) {
+        warnings.push(TableLimitWarning {
+            metric,
+            value,
+            threshold,
+            crossed: previous < threshold,
+        });
+    }
+    Ok(())
+}
+
+impl TableLimitWarning {
+    pub(crate) fn message(&self, table: &TableIdent) -> String {
+        format!(
+            "{} of table {} is {}, which reached the warning threshold of {}. Expire snapshots to reduce it.",
+            self.metric,
+            table_display(table),
+            self.value,
+            self.threshold
+        )
+    }
+}
+
+fn table_display(table: &TableIdent) -> String {
+    format!("{}.{}", table.namespace.to_url_string(), table.name)
+}
+
+fn snapshot_count(metadata: &TableMetadata) -> u64 {
+    metadata.snapshots().len() as u64
+}
+
+fn metadata_size(metadata: &TableMetadata) -> Result<u64> {
+    serde_json::to_vec(metadata)
+        .map(|bytes| bytes.len() as u64)
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to serialize table metadata",
+                "TableMetadataSerializationError",
+                Some(Box::new(e)),
+            )
+            .into()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn check(
+        previous: u64,
+        value: u64,
+        warning: Option<u64>,
+        limit: Option<u64>,
+    ) -> Result<Vec<TableLimitWarning>> {
+        let mut warnings = vec![];
+        check_metric(
+            TableLimitMetric::SnapshotCount,
+            &TableIdent::from_strs(["ns", "t"]).unwrap(),
+            || Ok(previous),
+            value,
+            warning,
+            limit,
+            &mut warnings,
+        )?;
+        Ok(warnings)
+    }
+
+    #[test]
+    fn test_limit_rejects_growing_tables() {
+        let err = check(100, 101, None, Some(100)).unwrap_err();
+        assert_eq!(err.error.r#type, "TableLimitExceeded");
+        // Tables above the limit can still shrink or keep their size
+        check(200, 150, None, Some(100)).unwrap();
+        check(200, 200, None, Some(100)).unwrap();
+        assert!(check(50, 100, None, Some(100)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_warning_threshold() {
+        let warnings = check(79, 80, Some(80), Some(100)).unwrap();
+        assert_eq!(
+            warnings,
+            vec![TableLimitWarning {
+                metric: TableLimitMetric::SnapshotCount,
+                value: 80,
+                threshold: 80,
+                crossed: true,
+            }]
+        );
+        let warnings = check(85, 90, Some(80), Some(100)).unwrap();
+        assert!(!warnings[0].crossed);
+        assert!(check(10, 20, Some(80), None).unwrap().is_empty());
+    }
+}
@@ -1,11 +1,11 @@
-use super::commit_tables::apply_commit;
-use super::io::delete_file;
+use super::commit_tables::{apply_commit, failed_requirements};
+use super::io::{delete_file, IoError};
 use super::namespace::authorized_namespace_ident_to_id;
+use super::snapshot_summary::enrich_snapshot_summaries;
+use super::table_limits::check_table_limits;
 use super::{
-    io::{read_metadata_file, write_metadata_file},
-    maybe_get_secret,
-    namespace::validate_namespace_ident,
-    require_warehouse_id, CatalogServer,
+    io::read_metadata_file, maybe_get_secret, maybe_get_secret_for_table_config,
+    namespace::validate_namespace_ident, require_warehouse_id, CatalogServer,
 };
 use crate::api::iceberg::types::DropParams;
 use crate::api::iceberg::v1::{
@@ -18,35 +18,48 @@ use crate::api::management::v1::warehouse::TabularDeleteProfile;
 use crate::api::management::v1::TabularType;
 use crate::api::set_not_found_status_code;
 use crate::catalog::compression_codec::CompressionCodec;
-use crate::request_metadata::RequestMetadata;
-use crate::service::authz::{CatalogNamespaceAction, CatalogTableAction, CatalogWarehouseAction};
+use crate::catalog::schema_compatibility::schema_incompatibilities;
+use crate::config::{CommitLocationCheck, RegisterTableSchemaCheck};
+use crate::request_metadata::{engine_query_id_from_properties, RequestMetadata};
+use crate::service::authz::{
+    CatalogNamespaceAction, CatalogTableAction, CatalogViewAction, CatalogWarehouseAction,
+};
 use crate::service::contract_verification::{ContractVerification, ContractVerificationOutcome};
 use crate::service::event_publisher::{CloudEventsPublisher, EventMetadata};
+use crate::service::id_generation::{
+    external_id_may_not_change, generate_id, IdKind, IdRequest, PROPERTY_EXTERNAL_ID,
+};
+use crate::service::missing_idents::invalidate_missing_idents;
 use crate::service::storage::{
-    StorageLocations as _, StoragePermissions, StorageProfile, ValidationError,
+    tabular_location_strategy, MetadataNaming, StorageLocations as _, StoragePermissions,
+    StorageProfile, TabularLocationRequest, ValidationError,
 };
+use crate::service::tabular_access::record_tabular_access;
 use crate::service::task_queue::tabular_expiration_queue::TabularExpirationInput;
 use crate::service::task_queue::tabular_purge_queue::TabularPurgeInput;
-use crate::service::TabularIdentUuid;
+use crate::service::task_queue::TaskFilter;
 use crate::service::{
-    authz::Authorizer, secrets::SecretStore, Catalog, CreateTableResponse, ListFlags,
-    LoadTableResponse as CatalogLoadTableResult, State, TabularDetails, Transaction,
+    authz::Authorizer, secrets::SecretStore, Catalog, CreateTableResponse,
+    DeletedTabularAtLocation, ListFlags, LoadTableResponse as CatalogLoadTableResult, State,
+    TabularDetails, Transaction,
 };
 use crate::service::{
-    GetNamespaceResponse, TableCommit, TableCreation, TableIdentUuid, WarehouseStatus,
+    GetNamespaceResponse, GetWarehouseResponse, TableCommit, TableCreation, TableIdentUuid,
+    ViewDependent, ViewIdentUuid, WarehouseStatus,
 };
-use futures::FutureExt;
-use fxhash::FxHashSet;
+use crate::service::{TabularAccessKind, TabularIdentUuid};
+use futures::{FutureExt, TryStreamExt};
+use fxhash::{FxHashMap, FxHashSet};
 use std::collections::{HashMap, HashSet};
 use std::str::FromStr as _;
 
 use crate::catalog::tabular::list_entities;
 use crate::retry::retry_fn;
-use crate::{catalog, WarehouseIdent};
+use crate::{catalog, WarehouseIdent, CONFIG};
 use http::StatusCode;
 use iceberg::spec::{
-    FormatVersion, MetadataLog, SchemaId, SortOrder, TableMetadata, TableMetadataBuildResult,
-    TableMetadataBuilder, UnboundPartitionSpec, PROPERTY_FORMAT_VERSION,
+    FormatVersion, MetadataLog, Schema, SchemaId, SortOrder, TableMetadata,
+    TableMetadataBuildResult, TableMetadataBuilder, UnboundPartitionSpec, PROPERTY_FORMAT_VERSION,
     PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX,
 };
 use iceberg::{NamespaceIdent, TableUpdate};
@@ -60,6 +73,9 @@ use uuid::Uuid;
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED: &str =
     "write.metadata.delete-after-commit.enabled";
 const PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED_DEFAULT: bool = false;
+/// Table property that expires soft-deleted tabulars occupying the location of a new table.
+/// It is consumed on creation and not stored with the table.
+pub(crate) const PROPERTY_FORCE_EXPIRE_DELETED: &str = "lakekeeper.force-expire-deleted";
 
 #[async_trait::async_trait]
 impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
@@ -74,6 +90,9 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         request_metadata: RequestMetadata,
     ) -> Result<ListTablesResponse> {
         let return_uuids = query.return_uuids;
+        let return_total = query.return_total;
+        let return_access_times = query.return_access_times;
+        let return_descriptions = query.return_descriptions;
         // ------------------- VALIDATIONS -------------------
         let NamespaceParameters { namespace, prefix } = parameters;
         let warehouse_id = require_warehouse_id(prefix)?;
@@ -109,12 +128,62 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 &mut t,
             )
             .await?;
+        let total_count = if let Some(mode) = return_total {
+            let total = C::count_tabulars(
+                warehouse_id,
+                &namespace,
+                TabularType::Table,
+                crate::catalog::tabular::default_table_flags(),
+                mode,
+                t.transaction(),
+            )
+            .await?;
+            Some(
+                catalog::count_authorized::<_, _, _, C>(
+                    mode,
+                    total,
+                    list_entities!(
+                        Table,
+                        list_tables,
+                        table_action,
+                        namespace,
+                        authorizer,
+                        request_metadata,
+                        warehouse_id
+                    ),
+                    &mut t,
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
+        let table_uuids = table_uuids.into_iter().map(|u| *u).collect::<Vec<_>>();
+        let access_times = if return_access_times {
+            Some(
+                crate::catalog::tabular::fetch_access_times::<C>(&table_uuids, t.transaction())
+                    .await?,
+            )
+        } else {
+            None
+        };
+        let descriptions = if return_descriptions {
+            Some(
+                crate::catalog::tabular::fetch_descriptions::<C>(&table_uuids, t.transaction())
+                    .await?,
+            )
+        } else {
+            None
+        };
         t.commit().await?;
 
         Ok(ListTablesResponse {
             next_page_token,
             identifiers,
-            table_uuids: return_uuids.then_some(table_uuids.into_iter().map(|u| *u).collect()),
+            table_uuids: return_uuids.then_some(table_uuids),
+            total_count,
+            access_times,
+            descriptions,
         })
     }
 
@@ -134,6 +203,22 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let table = TableIdent::new(namespace.clone(), request.name.clone());
         validate_table_or_view_ident(&table)?;
 
+        let force_expire_deleted = request
+            .properties
+            .as_mut()
+            .and_then(|properties| properties.remove(PROPERTY_FORCE_EXPIRE_DELETED))
+            .map(|value| {
+                bool::parse_value(&value).map_err(|e| {
+                    ErrorModel::bad_request(
+                        format!("Property `{PROPERTY_FORCE_EXPIRE_DELETED}` must be a boolean"),
+                        "InvalidTableProperty",
+                        Some(Box::new(e)),
+                    )
+                })
+            })
+            .transpose()?
+            .unwrap_or(false);
+
         if let Some(properties) = &request.properties {
             validate_table_properties(properties.keys())?;
         }
@@ -152,7 +237,15 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         .await?;
 
         // ------------------- BUSINESS LOGIC -------------------
-        let id = Uuid::now_v7();
+        let id = generate_id(IdRequest {
+            kind: IdKind::Table,
+            name: &request.name,
+            external_id: request
+                .properties
+                .as_ref()
+                .and_then(|properties| properties.get(PROPERTY_EXTERNAL_ID))
+                .map(String::as_str),
+        });
         let tabular_id = TabularIdentUuid::Table(id);
         let table_id = TableIdentUuid::from(id);
 
@@ -165,9 +258,23 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             &namespace,
             request.location.clone(),
             tabular_id,
+            &request.name,
             storage_profile,
         )?;
 
+        let force_expired = if force_expire_deleted {
+            force_expire_deleted_tabulars(
+                &warehouse,
+                &table_location,
+                &state,
+                &request_metadata,
+                &mut t,
+            )
+            .await?
+        } else {
+            vec![]
+        };
+
         // Update the request for event
         request.location = Some(table_location.to_string());
         let request = request; // Make it non-mutable again for our sanity
@@ -190,6 +297,14 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         let table_metadata = create_table_request_into_table_metadata(table_id, request)?;
 
+        state
+            .v1_state
+            .contract_verifiers
+            .for_warehouse(&warehouse.wasm_hooks)?
+            .check_table_create(&table, &table_metadata)
+            .await?
+            .into_result()?;
+
         let CreateTableResponse {
             table_metadata,
             staged_table_id,
@@ -199,6 +314,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 table_ident: &table,
                 table_metadata,
                 metadata_location: metadata_location.as_ref(),
+                force_expire_property: Some(PROPERTY_FORCE_EXPIRE_DELETED),
             },
             t.transaction(),
         )
@@ -248,13 +364,16 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         if let Some(metadata_location) = &metadata_location {
             let compression_codec = CompressionCodec::try_from_metadata(&table_metadata)?;
-            write_metadata_file(
-                metadata_location,
-                &table_metadata,
-                compression_codec,
-                &file_io,
-            )
-            .await?;
+            storage_profile
+                .write_metadata_file(
+                    &file_io,
+                    storage_secret.as_ref(),
+                    Some(warehouse_id),
+                    metadata_location,
+                    &table_metadata,
+                    compression_codec,
+                )
+                .await?;
         };
 
         // This requires the storage secret
@@ -266,6 +385,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 storage_secret.as_ref(),
                 &table_location,
                 StoragePermissions::ReadWriteDelete,
+                request_metadata.actor(),
             )
             .await?;
 
@@ -276,10 +396,15 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             }]
         });
 
+        let mut table_config = config.config.into();
+        CONFIG
+            .engine_profiles
+            .apply(request_metadata.engine(), &mut table_config);
+
         let load_table_result = LoadTableResult {
             metadata_location: metadata_location.map(|l| l.to_string()),
             metadata: table_metadata,
-            config: Some(config.config.into()),
+            config: Some(table_config),
             storage_credentials,
         };
 
@@ -293,6 +418,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         // Metadata file written, now we can commit the transaction
         t.commit().await?;
+        invalidate_missing_idents(warehouse_id);
+        finish_force_expire(&force_expired, &state, &request_metadata).await;
 
         // If a staged table was overwritten, delete it from authorizer
         if let Some(staged_table_id) = staged_table_id {
@@ -309,6 +436,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 num_events: 1,
                 sequence_number: 0,
                 trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
             },
             body,
             "createTable",
@@ -362,9 +491,32 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         validate_table_properties(table_metadata.properties().keys())?;
         storage_profile.require_allowed_location(&table_location)?;
 
+        state
+            .v1_state
+            .contract_verifiers
+            .for_warehouse(&warehouse.wasm_hooks)?
+            .check_table_create(&table, &table_metadata)
+            .await?
+            .into_result()?;
+
         let namespace = C::get_namespace(warehouse_id, namespace_id, t.transaction()).await?;
         let tabular_id = TableIdentUuid::from(table_metadata.uuid());
 
+        if CONFIG.register_table_schema_check != RegisterTableSchemaCheck::Off {
+            if let Some(previous_schema) =
+                C::get_replaced_table_schema(namespace.namespace_id, &table.name, t.transaction())
+                    .await?
+            {
+                check_register_table_schema(
+                    CONFIG.register_table_schema_check,
+                    &table,
+                    &previous_schema,
+                    table_metadata.current_schema(),
+                    &request_metadata,
+                )?;
+            }
+        }
+
         let CreateTableResponse {
             table_metadata,
             staged_table_id,
@@ -374,6 +526,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 table_ident: &table,
                 table_metadata,
                 metadata_location: Some(&metadata_location),
+                force_expire_property: None,
             },
             t.transaction(),
         )
@@ -388,6 +541,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 storage_secret.as_ref(),
                 &table_location,
                 StoragePermissions::ReadWriteDelete,
+                request_metadata.actor(),
             )
             .await?;
 
@@ -396,6 +550,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             .await?;
 
         t.commit().await?;
+        invalidate_missing_idents(warehouse_id);
 
         // If a staged table was overwritten, delete it from authorizer
         if let Some(staged_table_id) = staged_table_id {
@@ -413,6 +568,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 num_events: 1,
                 sequence_number: 0,
                 trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
             },
             maybe_body_to_json(&request),
             "registerTable",
@@ -420,10 +577,15 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         )
         .await;
 
+        let mut table_config = config.config.into();
+        CONFIG
+            .engine_profiles
+            .apply(request_metadata.engine(), &mut table_config);
+
         Ok(LoadTableResult {
             metadata_location: Some(metadata_location.to_string()),
             metadata: table_metadata,
-            config: Some(config.config.into()),
+            config: Some(table_config),
             storage_credentials: None,
         })
     }
@@ -458,7 +620,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         // ------------------- AUTHZ -------------------
         let authorizer = state.v1_state.authz;
         let catalog = state.v1_state.catalog;
-        let mut t = C::Transaction::begin_read(catalog).await?;
+        let mut t = C::Transaction::begin_read(catalog.clone()).await?;
 
         let (table_id, storage_permissions) = Self::resolve_and_authorize_table_access(
             &request_metadata,
@@ -488,15 +650,19 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             storage_profile,
         } = remove_table(&table_id.ident, &table, &mut metadatas)?;
         require_not_staged(metadata_location.as_ref())?;
+        record_tabular_access::<C>(table_id.ident.into(), TabularAccessKind::Read, catalog);
 
         let table_location =
             parse_location(table_metadata.location(), StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        // ToDo: This is a small inefficiency: We fetch the secret even if it might
-        // not be required based on the `data_access` parameter.
         let storage_config = if let Some(storage_permissions) = storage_permissions {
-            let storage_secret =
-                maybe_get_secret(storage_secret_ident, &state.v1_state.secrets).await?;
+            let storage_secret = maybe_get_secret_for_table_config(
+                storage_secret_ident,
+                &storage_profile,
+                &data_access,
+                &state.v1_state.secrets,
+            )
+            .await?;
             Some(
                 storage_profile
                     .generate_table_config(
@@ -504,6 +670,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                         storage_secret.as_ref(),
                         &table_location,
                         storage_permissions,
+                        request_metadata.actor(),
                     )
                     .await?,
             )
@@ -523,7 +690,13 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         let load_table_result = LoadTableResult {
             metadata_location: metadata_location.as_ref().map(ToString::to_string),
             metadata: table_metadata,
-            config: storage_config.map(|c| c.config.into()),
+            config: storage_config.map(|c| {
+                let mut table_config = c.config.into();
+                CONFIG
+                    .engine_profiles
+                    .apply(request_metadata.engine(), &mut table_config);
+                table_config
+            }),
             storage_credentials,
         };
 
@@ -562,8 +735,13 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
 
         let (storage_secret_ident, storage_profile) =
             C::load_storage_profile(warehouse_id, table_id.ident, t.transaction()).await?;
-        let storage_secret =
-            maybe_get_secret(storage_secret_ident, &state.v1_state.secrets).await?;
+        let storage_secret = maybe_get_secret_for_table_config(
+            storage_secret_ident,
+            &storage_profile,
+            &data_access,
+            &state.v1_state.secrets,
+        )
+        .await?;
         let storage_config = storage_profile
             .generate_table_config(
                 &data_access,
@@ -573,6 +751,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                     StatusCode::INTERNAL_SERVER_ERROR,
                 )?,
                 storage_permission,
+                request_metadata.actor(),
             )
             .await?;
 
@@ -631,7 +810,10 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     /// Drop a table from the catalog
     async fn drop_table(
         parameters: TableParameters,
-        DropParams { purge_requested }: DropParams,
+        DropParams {
+            purge_requested,
+            force,
+        }: DropParams,
         state: ApiContext<State<A, C, S>>,
         request_metadata: RequestMetadata,
     ) -> Result<()> {
@@ -679,10 +861,44 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         state
             .v1_state
             .contract_verifiers
+            .for_warehouse(&warehouse.wasm_hooks)?
             .check_drop(TabularIdentUuid::Table(*table_id))
             .await?
             .into_result()?;
 
+        let dependents = C::list_view_dependents(warehouse_id, table_id, t.transaction()).await?;
+        if !dependents.is_empty() {
+            let views =
+                visible_dependent_views(&authorizer, &request_metadata, &dependents).await?;
+            if !force.unwrap_or(false) {
+                return Err(ErrorModel::conflict(
+                    format!(
+                        "Table is in use by {} view(s). Drop the views first or drop the table with `force=true`.",
+                        dependents.len()
+                    ),
+                    "TableInUseByViews",
+                    None,
+                )
+                .append_details(views)
+                .into());
+            }
+            authorizer
+                .require_table_action(
+                    &request_metadata,
+                    Ok(Some(table_id)),
+                    &CatalogTableAction::CanForceDrop,
+                )
+                .await?;
+            tracing::warn!(
+                "Force dropping table {table_id} that {} view(s) depend on",
+                dependents.len()
+            );
+            request_metadata.add_warning(format!(
+                "Views depending on the dropped table can no longer be queried: {}",
+                views.join(", ")
+            ));
+        }
+
         match warehouse.tabular_delete_profile {
             TabularDeleteProfile::Hard {} => {
                 let location = C::drop_table(table_id, t.transaction()).await?;
@@ -740,6 +956,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 num_events: 1,
                 sequence_number: 0,
                 trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
             },
             serde_json::Value::Null,
             "dropTable",
@@ -821,14 +1039,25 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         )
         .await?;
 
-        let namespace_id =
-            C::namespace_to_id(warehouse_id, &source.namespace, t.transaction()).await; // We can't fail before AuthZ
+        // Moving the table to another namespace removes it from the source namespace,
+        // so we need to be allowed to drop it there.
+        if source.namespace != destination.namespace {
+            authorizer
+                .require_table_action(
+                    &request_metadata,
+                    Ok(Some(source_table_id)),
+                    &CatalogTableAction::CanDrop,
+                )
+                .await?;
+        }
 
-        // We need to be allowed to delete the old table and create the new one
+        // We need to be allowed to create the new table in the destination namespace
+        let destination_namespace_id =
+            C::namespace_to_id(warehouse_id, &destination.namespace, t.transaction()).await; // We can't fail before AuthZ
         authorizer
             .require_namespace_action(
                 &request_metadata,
-                namespace_id,
+                destination_namespace_id,
                 &CatalogNamespaceAction::CanCreateTable,
             )
             .await?;
@@ -847,14 +1076,17 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         )
         .await?;
 
+        let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
         state
             .v1_state
             .contract_verifiers
+            .for_warehouse(&warehouse.wasm_hooks)?
             .check_rename(TabularIdentUuid::Table(*source_table_id), &destination)
             .await?
             .into_result()?;
 
         t.commit().await?;
+        invalidate_missing_idents(warehouse_id);
 
         emit_change_event(
             EventMetadata {
@@ -866,6 +1098,8 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 num_events: 1,
                 sequence_number: 0,
                 trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
             },
             body,
             "renameTable",
@@ -943,7 +1177,7 @@ impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> CatalogServer<C, A, S> {
 }
 
 #[allow(clippy::too_many_lines)]
-async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+pub(crate) async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
     prefix: Option<Prefix>,
     request: CommitTransactionRequest,
     state: ApiContext<State<A, C, S>>,
@@ -951,6 +1185,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
 ) -> Result<Vec<CommitContext>> {
     // ------------------- VALIDATIONS -------------------
     let warehouse_id = require_warehouse_id(prefix.clone())?;
+    validate_commit_limits(&request.table_changes)?;
     for change in &request.table_changes {
         validate_table_updates(&change.updates)?;
         change
@@ -1041,12 +1276,17 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     // Store data for events before it is moved
     let mut events = vec![];
     let mut event_table_ids: Vec<(TableIdent, TableIdentUuid)> = vec![];
+    let mut event_query_ids = vec![];
     let mut updates = vec![];
     for commit_table_request in &request.table_changes {
         if let Some(id) = &commit_table_request.identifier {
             if let Some(uuid) = table_ids.get(id) {
                 events.push(maybe_body_to_json(commit_table_request));
                 event_table_ids.push((id.clone(), *uuid));
+                event_query_ids.push(
+                    engine_query_id_of_commit(&commit_table_request.updates)
+                        .or_else(|| request_metadata.engine_query_id.clone()),
+                );
                 updates.push(commit_table_request.updates.clone());
             }
         }
@@ -1061,13 +1301,60 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     )
     .await?;
 
+    let commits_staged_table = previous_metadatas
+        .values()
+        .any(|table| table.metadata_location.is_none());
+
+    // Check the requirements of all tables before any update is applied, so that a
+    // failing transaction reports every conflicting table instead of only the first one.
+    let requirement_failures = request
+        .table_changes
+        .iter()
+        .filter_map(|change| {
+            let table_ident = change.identifier.as_ref()?;
+            let previous_table = previous_metadatas.get(table_ids.get(table_ident)?)?;
+            let failures = failed_requirements(
+                &previous_table.table_metadata,
+                previous_table.metadata_location.as_ref(),
+                &change.requirements,
+            );
+            (!failures.is_empty()).then_some((table_ident, failures))
+        })
+        .collect_vec();
+    if !requirement_failures.is_empty() {
+        let failed_tables = requirement_failures
+            .iter()
+            .map(|(ident, _)| format!("{}.{}", ident.namespace.to_url_string(), ident.name))
+            .join(", ");
+        let details = requirement_failures
+            .iter()
+            .flat_map(|(ident, failures)| {
+                failures.iter().map(move |failure| {
+                    format!(
+                        "{}.{}: {failure}",
+                        ident.namespace.to_url_string(),
+                        ident.name
+                    )
+                })
+            })
+            .collect_vec();
+        return Err(ErrorModel::conflict(
+            format!("Requirements failed for tables: {failed_tables}"),
+            "CommitRequirementsFailed",
+            None,
+        )
+        .append_details(details)
+        .into());
+    }
+
     let mut expired_metadata_logs: Vec<MetadataLog> = vec![];
+    let mut crossed_table_limits = vec![];
 
     // Apply changes
     let commits = request
         .table_changes
         .into_iter()
-        .map(|change| {
+        .map(|mut change| {
             let table_ident = change.identifier.ok_or_else(||
                     // This should never happen due to validation
                     ErrorModel::internal(
@@ -1077,6 +1364,11 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                     ))?;
             let table_id = require_table_id(&table_ident, table_ids.get(&table_ident).copied())?;
             let previous_table = remove_table(&table_id, &table_ident, &mut previous_metadatas)?;
+            enrich_snapshot_summaries(
+                warehouse.snapshot_summary_enrichment,
+                &request_metadata,
+                &mut change.updates,
+            )?;
             let TableMetadataBuildResult {
                 metadata: new_metadata,
                 changes: _,
@@ -1088,6 +1380,18 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 change.updates.clone(),
             )?;
 
+            for warning in check_table_limits(
+                &warehouse.table_limits,
+                &table_ident,
+                &previous_table.table_metadata,
+                &new_metadata,
+            )? {
+                request_metadata.add_warning(warning.message(&table_ident));
+                if warning.crossed {
+                    crossed_table_limits.push((table_ident.clone(), table_id, warning));
+                }
+            }
+
             let number_expired_metadata_log_entries = this_expired.len();
 
             if get_delete_after_commit_enabled(new_metadata.properties()) {
@@ -1099,7 +1403,12 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
             let next_metadata_count = previous_table
                 .metadata_location
                 .as_ref()
-                .and_then(extract_count_from_metadata_location)
+                .and_then(|location| {
+                    extract_count_from_metadata_location(
+                        location,
+                        previous_table.storage_profile.metadata_naming(),
+                    )
+                })
                 .map_or(0, |v| v + 1);
 
             let new_table_location =
@@ -1112,6 +1421,14 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 next_metadata_count,
             );
 
+            check_commit_locations(
+                CONFIG.commit_location_check,
+                &previous_table.storage_profile,
+                &new_table_location,
+                &new_metadata_location,
+                &change.updates,
+            )?;
+
             let number_added_metadata_log_entries = (new_metadata.metadata_log().len()
                 + number_expired_metadata_log_entries)
                 .saturating_sub(previous_table.table_metadata.metadata_log().len());
@@ -1122,6 +1439,7 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 new_compression_codec,
                 updates: change.updates,
                 previous_metadata: previous_table.table_metadata,
+                previous_metadata_location: previous_table.metadata_location,
                 number_expired_metadata_log_entries,
                 number_added_metadata_log_entries,
             })
@@ -1137,12 +1455,13 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     .await?;
 
     // Check contract verification
-    let futures = commits.iter().map(|c| {
-        state
-            .v1_state
-            .contract_verifiers
-            .check_table_updates(&c.updates, &c.previous_metadata)
-    });
+    let contract_verifiers = state
+        .v1_state
+        .contract_verifiers
+        .for_warehouse(&warehouse.wasm_hooks)?;
+    let futures = commits
+        .iter()
+        .map(|c| contract_verifiers.check_table_updates(&c.updates, &c.previous_metadata));
 
     futures::future::try_join_all(futures)
         .await?
@@ -1160,17 +1479,23 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
     let write_futures: Vec<_> = commits
         .iter()
         .map(|commit| {
-            write_metadata_file(
+            warehouse.storage_profile.write_metadata_file(
+                &file_io,
+                storage_secret.as_ref(),
+                Some(warehouse_id),
                 &commit.new_metadata_location,
                 &commit.new_metadata,
                 commit.new_compression_codec,
-                &file_io,
             )
         })
         .collect();
     futures::future::try_join_all(write_futures).await?;
 
     transaction.commit().await?;
+    // Committing a staged table makes it visible to lookups.
+    if commits_staged_table {
+        invalidate_missing_idents(warehouse_id);
+    }
 
     // Delete files in parallel - if one delete fails, we still want to delete the rest
     let expired_locations = expired_metadata_logs
@@ -1200,11 +1525,54 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
             .ok()
     });
 
+    for commit in &commits {
+        state
+            .v1_state
+            .queues
+            .queue_compaction_if_needed(warehouse_id, &commit.new_metadata)
+            .await;
+    }
+
+    let number_of_limit_events = crossed_table_limits.len();
+    for (event_sequence_number, (table_ident, table_id, warning)) in
+        crossed_table_limits.into_iter().enumerate()
+    {
+        emit_change_event(
+            EventMetadata {
+                tabular_id: TabularIdentUuid::Table(*table_id),
+                warehouse_id,
+                name: table_ident.name,
+                namespace: table_ident.namespace.to_url_string(),
+                prefix: prefix
+                    .clone()
+                    .map(|p| p.as_str().to_string())
+                    .unwrap_or_default(),
+                num_events: number_of_limit_events,
+                sequence_number: event_sequence_number,
+                trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
+            },
+            serde_json::to_value(&warning).unwrap_or_default(),
+            "tableLimitWarning",
+            state.v1_state.publisher.clone(),
+        )
+        .await;
+    }
+
     let number_of_events = events.len();
 
-    for (event_sequence_number, (body, (table_ident, table_id))) in
-        events.into_iter().zip(event_table_ids).enumerate()
+    for (event_sequence_number, ((body, (table_ident, table_id)), engine_query_id)) in events
+        .into_iter()
+        .zip(event_table_ids)
+        .zip(event_query_ids)
+        .enumerate()
     {
+        record_tabular_access::<C>(
+            TabularIdentUuid::Table(*table_id),
+            TabularAccessKind::Commit,
+            state.v1_state.catalog.clone(),
+        );
         emit_change_event(
             EventMetadata {
                 tabular_id: TabularIdentUuid::Table(*table_id),
@@ -1218,6 +1586,8 @@ async fn commit_tables_internal<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 num_events: number_of_events,
                 sequence_number: event_sequence_number,
                 trace_id: request_metadata.request_id,
+                engine_query_id,
+                client_ip: request_metadata.client_ip,
             },
             body,
             "updateTable",
@@ -1247,7 +1617,13 @@ pub(crate) async fn authorized_table_ident_to_id<C: Catalog, A: Authorizer>(
         .map_err(set_not_found_status_code)
 }
 
-pub(crate) fn extract_count_from_metadata_location(location: &Location) -> Option<usize> {
+/// Number of metadata files written before `location`. Names of the default and the
+/// sequential strategy are recognized as well, so that counting continues after the
+/// naming of a warehouse was changed.
+pub(crate) fn extract_count_from_metadata_location(
+    location: &Location,
+    naming: &MetadataNaming,
+) -> Option<usize> {
     let last_segment = location
         .as_str()
         .trim_end_matches('/')
@@ -1255,20 +1631,16 @@ pub(crate) fn extract_count_from_metadata_location(location: &Location) -> Optio
         .last()
         .unwrap_or(location.as_str());
 
-    if let Some((_whole, version, _metadata_id)) = lazy_regex::regex_captures!(
-        r"^(\d+)-([\w-]{36})(?:\.\w+)?\.metadata\.json",
-        last_segment
-    ) {
-        version.parse().ok()
-    } else {
-        None
-    }
+    [naming, &MetadataNaming::Uuid, &MetadataNaming::Sequential]
+        .into_iter()
+        .find_map(|naming| naming.count_from_file_name(last_segment))
 }
 
-struct CommitContext {
+pub(crate) struct CommitContext {
     pub new_metadata: iceberg::spec::TableMetadata,
     pub new_metadata_location: Location,
     pub previous_metadata: iceberg::spec::TableMetadata,
+    pub previous_metadata_location: Option<Location>,
     pub updates: Vec<TableUpdate>,
     pub new_compression_codec: CompressionCodec,
     pub number_expired_metadata_log_entries: usize,
@@ -1276,7 +1648,7 @@ struct CommitContext {
 }
 
 impl CommitContext {
-    fn commit(&self) -> TableCommit {
+    pub(crate) fn commit(&self) -> TableCommit {
         let diffs = calculate_diffs(
             &self.new_metadata,
             &self.previous_metadata,
@@ -1288,6 +1660,7 @@ impl CommitContext {
             diffs,
             new_metadata: self.new_metadata.clone(),
             new_metadata_location: self.new_metadata_location.clone(),
+            previous_metadata_location: self.previous_metadata_location.clone(),
             updates: self.updates.clone(),
         }
     }
@@ -1387,39 +1760,22 @@ fn calculate_diffs(
             .saturating_sub(usize::from(head_of_snapshot_log_changed)),
     );
 
-    let old_stats = previous_metadata
-        .statistics_iter()
-        .map(|s| s.snapshot_id)
-        .collect::<FxHashSet<_>>();
-    let new_stats = new_metadata
-        .statistics_iter()
-        .map(|s| s.snapshot_id)
-        .collect::<FxHashSet<_>>();
-    let removed_stats = old_stats
-        .difference(&new_stats)
-        .copied()
-        .collect::<Vec<_>>();
-    let added_stats = new_stats
-        .difference(&old_stats)
-        .copied()
-        .collect::<Vec<_>>();
-
-    let old_partition_stats = previous_metadata
-        .partition_statistics_iter()
-        .map(|s| s.snapshot_id)
-        .collect::<FxHashSet<_>>();
-    let new_partition_stats = new_metadata
-        .partition_statistics_iter()
-        .map(|s| s.snapshot_id)
-        .collect::<FxHashSet<_>>();
-    let removed_partition_stats = old_partition_stats
-        .difference(&new_partition_stats)
-        .copied()
-        .collect::<Vec<_>>();
-    let added_partition_stats = new_partition_stats
-        .difference(&old_partition_stats)
-        .copied()
-        .collect::<Vec<_>>();
+    // `set-statistics` may replace the statistics of a snapshot that already has some,
+    // so statistics are compared by content, not only by snapshot.
+    let (added_stats, removed_stats) = diff_by_snapshot(
+        previous_metadata
+            .statistics_iter()
+            .map(|s| (s.snapshot_id, s)),
+        new_metadata.statistics_iter().map(|s| (s.snapshot_id, s)),
+    );
+    let (added_partition_stats, removed_partition_stats) = diff_by_snapshot(
+        previous_metadata
+            .partition_statistics_iter()
+            .map(|s| (s.snapshot_id, s)),
+        new_metadata
+            .partition_statistics_iter()
+            .map(|s| (s.snapshot_id, s)),
+    );
 
     TableMetadataDiffs {
         removed_snapshots: removed_snaps,
@@ -1444,6 +1800,26 @@ fn calculate_diffs(
     }
 }
 
+/// Snapshots whose statistics were added or changed, and snapshots whose statistics were removed.
+fn diff_by_snapshot<'a, T: PartialEq + 'a>(
+    old: impl Iterator<Item = (i64, &'a T)>,
+    new: impl Iterator<Item = (i64, &'a T)>,
+) -> (Vec<i64>, Vec<i64>) {
+    let old = old.collect::<FxHashMap<_, _>>();
+    let new = new.collect::<FxHashMap<_, _>>();
+    let upserted = new
+        .iter()
+        .filter(|(snapshot_id, stats)| old.get(*snapshot_id) != Some(*stats))
+        .map(|(snapshot_id, _)| *snapshot_id)
+        .collect();
+    let removed = old
+        .keys()
+        .filter(|snapshot_id| !new.contains_key(*snapshot_id))
+        .copied()
+        .collect();
+    (upserted, removed)
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct TableMetadataDiffs {
     pub(crate) removed_snapshots: Vec<i64>,
@@ -1520,6 +1896,7 @@ pub(super) fn determine_tabular_location(
     namespace: &GetNamespaceResponse,
     request_table_location: Option<String>,
     table_id: TabularIdentUuid,
+    name: &str,
     storage_profile: &StorageProfile,
 ) -> Result<Location> {
     let request_table_location = request_table_location
@@ -1547,7 +1924,11 @@ pub(super) fn determine_tabular_location(
                 })?,
         };
 
-        storage_profile.default_tabular_location(&namespace_location, table_id)
+        tabular_location_strategy().tabular_location(TabularLocationRequest {
+            namespace_location: &namespace_location,
+            tabular_id: table_id,
+            name,
+        })
     };
     // all locations are without a trailing slash
     location.without_trailing_slash();
@@ -1618,6 +1999,113 @@ pub(crate) fn require_active_warehouse(status: WarehouseStatus) -> Result<()> {
     Ok(())
 }
 
+/// Id of the engine query or job that created the snapshots added by a commit, as
+/// recorded by the engine in the snapshot summary.
+fn engine_query_id_of_commit(updates: &[TableUpdate]) -> Option<String> {
+    updates.iter().find_map(|update| match update {
+        TableUpdate::AddSnapshot { snapshot } => {
+            engine_query_id_from_properties(&snapshot.summary().additional_properties)
+                .map(str::to_string)
+        }
+        _ => None,
+    })
+}
+
+/// Check that the metadata location and all files referenced by added snapshots and
+/// statistics lie within the table location and are allowed by the storage profile.
+/// Absolute paths into other buckets are accepted by most engines but break
+/// credential vending for readers of the table later on.
+fn check_commit_locations(
+    mode: CommitLocationCheck,
+    storage_profile: &StorageProfile,
+    table_location: &Location,
+    metadata_location: &Location,
+    updates: &[TableUpdate],
+) -> Result<()> {
+    if mode == CommitLocationCheck::Off {
+        return Ok(());
+    }
+
+    let is_allowed = |location: &Location| {
+        location.is_sublocation_of(table_location)
+            && storage_profile.require_allowed_location(location).is_ok()
+    };
+
+    let mut violations = Vec::new();
+    if !is_allowed(metadata_location) {
+        violations.push(metadata_location.to_string());
+    }
+    for path in updates.iter().filter_map(|update| match update {
+        TableUpdate::AddSnapshot { snapshot } => Some(snapshot.manifest_list()),
+        TableUpdate::SetStatistics { statistics } => Some(statistics.statistics_path.as_str()),
+        TableUpdate::SetPartitionStatistics {
+            partition_statistics,
+        } => Some(partition_statistics.statistics_path.as_str()),
+        _ => None,
+    }) {
+        if !Location::from_str(path).is_ok_and(|location| is_allowed(&location)) {
+            violations.push(path.to_string());
+        }
+    }
+
+    if violations.is_empty() {
+        return Ok(());
+    }
+
+    match mode {
+        CommitLocationCheck::Strict => Err(ErrorModel::bad_request(
+            format!(
+                "Commit references locations outside of the table location {table_location}: {}",
+                violations.join(", ")
+            ),
+            "LocationOutsideTable",
+            None,
+        )
+        .into()),
+        CommitLocationCheck::Warn | CommitLocationCheck::Off => {
+            tracing::warn!(
+                %table_location,
+                ?violations,
+                "Commit references locations outside of the table location"
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Compare the schema of a registered table with the schema of the staged or soft-deleted
+/// table it replaces. Registering a table over an old one with reused field ids of
+/// different types breaks readers that still hold on to the old table.
+fn check_register_table_schema(
+    mode: RegisterTableSchemaCheck,
+    table: &TableIdent,
+    previous: &Schema,
+    new: &Schema,
+    request_metadata: &RequestMetadata,
+) -> Result<()> {
+    let incompatibilities = schema_incompatibilities(previous, new);
+    if incompatibilities.is_empty() {
+        return Ok(());
+    }
+
+    let message = format!(
+        "Schema of table {}.{} is incompatible with the schema of the table it replaces: {}",
+        table.namespace.to_url_string(),
+        table.name,
+        incompatibilities.join("; ")
+    );
+    match mode {
+        RegisterTableSchemaCheck::Strict => {
+            Err(ErrorModel::conflict(message, "IncompatibleSchema", None).into())
+        }
+        RegisterTableSchemaCheck::Warn | RegisterTableSchemaCheck::Off => {
+            tracing::warn!(?incompatibilities, "{message}");
+            request_metadata.add_warning(message);
+            Ok(())
+        }
+    }
+}
+
 async fn emit_change_event(
     parameters: EventMetadata,
     body: serde_json::Value,
@@ -1636,9 +2124,11 @@ fn validate_table_updates(updates: &Vec<TableUpdate>) -> Result<()> {
         match update {
             TableUpdate::SetProperties { updates } => {
                 validate_table_properties(updates.keys())?;
+                external_id_may_not_change(updates.keys())?;
             }
             TableUpdate::RemoveProperties { removals } => {
                 validate_table_properties(removals)?;
+                external_id_may_not_change(removals)?;
             }
             _ => {}
         }
@@ -1646,6 +2136,64 @@ fn validate_table_updates(updates: &Vec<TableUpdate>) -> Result<()> {
     Ok(())
 }
 
+/// Reject commits exceeding the configured limits before any work is done.
+fn validate_commit_limits(table_changes: &[CommitTableRequest]) -> Result<()> {
+    let too_large = |message: String, r#type: &str| -> Result<()> {
+        Err(ErrorModel::new(
+            message,
+            r#type,
+            StatusCode::PAYLOAD_TOO_LARGE.as_u16(),
+            None,
+        )
+        .into())
+    };
+
+    if table_changes.len() > CONFIG.max_tables_per_commit {
+        return too_large(
+            format!(
+                "Commit changes {} tables, at most {} are allowed",
+                table_changes.len(),
+                CONFIG.max_tables_per_commit
+            ),
+            "TooManyTablesInCommit",
+        );
+    }
+
+    let n_updates = table_changes.iter().map(|c| c.updates.len()).sum::<usize>();
+    if n_updates > CONFIG.max_updates_per_commit {
+        return too_large(
+            format!(
+                "Commit contains {n_updates} updates, at most {} are allowed",
+                CONFIG.max_updates_per_commit
+            ),
+            "TooManyUpdatesInCommit",
+        );
+    }
+
+    for update in table_changes.iter().flat_map(|c| &c.updates) {
+        if let TableUpdate::AddSnapshot { snapshot } = update {
+            let summary_size = snapshot
+                .summary()
+                .additional_properties
+                .iter()
+                .map(|(k, v)| k.len() + v.len())
+                .sum::<usize>();
+            if summary_size > CONFIG.max_snapshot_summary_size_bytes {
+                return too_large(
+                    format!(
+                        "Summary of snapshot {} has {summary_size} bytes, at most {} are allowed",
+                        snapshot.snapshot_id(),
+                        CONFIG.max_snapshot_summary_size_bytes
+                    ),
+                    "SnapshotSummaryTooLarge",
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub(crate) fn get_delete_after_commit_enabled(properties: &HashMap<String, String>) -> bool {
     properties
         .get(PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED)
@@ -1654,49 +2202,255 @@ pub(crate) fn get_delete_after_commit_enabled(properties: &HashMap<String, Strin
         })
 }
 
-pub(crate) fn validate_table_properties<'a, I>(properties: I) -> Result<()>
-where
-    I: IntoIterator<Item = &'a String>,
-{
-    for prop in properties {
-        if (prop.starts_with("write.metadata")
-            && ![
-                PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX,
-                PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED,
-                "write.metadata.compression-codec",
-            ]
-            .contains(&prop.as_str()))
-            || prop.starts_with("write.data.path")
-        {
-            return Err(ErrorModel::conflict(
-                format!("Properties contain unsupported property: '{prop}'"),
-                "FailedToSetProperties",
-                None,
-            )
-            .into());
-        }
+/// Expire the soft-deleted tabulars that occupy `location` right away, so that a new
+/// table can be created there. Requires permission to drop each of them.
+///
+/// The tabulars are dropped in `transaction`, so they are only gone once the new table is
+/// committed. Files of tabulars dropped with purge are removed right away, so that the
+/// location is empty when the new table is created. Call [`finish_force_expire`] after
+/// the commit.
+async fn force_expire_deleted_tabulars<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    warehouse: &GetWarehouseResponse,
+    location: &Location,
+    state: &ApiContext<State<A, C, S>>,
+    request_metadata: &RequestMetadata,
+    transaction: &mut C::Transaction,
+) -> Result<Vec<DeletedTabularAtLocation>> {
+    let deleted =
+        C::list_deleted_tabulars_at_location(warehouse.id, location, transaction.transaction())
+            .await?;
+    if deleted.is_empty() {
+        return Ok(deleted);
     }
 
-    Ok(())
-}
-
-pub(crate) fn validate_table_or_view_ident(table: &TableIdent) -> Result<()> {
-    let TableIdent {
-        ref namespace,
-        ref name,
-    } = &table;
-    validate_namespace_ident(namespace)?;
-
-    if name.is_empty() {
-        return Err(ErrorModel::bad_request(
-            "name of the identifier cannot be empty",
-            "IdentifierNameEmpty",
+    if let Some(exempt) = deleted.iter().find(|tabular| tabular.expiration_exempt) {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Soft-deleted {} at the location is exempt from expiration and cannot be force-expired.",
+                exempt.tabular_id
+            ),
+            "TabularExpirationExempt",
             None,
         )
         .into());
     }
-    Ok(())
-}
+
+    let authorizer = &state.v1_state.authz;
+    for tabular in &deleted {
+        match tabular.tabular_id {
+            TabularIdentUuid::Table(id) => {
+                authorizer
+                    .require_table_action(
+                        request_metadata,
+                        Ok(Some(TableIdentUuid::from(id))),
+                        &CatalogTableAction::CanDrop,
+                    )
+                    .await?;
+            }
+            TabularIdentUuid::View(id) => {
+                authorizer
+                    .require_view_action(
+                        request_metadata,
+                        Ok(Some(ViewIdentUuid::from(id))),
+                        &CatalogViewAction::CanDrop,
+                    )
+                    .await?;
+            }
+        }
+    }
+
+    for tabular in &deleted {
+        match tabular.tabular_id {
+            TabularIdentUuid::Table(id) => {
+                C::drop_table(id.into(), transaction.transaction()).await?;
+            }
+            TabularIdentUuid::View(id) => {
+                C::drop_view(id.into(), transaction.transaction()).await?;
+            }
+        }
+    }
+
+    let storage_secret =
+        maybe_get_secret(warehouse.storage_secret_id, &state.v1_state.secrets).await?;
+    let file_io = warehouse.storage_profile.file_io(storage_secret.as_ref())?;
+    for tabular in deleted.iter().filter(|tabular| tabular.purge) {
+        let tabular_location = Location::from_str(&tabular.location).map_err(|e| {
+            ErrorModel::internal(
+                "Failed to parse location of soft-deleted tabular.",
+                "ParseError",
+                Some(Box::new(e)),
+            )
+        })?;
+        let purge_error = |e: IoError| {
+            ErrorModel::internal(
+                format!(
+                    "Failed to purge soft-deleted {} at location {tabular_location}.",
+                    tabular.tabular_id
+                ),
+                "FileIOError",
+                Some(Box::new(e)),
+            )
+        };
+        let mut pages = warehouse
+            .storage_profile
+            .purge_location(&file_io, storage_secret.as_ref(), &tabular_location, None)
+            .await
+            .map_err(purge_error)?;
+        while let Some(page) = pages.try_next().await.map_err(purge_error)? {
+            if !page.skipped_archived.is_empty() {
+                tracing::warn!(
+                    "Skipped {} archived objects while purging soft-deleted {} at location {tabular_location}",
+                    page.skipped_archived.len(),
+                    tabular.tabular_id
+                );
+            }
+        }
+    }
+
+    Ok(deleted)
+}
+
+/// Cancel the expirations of force-expired tabulars and remove them from the authorizer,
+/// once their drop is committed.
+async fn finish_force_expire<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
+    deleted: &[DeletedTabularAtLocation],
+    state: &ApiContext<State<A, C, S>>,
+    request_metadata: &RequestMetadata,
+) {
+    if deleted.is_empty() {
+        return;
+    }
+
+    // The expirations find the tabulars gone, cancelling them only saves the work.
+    if let Err(e) = state
+        .v1_state
+        .queues
+        .cancel_tabular_expiration(TaskFilter::TaskIds(
+            deleted
+                .iter()
+                .map(|tabular| tabular.expiration_task_id.into())
+                .collect(),
+        ))
+        .await
+    {
+        tracing::warn!(
+            "Failed to cancel expirations of force-expired tabulars: {}",
+            e.error
+        );
+    }
+
+    let authorizer = &state.v1_state.authz;
+    for tabular in deleted {
+        tracing::info!(
+            actor = ?request_metadata.actor(),
+            "Force-expired soft-deleted {} at location {}",
+            tabular.tabular_id,
+            tabular.location
+        );
+        let result = match tabular.tabular_id {
+            TabularIdentUuid::Table(id) => authorizer.delete_table(id.into()).await,
+            TabularIdentUuid::View(id) => authorizer.delete_view(id.into()).await,
+        };
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to remove expired {} from authorizer: {}",
+                tabular.tabular_id,
+                e.error
+            );
+        }
+    }
+}
+
+pub(crate) fn validate_table_properties<'a, I>(properties: I) -> Result<()>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    for prop in properties {
+        if (prop.starts_with("write.metadata")
+            && ![
+                PROPERTY_METADATA_PREVIOUS_VERSIONS_MAX,
+                PROPERTY_METADATA_DELETE_AFTER_COMMIT_ENABLED,
+                "write.metadata.compression-codec",
+            ]
+            .contains(&prop.as_str()))
+            || prop.starts_with("write.data.path")
+        {
+            return Err(ErrorModel::conflict(
+                format!("Properties contain unsupported property: '{prop}'"),
+                "FailedToSetProperties",
+                None,
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Dependent views of a table as `namespace.view`. Views the caller may not list are
+/// only counted, so that a failed drop does not reveal their names.
+async fn visible_dependent_views<A: Authorizer>(
+    authorizer: &A,
+    request_metadata: &RequestMetadata,
+    dependents: &[ViewDependent],
+) -> Result<Vec<String>> {
+    let allowed = futures::future::try_join_all(dependents.iter().map(|dependent| {
+        authorizer.is_allowed_view_action(
+            request_metadata,
+            dependent.view_id,
+            &CatalogViewAction::CanIncludeInList,
+        )
+    }))
+    .await?;
+
+    let mut views = dependents
+        .iter()
+        .zip(&allowed)
+        .filter(|(_, allowed)| **allowed)
+        .map(|(dependent, _)| {
+            format!(
+                "{}.{}",
+                dependent.view.namespace.to_url_string(),
+                dependent.view.name
+            )
+        })
+        .collect::<Vec<_>>();
+    let hidden = allowed.iter().filter(|allowed| !**allowed).count();
+    if hidden > 0 {
+        views.push(format!("{hidden} view(s) not visible to you"));
+    }
+    Ok(views)
+}
+
+pub(crate) fn validate_table_or_view_ident(table: &TableIdent) -> Result<()> {
+    let TableIdent {
+        ref namespace,
+        ref name,
+    } = &table;
+    validate_namespace_ident(namespace)?;
+
+    if name.is_empty() {
+        return Err(ErrorModel::bad_request(
+            "name of the identifier cannot be empty",
+            "IdentifierNameEmpty",
+            None,
+        )
+        .into());
+    }
+
+    if name.len() > CONFIG.max_tabular_name_length_bytes {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "name of the identifier cannot be longer than {} bytes",
+                CONFIG.max_tabular_name_length_bytes
+            ),
+            "IdentifierNameTooLong",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
 
 // This function does not return a result but serde_json::Value::Null if serialization
 // fails. This follows the rationale that we'll likely end up ignoring the error in the API handler
@@ -1780,7 +2534,7 @@ mod test {
     use crate::api::iceberg::types::{PageToken, Prefix};
     use crate::api::iceberg::v1::tables::TablesService as _;
     use crate::api::iceberg::v1::{
-        DataAccess, ListTablesQuery, NamespaceParameters, TableParameters,
+        DataAccess, ListTablesQuery, NamespaceParameters, ReturnTotal, TableParameters,
     };
     use crate::api::management::v1::warehouse::TabularDeleteProfile;
     use crate::api::ApiContext;
@@ -1789,6 +2543,7 @@ mod test {
     use crate::implementations::postgres::{PostgresCatalog, SecretsState};
     use crate::service::authz::implementations::openfga::tests::ObjectHidingMock;
     use crate::service::authz::AllowAllAuthorizer;
+    use crate::service::storage::MetadataNaming;
     use crate::service::{State, UserId};
 
     use http::StatusCode;
@@ -1806,8 +2561,11 @@ mod test {
     use std::collections::HashMap;
     use uuid::Uuid;
 
-    use crate::catalog::tables::validate_table_properties;
+    use crate::api::iceberg::types::DropParams;
+    use crate::api::iceberg::v1::views::Service as _;
+    use crate::catalog::tables::{validate_table_properties, PROPERTY_FORCE_EXPIRE_DELETED};
     use crate::catalog::test::impl_pagination_tests;
+    use crate::catalog::views::dependencies::PROPERTY_VIEW_DEPENDENCIES;
     use crate::service::authz::implementations::openfga::OpenFGAAuthorizer;
     use iceberg_ext::configs::Location;
     use std::str::FromStr;
@@ -1818,45 +2576,193 @@ mod test {
         assert!(validate_table_properties(properties.iter()).is_ok());
     }
 
+    #[test]
+    fn test_statistics_diff_by_snapshot() {
+        let stats = |path: &str| iceberg::spec::PartitionStatisticsFile {
+            snapshot_id: 0,
+            statistics_path: path.to_string(),
+            file_size_in_bytes: 1,
+        };
+        let (a, b, b2, c) = (stats("a"), stats("b"), stats("b2"), stats("c"));
+        let old = [(1, &a), (2, &b), (3, &c)];
+        let new = [(1, &a), (2, &b2), (4, &c)];
+
+        let (mut upserted, removed) = super::diff_by_snapshot(old.into_iter(), new.into_iter());
+        upserted.sort_unstable();
+        // Replacing the statistics of snapshot 2 must be persisted as well.
+        assert_eq!(upserted, vec![2, 4]);
+        assert_eq!(removed, vec![3]);
+    }
+
+    #[test]
+    fn test_commit_limits() {
+        let change = |updates| CommitTableRequest {
+            identifier: None,
+            requirements: vec![],
+            updates,
+        };
+        assert!(super::validate_commit_limits(&[change(vec![])]).is_ok());
+
+        let too_many_tables = (0..=crate::CONFIG.max_tables_per_commit)
+            .map(|_| change(vec![]))
+            .collect_vec();
+        let err = super::validate_commit_limits(&too_many_tables).unwrap_err();
+        assert_eq!(err.error.code, StatusCode::PAYLOAD_TOO_LARGE.as_u16());
+        assert_eq!(err.error.r#type, "TooManyTablesInCommit");
+
+        let large_summary = Snapshot::builder()
+            .with_snapshot_id(1)
+            .with_sequence_number(1)
+            .with_timestamp_ms(0)
+            .with_manifest_list("s3://bucket/snap-1.avro")
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::from_iter([(
+                    "large".to_string(),
+                    "x".repeat(crate::CONFIG.max_snapshot_summary_size_bytes),
+                )]),
+            })
+            .build();
+        let err =
+            super::validate_commit_limits(&[change(vec![iceberg::TableUpdate::AddSnapshot {
+                snapshot: large_summary,
+            }])])
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "SnapshotSummaryTooLarge");
+    }
+
+    #[test]
+    fn test_identifier_limits() {
+        let max_name = "x".repeat(crate::CONFIG.max_tabular_name_length_bytes);
+        let max_part = "x".repeat(crate::CONFIG.max_namespace_part_length_bytes);
+        let ident = |parts: &[&str]| TableIdent::from_strs(parts).unwrap();
+        assert!(super::validate_table_or_view_ident(&ident(&[&max_part, &max_name])).is_ok());
+
+        // Lengths are measured in bytes, not characters.
+        let too_long_name = "ä".repeat(crate::CONFIG.max_tabular_name_length_bytes / 2 + 1);
+        let err = super::validate_table_or_view_ident(&ident(&["ns", &too_long_name])).unwrap_err();
+        assert_eq!(err.error.r#type, "IdentifierNameTooLong");
+
+        let too_long_part = format!("{max_part}x");
+        let err =
+            super::validate_table_or_view_ident(&ident(&[&too_long_part, "tbl"])).unwrap_err();
+        assert_eq!(err.error.r#type, "NamespacePartTooLong");
+
+        let too_deep = vec!["ns"; crate::CONFIG.max_namespace_depth + 2];
+        let err = super::validate_table_or_view_ident(&ident(&too_deep)).unwrap_err();
+        assert_eq!(err.error.r#type, "NamespaceDepthExceeded");
+    }
+
+    #[test]
+    fn test_check_commit_locations() {
+        use crate::config::CommitLocationCheck;
+
+        let profile = crate::catalog::test::test_io_profile();
+        let table_location = profile.base_location().unwrap().cloning_push("table");
+        let metadata_location = table_location.cloning_push("metadata/00001.metadata.json");
+        let add_snapshot = |manifest_list: &str| iceberg::TableUpdate::AddSnapshot {
+            snapshot: Snapshot::builder()
+                .with_snapshot_id(1)
+                .with_sequence_number(1)
+                .with_timestamp_ms(0)
+                .with_manifest_list(manifest_list)
+                .with_summary(Summary {
+                    operation: Operation::Append,
+                    additional_properties: HashMap::new(),
+                })
+                .build(),
+        };
+        let check = |mode, metadata_location: &Location, updates: &[iceberg::TableUpdate]| {
+            super::check_commit_locations(
+                mode,
+                &profile,
+                &table_location,
+                metadata_location,
+                updates,
+            )
+        };
+
+        let inside = [add_snapshot(
+            table_location.cloning_push("metadata/snap-1.avro").as_str(),
+        )];
+        check(CommitLocationCheck::Strict, &metadata_location, &inside).unwrap();
+
+        let outside = [add_snapshot("s3://other-bucket/snap-1.avro")];
+        let err = check(CommitLocationCheck::Strict, &metadata_location, &outside).unwrap_err();
+        assert_eq!(err.error.r#type, "LocationOutsideTable");
+        check(CommitLocationCheck::Warn, &metadata_location, &outside).unwrap();
+        check(CommitLocationCheck::Off, &metadata_location, &outside).unwrap();
+
+        let relative = [add_snapshot("/snap-1.avro")];
+        check(CommitLocationCheck::Strict, &metadata_location, &relative).unwrap_err();
+
+        let foreign_metadata = Location::from_str("s3://other-bucket/00001.metadata.json").unwrap();
+        check(CommitLocationCheck::Strict, &foreign_metadata, &inside).unwrap_err();
+    }
+
     #[test]
     fn test_extract_count_from_metadata_location() {
         let location = Location::from_str("s3://path/to/table/metadata/00000-d0407fb2-1112-4944-bb88-c68ae697e2b4.gz.metadata.json").unwrap();
-        let count = super::extract_count_from_metadata_location(&location).unwrap();
+        let count =
+            super::extract_count_from_metadata_location(&location, &MetadataNaming::Uuid).unwrap();
         assert_eq!(count, 0);
 
         let location = Location::from_str("s3://path/to/table/metadata/00010-d0407fb2-1112-4944-bb88-c68ae697e2b4.gz.metadata.json").unwrap();
-        let count = super::extract_count_from_metadata_location(&location).unwrap();
+        let count =
+            super::extract_count_from_metadata_location(&location, &MetadataNaming::Uuid).unwrap();
         assert_eq!(count, 10);
 
         let location = Location::from_str(
             "s3://path/to/table/metadata/1-d0407fb2-1112-4944-bb88-c68ae697e2b4.gz.metadata.json",
         )
         .unwrap();
-        let count = super::extract_count_from_metadata_location(&location).unwrap();
+        let count =
+            super::extract_count_from_metadata_location(&location, &MetadataNaming::Uuid).unwrap();
         assert_eq!(count, 1);
 
         let location = Location::from_str(
             "s3://path/to/table/metadata/10000010-d0407fb2-1112-4944-bb88-c68ae697e2b4.gz.metadata.json",
         )
             .unwrap();
-        let count = super::extract_count_from_metadata_location(&location).unwrap();
+        let count =
+            super::extract_count_from_metadata_location(&location, &MetadataNaming::Uuid).unwrap();
         assert_eq!(count, 10_000_010);
 
         let location = Location::from_str(
             "s3://path/to/table/metadata/10000010-d0407fb2-1112-4944-bb88-c68ae697e2b4.metadata.json",
         )
             .unwrap();
-        let count = super::extract_count_from_metadata_location(&location).unwrap();
+        let count =
+            super::extract_count_from_metadata_location(&location, &MetadataNaming::Uuid).unwrap();
         assert_eq!(count, 10_000_010);
 
         let location = Location::from_str(
             "s3://path/to/table/metadata/d0407fb2-1112-4944-bb88-c68ae697e2b4.metadata.json",
         )
         .unwrap();
-        let count = super::extract_count_from_metadata_location(&location);
+        let count = super::extract_count_from_metadata_location(&location, &MetadataNaming::Uuid);
         assert!(count.is_none());
     }
 
+    #[test]
+    fn test_extract_count_from_metadata_location_after_naming_change() {
+        let naming = MetadataNaming::Template {
+            template: "{version}-snapshot".to_string(),
+        };
+        let location = Location::from_str("s3://path/to/table/metadata/v3.metadata.json").unwrap();
+        let count = super::extract_count_from_metadata_location(&location, &naming).unwrap();
+        assert_eq!(count, 2);
+
+        let location = Location::from_str("s3://path/to/table/metadata/00004-d0407fb2-1112-4944-bb88-c68ae697e2b4.gz.metadata.json").unwrap();
+        let count = super::extract_count_from_metadata_location(&location, &naming).unwrap();
+        assert_eq!(count, 4);
+
+        let location =
+            Location::from_str("s3://path/to/table/metadata/6-snapshot.gz.metadata.json").unwrap();
+        let count = super::extract_count_from_metadata_location(&location, &naming).unwrap();
+        assert_eq!(count, 5);
+    }
+
     fn create_request(table_name: Option<String>) -> CreateTableRequest {
         CreateTableRequest {
             name: table_name.unwrap_or("my_table".to_string()),
@@ -1947,6 +2853,66 @@ mod test {
         assert_eq!(tab.metadata, table_metadata.metadata);
     }
 
+    #[sqlx::test]
+    async fn test_commit_transaction_reports_all_failed_requirements(pool: sqlx::PgPool) {
+        let (ctx, ns, ns_params, table) = commit_test_setup(pool).await;
+        let _ = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request(Some("tab-2".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let change = |name: &str, uuid: Uuid| CommitTableRequest {
+            identifier: Some(TableIdent {
+                namespace: ns.namespace.clone(),
+                name: name.to_string(),
+            }),
+            requirements: vec![iceberg::TableRequirement::UuidMatch { uuid }],
+            updates: vec![iceberg::TableUpdate::SetProperties {
+                updates: HashMap::from([("p1".to_string(), "v1".to_string())]),
+            }],
+        };
+        let err = super::commit_tables_internal(
+            ns_params.prefix.clone(),
+            super::CommitTransactionRequest {
+                table_changes: vec![
+                    change("tab-1", table.metadata.uuid()),
+                    change("tab-2", Uuid::now_v7()),
+                ],
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT.as_u16());
+        assert_eq!(err.error.r#type, "CommitRequirementsFailed");
+        assert!(err.error.message.contains("ns1.tab-2"));
+        assert!(!err.error.message.contains("tab-1"));
+        assert!(err.error.stack.iter().any(|d| d.starts_with("ns1.tab-2: ")));
+
+        // No table may be changed if any requirement fails
+        let tab = CatalogServer::load_table(
+            TableParameters {
+                prefix: ns_params.prefix,
+                table: TableIdent {
+                    namespace: ns.namespace.clone(),
+                    name: "tab-1".to_string(),
+                },
+            },
+            DataAccess::none(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        assert!(!tab.metadata.properties().contains_key("p1"));
+    }
+
     fn schema() -> Schema {
         Schema::builder()
             .with_fields(vec![
@@ -2699,6 +3665,95 @@ mod test {
         assert_eq!(e.error.r#type.as_str(), "LocationAlreadyTaken");
     }
 
+    #[sqlx::test]
+    async fn test_force_expire_deleted_table_at_location(pool: PgPool) {
+        let prof = crate::catalog::test::test_io_profile();
+        let base_location = prof.base_location().unwrap().to_string();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            prof,
+            None,
+            AllowAllAuthorizer,
+            TabularDeleteProfile::Soft {
+                expiration_seconds: chrono::Duration::days(1),
+            },
+            None,
+        )
+        .await;
+        let ns = crate::catalog::test::create_ns(
+            ctx.clone(),
+            warehouse.warehouse_id.to_string(),
+            "ns1".to_string(),
+        )
+        .await;
+        let ns_params = NamespaceParameters {
+            prefix: Some(Prefix(warehouse.warehouse_id.to_string())),
+            namespace: ns.namespace.clone(),
+        };
+        let location = format!("{base_location}/{}/tab", Uuid::now_v7());
+        let mut create_request_1 = create_request(Some("tab-1".to_string()));
+        create_request_1.location = Some(location.clone());
+        CatalogServer::create_table(
+            ns_params.clone(),
+            create_request_1,
+            DataAccess::none(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        CatalogServer::drop_table(
+            TableParameters {
+                prefix: ns_params.prefix.clone(),
+                table: TableIdent::new(ns.namespace.clone(), "tab-1".to_string()),
+            },
+            DropParams {
+                purge_requested: Some(true),
+                force: None,
+            },
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let mut create_request_2 = create_request(Some("tab-2".to_string()));
+        create_request_2.location = Some(location.clone());
+        let e = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request_2.clone(),
+            DataAccess::none(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .expect_err("Location of soft-deleted table should still be taken");
+        assert_eq!(e.error.code, StatusCode::BAD_REQUEST, "{e:?}");
+        assert_eq!(e.error.r#type.as_str(), "LocationTakenBySoftDeletedTabular");
+        assert!(
+            e.error.message.contains(PROPERTY_FORCE_EXPIRE_DELETED),
+            "{e:?}"
+        );
+
+        create_request_2.properties = Some(HashMap::from([(
+            PROPERTY_FORCE_EXPIRE_DELETED.to_string(),
+            "true".to_string(),
+        )]));
+        let table = CatalogServer::create_table(
+            ns_params.clone(),
+            create_request_2,
+            DataAccess::none(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+        assert!(!table
+            .metadata
+            .properties()
+            .contains_key(PROPERTY_FORCE_EXPIRE_DELETED));
+    }
+
     #[sqlx::test]
     async fn test_cannot_create_staged_tables_at_sublocations_1(pool: PgPool) {
         let (ctx, _, ns_params, base_location) = table_test_setup(pool).await;
@@ -2860,6 +3915,30 @@ mod test {
         |tid| { tid.name }
     );
 
+    #[sqlx::test]
+    async fn test_table_total_count_excludes_hidden(pool: PgPool) {
+        let (ctx, ns_params) = pagination_test_setup(pool, 20, &[(5, 15)]).await;
+        for mode in [ReturnTotal::Exact, ReturnTotal::Estimate] {
+            let page = CatalogServer::list_tables(
+                ns_params.clone(),
+                ListTablesQuery {
+                    page_token: PageToken::NotSpecified,
+                    page_size: Some(3),
+                    return_uuids: false,
+                    return_total: Some(mode),
+                    return_access_times: false,
+                    return_descriptions: false,
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(page.identifiers.len(), 3);
+            assert_eq!(page.total_count, Some(10), "{mode:?}");
+        }
+    }
+
     #[sqlx::test]
     async fn test_table_pagination(pool: sqlx::PgPool) {
         let prof = crate::catalog::test::test_io_profile();
@@ -2909,6 +3988,9 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(11),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -2924,6 +4006,9 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -2939,6 +4024,9 @@ mod test {
                 page_token: PageToken::Present(all.next_page_token.unwrap()),
                 page_size: Some(10),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -2955,6 +4043,9 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(6),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -2980,6 +4071,9 @@ mod test {
                 page_token: PageToken::Present(first_six.next_page_token.unwrap()),
                 page_size: Some(6),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -3013,6 +4107,9 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(5),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -3039,6 +4136,9 @@ mod test {
                 page_token: PageToken::Present(page.next_page_token.unwrap()),
                 page_size: Some(6),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -3059,4 +4159,153 @@ mod test {
             assert_eq!(next_page_items[idx], format!("tab-{i}"));
         }
     }
+
+    #[sqlx::test]
+    async fn test_rename_table_requires_create_on_destination(pool: PgPool) {
+        let prof = crate::catalog::test::test_io_profile();
+        let hiding_mock = ObjectHidingMock::new();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            prof,
+            None,
+            hiding_mock.to_authorizer(),
+            TabularDeleteProfile::Hard {},
+            Some(UserId::OIDC("test-user-id".to_string())),
+        )
+        .await;
+        let prefix = warehouse.warehouse_id.to_string();
+        let source_ns =
+            crate::catalog::test::create_ns(ctx.clone(), prefix.clone(), "source".to_string())
+                .await;
+        let destination_ns =
+            crate::catalog::test::create_ns(ctx.clone(), prefix.clone(), "destination".to_string())
+                .await;
+        let _ = CatalogServer::create_table(
+            NamespaceParameters {
+                prefix: Some(Prefix(prefix.clone())),
+                namespace: source_ns.namespace.clone(),
+            },
+            create_request(Some("tab".to_string())),
+            DataAccess::none(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let destination_ns_id = destination_ns
+            .properties
+            .as_ref()
+            .and_then(|p| p.get("namespace_id"))
+            .unwrap();
+        hiding_mock.hide(&format!("namespace:{destination_ns_id}"));
+
+        let rename = iceberg_ext::catalog::rest::RenameTableRequest {
+            source: TableIdent::new(source_ns.namespace.clone(), "tab".to_string()),
+            destination: TableIdent::new(destination_ns.namespace.clone(), "tab".to_string()),
+        };
+        let err = CatalogServer::rename_table(
+            Some(Prefix(prefix.clone())),
+            rename.clone(),
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::FORBIDDEN);
+
+        hiding_mock.hidden.write().unwrap().clear();
+        CatalogServer::rename_table(
+            Some(Prefix(prefix)),
+            rename,
+            ctx.clone(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_force_drop_table_with_dependent_views(pool: PgPool) {
+        let prof = crate::catalog::test::test_io_profile();
+        let hiding_mock = ObjectHidingMock::new();
+        let (ctx, warehouse) = crate::catalog::test::setup(
+            pool.clone(),
+            prof,
+            None,
+            hiding_mock.to_authorizer(),
+            TabularDeleteProfile::Hard {},
+            Some(UserId::OIDC("test-user-id".to_string())),
+        )
+        .await;
+        let prefix = warehouse.warehouse_id.to_string();
+        let ns =
+            crate::catalog::test::create_ns(ctx.clone(), prefix.clone(), "ns1".to_string()).await;
+        let ns_params = NamespaceParameters {
+            prefix: Some(Prefix(prefix.clone())),
+            namespace: ns.namespace.clone(),
+        };
+        let mut tables = HashMap::new();
+        for name in ["used", "unused"] {
+            let table = CatalogServer::create_table(
+                ns_params.clone(),
+                create_request(Some(name.to_string())),
+                DataAccess::none(),
+                ctx.clone(),
+                random_request_metadata(),
+            )
+            .await
+            .unwrap();
+            tables.insert(name, table.metadata.uuid());
+        }
+        let mut view_request =
+            crate::catalog::views::create::test::create_view_request(Some("dependent"), None);
+        view_request.properties.insert(
+            PROPERTY_VIEW_DEPENDENCIES.to_string(),
+            "ns1.used".to_string(),
+        );
+        CatalogServer::create_view(
+            ns_params.clone(),
+            view_request,
+            ctx.clone(),
+            DataAccess::none(),
+            random_request_metadata(),
+        )
+        .await
+        .unwrap();
+
+        let drop_table = |name: &str, force: Option<bool>| {
+            CatalogServer::drop_table(
+                TableParameters {
+                    prefix: ns_params.prefix.clone(),
+                    table: TableIdent::new(ns.namespace.clone(), name.to_string()),
+                },
+                DropParams {
+                    purge_requested: Some(true),
+                    force,
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+        };
+
+        // Without force, tables that views depend on are not dropped.
+        let e = drop_table("used", None).await.unwrap_err();
+        assert_eq!(e.error.code, StatusCode::CONFLICT, "{e:?}");
+        assert_eq!(e.error.r#type, "TableInUseByViews");
+
+        // Force dropping requires `can_force_drop`, `can_drop` alone is not sufficient.
+        let used_table = format!("table:{}", tables["used"]);
+        hiding_mock.deny(&used_table, "can_force_drop");
+        let e = drop_table("used", Some(true)).await.unwrap_err();
+        assert_eq!(e.error.code, StatusCode::FORBIDDEN, "{e:?}");
+
+        // Tables without dependents are dropped without `can_force_drop`, with or without force.
+        let unused_table = format!("table:{}", tables["unused"]);
+        hiding_mock.deny(&unused_table, "can_force_drop");
+        drop_table("unused", None).await.unwrap();
+
+        hiding_mock.denied.write().unwrap().clear();
+        drop_table("used", Some(true)).await.unwrap();
+    }
 }
@@ -1,4 +1,5 @@
-use crate::service::ListFlags;
+use crate::api::Result;
+use crate::service::{Catalog, ListFlags, TabularAccess, Transaction};
 
 pub(crate) fn default_view_flags() -> bool {
     false
@@ -74,3 +75,27 @@ macro_rules! list_entities {
 }
 
 pub(crate) use list_entities;
+
+/// Recorded access of the given tables or views, in the same order.
+pub(crate) async fn fetch_access_times<C: Catalog>(
+    tabular_ids: &[uuid::Uuid],
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<Vec<TabularAccess>> {
+    let access = C::get_tabular_access(tabular_ids, transaction).await?;
+    Ok(tabular_ids
+        .iter()
+        .map(|id| access.get(id).copied().unwrap_or_default())
+        .collect())
+}
+
+/// Descriptions of the given tables or views, in the same order.
+pub(crate) async fn fetch_descriptions<C: Catalog>(
+    tabular_ids: &[uuid::Uuid],
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<Vec<Option<String>>> {
+    let mut descriptions = C::get_tabular_descriptions(tabular_ids, transaction).await?;
+    Ok(tabular_ids
+        .iter()
+        .map(|id| descriptions.remove(id))
+        .collect())
+}
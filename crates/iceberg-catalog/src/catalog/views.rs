@@ -1,5 +1,6 @@
 mod commit;
 pub(crate) mod create;
+pub(crate) mod dependencies;
 mod drop;
 mod exists;
 mod list;
@@ -16,6 +17,7 @@ use crate::api::iceberg::v1::{
 };
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::Authorizer;
+use crate::service::id_generation::external_id_may_not_change;
 use crate::service::{Catalog, SecretStore, State};
 pub(crate) use exists::authorized_view_ident_to_id;
 use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
@@ -110,9 +112,11 @@ fn validate_view_updates(updates: &Vec<ViewUpdate>) -> Result<()> {
         match update {
             ViewUpdate::SetProperties { updates } => {
                 validate_view_properties(updates.keys())?;
+                external_id_may_not_change(updates.keys())?;
             }
             ViewUpdate::RemoveProperties { removals } => {
                 validate_view_properties(removals.iter())?;
+                external_id_may_not_change(removals)?;
             }
             _ => {}
         }
@@ -203,7 +207,16 @@ mod test {
                         crate::implementations::postgres::task_queues::TabularExpirationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap(),
                     ),
                     Arc::new(
-                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
+                        crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::TabularRelocationQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::TabularCompactionQueue::from_config(ReadWrite::from_pools(pool.clone(), pool.clone()), CONFIG.queue_config.clone()).unwrap()
+                    ),
+                    Arc::new(
+                        crate::implementations::postgres::task_queues::TableInventoryExportQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), CONFIG.queue_config.clone()).unwrap()
                     )
                 )
             },
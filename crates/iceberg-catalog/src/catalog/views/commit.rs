@@ -3,23 +3,24 @@ use crate::api::iceberg::v1::{
     ViewParameters,
 };
 use crate::catalog::compression_codec::CompressionCodec;
-use crate::catalog::io::write_metadata_file;
 use crate::catalog::require_warehouse_id;
 use crate::catalog::tables::{
     determine_table_ident, extract_count_from_metadata_location, maybe_body_to_json,
     require_active_warehouse, validate_table_or_view_ident,
 };
+use crate::catalog::views::dependencies::update_view_dependencies;
 use crate::catalog::views::{parse_view_location, validate_view_updates};
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{CatalogViewAction, CatalogWarehouseAction};
 use crate::service::contract_verification::ContractVerification;
 use crate::service::event_publisher::EventMetadata;
 use crate::service::storage::{StorageLocations as _, StoragePermissions};
+use crate::service::tabular_access::record_tabular_access;
 use crate::service::{
     authz::Authorizer, secrets::SecretStore, Catalog, GetWarehouseResponse, State, Transaction,
     ViewMetadataWithLocation,
 };
-use crate::service::{TabularIdentUuid, ViewIdentUuid};
+use crate::service::{TabularAccessKind, TabularIdentUuid, ViewIdentUuid};
 use http::StatusCode;
 use iceberg::spec::{AppendViewVersion, ViewFormatVersion, ViewMetadata, ViewMetadataBuilder};
 use iceberg_ext::catalog::rest::ViewUpdate;
@@ -57,7 +58,7 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             &CatalogWarehouseAction::CanUse,
         )
         .await?;
-    let mut t = C::Transaction::begin_write(state.v1_state.catalog).await?;
+    let mut t = C::Transaction::begin_write(state.v1_state.catalog.clone()).await?;
     let view_id = C::view_to_id(warehouse_id, &identifier, t.transaction()).await; // We can't fail before AuthZ;
 
     let view_id = authorizer
@@ -83,6 +84,14 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
         storage_secret_id,
         status,
         tabular_delete_profile: _,
+        storage_health: _,
+        namespace_defaults: _,
+        lifecycle: _,
+        allowed_client_networks: _,
+        table_limits: _,
+        snapshot_summary_enrichment: _,
+        wasm_hooks,
+        summary: _,
     } = C::require_warehouse(warehouse_id, t.transaction()).await?;
     require_active_warehouse(status)?;
 
@@ -98,6 +107,7 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     state
         .v1_state
         .contract_verifiers
+        .for_warehouse(&wasm_hooks)?
         .check_view_updates(updates, &before_update_metadata)
         .await?
         .into_result()?;
@@ -111,7 +121,11 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
         &view_location,
         &CompressionCodec::try_from_properties(requested_update_metadata.properties())?,
         Uuid::now_v7(),
-        extract_count_from_metadata_location(&before_update_metadata_location).map_or(0, |v| v + 1),
+        extract_count_from_metadata_location(
+            &before_update_metadata_location,
+            storage_profile.metadata_naming(),
+        )
+        .map_or(0, |v| v + 1),
     );
 
     C::update_view_metadata(
@@ -124,6 +138,14 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
         t.transaction(),
     )
     .await?;
+    update_view_dependencies::<C>(
+        warehouse_id,
+        view_id,
+        &requested_update_metadata,
+        state.v1_state.catalog.clone(),
+        t.transaction(),
+    )
+    .await?;
 
     // We don't commit the transaction yet, first we need to write the metadata file.
     let storage_secret = if let Some(secret_id) = &storage_secret_id {
@@ -140,13 +162,16 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     };
 
     let file_io = storage_profile.file_io(storage_secret.as_ref())?;
-    write_metadata_file(
-        &metadata_location,
-        &requested_update_metadata,
-        CompressionCodec::try_from_metadata(&requested_update_metadata)?,
-        &file_io,
-    )
-    .await?;
+    storage_profile
+        .write_metadata_file(
+            &file_io,
+            storage_secret.as_ref(),
+            Some(warehouse_id),
+            &metadata_location,
+            &requested_update_metadata,
+            CompressionCodec::try_from_metadata(&requested_update_metadata)?,
+        )
+        .await?;
 
     tracing::debug!("Wrote new metadata file to: '{}'", metadata_location);
     // Generate the storage profile. This requires the storage secret
@@ -162,9 +187,15 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             &metadata_location,
             // TODO: This should be a permission based on authz
             StoragePermissions::ReadWriteDelete,
+            request_metadata.actor(),
         )
         .await?;
     t.commit().await?;
+    record_tabular_access::<C>(
+        view_id.into(),
+        TabularAccessKind::Commit,
+        state.v1_state.catalog,
+    );
 
     let _ = state
         .v1_state
@@ -185,6 +216,8 @@ pub(crate) async fn commit_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 num_events: 1,
                 sequence_number: 0,
                 trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
             },
         )
         .await;
@@ -2,16 +2,18 @@ use crate::api::iceberg::types::Prefix;
 use crate::api::iceberg::v1::{DataAccess, NamespaceParameters};
 use crate::api::ApiContext;
 use crate::catalog::compression_codec::CompressionCodec;
-use crate::catalog::io::write_metadata_file;
 use crate::catalog::tables::{
     determine_tabular_location, maybe_body_to_json, require_active_warehouse,
     validate_table_or_view_ident,
 };
+use crate::catalog::views::dependencies::update_view_dependencies;
 use crate::catalog::views::validate_view_properties;
 use crate::catalog::{maybe_get_secret, require_warehouse_id};
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{Authorizer, CatalogNamespaceAction, CatalogWarehouseAction};
 use crate::service::event_publisher::EventMetadata;
+use crate::service::id_generation::{generate_id, IdKind, IdRequest, PROPERTY_EXTERNAL_ID};
+use crate::service::missing_idents::invalidate_missing_idents;
 use crate::service::storage::{StorageLocations as _, StoragePermissions};
 use crate::service::TabularIdentUuid;
 use crate::service::{Catalog, SecretStore, State, Transaction};
@@ -73,12 +75,20 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     let storage_profile = warehouse.storage_profile;
     require_active_warehouse(warehouse.status)?;
 
-    let view_id: TabularIdentUuid = TabularIdentUuid::View(uuid::Uuid::now_v7());
+    let view_id: TabularIdentUuid = TabularIdentUuid::View(generate_id(IdRequest {
+        kind: IdKind::View,
+        name: &request.name,
+        external_id: request
+            .properties
+            .get(PROPERTY_EXTERNAL_ID)
+            .map(String::as_str),
+    }));
 
     let view_location = determine_tabular_location(
         &namespace,
         request.location.clone(),
         view_id,
+        &request.name,
         &storage_profile,
     )?;
 
@@ -126,6 +136,14 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
         t.transaction(),
     )
     .await?;
+    update_view_dependencies::<C>(
+        warehouse_id,
+        ViewIdentUuid::from(*view_id),
+        &metadata,
+        state.v1_state.catalog.clone(),
+        t.transaction(),
+    )
+    .await?;
 
     // We don't commit the transaction yet, first we need to write the metadata file.
     let storage_secret =
@@ -133,7 +151,16 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
 
     let file_io = storage_profile.file_io(storage_secret.as_ref())?;
     let compression_codec = CompressionCodec::try_from_metadata(&metadata)?;
-    write_metadata_file(&metadata_location, &metadata, compression_codec, &file_io).await?;
+    storage_profile
+        .write_metadata_file(
+            &file_io,
+            storage_secret.as_ref(),
+            Some(warehouse_id),
+            &metadata_location,
+            &metadata,
+            compression_codec,
+        )
+        .await?;
     tracing::debug!("Wrote new metadata file to: '{}'", metadata_location);
 
     // Generate the storage profile. This requires the storage secret
@@ -148,6 +175,7 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             storage_secret.as_ref(),
             &view_location,
             StoragePermissions::Read,
+            request_metadata.actor(),
         )
         .await?;
 
@@ -160,6 +188,7 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
         .await?;
 
     t.commit().await?;
+    invalidate_missing_idents(warehouse_id);
 
     let _ = state
         .v1_state
@@ -177,6 +206,8 @@ pub(crate) async fn create_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 num_events: 1,
                 sequence_number: 0,
                 trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
             },
         )
         .await;
@@ -0,0 +1,184 @@
+use crate::api::Result;
+use crate::service::{Catalog, ListFlags, TableIdentUuid, Transaction, ViewIdentUuid};
+use crate::WarehouseIdent;
+use iceberg::spec::{ViewMetadata, ViewRepresentation};
+use iceberg::{NamespaceIdent, TableIdent};
+use sqlparser::ast::{ObjectName, Query, Visit, Visitor};
+use sqlparser::dialect::{dialect_from_str, GenericDialect};
+use sqlparser::parser::Parser;
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+/// View property listing tables the view depends on in addition to those found in its SQL.
+/// Comma-separated identifiers, namespace levels and table name separated by `.`.
+pub const PROPERTY_VIEW_DEPENDENCIES: &str = "lakekeeper.view-dependencies";
+
+/// Resolve the tables referenced by the current version of a view and replace the
+/// persisted dependencies of the view. References that do not resolve to an existing
+/// table of the warehouse are ignored.
+pub(crate) async fn update_view_dependencies<C: Catalog>(
+    warehouse_id: WarehouseIdent,
+    view_id: ViewIdentUuid,
+    metadata: &ViewMetadata,
+    catalog_state: C::State,
+    transaction: <C::Transaction as Transaction<C::State>>::Transaction<'_>,
+) -> Result<()> {
+    let referenced = referenced_tables(metadata);
+    let mut table_ids: Vec<TableIdentUuid> = if referenced.is_empty() {
+        vec![]
+    } else {
+        C::table_idents_to_ids(
+            warehouse_id,
+            referenced.iter().collect(),
+            ListFlags::default(),
+            catalog_state,
+        )
+        .await?
+        .into_values()
+        .flatten()
+        .collect()
+    };
+    table_ids.sort_unstable();
+    table_ids.dedup();
+    C::set_view_dependencies(view_id, &table_ids, transaction).await
+}
+
+/// Identifiers of tables the current version of a view may reference.
+///
+/// Best effort: relations are extracted from all SQL representations that can be parsed,
+/// plus the tables listed in [`PROPERTY_VIEW_DEPENDENCIES`]. Unqualified names resolve
+/// against the default namespace of the view version. Names with more than two parts
+/// may start with a catalog name, so both interpretations are returned.
+pub(crate) fn referenced_tables(metadata: &ViewMetadata) -> HashSet<TableIdent> {
+    let version = metadata.current_version();
+    let mut names = vec![];
+    for representation in version.representations().iter() {
+        let ViewRepresentation::Sql(representation) = representation;
+        names.extend(sql_relations(&representation.sql, &representation.dialect));
+    }
+    if let Some(explicit) = metadata.properties().get(PROPERTY_VIEW_DEPENDENCIES) {
+        names.extend(
+            explicit
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| name.split('.').map(ToString::to_string).collect()),
+        );
+    }
+
+    names
+        .into_iter()
+        .flat_map(|parts| candidates(parts, version.default_namespace()))
+        .collect()
+}
+
+/// Names of all relations in `sql`, excluding common table expressions.
+fn sql_relations(sql: &str, dialect: &str) -> Vec<Vec<String>> {
+    let dialect = dialect_from_str(dialect).unwrap_or_else(|| Box::new(GenericDialect {}));
+    let statements = match Parser::parse_sql(dialect.as_ref(), sql) {
+        Ok(statements) => statements,
+        Err(e) => {
+            tracing::debug!("Cannot parse view SQL to determine its dependencies: {e}");
+            return vec![];
+        }
+    };
+
+    let mut visitor = RelationVisitor::default();
+    let _ = statements.visit(&mut visitor);
+    let RelationVisitor { relations, ctes } = visitor;
+    relations
+        .into_iter()
+        .filter(|parts| !(parts.len() == 1 && ctes.contains(&parts[0])))
+        .collect()
+}
+
+fn candidates(mut parts: Vec<String>, default_namespace: &NamespaceIdent) -> Vec<TableIdent> {
+    let Some(name) = parts.pop() else {
+        return vec![];
+    };
+    if parts.is_empty() {
+        return vec![TableIdent::new(default_namespace.clone(), name)];
+    }
+
+    let mut candidates = vec![];
+    if parts.len() > 1 {
+        if let Ok(namespace) = NamespaceIdent::from_vec(parts[1..].to_vec()) {
+            candidates.push(TableIdent::new(namespace, name.clone()));
+        }
+    }
+    if let Ok(namespace) = NamespaceIdent::from_vec(parts) {
+        candidates.push(TableIdent::new(namespace, name));
+    }
+    candidates
+}
+
+#[derive(Debug, Default)]
+struct RelationVisitor {
+    relations: Vec<Vec<String>>,
+    ctes: HashSet<String>,
+}
+
+impl Visitor for RelationVisitor {
+    type Break = ();
+
+    fn pre_visit_query(&mut self, query: &Query) -> ControlFlow<Self::Break> {
+        if let Some(with) = &query.with {
+            self.ctes.extend(
+                with.cte_tables
+                    .iter()
+                    .map(|cte| cte.alias.name.value.clone()),
+            );
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
+        self.relations
+            .push(relation.0.iter().map(|ident| ident.value.clone()).collect());
+        ControlFlow::Continue(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ident(parts: &[&str]) -> TableIdent {
+        TableIdent::from_strs(parts).unwrap()
+    }
+
+    #[test]
+    fn test_sql_relations() {
+        let relations = sql_relations(
+            "WITH recent AS (SELECT * FROM sales.orders WHERE ts > now() - INTERVAL '1' DAY) \
+             SELECT r.id, c.name FROM recent r JOIN customers c ON r.customer_id = c.id \
+             WHERE c.id IN (SELECT customer_id FROM lake.crm.vip)",
+            "spark",
+        );
+        assert_eq!(
+            relations,
+            vec![
+                vec!["sales".to_string(), "orders".to_string()],
+                vec!["customers".to_string()],
+                vec!["lake".to_string(), "crm".to_string(), "vip".to_string()],
+            ]
+        );
+        assert!(sql_relations("not sql at all", "spark").is_empty());
+    }
+
+    #[test]
+    fn test_candidates() {
+        let default_namespace = NamespaceIdent::new("default".to_string());
+        assert_eq!(
+            candidates(vec!["t".to_string()], &default_namespace),
+            vec![ident(&["default", "t"])]
+        );
+        assert_eq!(
+            candidates(
+                vec!["catalog".to_string(), "ns".to_string(), "t".to_string()],
+                &default_namespace
+            ),
+            vec![ident(&["ns", "t"]), ident(&["catalog", "ns", "t"])]
+        );
+    }
+}
@@ -18,7 +18,9 @@ use uuid::Uuid;
 
 pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
     parameters: ViewParameters,
-    DropParams { purge_requested }: DropParams,
+    DropParams {
+        purge_requested, ..
+    }: DropParams,
     state: ApiContext<State<A, C, S>>,
     request_metadata: RequestMetadata,
 ) -> Result<()> {
@@ -51,6 +53,7 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     state
         .v1_state
         .contract_verifiers
+        .for_warehouse(&warehouse.wasm_hooks)?
         .check_drop(TabularIdentUuid::View(*view_id))
         .await?
         .into_result()?;
@@ -116,6 +119,8 @@ pub(crate) async fn drop_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
                 num_events: 1,
                 sequence_number: 0,
                 trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
             },
         )
         .await;
@@ -174,6 +179,7 @@ mod test {
             },
             DropParams {
                 purge_requested: None,
+                force: None,
             },
             api_context.clone(),
             RequestMetadata::new_random(),
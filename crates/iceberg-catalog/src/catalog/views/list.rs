@@ -1,14 +1,17 @@
 use crate::api::iceberg::v1::{ListTablesQuery, NamespaceParameters, PaginationQuery};
+use crate::api::management::v1::TabularType;
 use crate::api::ApiContext;
 use crate::api::Result;
 use crate::catalog::namespace::validate_namespace_ident;
 use crate::catalog::require_warehouse_id;
-use crate::catalog::tabular::list_entities;
+use crate::catalog::tabular::{
+    default_view_flags, fetch_access_times, fetch_descriptions, list_entities,
+};
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{
     Authorizer, CatalogNamespaceAction, CatalogViewAction, CatalogWarehouseAction,
 };
-use crate::service::{Catalog, SecretStore, State, Transaction};
+use crate::service::{Catalog, ListFlags, SecretStore, State, Transaction};
 use futures::FutureExt;
 use iceberg_ext::catalog::rest::ListTablesResponse;
 use itertools::Itertools;
@@ -20,6 +23,9 @@ pub(crate) async fn list_views<C: Catalog, A: Authorizer + Clone, S: SecretStore
     request_metadata: RequestMetadata,
 ) -> Result<ListTablesResponse> {
     let return_uuids = query.return_uuids;
+    let return_total = query.return_total;
+    let return_access_times = query.return_access_times;
+    let return_descriptions = query.return_descriptions;
     // ------------------- VALIDATIONS -------------------
     let NamespaceParameters { namespace, prefix } = parameters;
     let warehouse_id = require_warehouse_id(prefix)?;
@@ -64,19 +70,66 @@ pub(crate) async fn list_views<C: Catalog, A: Authorizer + Clone, S: SecretStore
             &mut t,
         )
         .await?;
+    let total_count = if let Some(mode) = return_total {
+        let total = C::count_tabulars(
+            warehouse_id,
+            &namespace,
+            TabularType::View,
+            ListFlags {
+                include_deleted: default_view_flags(),
+                ..ListFlags::default()
+            },
+            mode,
+            t.transaction(),
+        )
+        .await?;
+        Some(
+            crate::catalog::count_authorized::<_, _, _, C>(
+                mode,
+                total,
+                list_entities!(
+                    View,
+                    list_views,
+                    view_action,
+                    namespace,
+                    authorizer,
+                    request_metadata,
+                    warehouse_id
+                ),
+                &mut t,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+    let view_uuids = view_uuids.into_iter().map(|id| *id).collect::<Vec<_>>();
+    let access_times = if return_access_times {
+        Some(fetch_access_times::<C>(&view_uuids, t.transaction()).await?)
+    } else {
+        None
+    };
+    let descriptions = if return_descriptions {
+        Some(fetch_descriptions::<C>(&view_uuids, t.transaction()).await?)
+    } else {
+        None
+    };
     t.commit().await?;
 
     Ok(ListTablesResponse {
         next_page_token,
         identifiers,
-        table_uuids: return_uuids.then_some(view_uuids.into_iter().map(|id| *id).collect()),
+        table_uuids: return_uuids.then_some(view_uuids),
+        total_count,
+        access_times,
+        descriptions,
     })
 }
 
 #[cfg(test)]
 mod test {
     use crate::api::iceberg::types::{PageToken, Prefix};
-    use crate::api::iceberg::v1::{DataAccess, ListTablesQuery, NamespaceParameters};
+    use crate::api::iceberg::v1::{DataAccess, ListTablesQuery, NamespaceParameters, ReturnTotal};
     use crate::api::management::v1::warehouse::TabularDeleteProfile;
     use crate::catalog::test::{impl_pagination_tests, random_request_metadata};
     use crate::catalog::CatalogServer;
@@ -156,6 +209,30 @@ mod test {
         |tid| { tid.name }
     );
 
+    #[sqlx::test]
+    async fn test_view_total_count_excludes_hidden(pool: PgPool) {
+        let (ctx, ns_params) = pagination_test_setup(pool, 20, &[(5, 15)]).await;
+        for mode in [ReturnTotal::Exact, ReturnTotal::Estimate] {
+            let page = CatalogServer::list_views(
+                ns_params.clone(),
+                ListTablesQuery {
+                    page_token: PageToken::NotSpecified,
+                    page_size: Some(3),
+                    return_uuids: false,
+                    return_total: Some(mode),
+                    return_access_times: false,
+                    return_descriptions: false,
+                },
+                ctx.clone(),
+                random_request_metadata(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(page.identifiers.len(), 3);
+            assert_eq!(page.total_count, Some(10), "{mode:?}");
+        }
+    }
+
     #[sqlx::test]
     async fn test_view_pagination(pool: sqlx::PgPool) {
         let prof = crate::catalog::test::test_io_profile();
@@ -208,6 +285,9 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(11),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -223,6 +303,9 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -238,6 +321,9 @@ mod test {
                 page_token: PageToken::Present(all.next_page_token.unwrap()),
                 page_size: Some(10),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -254,6 +340,9 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(6),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -279,6 +368,9 @@ mod test {
                 page_token: PageToken::Present(first_six.next_page_token.unwrap()),
                 page_size: Some(6),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -313,6 +405,9 @@ mod test {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(5),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -339,6 +434,9 @@ mod test {
                 page_token: PageToken::Present(page.next_page_token.unwrap()),
                 page_size: Some(6),
                 return_uuids: true,
+                return_access_times: false,
+                return_descriptions: false,
+                return_total: None,
             },
             ctx.clone(),
             random_request_metadata(),
@@ -1,13 +1,14 @@
 use crate::api::iceberg::v1::{DataAccess, ViewParameters};
 use crate::api::{set_not_found_status_code, ApiContext};
-use crate::catalog::require_warehouse_id;
 use crate::catalog::tables::{require_active_warehouse, validate_table_or_view_ident};
 use crate::catalog::views::parse_view_location;
+use crate::catalog::{maybe_get_secret_for_table_config, require_warehouse_id};
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::{Authorizer, CatalogViewAction, CatalogWarehouseAction};
-use crate::service::storage::{StorageCredential, StoragePermissions};
+use crate::service::storage::StoragePermissions;
+use crate::service::tabular_access::record_tabular_access;
 use crate::service::{Catalog, SecretStore, State, Transaction, ViewMetadataWithLocation};
-use crate::service::{GetWarehouseResponse, Result};
+use crate::service::{GetWarehouseResponse, Result, TabularAccessKind};
 use iceberg_ext::catalog::rest::LoadViewResult;
 
 pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>(
@@ -43,7 +44,7 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             &CatalogWarehouseAction::CanUse,
         )
         .await?;
-    let mut t = C::Transaction::begin_read(state.v1_state.catalog).await?;
+    let mut t = C::Transaction::begin_read(state.v1_state.catalog.clone()).await?;
     let view_id = C::view_to_id(warehouse_id, &view, t.transaction()).await; // We can't fail before AuthZ
     let view_id = authorizer
         .require_view_action(
@@ -63,6 +64,14 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
         storage_secret_id,
         status,
         tabular_delete_profile: _,
+        storage_health: _,
+        namespace_defaults: _,
+        lifecycle: _,
+        allowed_client_networks: _,
+        table_limits: _,
+        snapshot_summary_enrichment: _,
+        wasm_hooks: _,
+        summary: _,
     } = C::require_warehouse(warehouse_id, t.transaction()).await?;
     require_active_warehouse(status)?;
 
@@ -74,19 +83,19 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
     let view_location = parse_view_location(&view_metadata.location)?;
 
     t.commit().await?;
+    record_tabular_access::<C>(
+        view_id.into(),
+        TabularAccessKind::Read,
+        state.v1_state.catalog,
+    );
 
-    let storage_secret: Option<StorageCredential> = if let Some(secret_id) = &storage_secret_id {
-        Some(
-            state
-                .v1_state
-                .secrets
-                .get_secret_by_id(secret_id)
-                .await?
-                .secret,
-        )
-    } else {
-        None
-    };
+    let storage_secret = maybe_get_secret_for_table_config(
+        storage_secret_id,
+        &storage_profile,
+        &data_access,
+        &state.v1_state.secrets,
+    )
+    .await?;
 
     let access = storage_profile
         .generate_table_config(
@@ -95,6 +104,7 @@ pub(crate) async fn load_view<C: Catalog, A: Authorizer + Clone, S: SecretStore>
             &view_location,
             // TODO: This should be a permission based on authz
             StoragePermissions::ReadWriteDelete,
+            request_metadata.actor(),
         )
         .await?;
     let load_table_result = LoadViewResult {
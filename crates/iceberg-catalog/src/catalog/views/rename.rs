@@ -8,6 +8,7 @@ use crate::service::authz::{
 };
 use crate::service::contract_verification::ContractVerification;
 use crate::service::event_publisher::EventMetadata;
+use crate::service::missing_idents::invalidate_missing_idents;
 use crate::service::Result;
 use crate::service::TabularIdentUuid;
 use crate::service::{Catalog, SecretStore, State, Transaction};
@@ -49,14 +50,26 @@ pub(crate) async fn rename_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
             e.error.code = StatusCode::NOT_FOUND.into();
             e
         })?;
-    // We need to be allowed to delete the old table and create the new one
-    let namespace_id = C::namespace_to_id(warehouse_id, &source.namespace, t.transaction()).await; // We can't fail before AuthZ
-                                                                                                   // We need to be allowed to delete the old table and create the new one
+    // Moving the view to another namespace removes it from the source namespace,
+    // so we need to be allowed to drop it there.
+    if source.namespace != destination.namespace {
+        authorizer
+            .require_view_action(
+                &request_metadata,
+                Ok(Some(source_id)),
+                &CatalogViewAction::CanDrop,
+            )
+            .await?;
+    }
+
+    // We need to be allowed to create the new view in the destination namespace
+    let destination_namespace_id =
+        C::namespace_to_id(warehouse_id, &destination.namespace, t.transaction()).await; // We can't fail before AuthZ
     authorizer
         .require_namespace_action(
             &request_metadata,
-            namespace_id,
-            &CatalogNamespaceAction::CanCreateTable,
+            destination_namespace_id,
+            &CatalogNamespaceAction::CanCreateView,
         )
         .await?;
 
@@ -75,14 +88,17 @@ pub(crate) async fn rename_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
     )
     .await?;
 
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
     state
         .v1_state
         .contract_verifiers
+        .for_warehouse(&warehouse.wasm_hooks)?
         .check_rename(TabularIdentUuid::View(*source_id), destination)
         .await?
         .into_result()?;
 
     t.commit().await?;
+    invalidate_missing_idents(warehouse_id);
 
     let _ = state
         .v1_state
@@ -100,6 +116,8 @@ pub(crate) async fn rename_view<C: Catalog, A: Authorizer + Clone, S: SecretStor
                 num_events: 1,
                 sequence_number: 0,
                 trace_id: request_metadata.request_id,
+                engine_query_id: request_metadata.engine_query_id.clone(),
+                client_ip: request_metadata.client_ip,
             },
         )
         .await;
@@ -0,0 +1,283 @@
+use crate::config::ClientIpHeader;
+use crate::CONFIG;
+use axum::extract::ConnectInfo;
+use http::HeaderMap;
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+
+/// Address of the client that sent a request.
+///
+/// Proxies in front of Lakekeeper report the client in the header selected by
+/// `client_ip_header`. The header is only honored if the peer is a trusted proxy, or if
+/// no trusted proxies are configured but `trusted_proxy_depth` is set. Otherwise any
+/// client could set it. `None` if the peer is unknown, for example if the router is
+/// served without connection info.
+pub(crate) fn client_ip<B>(request: &http::Request<B>) -> Option<IpAddr> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    resolve_client_ip(
+        peer,
+        request.headers(),
+        &ClientIpConfig {
+            header: CONFIG.client_ip_header,
+            trusted_proxies: &CONFIG.trusted_proxies,
+            depth: CONFIG.trusted_proxy_depth,
+        },
+    )
+}
+
+struct ClientIpConfig<'a> {
+    header: ClientIpHeader,
+    trusted_proxies: &'a [IpNet],
+    depth: Option<usize>,
+}
+
+fn resolve_client_ip(
+    peer: Option<IpAddr>,
+    headers: &HeaderMap,
+    config: &ClientIpConfig<'_>,
+) -> Option<IpAddr> {
+    let is_trusted = |ip: &IpAddr| config.trusted_proxies.iter().any(|net| net.contains(ip));
+
+    let peer = peer?.to_canonical();
+    let honor_headers = if config.trusted_proxies.is_empty() {
+        config.depth.is_some()
+    } else {
+        is_trusted(&peer)
+    };
+    if !honor_headers {
+        return Some(peer);
+    }
+
+    let hops = match config.header {
+        ClientIpHeader::XForwardedFor => forwarded_for_hops(headers),
+        ClientIpHeader::Forwarded => forwarded_hops(headers),
+        // Set by CloudFront itself, so it always holds the viewer.
+        ClientIpHeader::CloudfrontViewerAddress => {
+            return Some(cloudfront_viewer_address(headers).map_or(peer, |ip| ip.to_canonical()));
+        }
+    };
+
+    if let Some(depth) = config.depth {
+        // Fewer hops than proxies means that the request did not pass all of them.
+        let hop = hops
+            .len()
+            .checked_sub(depth)
+            .and_then(|i| hops.get(i).copied().flatten());
+        return Some(hop.map_or(peer, |ip| ip.to_canonical()));
+    }
+
+    let mut client = peer;
+    for hop in hops.into_iter().rev() {
+        let Some(ip) = hop else {
+            break;
+        };
+        client = ip.to_canonical();
+        if !is_trusted(&client) {
+            break;
+        }
+    }
+    Some(client)
+}
+
+/// Addresses in `X-Forwarded-For`, from the client to the last proxy.
+/// Entries that are not an address are `None`.
+fn forwarded_for_hops(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    header_elements(headers, "x-forwarded-for")
+        .map(|hop| hop.parse().ok())
+        .collect()
+}
+
+/// `for` parameters of the `Forwarded` header (RFC 7239), from the client to the last proxy.
+/// Obfuscated identifiers and `unknown` are `None`.
+fn forwarded_hops(headers: &HeaderMap) -> Vec<Option<IpAddr>> {
+    header_elements(headers, "forwarded")
+        .map(|element| {
+            element
+                .split(';')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| key.trim().eq_ignore_ascii_case("for"))
+                .and_then(|(_, value)| parse_node(value.trim().trim_matches('"')))
+        })
+        .collect()
+}
+
+/// Address of the viewer in `CloudFront-Viewer-Address`, which is sent as `<ip>:<port>`.
+fn cloudfront_viewer_address(headers: &HeaderMap) -> Option<IpAddr> {
+    let value = headers
+        .get("cloudfront-viewer-address")?
+        .to_str()
+        .ok()?
+        .trim();
+    value
+        .rsplit_once(':')
+        .and_then(|(ip, _port)| parse_node(ip))
+        .or_else(|| parse_node(value))
+}
+
+/// Parse an address that may be enclosed in brackets and followed by a port.
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Ok(ip) = node.parse() {
+        return Some(ip);
+    }
+    if let Some(rest) = node.strip_prefix('[') {
+        return rest.split_once(']').and_then(|(ip, _)| ip.parse().ok());
+    }
+    node.split_once(':').and_then(|(ip, _)| ip.parse().ok())
+}
+
+fn header_elements<'a>(headers: &'a HeaderMap, name: &str) -> impl Iterator<Item = &'a str> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn test_resolve_client_ip() {
+        let trusted: Vec<IpNet> = vec!["10.0.0.0/8".parse().unwrap()];
+        let config = ClientIpConfig {
+            header: ClientIpHeader::XForwardedFor,
+            trusted_proxies: &trusted,
+            depth: None,
+        };
+        let xff = |value: &str| headers("x-forwarded-for", value);
+
+        // Untrusted peers cannot spoof their address
+        assert_eq!(
+            resolve_client_ip(ip("203.0.113.7"), &xff("198.51.100.1"), &config),
+            ip("203.0.113.7")
+        );
+        // Trusted proxies are skipped from the right
+        assert_eq!(
+            resolve_client_ip(
+                ip("10.0.0.2"),
+                &xff("198.51.100.9, 198.51.100.1, 10.0.0.1"),
+                &config
+            ),
+            ip("198.51.100.1")
+        );
+        // Without header, the proxy is the client
+        assert_eq!(
+            resolve_client_ip(ip("10.0.0.2"), &HeaderMap::new(), &config),
+            ip("10.0.0.2")
+        );
+        assert_eq!(
+            resolve_client_ip(ip("::ffff:203.0.113.7"), &HeaderMap::new(), &config),
+            ip("203.0.113.7")
+        );
+        assert_eq!(resolve_client_ip(None, &HeaderMap::new(), &config), None);
+    }
+
+    #[test]
+    fn test_resolve_client_ip_with_depth() {
+        let config = ClientIpConfig {
+            header: ClientIpHeader::XForwardedFor,
+            trusted_proxies: &[],
+            depth: Some(2),
+        };
+        let xff = |value: &str| headers("x-forwarded-for", value);
+
+        assert_eq!(
+            resolve_client_ip(
+                ip("172.16.0.5"),
+                &xff("1.2.3.4, 198.51.100.1, 172.16.0.9"),
+                &config
+            ),
+            ip("198.51.100.1")
+        );
+        // Requests that bypassed a proxy are attributed to the peer
+        assert_eq!(
+            resolve_client_ip(ip("172.16.0.5"), &xff("198.51.100.1"), &config),
+            ip("172.16.0.5")
+        );
+        // Without depth and trusted proxies, headers are ignored
+        let config = ClientIpConfig {
+            depth: None,
+            ..config
+        };
+        assert_eq!(
+            resolve_client_ip(ip("172.16.0.5"), &xff("198.51.100.1"), &config),
+            ip("172.16.0.5")
+        );
+    }
+
+    #[test]
+    fn test_resolve_client_ip_forwarded() {
+        let trusted: Vec<IpNet> = vec!["10.0.0.0/8".parse().unwrap()];
+        let config = ClientIpConfig {
+            header: ClientIpHeader::Forwarded,
+            trusted_proxies: &trusted,
+            depth: None,
+        };
+
+        assert_eq!(
+            resolve_client_ip(
+                ip("10.0.0.2"),
+                &headers(
+                    "forwarded",
+                    r#"for="[2001:db8::1]:4711";proto=https, For=10.0.0.1;by=10.0.0.2"#
+                ),
+                &config
+            ),
+            ip("2001:db8::1")
+        );
+        // Obfuscated identifiers end the walk
+        assert_eq!(
+            resolve_client_ip(
+                ip("10.0.0.2"),
+                &headers("forwarded", "for=198.51.100.1, for=_hidden, for=10.0.0.1"),
+                &config
+            ),
+            ip("10.0.0.1")
+        );
+    }
+
+    #[test]
+    fn test_resolve_client_ip_cloudfront() {
+        let config = ClientIpConfig {
+            header: ClientIpHeader::CloudfrontViewerAddress,
+            trusted_proxies: &[],
+            depth: Some(1),
+        };
+
+        assert_eq!(
+            resolve_client_ip(
+                ip("172.16.0.5"),
+                &headers("cloudfront-viewer-address", "198.51.100.10:46532"),
+                &config
+            ),
+            ip("198.51.100.10")
+        );
+        assert_eq!(
+            resolve_client_ip(
+                ip("172.16.0.5"),
+                &headers("cloudfront-viewer-address", "2001:db8::1:46532"),
+                &config
+            ),
+            ip("2001:db8::1")
+        );
+        assert_eq!(
+            resolve_client_ip(ip("172.16.0.5"), &HeaderMap::new(), &config),
+            ip("172.16.0.5")
+        );
+    }
+}
@@ -5,7 +5,7 @@
 use anyhow::{anyhow, Context};
 use core::result::Result::Ok;
 use http::HeaderValue;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::Infallible;
 use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
@@ -13,6 +13,10 @@ use std::str::FromStr;
 use std::sync::LazyLock;
 use url::Url;
 
+use crate::service::authn::UserId;
+use crate::service::id_generation::IdGeneration;
+use crate::service::maintenance::CompactionThresholds;
+use crate::service::storage::TabularLocationLayout;
 use crate::service::task_queue::TaskQueueConfig;
 use crate::{ProjectIdent, WarehouseIdent};
 use itertools::Itertools;
@@ -21,6 +25,10 @@ use veil::Redact;
 
 const DEFAULT_RESERVED_NAMESPACES: [&str; 3] = ["system", "examples", "information_schema"];
 const DEFAULT_ENCRYPTION_KEY: &str = "<This is unsafe, please set a proper key>";
+/// Namespace names and table names are part of unique indexes in Postgres, whose
+/// entries are limited to about a third of a page (8 KiB). Leaves room for the other
+/// columns of the index and the array overhead.
+const MAX_INDEXED_IDENTIFIER_BYTES: usize = 2048;
 
 pub static CONFIG: LazyLock<DynAppConfig> = LazyLock::new(get_config);
 pub static DEFAULT_PROJECT_ID: LazyLock<Option<ProjectIdent>> = LazyLock::new(|| {
@@ -47,10 +55,14 @@ fn get_config() -> DynAppConfig {
         .expect("Valid Configuration");
 
     // Ensure base_uri has a trailing slash
-    let base_uri_path = config.base_uri.path().to_string();
-    config
-        .base_uri
-        .set_path(&format!("{}/", base_uri_path.trim_end_matches('/')));
+    for base_uri in
+        std::iter::once(&mut config.base_uri).chain(config.regional_base_uris.values_mut())
+    {
+        let base_uri_path = base_uri.path().to_string();
+        base_uri.set_path(&format!("{}/", base_uri_path.trim_end_matches('/')));
+    }
+
+    config.validate_identifier_limits();
 
     config
         .reserved_namespaces
@@ -106,6 +118,13 @@ pub struct DynAppConfig {
         serialize_with = "serialize_reserved_namespaces"
     )]
     pub reserved_namespaces: ReservedNamespaces,
+    /// Reject requests to deprecated routes of the management API with `410 Gone`
+    /// instead of serving them with deprecation headers.
+    pub disable_deprecated_management_routes: bool,
+    /// Sent as `Deprecation` header with responses of deprecated management routes.
+    pub deprecated_management_routes_deprecation_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Sent as `Sunset` header with responses of deprecated management routes.
+    pub deprecated_management_routes_sunset_date: Option<chrono::DateTime<chrono::Utc>>,
     // ------------- POSTGRES IMPLEMENTATION -------------
     #[redact]
     pub(crate) pg_encryption_key: String,
@@ -125,6 +144,12 @@ pub struct DynAppConfig {
     pub(crate) pg_connection_max_lifetime: Option<u64>,
     pub pg_read_pool_connections: u32,
     pub pg_write_pool_connections: u32,
+    /// Maximum connections of the separate read and write pools used by background
+    /// task workers. If 0, task workers share the pools of the API.
+    pub pg_task_pool_connections: u32,
+    /// Run migrations when the server starts. Replicas starting concurrently
+    /// wait for each other, so migrations only run once.
+    pub migrate_on_startup: bool,
 
     // ------------- NATS CLOUDEVENTS -------------
     pub nats_address: Option<Url>,
@@ -136,9 +161,38 @@ pub struct DynAppConfig {
     #[redact]
     pub nats_token: Option<String>,
 
+    // ------------- LOGGING -------------
+    /// Format of the logs written to stdout. Defaults to `json`.
+    pub log_format: LogFormat,
+
     // ------------- TRACING CLOUDEVENTS ----------
     pub log_cloudevents: Option<bool>,
 
+    // ------------- CLOUDEVENTS PRIVACY ----------
+    /// Fields of logged events to drop or hash, provided as JSON object from field name
+    /// to `drop` or `hash`, i.e. `{"name": "hash", "email": "drop"}`. Applies to extension
+    /// attributes as well as to keys at any depth of the event data.
+    #[serde(deserialize_with = "deserialize_event_redaction", default)]
+    pub event_redaction: BTreeMap<String, RedactionAction>,
+    /// Salt prepended to values before hashing. Without a salt, hashes of
+    /// guessable values such as table names can be reversed.
+    #[redact]
+    pub event_redaction_salt: Option<String>,
+    /// Fraction of events logged per event type, provided as JSON object from event type
+    /// to a number between 0 and 1, i.e. `{"updateTable": 0.1}`. Event types that are
+    /// not listed are always logged.
+    #[serde(deserialize_with = "deserialize_event_sampling", default)]
+    pub event_sampling: BTreeMap<String, f64>,
+
+    // ------------- COMMIT AUDIT ----------
+    /// Warehouse of the table that facts about every table commit are appended to.
+    pub commit_audit_warehouse_id: Option<uuid::Uuid>,
+    /// Table that facts about every table commit are appended to, as `<namespace>.<table>`.
+    /// Disabled if not set.
+    pub commit_audit_table: Option<String>,
+    /// Interval in seconds in which buffered commit facts are appended.
+    pub commit_audit_flush_interval_seconds: u64,
+
     // ------------- AUTHENTICATION -------------
     pub openid_provider_uri: Option<Url>,
     /// Expected audience for the provided token.
@@ -156,7 +210,34 @@ pub struct DynAppConfig {
     pub openid_additional_issuers: Option<Vec<String>>,
     /// A scopes that must be present in provided tokens
     pub openid_scope: Option<String>,
+    /// Identity providers that are only accepted for requests targeting a specific
+    /// project, provided as JSON object from project id to provider, i.e.
+    /// `{"<project-id>": {"provider-uri": "https://idp.example.com/realms/a"}}`.
+    /// The target project is selected via the `x-project-id` header.
+    #[serde(deserialize_with = "deserialize_openid_project_providers", default)]
+    pub openid_project_providers: BTreeMap<uuid::Uuid, ProjectOpenIdProvider>,
     pub enable_kubernetes_authentication: bool,
+    /// Principals that may act on behalf of other users by sending the
+    /// `x-lakekeeper-impersonate-user` header, for example the service account
+    /// of a Trino coordinator. Comma separated list of user ids such as `oidc~trino`.
+    #[serde(
+        deserialize_with = "deserialize_user_ids",
+        serialize_with = "serialize_user_ids"
+    )]
+    pub impersonation_trusted_principals: Vec<UserId>,
+
+    // ------------- STALE USERS -------------
+    /// Users that did not send an authenticated request for this many days are
+    /// handled according to `stale_user_action`. Set to 0 to disable.
+    pub stale_user_after_days: u32,
+    pub stale_user_action: StaleUserAction,
+    /// Users that are never considered stale, for example service accounts of
+    /// jobs that only run a few times a year. Comma separated list of user ids.
+    #[serde(
+        deserialize_with = "deserialize_user_ids",
+        serialize_with = "serialize_user_ids"
+    )]
+    pub stale_user_exempt_principals: Vec<UserId>,
 
     // ------------- AUTHORIZATION - OPENFGA -------------
     #[serde(default)]
@@ -166,10 +247,18 @@ pub struct DynAppConfig {
         serialize_with = "serialize_openfga_config"
     )]
     pub openfga: Option<OpenFGAConfig>,
+    /// External policy decision point, required if `authz_backend` is `webhook`.
+    pub authz_webhook: Option<AuthZWebhookConfig>,
 
     // ------------- Health -------------
     pub health_check_frequency_seconds: u64,
     pub health_check_jitter_millis: u64,
+    /// Interval in seconds in which the storage of every active warehouse
+    /// is re-validated in the background. Set to 0 to disable.
+    pub storage_health_check_interval_seconds: u64,
+    /// Interval in seconds in which expired cache entries, leftover storage validation
+    /// files and idempotency keys of finished tasks are removed. Set to 0 to disable.
+    pub housekeeping_interval_seconds: u64,
 
     // ------------- KV2 -------------
     pub kv2: Option<KV2Config>,
@@ -178,6 +267,77 @@ pub struct DynAppConfig {
 
     // ------------- Queues -------------
     pub queue_config: TaskQueueConfig,
+    /// Number of files copied concurrently when relocating a table.
+    pub table_relocation_parallelism: usize,
+    /// Purges of locations with more objects than this wait for approval via the
+    /// management API instead of deleting anything. Disabled if not set.
+    pub purge_approval_threshold: Option<usize>,
+
+    // ------------- Maintenance -------------
+    /// Endpoint of an HTTP job runner that compaction jobs are submitted to.
+    /// Compaction orchestration is disabled if not set.
+    pub maintenance_executor_url: Option<Url>,
+    #[redact]
+    pub maintenance_executor_token: Option<String>,
+    /// Maximum number of compaction jobs tracked at the same time by a single instance.
+    pub maintenance_executor_max_concurrent_jobs: usize,
+    /// Minimum number of data files of a table before a compaction is requested.
+    pub compaction_min_data_files: u64,
+    /// A compaction is requested if the average data file is smaller than this.
+    pub compaction_target_file_size_bytes: u64,
+
+    // ------------- Request Limits -------------
+    /// Maximum size of request bodies in bytes.
+    pub max_request_body_size_bytes: usize,
+    /// Maximum number of tables changed in a single commit.
+    pub max_tables_per_commit: usize,
+    /// Maximum number of updates in a single commit, summed over all tables.
+    pub max_updates_per_commit: usize,
+    /// Maximum size of the serialized summary of an added snapshot in bytes.
+    pub max_snapshot_summary_size_bytes: usize,
+
+    // ------------- Identifier Limits -------------
+    /// Maximum number of parts of a namespace.
+    pub max_namespace_depth: usize,
+    /// Maximum length of a single namespace part in bytes.
+    pub max_namespace_part_length_bytes: usize,
+    /// Maximum length of table and view names in bytes.
+    pub max_tabular_name_length_bytes: usize,
+
+    // ------------- HTTP Server -------------
+    /// Algorithms offered for compressing responses, in order of preference if the
+    /// client accepts several with the same weight. An empty list disables compression.
+    #[serde(
+        deserialize_with = "deserialize_response_compression",
+        serialize_with = "serialize_response_compression"
+    )]
+    pub response_compression: Vec<ResponseCompression>,
+    pub response_compression_level: ResponseCompressionLevel,
+    /// Responses smaller than this are sent uncompressed.
+    pub response_compression_min_size_bytes: u16,
+    /// HTTP/2 stream-level flow control window. Uses the hyper default if not set.
+    pub http2_initial_stream_window_size_bytes: Option<u32>,
+    /// HTTP/2 connection-level flow control window. Uses the hyper default if not set.
+    pub http2_initial_connection_window_size_bytes: Option<u32>,
+    /// Size the HTTP/2 flow control windows based on the measured bandwidth-delay
+    /// product. Overrides the configured window sizes.
+    pub http2_adaptive_window: bool,
+    /// Maximum number of concurrent streams per HTTP/2 connection.
+    pub http2_max_concurrent_streams: Option<u32>,
+    /// Proxies and load balancers whose `client_ip_header` is trusted
+    /// to determine the address of clients.
+    #[serde(
+        deserialize_with = "deserialize_ip_nets",
+        serialize_with = "serialize_ip_nets"
+    )]
+    pub trusted_proxies: Vec<ipnet::IpNet>,
+    /// Header in which proxies report the address of the client.
+    pub client_ip_header: ClientIpHeader,
+    /// Number of proxies in front of Lakekeeper that append to `client_ip_header`.
+    /// If set, the client is taken from the entry of the outermost proxy instead of
+    /// skipping entries of `trusted_proxies`. For load balancers without fixed
+    /// addresses, leave `trusted_proxies` empty to trust the header of any peer.
+    pub trusted_proxy_depth: Option<usize>,
 
     // ------------- Tabular -------------
     /// Delay in seconds after which a tabular will be deleted
@@ -186,6 +346,100 @@ pub struct DynAppConfig {
         serialize_with = "duration_to_seconds"
     )]
     pub default_tabular_expiration_delay_seconds: chrono::Duration,
+    /// Minimum interval in seconds between two recorded reads or commits of the same
+    /// table or view. Set to 0 to disable access tracking.
+    pub tabular_access_interval_seconds: u64,
+    /// Time in seconds namespaces, tables and views that were not found are remembered
+    /// as missing. Creations on other instances may take this long to be visible.
+    /// Set to 0 to disable caching.
+    pub missing_ident_cache_ttl_seconds: u64,
+    /// Number of previous property sets kept per namespace and table, so that
+    /// property updates can be reverted. Set to 0 to disable the history.
+    pub property_history_length: usize,
+
+    // ------------- Storage Usage -------------
+    /// Compute a daily rollup of the storage used by the tables of each namespace.
+    /// Sizes are estimated from the snapshot summaries, buckets are not scanned.
+    pub enable_storage_usage_rollup: bool,
+    /// Number of days rollups are kept for.
+    pub storage_usage_retention_days: u32,
+    /// Export the latest rollup as `lakekeeper_namespace_storage_bytes` gauges.
+    pub storage_usage_metrics: bool,
+
+    // ------------- Table TTL -------------
+    /// Interval in seconds in which tables that outlived the `table-ttl`
+    /// of their namespace are dropped. Set to 0 to disable table TTLs.
+    pub table_ttl_interval_seconds: u64,
+
+    // ------------- Ephemeral Warehouses -------------
+    /// Interval in seconds in which expired ephemeral warehouses are torn down.
+    /// Set to 0 to keep ephemeral warehouses forever.
+    pub ephemeral_warehouse_check_interval_seconds: u64,
+
+    // ------------- Contract Verification -------------
+    /// Maximum time in milliseconds a single contract verifier may take
+    /// for a single check. Set to 0 to disable the limit.
+    pub contract_verification_timeout_ms: u64,
+    /// Directory containing the `.wasm` (or `.wat`) modules warehouses can reference
+    /// as contract verifiers by their file name. Requires the `wasm-hooks` feature.
+    pub wasm_hook_dir: Option<PathBuf>,
+    /// Number of instructions (roughly) a WASM hook may execute for a single check.
+    pub wasm_hook_fuel: u64,
+    /// Maximum memory a WASM hook may use for a single check.
+    pub wasm_hook_memory_limit_bytes: usize,
+
+    // ------------- Engine Profiles -------------
+    /// Properties that are merged into the table config of `loadTable` / `createTable`
+    /// and the `defaults` of `GET /config`, depending on the query engine sending the request.
+    /// Provided as JSON object from engine name to properties, i.e.
+    /// `{"trino": {"s3.path-style-access": "true"}}`.
+    #[serde(
+        deserialize_with = "deserialize_engine_profiles",
+        default = "EngineProfiles::default"
+    )]
+    pub engine_profiles: EngineProfiles,
+
+    // ------------- Multi-Region -------------
+    /// Base URIs of Lakekeeper deployments serving a storage region, provided as JSON
+    /// object from region to URI, i.e. `{"eu-central-1": "https://eu.lakekeeper.example.com"}`.
+    /// Warehouses whose storage is located in one of these regions advertise the
+    /// regional deployment as S3 signer instead of `base_uri`.
+    #[serde(deserialize_with = "deserialize_regional_base_uris", default)]
+    pub regional_base_uris: BTreeMap<String, url::Url>,
+
+    // ------------- Config Endpoint -------------
+    /// Time in seconds the storage-derived part of the `GET /config` response of a
+    /// warehouse is cached in-process. Also used as `max-age` of the `Cache-Control`
+    /// header. Set to 0 to disable caching.
+    pub config_cache_ttl_seconds: u64,
+
+    // ------------- Manifest Cache -------------
+    /// Maximum size in bytes of manifests and manifest lists kept in memory.
+    /// Set to 0 to disable caching.
+    pub manifest_cache_capacity_bytes: u64,
+
+    // ------------- Response Validation -------------
+    /// Validate responses of the Iceberg REST API against the OpenAPI specification.
+    /// Enabled with `warn` in debug builds, integration tests should use `strict`.
+    pub response_validation: ResponseValidation,
+
+    // ------------- Commit Location Check -------------
+    /// Check that the metadata location and all files referenced by snapshots or statistics
+    /// added in a table commit are located within the table location. Defaults to `warn`.
+    pub commit_location_check: CommitLocationCheck,
+
+    // ------------- Register Schema Check -------------
+    /// Compare the schema of a registered table with the schema of the staged or
+    /// soft-deleted table of the same name it replaces. Defaults to `off`.
+    pub register_table_schema_check: RegisterTableSchemaCheck,
+
+    // ------------- Tabular Locations -------------
+    /// Layout of the default location of new tables and views. Defaults to `uuid`.
+    pub tabular_location_layout: TabularLocationLayout,
+
+    // ------------- Identifiers -------------
+    /// Generation of the ids of new namespaces, tables and views. Defaults to `uuid-v7`.
+    pub id_generation: IdGeneration,
 
     // ------------- Internal -------------
     /// Optional server id. We recommend to not change this unless multiple catalogs
@@ -242,6 +496,83 @@ where
         .serialize(serializer)
 }
 
+fn deserialize_user_ids<'de, D>(deserializer: D) -> Result<Vec<UserId>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let Some(buf) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(vec![]);
+    };
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            UserId::try_from(s.to_string()).map_err(|e| serde::de::Error::custom(e.error.message))
+        })
+        .collect()
+}
+
+fn deserialize_response_compression<'de, D>(
+    deserializer: D,
+) -> Result<Vec<ResponseCompression>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            ResponseCompression::from_str(&s.to_lowercase()).map_err(|_| {
+                serde::de::Error::custom(format!(
+                    "Unknown response compression '{s}'. Expected zstd, gzip, br or deflate."
+                ))
+            })
+        })
+        .collect()
+}
+
+fn serialize_response_compression<S>(
+    value: &[ResponseCompression],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+/// Comma separated networks. Single addresses are accepted as networks of one address.
+fn deserialize_ip_nets<'de, D>(deserializer: D) -> Result<Vec<ipnet::IpNet>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let buf = String::deserialize(deserializer)?;
+    buf.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            ipnet::IpNet::from_str(s)
+                .or_else(|_| std::net::IpAddr::from_str(s).map(ipnet::IpNet::from))
+                .map_err(|e| serde::de::Error::custom(format!("Invalid network '{s}': {e}")))
+        })
+        .collect()
+}
+
+fn serialize_ip_nets<S>(value: &[ipnet::IpNet], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
+fn serialize_user_ids<S>(value: &[UserId], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    value.iter().join(",").serialize(serializer)
+}
+
 fn deserialize_origin<'de, D>(deserializer: D) -> Result<Option<Vec<HeaderValue>>, D::Error>
 where
     D: Deserializer<'de>,
@@ -304,6 +635,19 @@ pub struct OpenFGAConfig {
     /// Authentication configuration
     #[serde(default)]
     pub auth: OpenFGAAuth,
+    /// Deadline of a single check request.
+    #[serde(default = "default_openfga_check_timeout_ms")]
+    pub check_timeout_ms: u64,
+    /// Deadline of a single read or list-objects request.
+    #[serde(default = "default_openfga_read_timeout_ms")]
+    pub read_timeout_ms: u64,
+    /// Deadline of a single write request.
+    #[serde(default = "default_openfga_write_timeout_ms")]
+    pub write_timeout_ms: u64,
+    /// If set, a check that has not been answered after this delay is sent a second
+    /// time and the first response is used. Disabled by default.
+    #[serde(default)]
+    pub check_hedge_delay_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -312,6 +656,53 @@ pub enum AuthZBackend {
     AllowAll,
     #[serde(alias = "openfga", alias = "OpenFGA", alias = "OPENFGA")]
     OpenFGA,
+    #[serde(alias = "webhook", alias = "Webhook", alias = "WEBHOOK")]
+    Webhook,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectOpenIdProvider {
+    /// `OpenID` provider of the project.
+    pub provider_uri: Url,
+    /// Expected audience for tokens of this provider.
+    #[serde(default)]
+    pub audience: Option<Vec<String>>,
+    /// Additional issuers to trust for this provider.
+    #[serde(default)]
+    pub additional_issuers: Option<Vec<String>>,
+    /// A scope that must be present in tokens of this provider.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Claim used as user id instead of `oid` / `sub`.
+    #[serde(default)]
+    pub subject_claim: Option<String>,
+    /// Claim used as name of the user instead of `name`.
+    #[serde(default)]
+    pub name_claim: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Redact)]
+pub struct AuthZWebhookConfig {
+    /// Url that receives a POST request for every authorization decision.
+    pub url: Url,
+    /// Sent as bearer token in the `Authorization` header if specified.
+    #[redact]
+    pub token: Option<String>,
+    /// Timeout of a single decision request. Requests that time out are denied.
+    #[serde(default = "default_authz_webhook_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Time decisions are cached for. Set to 0 to disable caching.
+    #[serde(default = "default_authz_webhook_cache_ttl_seconds")]
+    pub cache_ttl_seconds: u64,
+}
+
+fn default_authz_webhook_timeout_ms() -> u64 {
+    1000
+}
+
+fn default_authz_webhook_cache_ttl_seconds() -> u64 {
+    30
 }
 
 impl Default for AuthZBackend {
@@ -349,6 +740,9 @@ impl Default for DynAppConfig {
                 "system".to_string(),
                 "examples".to_string(),
             ])),
+            disable_deprecated_management_routes: false,
+            deprecated_management_routes_deprecation_date: None,
+            deprecated_management_routes_sunset_date: None,
             pg_encryption_key: DEFAULT_ENCRYPTION_KEY.to_string(),
             pg_database_url_read: None,
             pg_database_url_write: None,
@@ -365,6 +759,8 @@ impl Default for DynAppConfig {
             pg_connection_max_lifetime: None,
             pg_read_pool_connections: 10,
             pg_write_pool_connections: 5,
+            pg_task_pool_connections: 0,
+            migrate_on_startup: false,
             nats_address: None,
             nats_topic: None,
             nats_creds_file: None,
@@ -372,32 +768,132 @@ impl Default for DynAppConfig {
             nats_password: None,
             nats_token: None,
             log_cloudevents: None,
+            log_format: LogFormat::default(),
+            event_redaction: BTreeMap::new(),
+            event_redaction_salt: None,
+            event_sampling: BTreeMap::new(),
+            commit_audit_warehouse_id: None,
+            commit_audit_table: None,
+            commit_audit_flush_interval_seconds: 60,
             openid_provider_uri: None,
             openid_audience: None,
             openid_additional_issuers: None,
             openid_scope: None,
+            openid_project_providers: BTreeMap::new(),
             enable_kubernetes_authentication: false,
+            impersonation_trusted_principals: vec![],
+            stale_user_after_days: 0,
+            stale_user_action: StaleUserAction::Flag,
+            stale_user_exempt_principals: vec![],
             listen_port: 8181,
             health_check_frequency_seconds: 10,
             health_check_jitter_millis: 500,
+            storage_health_check_interval_seconds: 3600,
+            housekeeping_interval_seconds: 3600,
             kv2: None,
             authz_backend: AuthZBackend::AllowAll,
             openfga: None,
+            authz_webhook: None,
             secret_backend: SecretBackend::Postgres,
             queue_config: TaskQueueConfig::default(),
+            table_relocation_parallelism: 16,
+            purge_approval_threshold: None,
+            maintenance_executor_url: None,
+            maintenance_executor_token: None,
+            maintenance_executor_max_concurrent_jobs: 16,
+            compaction_min_data_files: 100,
+            compaction_target_file_size_bytes: 128 * 1024 * 1024,
+            max_request_body_size_bytes: 2 * 1024 * 1024,
+            response_compression: vec![
+                ResponseCompression::Zstd,
+                ResponseCompression::Gzip,
+                ResponseCompression::Br,
+                ResponseCompression::Deflate,
+            ],
+            response_compression_level: ResponseCompressionLevel::Default,
+            response_compression_min_size_bytes: 1024,
+            http2_initial_stream_window_size_bytes: None,
+            http2_initial_connection_window_size_bytes: None,
+            http2_adaptive_window: false,
+            http2_max_concurrent_streams: None,
+            trusted_proxies: vec![],
+            client_ip_header: ClientIpHeader::XForwardedFor,
+            trusted_proxy_depth: None,
+            max_tables_per_commit: 1000,
+            max_updates_per_commit: 10_000,
+            max_snapshot_summary_size_bytes: 64 * 1024,
+            max_namespace_depth: 5,
+            max_namespace_part_length_bytes: 255,
+            max_tabular_name_length_bytes: 1024,
             default_tabular_expiration_delay_seconds: chrono::Duration::days(7),
+            tabular_access_interval_seconds: 300,
+            missing_ident_cache_ttl_seconds: 0,
+            property_history_length: 10,
+            enable_storage_usage_rollup: false,
+            storage_usage_retention_days: 400,
+            storage_usage_metrics: false,
+            table_ttl_interval_seconds: 600,
+            ephemeral_warehouse_check_interval_seconds: 300,
+            contract_verification_timeout_ms: 5000,
+            wasm_hook_dir: None,
+            wasm_hook_fuel: 100_000_000,
+            wasm_hook_memory_limit_bytes: 64 * 1024 * 1024,
+            engine_profiles: EngineProfiles::default(),
+            regional_base_uris: BTreeMap::new(),
+            config_cache_ttl_seconds: 60,
+            manifest_cache_capacity_bytes: 0,
+            response_validation: ResponseValidation::default(),
+            commit_location_check: CommitLocationCheck::default(),
+            register_table_schema_check: RegisterTableSchemaCheck::default(),
+            tabular_location_layout: TabularLocationLayout::default(),
+            id_generation: IdGeneration::default(),
             server_id: uuid::Uuid::nil(),
         }
     }
 }
 
 impl DynAppConfig {
+    /// Panics if identifiers within the configured limits would not fit into the
+    /// database indexes.
+    fn validate_identifier_limits(&self) {
+        assert!(
+            self.max_namespace_depth > 0,
+            "max_namespace_depth must be at least 1"
+        );
+        assert!(
+            self.max_namespace_depth
+                .saturating_mul(self.max_namespace_part_length_bytes)
+                <= MAX_INDEXED_IDENTIFIER_BYTES,
+            "max_namespace_depth * max_namespace_part_length_bytes must not exceed {MAX_INDEXED_IDENTIFIER_BYTES}"
+        );
+        assert!(
+            self.max_tabular_name_length_bytes <= MAX_INDEXED_IDENTIFIER_BYTES,
+            "max_tabular_name_length_bytes must not exceed {MAX_INDEXED_IDENTIFIER_BYTES}"
+        );
+    }
+
     pub fn s3_signer_uri_for_warehouse(&self, warehouse_id: WarehouseIdent) -> url::Url {
-        self.base_uri
+        self.s3_signer_uri_for_warehouse_in_region(warehouse_id, None)
+    }
+
+    /// Signer URI of a warehouse served by the deployment of `region`.
+    /// Falls back to `base_uri` if no deployment is configured for the region.
+    pub fn s3_signer_uri_for_warehouse_in_region(
+        &self,
+        warehouse_id: WarehouseIdent,
+        region: Option<&str>,
+    ) -> url::Url {
+        self.base_uri_for_region(region)
             .join(&format!("catalog/v1/{warehouse_id}"))
             .expect("Valid URL")
     }
 
+    pub fn base_uri_for_region(&self, region: Option<&str>) -> &url::Url {
+        region
+            .and_then(|region| self.regional_base_uris.get(region))
+            .unwrap_or(&self.base_uri)
+    }
+
     pub fn base_uri_catalog(&self) -> url::Url {
         self.base_uri.join("catalog").expect("Valid URL")
     }
@@ -416,10 +912,137 @@ impl DynAppConfig {
     }
 
     pub fn authn_enabled(&self) -> bool {
-        self.openid_provider_uri.is_some()
+        self.openid_provider_uri.is_some() || !self.openid_project_providers.is_empty()
+    }
+
+    #[must_use]
+    pub fn compaction_thresholds(&self) -> CompactionThresholds {
+        CompactionThresholds {
+            min_data_files: self.compaction_min_data_files,
+            target_file_size_bytes: self.compaction_target_file_size_bytes,
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum StaleUserAction {
+    /// Stale users are marked, but keep their access.
+    Flag,
+    /// Stale users are rejected until they are reactivated via the management API.
+    Deactivate,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    strum_macros::Display,
+    strum_macros::EnumString,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum ResponseCompression {
+    Zstd,
+    Gzip,
+    Br,
+    Deflate,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResponseCompressionLevel {
+    /// Least CPU per response, largest responses.
+    Fastest,
+    /// The default level of each algorithm.
+    #[default]
+    Default,
+    /// Smallest responses, most CPU per response.
+    Best,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClientIpHeader {
+    /// `X-Forwarded-For`, appended to by each proxy.
+    #[default]
+    XForwardedFor,
+    /// `Forwarded` as specified in RFC 7239.
+    Forwarded,
+    /// `CloudFront-Viewer-Address`, set by Amazon CloudFront.
+    CloudfrontViewerAddress,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogFormat {
+    /// One JSON object per line with the fields of the event at the top level.
+    #[default]
+    Json,
+    /// JSON following the Elastic Common Schema.
+    Ecs,
+    /// JSON as expected by Google Cloud Logging.
+    Gcp,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RedactionAction {
+    /// The field is removed from the event.
+    Drop,
+    /// The value is replaced by its salted SHA-256 hash, so events can still be correlated.
+    Hash,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResponseValidation {
+    /// Responses are not validated.
+    Off,
+    /// Mismatches are logged as warning.
+    Warn,
+    /// Mismatches are logged and the response is replaced by an internal server error.
+    Strict,
+}
+
+impl Default for ResponseValidation {
+    fn default() -> Self {
+        if cfg!(debug_assertions) {
+            Self::Warn
+        } else {
+            Self::Off
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CommitLocationCheck {
+    /// Locations are not checked.
+    Off,
+    /// Locations outside of the table location are logged as warning.
+    #[default]
+    Warn,
+    /// Commits referencing locations outside of the table location are rejected.
+    Strict,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RegisterTableSchemaCheck {
+    /// Schemas are not compared.
+    #[default]
+    Off,
+    /// Incompatible schemas are logged and returned as `Warning` header.
+    Warn,
+    /// Registering a table with an incompatible schema is rejected.
+    Strict,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum PgSslMode {
     Disable,
@@ -459,6 +1082,139 @@ impl FromStr for PgSslMode {
     }
 }
 
+/// Property bundles per query engine. Engine names are stored lowercase.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EngineProfiles(BTreeMap<String, HashMap<String, String>>);
+
+impl EngineProfiles {
+    /// Determine the engine profile for a request.
+    ///
+    /// An explicit `X-Iceberg-Client` header must match a profile name exactly (ignoring case).
+    /// Otherwise, the first profile whose name is contained in the `User-Agent` is used.
+    #[must_use]
+    pub fn resolve(&self, client: Option<&str>, user_agent: Option<&str>) -> Option<&str> {
+        if let Some(client) = client {
+            let client = client.trim().to_lowercase();
+            return self.0.get_key_value(&client).map(|(name, _)| name.as_str());
+        }
+
+        let user_agent = user_agent?.to_lowercase();
+        self.0
+            .keys()
+            .find(|name| user_agent.contains(name.as_str()))
+            .map(String::as_str)
+    }
+
+    /// Properties of the profile with the given name, if configured.
+    #[must_use]
+    pub fn properties(&self, engine: &str) -> Option<&HashMap<String, String>> {
+        self.0.get(engine)
+    }
+
+    /// Merge the properties of `engine` into `config`, overwriting existing keys.
+    pub fn apply(&self, engine: Option<&str>, config: &mut HashMap<String, String>) {
+        if let Some(properties) = engine.and_then(|engine| self.properties(engine)) {
+            config.extend(properties.clone());
+        }
+    }
+}
+
+fn deserialize_engine_profiles<'de, D>(deserializer: D) -> Result<EngineProfiles, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) if s.trim().is_empty() => return Ok(EngineProfiles::default()),
+        serde_json::Value::String(s) => {
+            serde_json::from_str(&s).map_err(serde::de::Error::custom)?
+        }
+        value => value,
+    };
+    let profiles = BTreeMap::<String, HashMap<String, serde_json::Value>>::deserialize(value)
+        .map_err(serde::de::Error::custom)?;
+
+    profiles
+        .into_iter()
+        .map(|(engine, properties)| {
+            let properties = properties
+                .into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(s) => Ok((key, s)),
+                    serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {
+                        Ok((key, value.to_string()))
+                    }
+                    _ => Err(serde::de::Error::custom(format!(
+                        "Value of engine profile property '{engine}.{key}' must be a string"
+                    ))),
+                })
+                .collect::<Result<HashMap<_, _>, _>>()?;
+            Ok((engine.to_lowercase(), properties))
+        })
+        .collect::<Result<_, _>>()
+        .map(EngineProfiles)
+}
+
+fn deserialize_regional_base_uris<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, url::Url>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) if s.trim().is_empty() => Ok(BTreeMap::new()),
+        serde_json::Value::String(s) => serde_json::from_str(&s).map_err(serde::de::Error::custom),
+        value => BTreeMap::deserialize(value).map_err(serde::de::Error::custom),
+    }
+}
+
+fn deserialize_openid_project_providers<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<uuid::Uuid, ProjectOpenIdProvider>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) if s.trim().is_empty() => Ok(BTreeMap::new()),
+        serde_json::Value::String(s) => serde_json::from_str(&s).map_err(serde::de::Error::custom),
+        value => BTreeMap::deserialize(value).map_err(serde::de::Error::custom),
+    }
+}
+
+fn deserialize_event_redaction<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, RedactionAction>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) if s.trim().is_empty() => Ok(BTreeMap::new()),
+        serde_json::Value::String(s) => serde_json::from_str(&s).map_err(serde::de::Error::custom),
+        value => BTreeMap::deserialize(value).map_err(serde::de::Error::custom),
+    }
+}
+
+fn deserialize_event_sampling<'de, D>(deserializer: D) -> Result<BTreeMap<String, f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let sampling: BTreeMap<String, f64> = match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(s) if s.trim().is_empty() => return Ok(BTreeMap::new()),
+        serde_json::Value::String(s) => {
+            serde_json::from_str(&s).map_err(serde::de::Error::custom)?
+        }
+        value => BTreeMap::deserialize(value).map_err(serde::de::Error::custom)?,
+    };
+    if let Some((typ, rate)) = sampling
+        .iter()
+        .find(|(_, rate)| !(0.0..=1.0).contains(*rate))
+    {
+        return Err(serde::de::Error::custom(format!(
+            "Sampling rate of event type '{typ}' must be between 0 and 1, got {rate}"
+        )));
+    }
+    Ok(sampling)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ReservedNamespaces(HashSet<String>);
 impl Deref for ReservedNamespaces {
@@ -520,12 +1276,32 @@ struct OpenFGAConfigSerde {
     client_secret: Option<String>,
     /// Token Endpoint to use when exchanging client credentials for an access token.
     token_endpoint: Option<String>,
+    #[serde(default = "default_openfga_check_timeout_ms")]
+    check_timeout_ms: u64,
+    #[serde(default = "default_openfga_read_timeout_ms")]
+    read_timeout_ms: u64,
+    #[serde(default = "default_openfga_write_timeout_ms")]
+    write_timeout_ms: u64,
+    #[serde(default)]
+    check_hedge_delay_ms: Option<u64>,
 }
 
 fn default_openfga_store_name() -> String {
     "lakekeeper".to_string()
 }
 
+fn default_openfga_check_timeout_ms() -> u64 {
+    5000
+}
+
+fn default_openfga_read_timeout_ms() -> u64 {
+    10000
+}
+
+fn default_openfga_write_timeout_ms() -> u64 {
+    10000
+}
+
 fn deserialize_openfga_config<'de, D>(deserializer: D) -> Result<Option<OpenFGAConfig>, D::Error>
 where
     D: Deserializer<'de>,
@@ -537,6 +1313,10 @@ where
         api_key,
         endpoint,
         store_name,
+        check_timeout_ms,
+        read_timeout_ms,
+        write_timeout_ms,
+        check_hedge_delay_ms,
     }) = Option::<OpenFGAConfigSerde>::deserialize(deserializer)?
     else {
         return Ok(None);
@@ -566,6 +1346,10 @@ where
         endpoint,
         store_name,
         auth,
+        check_timeout_ms,
+        read_timeout_ms,
+        write_timeout_ms,
+        check_hedge_delay_ms,
     }))
 }
 
@@ -603,6 +1387,10 @@ where
         api_key,
         endpoint: value.endpoint.clone(),
         store_name: value.store_name.clone(),
+        check_timeout_ms: value.check_timeout_ms,
+        read_timeout_ms: value.read_timeout_ms,
+        write_timeout_ms: value.write_timeout_ms,
+        check_hedge_delay_ms: value.check_hedge_delay_ms,
     }
     .serialize(serializer)
 }
@@ -644,6 +1432,87 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_engine_profiles() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__ENGINE_PROFILES",
+                r#"{"Trino": {"s3.path-style-access": true}, "flink": {"lock.enabled": "false"}}"#,
+            );
+            let config = get_config();
+            let profiles = &config.engine_profiles;
+            assert_eq!(
+                profiles.properties("trino"),
+                Some(&HashMap::from([(
+                    "s3.path-style-access".to_string(),
+                    "true".to_string()
+                )]))
+            );
+            assert_eq!(profiles.resolve(Some("Flink"), None), Some("flink"));
+            assert_eq!(profiles.resolve(Some("spark"), Some("Trino/455")), None);
+            assert_eq!(profiles.resolve(None, Some("Trino/455")), Some("trino"));
+            assert_eq!(profiles.resolve(None, Some("curl/8.0")), None);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_regional_base_uris() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__REGIONAL_BASE_URIS",
+                r#"{"eu-central-1": "https://eu.lakekeeper.example.com/lakekeeper"}"#,
+            );
+            let config = get_config();
+            let warehouse_id = WarehouseIdent::from(uuid::Uuid::nil());
+            assert_eq!(
+                config
+                    .s3_signer_uri_for_warehouse_in_region(warehouse_id, Some("eu-central-1"))
+                    .to_string(),
+                format!("https://eu.lakekeeper.example.com/lakekeeper/catalog/v1/{warehouse_id}")
+            );
+            assert_eq!(
+                config.s3_signer_uri_for_warehouse_in_region(warehouse_id, Some("us-east-1")),
+                config.s3_signer_uri_for_warehouse(warehouse_id)
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_event_redaction_and_sampling() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__EVENT_REDACTION",
+                r#"{"name": "hash", "email": "drop"}"#,
+            );
+            jail.set_env("LAKEKEEPER_TEST__EVENT_SAMPLING", r#"{"updateTable": 0.1}"#);
+            let config = get_config();
+            assert_eq!(
+                config.event_redaction,
+                BTreeMap::from([
+                    ("email".to_string(), RedactionAction::Drop),
+                    ("name".to_string(), RedactionAction::Hash)
+                ])
+            );
+            assert_eq!(
+                config.event_sampling,
+                BTreeMap::from([("updateTable".to_string(), 0.1)])
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_log_format() {
+        figment::Jail::expect_with(|jail| {
+            assert_eq!(get_config().log_format, LogFormat::Json);
+            jail.set_env("LAKEKEEPER_TEST__LOG_FORMAT", "gcp");
+            assert_eq!(get_config().log_format, LogFormat::Gcp);
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_wildcard_allow_origin() {
         figment::Jail::expect_with(|jail| {
@@ -657,6 +1526,27 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_openid_project_providers() {
+        figment::Jail::expect_with(|jail| {
+            assert!(get_config().openid_project_providers.is_empty());
+            jail.set_env(
+                "LAKEKEEPER_TEST__OPENID_PROJECT_PROVIDERS",
+                r#"{"00000000-0000-0000-0000-000000000001": {"provider-uri": "https://idp.example.com/realms/tenant-a", "audience": ["lakekeeper"], "subject-claim": "employee_id"}}"#,
+            );
+            let config = get_config();
+            let provider = &config.openid_project_providers[&uuid::Uuid::from_u128(1)];
+            assert_eq!(
+                provider.provider_uri.as_str(),
+                "https://idp.example.com/realms/tenant-a"
+            );
+            assert_eq!(provider.audience, Some(vec!["lakekeeper".to_string()]));
+            assert_eq!(provider.subject_claim.as_deref(), Some("employee_id"));
+            assert_eq!(provider.name_claim, None);
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_single_audience() {
         figment::Jail::expect_with(|jail| {
@@ -707,6 +1597,81 @@ mod test {
         assert!(CONFIG.reserved_namespaces.contains("examples"));
     }
 
+    #[test]
+    fn test_impersonation_trusted_principals() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__IMPERSONATION_TRUSTED_PRINCIPALS",
+                "oidc~trino, kubernetes~spark-operator",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.impersonation_trusted_principals,
+                vec![
+                    UserId::try_from("oidc~trino".to_string()).unwrap(),
+                    UserId::try_from("kubernetes~spark-operator".to_string()).unwrap(),
+                ]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_stale_user_policy() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__STALE_USER_AFTER_DAYS", "90");
+            jail.set_env("LAKEKEEPER_TEST__STALE_USER_ACTION", "deactivate");
+            jail.set_env(
+                "LAKEKEEPER_TEST__STALE_USER_EXEMPT_PRINCIPALS",
+                "kubernetes~yearly-report",
+            );
+            let config = get_config();
+            assert_eq!(config.stale_user_after_days, 90);
+            assert_eq!(config.stale_user_action, StaleUserAction::Deactivate);
+            assert_eq!(
+                config.stale_user_exempt_principals,
+                vec![UserId::try_from("kubernetes~yearly-report".to_string()).unwrap()]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_wasm_hook_dir() {
+        figment::Jail::expect_with(|jail| {
+            let config = get_config();
+            assert!(config.wasm_hook_dir.is_none());
+
+            jail.set_env("LAKEKEEPER_TEST__WASM_HOOK_DIR", "/hooks");
+            let config = get_config();
+            assert_eq!(config.wasm_hook_dir, Some(PathBuf::from("/hooks")));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_authz_webhook_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__AUTHZ_BACKEND", "webhook");
+            jail.set_env(
+                "LAKEKEEPER_TEST__AUTHZ_WEBHOOK__URL",
+                "http://opa:8181/v1/data/lakekeeper/allow",
+            );
+            jail.set_env("LAKEKEEPER_TEST__AUTHZ_WEBHOOK__TIMEOUT_MS", "200");
+            let config = get_config();
+            assert_eq!(config.authz_backend, AuthZBackend::Webhook);
+            let webhook = config.authz_webhook.unwrap();
+            assert_eq!(
+                webhook.url.as_str(),
+                "http://opa:8181/v1/data/lakekeeper/allow"
+            );
+            assert_eq!(webhook.token, None);
+            assert_eq!(webhook.timeout_ms, 200);
+            assert_eq!(webhook.cache_ttl_seconds, 30);
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_openfga_config_no_auth() {
         figment::Jail::expect_with(|jail| {
@@ -718,6 +1683,8 @@ mod test {
             assert_eq!(authz_config.store_name, "store_name");
 
             assert_eq!(authz_config.auth, OpenFGAAuth::Anonymous);
+            assert_eq!(authz_config.check_timeout_ms, 5000);
+            assert_eq!(authz_config.check_hedge_delay_ms, None);
 
             Ok(())
         });
@@ -741,6 +1708,151 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_openfga_deadlines_and_hedging() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__AUTHZ_BACKEND", "openfga");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__CHECK_TIMEOUT_MS", "800");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__WRITE_TIMEOUT_MS", "30000");
+            jail.set_env("LAKEKEEPER_TEST__OPENFGA__CHECK_HEDGE_DELAY_MS", "50");
+            let authz_config = get_config().openfga.unwrap();
+            assert_eq!(authz_config.check_timeout_ms, 800);
+            assert_eq!(authz_config.read_timeout_ms, 10000);
+            assert_eq!(authz_config.write_timeout_ms, 30000);
+            assert_eq!(authz_config.check_hedge_delay_ms, Some(50));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_tabular_location_layout() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__TABULAR_LOCATION_LAYOUT", "hashed-prefix");
+            let config = get_config();
+            assert_eq!(
+                config.tabular_location_layout,
+                TabularLocationLayout::HashedPrefix
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_id_generation() {
+        figment::Jail::expect_with(|jail| {
+            assert_eq!(get_config().id_generation, IdGeneration::UuidV7);
+            jail.set_env("LAKEKEEPER_TEST__ID_GENERATION", "uuid-v4");
+            assert_eq!(get_config().id_generation, IdGeneration::UuidV4);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_trusted_proxies() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__TRUSTED_PROXIES",
+                "10.0.0.0/8, 192.168.1.1",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.trusted_proxies,
+                vec![
+                    "10.0.0.0/8".parse::<ipnet::IpNet>().unwrap(),
+                    "192.168.1.1/32".parse().unwrap()
+                ]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_deprecated_management_routes_dates() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__DEPRECATED_MANAGEMENT_ROUTES_SUNSET_DATE",
+                "2025-08-15T00:00:00Z",
+            );
+            let config = get_config();
+            assert_eq!(
+                config.deprecated_management_routes_sunset_date,
+                chrono::DateTime::from_timestamp(1_755_216_000, 0)
+            );
+            assert!(config
+                .deprecated_management_routes_deprecation_date
+                .is_none());
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_identifier_limits() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__MAX_NAMESPACE_DEPTH", "8");
+            jail.set_env("LAKEKEEPER_TEST__MAX_NAMESPACE_PART_LENGTH_BYTES", "128");
+            let config = get_config();
+            assert_eq!(config.max_namespace_depth, 8);
+            assert_eq!(config.max_namespace_part_length_bytes, 128);
+            assert_eq!(config.max_tabular_name_length_bytes, 1024);
+            Ok(())
+        });
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "max_namespace_depth * max_namespace_part_length_bytes must not exceed"
+    )]
+    fn test_identifier_limits_exceeding_index_size() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__MAX_NAMESPACE_DEPTH", "10");
+            jail.set_env("LAKEKEEPER_TEST__MAX_NAMESPACE_PART_LENGTH_BYTES", "1024");
+            get_config();
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_client_ip_header() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env(
+                "LAKEKEEPER_TEST__CLIENT_IP_HEADER",
+                "cloudfront-viewer-address",
+            );
+            jail.set_env("LAKEKEEPER_TEST__TRUSTED_PROXY_DEPTH", "2");
+            let config = get_config();
+            assert_eq!(
+                config.client_ip_header,
+                ClientIpHeader::CloudfrontViewerAddress
+            );
+            assert_eq!(config.trusted_proxy_depth, Some(2));
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_response_compression() {
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__RESPONSE_COMPRESSION", "gzip, ZSTD");
+            jail.set_env("LAKEKEEPER_TEST__RESPONSE_COMPRESSION_LEVEL", "fastest");
+            let config = get_config();
+            assert_eq!(
+                config.response_compression,
+                vec![ResponseCompression::Gzip, ResponseCompression::Zstd]
+            );
+            assert_eq!(
+                config.response_compression_level,
+                ResponseCompressionLevel::Fastest
+            );
+            Ok(())
+        });
+        figment::Jail::expect_with(|jail| {
+            jail.set_env("LAKEKEEPER_TEST__RESPONSE_COMPRESSION", "");
+            let config = get_config();
+            assert!(config.response_compression.is_empty());
+            Ok(())
+        });
+    }
+
     #[test]
     #[should_panic(expected = "openfga client_secret is required when client_id is specified")]
     fn test_openfga_client_config_fails_without_token() {
@@ -0,0 +1,646 @@
+//! Run Lakekeeper inside another Rust application.
+//!
+//! [`LakekeeperBuilder`] wires the catalog, secret store and authorizer of your choice into
+//! the same router the `lakekeeper` binary serves. The resulting [`EmbeddedLakekeeper`] can be
+//! mounted into an existing `axum` application, and its [`ApiContext`] can be used to call the
+//! Iceberg REST services in-process, without going through HTTP.
+//!
+//! Configuration that is not passed to the builder (for example the base URI, pagination or
+//! the task queue settings) is still read from [`CONFIG`](crate::CONFIG), i.e. from
+//! `LAKEKEEPER__` environment variables. If the router is nested below a path, the base URI
+//! must include it, so that the catalog returns correct URLs to clients.
+//!
+//! # Example
+//! ```no_run
+//! use iceberg_catalog::embedded::LakekeeperBuilder;
+//! use iceberg_catalog::implementations::postgres::task_queues::task_queues_from_config;
+//! use iceberg_catalog::implementations::postgres::{
+//!     get_reader_pool, get_writer_pool, CatalogState, PostgresCatalog, ReadWrite, SecretsState,
+//! };
+//! use iceberg_catalog::service::authz::AllowAllAuthorizer;
+//! use iceberg_catalog::CONFIG;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let read_pool = get_reader_pool(CONFIG.to_pool_opts()).await?;
+//! let write_pool = get_writer_pool(CONFIG.to_pool_opts()).await?;
+//!
+//! let lakekeeper = LakekeeperBuilder::<PostgresCatalog, _, _>::new(
+//!     AllowAllAuthorizer,
+//!     CatalogState::from_pools(read_pool.clone(), write_pool.clone()),
+//!     SecretsState::from_pools(read_pool.clone(), write_pool.clone()),
+//!     task_queues_from_config(
+//!         &ReadWrite::from_pools(read_pool, write_pool),
+//!         &CONFIG.queue_config,
+//!     )?,
+//! )
+//! .build()?;
+//!
+//! let app = axum::Router::new()
+//!     .route("/my-service", axum::routing::get(|| async { "Hello" }))
+//!     .merge(lakekeeper.router);
+//! tokio::spawn(lakekeeper.background_tasks.run(std::future::pending()));
+//!
+//! let listener = tokio::net::TcpListener::bind("0.0.0.0:8181").await?;
+//! axum::serve(listener, app).await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use axum::Router;
+use axum_prometheus::PrometheusMetricLayer;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use http::HeaderValue;
+
+pub use crate::api::iceberg::v1::{
+    config::Service as ConfigService, namespace::Service as NamespaceService,
+    tables::TablesService, views::Service as ViewService, RequestMetadata,
+};
+pub use crate::api::router::serve;
+pub use crate::api::{ApiContext, ThreadSafe};
+pub use crate::catalog::CatalogServer;
+pub use crate::service::authz::Authorizer;
+pub use crate::service::commit_audit::CommitAuditBackend;
+pub use crate::service::contract_verification::{ContractVerification, ContractVerifiers};
+pub use crate::service::event_publisher::CloudEventBackend;
+pub use crate::service::health::{HealthExt, ServiceHealthProvider};
+pub use crate::service::id_generation::{
+    set_id_generator, IdGeneration, IdGenerator, IdKind, IdRequest,
+};
+pub use crate::service::storage::{
+    set_tabular_location_strategy, TabularLocationRequest, TabularLocationStrategy,
+};
+pub use crate::service::task_queue::TaskQueues;
+pub use crate::service::{
+    AuthDetails, Catalog, IdpVerifier, K8sVerifier, ProjectIdent, SecretStore, State,
+};
+
+use crate::api::router::{new_full_router, RouterArgs};
+use crate::service::ephemeral_warehouses::ephemeral_warehouse_task;
+use crate::service::event_publisher::{
+    CloudEventsPublisher, CloudEventsPublisherBackgroundTask, Message,
+};
+use crate::service::housekeeping::{cache_housekeeping_task, housekeeping_task};
+use crate::service::stale_users::stale_user_task;
+use crate::service::storage_health::storage_health_task;
+use crate::service::storage_usage::storage_usage_task;
+use crate::service::table_ttl::table_ttl_task;
+use crate::CONFIG;
+
+/// A background duty. Called again whenever the replica regains leadership.
+pub type Duty = Box<dyn Fn() -> BoxFuture<'static, ()> + Send>;
+
+/// Runs the named [`Duty`] on a single replica of a deployment, until the process stops.
+/// Without leader election, every replica runs every duty.
+pub type LeaderElection = Arc<dyn Fn(&'static str, Duty) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Builder for an [`EmbeddedLakekeeper`].
+///
+/// Only the catalog, secret store, authorizer and task queues are required. Everything else
+/// defaults to the behavior of the binary without the corresponding configuration:
+/// no authentication, no CORS, no metrics, no contract verification, no event sinks,
+/// no commit audit and no leader election.
+pub struct LakekeeperBuilder<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    authorizer: A,
+    catalog_state: C::State,
+    task_catalog_state: Option<C::State>,
+    secrets_state: S,
+    queues: TaskQueues,
+    event_backends: Vec<Arc<dyn CloudEventBackend + Sync + Send>>,
+    commit_audit: Option<(Arc<CommitAuditBackend<C, S>>, Duration)>,
+    leader_election: Option<LeaderElection>,
+    contract_verifiers: ContractVerifiers,
+    token_verifier: Option<IdpVerifier>,
+    k8s_token_verifier: Option<K8sVerifier>,
+    project_token_verifiers: HashMap<ProjectIdent, IdpVerifier>,
+    cors_origins: Option<&'static [HeaderValue]>,
+    metrics_layer: Option<PrometheusMetricLayer<'static>>,
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> std::fmt::Debug
+    for LakekeeperBuilder<C, A, S>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LakekeeperBuilder")
+            .field("authorizer", &"Authorizer")
+            .field("catalog_state", &"CatalogState")
+            .field("secrets_state", &"SecretsState")
+            .field("queues", &self.queues)
+            .field("event_backends", &self.event_backends)
+            .field("commit_audit", &self.commit_audit)
+            .field(
+                "leader_election",
+                &self.leader_election.as_ref().map(|_| "LeaderElection"),
+            )
+            .field("contract_verifiers", &self.contract_verifiers)
+            .field("token_verifier", &self.token_verifier)
+            .field("k8s_token_verifier", &self.k8s_token_verifier)
+            .field("project_token_verifiers", &self.project_token_verifiers)
+            .field("cors_origins", &self.cors_origins)
+            .field(
+                "metrics_layer",
+                &self.metrics_layer.as_ref().map(|_| "PrometheusMetricLayer"),
+            )
+            .finish()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> LakekeeperBuilder<C, A, S> {
+    #[must_use]
+    pub fn new(
+        authorizer: A,
+        catalog_state: C::State,
+        secrets_state: S,
+        queues: TaskQueues,
+    ) -> Self {
+        Self {
+            authorizer,
+            catalog_state,
+            task_catalog_state: None,
+            secrets_state,
+            queues,
+            event_backends: vec![],
+            commit_audit: None,
+            leader_election: None,
+            contract_verifiers: ContractVerifiers::new(vec![]),
+            token_verifier: None,
+            k8s_token_verifier: None,
+            project_token_verifiers: HashMap::new(),
+            cors_origins: None,
+            metrics_layer: None,
+        }
+    }
+
+    /// Publish change events to `backend`. Can be called multiple times.
+    #[must_use]
+    pub fn with_event_backend(mut self, backend: Arc<dyn CloudEventBackend + Sync + Send>) -> Self {
+        self.event_backends.push(backend);
+        self
+    }
+
+    /// Append commit facts to an Iceberg table. Buffered facts are written every
+    /// `flush_interval` and when the background tasks shut down.
+    #[must_use]
+    pub fn with_commit_audit(
+        mut self,
+        commit_audit: Arc<CommitAuditBackend<C, S>>,
+        flush_interval: Duration,
+    ) -> Self {
+        self.event_backends.push(commit_audit.clone());
+        self.commit_audit = Some((commit_audit, flush_interval));
+        self
+    }
+
+    /// Catalog state used by the task queue workers, for example backed by separate
+    /// connection pools. Defaults to the catalog state serving requests.
+    #[must_use]
+    pub fn with_task_catalog_state(mut self, task_catalog_state: C::State) -> Self {
+        self.task_catalog_state = Some(task_catalog_state);
+        self
+    }
+
+    /// Run periodic duties, such as storage health checks or table TTLs,
+    /// on a single replica only.
+    #[must_use]
+    pub fn with_leader_election(mut self, leader_election: LeaderElection) -> Self {
+        self.leader_election = Some(leader_election);
+        self
+    }
+
+    #[must_use]
+    pub fn with_contract_verifiers(mut self, contract_verifiers: ContractVerifiers) -> Self {
+        self.contract_verifiers = contract_verifiers;
+        self
+    }
+
+    /// Authenticate requests with tokens of an `OpenID` provider.
+    #[must_use]
+    pub fn with_token_verifier(mut self, token_verifier: IdpVerifier) -> Self {
+        self.token_verifier = Some(token_verifier);
+        self
+    }
+
+    /// Authenticate requests with Kubernetes service account tokens.
+    #[must_use]
+    pub fn with_k8s_token_verifier(mut self, k8s_token_verifier: K8sVerifier) -> Self {
+        self.k8s_token_verifier = Some(k8s_token_verifier);
+        self
+    }
+
+    /// Authenticate requests carrying the `x-project-id` header of `project_id` with tokens
+    /// of a project-specific `OpenID` provider. Can be called once per project.
+    #[must_use]
+    pub fn with_project_token_verifier(
+        mut self,
+        project_id: ProjectIdent,
+        token_verifier: IdpVerifier,
+    ) -> Self {
+        self.project_token_verifiers
+            .insert(project_id, token_verifier);
+        self
+    }
+
+    #[must_use]
+    pub fn with_cors_origins(mut self, cors_origins: &'static [HeaderValue]) -> Self {
+        self.cors_origins = Some(cors_origins);
+        self
+    }
+
+    #[must_use]
+    pub fn with_metrics_layer(mut self, metrics_layer: PrometheusMetricLayer<'static>) -> Self {
+        self.metrics_layer = Some(metrics_layer);
+        self
+    }
+
+    /// Build the router and the in-process service handles.
+    ///
+    /// # Errors
+    /// - Fails if the token verifier chain cannot be created
+    pub fn build(self) -> anyhow::Result<EmbeddedLakekeeper<C, A, S>> {
+        let (tx, rx) = tokio::sync::mpsc::channel(1000);
+        let publisher = CloudEventsPublisher::new(tx.clone());
+
+        let health_provider = ServiceHealthProvider::new(
+            vec![
+                ("catalog", Arc::new(self.catalog_state.clone())),
+                ("secrets", Arc::new(self.secrets_state.clone())),
+                ("auth", Arc::new(self.authorizer.clone())),
+            ],
+            CONFIG.health_check_frequency_seconds,
+            CONFIG.health_check_jitter_millis,
+        );
+
+        let context = ApiContext {
+            v1_state: State {
+                authz: self.authorizer.clone(),
+                catalog: self.catalog_state.clone(),
+                secrets: self.secrets_state.clone(),
+                publisher: publisher.clone(),
+                contract_verifiers: self.contract_verifiers.clone(),
+                queues: self.queues.clone(),
+            },
+        };
+        let contract_verifiers = self.contract_verifiers.clone();
+
+        let router = new_full_router::<C, A, S>(RouterArgs {
+            authorizer: self.authorizer.clone(),
+            catalog_state: self.catalog_state.clone(),
+            secrets_state: self.secrets_state.clone(),
+            queues: self.queues.clone(),
+            publisher: publisher.clone(),
+            table_change_checkers: self.contract_verifiers,
+            token_verifier: self.token_verifier,
+            k8s_token_verifier: self.k8s_token_verifier,
+            project_token_verifiers: self.project_token_verifiers,
+            service_health_provider: health_provider.clone(),
+            cors_origins: self.cors_origins,
+            metrics_layer: self.metrics_layer,
+        })?;
+
+        Ok(EmbeddedLakekeeper {
+            router,
+            context,
+            background_tasks: BackgroundTasks {
+                authorizer: self.authorizer,
+                task_catalog_state: self
+                    .task_catalog_state
+                    .unwrap_or_else(|| self.catalog_state.clone()),
+                catalog_state: self.catalog_state,
+                secrets_state: self.secrets_state,
+                queues: self.queues,
+                health_provider,
+                publisher,
+                events_tx: tx,
+                events: CloudEventsPublisherBackgroundTask {
+                    source: rx,
+                    sinks: self.event_backends,
+                },
+                commit_audit: self.commit_audit,
+                leader_election: self.leader_election,
+                contract_verifiers,
+            },
+        })
+    }
+}
+
+/// A Lakekeeper instance built by [`LakekeeperBuilder`].
+#[derive(Debug)]
+pub struct EmbeddedLakekeeper<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    /// Router serving the Iceberg REST Catalog and Management APIs, the health endpoint
+    /// and the API documentation. Its state is already set, so it can be merged into
+    /// any other `Router`.
+    pub router: Router,
+    /// Handles to the services backing the router. Pass it to the services of
+    /// [`CatalogServer`] to call them in-process.
+    pub context: ApiContext<State<A, C, S>>,
+    /// Must be running for task queues, health checks, change events and periodic
+    /// duties to be processed.
+    pub background_tasks: BackgroundTasks<C, A, S>,
+}
+
+/// Background work of an [`EmbeddedLakekeeper`].
+pub struct BackgroundTasks<C: Catalog, A: Authorizer + Clone, S: SecretStore> {
+    authorizer: A,
+    catalog_state: C::State,
+    task_catalog_state: C::State,
+    secrets_state: S,
+    queues: TaskQueues,
+    health_provider: ServiceHealthProvider,
+    publisher: CloudEventsPublisher,
+    events_tx: tokio::sync::mpsc::Sender<Message>,
+    events: CloudEventsPublisherBackgroundTask,
+    commit_audit: Option<(Arc<CommitAuditBackend<C, S>>, Duration)>,
+    leader_election: Option<LeaderElection>,
+    contract_verifiers: ContractVerifiers,
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> std::fmt::Debug
+    for BackgroundTasks<C, A, S>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackgroundTasks")
+            .field("queues", &self.queues)
+            .field("health_provider", &self.health_provider)
+            .field("events", &self.events)
+            .field("commit_audit", &self.commit_audit)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Catalog, A: Authorizer + Clone, S: SecretStore> BackgroundTasks<C, A, S> {
+    /// Run the task queue workers, the event publisher and the periodic duties enabled
+    /// in [`CONFIG`] until `shutdown` completes or one of them fails.
+    /// Events that were already sent are published and buffered commit facts are
+    /// written before returning.
+    ///
+    /// # Errors
+    /// If a task queue worker, a periodic duty or the event publisher fails.
+    pub async fn run(self, shutdown: impl Future<Output = ()> + Send) -> anyhow::Result<()> {
+        self.health_provider.spawn_health_checks().await;
+
+        let workers = self.workers();
+        let mut publisher_handle = tokio::task::spawn(self.events.publish());
+        let commit_audit_handle = self.commit_audit.map(|(commit_audit, flush_interval)| {
+            let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+            let handle = tokio::task::spawn(commit_audit.flush_task(flush_interval, async move {
+                let _ = shutdown_rx.await;
+            }));
+            (shutdown_tx, handle)
+        });
+
+        let (result, publisher_stopped) = tokio::select! {
+            result = self.queues.spawn_queues::<C, _, _>(
+                self.task_catalog_state,
+                self.secrets_state,
+                self.authorizer,
+            ) => {
+                tracing::error!("Task queues stopped");
+                (result, false)
+            },
+            name = run_workers(workers) => (Err(anyhow!("{name} task stopped")), false),
+            result = &mut publisher_handle => {
+                tracing::error!("Event publisher stopped");
+                (result.map_err(Into::into).and_then(|r| r), true)
+            },
+            () = shutdown => (Ok(()), false),
+        };
+
+        if !publisher_stopped {
+            tracing::debug!("Sending shutdown signal to event publisher.");
+            if self.events_tx.send(Message::Shutdown).await.is_err() {
+                tracing::warn!("Event publisher is not running anymore.");
+            }
+            match publisher_handle.await? {
+                Ok(()) => tracing::info!("Exiting publisher task"),
+                Err(e) => tracing::error!("Publisher task failed: {e}"),
+            }
+        }
+        // All events reached the sinks, write the facts that are still buffered.
+        if let Some((shutdown_tx, handle)) = commit_audit_handle {
+            let _ = shutdown_tx.send(());
+            handle.await?;
+        }
+
+        result
+    }
+
+    /// Periodic duties enabled in [`CONFIG`], by name.
+    fn workers(&self) -> Vec<(&'static str, BoxFuture<'static, ()>)> {
+        let leader_election = self.leader_election.clone();
+        let lead = move |duty: &'static str, task: Duty| match &leader_election {
+            Some(leader_election) => leader_election(duty, task),
+            None => task(),
+        };
+        let mut workers = vec![];
+
+        if CONFIG.storage_health_check_interval_seconds == 0 {
+            tracing::info!("Running without background storage health checks.");
+        } else {
+            let catalog_state = self.catalog_state.clone();
+            let secrets_state = self.secrets_state.clone();
+            let publisher = self.publisher.clone();
+            let interval = Duration::from_secs(CONFIG.storage_health_check_interval_seconds);
+            // Checks are coordinated per warehouse anyway, but running them on a single
+            // replica avoids claiming warehouses from every replica.
+            workers.push((
+                "Storage health check",
+                lead(
+                    "storage-health",
+                    Box::new(move || {
+                        storage_health_task::<C, S>(
+                            catalog_state.clone(),
+                            secrets_state.clone(),
+                            publisher.clone(),
+                            interval,
+                        )
+                        .boxed()
+                    }),
+                ),
+            ));
+        }
+
+        if CONFIG.stale_user_after_days == 0 {
+            tracing::info!("Running without stale user policy.");
+        } else {
+            let catalog_state = self.catalog_state.clone();
+            let publisher = self.publisher.clone();
+            let stale_after =
+                Duration::from_secs(u64::from(CONFIG.stale_user_after_days) * 24 * 3600);
+            workers.push((
+                "Stale user",
+                lead(
+                    "stale-users",
+                    Box::new(move || {
+                        stale_user_task::<C>(
+                            catalog_state.clone(),
+                            publisher.clone(),
+                            stale_after,
+                            CONFIG.stale_user_action,
+                            CONFIG.stale_user_exempt_principals.clone(),
+                        )
+                        .boxed()
+                    }),
+                ),
+            ));
+        }
+
+        if CONFIG.enable_storage_usage_rollup {
+            let catalog_state = self.catalog_state.clone();
+            workers.push((
+                "Storage usage",
+                lead(
+                    "storage-usage",
+                    Box::new(move || {
+                        storage_usage_task::<C>(
+                            catalog_state.clone(),
+                            CONFIG.storage_usage_retention_days,
+                            CONFIG.storage_usage_metrics,
+                        )
+                        .boxed()
+                    }),
+                ),
+            ));
+        } else {
+            tracing::info!("Running without storage usage rollups.");
+        }
+
+        if CONFIG.table_ttl_interval_seconds == 0 {
+            tracing::info!("Running without table TTLs.");
+        } else {
+            let catalog_state = self.catalog_state.clone();
+            let queues = self.queues.clone();
+            let publisher = self.publisher.clone();
+            let contract_verifiers = self.contract_verifiers.clone();
+            let interval = Duration::from_secs(CONFIG.table_ttl_interval_seconds);
+            workers.push((
+                "Table TTL",
+                lead(
+                    "table-ttl",
+                    Box::new(move || {
+                        table_ttl_task::<C>(
+                            catalog_state.clone(),
+                            queues.clone(),
+                            publisher.clone(),
+                            contract_verifiers.clone(),
+                            interval,
+                        )
+                        .boxed()
+                    }),
+                ),
+            ));
+        }
+
+        if CONFIG.ephemeral_warehouse_check_interval_seconds == 0 {
+            tracing::info!("Running without teardown of ephemeral warehouses.");
+        } else {
+            let catalog_state = self.catalog_state.clone();
+            let authorizer = self.authorizer.clone();
+            let queues = self.queues.clone();
+            let interval = Duration::from_secs(CONFIG.ephemeral_warehouse_check_interval_seconds);
+            workers.push((
+                "Ephemeral warehouse",
+                lead(
+                    "ephemeral-warehouses",
+                    Box::new(move || {
+                        ephemeral_warehouse_task::<C, A>(
+                            catalog_state.clone(),
+                            authorizer.clone(),
+                            queues.clone(),
+                            interval,
+                        )
+                        .boxed()
+                    }),
+                ),
+            ));
+        }
+
+        if CONFIG.housekeeping_interval_seconds == 0 {
+            tracing::info!("Running without housekeeping.");
+        } else {
+            let catalog_state = self.catalog_state.clone();
+            let secrets_state = self.secrets_state.clone();
+            let interval = Duration::from_secs(CONFIG.housekeeping_interval_seconds);
+            // Caches are local to each replica, storage and tasks are shared.
+            workers.push((
+                "Cache housekeeping",
+                cache_housekeeping_task(interval).boxed(),
+            ));
+            workers.push((
+                "Housekeeping",
+                lead(
+                    "housekeeping",
+                    Box::new(move || {
+                        housekeeping_task::<C, S>(
+                            catalog_state.clone(),
+                            secrets_state.clone(),
+                            interval,
+                        )
+                        .boxed()
+                    }),
+                ),
+            ));
+        }
+
+        workers
+    }
+}
+
+/// Returns the name of the first worker that stops. Never returns without workers.
+async fn run_workers(workers: Vec<(&'static str, BoxFuture<'static, ()>)>) -> &'static str {
+    if workers.is_empty() {
+        return std::future::pending().await;
+    }
+    let (name, _, _) = futures::future::select_all(workers.into_iter().map(|(name, worker)| {
+        async move {
+            worker.await;
+            name
+        }
+        .boxed()
+    }))
+    .await;
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::postgres::task_queues::task_queues_from_config;
+    use crate::implementations::postgres::{
+        CatalogState, PostgresCatalog, ReadWrite, SecretsState,
+    };
+    use crate::service::authz::AllowAllAuthorizer;
+    use tower::ServiceExt;
+
+    #[sqlx::test]
+    async fn test_embedded_router_serves_management_api(pool: sqlx::PgPool) {
+        let lakekeeper = LakekeeperBuilder::<PostgresCatalog, _, _>::new(
+            AllowAllAuthorizer,
+            CatalogState::from_pools(pool.clone(), pool.clone()),
+            SecretsState::from_pools(pool.clone(), pool.clone()),
+            task_queues_from_config(
+                &ReadWrite::from_pools(pool.clone(), pool),
+                &CONFIG.queue_config,
+            )
+            .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+        let app = Router::new().nest("/lakekeeper", lakekeeper.router);
+        let response = app
+            .oneshot(
+                http::Request::builder()
+                    .uri("/lakekeeper/management/v1/info")
+                    .body(axum::body::Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::OK);
+    }
+}
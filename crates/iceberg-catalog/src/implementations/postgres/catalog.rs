@@ -1,63 +1,108 @@
 use super::{
     bootstrap::{bootstrap, get_validation_data},
+    description::{
+        get_namespace_descriptions, get_tabular_descriptions, search_descriptions,
+        set_namespace_description, set_tabular_description,
+    },
+    domain::{
+        add_domain_namespace, create_domain, delete_domain, get_domain, list_domain_namespaces,
+        list_domains, remove_domain_namespace, update_domain,
+    },
     namespace::{
-        create_namespace, drop_namespace, get_namespace, list_namespaces, namespace_to_id,
-        update_namespace_properties,
+        count_namespaces, create_namespace, drop_namespace, get_namespace, list_namespaces,
+        namespace_to_id, rename_namespace, update_namespace_properties,
+    },
+    notice::{create_notice, delete_notice, list_notices},
+    role::{
+        create_role, delete_role, get_role_default_warehouse, list_roles, lock_role_members,
+        set_role_default_warehouse, update_role,
     },
-    role::{create_role, delete_role, list_roles, update_role},
+    storage_usage::{list_storage_usage, list_table_storage_usage, rollup_storage_usage},
     tabular::table::{
         drop_table, get_table_metadata_by_id, get_table_metadata_by_s3_location, list_tables,
         load_tables, rename_table, resolve_table_ident, table_idents_to_ids,
     },
+    task_queues::release_idempotency_keys,
     warehouse::{
+        claim_warehouses_for_storage_check, clear_warehouse_namespaces, clear_warehouse_tabulars,
         create_project, create_warehouse, delete_project, delete_warehouse,
-        get_config_for_warehouse, get_project, get_warehouse, get_warehouse_by_name, list_projects,
-        list_warehouses, rename_project, rename_warehouse, set_warehouse_deletion_profile,
-        set_warehouse_status, update_storage_profile,
+        fold_warehouse_aggregate_deltas, get_config_for_warehouse, get_project, get_warehouse,
+        get_warehouse_by_name, get_warehouse_statistics, list_expired_ephemeral_warehouses,
+        list_projects, list_warehouses, rename_project, rename_warehouse,
+        set_warehouse_deletion_profile, set_warehouse_lifecycle, set_warehouse_namespace_defaults,
+        set_warehouse_snapshot_summary_enrichment, set_warehouse_status,
+        set_warehouse_storage_health, set_warehouse_table_limits, set_warehouse_wasm_hooks,
+        update_storage_profile,
     },
     CatalogState, PostgresTransaction,
 };
+use crate::api::management::v1::domain::{Domain, DomainNamespace};
+use crate::api::management::v1::notice::{Notice, NoticeSeverity};
+use crate::api::management::v1::property_history::PropertyVersion;
+use crate::api::management::v1::table::TableOverview;
 use crate::api::management::v1::user::{
-    ListUsersResponse, SearchUserResponse, UserLastUpdatedWith, UserType,
+    ListUsersResponse, SearchUserResponse, UserLastUpdatedWith, UserListFilter, UserType,
+};
+use crate::config::StaleUserAction;
+use crate::implementations::postgres::property_history::{
+    list_namespace_property_history, list_table_property_history,
 };
 use crate::implementations::postgres::role::search_role;
-use crate::implementations::postgres::tabular::table::create_table;
 use crate::implementations::postgres::tabular::table::{
-    commit_table_transaction, load_storage_profile,
+    commit_table_transaction, get_table_metrics, get_table_overview, load_storage_profile,
+    record_table_metrics, relocate_table,
 };
+use crate::implementations::postgres::tabular::table::{create_table, get_replaced_table_schema};
 use crate::implementations::postgres::tabular::{
-    clear_tabular_deleted_at, list_tabulars, mark_tabular_as_deleted,
+    claim_tabular_expiration, clear_tabular_deleted_at, count_tabulars, get_tabular_access,
+    list_deleted_tabulars, list_deleted_tabulars_at_location, list_location_usage,
+    list_pending_expirations, list_table_ttl_candidates, list_tabulars, mark_tabular_as_deleted,
+    record_tabular_access, reschedule_expirations, set_tabular_expiration_exempt,
 };
 use crate::implementations::postgres::user::{
-    create_or_update_user, delete_user, list_users, search_user,
+    apply_stale_user_policy, create_or_update_user, delete_user, get_user_default_warehouse,
+    list_users, reactivate_user, search_user, set_user_default_warehouse, update_user_last_seen,
 };
 use crate::service::authn::UserId;
-use crate::service::task_queue::TaskId;
+use crate::service::missing_idents::{lookup_unless_missing, LookupIdent};
+use crate::service::task_queue::{Task, TaskId};
 use crate::service::{
-    storage::StorageProfile, Catalog, CreateNamespaceRequest, CreateNamespaceResponse,
-    CreateOrUpdateUserResponse, CreateTableResponse, DeletionDetails, GetNamespaceResponse,
-    GetProjectResponse, GetTableMetadataResponse, GetWarehouseResponse, ListFlags,
-    ListNamespacesQuery, LoadTableResponse, NamespaceIdent, NamespaceIdentUuid, ProjectIdent,
-    Result, RoleId, StartupValidationData, TableCreation, TableIdent, TableIdentUuid, Transaction,
-    WarehouseIdent, WarehouseStatus,
+    storage::StorageProfile, Catalog, CatalogSearchMatch, CreateNamespaceRequest,
+    CreateNamespaceResponse, CreateOrUpdateUserResponse, CreateTableResponse,
+    DeletedTabularAtLocation, DeletionDetails, DomainId, GetNamespaceResponse, GetProjectResponse,
+    GetTableMetadataResponse, GetWarehouseResponse, ListFlags, ListNamespacesQuery,
+    LoadTableResponse, NamespaceIdent, NamespaceIdentUuid, NamespaceStorageUsage,
+    PendingExpiration, PrincipalDefaultWarehouse, ProjectIdent, Result, RoleId, StaleUser,
+    StartupValidationData, TableCreation, TableIdent, TableIdentUuid, TableMetrics,
+    TableMetricsReport, TableRelocation, TableStorageUsage, TableTtlCandidate, TabularAccess,
+    TabularAccessKind, TabularExpirationClaim, TabularLocationUsage, Transaction, UserActivity,
+    ViewDependency, ViewDependent, WarehouseIdent, WarehouseStatistics, WarehouseStatus,
+    WarehouseStorageHealth,
 };
 use crate::SecretIdent;
 use crate::{
-    api::iceberg::v1::{PaginatedMapping, PaginationQuery},
+    api::iceberg::v1::{PaginatedMapping, PaginationQuery, ReturnTotal},
     service::TableCommit,
 };
 use crate::{
-    api::management::v1::role::{ListRolesResponse, Role, SearchRoleResponse},
+    api::management::v1::role::{ListRolesResponse, Role, RoleListFilter, SearchRoleResponse},
     service::ViewIdentUuid,
 };
-use crate::{api::management::v1::warehouse::TabularDeleteProfile, service::TabularIdentUuid};
+use crate::{
+    api::management::v1::warehouse::{
+        DeletedTabularListFilter, SnapshotSummaryEnrichment, TableLimits, TabularDeleteProfile,
+        WarehouseLifecycle,
+    },
+    service::TabularIdentUuid,
+};
 use crate::{
     implementations::postgres::tabular::view::{
-        create_view, drop_view, list_views, load_view, rename_view, view_ident_to_id,
+        create_view, drop_view, list_view_dependencies, list_view_dependents, list_views,
+        load_view, rename_view, set_view_dependencies, view_ident_to_id,
     },
     service::TabularIdentOwned,
 };
-use iceberg::spec::ViewMetadata;
+use iceberg::spec::{Schema, ViewMetadata};
 use iceberg_ext::catalog::rest::ErrorModel;
 use iceberg_ext::{catalog::rest::CatalogConfig, configs::Location};
 use itertools::Itertools;
@@ -76,6 +121,10 @@ impl Catalog for super::PostgresCatalog {
         bootstrap(terms_accepted, &mut **transaction).await
     }
 
+    fn backend_name() -> &'static str {
+        "postgres"
+    }
+
     async fn get_server_info(
         catalog_state: Self::State,
     ) -> std::result::Result<StartupValidationData, ErrorModel> {
@@ -120,6 +169,7 @@ impl Catalog for super::PostgresCatalog {
         filter_project_id: Option<ProjectIdent>,
         filter_role_id: Option<Vec<RoleId>>,
         filter_name: Option<String>,
+        filter: RoleListFilter,
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListRolesResponse> {
@@ -127,6 +177,7 @@ impl Catalog for super::PostgresCatalog {
             filter_project_id,
             filter_role_id,
             filter_name,
+            filter,
             pagination,
             &catalog_state.read_pool(),
         )
@@ -140,6 +191,134 @@ impl Catalog for super::PostgresCatalog {
         delete_role(role_id, &mut **transaction).await
     }
 
+    async fn get_role_default_warehouse(
+        role_id: RoleId,
+        catalog_state: Self::State,
+    ) -> Result<Option<PrincipalDefaultWarehouse>> {
+        get_role_default_warehouse(role_id, &catalog_state.read_pool()).await
+    }
+
+    async fn set_role_default_warehouse<'a>(
+        role_id: RoleId,
+        warehouse_id: Option<WarehouseIdent>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_role_default_warehouse(role_id, warehouse_id, &mut **transaction).await
+    }
+
+    async fn lock_role_members<'a>(
+        project_id: ProjectIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        lock_role_members(project_id, transaction).await
+    }
+
+    // ---------------- Domain Management API ----------------
+    async fn create_domain<'a>(
+        domain_id: DomainId,
+        project_id: ProjectIdent,
+        name: &str,
+        description: Option<&str>,
+        tags: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Domain> {
+        create_domain(
+            domain_id,
+            project_id,
+            name,
+            description,
+            tags,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    async fn get_domain(domain_id: DomainId, catalog_state: Self::State) -> Result<Option<Domain>> {
+        get_domain(domain_id, &catalog_state.read_pool()).await
+    }
+
+    async fn list_domains(
+        project_id: ProjectIdent,
+        tag: Option<&str>,
+        catalog_state: Self::State,
+    ) -> Result<Vec<Domain>> {
+        list_domains(project_id, tag, &catalog_state.read_pool()).await
+    }
+
+    async fn update_domain<'a>(
+        domain_id: DomainId,
+        name: &str,
+        description: Option<&str>,
+        tags: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Domain>> {
+        update_domain(domain_id, name, description, tags, &mut **transaction).await
+    }
+
+    async fn delete_domain<'a>(
+        domain_id: DomainId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>> {
+        delete_domain(domain_id, &mut **transaction).await
+    }
+
+    async fn add_domain_namespace<'a>(
+        domain_id: DomainId,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool> {
+        add_domain_namespace(domain_id, namespace_id, transaction).await
+    }
+
+    async fn remove_domain_namespace<'a>(
+        domain_id: DomainId,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>> {
+        remove_domain_namespace(domain_id, namespace_id, &mut **transaction).await
+    }
+
+    async fn list_domain_namespaces(
+        domain_id: DomainId,
+        catalog_state: Self::State,
+    ) -> Result<Vec<DomainNamespace>> {
+        list_domain_namespaces(domain_id, &catalog_state.read_pool()).await
+    }
+
+    // ---------------- Notices API ----------------
+    async fn create_notice<'a>(
+        notice_id: uuid::Uuid,
+        message: &str,
+        severity: NoticeSeverity,
+        starts_at: Option<chrono::DateTime<chrono::Utc>>,
+        ends_at: Option<chrono::DateTime<chrono::Utc>>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Notice> {
+        create_notice(
+            notice_id,
+            message,
+            severity,
+            starts_at,
+            ends_at,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    async fn list_notices(
+        include_inactive: bool,
+        catalog_state: Self::State,
+    ) -> Result<Vec<Notice>> {
+        list_notices(include_inactive, &catalog_state.read_pool()).await
+    }
+
+    async fn delete_notice<'a>(
+        notice_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>> {
+        delete_notice(notice_id, &mut **transaction).await
+    }
+
     // ---------------- User Management API ----------------
     async fn create_or_update_user<'a>(
         user_id: &UserId,
@@ -171,18 +350,43 @@ impl Catalog for super::PostgresCatalog {
     async fn list_user(
         filter_user_id: Option<Vec<UserId>>,
         filter_name: Option<String>,
+        filter: UserListFilter,
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListUsersResponse> {
         list_users(
             filter_user_id,
             filter_name,
+            filter,
             pagination,
             &catalog_state.read_pool(),
         )
         .await
     }
 
+    async fn update_user_last_seen(
+        user_id: &UserId,
+        catalog_state: Self::State,
+    ) -> Result<UserActivity> {
+        update_user_last_seen(user_id, &catalog_state.write_pool()).await
+    }
+
+    async fn apply_stale_user_policy<'a>(
+        stale_after: chrono::Duration,
+        action: StaleUserAction,
+        exempt: &[UserId],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<StaleUser>> {
+        apply_stale_user_policy(stale_after, action, exempt, &mut **transaction).await
+    }
+
+    async fn reactivate_user<'a>(
+        user_id: &UserId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>> {
+        reactivate_user(user_id, &mut **transaction).await
+    }
+
     async fn delete_user<'a>(
         user_id: UserId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
@@ -190,6 +394,21 @@ impl Catalog for super::PostgresCatalog {
         delete_user(user_id, &mut **transaction).await
     }
 
+    async fn get_user_default_warehouse(
+        user_id: &UserId,
+        catalog_state: Self::State,
+    ) -> Result<Option<PrincipalDefaultWarehouse>> {
+        get_user_default_warehouse(user_id, &catalog_state.read_pool()).await
+    }
+
+    async fn set_user_default_warehouse<'a>(
+        user_id: &UserId,
+        warehouse_id: Option<WarehouseIdent>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_user_default_warehouse(user_id, warehouse_id, &mut **transaction).await
+    }
+
     async fn get_warehouse_by_name(
         warehouse_name: &str,
         project_id: ProjectIdent,
@@ -205,6 +424,13 @@ impl Catalog for super::PostgresCatalog {
         get_config_for_warehouse(warehouse_id, catalog_state).await
     }
 
+    async fn get_warehouse_allowed_client_networks(
+        warehouse_id: WarehouseIdent,
+        catalog_state: CatalogState,
+    ) -> Result<Option<Vec<ipnet::IpNet>>> {
+        get_warehouse_allowed_client_networks(warehouse_id, catalog_state).await
+    }
+
     async fn list_namespaces<'a>(
         warehouse_id: WarehouseIdent,
         query: &ListNamespacesQuery,
@@ -213,6 +439,15 @@ impl Catalog for super::PostgresCatalog {
         list_namespaces(warehouse_id, query, transaction).await
     }
 
+    async fn count_namespaces<'a>(
+        warehouse_id: WarehouseIdent,
+        parent: Option<&NamespaceIdent>,
+        mode: ReturnTotal,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<i64> {
+        count_namespaces(warehouse_id, parent, mode, transaction).await
+    }
+
     async fn create_namespace<'a>(
         warehouse_id: WarehouseIdent,
         namespace_id: NamespaceIdentUuid,
@@ -235,7 +470,12 @@ impl Catalog for super::PostgresCatalog {
         namespace: &NamespaceIdent,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Option<NamespaceIdentUuid>> {
-        namespace_to_id(warehouse_id, namespace, transaction).await
+        lookup_unless_missing(
+            warehouse_id,
+            || LookupIdent::Namespace(namespace.clone()),
+            namespace_to_id(warehouse_id, namespace, transaction),
+        )
+        .await
     }
 
     async fn drop_namespace<'a>(
@@ -255,6 +495,31 @@ impl Catalog for super::PostgresCatalog {
         update_namespace_properties(warehouse_id, namespace_id, properties, transaction).await
     }
 
+    async fn list_namespace_property_history(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'_>,
+    ) -> Result<Vec<PropertyVersion>> {
+        list_namespace_property_history(warehouse_id, namespace_id, transaction).await
+    }
+
+    async fn list_table_property_history(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'_>,
+    ) -> Result<Vec<PropertyVersion>> {
+        list_table_property_history(warehouse_id, table_id, transaction).await
+    }
+
+    async fn rename_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        source: &NamespaceIdent,
+        destination: &NamespaceIdent,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        rename_namespace(warehouse_id, source, destination, transaction).await
+    }
+
     async fn create_table<'a>(
         table_creation: TableCreation<'_>,
         transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
@@ -262,6 +527,14 @@ impl Catalog for super::PostgresCatalog {
         create_table(table_creation, transaction).await
     }
 
+    async fn get_replaced_table_schema<'a>(
+        namespace_id: NamespaceIdentUuid,
+        table_name: &str,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Option<Schema>> {
+        get_replaced_table_schema(namespace_id, table_name, transaction).await
+    }
+
     async fn list_tables<'a>(
         warehouse_id: WarehouseIdent,
         namespace: &NamespaceIdent,
@@ -294,9 +567,17 @@ impl Catalog for super::PostgresCatalog {
         list_flags: ListFlags,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Option<TableIdentUuid>> {
-        resolve_table_ident(warehouse_id, table, list_flags, &mut **transaction)
-            .await
-            .map(|x| x.map(|x| x.ident))
+        let lookup = async {
+            resolve_table_ident(warehouse_id, table, list_flags, &mut **transaction)
+                .await
+                .map(|x| x.map(|x| x.ident))
+        };
+        // Staged and deleted tables are not probed by engines.
+        if list_flags == ListFlags::default() {
+            lookup_unless_missing(warehouse_id, || LookupIdent::Table(table.clone()), lookup).await
+        } else {
+            lookup.await
+        }
     }
 
     async fn table_idents_to_ids(
@@ -318,6 +599,15 @@ impl Catalog for super::PostgresCatalog {
         load_tables(warehouse_id, tables, include_deleted, transaction).await
     }
 
+    async fn get_table_overview(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        snapshot_limit: u32,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TableOverview>> {
+        get_table_overview(warehouse_id, table_id, snapshot_limit, transaction).await
+    }
+
     async fn load_storage_profile(
         warehouse_id: WarehouseIdent,
         tabular_id: TableIdentUuid,
@@ -375,6 +665,84 @@ impl Catalog for super::PostgresCatalog {
         mark_tabular_as_deleted(table_id, None, transaction).await
     }
 
+    async fn list_table_ttl_candidates(
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<TableTtlCandidate>> {
+        list_table_ttl_candidates(&mut **transaction).await
+    }
+
+    async fn record_table_metrics<'a>(
+        table_id: TableIdentUuid,
+        report: TableMetricsReport,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        record_table_metrics(table_id, report, transaction).await
+    }
+
+    async fn get_table_metrics(
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<TableMetrics> {
+        get_table_metrics(table_id, catalog_state).await
+    }
+
+    async fn record_tabular_access(
+        tabular_id: TabularIdentUuid,
+        kind: TabularAccessKind,
+        min_interval: chrono::Duration,
+        catalog_state: Self::State,
+    ) -> Result<()> {
+        record_tabular_access(tabular_id, kind, min_interval, &catalog_state.write_pool()).await
+    }
+
+    async fn get_tabular_access<'a>(
+        tabular_ids: &[uuid::Uuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<HashMap<uuid::Uuid, TabularAccess>> {
+        get_tabular_access(tabular_ids, &mut **transaction).await
+    }
+
+    async fn set_namespace_description<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        description: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_namespace_description(warehouse_id, namespace_id, description, transaction).await
+    }
+
+    async fn set_tabular_description<'a>(
+        warehouse_id: WarehouseIdent,
+        tabular_id: TabularIdentUuid,
+        description: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_tabular_description(warehouse_id, tabular_id, description, transaction).await
+    }
+
+    async fn get_namespace_descriptions<'a>(
+        namespace_ids: &[uuid::Uuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<HashMap<uuid::Uuid, String>> {
+        get_namespace_descriptions(namespace_ids, &mut **transaction).await
+    }
+
+    async fn get_tabular_descriptions<'a>(
+        tabular_ids: &[uuid::Uuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<HashMap<uuid::Uuid, String>> {
+        get_tabular_descriptions(tabular_ids, &mut **transaction).await
+    }
+
+    async fn search_descriptions<'a>(
+        warehouse_id: WarehouseIdent,
+        search: &str,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<CatalogSearchMatch>> {
+        search_descriptions(warehouse_id, search, limit, &mut **transaction).await
+    }
+
     async fn commit_table_transaction<'a>(
         warehouse_id: WarehouseIdent,
         commits: impl IntoIterator<Item = TableCommit> + Send,
@@ -383,6 +751,13 @@ impl Catalog for super::PostgresCatalog {
         commit_table_transaction(warehouse_id, commits, transaction).await
     }
 
+    async fn relocate_table<'a>(
+        relocation: &TableRelocation,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<()> {
+        relocate_table(relocation, transaction).await
+    }
+
     async fn create_warehouse<'a>(
         warehouse_name: String,
         project_id: ProjectIdent,
@@ -480,6 +855,84 @@ impl Catalog for super::PostgresCatalog {
         set_warehouse_deletion_profile(warehouse_id, deletion_profile, &mut **transaction).await
     }
 
+    async fn set_warehouse_namespace_defaults<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_defaults: &HashMap<String, String>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_namespace_defaults(warehouse_id, namespace_defaults, &mut **transaction).await
+    }
+
+    async fn set_warehouse_allowed_client_networks<'a>(
+        warehouse_id: WarehouseIdent,
+        allowed_client_networks: &[ipnet::IpNet],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_allowed_client_networks(
+            warehouse_id,
+            allowed_client_networks,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    async fn set_warehouse_table_limits<'a>(
+        warehouse_id: WarehouseIdent,
+        table_limits: &TableLimits,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_table_limits(warehouse_id, table_limits, &mut **transaction).await
+    }
+
+    async fn set_warehouse_snapshot_summary_enrichment<'a>(
+        warehouse_id: WarehouseIdent,
+        snapshot_summary_enrichment: &SnapshotSummaryEnrichment,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_snapshot_summary_enrichment(
+            warehouse_id,
+            snapshot_summary_enrichment,
+            &mut **transaction,
+        )
+        .await
+    }
+
+    async fn set_warehouse_wasm_hooks<'a>(
+        warehouse_id: WarehouseIdent,
+        wasm_hooks: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_wasm_hooks(warehouse_id, wasm_hooks, &mut **transaction).await
+    }
+
+    async fn set_warehouse_lifecycle<'a>(
+        warehouse_id: WarehouseIdent,
+        lifecycle: &WarehouseLifecycle,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_warehouse_lifecycle(warehouse_id, lifecycle, &mut **transaction).await
+    }
+
+    async fn list_expired_ephemeral_warehouses<'a>(
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<WarehouseIdent>> {
+        list_expired_ephemeral_warehouses(&mut **transaction).await
+    }
+
+    async fn clear_warehouse_tabulars<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<(TabularIdentUuid, String)>> {
+        clear_warehouse_tabulars(warehouse_id, &mut **transaction).await
+    }
+
+    async fn clear_warehouse_namespaces<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceIdentUuid>> {
+        clear_warehouse_namespaces(warehouse_id, &mut **transaction).await
+    }
+
     async fn set_warehouse_status<'a>(
         warehouse_id: WarehouseIdent,
         status: WarehouseStatus,
@@ -503,12 +956,80 @@ impl Catalog for super::PostgresCatalog {
         .await
     }
 
+    async fn get_warehouse_statistics<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<WarehouseStatistics> {
+        get_warehouse_statistics(warehouse_id, transaction).await
+    }
+
+    async fn fold_warehouse_aggregate_deltas<'a>(
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<u64> {
+        fold_warehouse_aggregate_deltas(transaction).await
+    }
+
+    async fn rollup_storage_usage<'a>(
+        day: chrono::NaiveDate,
+        retain_from: chrono::NaiveDate,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceStorageUsage>> {
+        rollup_storage_usage(day, retain_from, transaction).await
+    }
+
+    async fn list_storage_usage<'a>(
+        warehouse_id: WarehouseIdent,
+        day: Option<chrono::NaiveDate>,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceStorageUsage>> {
+        list_storage_usage(warehouse_id, day, transaction).await
+    }
+
+    async fn list_table_storage_usage<'a>(
+        warehouse_id: WarehouseIdent,
+        day: Option<chrono::NaiveDate>,
+        namespace_id: Option<NamespaceIdentUuid>,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<PaginatedMapping<TableIdentUuid, TableStorageUsage>> {
+        list_table_storage_usage(
+            warehouse_id,
+            day,
+            namespace_id,
+            pagination_query,
+            transaction,
+        )
+        .await
+    }
+
+    async fn claim_warehouses_for_storage_check<'a>(
+        min_interval: chrono::Duration,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Vec<WarehouseIdent>> {
+        claim_warehouses_for_storage_check(min_interval, limit, transaction).await
+    }
+
+    async fn set_warehouse_storage_health<'a>(
+        warehouse_id: WarehouseIdent,
+        storage_health: WarehouseStorageHealth,
+        failed_checks: Option<serde_json::Value>,
+        transaction: <Self::Transaction as Transaction<CatalogState>>::Transaction<'a>,
+    ) -> Result<Option<WarehouseStorageHealth>> {
+        set_warehouse_storage_health(warehouse_id, storage_health, failed_checks, transaction).await
+    }
+
     async fn view_to_id<'a>(
         warehouse_id: WarehouseIdent,
         view: &TableIdent,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Option<ViewIdentUuid>> {
-        view_ident_to_id(warehouse_id, view, false, &mut **transaction).await
+        lookup_unless_missing(
+            warehouse_id,
+            || LookupIdent::View(view.clone()),
+            view_ident_to_id(warehouse_id, view, false, &mut **transaction),
+        )
+        .await
     }
 
     async fn create_view<'a>(
@@ -593,6 +1114,30 @@ impl Catalog for super::PostgresCatalog {
         rename_view(warehouse_id, source_id, source, destination, transaction).await
     }
 
+    async fn set_view_dependencies(
+        view_id: ViewIdentUuid,
+        table_ids: &[TableIdentUuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()> {
+        set_view_dependencies(view_id, table_ids, transaction).await
+    }
+
+    async fn list_view_dependents(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<ViewDependent>> {
+        list_view_dependents(warehouse_id, table_id, transaction).await
+    }
+
+    async fn list_view_dependencies(
+        warehouse_id: WarehouseIdent,
+        view_id: ViewIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<ViewDependency>> {
+        list_view_dependencies(warehouse_id, view_id, transaction).await
+    }
+
     async fn list_tabulars(
         warehouse_id: WarehouseIdent,
         namespace_id: Option<NamespaceIdentUuid>,
@@ -613,4 +1158,88 @@ impl Catalog for super::PostgresCatalog {
         )
         .await
     }
+
+    async fn list_deleted_tabulars(
+        warehouse_id: WarehouseIdent,
+        filter: DeletedTabularListFilter,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+        pagination_query: PaginationQuery,
+    ) -> Result<PaginatedMapping<TabularIdentUuid, (TabularIdentOwned, Option<DeletionDetails>)>>
+    {
+        list_deleted_tabulars(warehouse_id, filter, &mut **transaction, pagination_query).await
+    }
+
+    async fn count_tabulars<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace: &NamespaceIdent,
+        typ: crate::api::management::v1::TabularType,
+        list_flags: ListFlags,
+        mode: ReturnTotal,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<i64> {
+        count_tabulars(
+            warehouse_id,
+            namespace,
+            typ.into(),
+            list_flags,
+            mode,
+            transaction,
+        )
+        .await
+    }
+
+    async fn list_location_usage(
+        warehouse_id: WarehouseIdent,
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+        pagination_query: PaginationQuery,
+    ) -> Result<PaginatedMapping<TabularIdentUuid, TabularLocationUsage>> {
+        list_location_usage(warehouse_id, location, &mut **transaction, pagination_query).await
+    }
+
+    async fn list_deleted_tabulars_at_location(
+        warehouse_id: WarehouseIdent,
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<DeletedTabularAtLocation>> {
+        list_deleted_tabulars_at_location(warehouse_id, location, &mut **transaction).await
+    }
+
+    async fn list_pending_expirations<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<PendingExpiration>> {
+        list_pending_expirations(warehouse_id, transaction).await
+    }
+
+    async fn claim_tabular_expiration<'a>(
+        tabular_id: uuid::Uuid,
+        task: &Task,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<TabularExpirationClaim> {
+        claim_tabular_expiration(tabular_id, task, transaction).await
+    }
+
+    async fn reschedule_expirations<'a>(
+        expirations: &[(uuid::Uuid, chrono::DateTime<chrono::Utc>)],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        reschedule_expirations(expirations, transaction).await
+    }
+
+    async fn set_tabular_expiration_exempt<'a>(
+        warehouse_id: WarehouseIdent,
+        tabular_id: uuid::Uuid,
+        exempt: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()> {
+        set_tabular_expiration_exempt(warehouse_id, tabular_id, exempt, transaction).await
+    }
+
+    async fn release_idempotency_keys<'a>(
+        older_than: chrono::Duration,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<u64> {
+        release_idempotency_keys(older_than, transaction).await
+    }
 }
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use super::dbutils::DBErrorHandler as _;
+use super::tabular::{try_parse_namespace_ident, TabularType};
+use crate::service::{
+    CatalogSearchMatch, NamespaceIdentUuid, Result, TableIdent, TabularIdentUuid,
+};
+use crate::WarehouseIdent;
+
+pub(crate) async fn set_namespace_description(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    description: Option<&str>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE namespace
+        SET description = $3
+        WHERE warehouse_id = $1 AND namespace_id = $2
+        "#,
+        *warehouse_id,
+        *namespace_id,
+        description
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting namespace description"))?;
+
+    if updated.rows_affected() == 0 {
+        return Err(ErrorModel::not_found(
+            format!("Namespace {namespace_id} not found in warehouse {warehouse_id}"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+pub(crate) async fn set_tabular_description(
+    warehouse_id: WarehouseIdent,
+    tabular_id: TabularIdentUuid,
+    description: Option<&str>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let typ = match tabular_id {
+        TabularIdentUuid::Table(_) => TabularType::Table,
+        TabularIdentUuid::View(_) => TabularType::View,
+    };
+    let updated = sqlx::query!(
+        r#"
+        UPDATE tabular t
+        SET description = $4
+        FROM namespace n
+        WHERE t.namespace_id = n.namespace_id
+            AND n.warehouse_id = $1
+            AND t.tabular_id = $2
+            AND t.typ = $3
+            AND t.deleted_at IS NULL
+        "#,
+        *warehouse_id,
+        *tabular_id,
+        typ as _,
+        description
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting tabular description"))?;
+
+    if updated.rows_affected() == 0 {
+        return Err(ErrorModel::not_found(
+            format!(
+                "{} {} not found in warehouse {warehouse_id}",
+                tabular_id.typ_str(),
+                *tabular_id
+            ),
+            "NoSuchTabularError",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+pub(crate) async fn get_namespace_descriptions<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    namespace_ids: &[Uuid],
+    connection: E,
+) -> Result<HashMap<Uuid, String>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT namespace_id, description as "description!"
+        FROM namespace
+        WHERE namespace_id = ANY($1) AND description IS NOT NULL
+        "#,
+        namespace_ids
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespace descriptions"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.namespace_id, row.description))
+        .collect())
+}
+
+pub(crate) async fn get_tabular_descriptions<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    tabular_ids: &[Uuid],
+    connection: E,
+) -> Result<HashMap<Uuid, String>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT tabular_id, description as "description!"
+        FROM tabular
+        WHERE tabular_id = ANY($1) AND description IS NOT NULL
+        "#,
+        tabular_ids
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching tabular descriptions"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| (row.tabular_id, row.description))
+        .collect())
+}
+
+/// Namespaces, tables and views of the warehouse whose description contains
+/// words similar to `search`, best matches first.
+pub(crate) async fn search_descriptions<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    search: &str,
+    limit: i64,
+    connection: E,
+) -> Result<Vec<CatalogSearchMatch>> {
+    // Each branch is ordered on its own, so that the trigram indexes can be used.
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            id as "id!",
+            namespace_name as "namespace_name!",
+            name,
+            typ as "typ: TabularType",
+            description as "description!"
+        FROM (
+            (
+                SELECT
+                    namespace_id as id,
+                    namespace_name,
+                    NULL::text as name,
+                    NULL::tabular_type as typ,
+                    description,
+                    $2 <<-> description as distance
+                FROM namespace
+                WHERE warehouse_id = $1
+                    AND description IS NOT NULL
+                    AND $2 <% description
+                ORDER BY $2 <<-> description
+                LIMIT $3
+            )
+            UNION ALL
+            (
+                SELECT
+                    t.tabular_id as id,
+                    n.namespace_name,
+                    t.name,
+                    t.typ,
+                    t.description,
+                    $2 <<-> t.description as distance
+                FROM tabular t
+                INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+                WHERE n.warehouse_id = $1
+                    AND t.description IS NOT NULL
+                    AND t.deleted_at IS NULL
+                    AND t.metadata_location IS NOT NULL
+                    AND $2 <% t.description
+                ORDER BY $2 <<-> t.description
+                LIMIT $3
+            )
+        ) matches
+        ORDER BY distance, id
+        LIMIT $3
+        "#,
+        *warehouse_id,
+        search,
+        limit
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error searching descriptions"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            let namespace = try_parse_namespace_ident(row.namespace_name)?;
+            let description = row.description;
+            Ok(match (row.typ, row.name) {
+                (Some(typ), Some(name)) => CatalogSearchMatch::Tabular {
+                    id: match typ {
+                        TabularType::Table => TabularIdentUuid::Table(row.id),
+                        TabularType::View => TabularIdentUuid::View(row.id),
+                    },
+                    ident: TableIdent { namespace, name },
+                    description,
+                },
+                _ => CatalogSearchMatch::Namespace {
+                    id: row.id.into(),
+                    namespace,
+                    description,
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::{CatalogState, PostgresTransaction};
+    use crate::service::Transaction;
+
+    #[sqlx::test]
+    async fn test_descriptions(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let tabular_id = TabularIdentUuid::from(table.table_id);
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        set_tabular_description(
+            warehouse_id,
+            tabular_id,
+            Some("Daily revenue per customer, deduplicated"),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        // Views and tables share ids, but the type has to match
+        set_tabular_description(
+            warehouse_id,
+            TabularIdentUuid::View(*tabular_id),
+            Some("Not a view"),
+            t.transaction(),
+        )
+        .await
+        .unwrap_err();
+        t.commit().await.unwrap();
+
+        let descriptions = get_tabular_descriptions(&[*tabular_id], &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            descriptions.get(&*tabular_id).map(String::as_str),
+            Some("Daily revenue per customer, deduplicated")
+        );
+
+        let matches = search_descriptions(warehouse_id, "revenue", 10, &pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            matches,
+            vec![CatalogSearchMatch::Tabular {
+                id: tabular_id,
+                ident: table.table_ident.clone(),
+                description: "Daily revenue per customer, deduplicated".to_string(),
+            }]
+        );
+        let matches = search_descriptions(warehouse_id, "inventory", 10, &pool)
+            .await
+            .unwrap();
+        assert!(matches.is_empty());
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        set_tabular_description(warehouse_id, tabular_id, None, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+        let descriptions = get_tabular_descriptions(&[*tabular_id], &pool)
+            .await
+            .unwrap();
+        assert!(descriptions.is_empty());
+    }
+}
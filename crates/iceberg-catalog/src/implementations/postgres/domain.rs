@@ -0,0 +1,396 @@
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use super::dbutils::DBErrorHandler as _;
+use super::tabular::try_parse_namespace_ident;
+use crate::api::management::v1::domain::{Domain, DomainNamespace};
+use crate::service::{DomainId, NamespaceIdentUuid, Result};
+use crate::{ProjectIdent, WarehouseIdent};
+
+#[derive(Debug)]
+struct DomainRow {
+    domain_id: Uuid,
+    project_id: Uuid,
+    name: String,
+    description: Option<String>,
+    tags: Vec<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<DomainRow> for Domain {
+    fn from(
+        DomainRow {
+            domain_id,
+            project_id,
+            name,
+            description,
+            tags,
+            created_at,
+            updated_at,
+        }: DomainRow,
+    ) -> Self {
+        Self {
+            id: DomainId::new(domain_id),
+            name,
+            description,
+            tags,
+            project_id: ProjectIdent::from(project_id),
+            created_at,
+            updated_at,
+        }
+    }
+}
+
+fn map_domain_write_error(e: sqlx::Error, project_id: Option<ProjectIdent>) -> ErrorModel {
+    match e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => ErrorModel::conflict(
+            "A domain with this name already exists in the project",
+            "DomainAlreadyExists",
+            Some(Box::new(db_error)),
+        ),
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+            ErrorModel::not_found(
+                format!(
+                    "Project {} not found",
+                    project_id.map(|p| p.to_string()).unwrap_or_default()
+                ),
+                "ProjectNotFound",
+                Some(Box::new(db_error)),
+            )
+        }
+        _ => e.into_error_model("Error writing domain"),
+    }
+}
+
+pub(crate) async fn create_domain<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    domain_id: DomainId,
+    project_id: ProjectIdent,
+    name: &str,
+    description: Option<&str>,
+    tags: &[String],
+    connection: E,
+) -> Result<Domain> {
+    let domain = sqlx::query_as!(
+        DomainRow,
+        r#"
+        INSERT INTO domain (domain_id, project_id, name, description, tags)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING domain_id, project_id, name, description, tags, created_at, updated_at
+        "#,
+        *domain_id,
+        *project_id,
+        name,
+        description,
+        tags
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| map_domain_write_error(e, Some(project_id)))?;
+
+    Ok(Domain::from(domain))
+}
+
+pub(crate) async fn get_domain<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    domain_id: DomainId,
+    connection: E,
+) -> Result<Option<Domain>> {
+    let domain = sqlx::query_as!(
+        DomainRow,
+        r#"
+        SELECT domain_id, project_id, name, description, tags, created_at, updated_at
+        FROM domain
+        WHERE domain_id = $1
+        "#,
+        *domain_id
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching domain"))?;
+
+    Ok(domain.map(Domain::from))
+}
+
+pub(crate) async fn list_domains<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    project_id: ProjectIdent,
+    tag: Option<&str>,
+    connection: E,
+) -> Result<Vec<Domain>> {
+    let domains = sqlx::query_as!(
+        DomainRow,
+        r#"
+        SELECT domain_id, project_id, name, description, tags, created_at, updated_at
+        FROM domain
+        WHERE project_id = $1 AND ($2::text IS NULL OR tags @> ARRAY[$2::text])
+        ORDER BY name, domain_id
+        "#,
+        *project_id,
+        tag
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing domains"))?;
+
+    Ok(domains.into_iter().map(Domain::from).collect())
+}
+
+pub(crate) async fn update_domain<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    domain_id: DomainId,
+    name: &str,
+    description: Option<&str>,
+    tags: &[String],
+    connection: E,
+) -> Result<Option<Domain>> {
+    let domain = sqlx::query_as!(
+        DomainRow,
+        r#"
+        UPDATE domain
+        SET name = $2, description = $3, tags = $4
+        WHERE domain_id = $1
+        RETURNING domain_id, project_id, name, description, tags, created_at, updated_at
+        "#,
+        *domain_id,
+        name,
+        description,
+        tags
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| map_domain_write_error(e, None))?;
+
+    Ok(domain.map(Domain::from))
+}
+
+pub(crate) async fn delete_domain<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    domain_id: DomainId,
+    connection: E,
+) -> Result<Option<()>> {
+    let deleted = sqlx::query!("DELETE FROM domain WHERE domain_id = $1", *domain_id)
+        .execute(connection)
+        .await
+        .map_err(|e| e.into_error_model("Error deleting domain"))?;
+
+    Ok((deleted.rows_affected() > 0).then_some(()))
+}
+
+/// Returns `false` if the namespace already was part of the domain.
+pub(crate) async fn add_domain_namespace(
+    domain_id: DomainId,
+    namespace_id: NamespaceIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<bool> {
+    // Only namespaces of warehouses in the project of the domain can be added.
+    let inserted = sqlx::query!(
+        r#"
+        INSERT INTO domain_namespace (namespace_id, domain_id)
+        SELECT n.namespace_id, d.domain_id
+        FROM namespace n
+        INNER JOIN warehouse w ON w.warehouse_id = n.warehouse_id
+        INNER JOIN domain d ON d.project_id = w.project_id
+        WHERE n.namespace_id = $1 AND d.domain_id = $2
+        ON CONFLICT (namespace_id) DO NOTHING
+        "#,
+        *namespace_id,
+        *domain_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error adding namespace to domain"))?;
+
+    if inserted.rows_affected() > 0 {
+        return Ok(true);
+    }
+
+    let existing_domain = sqlx::query_scalar!(
+        r#"
+        SELECT domain_id FROM domain_namespace WHERE namespace_id = $1
+        "#,
+        *namespace_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching domain of namespace"))?;
+
+    match existing_domain {
+        Some(existing) if existing == *domain_id => Ok(false),
+        Some(existing) => Err(ErrorModel::conflict(
+            format!("Namespace {namespace_id} already belongs to domain {existing}"),
+            "NamespaceInOtherDomain",
+            None,
+        )
+        .into()),
+        None => Err(ErrorModel::bad_request(
+            format!(
+                "Namespace {namespace_id} or domain {domain_id} not found, or the namespace does not belong to the project of the domain"
+            ),
+            "NamespaceNotInDomainProject",
+            None,
+        )
+        .into()),
+    }
+}
+
+pub(crate) async fn remove_domain_namespace<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    domain_id: DomainId,
+    namespace_id: NamespaceIdentUuid,
+    connection: E,
+) -> Result<Option<()>> {
+    let deleted = sqlx::query!(
+        "DELETE FROM domain_namespace WHERE domain_id = $1 AND namespace_id = $2",
+        *domain_id,
+        *namespace_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error removing namespace from domain"))?;
+
+    Ok((deleted.rows_affected() > 0).then_some(()))
+}
+
+pub(crate) async fn list_domain_namespaces<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    domain_id: DomainId,
+    connection: E,
+) -> Result<Vec<DomainNamespace>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT w.warehouse_id, w.warehouse_name, n.namespace_id, n.namespace_name
+        FROM domain_namespace dn
+        INNER JOIN namespace n ON n.namespace_id = dn.namespace_id
+        INNER JOIN warehouse w ON w.warehouse_id = n.warehouse_id
+        WHERE dn.domain_id = $1 AND w.status = 'active'
+        ORDER BY w.warehouse_name, n.namespace_name
+        "#,
+        *domain_id
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing namespaces of domain"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(DomainNamespace {
+                warehouse_id: WarehouseIdent::from(row.warehouse_id),
+                warehouse_name: row.warehouse_name,
+                namespace_id: NamespaceIdentUuid::from(row.namespace_id),
+                namespace: try_parse_namespace_ident(row.namespace_name)?.inner(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::postgres::namespace::tests::initialize_namespace;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::{CatalogState, PostgresTransaction};
+    use crate::service::Transaction;
+
+    #[sqlx::test]
+    async fn test_domain_namespaces(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let warehouse = sqlx::query!(
+            "SELECT project_id, warehouse_name FROM warehouse WHERE warehouse_id = $1",
+            *warehouse_id
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let project_id = ProjectIdent::from(warehouse.project_id);
+        let warehouse_name = warehouse.warehouse_name;
+        let namespace = iceberg::NamespaceIdent::new("sales".to_string());
+        let (namespace_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let domain_id = DomainId::default();
+        let domain = create_domain(
+            domain_id,
+            project_id,
+            "Sales",
+            None,
+            &["revenue".to_string()],
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(domain.tags, vec!["revenue".to_string()]);
+        // Names are unique per project, case-insensitive
+        create_domain(
+            DomainId::default(),
+            project_id,
+            "sales",
+            None,
+            &[],
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap_err();
+        t.commit().await.unwrap();
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        assert!(
+            add_domain_namespace(domain_id, namespace_id, t.transaction())
+                .await
+                .unwrap()
+        );
+        assert!(
+            !add_domain_namespace(domain_id, namespace_id, t.transaction())
+                .await
+                .unwrap()
+        );
+        let other_domain = create_domain(
+            DomainId::default(),
+            project_id,
+            "Marketing",
+            None,
+            &[],
+            &mut **t.transaction(),
+        )
+        .await
+        .unwrap();
+        let err = add_domain_namespace(other_domain.id, namespace_id, t.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "NamespaceInOtherDomain");
+        t.commit().await.unwrap();
+
+        let namespaces = list_domain_namespaces(domain_id, &pool).await.unwrap();
+        assert_eq!(
+            namespaces,
+            vec![DomainNamespace {
+                warehouse_id,
+                warehouse_name,
+                namespace_id,
+                namespace: namespace.clone().inner(),
+            }]
+        );
+        let tagged = list_domains(project_id, Some("revenue"), &pool)
+            .await
+            .unwrap();
+        assert_eq!(tagged, vec![domain]);
+
+        delete_domain(domain_id, &pool).await.unwrap().unwrap();
+        assert!(list_domain_namespaces(domain_id, &pool)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(remove_domain_namespace(domain_id, namespace_id, &pool)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
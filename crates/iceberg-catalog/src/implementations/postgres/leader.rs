@@ -0,0 +1,167 @@
+//! Leader election for duties that must only run on a single replica.
+//!
+//! Leadership is bound to a session level advisory lock on a dedicated connection. If the
+//! leader crashes or loses its connection, Postgres releases the lock and another replica
+//! takes over on its next attempt.
+use axum_prometheus::metrics::{counter, gauge};
+use futures::future::BoxFuture;
+use sqlx::{Connection, PgConnection, PgPool};
+use std::time::Duration;
+
+/// `1` on the replica that currently performs a duty, `0` on all others.
+const LEADER: &str = "lakekeeper_leader";
+const LEADER_ACQUISITIONS_TOTAL: &str = "lakekeeper_leader_acquisitions_total";
+
+/// First key of all advisory locks taken by Lakekeeper. The second key is derived from
+/// the name of the lock, so that locks don't collide with those of other applications.
+pub(crate) const ADVISORY_LOCK_NAMESPACE: i32 = 0x4c4b_4b50;
+
+/// Leadership for a single duty. Dropping the lock closes the connection and thereby
+/// releases the leadership.
+#[derive(Debug)]
+pub struct LeaderLock {
+    duty: &'static str,
+    connection: PgConnection,
+}
+
+impl LeaderLock {
+    /// Try to become the leader for `duty`. Returns `None` if another session holds the lock.
+    ///
+    /// # Errors
+    /// Fails if no connection can be acquired or the lock query fails.
+    pub async fn try_acquire(pool: &PgPool, duty: &'static str) -> sqlx::Result<Option<Self>> {
+        // The connection is detached from the pool so that the lock is not returned
+        // to the pool with the connection and does not count against its size.
+        let mut connection = pool.acquire().await?.detach();
+        let acquired = sqlx::query_scalar!(
+            r#"SELECT pg_try_advisory_lock($1, hashtext($2)) as "acquired!""#,
+            ADVISORY_LOCK_NAMESPACE,
+            duty
+        )
+        .fetch_one(&mut connection)
+        .await?;
+        Ok(acquired.then_some(Self { duty, connection }))
+    }
+
+    /// Check that the session holding the lock is still alive.
+    pub async fn is_held(&mut self) -> bool {
+        self.connection.ping().await.is_ok()
+    }
+
+    #[must_use]
+    pub fn duty(&self) -> &'static str {
+        self.duty
+    }
+
+    /// Release the leadership.
+    ///
+    /// # Errors
+    /// Fails if the connection is broken. The lock is released by Postgres in this case.
+    pub async fn release(self) -> sqlx::Result<()> {
+        self.connection.close().await
+    }
+}
+
+/// Run `task` only while this replica is the leader for `duty`.
+///
+/// Replicas that are not the leader retry every `interval`. The leader checks its
+/// connection every `interval` and aborts `task` once it lost the lock. `task` is
+/// restarted whenever leadership is acquired, so it should be an endless loop that
+/// tolerates being cancelled. Returns if `task` returns.
+pub async fn run_as_leader<F>(pool: PgPool, duty: &'static str, interval: Duration, task: F)
+where
+    F: Fn() -> BoxFuture<'static, ()> + Send,
+{
+    loop {
+        gauge!(LEADER, "duty" => duty).set(0.);
+        let mut lock = match LeaderLock::try_acquire(&pool, duty).await {
+            Ok(Some(lock)) => lock,
+            Ok(None) => {
+                tracing::debug!("Another replica is the leader for {duty}");
+                tokio::time::sleep(interval).await;
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to run leader election for {duty}: {e}");
+                tokio::time::sleep(interval).await;
+                continue;
+            }
+        };
+
+        tracing::info!("Acquired leadership for {duty}");
+        counter!(LEADER_ACQUISITIONS_TOTAL, "duty" => duty).increment(1);
+        gauge!(LEADER, "duty" => duty).set(1.);
+
+        let lost_leadership = async {
+            loop {
+                tokio::time::sleep(interval).await;
+                if !lock.is_held().await {
+                    return;
+                }
+            }
+        };
+        tokio::select! {
+            () = task() => {
+                gauge!(LEADER, "duty" => duty).set(0.);
+                if let Err(e) = lock.release().await {
+                    tracing::debug!("Failed to release leadership for {duty}: {e}");
+                }
+                return;
+            }
+            () = lost_leadership => {
+                tracing::warn!("Lost leadership for {duty}, stopping it on this replica");
+            }
+        }
+    }
+}
+
+/// Run `f` while holding an exclusive advisory lock named `name`. Waits until
+/// other holders of the lock are finished.
+///
+/// # Errors
+/// Fails if the lock cannot be acquired or `f` fails.
+pub async fn with_exclusive_lock<T, F>(pool: &PgPool, name: &str, f: F) -> anyhow::Result<T>
+where
+    F: std::future::Future<Output = anyhow::Result<T>>,
+{
+    let mut connection = pool.acquire().await?.detach();
+    sqlx::query!(
+        "SELECT pg_advisory_lock($1, hashtext($2))",
+        ADVISORY_LOCK_NAMESPACE,
+        name
+    )
+    .execute(&mut connection)
+    .await?;
+    let result = f.await;
+    // Closing the session releases the lock, even if `f` failed.
+    if let Err(e) = connection.close().await {
+        tracing::debug!("Failed to close connection holding lock {name}: {e}");
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_single_leader(pool: PgPool) {
+        let first = LeaderLock::try_acquire(&pool, "test-duty")
+            .await
+            .unwrap()
+            .expect("First replica should become leader");
+        assert!(LeaderLock::try_acquire(&pool, "test-duty")
+            .await
+            .unwrap()
+            .is_none());
+        // Other duties are independent
+        let other = LeaderLock::try_acquire(&pool, "other-duty").await.unwrap();
+        assert!(other.is_some());
+
+        first.release().await.unwrap();
+        assert!(LeaderLock::try_acquire(&pool, "test-duty")
+            .await
+            .unwrap()
+            .is_some());
+    }
+}
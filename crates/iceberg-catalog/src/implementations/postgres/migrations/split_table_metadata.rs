@@ -97,6 +97,7 @@ async fn split_table_metadata(
                             table_ident: tab.0.clone().as_table()?,
                             metadata_location: table.metadata_location.as_ref(),
                             table_metadata: table.table_metadata,
+                            force_expire_property: None,
                         },
                         transaction,
                     )
@@ -162,6 +163,7 @@ mod test {
                     },
                     table_metadata: js,
                     metadata_location: None,
+                    force_expire_property: None,
                 },
                 &mut trx,
             )
@@ -204,6 +206,7 @@ mod test {
                     },
                     table_metadata: js,
                     metadata_location: None,
+                    force_expire_property: None,
                 },
                 &mut trx,
             )
@@ -298,6 +301,7 @@ mod test {
                     },
                     table_metadata: js,
                     metadata_location: None,
+                    force_expire_property: None,
                 },
                 &mut trx,
             )
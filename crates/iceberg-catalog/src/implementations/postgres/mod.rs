@@ -1,11 +1,20 @@
 mod bootstrap;
 mod catalog;
 pub(crate) mod dbutils;
+mod description;
+mod domain;
+pub mod leader;
 pub mod migrations;
 pub(crate) mod namespace;
+mod notice;
 mod pagination;
+pub mod pool_metrics;
+mod property_history;
 pub(crate) mod role;
 pub(crate) mod secrets;
+mod storage_usage;
+#[cfg(feature = "synthetic-data")]
+pub mod synthetic;
 pub mod tabular;
 pub mod task_queues;
 pub(crate) mod user;
@@ -61,21 +70,29 @@ impl crate::service::Transaction<CatalogState> for PostgresTransaction {
     type Transaction<'a> = &'a mut sqlx::Transaction<'static, sqlx::Postgres>;
 
     async fn begin_write(db_state: CatalogState) -> Result<Self> {
-        let transaction = db_state
-            .write_pool()
-            .begin()
-            .await
-            .map_err(|e| e.into_error_model("Error starting transaction".to_string()))?;
+        let pool = db_state.read_write.metric_labels.write;
+        let start = std::time::Instant::now();
+        let transaction = db_state.write_pool().begin().await.map_err(|e| {
+            if matches!(e, sqlx::Error::PoolTimedOut) {
+                pool_metrics::record_timeout(pool);
+            }
+            e.into_error_model("Error starting transaction".to_string())
+        })?;
+        pool_metrics::record_acquire(pool, start.elapsed());
 
         Ok(Self { transaction })
     }
 
     async fn begin_read(db_state: CatalogState) -> Result<Self> {
-        let mut transaction = db_state
-            .read_pool()
-            .begin()
-            .await
-            .map_err(|e| e.into_error_model("Error starting transaction".to_string()))?;
+        let pool = db_state.read_write.metric_labels.read;
+        let start = std::time::Instant::now();
+        let mut transaction = db_state.read_pool().begin().await.map_err(|e| {
+            if matches!(e, sqlx::Error::PoolTimedOut) {
+                pool_metrics::record_timeout(pool);
+            }
+            e.into_error_model("Error starting transaction".to_string())
+        })?;
+        pool_metrics::record_acquire(pool, start.elapsed());
 
         transaction
             .execute("SET TRANSACTION READ ONLY")
@@ -112,6 +129,14 @@ pub struct ReadWrite {
     pub read_pool: sqlx::PgPool,
     pub write_pool: sqlx::PgPool,
     pub health: Arc<RwLock<Vec<Health>>>,
+    metric_labels: PoolMetricLabels,
+}
+
+/// Values of the `pool` label of the metrics recorded for the pools of a [`ReadWrite`].
+#[derive(Clone, Copy, Debug)]
+struct PoolMetricLabels {
+    read: &'static str,
+    write: &'static str,
 }
 
 #[async_trait]
@@ -144,9 +169,41 @@ impl ReadWrite {
                 Health::now("read_pool", HealthStatus::Unknown),
                 Health::now("write_pool", HealthStatus::Unknown),
             ])),
+            metric_labels: PoolMetricLabels {
+                read: "read",
+                write: "write",
+            },
+        }
+    }
+
+    /// Set the `pool` label of the metrics recorded for the read and the write pool.
+    #[must_use]
+    pub fn with_metric_labels(mut self, read: &'static str, write: &'static str) -> Self {
+        self.metric_labels = PoolMetricLabels { read, write };
+        self
+    }
+
+    /// Pools used by background tasks. Tasks get the dedicated `task_pools` (read, write) if
+    /// provided, so that they cannot exhaust the connections of `self`, and share the pools
+    /// of `self` otherwise.
+    #[must_use]
+    pub fn for_tasks(&self, task_pools: Option<(PgPool, PgPool)>) -> Self {
+        match task_pools {
+            Some((read_pool, write_pool)) => Self::from_pools(read_pool, write_pool)
+                .with_metric_labels("task-read", "task-write"),
+            None => self.clone(),
         }
     }
 
+    /// The read and the write pool with the `pool` label of their metrics.
+    #[must_use]
+    pub fn labeled_pools(&self) -> Vec<(&'static str, PgPool)> {
+        vec![
+            (self.metric_labels.read, self.read_pool.clone()),
+            (self.metric_labels.write, self.write_pool.clone()),
+        ]
+    }
+
     #[cfg(feature = "sqlx-postgres")]
     async fn health(pool: PgPool) -> HealthStatus {
         match sqlx::query("SELECT 1").fetch_one(&pool).await {
@@ -1,11 +1,13 @@
 use super::dbutils::DBErrorHandler;
-use crate::api::iceberg::v1::{PaginatedMapping, MAX_PAGE_SIZE};
-use crate::implementations::postgres::pagination::{PaginateToken, V1PaginateToken};
+use crate::api::iceberg::v1::{PaginatedMapping, ReturnTotal, MAX_PAGE_SIZE};
+use crate::implementations::postgres::pagination::{count_rows, PaginateToken, V1PaginateToken};
+use crate::implementations::postgres::property_history::record_namespace_properties;
+use crate::service::id_generation::PROPERTY_EXTERNAL_ID;
 use crate::service::{
     CreateNamespaceRequest, CreateNamespaceResponse, ErrorModel, GetNamespaceResponse,
     ListNamespacesQuery, NamespaceIdent, Result,
 };
-use crate::{catalog::namespace::MAX_NAMESPACE_DEPTH, service::NamespaceIdentUuid, WarehouseIdent};
+use crate::{service::NamespaceIdentUuid, WarehouseIdent, CONFIG};
 use chrono::Utc;
 use http::StatusCode;
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
@@ -68,6 +70,8 @@ pub(crate) async fn list_namespaces(
         page_size,
         parent,
         return_uuids: _,
+        return_total: _,
+        return_descriptions: _,
     }: &ListNamespacesQuery,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<PaginatedMapping<NamespaceIdentUuid, NamespaceIdent>> {
@@ -94,7 +98,7 @@ pub(crate) async fn list_namespaces(
     let namespaces: Vec<(Uuid, Vec<String>, chrono::DateTime<Utc>)> = if let Some(parent) = parent {
         // If it doesn't fit in a i32 it is way too large. Validation would have failed
         // already in the catalog.
-        let parent_len: i32 = parent.len().try_into().unwrap_or(MAX_NAMESPACE_DEPTH + 1);
+        let parent_len: i32 = parent.len().try_into().unwrap_or(i32::MAX);
 
         // Namespace name field is an array.
         // Get all namespaces where the "name" array has
@@ -188,6 +192,39 @@ pub(crate) async fn list_namespaces(
     Ok(namespace_map)
 }
 
+pub(crate) async fn count_namespaces(
+    warehouse_id: WarehouseIdent,
+    parent: Option<&NamespaceIdent>,
+    mode: ReturnTotal,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<i64> {
+    let parent = parent.map(|p| p.clone().inner()).unwrap_or_default();
+    let parent_len: i32 = parent.len().try_into().unwrap_or(i32::MAX);
+
+    count_rows(
+        mode,
+        |query_builder| {
+            query_builder
+                .push(
+                    "FROM namespace n INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id WHERE n.warehouse_id = ",
+                )
+                .push_bind(*warehouse_id)
+                .push(" AND w.status = 'active' AND array_length(\"namespace_name\", 1) = ")
+                .push_bind(parent_len)
+                .push(" + 1");
+            if parent_len > 0 {
+                query_builder
+                    .push(" AND \"namespace_name\"[1:")
+                    .push_bind(parent_len)
+                    .push("] = ")
+                    .push_bind(parent.clone());
+            }
+        },
+        transaction,
+    )
+    .await
+}
+
 pub(crate) async fn create_namespace(
     warehouse_id: WarehouseIdent,
     namespace_id: NamespaceIdentUuid,
@@ -198,12 +235,16 @@ pub(crate) async fn create_namespace(
         namespace,
         properties,
     } = request;
+    let external_id = properties
+        .as_ref()
+        .and_then(|properties| properties.get(PROPERTY_EXTERNAL_ID))
+        .cloned();
 
     let _namespace_id = sqlx::query_scalar!(
         r#"
-        INSERT INTO namespace (warehouse_id, namespace_id, namespace_name, namespace_properties)
+        INSERT INTO namespace (warehouse_id, namespace_id, namespace_name, namespace_properties, external_id)
         (
-            SELECT $1, $2, $3, $4
+            SELECT $1, $2, $3, $4, $5
             WHERE EXISTS (
                 SELECT 1
                 FROM warehouse
@@ -222,13 +263,23 @@ pub(crate) async fn create_namespace(
                 .r#type("NamespacePropertiesSerializationError".to_string())
                 .source(Some(Box::new(e)))
                 .build()
-        })?
+        })?,
+        external_id.as_deref(),
     )
     .fetch_one(&mut **transaction)
     .await
     .map_err(|e| match e {
         sqlx::Error::Database(db_error) => {
-            if db_error.is_unique_violation() {
+            if db_error.constraint() == Some("namespace_external_id_idx") {
+                ErrorModel::conflict(
+                    format!(
+                        "A namespace with external id '{}' already exists",
+                        external_id.as_deref().unwrap_or_default()
+                    ),
+                    "ExternalIdAlreadyExists",
+                    None,
+                )
+            } else if db_error.is_unique_violation() {
                 ErrorModel::builder()
                     .code(StatusCode::CONFLICT.into())
                     .message("Namespace already exists".to_string())
@@ -387,6 +438,7 @@ pub(crate) async fn update_namespace_properties(
             .source(Some(Box::new(e)))
             .build()
     })?;
+    record_namespace_properties(warehouse_id, namespace_id, &properties, transaction).await?;
 
     sqlx::query!(
         r#"
@@ -408,6 +460,70 @@ pub(crate) async fn update_namespace_properties(
     Ok(())
 }
 
+/// Rename or move a namespace. Child namespaces are moved along, as their names start with
+/// the name of the renamed namespace.
+pub(crate) async fn rename_namespace(
+    warehouse_id: WarehouseIdent,
+    source: &NamespaceIdent,
+    destination: &NamespaceIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let depths: Vec<Option<i32>> = sqlx::query_scalar!(
+        r#"
+        UPDATE namespace
+        SET namespace_name = $3::text[] || namespace_name[array_length($2::text[], 1) + 1:]
+        WHERE warehouse_id = $1
+        AND namespace_name[1:array_length($2::text[], 1)] = $2
+        AND warehouse_id IN (
+            SELECT warehouse_id FROM warehouse WHERE status = 'active'
+        )
+        RETURNING array_length(namespace_name, 1)
+        "#,
+        *warehouse_id,
+        source.as_ref(),
+        destination.as_ref()
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => ErrorModel::conflict(
+            format!(
+                "Namespace {} or one of its children already exists",
+                destination.as_ref().join(".")
+            ),
+            "NamespaceAlreadyExists",
+            Some(Box::new(e)),
+        ),
+        _ => e.into_error_model("Error renaming namespace"),
+    })?;
+
+    if depths.is_empty() {
+        return Err(ErrorModel::not_found(
+            format!("Namespace {} not found", source.as_ref().join(".")),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+
+    // Moving a namespace below another one makes all of its children deeper.
+    if depths.into_iter().any(|depth| {
+        usize::try_from(depth.unwrap_or_default()).unwrap_or_default() > CONFIG.max_namespace_depth
+    }) {
+        return Err(ErrorModel::bad_request(
+            format!(
+                "Namespace exceeds maximum depth of {}",
+                CONFIG.max_namespace_depth
+            ),
+            "NamespaceDepthExceeded",
+            None,
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use crate::implementations::postgres::{CatalogState, PostgresTransaction};
@@ -503,6 +619,8 @@ pub(crate) mod tests {
                 page_size: None,
                 parent: None,
                 return_uuids: false,
+                return_total: None,
+                return_descriptions: false,
             },
             transaction.transaction(),
         )
@@ -591,6 +709,8 @@ pub(crate) mod tests {
                 page_size: Some(1),
                 parent: None,
                 return_uuids: false,
+                return_total: None,
+                return_descriptions: false,
             },
             t.transaction(),
         )
@@ -617,6 +737,8 @@ pub(crate) mod tests {
                 page_size: Some(2),
                 parent: None,
                 return_uuids: false,
+                return_total: None,
+                return_descriptions: false,
             },
             t.transaction(),
         )
@@ -644,6 +766,8 @@ pub(crate) mod tests {
                 page_size: Some(3),
                 parent: None,
                 return_uuids: false,
+                return_total: None,
+                return_descriptions: false,
             },
             t.transaction(),
         )
@@ -654,6 +778,62 @@ pub(crate) mod tests {
         assert_eq!(namespaces.into_hashmap(), HashMap::new());
     }
 
+    #[sqlx::test]
+    async fn test_count_namespaces(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let parent = NamespaceIdent::from_vec(vec!["parent".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &parent, None).await;
+        for name in ["a", "b", "c"] {
+            let child =
+                NamespaceIdent::from_vec(vec!["parent".to_string(), name.to_string()]).unwrap();
+            initialize_namespace(state.clone(), warehouse_id, &child, None).await;
+        }
+        let other = NamespaceIdent::from_vec(vec!["other".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &other, None).await;
+        sqlx::query!("ANALYZE namespace")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut t = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+
+        let top_level = PostgresCatalog::count_namespaces(
+            warehouse_id,
+            None,
+            ReturnTotal::Exact,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(top_level, 2);
+
+        let children = PostgresCatalog::count_namespaces(
+            warehouse_id,
+            Some(&parent),
+            ReturnTotal::Exact,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(children, 3);
+
+        let estimate = PostgresCatalog::count_namespaces(
+            warehouse_id,
+            Some(&parent),
+            ReturnTotal::Estimate,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        // The planner never estimates zero rows and cannot exceed the 5 namespaces that exist.
+        assert!((1..=5).contains(&estimate), "{estimate}");
+        t.commit().await.unwrap();
+    }
+
     #[sqlx::test]
     async fn test_cannot_drop_nonempty_namespace(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -756,4 +936,144 @@ pub(crate) mod tests {
         assert_eq!(response.error.code, StatusCode::CONFLICT);
         assert_eq!(response.error.r#type, "NamespaceAlreadyExists");
     }
+
+    #[sqlx::test]
+    async fn test_rename_namespace(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let ident = |parts: &[&str]| NamespaceIdent::from_strs(parts).unwrap();
+
+        let (sales_id, _) =
+            initialize_namespace(state.clone(), warehouse_id, &ident(&["sales"]), None).await;
+        let (emea_id, _) = initialize_namespace(
+            state.clone(),
+            warehouse_id,
+            &ident(&["sales", "emea"]),
+            None,
+        )
+        .await;
+        let (sales_archive_id, _) = initialize_namespace(
+            state.clone(),
+            warehouse_id,
+            &ident(&["sales_archive"]),
+            None,
+        )
+        .await;
+        initialize_namespace(state.clone(), warehouse_id, &ident(&["revenue"]), None).await;
+
+        // The new name is taken
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = rename_namespace(
+            warehouse_id,
+            &ident(&["sales"]),
+            &ident(&["revenue"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::CONFLICT);
+        transaction.rollback().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        rename_namespace(
+            warehouse_id,
+            &ident(&["sales"]),
+            &ident(&["commerce"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        for (id, expected) in [
+            (sales_id, ident(&["commerce"])),
+            (emea_id, ident(&["commerce", "emea"])),
+            (sales_archive_id, ident(&["sales_archive"])),
+        ] {
+            let namespace =
+                PostgresCatalog::get_namespace(warehouse_id, id, transaction.transaction())
+                    .await
+                    .unwrap()
+                    .namespace;
+            assert_eq!(namespace, expected);
+        }
+        let err = rename_namespace(
+            warehouse_id,
+            &ident(&["sales"]),
+            &ident(&["sales_new"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, StatusCode::NOT_FOUND);
+    }
+
+    #[sqlx::test]
+    async fn test_move_namespace(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let ident = |parts: &[&str]| NamespaceIdent::from_strs(parts).unwrap();
+
+        let (emea_id, _) = initialize_namespace(
+            state.clone(),
+            warehouse_id,
+            &ident(&["sales", "emea"]),
+            None,
+        )
+        .await;
+        let (raw_id, _) = initialize_namespace(
+            state.clone(),
+            warehouse_id,
+            &ident(&["sales", "emea", "raw"]),
+            None,
+        )
+        .await;
+        initialize_namespace(state.clone(), warehouse_id, &ident(&["marketing"]), None).await;
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        rename_namespace(
+            warehouse_id,
+            &ident(&["sales", "emea"]),
+            &ident(&["marketing", "emea"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        transaction.commit().await.unwrap();
+
+        let mut transaction = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        for (id, expected) in [
+            (emea_id, ident(&["marketing", "emea"])),
+            (raw_id, ident(&["marketing", "emea", "raw"])),
+        ] {
+            let namespace =
+                PostgresCatalog::get_namespace(warehouse_id, id, transaction.transaction())
+                    .await
+                    .unwrap()
+                    .namespace;
+            assert_eq!(namespace, expected);
+        }
+
+        // The child would exceed the maximum depth
+        let err = rename_namespace(
+            warehouse_id,
+            &ident(&["marketing", "emea"]),
+            &ident(&["a", "b", "c", "emea"]),
+            transaction.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.r#type, "NamespaceDepthExceeded");
+    }
 }
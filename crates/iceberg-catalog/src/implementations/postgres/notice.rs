@@ -0,0 +1,167 @@
+use uuid::Uuid;
+
+use super::dbutils::DBErrorHandler as _;
+use crate::api::management::v1::notice::{Notice, NoticeSeverity};
+use crate::service::Result;
+
+#[derive(sqlx::Type, Debug, Clone, Copy)]
+#[sqlx(rename_all = "kebab-case", type_name = "notice_severity")]
+enum DbNoticeSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl From<DbNoticeSeverity> for NoticeSeverity {
+    fn from(severity: DbNoticeSeverity) -> Self {
+        match severity {
+            DbNoticeSeverity::Info => NoticeSeverity::Info,
+            DbNoticeSeverity::Warning => NoticeSeverity::Warning,
+            DbNoticeSeverity::Critical => NoticeSeverity::Critical,
+        }
+    }
+}
+
+impl From<NoticeSeverity> for DbNoticeSeverity {
+    fn from(severity: NoticeSeverity) -> Self {
+        match severity {
+            NoticeSeverity::Info => DbNoticeSeverity::Info,
+            NoticeSeverity::Warning => DbNoticeSeverity::Warning,
+            NoticeSeverity::Critical => DbNoticeSeverity::Critical,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NoticeRow {
+    notice_id: Uuid,
+    message: String,
+    severity: DbNoticeSeverity,
+    starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<NoticeRow> for Notice {
+    fn from(row: NoticeRow) -> Self {
+        Self {
+            id: row.notice_id,
+            message: row.message,
+            severity: row.severity.into(),
+            starts_at: row.starts_at,
+            ends_at: row.ends_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+pub(crate) async fn create_notice<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    notice_id: Uuid,
+    message: &str,
+    severity: NoticeSeverity,
+    starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    connection: E,
+) -> Result<Notice> {
+    let row = sqlx::query_as!(
+        NoticeRow,
+        r#"
+        INSERT INTO notice (notice_id, message, severity, starts_at, ends_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING notice_id, message, severity as "severity: DbNoticeSeverity", starts_at, ends_at, created_at
+        "#,
+        notice_id,
+        message,
+        DbNoticeSeverity::from(severity) as _,
+        starts_at,
+        ends_at
+    )
+    .fetch_one(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error creating notice"))?;
+
+    Ok(row.into())
+}
+
+/// Active notices, or all notices if `include_inactive` is set. Most severe first.
+pub(crate) async fn list_notices<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    include_inactive: bool,
+    connection: E,
+) -> Result<Vec<Notice>> {
+    let rows = sqlx::query_as!(
+        NoticeRow,
+        r#"
+        SELECT notice_id, message, severity as "severity: DbNoticeSeverity", starts_at, ends_at, created_at
+        FROM notice
+        WHERE $1
+            OR ((starts_at IS NULL OR starts_at <= now()) AND (ends_at IS NULL OR ends_at > now()))
+        ORDER BY severity DESC, created_at DESC
+        "#,
+        include_inactive
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing notices"))?;
+
+    Ok(rows.into_iter().map(Notice::from).collect())
+}
+
+pub(crate) async fn delete_notice<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
+    notice_id: Uuid,
+    connection: E,
+) -> Result<Option<()>> {
+    let deleted = sqlx::query!("DELETE FROM notice WHERE notice_id = $1", notice_id)
+        .execute(connection)
+        .await
+        .map_err(|e| e.into_error_model("Error deleting notice"))?;
+
+    Ok((deleted.rows_affected() > 0).then_some(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_list_active_notices(pool: sqlx::PgPool) {
+        let now = chrono::Utc::now();
+        let active = create_notice(
+            Uuid::now_v7(),
+            "Upgrade to v0.7 on Friday",
+            NoticeSeverity::Info,
+            None,
+            Some(now + chrono::Duration::days(1)),
+            &pool,
+        )
+        .await
+        .unwrap();
+        let critical = create_notice(
+            Uuid::now_v7(),
+            "Degraded performance",
+            NoticeSeverity::Critical,
+            Some(now - chrono::Duration::hours(1)),
+            None,
+            &pool,
+        )
+        .await
+        .unwrap();
+        let scheduled = create_notice(
+            Uuid::now_v7(),
+            "Maintenance window",
+            NoticeSeverity::Warning,
+            Some(now + chrono::Duration::days(1)),
+            Some(now + chrono::Duration::days(2)),
+            &pool,
+        )
+        .await
+        .unwrap();
+
+        let notices = list_notices(false, &pool).await.unwrap();
+        assert_eq!(notices, vec![critical.clone(), active.clone()]);
+        let notices = list_notices(true, &pool).await.unwrap();
+        assert_eq!(notices, vec![critical, scheduled.clone(), active]);
+
+        delete_notice(scheduled.id, &pool).await.unwrap().unwrap();
+        assert!(delete_notice(scheduled.id, &pool).await.unwrap().is_none());
+    }
+}
@@ -1,6 +1,10 @@
+use crate::api::iceberg::v1::ReturnTotal;
+use crate::api::management::v1::SortOrder;
+use crate::implementations::postgres::dbutils::DBErrorHandler;
 use base64::Engine;
 use chrono::Utc;
 use iceberg_ext::catalog::rest::ErrorModel;
+use sqlx::{Postgres, QueryBuilder};
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq)]
@@ -14,6 +18,54 @@ pub(crate) struct V1PaginateToken<T> {
     pub(crate) id: T,
 }
 
+/// Id of a page token of a listing with a selectable sort. The key and order of the sort
+/// are part of the id, so that a token cannot continue a listing with a different sort.
+#[derive(Debug, PartialEq)]
+pub(crate) struct SortedTokenId<T> {
+    pub(crate) sort: String,
+    pub(crate) id: T,
+}
+
+impl<T> SortedTokenId<T> {
+    /// The id of the token, if it was issued for `sort`.
+    pub(crate) fn require_sort(self, sort: &str) -> Result<T, ErrorModel> {
+        if self.sort == sort {
+            Ok(self.id)
+        } else {
+            Err(ErrorModel::bad_request(
+                format!(
+                    "Paginate token was issued for sort `{}`, not `{sort}`",
+                    self.sort
+                ),
+                "PaginateTokenSortMismatch".to_string(),
+                None,
+            ))
+        }
+    }
+}
+
+impl<T: Display> Display for SortedTokenId<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}&{}", self.sort, self.id)
+    }
+}
+
+impl<T, Z> TryFrom<&str> for SortedTokenId<T>
+where
+    T: for<'a> TryFrom<&'a str, Error = Z>,
+    Z: std::error::Error + Send + Sync + 'static,
+{
+    type Error = ErrorModel;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let (sort, id) = s.split_once('&').ok_or(parse_error(None))?;
+        Ok(Self {
+            sort: sort.to_string(),
+            id: id.try_into().map_err(|e| parse_error(Some(Box::new(e))))?,
+        })
+    }
+}
+
 impl<T> Display for PaginateToken<T>
 where
     T: Display,
@@ -86,6 +138,59 @@ fn parse_error(e: Option<Box<dyn std::error::Error + Send + Sync + 'static>>) ->
     )
 }
 
+/// Comparison operator that selects the rows following a page token, and the
+/// direction of the `ORDER BY` clause, for listings sorted in `order`.
+pub(crate) fn keyset_direction(order: SortOrder) -> (&'static str, &'static str) {
+    match order {
+        SortOrder::Asc => (">", "ASC"),
+        SortOrder::Desc => ("<", "DESC"),
+    }
+}
+
+/// Count the rows matched by the `FROM ... WHERE ...` clause pushed by `from_where`.
+///
+/// Exact counts stop at [`ReturnTotal::EXACT_COUNT_LIMIT`] rows and fall back to the estimate
+/// of the query planner, so that large warehouses cannot be used to trigger expensive scans.
+pub(crate) async fn count_rows(
+    mode: ReturnTotal,
+    from_where: impl for<'q> Fn(&mut QueryBuilder<'q, Postgres>),
+    transaction: &mut sqlx::Transaction<'_, Postgres>,
+) -> crate::api::Result<i64> {
+    if mode == ReturnTotal::Exact {
+        let mut query_builder = QueryBuilder::new("SELECT count(*) FROM (SELECT 1 ");
+        from_where(&mut query_builder);
+        query_builder
+            .push(" LIMIT ")
+            .push_bind(ReturnTotal::EXACT_COUNT_LIMIT + 1)
+            .push(") c");
+        let count: i64 = query_builder
+            .build_query_scalar()
+            .fetch_one(&mut **transaction)
+            .await
+            .map_err(|e| e.into_error_model("Error counting entries"))?;
+        if count <= ReturnTotal::EXACT_COUNT_LIMIT {
+            return Ok(count);
+        }
+    }
+
+    let mut query_builder = QueryBuilder::new("EXPLAIN (FORMAT JSON) SELECT 1 ");
+    from_where(&mut query_builder);
+    let plan: serde_json::Value = query_builder
+        .build_query_scalar()
+        .fetch_one(&mut **transaction)
+        .await
+        .map_err(|e| e.into_error_model("Error estimating number of entries"))?;
+    Ok(plan_rows(&plan))
+}
+
+/// Extract the estimated number of rows from the output of `EXPLAIN (FORMAT JSON)`.
+#[allow(clippy::cast_possible_truncation)]
+fn plan_rows(plan: &serde_json::Value) -> i64 {
+    plan.pointer("/0/Plan/Plan Rows")
+        .and_then(serde_json::Value::as_f64)
+        .map_or(0, |rows| rows.round().max(0.) as i64)
+}
+
 #[cfg(test)]
 mod test {
     use crate::service::ProjectIdent;
@@ -157,4 +262,37 @@ mod test {
             })
         );
     }
+
+    #[test]
+    fn test_paginate_token_with_sort() {
+        let created_at = Utc::now();
+        let token = PaginateToken::V1(V1PaginateToken {
+            created_at,
+            id: SortedTokenId {
+                sort: "deleted-at.desc".to_string(),
+                id: uuid::Uuid::nil(),
+            },
+        });
+
+        let token_str = token.to_string();
+        let PaginateToken::V1(V1PaginateToken { id, .. }) =
+            PaginateToken::<SortedTokenId<uuid::Uuid>>::try_from(token_str.as_str()).unwrap();
+        assert_eq!(
+            id.require_sort("created-at.asc").unwrap_err().r#type,
+            "PaginateTokenSortMismatch"
+        );
+        let PaginateToken::V1(V1PaginateToken { id, .. }) =
+            PaginateToken::<SortedTokenId<uuid::Uuid>>::try_from(token_str.as_str()).unwrap();
+        assert_eq!(
+            id.require_sort("deleted-at.desc").unwrap(),
+            uuid::Uuid::nil()
+        );
+    }
+
+    #[test]
+    fn test_plan_rows() {
+        let plan = serde_json::json!([{"Plan": {"Node Type": "Seq Scan", "Plan Rows": 1234.0}}]);
+        assert_eq!(plan_rows(&plan), 1234);
+        assert_eq!(plan_rows(&serde_json::json!([])), 0);
+    }
 }
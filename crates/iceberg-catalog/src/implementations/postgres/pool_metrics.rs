@@ -0,0 +1,126 @@
+//! Prometheus metrics of the Postgres connection pools.
+use axum_prometheus::metrics::{counter, gauge, histogram};
+use sqlx::PgPool;
+use std::time::Duration;
+
+/// Histogram of the time it takes to acquire a connection for a transaction.
+pub const POOL_ACQUIRE_SECONDS: &str = "lakekeeper_pg_pool_acquire_seconds";
+const POOL_TIMEOUTS_TOTAL: &str = "lakekeeper_pg_pool_timeouts_total";
+const POOL_CONNECTIONS: &str = "lakekeeper_pg_pool_connections";
+const POOL_MAX_CONNECTIONS: &str = "lakekeeper_pg_pool_max_connections";
+
+/// Record the time it took to get a connection of `pool` for a transaction.
+pub(crate) fn record_acquire(pool: &'static str, elapsed: Duration) {
+    histogram!(POOL_ACQUIRE_SECONDS, "pool" => pool).record(elapsed.as_secs_f64());
+}
+
+pub(crate) fn record_timeout(pool: &'static str) {
+    counter!(POOL_TIMEOUTS_TOTAL, "pool" => pool).increment(1);
+}
+
+/// Periodically report the number of idle and in-use connections of each pool.
+/// `pools` maps the name used as `pool` label to the pool.
+pub async fn pool_metrics_task(pools: Vec<(&'static str, PgPool)>, interval: Duration) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        record_pool_sizes(&pools);
+    }
+}
+
+fn record_pool_sizes(pools: &[(&'static str, PgPool)]) {
+    for (name, pool) in pools {
+        let size = pool.size();
+        let idle = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX);
+        gauge!(POOL_CONNECTIONS, "pool" => *name, "state" => "idle").set(f64::from(idle));
+        gauge!(POOL_CONNECTIONS, "pool" => *name, "state" => "in-use")
+            .set(f64::from(size.saturating_sub(idle)));
+        gauge!(POOL_MAX_CONNECTIONS, "pool" => *name)
+            .set(f64::from(pool.options().get_max_connections()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_prometheus::metrics;
+    use axum_prometheus::metrics_exporter_prometheus::PrometheusBuilder;
+    use sqlx::postgres::PgPoolOptions;
+
+    /// Value of the series starting with `series` in the rendered metrics.
+    fn value(rendered: &str, series: &str) -> f64 {
+        rendered
+            .lines()
+            .find_map(|line| line.strip_prefix(series))
+            .unwrap_or_else(|| panic!("{series} not found in:\n{rendered}"))
+            .trim()
+            .parse()
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_pool_metrics(pool: PgPool) {
+        let recorder = PrometheusBuilder::new().build_recorder();
+        let handle = recorder.handle();
+        let task_pool = PgPoolOptions::new()
+            .max_connections(3)
+            .connect_lazy_with((*pool.connect_options()).clone());
+        let pools = [("read", pool.clone()), ("task-write", task_pool.clone())];
+
+        let connection = task_pool.acquire().await.unwrap();
+        metrics::with_local_recorder(&recorder, || {
+            record_pool_sizes(&pools);
+            record_acquire("task-write", Duration::from_millis(20));
+            record_acquire("task-write", Duration::from_millis(40));
+            record_timeout("task-write");
+        });
+        let rendered = handle.render();
+        assert_eq!(
+            value(
+                &rendered,
+                r#"lakekeeper_pg_pool_connections{pool="task-write",state="in-use"}"#
+            ),
+            1.0
+        );
+        assert_eq!(
+            value(
+                &rendered,
+                r#"lakekeeper_pg_pool_max_connections{pool="task-write"}"#
+            ),
+            3.0
+        );
+        assert_eq!(
+            value(
+                &rendered,
+                r#"lakekeeper_pg_pool_max_connections{pool="read"}"#
+            ),
+            f64::from(pool.options().get_max_connections())
+        );
+        assert_eq!(
+            value(
+                &rendered,
+                r#"lakekeeper_pg_pool_acquire_seconds_count{pool="task-write"}"#
+            ),
+            2.0
+        );
+        assert_eq!(
+            value(
+                &rendered,
+                r#"lakekeeper_pg_pool_timeouts_total{pool="task-write"}"#
+            ),
+            1.0
+        );
+
+        // Gauges follow the pools on the next report.
+        connection.close().await.unwrap();
+        metrics::with_local_recorder(&recorder, || record_pool_sizes(&pools));
+        let rendered = handle.render();
+        assert_eq!(
+            value(
+                &rendered,
+                r#"lakekeeper_pg_pool_connections{pool="task-write",state="in-use"}"#
+            ),
+            0.0
+        );
+    }
+}
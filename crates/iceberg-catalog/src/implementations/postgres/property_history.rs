@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use super::dbutils::DBErrorHandler as _;
+use crate::api::management::v1::property_history::PropertyVersion;
+use crate::service::{ErrorModel, NamespaceIdentUuid, Result, TableIdentUuid};
+use crate::{WarehouseIdent, CONFIG};
+
+#[derive(Debug, Clone, Copy)]
+enum HistoryOf {
+    Namespace,
+    Table,
+}
+
+#[derive(Debug)]
+struct PropertyVersionRow {
+    history_id: i64,
+    properties: Json<HashMap<String, String>>,
+    replaced_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<PropertyVersionRow> for PropertyVersion {
+    fn from(row: PropertyVersionRow) -> Self {
+        Self {
+            version: row.history_id,
+            properties: row.properties.0,
+            replaced_at: row.replaced_at,
+        }
+    }
+}
+
+/// Row of a history listing. All fields are `None` for an entity without history.
+#[derive(Debug)]
+struct MaybePropertyVersionRow {
+    history_id: Option<i64>,
+    properties: Option<Json<HashMap<String, String>>>,
+    replaced_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl MaybePropertyVersionRow {
+    fn into_version(self) -> Option<PropertyVersion> {
+        Some(
+            PropertyVersionRow {
+                history_id: self.history_id?,
+                properties: self.properties?,
+                replaced_at: self.replaced_at?,
+            }
+            .into(),
+        )
+    }
+}
+
+/// Keep the current properties of the namespace if they differ from `new_properties`.
+/// Must be called before the new properties are written.
+pub(crate) async fn record_namespace_properties(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    new_properties: &serde_json::Value,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    if CONFIG.property_history_length == 0 {
+        return Ok(());
+    }
+
+    let recorded = sqlx::query!(
+        r#"
+        INSERT INTO namespace_property_history (namespace_id, properties)
+        SELECT namespace_id, namespace_properties
+        FROM namespace
+        WHERE warehouse_id = $1 AND namespace_id = $2 AND namespace_properties != $3
+        "#,
+        *warehouse_id,
+        *namespace_id,
+        new_properties
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error recording previous namespace properties"))?;
+
+    if recorded.rows_affected() > 0 {
+        trim_history(HistoryOf::Namespace, *namespace_id, transaction).await?;
+    }
+    Ok(())
+}
+
+/// Keep the current properties of the table if they differ from `new_properties`.
+/// Must be called before the new properties are written.
+pub(crate) async fn record_table_properties(
+    table_id: Uuid,
+    new_properties: &HashMap<String, String>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    if CONFIG.property_history_length == 0 {
+        return Ok(());
+    }
+
+    let recorded = sqlx::query!(
+        r#"
+        INSERT INTO table_property_history (table_id, properties)
+        SELECT $1, previous.properties
+        FROM (
+            SELECT COALESCE(jsonb_object_agg(key, value), '{}'::jsonb) AS properties
+            FROM table_properties
+            WHERE table_id = $1
+        ) previous
+        WHERE previous.properties != $2
+        "#,
+        table_id,
+        Json(new_properties) as _
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error recording previous table properties"))?;
+
+    if recorded.rows_affected() > 0 {
+        trim_history(HistoryOf::Table, table_id, transaction).await?;
+    }
+    Ok(())
+}
+
+async fn trim_history(
+    of: HistoryOf,
+    id: Uuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let length = i64::try_from(CONFIG.property_history_length).unwrap_or(i64::MAX);
+    match of {
+        HistoryOf::Namespace => {
+            sqlx::query!(
+                r#"
+            DELETE FROM namespace_property_history
+            WHERE namespace_id = $1 AND history_id NOT IN (
+                SELECT history_id FROM namespace_property_history
+                WHERE namespace_id = $1
+                ORDER BY history_id DESC
+                LIMIT $2
+            )
+            "#,
+                id,
+                length
+            )
+            .execute(&mut **transaction)
+            .await
+        }
+        HistoryOf::Table => {
+            sqlx::query!(
+                r#"
+            DELETE FROM table_property_history
+            WHERE table_id = $1 AND history_id NOT IN (
+                SELECT history_id FROM table_property_history
+                WHERE table_id = $1
+                ORDER BY history_id DESC
+                LIMIT $2
+            )
+            "#,
+                id,
+                length
+            )
+            .execute(&mut **transaction)
+            .await
+        }
+    }
+    .map_err(|e| e.into_error_model("Error removing old property versions"))?;
+    Ok(())
+}
+
+/// Previous properties of the namespace, most recently replaced first.
+///
+/// # Errors
+/// - the namespace does not exist in the warehouse
+pub(crate) async fn list_namespace_property_history(
+    warehouse_id: WarehouseIdent,
+    namespace_id: NamespaceIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<PropertyVersion>> {
+    // The namespace is joined first, so that an unknown namespace returns no row
+    // and a namespace without history a single row without version.
+    let rows = sqlx::query_as!(
+        MaybePropertyVersionRow,
+        r#"
+        SELECT
+            h.history_id as "history_id?",
+            h.properties as "properties?: Json<HashMap<String, String>>",
+            h.replaced_at as "replaced_at?"
+        FROM namespace n
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        LEFT JOIN namespace_property_history h ON h.namespace_id = n.namespace_id
+        WHERE n.warehouse_id = $1 AND n.namespace_id = $2 AND w.status = 'active'
+        ORDER BY h.history_id DESC
+        "#,
+        *warehouse_id,
+        *namespace_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching namespace property history"))?;
+
+    if rows.is_empty() {
+        return Err(ErrorModel::not_found(
+            format!("Namespace {namespace_id} not found"),
+            "NamespaceNotFound",
+            None,
+        )
+        .into());
+    }
+    Ok(rows
+        .into_iter()
+        .filter_map(MaybePropertyVersionRow::into_version)
+        .collect())
+}
+
+/// Previous properties of the table, most recently replaced first.
+///
+/// # Errors
+/// - the table does not exist in the warehouse or is soft-deleted
+pub(crate) async fn list_table_property_history(
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<PropertyVersion>> {
+    let rows = sqlx::query_as!(
+        MaybePropertyVersionRow,
+        r#"
+        SELECT
+            h.history_id as "history_id?",
+            h.properties as "properties?: Json<HashMap<String, String>>",
+            h.replaced_at as "replaced_at?"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        LEFT JOIN table_property_history h ON h.table_id = t.tabular_id
+        WHERE n.warehouse_id = $1
+            AND t.tabular_id = $2
+            AND t.typ = 'table'
+            AND t.deleted_at IS NULL
+            AND w.status = 'active'
+        ORDER BY h.history_id DESC
+        "#,
+        *warehouse_id,
+        *table_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table property history"))?;
+
+    if rows.is_empty() {
+        return Err(ErrorModel::not_found(
+            format!("Table {table_id} not found"),
+            "TableNotFound",
+            None,
+        )
+        .into());
+    }
+    Ok(rows
+        .into_iter()
+        .filter_map(MaybePropertyVersionRow::into_version)
+        .collect())
+}
@@ -1,9 +1,14 @@
 use crate::api::iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE};
-use crate::api::management::v1::role::{ListRolesResponse, Role, SearchRoleResponse};
+use crate::api::management::v1::role::{
+    ListRolesResponse, Role, RoleListFilter, RoleSortBy, SearchRoleResponse,
+};
 use crate::implementations::postgres::dbutils::DBErrorHandler;
-use crate::implementations::postgres::pagination::{PaginateToken, V1PaginateToken};
-use crate::service::{Result, RoleId};
-use crate::ProjectIdent;
+use crate::implementations::postgres::leader::ADVISORY_LOCK_NAMESPACE;
+use crate::implementations::postgres::pagination::{
+    keyset_direction, PaginateToken, V1PaginateToken,
+};
+use crate::service::{PrincipalDefaultWarehouse, Result, RoleId};
+use crate::{ProjectIdent, WarehouseIdent};
 use iceberg_ext::catalog::rest::ErrorModel;
 use uuid::Uuid;
 
@@ -145,6 +150,12 @@ pub(crate) async fn list_roles<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
     filter_project_id: Option<ProjectIdent>,
     filter_role_id: Option<Vec<RoleId>>,
     filter_name: Option<String>,
+    RoleListFilter {
+        created_after,
+        created_before,
+        sort_by,
+        sort_order,
+    }: RoleListFilter,
     PaginationQuery {
         page_size,
         page_token,
@@ -152,61 +163,73 @@ pub(crate) async fn list_roles<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
     connection: E,
 ) -> Result<ListRolesResponse> {
     let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
-    let filter_name = filter_name.unwrap_or_default();
 
     let token = page_token
         .as_option()
-        .map(PaginateToken::try_from)
+        .map(PaginateToken::<Uuid>::try_from)
         .transpose()?;
 
-    let (token_ts, token_id) = token
-        .as_ref()
-        .map(
-            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
-                (created_at, id)
-            },
-        )
-        .unzip();
-
-    let roles: Vec<Role> = sqlx::query_as!(
-        RoleRow,
+    let mut query = sqlx::QueryBuilder::new(
         r#"
-        SELECT
-            id,
-            name,
-            description,
-            project_id,
-            created_at,
-            updated_at
+        SELECT id, name, description, project_id, created_at, updated_at
         FROM role r
-        WHERE ($1 OR project_id = $2)
-            AND ($3 OR id = any($4))
-            AND ($5 OR name ILIKE ('%' || $6 || '%'))
-            --- PAGINATION
-            AND ((r.created_at > $7 OR $7 IS NULL) OR (r.created_at = $7 AND r.id > $8))
-        ORDER BY r.created_at, r.id ASC
-        LIMIT $9
-        "#,
-        filter_project_id.is_none(),
-        uuid::Uuid::from(filter_project_id.unwrap_or_default()),
-        filter_role_id.is_none(),
-        filter_role_id
-            .unwrap_or_default()
-            .into_iter()
-            .map(|id| Uuid::from(id))
-            .collect::<Vec<uuid::Uuid>>() as Vec<Uuid>,
-        filter_name.is_empty(),
-        filter_name.to_string(),
-        token_ts,
-        token_id,
-        page_size,
-    )
-    .fetch_all(connection)
-    .await
-    .map_err(|e| e.into_error_model("Error fetching roles".to_string()))?
-    .into_iter()
-    .map(Role::from)
-    .collect();
+        WHERE true"#,
+    );
+    if let Some(project_id) = filter_project_id {
+        query
+            .push(" AND project_id = ")
+            .push_bind(Uuid::from(project_id));
+    }
+    if let Some(role_ids) = filter_role_id {
+        query
+            .push(" AND id = any(")
+            .push_bind(role_ids.into_iter().map(Uuid::from).collect::<Vec<_>>())
+            .push(")");
+    }
+    if let Some(name) = filter_name.filter(|n| !n.is_empty()) {
+        query
+            .push(" AND name ILIKE ('%' || ")
+            .push_bind(name)
+            .push(" || '%')");
+    }
+    if let Some(created_after) = created_after {
+        query.push(" AND created_at >= ").push_bind(created_after);
+    }
+    if let Some(created_before) = created_before {
+        query.push(" AND created_at < ").push_bind(created_before);
+    }
+
+    let sort_key = match sort_by {
+        RoleSortBy::CreatedAt => "created_at",
+        RoleSortBy::Name => "lower(name)",
+    };
+    let (op, direction) = keyset_direction(sort_order);
+    if let Some(PaginateToken::V1(V1PaginateToken { created_at, id })) = token {
+        query.push(format!(" AND ({sort_key}, r.id) {op} ("));
+        if sort_by == RoleSortBy::CreatedAt {
+            query.push_bind(created_at);
+        } else {
+            query
+                .push(format!("(SELECT {sort_key} FROM role WHERE id = "))
+                .push_bind(id)
+                .push(")");
+        }
+        query.push(", ").push_bind(id).push(")");
+    }
+    query
+        .push(format!(
+            " ORDER BY {sort_key} {direction}, r.id {direction} LIMIT "
+        ))
+        .push_bind(page_size);
+
+    let roles: Vec<Role> = query
+        .build_query_as::<RoleRow>()
+        .fetch_all(connection)
+        .await
+        .map_err(|e| e.into_error_model("Error fetching roles".to_string()))?
+        .into_iter()
+        .map(Role::from)
+        .collect();
 
     let next_page_token = roles.last().map(|r| {
         PaginateToken::V1(V1PaginateToken::<Uuid> {
@@ -241,6 +264,104 @@ pub(crate) async fn delete_role<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sql
     Ok(role.map(|_| ()))
 }
 
+pub(crate) async fn get_role_default_warehouse<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    role_id: RoleId,
+    connection: E,
+) -> Result<Option<PrincipalDefaultWarehouse>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT d.warehouse_id, w.project_id
+        FROM role_default_warehouse d
+        INNER JOIN warehouse w ON d.warehouse_id = w.warehouse_id
+        WHERE d.role_id = $1 AND w.status = 'active'
+        "#,
+        uuid::Uuid::from(role_id),
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching default warehouse of role".to_string()))?;
+
+    Ok(row.map(|row| PrincipalDefaultWarehouse {
+        warehouse_id: row.warehouse_id.into(),
+        project_id: row.project_id.into(),
+    }))
+}
+
+pub(crate) async fn set_role_default_warehouse<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    role_id: RoleId,
+    warehouse_id: Option<WarehouseIdent>,
+    connection: E,
+) -> Result<()> {
+    let Some(warehouse_id) = warehouse_id else {
+        sqlx::query!(
+            r#"
+            DELETE FROM role_default_warehouse
+            WHERE role_id = $1
+            "#,
+            uuid::Uuid::from(role_id),
+        )
+        .execute(connection)
+        .await
+        .map_err(|e| e.into_error_model("Error removing default warehouse of role".to_string()))?;
+        return Ok(());
+    };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO role_default_warehouse (role_id, warehouse_id)
+        VALUES ($1, $2)
+        ON CONFLICT (role_id)
+        DO UPDATE SET warehouse_id = $2
+        "#,
+        uuid::Uuid::from(role_id),
+        *warehouse_id,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_err) => match db_err.constraint() {
+            Some("role_default_warehouse_role_id_fkey") => ErrorModel::not_found(
+                format!("Role {role_id} not found"),
+                "RoleNotFound",
+                Some(Box::new(e)),
+            ),
+            Some("role_default_warehouse_warehouse_id_fkey") => ErrorModel::not_found(
+                format!("Warehouse {warehouse_id} not found"),
+                "WarehouseNotFound",
+                Some(Box::new(e)),
+            ),
+            _ => e.into_error_model("Error setting default warehouse of role"),
+        },
+        _ => e.into_error_model("Error setting default warehouse of role"),
+    })?;
+
+    Ok(())
+}
+
+/// Serialize role membership changes of `project_id` until the transaction ends.
+pub(crate) async fn lock_role_members(
+    project_id: ProjectIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock($1, hashtext($2))",
+        ADVISORY_LOCK_NAMESPACE,
+        format!("role-members:{project_id}")
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error locking role members".to_string()))?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -464,6 +585,7 @@ mod test {
             None,
             None,
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -479,6 +601,7 @@ mod test {
             Some(project1_id),
             None,
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -496,6 +619,7 @@ mod test {
             None,
             Some(vec![role2_id]),
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -513,6 +637,7 @@ mod test {
             None,
             None,
             Some("Role 1".to_string()),
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -561,6 +686,7 @@ mod test {
             None,
             None,
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -575,6 +701,7 @@ mod test {
             None,
             None,
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(5),
                 page_token: PageToken::Empty,
@@ -594,6 +721,7 @@ mod test {
             None,
             None,
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(5),
                 page_token: roles.next_page_token.into(),
@@ -612,6 +740,7 @@ mod test {
             None,
             None,
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(5),
                 page_token: roles.next_page_token.into(),
@@ -663,6 +792,7 @@ mod test {
             None,
             None,
             None,
+            RoleListFilter::default(),
             PaginationQuery {
                 page_size: Some(10),
                 page_token: PageToken::Empty,
@@ -181,6 +181,7 @@ mod tests {
         let secret: StorageCredential = S3Credential::AccessKey {
             aws_access_key_id: "my access key".to_string(),
             aws_secret_access_key: "my secret key".to_string(),
+            signing_keys: vec![],
         }
         .into();
 
@@ -201,6 +202,7 @@ mod tests {
         let secret: StorageCredential = S3Credential::AccessKey {
             aws_access_key_id: "my access key".to_string(),
             aws_secret_access_key: "my secret key".to_string(),
+            signing_keys: vec![],
         }
         .into();
 
@@ -0,0 +1,382 @@
+use chrono::NaiveDate;
+use iceberg_ext::NamespaceIdent;
+use uuid::Uuid;
+
+use crate::api::iceberg::v1::{PaginatedMapping, PaginationQuery, MAX_PAGE_SIZE};
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::implementations::postgres::pagination::{PaginateToken, V1PaginateToken};
+use crate::service::{
+    ErrorModel, NamespaceIdentUuid, NamespaceStorageUsage, Result, TableIdentUuid,
+    TableStorageUsage,
+};
+use crate::WarehouseIdent;
+
+struct StorageUsageRow {
+    warehouse_id: Uuid,
+    namespace_id: Uuid,
+    namespace_name: Vec<String>,
+    day: NaiveDate,
+    number_of_tables: i64,
+    total_bytes: i64,
+    tables_without_size: i64,
+}
+
+fn parse_namespace(namespace_name: Vec<String>) -> Result<NamespaceIdent> {
+    NamespaceIdent::from_vec(namespace_name).map_err(|e| {
+        ErrorModel::internal(
+            "Error parsing namespace",
+            "NamespaceParseError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+impl TryFrom<StorageUsageRow> for NamespaceStorageUsage {
+    type Error = crate::service::IcebergErrorResponse;
+
+    fn try_from(row: StorageUsageRow) -> Result<Self> {
+        Ok(NamespaceStorageUsage {
+            warehouse_id: row.warehouse_id.into(),
+            namespace_id: row.namespace_id.into(),
+            namespace: parse_namespace(row.namespace_name)?,
+            day: row.day,
+            number_of_tables: row.number_of_tables,
+            total_bytes: row.total_bytes,
+            tables_without_size: row.tables_without_size,
+        })
+    }
+}
+
+pub(crate) async fn rollup_storage_usage(
+    day: NaiveDate,
+    retain_from: NaiveDate,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<NamespaceStorageUsage>> {
+    sqlx::query!(
+        "DELETE FROM namespace_storage_usage WHERE day < $1",
+        retain_from
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting expired storage usage"))?;
+
+    sqlx::query!(
+        "DELETE FROM table_storage_usage WHERE day < $1",
+        retain_from
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting expired table storage usage"))?;
+
+    // As for the warehouse statistics, the size of a table is taken from the summary
+    // of the snapshot `main` points to. Tables without `main` do not occupy storage yet.
+    // Soft-deleted tables still occupy storage and are included, staged tables are not.
+    sqlx::query!(
+        r#"
+        INSERT INTO table_storage_usage (
+            warehouse_id,
+            namespace_id,
+            namespace_name,
+            table_id,
+            table_name,
+            day,
+            total_bytes
+        )
+        SELECT
+            n.warehouse_id,
+            n.namespace_id,
+            n.namespace_name,
+            t.tabular_id,
+            t.name,
+            $1::date,
+            CASE
+                WHEN s.snapshot_id IS NULL THEN 0
+                ELSE (s.summary->>'total-files-size')::bigint
+            END
+        FROM tabular t
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        INNER JOIN warehouse w ON w.warehouse_id = n.warehouse_id AND w.status = 'active'
+        LEFT JOIN table_refs r ON r.table_id = t.tabular_id AND r.table_ref_name = 'main'
+        LEFT JOIN table_snapshot s ON s.table_id = r.table_id AND s.snapshot_id = r.snapshot_id
+        WHERE t.typ = 'table' AND t.metadata_location IS NOT NULL
+        ON CONFLICT (table_id, day) DO UPDATE SET
+            namespace_id = EXCLUDED.namespace_id,
+            namespace_name = EXCLUDED.namespace_name,
+            table_name = EXCLUDED.table_name,
+            total_bytes = EXCLUDED.total_bytes
+        "#,
+        day
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error computing table storage usage"))?;
+
+    // Namespaces are rolled up from the tables, so that both always add up.
+    let rows = sqlx::query_as!(
+        StorageUsageRow,
+        r#"
+        INSERT INTO namespace_storage_usage (
+            warehouse_id,
+            namespace_id,
+            namespace_name,
+            day,
+            number_of_tables,
+            total_bytes,
+            tables_without_size
+        )
+        SELECT
+            n.warehouse_id,
+            n.namespace_id,
+            n.namespace_name,
+            $1::date,
+            count(u.table_id),
+            COALESCE(sum(u.total_bytes), 0)::bigint,
+            count(u.table_id) FILTER (WHERE u.total_bytes IS NULL)
+        FROM namespace n
+        INNER JOIN warehouse w ON w.warehouse_id = n.warehouse_id AND w.status = 'active'
+        LEFT JOIN table_storage_usage u ON u.namespace_id = n.namespace_id AND u.day = $1
+        GROUP BY n.warehouse_id, n.namespace_id, n.namespace_name
+        ON CONFLICT (namespace_id, day) DO UPDATE SET
+            namespace_name = EXCLUDED.namespace_name,
+            number_of_tables = EXCLUDED.number_of_tables,
+            total_bytes = EXCLUDED.total_bytes,
+            tables_without_size = EXCLUDED.tables_without_size
+        RETURNING
+            warehouse_id,
+            namespace_id,
+            namespace_name,
+            day,
+            number_of_tables,
+            total_bytes,
+            tables_without_size
+        "#,
+        day
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error computing storage usage"))?;
+
+    rows.into_iter().map(TryInto::try_into).collect()
+}
+
+pub(crate) async fn list_storage_usage(
+    warehouse_id: WarehouseIdent,
+    day: Option<NaiveDate>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<NamespaceStorageUsage>> {
+    let rows = sqlx::query_as!(
+        StorageUsageRow,
+        r#"
+        SELECT
+            warehouse_id,
+            namespace_id,
+            namespace_name,
+            day,
+            number_of_tables,
+            total_bytes,
+            tables_without_size
+        FROM namespace_storage_usage
+        WHERE warehouse_id = $1
+        AND day = COALESCE(
+            $2,
+            (SELECT max(day) FROM namespace_storage_usage WHERE warehouse_id = $1)
+        )
+        ORDER BY namespace_name
+        "#,
+        *warehouse_id,
+        day
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching storage usage"))?;
+
+    rows.into_iter().map(TryInto::try_into).collect()
+}
+
+pub(crate) async fn list_table_storage_usage(
+    warehouse_id: WarehouseIdent,
+    day: Option<NaiveDate>,
+    namespace_id: Option<NamespaceIdentUuid>,
+    pagination_query: PaginationQuery,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<PaginatedMapping<TableIdentUuid, TableStorageUsage>> {
+    let page_size = pagination_query
+        .page_size
+        .map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+    let token = pagination_query
+        .page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?;
+    let (token_ts, token_id) = token
+        .as_ref()
+        .map(
+            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
+                (created_at, id)
+            },
+        )
+        .unzip();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            warehouse_id,
+            namespace_id,
+            namespace_name,
+            table_id,
+            table_name,
+            day,
+            total_bytes,
+            created_at
+        FROM table_storage_usage
+        WHERE warehouse_id = $1
+        AND day = COALESCE(
+            $2,
+            (SELECT max(day) FROM table_storage_usage WHERE warehouse_id = $1)
+        )
+        AND (namespace_id = $3 OR $3 IS NULL)
+        AND ((created_at > $4 OR $4 IS NULL) OR (created_at = $4 AND table_id > $5))
+        ORDER BY created_at, table_id ASC
+        LIMIT $6
+        "#,
+        *warehouse_id,
+        day,
+        namespace_id.map(|id| *id),
+        token_ts,
+        token_id,
+        page_size
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table storage usage"))?;
+
+    let mut usage = PaginatedMapping::with_capacity(rows.len());
+    for row in rows {
+        usage.insert(
+            row.table_id.into(),
+            TableStorageUsage {
+                warehouse_id: row.warehouse_id.into(),
+                namespace_id: row.namespace_id.into(),
+                namespace: parse_namespace(row.namespace_name)?,
+                table_id: row.table_id.into(),
+                name: row.table_name,
+                day: row.day,
+                total_bytes: row.total_bytes,
+            },
+            PaginateToken::V1(V1PaginateToken {
+                created_at: row.created_at,
+                id: row.table_id,
+            })
+            .to_string(),
+        );
+    }
+
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::iceberg::v1::PageToken;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::{CatalogState, PostgresTransaction};
+    use crate::service::Transaction as _;
+
+    #[sqlx::test]
+    async fn test_storage_usage_rollup(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        initialize_table(
+            warehouse_id,
+            state.clone(),
+            false,
+            Some(table.namespace.clone()),
+            None,
+        )
+        .await;
+        // Staged tables do not occupy storage yet
+        initialize_table(
+            warehouse_id,
+            state.clone(),
+            true,
+            Some(table.namespace.clone()),
+            None,
+        )
+        .await;
+
+        let yesterday = NaiveDate::from_ymd_opt(2025, 1, 26).unwrap();
+        let today = NaiveDate::from_ymd_opt(2025, 1, 27).unwrap();
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        rollup_storage_usage(yesterday, yesterday, t.transaction())
+            .await
+            .unwrap();
+        let usage = rollup_storage_usage(today, yesterday, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].namespace, table.namespace);
+        assert_eq!(usage[0].number_of_tables, 2);
+        // Test tables have no `main` branch
+        assert_eq!(usage[0].total_bytes, 0);
+        assert_eq!(usage[0].tables_without_size, 0);
+
+        let latest = list_storage_usage(warehouse_id, None, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(latest, usage);
+        let previous = list_storage_usage(warehouse_id, Some(yesterday), t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(previous.len(), 1);
+        assert_eq!(previous[0].day, yesterday);
+
+        let tables = list_table_storage_usage(
+            warehouse_id,
+            None,
+            Some(usage[0].namespace_id),
+            PaginationQuery {
+                page_token: PageToken::Empty,
+                page_size: Some(1),
+            },
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tables.len(), 1);
+        let next_page_token = tables.next_token().unwrap().to_string();
+        let first = tables.into_iter_with_page_tokens().next().unwrap().1;
+        assert_eq!(first.day, today);
+        assert_eq!(first.namespace, table.namespace);
+        assert_eq!(first.total_bytes, Some(0));
+        let tables = list_table_storage_usage(
+            warehouse_id,
+            Some(today),
+            None,
+            PaginationQuery {
+                page_token: PageToken::Present(next_page_token),
+                page_size: Some(10),
+            },
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tables.len(), 1);
+        let second = tables.into_iter_with_page_tokens().next().unwrap().1;
+        assert_ne!(first.table_id, second.table_id);
+
+        // Rollups before `retain_from` are removed
+        rollup_storage_usage(today, today, t.transaction())
+            .await
+            .unwrap();
+        let previous = list_storage_usage(warehouse_id, Some(yesterday), t.transaction())
+            .await
+            .unwrap();
+        assert!(previous.is_empty());
+        t.commit().await.unwrap();
+    }
+}
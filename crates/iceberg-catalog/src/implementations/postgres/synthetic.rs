@@ -0,0 +1,488 @@
+//! Generator for synthetic warehouses, used to benchmark the catalog against realistic data volumes.
+//!
+//! Warehouses are written directly into Postgres, no storage is accessed. Metadata locations
+//! point to files that do not exist, so generated tables can be listed and loaded, but not read.
+use super::dbutils::DBErrorHandler as _;
+use super::tabular::list_tabulars;
+use super::{CatalogState, PostgresCatalog, PostgresTransaction};
+use crate::api::iceberg::v1::PaginationQuery;
+use crate::api::management::v1::warehouse::TabularDeleteProfile;
+use crate::api::Result;
+use crate::service::storage::{
+    LocationPlacement, MetadataNaming, S3Flavor, S3Profile, StorageProfile,
+};
+use crate::service::{
+    Catalog as _, CreateNamespaceRequest, ListFlags, NamespaceIdent, NamespaceIdentUuid,
+    TableCreation, TableIdentUuid, Transaction as _,
+};
+use crate::{ProjectIdent, WarehouseIdent};
+use iceberg::spec::{
+    FormatVersion, NestedField, Operation, PrimitiveType, Schema, Snapshot, SnapshotReference,
+    SnapshotRetention, SortOrder, Summary, TableMetadata, TableMetadataBuilder, Type,
+    UnboundPartitionSpec, MAIN_BRANCH,
+};
+use iceberg::TableIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::configs::Location;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntheticWarehouseSpec {
+    pub project_id: ProjectIdent,
+    pub warehouse_name: String,
+    pub namespaces: usize,
+    pub tables_per_namespace: usize,
+    /// Length of the snapshot history of every table
+    pub snapshots_per_table: usize,
+    pub columns_per_table: usize,
+}
+
+/// Create a warehouse with `spec.namespaces` namespaces holding `spec.tables_per_namespace` tables each.
+///
+/// The project is created if it does not exist. Each namespace is written in its own transaction.
+///
+/// # Errors
+/// Fails if the warehouse already exists or any write fails.
+pub async fn generate_synthetic_warehouse(
+    state: CatalogState,
+    spec: &SyntheticWarehouseSpec,
+) -> Result<WarehouseIdent> {
+    let mut t = PostgresTransaction::begin_write(state.clone()).await?;
+    if PostgresCatalog::get_project(spec.project_id, t.transaction())
+        .await?
+        .is_none()
+    {
+        PostgresCatalog::create_project(
+            spec.project_id,
+            format!("Project {}", spec.project_id),
+            t.transaction(),
+        )
+        .await?;
+    }
+    let bucket = format!("synthetic-{}", uuid::Uuid::now_v7());
+    let warehouse_id = PostgresCatalog::create_warehouse(
+        spec.warehouse_name.clone(),
+        spec.project_id,
+        StorageProfile::S3(S3Profile {
+            bucket: bucket.clone(),
+            key_prefix: None,
+            assume_role_arn: None,
+            endpoint: None,
+            region: "us-east-1".to_string(),
+            path_style_access: None,
+            sts_role_arn: None,
+            sts_endpoint: None,
+            sts_base_role_arn: None,
+            sts_external_id: None,
+            sts_session_tags: None,
+            sts_principal_identity: false,
+            object_tags: None,
+            metadata_storage_class: None,
+            skip_archived_on_purge: false,
+            metadata_naming: MetadataNaming::default(),
+            sts_enabled: false,
+            flavor: S3Flavor::S3Compat,
+            additional_buckets: vec![],
+            location_placement: LocationPlacement::default(),
+        }),
+        TabularDeleteProfile::Hard {},
+        None,
+        t.transaction(),
+    )
+    .await?;
+    t.commit().await?;
+
+    for n in 0..spec.namespaces {
+        let namespace = NamespaceIdent::new(format!("namespace_{n}"));
+        let namespace_id = NamespaceIdentUuid::default();
+        let mut t = PostgresTransaction::begin_write(state.clone()).await?;
+        PostgresCatalog::create_namespace(
+            warehouse_id,
+            namespace_id,
+            CreateNamespaceRequest {
+                namespace: namespace.clone(),
+                properties: None,
+            },
+            t.transaction(),
+        )
+        .await?;
+
+        for i in 0..spec.tables_per_namespace {
+            let table_ident = TableIdent::new(namespace.clone(), format!("table_{i}"));
+            let table_id = TableIdentUuid::from(uuid::Uuid::now_v7());
+            let location = format!("s3://{bucket}/namespace_{n}/{table_id}");
+            let metadata_location = Location::from_str(&format!(
+                "{location}/metadata/{}.metadata.json",
+                uuid::Uuid::now_v7()
+            ))
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Invalid synthetic metadata location",
+                    "InternalLocationParseError",
+                    Some(Box::new(e)),
+                )
+            })?;
+            PostgresCatalog::create_table(
+                TableCreation {
+                    namespace_id,
+                    table_ident: &table_ident,
+                    metadata_location: Some(&metadata_location),
+                    table_metadata: synthetic_table_metadata(table_id, location, spec)?,
+                    force_expire_property: None,
+                },
+                t.transaction(),
+            )
+            .await?;
+        }
+        t.commit().await?;
+        tracing::debug!(
+            "Generated namespace {n} of {} in warehouse {warehouse_id}",
+            spec.namespaces
+        );
+    }
+
+    Ok(warehouse_id)
+}
+
+fn synthetic_table_metadata(
+    table_id: TableIdentUuid,
+    location: String,
+    spec: &SyntheticWarehouseSpec,
+) -> Result<TableMetadata> {
+    let build_error = |e: iceberg::Error| {
+        ErrorModel::internal(
+            "Failed to build synthetic table metadata",
+            "SyntheticMetadataError",
+            Some(Box::new(e)),
+        )
+    };
+
+    let schema = Schema::builder()
+        .with_fields((1..=spec.columns_per_table.max(1)).map(|id| {
+            let id = i32::try_from(id).unwrap_or(i32::MAX);
+            NestedField::optional(
+                id,
+                format!("column_{id}"),
+                Type::Primitive(PrimitiveType::String),
+            )
+            .into()
+        }))
+        .build()
+        .map_err(build_error)?;
+    let mut builder = TableMetadataBuilder::new(
+        schema,
+        UnboundPartitionSpec::builder().build(),
+        SortOrder::unsorted_order(),
+        location.clone(),
+        FormatVersion::V2,
+        HashMap::new(),
+    )
+    .map_err(build_error)?
+    .assign_uuid(*table_id);
+
+    // One append per snapshot, ten files each, spaced a minute apart and ending now.
+    let now = chrono::Utc::now().timestamp_millis();
+    let snapshots = i64::try_from(spec.snapshots_per_table).unwrap_or(i64::MAX);
+    for snapshot_id in 1..=snapshots {
+        let snapshot = Snapshot::builder()
+            .with_snapshot_id(snapshot_id)
+            .with_parent_snapshot_id((snapshot_id > 1).then_some(snapshot_id - 1))
+            .with_sequence_number(snapshot_id)
+            .with_timestamp_ms(now - (snapshots - snapshot_id) * 60_000)
+            .with_manifest_list(format!(
+                "{location}/metadata/snap-{snapshot_id}-{}.avro",
+                uuid::Uuid::now_v7()
+            ))
+            .with_schema_id(0)
+            .with_summary(Summary {
+                operation: Operation::Append,
+                additional_properties: HashMap::from_iter([
+                    ("added-data-files".to_string(), "10".to_string()),
+                    (
+                        "total-data-files".to_string(),
+                        (snapshot_id * 10).to_string(),
+                    ),
+                    (
+                        "total-files-size".to_string(),
+                        (snapshot_id * 10 * 64 * 1024 * 1024).to_string(),
+                    ),
+                ]),
+            })
+            .build();
+        builder = builder
+            .add_snapshot(snapshot)
+            .map_err(build_error)?
+            .set_ref(
+                MAIN_BRANCH,
+                SnapshotReference {
+                    snapshot_id,
+                    retention: SnapshotRetention::Branch {
+                        min_snapshots_to_keep: None,
+                        max_snapshot_age_ms: None,
+                        max_ref_age_ms: None,
+                    },
+                },
+            )
+            .map_err(build_error)?;
+    }
+
+    Ok(builder.build().map_err(build_error)?.metadata)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadTablesBenchResult {
+    /// Number of tables loaded per `load_tables` call
+    pub batch_size: usize,
+    pub calls: usize,
+    pub tables: usize,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl LoadTablesBenchResult {
+    #[must_use]
+    pub fn per_table(&self) -> Duration {
+        self.mean / u32::try_from(self.batch_size).unwrap_or(u32::MAX)
+    }
+}
+
+/// Time `load_tables` for all tables of a warehouse, once for every batch size.
+///
+/// A batch size of 1 corresponds to loading tables one by one, as done by `loadTable`,
+/// larger batch sizes correspond to commits of multiple tables.
+///
+/// # Errors
+/// Fails if the warehouse has no tables or a query fails.
+pub async fn bench_load_tables(
+    state: CatalogState,
+    warehouse_id: WarehouseIdent,
+    batch_sizes: &[usize],
+    iterations: usize,
+) -> Result<Vec<LoadTablesBenchResult>> {
+    let table_ids: Vec<TableIdentUuid> = sqlx::query_scalar!(
+        r#"
+        SELECT t.tabular_id
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE n.warehouse_id = $1 AND t.typ = 'table' AND t.deleted_at IS NULL
+        "#,
+        *warehouse_id
+    )
+    .fetch_all(&state.read_pool())
+    .await
+    .map_err(|e| e.into_error_model("Error fetching tables to benchmark".to_string()))?
+    .into_iter()
+    .map(Into::into)
+    .collect();
+
+    if table_ids.is_empty() {
+        return Err(ErrorModel::bad_request(
+            format!("Warehouse {warehouse_id} has no tables to benchmark"),
+            "WarehouseEmpty",
+            None,
+        )
+        .into());
+    }
+
+    let mut results = Vec::with_capacity(batch_sizes.len());
+    for &batch_size in batch_sizes {
+        let batch_size = batch_size.clamp(1, table_ids.len());
+        let mut durations = vec![];
+        for _ in 0..iterations.max(1) {
+            for batch in table_ids.chunks(batch_size) {
+                let mut t = PostgresTransaction::begin_read(state.clone()).await?;
+                let start = Instant::now();
+                PostgresCatalog::load_tables(
+                    warehouse_id,
+                    batch.iter().copied(),
+                    false,
+                    t.transaction(),
+                )
+                .await?;
+                durations.push(start.elapsed());
+                t.commit().await?;
+            }
+        }
+        results.push(summarize(batch_size, table_ids.len(), durations));
+    }
+
+    Ok(results)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListTabularsBenchResult {
+    /// Requested number of tabulars per page
+    pub page_size: usize,
+    /// Whether soft-deleted tabulars were listed as well
+    pub include_deleted: bool,
+    pub pages: usize,
+    /// Number of tabulars listed in one pass over all pages
+    pub tabulars: usize,
+    pub mean: Duration,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+/// Time `list_tabulars` paging through all tabulars of a warehouse, once for every page size
+/// with and without soft-deleted tabulars.
+///
+/// Each page is fetched with the token of the previous one, so later pages measure the
+/// keyset condition rather than an offset. Listing stops at the first empty page.
+///
+/// # Errors
+/// Fails if a query fails.
+pub async fn bench_list_tabulars(
+    state: CatalogState,
+    warehouse_id: WarehouseIdent,
+    page_sizes: &[usize],
+    iterations: usize,
+) -> Result<Vec<ListTabularsBenchResult>> {
+    let mut results = Vec::with_capacity(page_sizes.len() * 2);
+    for &page_size in page_sizes {
+        for include_deleted in [false, true] {
+            let list_flags = ListFlags {
+                include_deleted,
+                ..ListFlags::default()
+            };
+            let mut durations = vec![];
+            let mut tabulars = 0;
+            for _ in 0..iterations.max(1) {
+                tabulars = 0;
+                let mut token = None;
+                loop {
+                    let start = Instant::now();
+                    let page = list_tabulars(
+                        warehouse_id,
+                        None,
+                        None,
+                        list_flags,
+                        &state.read_pool(),
+                        None,
+                        PaginationQuery::new(
+                            token.into(),
+                            Some(i64::try_from(page_size).unwrap_or(i64::MAX)),
+                        ),
+                        false,
+                    )
+                    .await?;
+                    durations.push(start.elapsed());
+                    tabulars += page.len();
+                    token = page.next_token().map(ToString::to_string);
+                    if token.is_none() || page.is_empty() {
+                        break;
+                    }
+                }
+            }
+            let (mean, p50, p95, max) = timings(&mut durations);
+            results.push(ListTabularsBenchResult {
+                page_size,
+                include_deleted,
+                pages: durations.len() / iterations.max(1),
+                tabulars,
+                mean,
+                p50,
+                p95,
+                max,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+fn summarize(
+    batch_size: usize,
+    tables: usize,
+    mut durations: Vec<Duration>,
+) -> LoadTablesBenchResult {
+    let (mean, p50, p95, max) = timings(&mut durations);
+    LoadTablesBenchResult {
+        batch_size,
+        calls: durations.len(),
+        tables,
+        mean,
+        p50,
+        p95,
+        max,
+    }
+}
+
+/// Mean, median, 95th percentile and maximum of `durations`, which must not be empty.
+fn timings(durations: &mut [Duration]) -> (Duration, Duration, Duration, Duration) {
+    durations.sort_unstable();
+    let percentile = |p: usize| durations[(durations.len() * p / 100).min(durations.len() - 1)];
+    (
+        durations.iter().sum::<Duration>() / u32::try_from(durations.len()).unwrap_or(u32::MAX),
+        percentile(50),
+        percentile(95),
+        durations[durations.len() - 1],
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_generate_and_bench(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let spec = SyntheticWarehouseSpec {
+            project_id: ProjectIdent::from(uuid::Uuid::nil()),
+            warehouse_name: "synthetic".to_string(),
+            namespaces: 2,
+            tables_per_namespace: 3,
+            snapshots_per_table: 5,
+            columns_per_table: 4,
+        };
+        let warehouse_id = generate_synthetic_warehouse(state.clone(), &spec)
+            .await
+            .unwrap();
+
+        let results = bench_load_tables(state.clone(), warehouse_id, &[1, 4, 100], 1)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].calls, 6);
+        assert_eq!(results[1].calls, 2);
+        // Batch sizes are capped at the number of tables
+        assert_eq!(results[2].batch_size, 6);
+        assert_eq!(results[2].calls, 1);
+
+        let results = bench_list_tabulars(state.clone(), warehouse_id, &[4, 1000], 2)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(|r| r.tabulars == 6));
+        // The last page is empty, as for clients following the page tokens.
+        assert_eq!(results[0].pages, 3);
+        assert!(!results[0].include_deleted);
+        assert!(results[1].include_deleted);
+        assert_eq!(results[2].pages, 2);
+
+        let mut t = PostgresTransaction::begin_read(state).await.unwrap();
+        let table_id = bench_table_id(&pool, warehouse_id).await;
+        let loaded = PostgresCatalog::load_tables(warehouse_id, [table_id], false, t.transaction())
+            .await
+            .unwrap();
+        let metadata = &loaded[&table_id].table_metadata;
+        assert_eq!(metadata.snapshots().count(), 5);
+        assert_eq!(metadata.current_snapshot_id(), Some(5));
+        assert_eq!(metadata.current_schema().as_struct().fields().len(), 4);
+    }
+
+    async fn bench_table_id(pool: &sqlx::PgPool, warehouse_id: WarehouseIdent) -> TableIdentUuid {
+        sqlx::query_scalar!(
+            "SELECT t.tabular_id FROM tabular t INNER JOIN namespace n ON t.namespace_id = n.namespace_id WHERE n.warehouse_id = $1 LIMIT 1",
+            *warehouse_id
+        )
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .into()
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::service::{Result, TabularAccess, TabularAccessKind, TabularIdentUuid};
+
+pub(crate) async fn record_tabular_access<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    tabular_id: TabularIdentUuid,
+    kind: TabularAccessKind,
+    min_interval: chrono::Duration,
+    connection: E,
+) -> Result<()> {
+    #[allow(clippy::cast_precision_loss)]
+    let min_interval_secs = min_interval.num_milliseconds() as f64 / 1000.0;
+
+    // Selecting from `tabular` skips tabulars that were dropped in the meantime
+    // instead of failing on the foreign key.
+    match kind {
+        TabularAccessKind::Read => {
+            sqlx::query!(
+                r#"
+            INSERT INTO tabular_access (tabular_id, last_read_at)
+            SELECT tabular_id, now() FROM tabular WHERE tabular_id = $1
+            ON CONFLICT (tabular_id) DO UPDATE SET last_read_at = EXCLUDED.last_read_at
+            WHERE tabular_access.last_read_at IS NULL
+                OR tabular_access.last_read_at < now() - make_interval(secs => $2)
+            "#,
+                *tabular_id,
+                min_interval_secs
+            )
+            .execute(connection)
+            .await
+        }
+        TabularAccessKind::Commit => {
+            sqlx::query!(
+                r#"
+            INSERT INTO tabular_access (tabular_id, last_commit_at)
+            SELECT tabular_id, now() FROM tabular WHERE tabular_id = $1
+            ON CONFLICT (tabular_id) DO UPDATE SET last_commit_at = EXCLUDED.last_commit_at
+            WHERE tabular_access.last_commit_at IS NULL
+                OR tabular_access.last_commit_at < now() - make_interval(secs => $2)
+            "#,
+                *tabular_id,
+                min_interval_secs
+            )
+            .execute(connection)
+            .await
+        }
+    }
+    .map_err(|e| e.into_error_model("Error recording tabular access"))?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_tabular_access<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    tabular_ids: &[Uuid],
+    connection: E,
+) -> Result<HashMap<Uuid, TabularAccess>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT tabular_id, last_read_at, last_commit_at
+        FROM tabular_access
+        WHERE tabular_id = ANY($1)
+        "#,
+        tabular_ids
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching tabular access"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            (
+                row.tabular_id,
+                TabularAccess {
+                    last_read_at: row.last_read_at,
+                    last_commit_at: row.last_commit_at,
+                },
+            )
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::CatalogState;
+
+    #[sqlx::test]
+    async fn test_record_tabular_access(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let tabular_id = TabularIdentUuid::from(table.table_id);
+
+        let access = get_tabular_access(&[*tabular_id], &pool).await.unwrap();
+        assert!(access.is_empty());
+
+        record_tabular_access(
+            tabular_id,
+            TabularAccessKind::Read,
+            chrono::Duration::minutes(5),
+            &pool,
+        )
+        .await
+        .unwrap();
+        let first = get_tabular_access(&[*tabular_id], &pool).await.unwrap()[&*tabular_id];
+        assert!(first.last_read_at.is_some());
+        assert!(first.last_commit_at.is_none());
+
+        // Within the interval, reads are not recorded again
+        record_tabular_access(
+            tabular_id,
+            TabularAccessKind::Read,
+            chrono::Duration::minutes(5),
+            &pool,
+        )
+        .await
+        .unwrap();
+        record_tabular_access(
+            tabular_id,
+            TabularAccessKind::Commit,
+            chrono::Duration::minutes(5),
+            &pool,
+        )
+        .await
+        .unwrap();
+        let second = get_tabular_access(&[*tabular_id], &pool).await.unwrap()[&*tabular_id];
+        assert_eq!(second.last_read_at, first.last_read_at);
+        assert!(second.last_commit_at.is_some());
+
+        record_tabular_access(
+            tabular_id,
+            TabularAccessKind::Read,
+            chrono::Duration::zero(),
+            &pool,
+        )
+        .await
+        .unwrap();
+        let third = get_tabular_access(&[*tabular_id], &pool).await.unwrap()[&*tabular_id];
+        assert!(third.last_read_at > first.last_read_at);
+    }
+
+    #[sqlx::test]
+    async fn test_record_tabular_access_unknown_tabular(pool: sqlx::PgPool) {
+        let tabular_id = TabularIdentUuid::Table(Uuid::now_v7());
+        record_tabular_access(
+            tabular_id,
+            TabularAccessKind::Commit,
+            chrono::Duration::minutes(5),
+            &pool,
+        )
+        .await
+        .unwrap();
+        let access = get_tabular_access(&[*tabular_id], &pool).await.unwrap();
+        assert!(access.is_empty());
+    }
+}
@@ -0,0 +1,428 @@
+use uuid::Uuid;
+
+use super::{try_parse_namespace_ident, TabularType};
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::service::task_queue::Task;
+use crate::service::{
+    DeletionDetails, ErrorModel, PendingExpiration, Result, TableIdent, TabularExpirationClaim,
+    TabularIdentOwned, TabularIdentUuid,
+};
+use crate::WarehouseIdent;
+
+/// Expiration tasks that are not running yet are locked until the end of the
+/// transaction, so that they are not picked up while being rescheduled.
+pub(crate) async fn list_pending_expirations(
+    warehouse_id: WarehouseIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<PendingExpiration>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            t.tabular_id,
+            t.name,
+            n.namespace_name,
+            t.typ as "typ: TabularType",
+            t.created_at,
+            t.deleted_at as "deleted_at!",
+            tt.task_id,
+            tt.suspend_until as "expire_at!",
+            t.expiration_exempt
+        FROM task tt
+        INNER JOIN tabular_expirations te ON te.task_id = tt.task_id
+        INNER JOIN tabular t ON t.tabular_id = te.tabular_id
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        WHERE tt.warehouse_id = $1
+            AND tt.queue_name = 'tabular_expiration'
+            AND tt.status = 'pending'
+            AND tt.suspend_until IS NOT NULL
+            AND t.deleted_at IS NOT NULL
+        ORDER BY tt.suspend_until, t.tabular_id
+        FOR UPDATE OF tt
+        "#,
+        *warehouse_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching pending expirations"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            let ident = TableIdent {
+                namespace: try_parse_namespace_ident(row.namespace_name)?,
+                name: row.name,
+            };
+            let (tabular_id, tabular) = match row.typ {
+                TabularType::Table => (
+                    TabularIdentUuid::Table(row.tabular_id),
+                    TabularIdentOwned::Table(ident),
+                ),
+                TabularType::View => (
+                    TabularIdentUuid::View(row.tabular_id),
+                    TabularIdentOwned::View(ident),
+                ),
+            };
+            Ok(PendingExpiration {
+                tabular_id,
+                tabular,
+                deletion_details: DeletionDetails {
+                    expiration_task_id: row.task_id,
+                    expiration_date: row.expire_at,
+                    deleted_at: row.deleted_at,
+                    created_at: row.created_at,
+                    expiration_exempt: row.expiration_exempt,
+                },
+            })
+        })
+        .collect()
+}
+
+pub(crate) async fn reschedule_expirations(
+    expirations: &[(Uuid, chrono::DateTime<chrono::Utc>)],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let (task_ids, expire_at): (Vec<_>, Vec<_>) = expirations.iter().copied().unzip();
+
+    sqlx::query!(
+        r#"
+        UPDATE task
+        SET suspend_until = r.expire_at
+        FROM unnest($1::uuid[], $2::timestamptz[]) AS r(task_id, expire_at)
+        WHERE task.task_id = r.task_id
+            AND task.queue_name = 'tabular_expiration'
+            AND task.status = 'pending'
+        "#,
+        &task_ids,
+        &expire_at
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error rescheduling expirations"))?;
+
+    Ok(())
+}
+
+/// Locks the soft-deleted tabular and checks that `task` still owns its expiration.
+pub(crate) async fn claim_tabular_expiration(
+    tabular_id: Uuid,
+    task: &Task,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<TabularExpirationClaim> {
+    // Same lock order as undrop: first the tabular, then the task.
+    let tabular = sqlx::query!(
+        r#"
+        SELECT deleted_at IS NOT NULL as "deleted!", expiration_exempt
+        FROM tabular
+        WHERE tabular_id = $1
+        FOR UPDATE
+        "#,
+        tabular_id
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error locking tabular for expiration"))?;
+    let Some(tabular) = tabular.filter(|t| t.deleted) else {
+        return Ok(TabularExpirationClaim::NotDeleted);
+    };
+    let exempt = tabular.expiration_exempt;
+
+    let owned = sqlx::query_scalar!(
+        r#"
+        SELECT status = 'running' AND attempt = $2 as "owned!"
+        FROM task
+        WHERE task_id = $1
+        FOR UPDATE
+        "#,
+        task.task_id,
+        task.attempt
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error locking expiration task"))?;
+
+    if owned != Some(true) {
+        return Ok(TabularExpirationClaim::TaskNotOwned);
+    }
+    if exempt {
+        postpone_expiration(task, transaction).await?;
+        return Ok(TabularExpirationClaim::Exempt);
+    }
+    Ok(TabularExpirationClaim::Claimed)
+}
+
+/// Return the running expiration `task` to the queue, to be checked again later.
+/// The attempt is not counted, as postponing is not a failure.
+async fn postpone_expiration(
+    task: &Task,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        r#"
+        UPDATE task
+        SET status = 'pending',
+            suspend_until = now() + interval '1 hour',
+            attempt = attempt - 1
+        WHERE task_id = $1 AND status = 'running' AND attempt = $2
+        "#,
+        task.task_id,
+        task.attempt
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error postponing expiration"))?;
+    Ok(())
+}
+
+/// Exempt a soft-deleted tabular from expiration, or lift the exemption.
+pub(crate) async fn set_tabular_expiration_exempt(
+    warehouse_id: WarehouseIdent,
+    tabular_id: Uuid,
+    exempt: bool,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let updated = sqlx::query!(
+        r#"
+        UPDATE tabular t
+        SET expiration_exempt = $3
+        FROM namespace n
+        WHERE t.namespace_id = n.namespace_id
+            AND n.warehouse_id = $1
+            AND t.tabular_id = $2
+            AND t.deleted_at IS NOT NULL
+        "#,
+        *warehouse_id,
+        tabular_id,
+        exempt
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error setting expiration exemption"))?;
+
+    if updated.rows_affected() == 0 {
+        return Err(ErrorModel::not_found(
+            format!("Soft-deleted tabular {tabular_id} not found"),
+            "NoSuchTabularError",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::management::v1::TabularType as ApiTabularType;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::tabular::{
+        clear_tabular_deleted_at, mark_tabular_as_deleted,
+    };
+    use crate::implementations::postgres::task_queues::TabularExpirationQueue;
+    use crate::implementations::postgres::warehouse::clear_warehouse_tabulars;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::{CatalogState, PostgresTransaction, ReadWrite};
+    use crate::service::task_queue::tabular_expiration_queue::TabularExpirationInput;
+    use crate::service::task_queue::TaskQueue;
+    use crate::service::Transaction;
+
+    #[sqlx::test]
+    async fn test_reschedule_expirations(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let tabular_id = TabularIdentUuid::from(table.table_id);
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        mark_tabular_as_deleted(tabular_id, None, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+
+        let expire_at = chrono::Utc::now() + chrono::Duration::days(1);
+        let queue = TabularExpirationQueue::new(ReadWrite::from_pools(pool.clone(), pool.clone()));
+        queue
+            .enqueue(TabularExpirationInput {
+                tabular_id: *tabular_id,
+                warehouse_ident: warehouse_id,
+                tabular_type: ApiTabularType::Table,
+                purge: false,
+                expire_at,
+            })
+            .await
+            .unwrap();
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let pending = list_pending_expirations(warehouse_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].tabular_id, tabular_id);
+        assert_eq!(
+            pending[0].deletion_details.expiration_date.timestamp(),
+            expire_at.timestamp()
+        );
+
+        let new_expire_at = expire_at + chrono::Duration::days(7);
+        reschedule_expirations(
+            &[(
+                pending[0].deletion_details.expiration_task_id,
+                new_expire_at,
+            )],
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        let pending = list_pending_expirations(warehouse_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(
+            pending[0].deletion_details.expiration_date.timestamp(),
+            new_expire_at.timestamp()
+        );
+        t.commit().await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_undrop_races_with_expiration(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let queue = TabularExpirationQueue::new(ReadWrite::from_pools(pool.clone(), pool.clone()));
+
+        let mut tabular_ids = vec![];
+        for _ in 0..2 {
+            let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+            let tabular_id = TabularIdentUuid::from(table.table_id);
+            let mut t = PostgresTransaction::begin_write(state.clone())
+                .await
+                .unwrap();
+            mark_tabular_as_deleted(tabular_id, None, t.transaction())
+                .await
+                .unwrap();
+            t.commit().await.unwrap();
+            queue
+                .enqueue(TabularExpirationInput {
+                    tabular_id: *tabular_id,
+                    warehouse_ident: warehouse_id,
+                    tabular_type: ApiTabularType::Table,
+                    purge: false,
+                    expire_at: chrono::Utc::now() - chrono::Duration::seconds(1),
+                })
+                .await
+                .unwrap();
+            tabular_ids.push(*tabular_id);
+        }
+
+        // The worker picked up the first expiration, undropping it must fail.
+        let expiration = queue.pick_new_task().await.unwrap().unwrap();
+        let running = expiration.tabular_id;
+        let other = *tabular_ids.iter().find(|id| **id != running).unwrap();
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = clear_tabular_deleted_at(&[running], t.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "TabularExpirationRunning");
+        t.rollback().await.unwrap();
+
+        // A stale attempt no longer owns the task.
+        let mut stale_task = expiration.task.clone();
+        stale_task.attempt -= 1;
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            claim_tabular_expiration(running, &stale_task, t.transaction())
+                .await
+                .unwrap(),
+            TabularExpirationClaim::TaskNotOwned
+        );
+        assert_eq!(
+            claim_tabular_expiration(running, &expiration.task, t.transaction())
+                .await
+                .unwrap(),
+            TabularExpirationClaim::Claimed
+        );
+        t.rollback().await.unwrap();
+
+        // The pending expiration of the second tabular is cancelled by the undrop.
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let cancelled = clear_tabular_deleted_at(&[other], t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(cancelled.len(), 1);
+        t.commit().await.unwrap();
+        assert!(queue.pick_new_task().await.unwrap().is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_exempt_tabular_is_not_expired(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let queue = TabularExpirationQueue::new(ReadWrite::from_pools(pool.clone(), pool.clone()));
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let tabular_id = TabularIdentUuid::from(table.table_id);
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        // Only soft-deleted tabulars can be exempt.
+        let err = set_tabular_expiration_exempt(warehouse_id, *tabular_id, true, t.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "NoSuchTabularError");
+        mark_tabular_as_deleted(tabular_id, None, t.transaction())
+            .await
+            .unwrap();
+        set_tabular_expiration_exempt(warehouse_id, *tabular_id, true, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+        queue
+            .enqueue(TabularExpirationInput {
+                tabular_id: *tabular_id,
+                warehouse_ident: warehouse_id,
+                tabular_type: ApiTabularType::Table,
+                purge: false,
+                expire_at: chrono::Utc::now() - chrono::Duration::seconds(1),
+            })
+            .await
+            .unwrap();
+
+        let expiration = queue.pick_new_task().await.unwrap().unwrap();
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        assert_eq!(
+            claim_tabular_expiration(*tabular_id, &expiration.task, t.transaction())
+                .await
+                .unwrap(),
+            TabularExpirationClaim::Exempt
+        );
+        t.commit().await.unwrap();
+
+        // The expiration is postponed instead of being picked up again right away.
+        assert!(queue.pick_new_task().await.unwrap().is_none());
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let pending = list_pending_expirations(warehouse_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        assert!(pending[0].deletion_details.expiration_exempt);
+        assert!(pending[0].deletion_details.expiration_date > chrono::Utc::now());
+
+        // Tearing down the warehouse does not delete exempt tabulars either.
+        let err = clear_warehouse_tabulars(warehouse_id, &mut **t.transaction())
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.r#type, "WarehouseHasExpirationExemptTabulars");
+        t.commit().await.unwrap();
+    }
+}
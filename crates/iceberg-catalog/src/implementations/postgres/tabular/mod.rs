@@ -1,6 +1,16 @@
+mod access;
+mod expiration;
 pub mod table;
+mod ttl;
 pub(crate) mod view;
 
+pub(crate) use access::{get_tabular_access, record_tabular_access};
+pub(crate) use expiration::{
+    claim_tabular_expiration, list_pending_expirations, reschedule_expirations,
+    set_tabular_expiration_exempt,
+};
+pub(crate) use ttl::list_table_ttl_candidates;
+
 use super::dbutils::DBErrorHandler as _;
 use crate::{
     service::{ErrorModel, NamespaceIdentUuid, Result, TableIdent},
@@ -9,11 +19,14 @@ use crate::{
 use http::StatusCode;
 use iceberg_ext::NamespaceIdent;
 
-use crate::api::iceberg::v1::{PaginatedMapping, PaginationQuery, MAX_PAGE_SIZE};
-
-use crate::implementations::postgres::pagination::{PaginateToken, V1PaginateToken};
+use crate::api::iceberg::v1::{PaginatedMapping, PaginationQuery, ReturnTotal, MAX_PAGE_SIZE};
+use crate::api::management::v1::warehouse::{DeletedTabularListFilter, DeletedTabularSortBy};
+use crate::api::management::v1::SortOrder;
+use crate::implementations::postgres::pagination::{
+    count_rows, keyset_direction, PaginateToken, SortedTokenId, V1PaginateToken,
+};
 use crate::service::task_queue::TaskId;
-use crate::service::DeletionDetails;
+use crate::service::{DeletedTabularAtLocation, DeletionDetails, TabularLocationUsage};
 use crate::service::{TabularIdentBorrowed, TabularIdentOwned, TabularIdentUuid};
 use chrono::Utc;
 use iceberg_ext::configs::Location;
@@ -246,6 +259,10 @@ pub(crate) struct CreateTabular<'a> {
     pub(crate) typ: TabularType,
     pub(crate) metadata_location: Option<&'a Location>,
     pub(crate) location: &'a Location,
+    pub(crate) external_id: Option<&'a str>,
+    /// Property that expires soft-deleted tabulars at the location on creation.
+    /// Mentioned in the error if such a tabular blocks the location.
+    pub(crate) force_expire_property: Option<&'a str>,
 }
 
 pub(crate) async fn create_tabular(
@@ -256,6 +273,8 @@ pub(crate) async fn create_tabular(
         typ,
         metadata_location,
         location,
+        external_id,
+        force_expire_property,
     }: CreateTabular<'_>,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<Uuid> {
@@ -267,8 +286,8 @@ pub(crate) async fn create_tabular(
 
     let tabular_id = sqlx::query_scalar!(
         r#"
-        INSERT INTO tabular (tabular_id, name, namespace_id, typ, metadata_location, location, table_migrated)
-        VALUES ($1, $2, $3, $4, $5, $6, 'true')
+        INSERT INTO tabular (tabular_id, name, namespace_id, typ, metadata_location, location, table_migrated, external_id)
+        VALUES ($1, $2, $3, $4, $5, $6, 'true', $7)
         RETURNING tabular_id
         "#,
         id,
@@ -277,10 +296,20 @@ pub(crate) async fn create_tabular(
         typ as _,
         metadata_location.map(iceberg_ext::configs::Location::as_str),
         location.as_str(),
+        external_id,
     )
     .fetch_one(&mut **transaction)
     .await
     .map_err(|e| {
+        if let sqlx::Error::Database(db_error) = &e {
+            if db_error.constraint() == Some("tabular_external_id_idx") {
+                return ErrorModel::conflict(
+                    format!("A table or view with external id '{}' already exists", external_id.unwrap_or_default()),
+                    "ExternalIdAlreadyExists",
+                    None,
+                );
+            }
+        }
         tracing::warn!(?e, "Error creating new {typ}");
         e.into_error_model(format!("Error creating {typ}"))
     })?;
@@ -308,6 +337,41 @@ pub(crate) async fn create_tabular(
     })?;
 
     if location_is_taken {
+        // Soft-deleted tabulars keep their location until they expire. Tell users how
+        // to get rid of them instead of failing with the generic error.
+        let (partial_locations, sublocation_pattern) = overlapping_location_patterns(location);
+        let taken_by_live_tabular = sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                   SELECT 1
+                   FROM tabular
+                   WHERE (location = ANY($1) OR location LIKE $2)
+                       AND tabular_id != $3
+                       AND deleted_at IS NULL
+               ) as "exists!""#,
+            &partial_locations,
+            sublocation_pattern,
+            id
+        )
+        .fetch_one(&mut **transaction)
+        .await
+        .map_err(|e| {
+            tracing::warn!(?e, "Error checking for conflicting locations");
+            e.into_error_model("Error checking for conflicting locations".to_string())
+        })?;
+        if !taken_by_live_tabular {
+            let hint = force_expire_property.map_or_else(
+                || " Undrop and move it, or wait for it to expire.".to_string(),
+                |property| format!(" Undrop and move it, or set the table property `{property}` to `true` to expire it immediately."),
+            );
+            return Err(ErrorModel::bad_request(
+                format!(
+                    "Location is taken by a soft-deleted table or view until it expires.{hint}"
+                ),
+                "LocationTakenBySoftDeletedTabular",
+                None,
+            )
+            .into());
+        }
         return Err(ErrorModel::bad_request(
             "Location is already taken by another table or view",
             "LocationAlreadyTaken",
@@ -319,6 +383,175 @@ pub(crate) async fn create_tabular(
     Ok(tabular_id)
 }
 
+/// Patterns matching locations that overlap with `location`: the location itself and its
+/// parents, and a `LIKE` pattern for locations inside of it.
+fn overlapping_location_patterns(location: &Location) -> (Vec<String>, String) {
+    // Locations may be stored with or without trailing slash
+    let partial_locations = location
+        .partial_locations()
+        .into_iter()
+        .flat_map(|l| [l.to_string(), format!("{l}/")])
+        .collect::<Vec<_>>();
+    let sublocation_pattern = format!(
+        "{}/%",
+        location
+            .as_str()
+            .trim_end_matches('/')
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_")
+    );
+    (partial_locations, sublocation_pattern)
+}
+
+pub(crate) async fn list_deleted_tabulars_at_location<'e, 'c, E>(
+    warehouse_id: WarehouseIdent,
+    location: &Location,
+    catalog_state: E,
+) -> Result<Vec<DeletedTabularAtLocation>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let (partial_locations, sublocation_pattern) = overlapping_location_patterns(location);
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            t.tabular_id,
+            t.typ as "typ: TabularType",
+            t.location,
+            te.task_id,
+            te.deletion_kind as "deletion_kind: DeletionKind",
+            t.expiration_exempt
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        INNER JOIN tabular_expirations te ON te.tabular_id = t.tabular_id
+        INNER JOIN task tk ON tk.task_id = te.task_id
+        WHERE n.warehouse_id = $1
+            AND t.deleted_at IS NOT NULL
+            AND tk.status = 'pending'
+            AND (t.location = ANY($2) OR t.location LIKE $3)
+        "#,
+        *warehouse_id,
+        &partial_locations,
+        sublocation_pattern
+    )
+    .fetch_all(catalog_state)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching soft-deleted tabulars at location"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| DeletedTabularAtLocation {
+            tabular_id: match row.typ {
+                TabularType::Table => TabularIdentUuid::Table(row.tabular_id),
+                TabularType::View => TabularIdentUuid::View(row.tabular_id),
+            },
+            location: row.location,
+            expiration_task_id: row.task_id,
+            purge: matches!(row.deletion_kind, DeletionKind::Purge),
+            expiration_exempt: row.expiration_exempt,
+        })
+        .collect())
+}
+
+/// Tabulars of a warehouse whose location is `location`, a parent of `location`
+/// or inside of `location`.
+pub(crate) async fn list_location_usage<'e, 'c, E>(
+    warehouse_id: WarehouseIdent,
+    location: &Location,
+    catalog_state: E,
+    pagination_query: PaginationQuery,
+) -> Result<PaginatedMapping<TabularIdentUuid, TabularLocationUsage>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let page_size = pagination_query
+        .page_size
+        .map(i64::from)
+        .map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    let token = pagination_query
+        .page_token
+        .as_option()
+        .map(PaginateToken::try_from)
+        .transpose()?;
+
+    let (token_ts, token_id) = token
+        .as_ref()
+        .map(
+            |PaginateToken::V1(V1PaginateToken { created_at, id }): &PaginateToken<Uuid>| {
+                (created_at, id)
+            },
+        )
+        .unzip();
+
+    let (partial_locations, sublocation_pattern) = overlapping_location_patterns(location);
+
+    let tabulars = sqlx::query!(
+        r#"
+        SELECT
+            t.tabular_id,
+            t.name as "tabular_name",
+            namespace_name,
+            t.typ as "typ: TabularType",
+            t.location,
+            t.metadata_location IS NULL as "staged!",
+            t.created_at,
+            t.deleted_at
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        WHERE n.warehouse_id = $1
+            AND (t.location = ANY($2) OR t.location LIKE $3)
+            AND ((t.created_at > $4 OR $4 IS NULL) OR (t.created_at = $4 AND t.tabular_id > $5))
+            ORDER BY t.created_at, t.tabular_id ASC
+            LIMIT $6
+        "#,
+        *warehouse_id,
+        &partial_locations,
+        sublocation_pattern,
+        token_ts,
+        token_id,
+        page_size,
+    )
+    .fetch_all(catalog_state)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching location usage".to_string()))?;
+
+    let mut usages = PaginatedMapping::with_capacity(tabulars.len());
+    for tabular in tabulars {
+        let ident = TableIdent {
+            namespace: try_parse_namespace_ident(tabular.namespace_name)?,
+            name: tabular.tabular_name,
+        };
+        let (id, ident) = match tabular.typ {
+            TabularType::Table => (
+                TabularIdentUuid::Table(tabular.tabular_id),
+                TabularIdentOwned::Table(ident),
+            ),
+            TabularType::View => (
+                TabularIdentUuid::View(tabular.tabular_id),
+                TabularIdentOwned::View(ident),
+            ),
+        };
+        usages.insert(
+            id,
+            TabularLocationUsage {
+                tabular: ident,
+                location: tabular.location,
+                staged: tabular.staged,
+                deleted_at: tabular.deleted_at,
+            },
+            PaginateToken::V1(V1PaginateToken {
+                created_at: tabular.created_at,
+                id: tabular.tabular_id,
+            })
+            .to_string(),
+        );
+    }
+
+    Ok(usages)
+}
+
 #[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 pub(crate) async fn list_tabulars<'e, 'c, E>(
     warehouse_id: WarehouseIdent,
@@ -355,35 +588,47 @@ where
         )
         .unzip();
 
+    // The page is selected first, so that the expiration details are only looked up
+    // for the returned deleted tabulars instead of for every tabular of the warehouse.
+    // The row comparison of the keyset condition can be answered by
+    // `tabular_namespace_id_created_at_idx`.
     let tables = sqlx::query!(
         r#"
         SELECT
-            t.tabular_id,
-            t.name as "tabular_name",
-            namespace_name,
-            t.typ as "typ: TabularType",
-            t.created_at,
-            t.deleted_at,
-            tt.suspend_until as "cleanup_at?",
-            tt.task_id as "cleanup_task_id?"
-        FROM tabular t
-        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
-        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
-        LEFT JOIN tabular_expirations te ON t.tabular_id = te.tabular_id
-        LEFT JOIN task tt ON te.task_id = tt.task_id
-        WHERE n.warehouse_id = $1
-            AND (namespace_name = $2 OR $2 IS NULL)
-            AND (n.namespace_id = $11 OR $11 IS NULL)
-            AND w.status = 'active'
-            AND (t.typ = $3 OR $3 IS NULL)
-            -- active tables are tables that are not staged and not deleted
-            AND ((t.deleted_at IS NOT NULL OR t.metadata_location IS NULL) OR $4)
-            AND (t.deleted_at IS NULL OR $5)
-            AND (t.metadata_location IS NOT NULL OR $6)
-            AND (t.table_migrated != $7)
-            AND ((t.created_at > $8 OR $8 IS NULL) OR (t.created_at = $8 AND t.tabular_id > $9))
+            p.tabular_id as "tabular_id!",
+            p.name as "tabular_name!",
+            p.namespace_name as "namespace_name!",
+            p.typ as "typ!: TabularType",
+            p.created_at as "created_at!",
+            p.deleted_at,
+            p.expiration_exempt as "expiration_exempt!",
+            exp.suspend_until as "cleanup_at?",
+            exp.task_id as "cleanup_task_id?"
+        FROM (
+            SELECT t.tabular_id, t.name, n.namespace_name, t.typ, t.created_at, t.deleted_at, t.expiration_exempt
+            FROM tabular t
+            INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+            WHERE n.warehouse_id = $1
+                AND EXISTS (SELECT 1 FROM warehouse w WHERE w.warehouse_id = $1 AND w.status = 'active')
+                AND (n.namespace_name = $2 OR $2 IS NULL)
+                AND (n.namespace_id = $11 OR $11 IS NULL)
+                AND (t.typ = $3 OR $3 IS NULL)
+                -- active tables are tables that are not staged and not deleted
+                AND ((t.deleted_at IS NOT NULL OR t.metadata_location IS NULL) OR $4)
+                AND (t.deleted_at IS NULL OR $5)
+                AND (t.metadata_location IS NOT NULL OR $6)
+                AND (t.table_migrated != $7)
+                AND ($8::timestamptz IS NULL OR (t.created_at, t.tabular_id) > ($8, $9))
             ORDER BY t.created_at, t.tabular_id ASC
             LIMIT $10
+        ) p
+        LEFT JOIN LATERAL (
+            SELECT tt.suspend_until, tt.task_id
+            FROM tabular_expirations te
+            INNER JOIN task tt ON te.task_id = tt.task_id
+            WHERE p.deleted_at IS NOT NULL AND te.tabular_id = p.tabular_id
+        ) exp ON true
+        ORDER BY p.created_at, p.tabular_id ASC
         "#,
         *warehouse_id,
         namespace.as_deref().map(|n| n.as_ref().as_slice()),
@@ -420,6 +665,7 @@ where
                 ))?,
                 deleted_at,
                 created_at: table.created_at,
+                expiration_exempt: table.expiration_exempt,
             })
         } else {
             None
@@ -460,6 +706,213 @@ where
     Ok(tabulars)
 }
 
+#[derive(Debug, FromRow)]
+struct DeletedTabularRow {
+    tabular_id: Uuid,
+    tabular_name: String,
+    namespace_name: Vec<String>,
+    typ: TabularType,
+    created_at: chrono::DateTime<Utc>,
+    deleted_at: chrono::DateTime<Utc>,
+    expiration_exempt: bool,
+    expiration_date: chrono::DateTime<Utc>,
+    expiration_task_id: Uuid,
+}
+
+pub(crate) async fn list_deleted_tabulars<'e, 'c: 'e, E>(
+    warehouse_id: WarehouseIdent,
+    DeletedTabularListFilter {
+        namespace_id,
+        include_child_namespaces,
+        sort_by,
+        sort_order,
+    }: DeletedTabularListFilter,
+    connection: E,
+    pagination_query: PaginationQuery,
+) -> Result<PaginatedMapping<TabularIdentUuid, (TabularIdentOwned, Option<DeletionDetails>)>>
+where
+    E: 'e + sqlx::Executor<'c, Database = sqlx::Postgres>,
+{
+    let page_size = pagination_query
+        .page_size
+        .map(i64::from)
+        .map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
+
+    // Tokens of one sort cannot continue another: their positions are not comparable.
+    let sort = format!(
+        "{}.{}",
+        match sort_by {
+            DeletedTabularSortBy::CreatedAt => "created-at",
+            DeletedTabularSortBy::DeletedAt => "deleted-at",
+            DeletedTabularSortBy::ExpirationDate => "expiration-date",
+        },
+        match sort_order {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    );
+    let token = pagination_query
+        .page_token
+        .as_option()
+        .map(PaginateToken::<SortedTokenId<Uuid>>::try_from)
+        .transpose()?
+        .map(|PaginateToken::V1(V1PaginateToken { created_at, id })| {
+            id.require_sort(&sort).map(|id| (created_at, id))
+        })
+        .transpose()?;
+
+    let mut query = QueryBuilder::new(
+        r#"
+        SELECT
+            t.tabular_id,
+            t.name as tabular_name,
+            n.namespace_name,
+            t.typ,
+            t.created_at,
+            t.deleted_at,
+            t.expiration_exempt,
+            tt.suspend_until as expiration_date,
+            tt.task_id as expiration_task_id
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        INNER JOIN tabular_expirations te ON t.tabular_id = te.tabular_id
+        INNER JOIN task tt ON te.task_id = tt.task_id
+        WHERE w.status = 'active' AND t.deleted_at IS NOT NULL AND n.warehouse_id = "#,
+    );
+    query.push_bind(*warehouse_id);
+    match namespace_id {
+        Some(namespace_id) if include_child_namespaces => {
+            query
+                .push(" AND EXISTS (SELECT 1 FROM namespace p WHERE p.namespace_id = ")
+                .push_bind(*namespace_id)
+                .push(
+                    " AND p.warehouse_id = n.warehouse_id \
+                    AND n.namespace_name[1:array_length(p.namespace_name, 1)] = p.namespace_name)",
+                );
+        }
+        Some(namespace_id) => {
+            query
+                .push(" AND n.namespace_id = ")
+                .push_bind(*namespace_id);
+        }
+        None => {}
+    }
+
+    // All sort keys are timestamps, so the token carries the sort key of its row
+    // instead of the creation time.
+    let sort_key = match sort_by {
+        DeletedTabularSortBy::CreatedAt => "t.created_at",
+        DeletedTabularSortBy::DeletedAt => "t.deleted_at",
+        DeletedTabularSortBy::ExpirationDate => "tt.suspend_until",
+    };
+    let (op, direction) = keyset_direction(sort_order);
+    if let Some((sort_value, id)) = token {
+        query
+            .push(format!(" AND ({sort_key}, t.tabular_id) {op} ("))
+            .push_bind(sort_value)
+            .push(", ")
+            .push_bind(id)
+            .push(")");
+    }
+    query
+        .push(format!(
+            " ORDER BY {sort_key} {direction}, t.tabular_id {direction} LIMIT "
+        ))
+        .push_bind(page_size);
+
+    let rows: Vec<DeletedTabularRow> = query
+        .build_query_as()
+        .fetch_all(connection)
+        .await
+        .map_err(|e| e.into_error_model("Error fetching deleted tables or views".to_string()))?;
+
+    let mut tabulars = PaginatedMapping::with_capacity(rows.len());
+    for row in rows {
+        let sort_value = match sort_by {
+            DeletedTabularSortBy::CreatedAt => row.created_at,
+            DeletedTabularSortBy::DeletedAt => row.deleted_at,
+            DeletedTabularSortBy::ExpirationDate => row.expiration_date,
+        };
+        let ident = TableIdent {
+            namespace: try_parse_namespace_ident(row.namespace_name)?,
+            name: row.tabular_name,
+        };
+        let (id, ident) = match row.typ {
+            TabularType::Table => (
+                TabularIdentUuid::Table(row.tabular_id),
+                TabularIdentOwned::Table(ident),
+            ),
+            TabularType::View => (
+                TabularIdentUuid::View(row.tabular_id),
+                TabularIdentOwned::View(ident),
+            ),
+        };
+        tabulars.insert(
+            id,
+            (
+                ident,
+                Some(DeletionDetails {
+                    expiration_task_id: row.expiration_task_id,
+                    expiration_date: row.expiration_date,
+                    deleted_at: row.deleted_at,
+                    created_at: row.created_at,
+                    expiration_exempt: row.expiration_exempt,
+                }),
+            ),
+            PaginateToken::V1(V1PaginateToken {
+                created_at: sort_value,
+                id: SortedTokenId {
+                    sort: sort.clone(),
+                    id: row.tabular_id,
+                },
+            })
+            .to_string(),
+        );
+    }
+
+    Ok(tabulars)
+}
+
+/// Count the tabulars [`list_tabulars`] returns for `namespace`.
+pub(crate) async fn count_tabulars(
+    warehouse_id: WarehouseIdent,
+    namespace: &NamespaceIdent,
+    typ: TabularType,
+    list_flags: crate::service::ListFlags,
+    mode: ReturnTotal,
+    transaction: &mut sqlx::Transaction<'_, Postgres>,
+) -> Result<i64> {
+    let namespace = namespace.clone().inner();
+    count_rows(
+        mode,
+        |query_builder| {
+            query_builder
+                .push(
+                    "FROM tabular t
+                    INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+                    INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+                    WHERE w.status = 'active' AND t.table_migrated AND n.warehouse_id = ",
+                )
+                .push_bind(*warehouse_id)
+                .push(" AND n.namespace_name = ")
+                .push_bind(namespace.clone())
+                .push(" AND t.typ = ")
+                .push_bind(typ)
+                // active tables are tables that are not staged and not deleted
+                .push(" AND ((t.deleted_at IS NOT NULL OR t.metadata_location IS NULL) OR ")
+                .push_bind(list_flags.include_active)
+                .push(") AND (t.deleted_at IS NULL OR ")
+                .push_bind(list_flags.include_deleted)
+                .push(") AND (t.metadata_location IS NOT NULL OR ")
+                .push_bind(list_flags.include_staged)
+                .push(")");
+        },
+        transaction,
+    )
+    .await
+}
+
 /// Rename a tabular. Tabulars may be moved across namespaces.
 pub(crate) async fn rename_tabular(
     warehouse_id: WarehouseIdent,
@@ -575,15 +1028,18 @@ impl From<TabularType> for crate::api::management::v1::TabularType {
     }
 }
 
+/// Clears `deleted_at` of the tabulars and cancels their expiration tasks in the
+/// same transaction. Fails if an expiration task is already running, so that an
+/// undrop never races with a drop in progress.
 pub(crate) async fn clear_tabular_deleted_at(
     tabular_ids: &[Uuid],
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> Result<Vec<TaskId>> {
-    let deleted = sqlx::query!(
+    let undeleted = sqlx::query!(
         r#"
         UPDATE tabular t
-        SET deleted_at = NULL
-        WHERE t.tabular_id = any($1)
+        SET deleted_at = NULL, expiration_exempt = false
+        WHERE t.tabular_id = any($1) AND t.deleted_at IS NOT NULL
         "#,
         tabular_ids
     )
@@ -593,19 +1049,23 @@ pub(crate) async fn clear_tabular_deleted_at(
         tracing::warn!("Error marking tabular as undeleted: {}", e);
         e.into_error_model("Error marking tabular as undeleted")
     })?;
-    if deleted.rows_affected() != tabular_ids.len() as u64 {
-        return Err(ErrorModel::internal(
-            "Mismatch between deleted_at entries in tabular to-be-deleted tabulars.",
-            "InternalDatabaseError",
+    if undeleted.rows_affected() != tabular_ids.len() as u64 {
+        return Err(ErrorModel::not_found(
+            "Some of the tabulars to undrop do not exist or are not soft-deleted.",
+            "NoSuchTabularError",
             None,
         )
         .into());
     }
 
-    let task_id = sqlx::query!(
+    // Tabular rows are locked before task rows, in the same order as the expiration worker.
+    let tasks = sqlx::query!(
         r#"
-        SELECT task_id FROM tabular_expirations
-        WHERE tabular_id = any($1)
+        SELECT tt.task_id, tt.status = 'running' as "running!"
+        FROM tabular_expirations te
+        INNER JOIN task tt ON tt.task_id = te.task_id
+        WHERE te.tabular_id = any($1)
+        FOR UPDATE OF tt
         "#,
         tabular_ids
     )
@@ -616,7 +1076,7 @@ pub(crate) async fn clear_tabular_deleted_at(
         e.into_error_model("Error fetching task IDs for tabulars")
     })?;
 
-    if task_id.len() != tabular_ids.len() {
+    if tasks.len() != tabular_ids.len() {
         return Err(ErrorModel::internal(
             "Mismatch between task IDs in tabular_expirations and to-be-deleted tabulars.",
             "InternalDatabaseError",
@@ -624,11 +1084,34 @@ pub(crate) async fn clear_tabular_deleted_at(
         )
         .into());
     }
+    if tasks.iter().any(|task| task.running) {
+        return Err(ErrorModel::conflict(
+            "The expiration of some of the tabulars is in progress. Retry after it finished or failed.",
+            "TabularExpirationRunning",
+            None,
+        )
+        .into());
+    }
 
-    Ok(task_id
+    let task_ids = tasks
         .into_iter()
-        .map(|task_id| TaskId::from(task_id.task_id))
-        .collect::<Vec<TaskId>>())
+        .map(|task| task.task_id)
+        .collect::<Vec<_>>();
+    sqlx::query!(
+        r#"
+        UPDATE task SET status = 'cancelled'
+        WHERE task_id = any($1) AND status = 'pending'
+        "#,
+        &task_ids
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::warn!("Error cancelling expiration tasks: {}", e);
+        e.into_error_model("Error cancelling expiration tasks")
+    })?;
+
+    Ok(task_ids.into_iter().map(TaskId::from).collect())
 }
 
 pub(crate) async fn mark_tabular_as_deleted(
@@ -694,7 +1177,7 @@ pub(crate) async fn drop_tabular(
     Ok(location)
 }
 
-fn try_parse_namespace_ident(namespace: Vec<String>) -> Result<NamespaceIdent> {
+pub(crate) fn try_parse_namespace_ident(namespace: Vec<String>) -> Result<NamespaceIdent> {
     NamespaceIdent::from_vec(namespace).map_err(|e| {
         ErrorModel::internal(
             "Error parsing namespace",
@@ -723,6 +1206,15 @@ impl<'a> From<&'a TabularIdentUuid> for TabularType {
     }
 }
 
+impl From<crate::api::management::v1::TabularType> for TabularType {
+    fn from(typ: crate::api::management::v1::TabularType) -> Self {
+        match typ {
+            crate::api::management::v1::TabularType::Table => TabularType::Table,
+            crate::api::management::v1::TabularType::View => TabularType::View,
+        }
+    }
+}
+
 impl From<TabularIdentUuid> for TabularType {
     fn from(ident: TabularIdentUuid) -> Self {
         match ident {
@@ -1,5 +1,6 @@
 use crate::catalog::tables::TableMetadataDiffs;
 use crate::implementations::postgres::dbutils::DBErrorHandler;
+use crate::implementations::postgres::property_history::record_table_properties;
 use crate::implementations::postgres::tabular::table::common::{
     expire_metadata_log_entries, remove_snapshot_log_entries,
 };
@@ -29,21 +30,45 @@ pub(crate) async fn commit_table_transaction(
             let TableCommit {
                 new_metadata,
                 new_metadata_location,
+                previous_metadata_location,
                 updates,
                 diffs,
             } = c;
-            let t = (new_metadata, new_metadata_location);
+            let t = (
+                new_metadata,
+                new_metadata_location,
+                previous_metadata_location,
+            );
             (t, (updates, diffs))
         })
         .unzip();
 
-    for ((updates, diffs), (meta, _)) in atomic.into_iter().zip(meta.iter()) {
+    let (mut query_meta_update, mut query_meta_location_update) = build_queries(n_commits, &meta);
+
+    // The location is swapped first: it only matches if the table was not changed since its
+    // metadata was loaded, and it locks the table until the transaction completes.
+    // Concurrent commits to the same table thus fail instead of overwriting each other.
+    let updated_meta_location = query_meta_location_update
+        .build()
+        .fetch_all(&mut **transaction)
+        .await
+        .map_err(|e| {
+            e.into_error_model("Error committing tablemetadata location updates".to_string())
+        })?;
+    if updated_meta_location.len() != n_commits {
+        return Err(ErrorModel::conflict(
+            "Table was changed by a concurrent commit, retry the commit",
+            "ConcurrentCommit",
+            None,
+        )
+        .into());
+    }
+
+    for ((updates, diffs), (meta, _, _)) in atomic.into_iter().zip(meta.iter()) {
         let updates = TableUpdates::from(updates.as_slice());
         handle_atomic_updates(transaction, updates, meta, diffs).await?;
     }
 
-    let (mut query_meta_update, mut query_meta_location_update) = build_queries(n_commits, meta);
-
     // futures::try_join didn't work due to concurrent mutable borrow of transaction
     let updated_meta = query_meta_update
         .build()
@@ -51,14 +76,6 @@ pub(crate) async fn commit_table_transaction(
         .await
         .map_err(|e| e.into_error_model("Error committing tablemetadata updates".to_string()))?;
 
-    let updated_meta_location = query_meta_location_update
-        .build()
-        .fetch_all(&mut **transaction)
-        .await
-        .map_err(|e| {
-            e.into_error_model("Error committing tablemetadata location updates".to_string())
-        })?;
-
     check_post_conditions(updated_meta.len(), n_commits, updated_meta_location.len())?;
 
     Ok(())
@@ -66,7 +83,7 @@ pub(crate) async fn commit_table_transaction(
 
 fn build_queries(
     n_commits: usize,
-    meta: Vec<(TableMetadata, Location)>,
+    meta: &[(TableMetadata, Location, Option<Location>)],
 ) -> (
     sqlx::QueryBuilder<'static, Postgres>,
     sqlx::QueryBuilder<'static, Postgres>,
@@ -91,7 +108,9 @@ fn build_queries(
         FROM (VALUES
         "#,
     );
-    for (i, (new_metadata, new_metadata_location)) in meta.into_iter().enumerate() {
+    for (i, (new_metadata, new_metadata_location, previous_metadata_location)) in
+        meta.iter().enumerate()
+    {
         query_builder_table.push("(");
         query_builder_table.push_bind(new_metadata.uuid());
         query_builder_table.push(", ");
@@ -115,6 +134,9 @@ fn build_queries(
         query_builder_tabular.push_bind(new_metadata_location.to_string());
         query_builder_tabular.push(", ");
         query_builder_tabular.push_bind(new_metadata.location().to_string());
+        query_builder_tabular.push(", ");
+        query_builder_tabular
+            .push_bind(previous_metadata_location.as_ref().map(ToString::to_string));
         query_builder_tabular.push(")");
 
         if i != n_commits - 1 {
@@ -126,7 +148,7 @@ fn build_queries(
     query_builder_table
         .push(") as c(table_id, table_format_version, last_column_id, last_sequence_number, last_updated_ms, last_partition_id) WHERE c.table_id = t.table_id");
     query_builder_tabular.push(
-        ") as c(table_id, metadata_location, location) WHERE c.table_id = t.tabular_id AND t.typ = 'table'",
+        ") as c(table_id, metadata_location, location, previous_metadata_location) WHERE c.table_id = t.tabular_id AND t.typ = 'table' AND t.metadata_location IS NOT DISTINCT FROM c.previous_metadata_location",
     );
 
     query_builder_table.push(" RETURNING t.table_id");
@@ -348,6 +370,8 @@ async fn handle_atomic_updates(
     }
 
     if properties {
+        record_table_properties(new_metadata.uuid(), new_metadata.properties(), transaction)
+            .await?;
         common::set_table_properties(new_metadata.uuid(), new_metadata.properties(), transaction)
             .await?;
     }
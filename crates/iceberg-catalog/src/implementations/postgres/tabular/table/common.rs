@@ -567,7 +567,10 @@ pub(super) async fn insert_partition_statistics(
 
     let _ = sqlx::query!(
         r#"INSERT INTO partition_statistics(snapshot_id, table_id, statistics_path, file_size_in_bytes)
-           SELECT UNNEST($1::BIGINT[]), $2, UNNEST($3::TEXT[]), UNNEST($4::BIGINT[])"#,
+           SELECT UNNEST($1::BIGINT[]), $2, UNNEST($3::TEXT[]), UNNEST($4::BIGINT[])
+           ON CONFLICT (table_id, snapshot_id) DO UPDATE
+           SET statistics_path = EXCLUDED.statistics_path,
+               file_size_in_bytes = EXCLUDED.file_size_in_bytes"#,
         &snapshot_ids,
         tabular_id,
         &paths,
@@ -589,7 +592,7 @@ pub(super) async fn remove_partition_statistics(
     transaction: &mut Transaction<'_, Postgres>,
 ) -> api::Result<()> {
     let _ = sqlx::query!(
-        r#"DELETE FROM table_statistics WHERE table_id = $1 AND snapshot_id = ANY($2::BIGINT[])"#,
+        r#"DELETE FROM partition_statistics WHERE table_id = $1 AND snapshot_id = ANY($2::BIGINT[])"#,
         table_id,
         &statistics_ids,
     )
@@ -597,7 +600,7 @@ pub(super) async fn remove_partition_statistics(
     .await
     .map_err(|err| {
         tracing::warn!("Error creating table: {}", err);
-        err.into_error_model("Error deleting table statistics".to_string())
+        err.into_error_model("Error deleting partition statistics".to_string())
     })?;
 
     Ok(())
@@ -636,7 +639,13 @@ pub(super) async fn insert_table_statistics(
 
     let _ = sqlx::query!(
         r#"INSERT INTO table_statistics(snapshot_id, table_id, statistics_path, file_size_in_bytes, file_footer_size_in_bytes, key_metadata, blob_metadata)
-           SELECT UNNEST($1::BIGINT[]), $2, UNNEST($3::TEXT[]), UNNEST($4::BIGINT[]), UNNEST($5::BIGINT[]), UNNEST($6::TEXT[]), UNNEST($7::JSONB[])"#,
+           SELECT UNNEST($1::BIGINT[]), $2, UNNEST($3::TEXT[]), UNNEST($4::BIGINT[]), UNNEST($5::BIGINT[]), UNNEST($6::TEXT[]), UNNEST($7::JSONB[])
+           ON CONFLICT (table_id, snapshot_id) DO UPDATE
+           SET statistics_path = EXCLUDED.statistics_path,
+               file_size_in_bytes = EXCLUDED.file_size_in_bytes,
+               file_footer_size_in_bytes = EXCLUDED.file_footer_size_in_bytes,
+               key_metadata = EXCLUDED.key_metadata,
+               blob_metadata = EXCLUDED.blob_metadata"#,
         &snapshot_ids,
         tabular_id,
         &paths,
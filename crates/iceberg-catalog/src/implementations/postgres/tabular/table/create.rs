@@ -2,11 +2,13 @@ use crate::api::{self, Result};
 use crate::implementations::postgres::dbutils::DBErrorHandler;
 use crate::implementations::postgres::tabular::table::{common, DbTableFormatVersion};
 use crate::implementations::postgres::tabular::{create_tabular, CreateTabular, TabularType};
+use crate::service::id_generation::PROPERTY_EXTERNAL_ID;
 use crate::service::{CreateTableResponse, NamespaceIdentUuid, TableCreation, TableIdentUuid};
-use iceberg::spec::{FormatVersion, TableMetadata};
+use iceberg::spec::{FormatVersion, Schema, TableMetadata};
 use iceberg::TableIdent;
 use iceberg_ext::catalog::rest::ErrorModel;
 use iceberg_ext::configs::Location;
+use sqlx::types::Json;
 use sqlx::{Postgres, Transaction};
 use std::str::FromStr;
 use uuid::Uuid;
@@ -17,6 +19,7 @@ pub(crate) async fn create_table(
         table_ident,
         table_metadata,
         metadata_location,
+        force_expire_property,
     }: TableCreation<'_>,
     transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
 ) -> api::Result<CreateTableResponse> {
@@ -39,6 +42,11 @@ pub(crate) async fn create_table(
             typ: TabularType::Table,
             metadata_location,
             location: &location,
+            external_id: table_metadata
+                .properties()
+                .get(PROPERTY_EXTERNAL_ID)
+                .map(String::as_str),
+            force_expire_property,
         },
         transaction,
     )
@@ -98,6 +106,36 @@ pub(crate) async fn create_table(
     })
 }
 
+pub(crate) async fn get_replaced_table_schema(
+    namespace_id: NamespaceIdentUuid,
+    name: &str,
+    transaction: &mut Transaction<'_, Postgres>,
+) -> Result<Option<Schema>> {
+    // Staged tables are replaced directly, soft-deleted tables keep their name until they expire.
+    // Prefer the staged table, then the most recently deleted one.
+    let schema = sqlx::query_scalar!(
+        r#"SELECT ts.schema as "schema: Json<Schema>"
+           FROM tabular t
+           INNER JOIN table_current_schema tcs ON tcs.table_id = t.tabular_id
+           INNER JOIN table_schema ts ON ts.table_id = tcs.table_id AND ts.schema_id = tcs.schema_id
+           WHERE t.namespace_id = $1 AND t.name = $2
+               AND (t.metadata_location IS NULL OR t.deleted_at IS NOT NULL)
+           ORDER BY t.metadata_location IS NULL DESC, t.deleted_at DESC
+           LIMIT 1
+        "#,
+        *namespace_id,
+        name
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| {
+        tracing::warn!(?e, "Error fetching schema of replaced table");
+        e.into_error_model("Error fetching schema of replaced table".to_string())
+    })?;
+
+    Ok(schema.map(|Json(schema)| schema))
+}
+
 async fn maybe_delete_staged_table(
     namespace_id: NamespaceIdentUuid,
     transaction: &mut Transaction<'_, Postgres>,
@@ -0,0 +1,186 @@
+use crate::implementations::postgres::{dbutils::DBErrorHandler as _, CatalogState};
+use crate::service::{ErrorModel, Result, TableIdentUuid, TableMetrics, TableMetricsReport};
+
+pub(crate) async fn record_table_metrics(
+    table_id: TableIdentUuid,
+    report: TableMetricsReport,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let (scans, scanned_data_files, scanned_bytes, planning_duration_ms) = match report {
+        TableMetricsReport::Scan {
+            data_files,
+            bytes,
+            planning_duration_ms,
+        } => (1, data_files, bytes, planning_duration_ms),
+        TableMetricsReport::Commit { .. } => (0, 0, 0, 0),
+    };
+    let (commits, committed_data_files, committed_bytes) = match report {
+        TableMetricsReport::Commit { data_files, bytes } => (1, data_files, bytes),
+        TableMetricsReport::Scan { .. } => (0, 0, 0),
+    };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO table_metrics (
+            table_id,
+            scan_count,
+            scanned_data_files,
+            scanned_bytes,
+            total_planning_duration_ms,
+            commit_count,
+            committed_data_files,
+            committed_bytes,
+            last_scan_at,
+            last_commit_at
+        )
+        VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8,
+            CASE WHEN $2::bigint > 0 THEN now() END,
+            CASE WHEN $6::bigint > 0 THEN now() END
+        )
+        ON CONFLICT (table_id) DO UPDATE SET
+            scan_count = table_metrics.scan_count + EXCLUDED.scan_count,
+            scanned_data_files = table_metrics.scanned_data_files + EXCLUDED.scanned_data_files,
+            scanned_bytes = table_metrics.scanned_bytes + EXCLUDED.scanned_bytes,
+            total_planning_duration_ms = table_metrics.total_planning_duration_ms + EXCLUDED.total_planning_duration_ms,
+            commit_count = table_metrics.commit_count + EXCLUDED.commit_count,
+            committed_data_files = table_metrics.committed_data_files + EXCLUDED.committed_data_files,
+            committed_bytes = table_metrics.committed_bytes + EXCLUDED.committed_bytes,
+            last_scan_at = COALESCE(EXCLUDED.last_scan_at, table_metrics.last_scan_at),
+            last_commit_at = COALESCE(EXCLUDED.last_commit_at, table_metrics.last_commit_at)
+        "#,
+        *table_id,
+        scans,
+        scanned_data_files,
+        scanned_bytes,
+        planning_duration_ms,
+        commits,
+        committed_data_files,
+        committed_bytes
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_err) => match db_err.constraint() {
+            Some("table_metrics_table_id_fkey") => ErrorModel::not_found(
+                format!("Table {table_id} not found"),
+                "TableNotFound",
+                Some(Box::new(e)),
+            ),
+            _ => e.into_error_model("Error recording table metrics"),
+        },
+        _ => e.into_error_model("Error recording table metrics"),
+    })?;
+
+    Ok(())
+}
+
+pub(crate) async fn get_table_metrics(
+    table_id: TableIdentUuid,
+    catalog_state: CatalogState,
+) -> Result<TableMetrics> {
+    let metrics = sqlx::query_as!(
+        TableMetrics,
+        r#"
+        SELECT
+            scan_count,
+            scanned_data_files,
+            scanned_bytes,
+            total_planning_duration_ms,
+            commit_count,
+            committed_data_files,
+            committed_bytes,
+            last_scan_at,
+            last_commit_at
+        FROM table_metrics
+        WHERE table_id = $1
+        "#,
+        *table_id
+    )
+    .fetch_optional(&catalog_state.read_pool())
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table metrics"))?;
+
+    Ok(metrics.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::PostgresTransaction;
+    use crate::service::Transaction as _;
+
+    #[sqlx::test]
+    async fn test_record_table_metrics(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+
+        let metrics = get_table_metrics(table.table_id, state.clone())
+            .await
+            .unwrap();
+        assert_eq!(metrics, TableMetrics::default());
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            record_table_metrics(
+                table.table_id,
+                TableMetricsReport::Scan {
+                    data_files: 3,
+                    bytes: 100,
+                    planning_duration_ms: 10,
+                },
+                t.transaction(),
+            )
+            .await
+            .unwrap();
+        }
+        record_table_metrics(
+            table.table_id,
+            TableMetricsReport::Commit {
+                data_files: 1,
+                bytes: 50,
+            },
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+
+        let metrics = get_table_metrics(table.table_id, state.clone())
+            .await
+            .unwrap();
+        assert_eq!(metrics.scan_count, 2);
+        assert_eq!(metrics.scanned_data_files, 6);
+        assert_eq!(metrics.scanned_bytes, 200);
+        assert_eq!(metrics.total_planning_duration_ms, 20);
+        assert_eq!(metrics.commit_count, 1);
+        assert_eq!(metrics.committed_data_files, 1);
+        assert_eq!(metrics.committed_bytes, 50);
+        assert!(metrics.last_scan_at.is_some());
+        assert!(metrics.last_commit_at.is_some());
+    }
+
+    #[sqlx::test]
+    async fn test_record_table_metrics_unknown_table(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let err = record_table_metrics(
+            TableIdentUuid::from(uuid::Uuid::now_v7()),
+            TableMetricsReport::Commit {
+                data_files: 1,
+                bytes: 1,
+            },
+            t.transaction(),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, http::StatusCode::NOT_FOUND);
+    }
+}
@@ -1,9 +1,15 @@
 mod commit;
 mod common;
 mod create;
+mod metrics;
+mod overview;
+mod relocate;
 
 pub(crate) use commit::commit_table_transaction;
-pub(crate) use create::create_table;
+pub(crate) use create::{create_table, get_replaced_table_schema};
+pub(crate) use metrics::{get_table_metrics, record_table_metrics};
+pub(crate) use overview::get_table_overview;
+pub(crate) use relocate::relocate_table;
 
 use crate::implementations::postgres::{dbutils::DBErrorHandler as _, CatalogState};
 use crate::{
@@ -931,8 +937,8 @@ pub(crate) mod tests {
     use std::time::SystemTime;
 
     use crate::catalog::tables::create_table_request_into_table_metadata;
-    use crate::implementations::postgres::tabular::mark_tabular_as_deleted;
     use crate::implementations::postgres::tabular::table::create::create_table;
+    use crate::implementations::postgres::tabular::{list_location_usage, mark_tabular_as_deleted};
     use iceberg::spec::{
         NestedField, Operation, PrimitiveType, Schema, Snapshot, SnapshotReference,
         UnboundPartitionSpec,
@@ -1091,6 +1097,7 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            force_expire_property: None,
         };
         let mut transaction = state.write_pool().begin().await.unwrap();
         let _create_result = create_table(create, &mut transaction).await.unwrap();
@@ -1132,6 +1139,7 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            force_expire_property: None,
         };
 
         let create_result = create_table(request.clone(), &mut transaction)
@@ -1200,6 +1208,7 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            force_expire_property: None,
         };
 
         let _create_result = create_table(request.clone(), &mut transaction)
@@ -1245,6 +1254,7 @@ pub(crate) mod tests {
             table_ident: &table_ident,
             table_metadata,
             metadata_location: metadata_location.as_ref(),
+            force_expire_property: None,
         };
         let mut transaction = pool.begin().await.unwrap();
         let create_result = create_table(request, &mut transaction).await.unwrap();
@@ -1678,6 +1688,55 @@ pub(crate) mod tests {
         assert!(tables.next_token().is_none());
     }
 
+    #[sqlx::test]
+    async fn test_list_tables_pagination_with_equal_created_at(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let namespace = NamespaceIdent::from_vec(vec!["my_namespace".to_string()]).unwrap();
+        initialize_namespace(state.clone(), warehouse_id, &namespace, None).await;
+        let namespace_id = get_namespace_id(state.clone(), warehouse_id, &namespace).await;
+
+        // Bulk inserted rows share their `created_at`, so pages are only separated
+        // by the `tabular_id` part of the keyset.
+        let table_ids = sqlx::query_scalar!(
+            r#"INSERT INTO tabular (tabular_id, namespace_id, name, typ, metadata_location, location, table_migrated)
+               SELECT gen_random_uuid(), $1, 't' || i, 'table', 's3://bucket/t' || i || '/metadata.json', 's3://bucket/t' || i, true
+               FROM generate_series(1, 2500) i
+               RETURNING tabular_id"#,
+            *namespace_id
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        let mut listed = Vec::new();
+        let mut page_token = PageToken::NotSpecified;
+        loop {
+            let tables = list_tables(
+                warehouse_id,
+                &namespace,
+                ListFlags::default(),
+                &state.read_pool(),
+                PaginationQuery {
+                    page_token,
+                    page_size: Some(1000),
+                },
+            )
+            .await
+            .unwrap();
+            if tables.is_empty() {
+                break;
+            }
+            page_token = PageToken::Present(tables.next_token().unwrap().to_string());
+            listed.extend(tables.into_iter().map(|(id, _)| *id));
+        }
+
+        let mut expected = table_ids;
+        expected.sort_unstable();
+        assert_eq!(listed, expected);
+    }
+
     #[sqlx::test]
     async fn test_get_id_by_location(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -1787,6 +1846,76 @@ pub(crate) mod tests {
         assert!(r.is_none());
     }
 
+    #[sqlx::test]
+    async fn test_list_location_usage(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let staged = initialize_table(warehouse_id, state.clone(), true, None, None).await;
+
+        let metadata = get_table_metadata_by_id(
+            warehouse_id,
+            table.table_id,
+            ListFlags::default(),
+            state.clone(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let table_location = metadata.location.parse::<Location>().unwrap();
+
+        // Own location and subpaths find the table
+        let mut subpath = table_location.clone();
+        subpath.push("data/foo.parquet");
+        for location in [&table_location, &subpath] {
+            let usages = list_location_usage(
+                warehouse_id,
+                location,
+                &state.read_pool(),
+                PaginationQuery::empty(),
+            )
+            .await
+            .unwrap();
+            assert_eq!(usages.len(), 1);
+            let (id, usage, _) = usages.into_iter_with_page_tokens().next().unwrap();
+            assert_eq!(id, TabularIdentUuid::Table(*table.table_id));
+            assert_eq!(usage.tabular.into_inner(), table.table_ident);
+            assert!(!usage.staged);
+            assert!(usage.deleted_at.is_none());
+        }
+
+        // Parent location finds both tables, including the staged one
+        let parent = "s3://my_bucket/my_table".parse::<Location>().unwrap();
+        let usages = list_location_usage(
+            warehouse_id,
+            &parent,
+            &state.read_pool(),
+            PaginationQuery::empty(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(usages.len(), 2);
+        let usage = usages
+            .get(&TabularIdentUuid::Table(*staged.table_id))
+            .unwrap();
+        assert!(usage.staged);
+
+        // Locations sharing only a string prefix are not considered
+        let sibling = format!("{}x", table_location.as_str().trim_end_matches('/'))
+            .parse::<Location>()
+            .unwrap();
+        let usages = list_location_usage(
+            warehouse_id,
+            &sibling,
+            &state.read_pool(),
+            PaginationQuery::empty(),
+        )
+        .await
+        .unwrap();
+        assert!(usages.is_empty());
+    }
+
     #[sqlx::test]
     async fn test_drop_table_works(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use iceberg::spec::{FormatVersion, PartitionSpec, Schema, Snapshot, Summary, MAIN_BRANCH};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+use super::DbTableFormatVersion;
+use crate::api::management::v1::table::TableOverview;
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::service::{Result, TableIdentUuid};
+use crate::WarehouseIdent;
+
+#[derive(Debug)]
+struct TableOverviewRow {
+    name: String,
+    namespace_name: Vec<String>,
+    location: String,
+    metadata_location: String,
+    table_format_version: DbTableFormatVersion,
+    last_updated_ms: i64,
+    current_snapshot_id: Option<i64>,
+    current_manifest_list: Option<String>,
+    snapshot_count: i64,
+    schema: Json<Schema>,
+    partition_spec: Json<PartitionSpec>,
+    properties: Option<Json<HashMap<String, String>>>,
+}
+
+#[derive(Debug)]
+struct SnapshotRow {
+    snapshot_id: i64,
+    parent_snapshot_id: Option<i64>,
+    sequence_number: i64,
+    manifest_list: String,
+    summary: Json<Summary>,
+    schema_id: Option<i32>,
+    timestamp_ms: i64,
+}
+
+impl From<SnapshotRow> for Snapshot {
+    fn from(row: SnapshotRow) -> Self {
+        let builder = Snapshot::builder()
+            .with_manifest_list(row.manifest_list)
+            .with_parent_snapshot_id(row.parent_snapshot_id)
+            .with_sequence_number(row.sequence_number)
+            .with_snapshot_id(row.snapshot_id)
+            .with_summary(row.summary.0)
+            .with_timestamp_ms(row.timestamp_ms);
+        if let Some(schema_id) = row.schema_id {
+            builder.with_schema_id(schema_id).build()
+        } else {
+            builder.build()
+        }
+    }
+}
+
+/// Only reads the rows needed for the overview, unlike [`super::load_tables`],
+/// which aggregates every schema, spec, snapshot and log entry of the table.
+pub(crate) async fn get_table_overview(
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    snapshot_limit: u32,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<TableOverview>> {
+    let row = sqlx::query_as!(
+        TableOverviewRow,
+        r#"
+        SELECT
+            ti.name,
+            n.namespace_name,
+            ti.location,
+            ti.metadata_location as "metadata_location!",
+            t.table_format_version as "table_format_version!: DbTableFormatVersion",
+            t.last_updated_ms as "last_updated_ms!",
+            (SELECT snapshot_id FROM table_refs tr
+             WHERE tr.table_id = t.table_id AND tr.table_ref_name = $3) as current_snapshot_id,
+            (SELECT tsnap.manifest_list FROM table_refs tr
+             INNER JOIN table_snapshot tsnap
+                ON tsnap.table_id = tr.table_id AND tsnap.snapshot_id = tr.snapshot_id
+             WHERE tr.table_id = t.table_id AND tr.table_ref_name = $3) as current_manifest_list,
+            (SELECT count(*) FROM table_snapshot tsnap
+             WHERE tsnap.table_id = t.table_id) as "snapshot_count!",
+            ts.schema as "schema: Json<Schema>",
+            tps.partition_spec as "partition_spec: Json<PartitionSpec>",
+            (SELECT jsonb_object_agg(tp.key, tp.value) FROM table_properties tp
+             WHERE tp.table_id = t.table_id) as "properties: Json<HashMap<String, String>>"
+        FROM "table" t
+        INNER JOIN tabular ti ON t.table_id = ti.tabular_id
+        INNER JOIN namespace n ON ti.namespace_id = n.namespace_id
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        INNER JOIN table_current_schema tcs ON tcs.table_id = t.table_id
+        INNER JOIN table_schema ts ON ts.table_id = t.table_id AND ts.schema_id = tcs.schema_id
+        INNER JOIN table_default_partition_spec tdps ON tdps.table_id = t.table_id
+        INNER JOIN table_partition_spec tps
+            ON tps.table_id = t.table_id AND tps.partition_spec_id = tdps.partition_spec_id
+        WHERE w.warehouse_id = $1
+            AND w.status = 'active'
+            AND t.table_id = $2
+            AND ti.deleted_at IS NULL
+            AND ti.metadata_location IS NOT NULL
+        "#,
+        *warehouse_id,
+        *table_id,
+        MAIN_BRANCH
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table overview"))?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let snapshots = sqlx::query_as!(
+        SnapshotRow,
+        r#"
+        SELECT
+            snapshot_id,
+            parent_snapshot_id,
+            sequence_number,
+            manifest_list,
+            summary as "summary: Json<Summary>",
+            schema_id,
+            timestamp_ms
+        FROM table_snapshot
+        WHERE table_id = $1
+        ORDER BY sequence_number DESC, timestamp_ms DESC
+        LIMIT $2
+        "#,
+        *table_id,
+        i64::from(snapshot_limit)
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching table snapshots"))?;
+
+    Ok(Some(TableOverview {
+        table_id: Uuid::from(table_id),
+        name: row.name,
+        namespace: row.namespace_name,
+        location: row.location,
+        metadata_location: row.metadata_location,
+        format_version: FormatVersion::from(row.table_format_version),
+        last_updated_ms: row.last_updated_ms,
+        current_snapshot_id: row.current_snapshot_id,
+        current_manifest_list: row.current_manifest_list,
+        snapshot_count: row.snapshot_count,
+        schema: row.schema.0,
+        partition_spec: row.partition_spec.0,
+        properties: row.properties.map(|p| p.0).unwrap_or_default(),
+        snapshots: snapshots.into_iter().map(Snapshot::from).collect(),
+        manifest_statistics: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::initialize_table;
+    use super::*;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::CatalogState;
+
+    #[sqlx::test]
+    async fn test_get_table_overview(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let table = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+
+        let mut transaction = pool.begin().await.unwrap();
+        let overview = get_table_overview(warehouse_id, table.table_id, 10, &mut transaction)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(overview.table_id, *table.table_id);
+        assert_eq!(overview.name, table.table_ident.name);
+        assert_eq!(overview.namespace, table.namespace.clone().inner());
+        assert_eq!(overview.snapshot_count, 1);
+        assert_eq!(overview.snapshots.len(), 1);
+        assert_eq!(overview.snapshots[0].snapshot_id(), 1);
+        // The snapshot is only referenced by a tag
+        assert_eq!(overview.current_snapshot_id, None);
+        assert_eq!(overview.current_manifest_list, None);
+        assert_eq!(overview.schema.as_struct().fields().len(), 2);
+
+        let overview = get_table_overview(warehouse_id, table.table_id, 0, &mut transaction)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(overview.snapshots.is_empty());
+        assert_eq!(overview.snapshot_count, 1);
+
+        let staged = initialize_table(warehouse_id, state.clone(), true, None, None).await;
+        assert!(
+            get_table_overview(warehouse_id, staged.table_id, 10, &mut transaction)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        transaction.commit().await.unwrap();
+    }
+}
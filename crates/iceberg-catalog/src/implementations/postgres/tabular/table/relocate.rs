@@ -0,0 +1,79 @@
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::service::{ErrorModel, Result, TableRelocation};
+
+pub(crate) async fn relocate_table(
+    TableRelocation {
+        table_id,
+        source_location,
+        target_location,
+        previous_metadata_location,
+        new_metadata_location,
+    }: &TableRelocation,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    let updated = sqlx::query_scalar!(
+        r#"
+        UPDATE tabular
+        SET location = $2, metadata_location = $3
+        WHERE tabular_id = $1
+            AND typ = 'table'
+            AND deleted_at IS NULL
+            AND rtrim(location, '/') = rtrim($4, '/')
+            AND metadata_location IS NOT DISTINCT FROM $5
+        RETURNING tabular_id
+        "#,
+        **table_id,
+        target_location.to_string(),
+        new_metadata_location.to_string(),
+        source_location.to_string(),
+        previous_metadata_location.as_ref().map(ToString::to_string),
+    )
+    .fetch_optional(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error updating table location"))?;
+
+    if updated.is_none() {
+        return Err(ErrorModel::conflict(
+            format!("Table {table_id} was modified while it was relocated"),
+            "TableChangedDuringRelocation",
+            None,
+        )
+        .into());
+    }
+
+    // Paths are moved by replacing the prefix, locations are compared with a trailing slash
+    // so that `s3://bucket/table-1` does not match `s3://bucket/table-10`.
+    let source_prefix = format!("{}/", source_location.as_str().trim_end_matches('/'));
+    let target_prefix = format!("{}/", target_location.as_str().trim_end_matches('/'));
+
+    sqlx::query!(
+        r#"
+        WITH snapshots AS (
+            UPDATE table_snapshot
+            SET manifest_list = $3 || substr(manifest_list, length($2) + 1)
+            WHERE table_id = $1 AND starts_with(manifest_list, $2)
+        ),
+        metadata_log AS (
+            UPDATE table_metadata_log
+            SET metadata_file = $3 || substr(metadata_file, length($2) + 1)
+            WHERE table_id = $1 AND starts_with(metadata_file, $2)
+        ),
+        statistics AS (
+            UPDATE table_statistics
+            SET statistics_path = $3 || substr(statistics_path, length($2) + 1)
+            WHERE table_id = $1 AND starts_with(statistics_path, $2)
+        )
+        UPDATE partition_statistics
+        SET statistics_path = $3 || substr(statistics_path, length($2) + 1)
+        WHERE table_id = $1 AND starts_with(statistics_path, $2)
+        "#,
+        **table_id,
+        source_prefix,
+        target_prefix,
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error updating paths of relocated table"))?;
+
+    Ok(())
+}
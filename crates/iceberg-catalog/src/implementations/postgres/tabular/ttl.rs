@@ -0,0 +1,65 @@
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::service::{NamespaceIdent, Result, TableTtlCandidate};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+
+/// Active tables of active warehouses in namespaces with a `table-ttl` property,
+/// excluding tables with the `table-ttl-exempt` property set to `true`.
+pub(crate) async fn list_table_ttl_candidates<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    connection: E,
+) -> Result<Vec<TableTtlCandidate>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.tabular_id,
+               n.warehouse_id,
+               n.namespace_name,
+               t.name,
+               t.created_at,
+               t.updated_at,
+               n.namespace_properties ->> 'table-ttl' as "ttl!",
+               n.namespace_properties ->> 'table-ttl-basis' as "basis"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        INNER JOIN warehouse w ON n.warehouse_id = w.warehouse_id
+        WHERE t.typ = 'table'
+            AND t.deleted_at is null
+            AND t.metadata_location is not null
+            AND w.status = 'active'
+            AND n.namespace_properties ->> 'table-ttl' is not null
+            AND NOT EXISTS (
+                SELECT 1 FROM table_properties p
+                WHERE p.table_id = t.tabular_id
+                    AND p.key = 'table-ttl-exempt'
+                    AND lower(p.value) = 'true'
+            )
+        "#
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing tables with a TTL".to_string()))?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(TableTtlCandidate {
+                table_id: row.tabular_id.into(),
+                warehouse_id: WarehouseIdent::from(row.warehouse_id),
+                namespace: NamespaceIdent::from_vec(row.namespace_name).map_err(|e| {
+                    ErrorModel::internal(
+                        "Error converting namespace",
+                        "NamespaceConversionError",
+                        Some(Box::new(e)),
+                    )
+                })?,
+                name: row.name,
+                created_at: row.created_at,
+                last_commit_at: row.updated_at,
+                ttl: row.ttl,
+                basis: row.basis,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,124 @@
+use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::implementations::postgres::tabular::try_parse_namespace_ident;
+use crate::service::{
+    Result, TableIdent, TableIdentUuid, ViewDependency, ViewDependent, ViewIdentUuid,
+};
+use crate::WarehouseIdent;
+use uuid::Uuid;
+
+pub(crate) async fn set_view_dependencies(
+    view_id: ViewIdentUuid,
+    table_ids: &[TableIdentUuid],
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<()> {
+    sqlx::query!(
+        "DELETE FROM view_table_dependency WHERE view_id = $1",
+        *view_id
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting view dependencies"))?;
+
+    if table_ids.is_empty() {
+        return Ok(());
+    }
+    let table_ids = table_ids.iter().map(|id| **id).collect::<Vec<Uuid>>();
+    sqlx::query!(
+        r#"
+        INSERT INTO view_table_dependency (view_id, table_id, snapshot_id, recorded_at)
+        SELECT $1, d.table_id, r.snapshot_id, now()
+        FROM UNNEST($2::uuid[]) AS d(table_id)
+        LEFT JOIN table_refs r ON r.table_id = d.table_id AND r.table_ref_name = 'main'
+        ON CONFLICT DO NOTHING
+        "#,
+        *view_id,
+        &table_ids
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error inserting view dependencies"))?;
+
+    Ok(())
+}
+
+fn table_ident(namespace_name: Vec<String>, name: String) -> Result<TableIdent> {
+    Ok(TableIdent {
+        namespace: try_parse_namespace_ident(namespace_name)?,
+        name,
+    })
+}
+
+pub(crate) async fn list_view_dependents(
+    warehouse_id: WarehouseIdent,
+    table_id: TableIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<ViewDependent>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.tabular_id, t.name, n.namespace_name
+        FROM view_table_dependency d
+        INNER JOIN tabular t ON t.tabular_id = d.view_id
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        WHERE d.table_id = $1
+            AND n.warehouse_id = $2
+            AND t.deleted_at IS NULL
+        ORDER BY n.namespace_name, t.name
+        "#,
+        *table_id,
+        *warehouse_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching views depending on table"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(ViewDependent {
+                view_id: row.tabular_id.into(),
+                view: table_ident(row.namespace_name, row.name)?,
+            })
+        })
+        .collect()
+}
+
+pub(crate) async fn list_view_dependencies(
+    warehouse_id: WarehouseIdent,
+    view_id: ViewIdentUuid,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<ViewDependency>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT t.tabular_id, t.name, n.namespace_name,
+            d.snapshot_id, d.recorded_at,
+            r.snapshot_id AS "current_snapshot_id?",
+            s.timestamp_ms AS "current_snapshot_timestamp_ms?"
+        FROM view_table_dependency d
+        INNER JOIN tabular t ON t.tabular_id = d.table_id
+        INNER JOIN namespace n ON n.namespace_id = t.namespace_id
+        LEFT JOIN table_refs r ON r.table_id = d.table_id AND r.table_ref_name = 'main'
+        LEFT JOIN table_snapshot s ON s.table_id = r.table_id AND s.snapshot_id = r.snapshot_id
+        WHERE d.view_id = $1
+            AND n.warehouse_id = $2
+            AND t.deleted_at IS NULL
+        ORDER BY n.namespace_name, t.name
+        "#,
+        *view_id,
+        *warehouse_id
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching tables the view depends on"))?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(ViewDependency {
+                table_id: row.tabular_id.into(),
+                table: table_ident(row.namespace_name, row.name)?,
+                snapshot_id_at_view_commit: row.snapshot_id,
+                current_snapshot_id: row.current_snapshot_id,
+                current_snapshot_timestamp_ms: row.current_snapshot_timestamp_ms,
+                recorded_at: row.recorded_at,
+            })
+        })
+        .collect()
+}
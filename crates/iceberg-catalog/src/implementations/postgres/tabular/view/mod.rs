@@ -1,6 +1,8 @@
+mod dependencies;
 mod load;
 
 use crate::implementations::postgres::dbutils::DBErrorHandler as _;
+use crate::service::id_generation::PROPERTY_EXTERNAL_ID;
 use crate::{
     service::{ErrorModel, ListFlags, NamespaceIdentUuid, Result, TableIdent, ViewIdentUuid},
     WarehouseIdent,
@@ -15,6 +17,9 @@ use crate::implementations::postgres::tabular::{
 };
 pub(crate) use crate::service::ViewMetadataWithLocation;
 use chrono::{DateTime, Utc};
+pub(crate) use dependencies::{
+    list_view_dependencies, list_view_dependents, set_view_dependencies,
+};
 use iceberg::spec::{SchemaRef, ViewMetadata, ViewRepresentation, ViewVersionId, ViewVersionRef};
 use iceberg::NamespaceIdent;
 use iceberg_ext::configs::Location;
@@ -87,6 +92,11 @@ pub(crate) async fn create_view(
             typ: TabularType::View,
             metadata_location: Some(metadata_location),
             location,
+            external_id: metadata
+                .properties()
+                .get(PROPERTY_EXTERNAL_ID)
+                .map(String::as_str),
+            force_expire_property: None,
         },
         &mut *transaction,
     )
@@ -1,18 +1,56 @@
+mod table_inventory_export_queue;
+mod tabular_compaction_queue;
 mod tabular_expiration_queue;
 mod tabular_purge_queue;
+mod tabular_relocation_queue;
 
 use crate::implementations::postgres::dbutils::DBErrorHandler;
 use crate::implementations::postgres::ReadWrite;
-use crate::service::task_queue::{Task, TaskFilter, TaskQueueConfig, TaskStatus};
+use crate::service::task_queue::{Task, TaskFilter, TaskQueueConfig, TaskQueues, TaskStatus};
 use crate::WarehouseIdent;
+pub use table_inventory_export_queue::TableInventoryExportQueue;
+pub use tabular_compaction_queue::TabularCompactionQueue;
 pub use tabular_expiration_queue::TabularExpirationQueue;
 pub use tabular_purge_queue::TabularPurgeQueue;
+pub use tabular_relocation_queue::TabularRelocationQueue;
 
 use chrono::{DateTime, Utc};
 use iceberg_ext::catalog::rest::IcebergErrorResponse;
 use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
+/// All task queues required by the catalog, stored in Postgres.
+///
+/// # Errors
+/// Returns an error if the max age in `config` is invalid.
+pub fn task_queues_from_config(
+    read_write: &ReadWrite,
+    config: &TaskQueueConfig,
+) -> anyhow::Result<TaskQueues> {
+    Ok(TaskQueues::new(
+        std::sync::Arc::new(TabularExpirationQueue::from_config(
+            read_write.clone(),
+            config.clone(),
+        )?),
+        std::sync::Arc::new(TabularPurgeQueue::from_config(
+            read_write.clone(),
+            config.clone(),
+        )?),
+        std::sync::Arc::new(TabularRelocationQueue::from_config(
+            read_write.clone(),
+            config.clone(),
+        )?),
+        std::sync::Arc::new(TabularCompactionQueue::from_config(
+            read_write.clone(),
+            config.clone(),
+        )?),
+        std::sync::Arc::new(TableInventoryExportQueue::from_config(
+            read_write.clone(),
+            config.clone(),
+        )?),
+    ))
+}
+
 #[derive(Debug, Clone)]
 struct PgQueue {
     pub read_write: ReadWrite,
@@ -273,14 +311,39 @@ async fn cancel_pending_tasks(
     Ok(())
 }
 
+/// Replace the idempotency key of tasks that are done or failed since `older_than`
+/// by their task id, so that the same work can be queued again.
+pub(crate) async fn release_idempotency_keys(
+    older_than: chrono::Duration,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<u64, IcebergErrorResponse> {
+    #[allow(clippy::cast_precision_loss)]
+    let older_than_seconds = older_than.num_seconds() as f64;
+    let result = sqlx::query!(
+        r#"
+        UPDATE task
+        SET idempotency_key = task_id
+        WHERE status IN ('done', 'failed')
+        AND idempotency_key <> task_id
+        AND updated_at < now() - make_interval(secs => $1)
+        "#,
+        older_than_seconds
+    )
+    .execute(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("failed to release idempotency keys"))?;
+    Ok(result.rows_affected())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    use crate::service::task_queue::TaskQueue as _;
     use crate::WarehouseIdent;
     use sqlx::PgPool;
     use uuid::Uuid;
-    const TEST_WAREHOUSE: WarehouseIdent = WarehouseIdent(Uuid::nil());
+    const TEST_WAREHOUSE: WarehouseIdent = WarehouseIdent::new(Uuid::nil());
 
     #[sqlx::test]
     async fn test_queue_task(pool: PgPool) {
@@ -325,6 +388,56 @@ mod test {
         assert_ne!(id, id3);
     }
 
+    #[sqlx::test]
+    async fn test_release_idempotency_keys(pool: PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        let idempotency_key = Uuid::new_v5(&TEST_WAREHOUSE, b"test");
+        let id = queue_task(
+            &mut conn,
+            "test",
+            None,
+            idempotency_key,
+            TEST_WAREHOUSE,
+            None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        // Pending tasks keep their key
+        let mut t = pool.begin().await.unwrap();
+        let released = release_idempotency_keys(chrono::Duration::zero(), &mut t)
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+        assert_eq!(released, 0);
+
+        record_success(id, &pool).await.unwrap();
+        let mut t = pool.begin().await.unwrap();
+        let released = release_idempotency_keys(chrono::Duration::days(1), &mut t)
+            .await
+            .unwrap();
+        assert_eq!(released, 0, "The task finished too recently");
+        let released = release_idempotency_keys(chrono::Duration::zero(), &mut t)
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+        assert_eq!(released, 1);
+
+        let id2 = queue_task(
+            &mut conn,
+            "test",
+            None,
+            idempotency_key,
+            TEST_WAREHOUSE,
+            None,
+        )
+        .await
+        .unwrap()
+        .expect("The key should be free again");
+        assert_ne!(id, id2);
+    }
+
     pub(crate) fn setup(pool: PgPool, config: TaskQueueConfig) -> PgQueue {
         PgQueue::from_config(ReadWrite::from_pools(pool.clone(), pool), config).unwrap()
     }
@@ -566,4 +679,50 @@ mod test {
         record_success(task.task_id, &pool).await.unwrap();
         record_success(id2, &pool).await.unwrap();
     }
+
+    #[sqlx::test]
+    async fn test_queues_run_on_task_pools(pool: PgPool) {
+        let pool_with = |max_connections| {
+            sqlx::postgres::PgPoolOptions::new()
+                .max_connections(max_connections)
+                .acquire_timeout(std::time::Duration::from_millis(500))
+                .connect_lazy_with((*pool.connect_options()).clone())
+        };
+        let labels = |read_write: &ReadWrite| {
+            read_write
+                .labeled_pools()
+                .into_iter()
+                .map(|(label, _)| label)
+                .collect::<Vec<_>>()
+        };
+        let requests = ReadWrite::from_pools(pool_with(1), pool_with(1));
+        let config = TaskQueueConfig::default();
+
+        // Without dedicated pools, queues are blocked by requests holding all connections.
+        let shared = requests.for_tasks(None);
+        assert_eq!(labels(&shared), ["read", "write"]);
+        let expiration = TabularExpirationQueue::from_config(shared, config.clone()).unwrap();
+        let request_connection = requests.write_pool.acquire().await.unwrap();
+        expiration.pick_new_task().await.unwrap_err();
+
+        let tasks = requests.for_tasks(Some((pool_with(2), pool_with(2))));
+        assert_eq!(labels(&tasks), ["task-read", "task-write"]);
+        assert_eq!(tasks.read_pool.options().get_max_connections(), 2);
+        assert_eq!(tasks.write_pool.options().get_max_connections(), 2);
+        let expiration =
+            TabularExpirationQueue::from_config(tasks.clone(), config.clone()).unwrap();
+        let purge = TabularPurgeQueue::from_config(tasks.clone(), config).unwrap();
+        assert!(expiration.pick_new_task().await.unwrap().is_none());
+        assert!(purge.pick_new_task().await.unwrap().is_none());
+        drop(request_connection);
+
+        // Tasks use at most the configured number of connections.
+        let task_connections = [
+            tasks.write_pool.acquire().await.unwrap(),
+            tasks.write_pool.acquire().await.unwrap(),
+        ];
+        purge.pick_new_task().await.unwrap_err();
+        drop(task_connections);
+        assert!(purge.pick_new_task().await.unwrap().is_none());
+    }
 }
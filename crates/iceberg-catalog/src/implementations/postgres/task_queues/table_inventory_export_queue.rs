@@ -0,0 +1,326 @@
+use async_trait::async_trait;
+use iceberg_ext::catalog::rest::ErrorModel;
+use uuid::Uuid;
+
+use crate::api::management::v1::table::{InventoryExportStatus, InventoryFormat};
+use crate::implementations::postgres::dbutils::DBErrorHandler;
+use crate::implementations::postgres::task_queues::{
+    pick_task, queue_task, record_failure, record_success,
+};
+use crate::service::task_queue::table_inventory_export_queue::{
+    InventoryExportDetails, InventoryExportQueue, InventoryExportResult, TableInventoryExportInput,
+    TableInventoryExportTask,
+};
+use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
+use crate::service::TableIdentUuid;
+use crate::WarehouseIdent;
+
+use super::{cancel_pending_tasks, TaskFilter};
+
+super::impl_pg_task_queue!(TableInventoryExportQueue);
+
+fn format_from_db(format: &str) -> crate::api::Result<InventoryFormat> {
+    match format {
+        "csv" => Ok(InventoryFormat::Csv),
+        "parquet" => Ok(InventoryFormat::Parquet),
+        other => Err(ErrorModel::internal(
+            format!("Unknown inventory format {other}"),
+            "InvalidInventoryFormat",
+            None,
+        )
+        .into()),
+    }
+}
+
+#[async_trait]
+impl TaskQueue for TableInventoryExportQueue {
+    type Task = TableInventoryExportTask;
+    type Input = TableInventoryExportInput;
+
+    fn config(&self) -> &TaskQueueConfig {
+        &self.pg_queue.config
+    }
+
+    fn queue_name(&self) -> &'static str {
+        "table_inventory_exports"
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn pick_new_task(&self) -> crate::api::Result<Option<Self::Task>> {
+        let task = pick_task(
+            &self.pg_queue.read_write.write_pool,
+            self.queue_name(),
+            &self.pg_queue.max_age,
+        )
+        .await?;
+
+        let Some(task) = task else {
+            tracing::debug!("No task found in {}", self.queue_name());
+            return Ok(None);
+        };
+
+        let export = sqlx::query!(
+            r#"
+            SELECT export_id, table_id, warehouse_id, target_location, format
+            FROM table_inventory_exports
+            WHERE task_id = $1
+            "#,
+            task.task_id
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "error selecting table inventory export");
+            e.into_error_model("failed to read task after picking one up")
+        })?;
+
+        Ok(Some(TableInventoryExportTask {
+            export_id: export.export_id,
+            table_id: export.table_id.into(),
+            warehouse_ident: export.warehouse_id.into(),
+            target_location: export.target_location,
+            format: format_from_db(&export.format)?,
+            task,
+        }))
+    }
+
+    async fn record_success(&self, id: Uuid) -> crate::api::Result<()> {
+        record_success(id, &self.pg_queue.read_write.write_pool).await
+    }
+
+    async fn record_failure(&self, id: Uuid, error_details: &str) -> crate::api::Result<()> {
+        record_failure(
+            &self.pg_queue.read_write.write_pool,
+            id,
+            self.config().max_retries,
+            error_details,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn enqueue(
+        &self,
+        TableInventoryExportInput {
+            export_id,
+            table_id,
+            warehouse_ident,
+            target_location,
+            format,
+        }: TableInventoryExportInput,
+    ) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| e.into_error_model("failed begin transaction to inventory export task"))?;
+
+        tracing::info!(
+            "Queuing inventory export '{export_id}' of table '{table_id}' to '{target_location}'"
+        );
+
+        let Some(task_id) = queue_task(
+            &mut transaction,
+            self.queue_name(),
+            None,
+            export_id,
+            warehouse_ident,
+            None,
+        )
+        .await?
+        else {
+            tracing::debug!("Task already exists");
+            transaction.commit().await.map_err(|e| {
+                tracing::error!(?e, "failed to commit");
+                e.into_error_model("failed commiting transaction")
+            })?;
+            return Ok(());
+        };
+
+        sqlx::query!(
+            r#"INSERT INTO table_inventory_exports(export_id, task_id, warehouse_id, table_id, target_location, format)
+               VALUES ($1, $2, $3, $4, $5, $6)"#,
+            export_id,
+            task_id,
+            *warehouse_ident,
+            *table_id,
+            &target_location,
+            format.extension()
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to insert into table_inventory_exports");
+            e.into_error_model("failed to insert into table inventory exports")
+        })?;
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit table inventory export task")
+        })?;
+
+        Ok(())
+    }
+
+    async fn cancel_pending_tasks(&self, filter: TaskFilter) -> crate::api::Result<()> {
+        cancel_pending_tasks(&self.pg_queue, filter, self.queue_name()).await
+    }
+}
+
+#[async_trait]
+impl InventoryExportQueue for TableInventoryExportQueue {
+    async fn record_result(
+        &self,
+        id: Uuid,
+        result: &InventoryExportResult,
+    ) -> crate::api::Result<()> {
+        sqlx::query!(
+            r#"
+            UPDATE table_inventory_exports
+            SET data_file_count = $2, inventory_files = $3
+            WHERE task_id = $1
+            "#,
+            id,
+            result.data_file_count,
+            &result.inventory_files
+        )
+        .execute(&self.pg_queue.read_write.write_pool)
+        .await
+        .map_err(|e| e.into_error_model("failed to record inventory export result"))?;
+        Ok(())
+    }
+
+    async fn get_export(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        table_id: TableIdentUuid,
+        export_id: Uuid,
+    ) -> crate::api::Result<Option<InventoryExportDetails>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT e.target_location, e.format, t.status::text as "status!", t.attempt,
+                t.last_error_details, e.data_file_count, e.inventory_files, e.created_at
+            FROM table_inventory_exports e
+            JOIN task t ON t.task_id = e.task_id
+            WHERE e.export_id = $1 AND e.warehouse_id = $2 AND e.table_id = $3
+            "#,
+            export_id,
+            *warehouse_ident,
+            *table_id
+        )
+        .fetch_optional(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| e.into_error_model("failed to get inventory export"))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let status = match row.status.as_str() {
+            "pending" => InventoryExportStatus::Pending,
+            "running" => InventoryExportStatus::Running,
+            "done" => InventoryExportStatus::Done,
+            "failed" => InventoryExportStatus::Failed,
+            "cancelled" => InventoryExportStatus::Cancelled,
+            other => {
+                return Err(ErrorModel::internal(
+                    format!("Unknown task status {other}"),
+                    "InvalidTaskStatus",
+                    None,
+                )
+                .into())
+            }
+        };
+        let result = row.data_file_count.zip(row.inventory_files).map(
+            |(data_file_count, inventory_files)| InventoryExportResult {
+                data_file_count,
+                inventory_files,
+            },
+        );
+
+        Ok(Some(InventoryExportDetails {
+            export_id,
+            table_id,
+            target_location: row.target_location,
+            format: format_from_db(&row.format)?,
+            status,
+            attempt: row.attempt,
+            last_error: row.last_error_details,
+            result,
+            created_at: row.created_at,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::setup;
+    use super::*;
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn test_queue_inventory_export_task(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::TableInventoryExportQueue { pg_queue };
+        let input = TableInventoryExportInput {
+            export_id: Uuid::now_v7(),
+            table_id: Uuid::new_v4().into(),
+            warehouse_ident: Uuid::new_v4().into(),
+            target_location: "s3://bucket/inventories/table".to_string(),
+            format: InventoryFormat::Csv,
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        queue.enqueue(input.clone()).await.unwrap();
+
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("There should be a task");
+        assert_eq!(task.export_id, input.export_id);
+        assert_eq!(task.table_id, input.table_id);
+        assert_eq!(task.format, InventoryFormat::Csv);
+        assert!(
+            queue.pick_new_task().await.unwrap().is_none(),
+            "There should only be one task, idempotency didn't work."
+        );
+
+        let export = queue
+            .get_export(input.warehouse_ident, input.table_id, input.export_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(export.status, InventoryExportStatus::Running);
+        assert!(export.result.is_none());
+
+        let result = InventoryExportResult {
+            data_file_count: 3,
+            inventory_files: vec!["s3://bucket/inventories/table/inventory-00000.csv".to_string()],
+        };
+        queue
+            .record_result(task.task.task_id, &result)
+            .await
+            .unwrap();
+        queue.record_success(task.task.task_id).await.unwrap();
+        let export = queue
+            .get_export(input.warehouse_ident, input.table_id, input.export_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(export.status, InventoryExportStatus::Done);
+        assert_eq!(export.result, Some(result));
+
+        assert!(queue
+            .get_export(
+                input.warehouse_ident,
+                Uuid::new_v4().into(),
+                input.export_id
+            )
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,229 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::implementations::postgres::dbutils::DBErrorHandler;
+use crate::implementations::postgres::task_queues::{
+    pick_task, queue_task, record_failure, record_success,
+};
+use crate::service::task_queue::tabular_compaction_queue::{
+    TabularCompactionInput, TabularCompactionTask,
+};
+use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
+
+use super::{cancel_pending_tasks, TaskFilter};
+
+super::impl_pg_task_queue!(TabularCompactionQueue);
+
+#[async_trait]
+impl TaskQueue for TabularCompactionQueue {
+    type Task = TabularCompactionTask;
+    type Input = TabularCompactionInput;
+
+    fn config(&self) -> &TaskQueueConfig {
+        &self.pg_queue.config
+    }
+
+    fn queue_name(&self) -> &'static str {
+        "tabular_compactions"
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn pick_new_task(&self) -> crate::api::Result<Option<Self::Task>> {
+        let task = pick_task(
+            &self.pg_queue.read_write.write_pool,
+            self.queue_name(),
+            &self.pg_queue.max_age,
+        )
+        .await?;
+
+        let Some(task) = task else {
+            tracing::debug!("No task found in {}", self.queue_name());
+            return Ok(None);
+        };
+
+        let compaction = sqlx::query!(
+            r#"
+            SELECT tabular_id, warehouse_id, snapshot_id, reason
+            FROM tabular_compactions
+            WHERE task_id = $1
+            "#,
+            task.task_id
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "error selecting tabular compaction");
+            e.into_error_model("failed to read task after picking one up")
+        })?;
+
+        Ok(Some(TabularCompactionTask {
+            table_id: compaction.tabular_id.into(),
+            warehouse_ident: compaction.warehouse_id.into(),
+            snapshot_id: compaction.snapshot_id,
+            reason: compaction.reason,
+            task,
+        }))
+    }
+
+    async fn record_success(&self, id: Uuid) -> crate::api::Result<()> {
+        record_success(id, &self.pg_queue.read_write.write_pool).await
+    }
+
+    async fn record_failure(&self, id: Uuid, error_details: &str) -> crate::api::Result<()> {
+        record_failure(
+            &self.pg_queue.read_write.write_pool,
+            id,
+            self.config().max_retries,
+            error_details,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn enqueue(
+        &self,
+        TabularCompactionInput {
+            table_id,
+            warehouse_ident,
+            snapshot_id,
+            reason,
+        }: TabularCompactionInput,
+    ) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| e.into_error_model("failed begin transaction to compaction task"))?;
+
+        // One key per table: commits arriving while a compaction is pending or running
+        // conflict with the existing task instead of queuing another one. The key of a
+        // finished compaction is released, so that the next one can be queued right away.
+        let idempotency_key = Uuid::new_v5(&table_id, b"compaction");
+        sqlx::query!(
+            r#"UPDATE task SET idempotency_key = task_id
+            WHERE queue_name = $1
+                AND idempotency_key = $2
+                AND status IN ('done', 'failed')"#,
+            self.queue_name(),
+            idempotency_key,
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to release compaction idempotency key");
+            e.into_error_model("failed to release compaction idempotency key")
+        })?;
+
+        let Some(task_id) = queue_task(
+            &mut transaction,
+            self.queue_name(),
+            None,
+            idempotency_key,
+            warehouse_ident,
+            None,
+        )
+        .await?
+        else {
+            tracing::debug!("Compaction for table '{table_id}' already queued");
+            transaction.commit().await.map_err(|e| {
+                tracing::error!(?e, "failed to commit");
+                e.into_error_model("failed commiting transaction")
+            })?;
+            return Ok(());
+        };
+
+        tracing::info!("Queuing compaction for table '{table_id}': {reason}");
+
+        sqlx::query!(
+            r#"INSERT INTO tabular_compactions(task_id, tabular_id, warehouse_id, snapshot_id, reason)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (task_id) DO UPDATE SET snapshot_id = $4, reason = $5"#,
+            task_id,
+            *table_id,
+            *warehouse_ident,
+            snapshot_id,
+            reason,
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to insert into tabular_compactions");
+            e.into_error_model("failed to insert into tabular compactions")
+        })?;
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit tabular compaction task")
+        })?;
+
+        Ok(())
+    }
+
+    async fn cancel_pending_tasks(&self, filter: TaskFilter) -> crate::api::Result<()> {
+        cancel_pending_tasks(&self.pg_queue, filter, self.queue_name()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::setup;
+    use crate::service::task_queue::tabular_compaction_queue::TabularCompactionInput;
+    use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn test_queue_compaction_queue_task(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::TabularCompactionQueue { pg_queue };
+        let input = TabularCompactionInput {
+            table_id: uuid::Uuid::new_v4().into(),
+            warehouse_ident: uuid::Uuid::new_v4().into(),
+            snapshot_id: 1,
+            reason: "too many small files".to_string(),
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        // A newer snapshot must not queue a second compaction while the first is pending
+        queue
+            .enqueue(TabularCompactionInput {
+                snapshot_id: 2,
+                ..input.clone()
+            })
+            .await
+            .unwrap();
+
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("There should be a task");
+
+        assert_eq!(task.warehouse_ident, input.warehouse_ident);
+        assert_eq!(task.table_id, input.table_id);
+        assert_eq!(task.snapshot_id, input.snapshot_id);
+        assert_eq!(task.reason, input.reason);
+
+        let next = queue.pick_new_task().await.unwrap();
+        assert!(
+            next.is_none(),
+            "There should only be one task while a compaction is in progress."
+        );
+
+        queue.record_success(task.task.task_id).await.unwrap();
+        queue
+            .enqueue(TabularCompactionInput {
+                snapshot_id: 2,
+                ..input.clone()
+            })
+            .await
+            .unwrap();
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("A new compaction should be queued after the previous one finished");
+        assert_eq!(task.snapshot_id, 2);
+    }
+}
@@ -1,14 +1,20 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
+use crate::api::management::v1::task::PurgeTaskStatus;
 use crate::api::management::v1::TabularType;
 use crate::implementations::postgres::dbutils::DBErrorHandler;
 use crate::implementations::postgres::tabular::TabularType as DbTabularType;
 use crate::implementations::postgres::task_queues::{
     pick_task, queue_task, record_failure, record_success,
 };
-use crate::service::task_queue::tabular_purge_queue::{TabularPurgeInput, TabularPurgeTask};
+use crate::service::task_queue::tabular_purge_queue::{
+    PurgeAwaitingApproval, PurgeProgress, PurgeQueue, PurgeTaskDetails, TabularPurgeInput,
+    TabularPurgeTask,
+};
 use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
 
 use super::{cancel_pending_tasks, TaskFilter};
 
@@ -43,10 +49,17 @@ impl TaskQueue for TabularPurgeQueue {
 
         let purge = sqlx::query!(
             r#"
-            SELECT tabular_id, tabular_location, warehouse_id, typ as "tabular_type: DbTabularType"
-            FROM tabular_purges
-            WHERE task_id = $1
-            "#,
+                SELECT
+                    tabular_id,
+                    tabular_location,
+                    warehouse_id,
+                    typ as "tabular_type: DbTabularType",
+                    approved_at IS NOT NULL as "approved!",
+                    deleted_count,
+                    checkpoint
+                FROM tabular_purges
+                WHERE task_id = $1
+                "#,
             task.task_id
         )
         .fetch_one(&self.pg_queue.read_write.read_pool)
@@ -61,6 +74,11 @@ impl TaskQueue for TabularPurgeQueue {
             tabular_location: purge.tabular_location,
             warehouse_ident: purge.warehouse_id.into(),
             tabular_type: purge.tabular_type.into(),
+            approved: purge.approved,
+            progress: PurgeProgress {
+                deleted_count: purge.deleted_count,
+                checkpoint: purge.checkpoint,
+            },
             task,
         }))
     }
@@ -123,6 +141,22 @@ impl TaskQueue for TabularPurgeQueue {
             return Ok(());
         };
 
+        // A cancelled purge that is queued again has to be approved again and starts
+        // from scratch, as its location may have changed.
+        sqlx::query!(
+            r#"UPDATE tabular_purges
+               SET object_count = NULL, approval_requested_at = NULL, approved_at = NULL,
+                   deleted_count = 0, checkpoint = NULL, progress_updated_at = NULL
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to reset approval of tabular purge");
+            e.into_error_model("failed to reset approval of tabular purge")
+        })?;
+
         let it = sqlx::query!(
             r#"INSERT INTO tabular_purges(task_id, tabular_id, warehouse_id, typ, tabular_location)
                VALUES ($1, $2, $3, $4, $5)
@@ -164,10 +198,240 @@ impl TaskQueue for TabularPurgeQueue {
     }
 }
 
+#[async_trait]
+impl PurgeQueue for TabularPurgeQueue {
+    async fn request_approval(&self, id: Uuid, object_count: i64) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| {
+                e.into_error_model("failed to begin transaction to request purge approval")
+            })?;
+
+        sqlx::query!(
+            r#"UPDATE tabular_purges
+               SET object_count = $2, approval_requested_at = now(), approved_at = NULL
+               WHERE task_id = $1"#,
+            id,
+            object_count
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| e.into_error_model("failed to request purge approval"))?;
+
+        // Suspended tasks are not picked up until `approve` clears `suspend_until`.
+        sqlx::query!(
+            r#"UPDATE task
+               SET status = 'pending', suspend_until = 'infinity', last_error_details = $2
+               WHERE task_id = $1"#,
+            id,
+            format!("Waiting for approval: location contains at least {object_count} objects")
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| e.into_error_model("failed to suspend purge task"))?;
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit purge approval request")
+        })?;
+        Ok(())
+    }
+
+    async fn approve(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        task_id: Uuid,
+    ) -> crate::api::Result<bool> {
+        let approved = sqlx::query_scalar!(
+            r#"
+            WITH approved AS (
+                UPDATE tabular_purges p
+                SET approved_at = now()
+                FROM task t
+                WHERE p.task_id = $1
+                    AND t.task_id = p.task_id
+                    AND p.warehouse_id = $2
+                    AND t.status = 'pending'
+                    AND p.approval_requested_at IS NOT NULL
+                    AND p.approved_at IS NULL
+                RETURNING p.task_id
+            )
+            UPDATE task
+            SET suspend_until = NULL, attempt = 0, last_error_details = NULL
+            WHERE task_id IN (SELECT task_id FROM approved)
+            RETURNING task_id
+            "#,
+            task_id,
+            *warehouse_ident
+        )
+        .fetch_optional(&self.pg_queue.read_write.write_pool)
+        .await
+        .map_err(|e| e.into_error_model("failed to approve purge"))?;
+        Ok(approved.is_some())
+    }
+
+    async fn list_awaiting_approval(
+        &self,
+        warehouse_ident: WarehouseIdent,
+    ) -> crate::api::Result<Vec<PurgeAwaitingApproval>> {
+        let rows = sqlx::query!(
+            r#"
+            SELECT
+                p.task_id,
+                p.tabular_id,
+                p.typ as "typ: DbTabularType",
+                p.tabular_location,
+                p.object_count as "object_count!",
+                p.approval_requested_at as "approval_requested_at!"
+            FROM tabular_purges p
+            JOIN task t ON t.task_id = p.task_id
+            WHERE p.warehouse_id = $1
+                AND t.status = 'pending'
+                AND p.approval_requested_at IS NOT NULL
+                AND p.approved_at IS NULL
+            ORDER BY p.approval_requested_at
+            "#,
+            *warehouse_ident
+        )
+        .fetch_all(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| e.into_error_model("failed to list purges awaiting approval"))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PurgeAwaitingApproval {
+                task_id: row.task_id,
+                tabular_id: row.tabular_id,
+                tabular_type: row.typ.into(),
+                tabular_location: row.tabular_location,
+                object_count: row.object_count,
+                approval_requested_at: row.approval_requested_at,
+            })
+            .collect())
+    }
+
+    async fn record_progress(&self, id: Uuid, progress: &PurgeProgress) -> crate::api::Result<()> {
+        // Refreshing `picked_up_at` keeps other workers from taking over a purge
+        // that is still making progress.
+        sqlx::query!(
+            r#"
+            WITH progress AS (
+                UPDATE tabular_purges
+                SET deleted_count = $2, checkpoint = $3, progress_updated_at = now()
+                WHERE task_id = $1
+                RETURNING task_id
+            )
+            UPDATE task
+            SET picked_up_at = now()
+            WHERE task_id IN (SELECT task_id FROM progress) AND status = 'running'
+            "#,
+            id,
+            progress.deleted_count,
+            progress.checkpoint.as_deref()
+        )
+        .execute(&self.pg_queue.read_write.write_pool)
+        .await
+        .map_err(|e| e.into_error_model("failed to record purge progress"))?;
+        Ok(())
+    }
+
+    async fn has_unfinished(&self, warehouse_ident: WarehouseIdent) -> crate::api::Result<bool> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM tabular_purges p
+                JOIN task t ON t.task_id = p.task_id
+                WHERE p.warehouse_id = $1
+                    AND t.status IN ('pending', 'running')
+            ) as "exists!"
+            "#,
+            *warehouse_ident
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            e.into_error_model("failed to check for unfinished purges")
+                .into()
+        })
+    }
+
+    async fn get_purge(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        task_id: Uuid,
+    ) -> crate::api::Result<Option<PurgeTaskDetails>> {
+        let row = sqlx::query!(
+            r#"
+            SELECT
+                p.tabular_id,
+                p.typ as "tabular_type: DbTabularType",
+                p.tabular_location,
+                t.status::text as "status!",
+                p.approval_requested_at IS NOT NULL AND p.approved_at IS NULL as "awaiting_approval!",
+                t.attempt,
+                p.deleted_count,
+                p.checkpoint,
+                p.progress_updated_at
+            FROM tabular_purges p
+            JOIN task t ON t.task_id = p.task_id
+            WHERE p.task_id = $1 AND p.warehouse_id = $2
+            "#,
+            task_id,
+            *warehouse_ident
+        )
+        .fetch_optional(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| e.into_error_model("failed to get purge"))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let status = match row.status.as_str() {
+            "pending" if row.awaiting_approval => PurgeTaskStatus::AwaitingApproval,
+            "pending" => PurgeTaskStatus::Pending,
+            "running" => PurgeTaskStatus::Running,
+            "done" => PurgeTaskStatus::Done,
+            "failed" => PurgeTaskStatus::Failed,
+            "cancelled" => PurgeTaskStatus::Cancelled,
+            other => {
+                return Err(ErrorModel::internal(
+                    format!("Unknown task status {other}"),
+                    "InvalidTaskStatus",
+                    None,
+                )
+                .into())
+            }
+        };
+
+        Ok(Some(PurgeTaskDetails {
+            task_id,
+            tabular_id: row.tabular_id,
+            tabular_type: row.tabular_type.into(),
+            tabular_location: row.tabular_location,
+            status,
+            attempt: row.attempt,
+            progress: PurgeProgress {
+                deleted_count: row.deleted_count,
+                checkpoint: row.checkpoint,
+            },
+            progress_updated_at: row.progress_updated_at,
+        }))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::super::test::setup;
-    use crate::service::task_queue::tabular_purge_queue::TabularPurgeInput;
+    use crate::api::management::v1::task::PurgeTaskStatus;
+    use crate::service::task_queue::tabular_purge_queue::{
+        PurgeProgress, PurgeQueue, TabularPurgeInput,
+    };
     use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
     use sqlx::PgPool;
 
@@ -203,4 +467,108 @@ mod test {
             "There should only be one task, idempotency didn't work."
         );
     }
+
+    #[sqlx::test]
+    async fn test_purge_waits_for_approval(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::TabularPurgeQueue { pg_queue };
+        let input = TabularPurgeInput {
+            tabular_id: uuid::Uuid::new_v4(),
+            warehouse_ident: uuid::Uuid::new_v4().into(),
+            tabular_type: crate::api::management::v1::TabularType::Table,
+            parent_id: None,
+            tabular_location: "s3://bucket/shared-prefix".to_string(),
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        let task = queue.pick_new_task().await.unwrap().unwrap();
+        assert!(!task.approved);
+
+        queue
+            .request_approval(task.task.task_id, 1001)
+            .await
+            .unwrap();
+        assert!(
+            queue.pick_new_task().await.unwrap().is_none(),
+            "Purges waiting for approval must not be picked up"
+        );
+        let awaiting = queue
+            .list_awaiting_approval(input.warehouse_ident)
+            .await
+            .unwrap();
+        assert_eq!(awaiting.len(), 1);
+        assert_eq!(awaiting[0].task_id, task.task.task_id);
+        assert_eq!(awaiting[0].object_count, 1001);
+        assert_eq!(awaiting[0].tabular_location, input.tabular_location);
+
+        let other_warehouse = uuid::Uuid::new_v4().into();
+        assert!(!queue
+            .approve(other_warehouse, task.task.task_id)
+            .await
+            .unwrap());
+        assert!(queue
+            .approve(input.warehouse_ident, task.task.task_id)
+            .await
+            .unwrap());
+        assert!(!queue
+            .approve(input.warehouse_ident, task.task.task_id)
+            .await
+            .unwrap());
+        assert!(queue
+            .list_awaiting_approval(input.warehouse_ident)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let task = queue.pick_new_task().await.unwrap().unwrap();
+        assert!(task.approved);
+        assert_eq!(task.task.attempt, 1);
+    }
+
+    #[sqlx::test]
+    async fn test_purge_resumes_from_progress(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::TabularPurgeQueue { pg_queue };
+        let input = TabularPurgeInput {
+            tabular_id: uuid::Uuid::new_v4(),
+            warehouse_ident: uuid::Uuid::new_v4().into(),
+            tabular_type: crate::api::management::v1::TabularType::Table,
+            parent_id: None,
+            tabular_location: "s3://bucket/table".to_string(),
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        let task = queue.pick_new_task().await.unwrap().unwrap();
+        assert_eq!(task.progress, PurgeProgress::default());
+
+        let progress = PurgeProgress {
+            deleted_count: 1000,
+            checkpoint: Some("s3://bucket/table/data/00999.parquet".to_string()),
+        };
+        queue
+            .record_progress(task.task.task_id, &progress)
+            .await
+            .unwrap();
+        let details = queue
+            .get_purge(input.warehouse_ident, task.task.task_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(details.status, PurgeTaskStatus::Running);
+        assert_eq!(details.progress, progress);
+        assert!(details.progress_updated_at.is_some());
+        assert!(queue
+            .get_purge(uuid::Uuid::new_v4().into(), task.task.task_id)
+            .await
+            .unwrap()
+            .is_none());
+
+        queue
+            .record_failure(task.task.task_id, "worker crashed")
+            .await
+            .unwrap();
+        let task = queue.pick_new_task().await.unwrap().unwrap();
+        assert_eq!(task.task.attempt, 2);
+        assert_eq!(task.progress, progress);
+    }
 }
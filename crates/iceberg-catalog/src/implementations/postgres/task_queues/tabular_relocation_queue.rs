@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::implementations::postgres::dbutils::DBErrorHandler;
+use crate::implementations::postgres::task_queues::{
+    pick_task, queue_task, record_failure, record_success,
+};
+use crate::service::task_queue::tabular_relocation_queue::{
+    TabularRelocationInput, TabularRelocationTask,
+};
+use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
+
+use super::{cancel_pending_tasks, TaskFilter};
+
+super::impl_pg_task_queue!(TabularRelocationQueue);
+
+#[async_trait]
+impl TaskQueue for TabularRelocationQueue {
+    type Task = TabularRelocationTask;
+    type Input = TabularRelocationInput;
+
+    fn config(&self) -> &TaskQueueConfig {
+        &self.pg_queue.config
+    }
+
+    fn queue_name(&self) -> &'static str {
+        "tabular_relocations"
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn pick_new_task(&self) -> crate::api::Result<Option<Self::Task>> {
+        let task = pick_task(
+            &self.pg_queue.read_write.write_pool,
+            self.queue_name(),
+            &self.pg_queue.max_age,
+        )
+        .await?;
+
+        let Some(task) = task else {
+            tracing::debug!("No task found in {}", self.queue_name());
+            return Ok(None);
+        };
+
+        let relocation = sqlx::query!(
+            r#"
+            SELECT tabular_id, warehouse_id, source_location, target_location
+            FROM tabular_relocations
+            WHERE task_id = $1
+            "#,
+            task.task_id
+        )
+        .fetch_one(&self.pg_queue.read_write.read_pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "error selecting tabular relocation");
+            e.into_error_model("failed to read task after picking one up")
+        })?;
+
+        Ok(Some(TabularRelocationTask {
+            table_id: relocation.tabular_id.into(),
+            warehouse_ident: relocation.warehouse_id.into(),
+            source_location: relocation.source_location,
+            target_location: relocation.target_location,
+            task,
+        }))
+    }
+
+    async fn record_success(&self, id: Uuid) -> crate::api::Result<()> {
+        record_success(id, &self.pg_queue.read_write.write_pool).await
+    }
+
+    async fn record_failure(&self, id: Uuid, error_details: &str) -> crate::api::Result<()> {
+        record_failure(
+            &self.pg_queue.read_write.write_pool,
+            id,
+            self.config().max_retries,
+            error_details,
+        )
+        .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn enqueue(
+        &self,
+        TabularRelocationInput {
+            table_id,
+            warehouse_ident,
+            source_location,
+            target_location,
+        }: TabularRelocationInput,
+    ) -> crate::api::Result<()> {
+        let mut transaction = self
+            .pg_queue
+            .read_write
+            .write_pool
+            .begin()
+            .await
+            .map_err(|e| e.into_error_model("failed begin transaction to relocation task"))?;
+
+        tracing::info!(
+            "Queuing relocation for table '{table_id}' from '{source_location}' to '{target_location}'"
+        );
+
+        let idempotency_key = Uuid::new_v5(&table_id, target_location.as_bytes());
+
+        let Some(task_id) = queue_task(
+            &mut transaction,
+            self.queue_name(),
+            None,
+            idempotency_key,
+            warehouse_ident,
+            None,
+        )
+        .await?
+        else {
+            tracing::debug!("Task already exists");
+            transaction.commit().await.map_err(|e| {
+                tracing::error!(?e, "failed to commit");
+                e.into_error_model("failed commiting transaction")
+            })?;
+            return Ok(());
+        };
+
+        sqlx::query!(
+            r#"INSERT INTO tabular_relocations(task_id, tabular_id, warehouse_id, source_location, target_location)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (task_id) DO UPDATE SET source_location = $4"#,
+            task_id,
+            *table_id,
+            *warehouse_ident,
+            source_location,
+            target_location,
+        )
+        .execute(&mut *transaction)
+        .await
+        .map_err(|e| {
+            tracing::error!(?e, "failed to insert into tabular_relocations");
+            e.into_error_model("failed to insert into tabular relocations")
+        })?;
+
+        transaction.commit().await.map_err(|e| {
+            tracing::error!(?e, "failed to commit");
+            e.into_error_model("failed to commit tabular relocation task")
+        })?;
+
+        Ok(())
+    }
+
+    async fn cancel_pending_tasks(&self, filter: TaskFilter) -> crate::api::Result<()> {
+        cancel_pending_tasks(&self.pg_queue, filter, self.queue_name()).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::setup;
+    use crate::service::task_queue::tabular_relocation_queue::TabularRelocationInput;
+    use crate::service::task_queue::{TaskQueue, TaskQueueConfig};
+    use sqlx::PgPool;
+
+    #[sqlx::test]
+    async fn test_queue_relocation_queue_task(pool: PgPool) {
+        let config = TaskQueueConfig::default();
+        let pg_queue = setup(pool, config);
+        let queue = super::TabularRelocationQueue { pg_queue };
+        let input = TabularRelocationInput {
+            table_id: uuid::Uuid::new_v4().into(),
+            warehouse_ident: uuid::Uuid::new_v4().into(),
+            source_location: "s3://old-bucket/table".to_string(),
+            target_location: "s3://new-bucket/table".to_string(),
+        };
+        queue.enqueue(input.clone()).await.unwrap();
+        queue.enqueue(input.clone()).await.unwrap();
+
+        let task = queue
+            .pick_new_task()
+            .await
+            .unwrap()
+            .expect("There should be a task");
+
+        assert_eq!(task.warehouse_ident, input.warehouse_ident);
+        assert_eq!(task.table_id, input.table_id);
+        assert_eq!(task.source_location, input.source_location);
+        assert_eq!(task.target_location, input.target_location);
+
+        let task = queue.pick_new_task().await.unwrap();
+        assert!(
+            task.is_none(),
+            "There should only be one task, idempotency didn't work."
+        );
+    }
+}
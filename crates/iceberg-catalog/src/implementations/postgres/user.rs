@@ -1,11 +1,19 @@
 use super::dbutils::DBErrorHandler;
 use crate::api::iceberg::v1::{PaginationQuery, MAX_PAGE_SIZE};
 use crate::api::management::v1::user::{
-    ListUsersResponse, SearchUser, SearchUserResponse, User, UserLastUpdatedWith, UserType,
+    ListUsersResponse, SearchUser, SearchUserResponse, User, UserLastUpdatedWith, UserListFilter,
+    UserSortBy, UserType,
+};
+use crate::config::StaleUserAction;
+use crate::implementations::postgres::pagination::{
+    keyset_direction, PaginateToken, V1PaginateToken,
 };
-use crate::implementations::postgres::pagination::{PaginateToken, V1PaginateToken};
 use crate::service::authn::UserId;
-use crate::service::{CreateOrUpdateUserResponse, Result};
+use crate::service::{
+    CreateOrUpdateUserResponse, PrincipalDefaultWarehouse, Result, StaleUser, UserActivity,
+};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
 use itertools::Itertools;
 
 #[derive(sqlx::Type, Debug, Clone, Copy)]
@@ -50,6 +58,9 @@ struct UserRow {
     user_type: DbUserType,
     created_at: chrono::DateTime<chrono::Utc>,
     updated_at: Option<chrono::DateTime<chrono::Utc>>,
+    last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
+    flagged_stale_at: Option<chrono::DateTime<chrono::Utc>>,
+    deactivated_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl TryFrom<UserRow> for User {
@@ -64,6 +75,9 @@ impl TryFrom<UserRow> for User {
             user_type,
             created_at,
             updated_at,
+            last_seen_at,
+            flagged_stale_at,
+            deactivated_at,
         }: UserRow,
     ) -> Result<Self> {
         Ok(User {
@@ -80,6 +94,9 @@ impl TryFrom<UserRow> for User {
             },
             created_at,
             updated_at,
+            last_seen_at,
+            flagged_stale_at,
+            deactivated_at,
         })
     }
 }
@@ -87,6 +104,15 @@ impl TryFrom<UserRow> for User {
 pub(crate) async fn list_users<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
     filter_user_id: Option<Vec<UserId>>,
     filter_name: Option<String>,
+    UserListFilter {
+        created_after,
+        created_before,
+        last_seen_after,
+        last_seen_before,
+        idp,
+        sort_by,
+        sort_order,
+    }: UserListFilter,
     PaginationQuery {
         page_token,
         page_size,
@@ -94,56 +120,89 @@ pub(crate) async fn list_users<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
     connection: E,
 ) -> Result<ListUsersResponse> {
     let page_size = page_size.map_or(MAX_PAGE_SIZE, |i| i.clamp(1, MAX_PAGE_SIZE));
-    let filter_name = filter_name.unwrap_or_default();
 
     let token = page_token
         .as_option()
-        .map(PaginateToken::try_from)
+        .map(PaginateToken::<String>::try_from)
         .transpose()?;
 
-    let (token_ts, token_id): (_, Option<&String>) = token
-        .as_ref()
-        .map(|PaginateToken::V1(V1PaginateToken { created_at, id })| (created_at, id))
-        .unzip();
-
-    let users: Vec<User> = sqlx::query_as!(
-        UserRow,
+    let mut query = sqlx::QueryBuilder::new(
         r#"
-        SELECT
-            id,
-            name,
-            last_updated_with as "last_updated_with: DbUserLastUpdatedWith",
-            user_type as "user_type: DbUserType",
-            email,
-            created_at,
-            updated_at
+        SELECT id, name, last_updated_with, user_type, email, created_at, updated_at,
+            last_seen_at, flagged_stale_at, deactivated_at
         FROM users u
-        where (deleted_at is null)
-            AND ($1 OR name ILIKE ('%' || $2 || '%'))
-            AND ($3 OR id = any($4))
-            --- PAGINATION
-            AND ((u.created_at > $5 OR $5 IS NULL) OR (u.created_at = $5 AND u.id > $6))
-        ORDER BY u.created_at, u.id ASC
-        LIMIT $7
-        "#,
-        filter_name.is_empty(),
-        filter_name.to_string(),
-        filter_user_id.is_none(),
-        filter_user_id
-            .unwrap_or_default()
-            .into_iter()
-            .map_into()
-            .collect::<Vec<String>>() as Vec<String>,
-        token_ts,
-        token_id,
-        page_size,
-    )
-    .fetch_all(connection)
-    .await
-    .map_err(|e| e.into_error_model("Error fetching users".to_string()))?
-    .into_iter()
-    .map(User::try_from)
-    .collect::<Result<_>>()?;
+        WHERE deleted_at is null"#,
+    );
+    if let Some(name) = filter_name.filter(|n| !n.is_empty()) {
+        query
+            .push(" AND name ILIKE ('%' || ")
+            .push_bind(name)
+            .push(" || '%')");
+    }
+    if let Some(user_ids) = filter_user_id {
+        query
+            .push(" AND id = any(")
+            .push_bind(user_ids.into_iter().map_into().collect::<Vec<String>>())
+            .push(")");
+    }
+    if let Some(created_after) = created_after {
+        query.push(" AND created_at >= ").push_bind(created_after);
+    }
+    if let Some(created_before) = created_before {
+        query.push(" AND created_at < ").push_bind(created_before);
+    }
+    if let Some(last_seen_after) = last_seen_after {
+        query
+            .push(" AND last_seen_at >= ")
+            .push_bind(last_seen_after);
+    }
+    if let Some(last_seen_before) = last_seen_before {
+        query
+            .push(" AND (last_seen_at IS NULL OR last_seen_at < ")
+            .push_bind(last_seen_before)
+            .push(")");
+    }
+    if let Some(idp) = idp {
+        query
+            .push(" AND starts_with(id, ")
+            .push_bind(idp.user_id_prefix())
+            .push(")");
+    }
+
+    // The sort key of the token's row is looked up, so that tokens stay small
+    // and look the same for every sort order.
+    let sort_key = match sort_by {
+        UserSortBy::CreatedAt => "created_at",
+        UserSortBy::Name => "lower(name)",
+        UserSortBy::LastSeen => "COALESCE(last_seen_at, '-infinity')",
+    };
+    let (op, direction) = keyset_direction(sort_order);
+    if let Some(PaginateToken::V1(V1PaginateToken { created_at, id })) = token {
+        query.push(format!(" AND ({sort_key}, u.id) {op} ("));
+        if sort_by == UserSortBy::CreatedAt {
+            query.push_bind(created_at);
+        } else {
+            query
+                .push(format!("(SELECT {sort_key} FROM users WHERE id = "))
+                .push_bind(id.clone())
+                .push(")");
+        }
+        query.push(", ").push_bind(id).push(")");
+    }
+    query
+        .push(format!(
+            " ORDER BY {sort_key} {direction}, u.id {direction} LIMIT "
+        ))
+        .push_bind(page_size);
+
+    let users: Vec<User> = query
+        .build_query_as::<UserRow>()
+        .fetch_all(connection)
+        .await
+        .map_err(|e| e.into_error_model("Error fetching users".to_string()))?
+        .into_iter()
+        .map(User::try_from)
+        .collect::<Result<_>>()?;
 
     let next_page_token = users.last().map(|u| {
         PaginateToken::V1(V1PaginateToken {
@@ -159,6 +218,115 @@ pub(crate) async fn list_users<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sqlx
     })
 }
 
+/// Marks the user as seen and lifts a previous stale flag.
+/// Deactivated users are not marked, so that rejected requests do not count as activity.
+pub(crate) async fn update_user_last_seen<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    id: &UserId,
+    connection: E,
+) -> Result<UserActivity> {
+    let deactivated_at = sqlx::query_scalar!(
+        r#"
+        WITH seen AS (
+            UPDATE users
+            SET last_seen_at = now(), flagged_stale_at = null
+            WHERE id = $1 AND deleted_at is null AND deactivated_at is null
+        )
+        SELECT deactivated_at FROM users WHERE id = $1 AND deleted_at is null
+        "#,
+        id.to_string()
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error updating last seen time of user".to_string()))?;
+
+    Ok(match deactivated_at {
+        None => UserActivity::Unknown,
+        Some(None) => UserActivity::Active,
+        Some(Some(_)) => UserActivity::Deactivated,
+    })
+}
+
+/// Flags or deactivates users that were not seen within `stale_after`.
+/// Users that were never seen are judged by their creation time.
+/// Returns the users that changed.
+pub(crate) async fn apply_stale_user_policy<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    stale_after: chrono::Duration,
+    action: StaleUserAction,
+    exempt: &[UserId],
+    connection: E,
+) -> Result<Vec<StaleUser>> {
+    let deactivate = match action {
+        StaleUserAction::Flag => false,
+        StaleUserAction::Deactivate => true,
+    };
+
+    #[allow(clippy::cast_precision_loss)]
+    let stale_after_seconds = stale_after.num_seconds() as f64;
+    // Flagging skips users that are already flagged, deactivating keeps the original flag time.
+    let rows = sqlx::query!(
+        r#"
+        UPDATE users
+        SET flagged_stale_at = CASE WHEN $3 THEN COALESCE(flagged_stale_at, now()) ELSE now() END,
+            deactivated_at = CASE WHEN $3 THEN now() ELSE deactivated_at END
+        WHERE deleted_at is null
+            AND deactivated_at is null
+            AND ($3 OR flagged_stale_at is null)
+            AND COALESCE(last_seen_at, created_at) < now() - make_interval(secs => $1)
+            AND NOT id = any($2)
+        RETURNING id, name, last_seen_at
+        "#,
+        stale_after_seconds,
+        &exempt.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        deactivate
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error applying stale user policy".to_string()))?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(StaleUser {
+                user_id: row.id.try_into()?,
+                name: row.name,
+                last_seen_at: row.last_seen_at,
+            })
+        })
+        .collect()
+}
+
+/// Lifts a deactivation. Counts as activity, so that the user is not
+/// deactivated again right away.
+pub(crate) async fn reactivate_user<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    id: &UserId,
+    connection: E,
+) -> Result<Option<()>> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE users
+        SET deactivated_at = null, flagged_stale_at = null, last_seen_at = now()
+        WHERE id = $1 AND deleted_at is null
+        "#,
+        id.to_string()
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error reactivating user".to_string()))?;
+
+    Ok((result.rows_affected() > 0).then_some(()))
+}
+
 pub(crate) async fn delete_user<'c, 'e: 'c, E: sqlx::Executor<'c, Database = sqlx::Postgres>>(
     id: UserId,
     connection: E,
@@ -202,14 +370,15 @@ pub(crate) async fn create_or_update_user<
         UserLastUpdatedWith::UpdateEndpoint => DbUserLastUpdatedWith::UpdateEndpoint,
     };
 
-    // query_as doesn't respect FromRow: https://github.com/launchbadge/sqlx/issues/2584
-    let user = sqlx::query!(
+    let row = sqlx::query!(
         r#"
         INSERT INTO users (id, name, email, last_updated_with, user_type)
         VALUES ($1, $2, $3, $4, $5)
         ON CONFLICT (id)
         DO UPDATE SET name = $2, email = $3, last_updated_with = $4, user_type = $5, deleted_at = null
-        returning (xmax = 0) AS created, id, name, email, created_at, updated_at, last_updated_with as "last_updated_with: DbUserLastUpdatedWith", user_type as "user_type: DbUserType"
+        RETURNING (xmax = 0) AS "created!", id, name, email, created_at, updated_at,
+            last_updated_with as "last_updated_with: DbUserLastUpdatedWith",
+            user_type as "user_type: DbUserType", last_seen_at, flagged_stale_at, deactivated_at
         "#,
         id.to_string(),
         name,
@@ -220,15 +389,18 @@ pub(crate) async fn create_or_update_user<
     .fetch_one(connection)
     .await
     .map_err(|e| e.into_error_model("Error creating or updating user".to_string()))?;
-    let created = user.created.unwrap_or_default();
+    let created = row.created;
     let user = UserRow {
-        id: user.id,
-        name: user.name,
-        email: user.email,
-        user_type: user.user_type,
-        last_updated_with: user.last_updated_with,
-        created_at: user.created_at,
-        updated_at: user.updated_at,
+        id: row.id,
+        name: row.name,
+        email: row.email,
+        last_updated_with: row.last_updated_with,
+        user_type: row.user_type,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+        last_seen_at: row.last_seen_at,
+        flagged_stale_at: row.flagged_stale_at,
+        deactivated_at: row.deactivated_at,
     };
 
     Ok(if created {
@@ -267,10 +439,97 @@ pub(crate) async fn search_user<'e, 'c: 'e, E: sqlx::Executor<'c, Database = sql
     Ok(SearchUserResponse { users })
 }
 
+pub(crate) async fn get_user_default_warehouse<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    user_id: &UserId,
+    connection: E,
+) -> Result<Option<PrincipalDefaultWarehouse>> {
+    let row = sqlx::query!(
+        r#"
+        SELECT d.warehouse_id, w.project_id
+        FROM user_default_warehouse d
+        INNER JOIN warehouse w ON d.warehouse_id = w.warehouse_id
+        WHERE d.user_id = $1 AND w.status = 'active'
+        "#,
+        user_id.to_string(),
+    )
+    .fetch_optional(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching default warehouse of user".to_string()))?;
+
+    Ok(row.map(|row| PrincipalDefaultWarehouse {
+        warehouse_id: row.warehouse_id.into(),
+        project_id: row.project_id.into(),
+    }))
+}
+
+pub(crate) async fn set_user_default_warehouse<
+    'e,
+    'c: 'e,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    user_id: &UserId,
+    warehouse_id: Option<WarehouseIdent>,
+    connection: E,
+) -> Result<()> {
+    let Some(warehouse_id) = warehouse_id else {
+        sqlx::query!(
+            r#"
+            DELETE FROM user_default_warehouse
+            WHERE user_id = $1
+            "#,
+            user_id.to_string(),
+        )
+        .execute(connection)
+        .await
+        .map_err(|e| e.into_error_model("Error removing default warehouse of user".to_string()))?;
+        return Ok(());
+    };
+
+    sqlx::query!(
+        r#"
+        INSERT INTO user_default_warehouse (user_id, warehouse_id)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id)
+        DO UPDATE SET warehouse_id = $2
+        "#,
+        user_id.to_string(),
+        *warehouse_id,
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_err) => match db_err.constraint() {
+            Some("user_default_warehouse_user_id_fkey") => ErrorModel::not_found(
+                format!("User {user_id} not found"),
+                "UserNotFound",
+                Some(Box::new(e)),
+            ),
+            Some("user_default_warehouse_warehouse_id_fkey") => ErrorModel::not_found(
+                format!("Warehouse {warehouse_id} not found"),
+                "WarehouseNotFound",
+                Some(Box::new(e)),
+            ),
+            _ => e.into_error_model("Error setting default warehouse of user"),
+        },
+        _ => e.into_error_model("Error setting default warehouse of user"),
+    })?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use crate::api::iceberg::types::PageToken;
-    use crate::implementations::postgres::CatalogState;
+    use crate::api::management::v1::user::IdentityProvider;
+    use crate::api::management::v1::SortOrder;
+    use crate::implementations::postgres::warehouse::set_warehouse_status;
+    use crate::implementations::postgres::warehouse::test::initialize_warehouse;
+    use crate::implementations::postgres::{CatalogState, PostgresTransaction};
+    use crate::service::{Transaction, WarehouseStatus};
 
     use super::*;
 
@@ -295,6 +554,7 @@ mod test {
         let users = list_users(
             None,
             None,
+            UserListFilter::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
@@ -326,6 +586,7 @@ mod test {
         let users = list_users(
             None,
             None,
+            UserListFilter::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
@@ -393,6 +654,7 @@ mod test {
         let users = list_users(
             None,
             None,
+            UserListFilter::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
@@ -433,6 +695,7 @@ mod test {
         let users = list_users(
             None,
             None,
+            UserListFilter::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(10),
@@ -447,6 +710,7 @@ mod test {
         let users = list_users(
             None,
             None,
+            UserListFilter::default(),
             PaginationQuery {
                 page_token: PageToken::NotSpecified,
                 page_size: Some(5),
@@ -467,6 +731,7 @@ mod test {
         let users = list_users(
             None,
             None,
+            UserListFilter::default(),
             PaginationQuery {
                 page_token: users.next_page_token.into(),
                 page_size: Some(5),
@@ -490,6 +755,7 @@ mod test {
         let users = list_users(
             None,
             None,
+            UserListFilter::default(),
             PaginationQuery {
                 page_token: users.next_page_token.into(),
                 page_size: Some(5),
@@ -501,4 +767,297 @@ mod test {
         assert_eq!(users.users.len(), 0);
         assert!(users.next_page_token.is_none());
     }
+
+    #[sqlx::test]
+    async fn test_list_users_filter_and_sort(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let charlie = UserId::oidc("user_a").unwrap();
+        let alice = UserId::oidc("user_b").unwrap();
+        let bob = UserId::kubernetes("user_c").unwrap();
+        for (user_id, name) in [(&charlie, "Charlie"), (&alice, "alice"), (&bob, "Bob")] {
+            create_or_update_user(
+                user_id,
+                name,
+                None,
+                UserLastUpdatedWith::ConfigCallCreation,
+                UserType::Human,
+                &state.read_write.write_pool,
+            )
+            .await
+            .unwrap();
+        }
+        update_user_last_seen(&alice, &state.read_write.write_pool)
+            .await
+            .unwrap();
+
+        let list = |filter: UserListFilter, page_token: PageToken| {
+            list_users(
+                None,
+                None,
+                filter,
+                PaginationQuery {
+                    page_token,
+                    page_size: Some(2),
+                },
+                &state.read_write.read_pool,
+            )
+        };
+        let names = |users: &ListUsersResponse| {
+            users
+                .users
+                .iter()
+                .map(|u| u.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        let users = list(
+            UserListFilter {
+                idp: Some(IdentityProvider::Kubernetes),
+                ..Default::default()
+            },
+            PageToken::NotSpecified,
+        )
+        .await
+        .unwrap();
+        assert_eq!(names(&users), vec!["Bob"]);
+
+        // Name sort is case-insensitive and keeps working across pages
+        let by_name = UserListFilter {
+            sort_by: UserSortBy::Name,
+            ..Default::default()
+        };
+        let users = list(by_name.clone(), PageToken::NotSpecified)
+            .await
+            .unwrap();
+        assert_eq!(names(&users), vec!["alice", "Bob"]);
+        let users = list(by_name, users.next_page_token.into()).await.unwrap();
+        assert_eq!(names(&users), vec!["Charlie"]);
+
+        let by_name_desc = UserListFilter {
+            sort_by: UserSortBy::Name,
+            sort_order: SortOrder::Desc,
+            ..Default::default()
+        };
+        let users = list(by_name_desc.clone(), PageToken::NotSpecified)
+            .await
+            .unwrap();
+        assert_eq!(names(&users), vec!["Charlie", "Bob"]);
+        let users = list(by_name_desc, users.next_page_token.into())
+            .await
+            .unwrap();
+        assert_eq!(names(&users), vec!["alice"]);
+
+        let an_hour_ago = chrono::Utc::now() - chrono::Duration::hours(1);
+        let users = list(
+            UserListFilter {
+                last_seen_after: Some(an_hour_ago),
+                ..Default::default()
+            },
+            PageToken::NotSpecified,
+        )
+        .await
+        .unwrap();
+        assert_eq!(names(&users), vec!["alice"]);
+        assert!(users.users[0].last_seen_at.is_some());
+
+        // Users that were never seen are included
+        let users = list(
+            UserListFilter {
+                last_seen_before: Some(an_hour_ago),
+                sort_by: UserSortBy::Name,
+                ..Default::default()
+            },
+            PageToken::NotSpecified,
+        )
+        .await
+        .unwrap();
+        assert_eq!(names(&users), vec!["Bob", "Charlie"]);
+
+        let users = list(
+            UserListFilter {
+                sort_by: UserSortBy::LastSeen,
+                sort_order: SortOrder::Desc,
+                ..Default::default()
+            },
+            PageToken::NotSpecified,
+        )
+        .await
+        .unwrap();
+        assert_eq!(names(&users)[0], "alice");
+    }
+
+    #[sqlx::test]
+    async fn test_stale_user_policy(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let active = UserId::oidc("active").unwrap();
+        let exempt = UserId::kubernetes("exempt").unwrap();
+        let stale = UserId::oidc("stale").unwrap();
+        for user_id in [&active, &exempt, &stale] {
+            create_or_update_user(
+                user_id,
+                "Test User",
+                None,
+                UserLastUpdatedWith::CreateEndpoint,
+                UserType::Human,
+                &state.read_write.write_pool,
+            )
+            .await
+            .unwrap();
+        }
+        sqlx::query!("UPDATE users SET created_at = now() - interval '100 days'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let activity = update_user_last_seen(&active, &state.read_write.write_pool)
+            .await
+            .unwrap();
+        assert_eq!(activity, UserActivity::Active);
+
+        let stale_after = chrono::Duration::days(90);
+        let exempt = vec![exempt];
+        let affected = apply_stale_user_policy(
+            stale_after,
+            StaleUserAction::Flag,
+            &exempt,
+            &state.read_write.write_pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            affected.iter().map(|u| &u.user_id).collect::<Vec<_>>(),
+            vec![&stale]
+        );
+        // Flagged users are not flagged again
+        let affected = apply_stale_user_policy(
+            stale_after,
+            StaleUserAction::Flag,
+            &exempt,
+            &state.read_write.write_pool,
+        )
+        .await
+        .unwrap();
+        assert!(affected.is_empty());
+
+        let affected = apply_stale_user_policy(
+            stale_after,
+            StaleUserAction::Deactivate,
+            &exempt,
+            &state.read_write.write_pool,
+        )
+        .await
+        .unwrap();
+        assert_eq!(affected.len(), 1);
+        let activity = update_user_last_seen(&stale, &state.read_write.write_pool)
+            .await
+            .unwrap();
+        assert_eq!(activity, UserActivity::Deactivated);
+
+        reactivate_user(&stale, &state.read_write.write_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        let activity = update_user_last_seen(&stale, &state.read_write.write_pool)
+            .await
+            .unwrap();
+        assert_eq!(activity, UserActivity::Active);
+
+        let unknown = UserId::oidc("unknown").unwrap();
+        assert_eq!(
+            update_user_last_seen(&unknown, &state.read_write.write_pool)
+                .await
+                .unwrap(),
+            UserActivity::Unknown
+        );
+        assert!(reactivate_user(&unknown, &state.read_write.write_pool)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[sqlx::test]
+    async fn test_user_default_warehouse(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let user_id = UserId::oidc("test_user_1").unwrap();
+
+        // Yield 404 on user not found
+        let err =
+            set_user_default_warehouse(&user_id, Some(warehouse_id), &state.read_write.write_pool)
+                .await
+                .unwrap_err();
+        assert_eq!(err.error.code, 404);
+        assert_eq!(err.error.r#type, "UserNotFound");
+
+        create_or_update_user(
+            &user_id,
+            "Test User 1",
+            None,
+            UserLastUpdatedWith::CreateEndpoint,
+            UserType::Human,
+            &state.read_write.write_pool,
+        )
+        .await
+        .unwrap();
+
+        let default = get_user_default_warehouse(&user_id, &state.read_write.read_pool)
+            .await
+            .unwrap();
+        assert_eq!(default, None);
+
+        // Yield 404 on warehouse not found
+        let err = set_user_default_warehouse(
+            &user_id,
+            Some(uuid::Uuid::now_v7().into()),
+            &state.read_write.write_pool,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.error.code, 404);
+        assert_eq!(err.error.r#type, "WarehouseNotFound");
+
+        set_user_default_warehouse(&user_id, Some(warehouse_id), &state.read_write.write_pool)
+            .await
+            .unwrap();
+        let default = get_user_default_warehouse(&user_id, &state.read_write.read_pool)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(default.warehouse_id, warehouse_id);
+        assert_eq!(default.project_id, uuid::Uuid::nil().into());
+
+        // Inactive warehouses are not returned
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        set_warehouse_status(warehouse_id, WarehouseStatus::Inactive, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+        let default = get_user_default_warehouse(&user_id, &state.read_write.read_pool)
+            .await
+            .unwrap();
+        assert_eq!(default, None);
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        set_warehouse_status(warehouse_id, WarehouseStatus::Active, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+        let default = get_user_default_warehouse(&user_id, &state.read_write.read_pool)
+            .await
+            .unwrap();
+        assert!(default.is_some());
+
+        // Remove the default
+        set_user_default_warehouse(&user_id, None, &state.read_write.write_pool)
+            .await
+            .unwrap();
+        let default = get_user_default_warehouse(&user_id, &state.read_write.read_pool)
+            .await
+            .unwrap();
+        assert_eq!(default, None);
+    }
 }
@@ -1,13 +1,21 @@
 use super::dbutils::DBErrorHandler as _;
 use crate::api::{CatalogConfig, ErrorModel, Result};
-use crate::service::{GetProjectResponse, GetWarehouseResponse, WarehouseStatus};
+use crate::service::{
+    GetProjectResponse, GetWarehouseResponse, NamespaceIdentUuid, TabularIdentUuid,
+    WarehouseStatistics, WarehouseStatus, WarehouseStorageHealth,
+};
 use crate::{service::storage::StorageProfile, ProjectIdent, SecretIdent, WarehouseIdent};
 use sqlx::Error;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 
+use super::tabular::TabularType;
 use super::CatalogState;
-use crate::api::management::v1::warehouse::TabularDeleteProfile;
+use crate::api::management::v1::warehouse::{
+    SnapshotSummaryEnrichment, TableLimits, TabularDeleteProfile, WarehouseLifecycle,
+    WarehouseSummary,
+};
+use ipnet::IpNet;
 use sqlx::types::Json;
 
 pub(super) async fn get_warehouse_by_name(
@@ -70,6 +78,330 @@ pub(super) async fn set_warehouse_deletion_profile<
     Ok(())
 }
 
+pub(super) async fn set_warehouse_namespace_defaults<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    namespace_defaults: &HashMap<String, String>,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET namespace_defaults = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        Json(namespace_defaults) as _,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse namespace defaults"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(super) async fn set_warehouse_allowed_client_networks<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    allowed_client_networks: &[IpNet],
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET allowed_client_networks = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        Json(allowed_client_networks) as _,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting allowed client networks of warehouse"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(super) async fn set_warehouse_table_limits<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    table_limits: &TableLimits,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET table_limits = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        Json(table_limits) as _,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting table limits of warehouse"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(super) async fn set_warehouse_snapshot_summary_enrichment<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    snapshot_summary_enrichment: &SnapshotSummaryEnrichment,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET snapshot_summary_enrichment = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        Json(snapshot_summary_enrichment) as _,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting snapshot summary enrichment of warehouse"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(super) async fn set_warehouse_wasm_hooks<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    wasm_hooks: &[String],
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET wasm_hooks = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        wasm_hooks,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting WASM hooks of warehouse"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(super) async fn get_warehouse_allowed_client_networks(
+    warehouse_id: WarehouseIdent,
+    catalog_state: CatalogState,
+) -> Result<Option<Vec<IpNet>>> {
+    let networks = sqlx::query_scalar!(
+        r#"
+            SELECT allowed_client_networks as "allowed_client_networks: Json<Vec<IpNet>>"
+            FROM warehouse
+            WHERE warehouse_id = $1
+            "#,
+        *warehouse_id
+    )
+    .fetch_optional(&catalog_state.read_pool())
+    .await
+    .map_err(|e| e.into_error_model("Error fetching allowed client networks of warehouse"))?;
+
+    Ok(networks.map(|networks| networks.0))
+}
+
+pub(super) async fn set_warehouse_lifecycle<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    lifecycle: &WarehouseLifecycle,
+    connection: E,
+) -> Result<()> {
+    let row_count = sqlx::query!(
+        r#"
+            UPDATE warehouse
+            SET lifecycle = $1
+            WHERE warehouse_id = $2
+            AND status = 'active'
+            "#,
+        Json(lifecycle) as _,
+        *warehouse_id
+    )
+    .execute(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error setting warehouse lifecycle"))?
+    .rows_affected();
+
+    if row_count == 0 {
+        return Err(ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", None).into());
+    }
+
+    Ok(())
+}
+
+pub(super) async fn list_expired_ephemeral_warehouses<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    connection: E,
+) -> Result<Vec<WarehouseIdent>> {
+    // Activity is the latest creation, load or commit of any tabular in the warehouse.
+    let warehouse_ids = sqlx::query_scalar!(
+        r#"
+            SELECT w.warehouse_id
+            FROM warehouse w
+            LEFT JOIN LATERAL (
+                SELECT max(GREATEST(t.created_at, t.updated_at, a.last_read_at, a.last_commit_at)) AS last_activity
+                FROM tabular t
+                JOIN namespace n ON n.namespace_id = t.namespace_id
+                LEFT JOIN tabular_access a ON a.tabular_id = t.tabular_id
+                WHERE n.warehouse_id = w.warehouse_id
+            ) activity ON true
+            WHERE w.lifecycle ->> 'type' = 'ephemeral'
+            AND (
+                w.created_at + make_interval(secs => (w.lifecycle ->> 'max-lifetime-seconds')::bigint) < now()
+                OR GREATEST(w.created_at, activity.last_activity)
+                    + make_interval(secs => (w.lifecycle ->> 'max-idle-seconds')::bigint) < now()
+            )
+            "#
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| e.into_error_model("Error listing expired ephemeral warehouses"))?;
+
+    Ok(warehouse_ids.into_iter().map(Into::into).collect())
+}
+
+pub(super) async fn clear_warehouse_tabulars(
+    warehouse_id: WarehouseIdent,
+    connection: &mut sqlx::PgConnection,
+) -> Result<Vec<(TabularIdentUuid, String)>> {
+    // Exempt tabulars are on hold and must outlive the warehouse.
+    let exempt = sqlx::query_scalar!(
+        r#"
+            SELECT count(*) as "count!"
+            FROM tabular t
+            JOIN namespace n ON t.namespace_id = n.namespace_id
+            WHERE n.warehouse_id = $1
+            AND t.expiration_exempt
+            "#,
+        *warehouse_id
+    )
+    .fetch_one(&mut *connection)
+    .await
+    .map_err(|e| e.into_error_model("Error counting expiration exempt tabulars of warehouse"))?;
+    if exempt > 0 {
+        return Err(ErrorModel::conflict(
+            format!("Warehouse contains {exempt} tabulars exempt from expiration"),
+            "WarehouseHasExpirationExemptTabulars",
+            None,
+        )
+        .into());
+    }
+
+    let rows = sqlx::query!(
+        r#"
+            DELETE FROM tabular t
+            USING namespace n
+            WHERE t.namespace_id = n.namespace_id
+            AND n.warehouse_id = $1
+            RETURNING t.tabular_id, t.typ as "typ: TabularType", t.location
+            "#,
+        *warehouse_id
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .map_err(|e| e.into_error_model("Error deleting tabulars of warehouse"))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let tabular_id = match row.typ {
+                TabularType::Table => TabularIdentUuid::Table(row.tabular_id),
+                TabularType::View => TabularIdentUuid::View(row.tabular_id),
+            };
+            (tabular_id, row.location)
+        })
+        .collect())
+}
+
+pub(super) async fn clear_warehouse_namespaces<
+    'c,
+    'e: 'c,
+    E: sqlx::Executor<'c, Database = sqlx::Postgres>,
+>(
+    warehouse_id: WarehouseIdent,
+    connection: E,
+) -> Result<Vec<NamespaceIdentUuid>> {
+    let namespace_ids = sqlx::query_scalar!(
+        r#"
+            DELETE FROM namespace
+            WHERE warehouse_id = $1
+            RETURNING namespace_id
+            "#,
+        *warehouse_id
+    )
+    .fetch_all(connection)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_error) if db_error.is_foreign_key_violation() => {
+            ErrorModel::conflict(
+                "Warehouse still contains tabulars",
+                "WarehouseNotEmpty",
+                Some(Box::new(e)),
+            )
+        }
+        _ => e.into_error_model("Error deleting namespaces of warehouse"),
+    })?;
+
+    Ok(namespace_ids.into_iter().map(Into::into).collect())
+}
+
 pub(super) async fn get_config_for_warehouse(
     warehouse_id: WarehouseIdent,
     catalog_state: CatalogState,
@@ -278,6 +610,17 @@ pub(crate) async fn list_warehouses<
         status: WarehouseStatus,
         tabular_delete_mode: DbTabularDeleteProfile,
         tabular_expiration_seconds: Option<i64>,
+        storage_health: WarehouseStorageHealth,
+        namespace_defaults: Json<HashMap<String, String>>,
+        lifecycle: Json<WarehouseLifecycle>,
+        allowed_client_networks: Json<Vec<IpNet>>,
+        table_limits: Json<TableLimits>,
+        snapshot_summary_enrichment: Json<SnapshotSummaryEnrichment>,
+        wasm_hooks: Vec<String>,
+        number_of_namespaces: i64,
+        number_of_tables: i64,
+        number_of_views: i64,
+        last_activity_at: Option<chrono::DateTime<chrono::Utc>>,
     }
 
     let include_status = include_status.unwrap_or_else(|| vec![WarehouseStatus::Active]);
@@ -285,16 +628,29 @@ pub(crate) async fn list_warehouses<
         WarehouseRecord,
         r#"
             SELECT 
-                warehouse_id,
-                warehouse_name,
-                storage_profile as "storage_profile: Json<StorageProfile>",
-                storage_secret_id,
-                status AS "status: WarehouseStatus",
-                tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-                tabular_expiration_seconds
-            FROM warehouse
-            WHERE project_id = $1
-            AND status = ANY($2)
+                w.warehouse_id,
+                w.warehouse_name,
+                w.storage_profile as "storage_profile: Json<StorageProfile>",
+                w.storage_secret_id,
+                w.status AS "status: WarehouseStatus",
+                w.tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
+                w.tabular_expiration_seconds,
+                COALESCE(h.storage_health, 'healthy') AS "storage_health!: WarehouseStorageHealth",
+                w.namespace_defaults as "namespace_defaults: Json<HashMap<String, String>>",
+                w.lifecycle as "lifecycle: Json<WarehouseLifecycle>",
+                w.allowed_client_networks as "allowed_client_networks: Json<Vec<IpNet>>",
+                w.table_limits as "table_limits: Json<TableLimits>",
+                w.snapshot_summary_enrichment as "snapshot_summary_enrichment: Json<SnapshotSummaryEnrichment>",
+                w.wasm_hooks,
+                COALESCE(a.number_of_namespaces, 0) AS "number_of_namespaces!",
+                COALESCE(a.number_of_tables, 0) AS "number_of_tables!",
+                COALESCE(a.number_of_views, 0) AS "number_of_views!",
+                a.last_activity_at
+            FROM warehouse w
+            LEFT JOIN warehouse_storage_health h ON h.warehouse_id = w.warehouse_id
+            LEFT JOIN LATERAL warehouse_summary(w.warehouse_id) a ON true
+            WHERE w.project_id = $1
+            AND w.status = ANY($2)
             "#,
         *project_id,
         include_status as Vec<WarehouseStatus>
@@ -329,6 +685,19 @@ pub(crate) async fn list_warehouses<
                 storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
                 status: warehouse.status,
                 tabular_delete_profile,
+                storage_health: warehouse.storage_health,
+                namespace_defaults: warehouse.namespace_defaults.0,
+                lifecycle: warehouse.lifecycle.0,
+                allowed_client_networks: warehouse.allowed_client_networks.0,
+                table_limits: warehouse.table_limits.0,
+                snapshot_summary_enrichment: warehouse.snapshot_summary_enrichment.0,
+                wasm_hooks: warehouse.wasm_hooks,
+                summary: WarehouseSummary {
+                    number_of_namespaces: warehouse.number_of_namespaces,
+                    number_of_tables: warehouse.number_of_tables,
+                    number_of_views: warehouse.number_of_views,
+                    last_activity_at: warehouse.last_activity_at,
+                },
             })
         })
         .collect::<Result<Vec<_>>>()
@@ -341,15 +710,28 @@ pub(crate) async fn get_warehouse(
     let warehouse = sqlx::query!(
         r#"
         SELECT 
-            warehouse_name,
-            project_id,
-            storage_profile as "storage_profile: Json<StorageProfile>",
-            storage_secret_id,
-            status AS "status: WarehouseStatus",
-            tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
-            tabular_expiration_seconds
-        FROM warehouse
-        WHERE warehouse_id = $1
+            w.warehouse_name,
+            w.project_id,
+            w.storage_profile as "storage_profile: Json<StorageProfile>",
+            w.storage_secret_id,
+            w.status AS "status: WarehouseStatus",
+            w.tabular_delete_mode as "tabular_delete_mode: DbTabularDeleteProfile",
+            w.tabular_expiration_seconds,
+            COALESCE(h.storage_health, 'healthy') AS "storage_health!: WarehouseStorageHealth",
+            w.namespace_defaults as "namespace_defaults: Json<HashMap<String, String>>",
+            w.lifecycle as "lifecycle: Json<WarehouseLifecycle>",
+            w.allowed_client_networks as "allowed_client_networks: Json<Vec<IpNet>>",
+            w.table_limits as "table_limits: Json<TableLimits>",
+            w.snapshot_summary_enrichment as "snapshot_summary_enrichment: Json<SnapshotSummaryEnrichment>",
+            w.wasm_hooks,
+            COALESCE(a.number_of_namespaces, 0) AS "number_of_namespaces!",
+            COALESCE(a.number_of_tables, 0) AS "number_of_tables!",
+            COALESCE(a.number_of_views, 0) AS "number_of_views!",
+            a.last_activity_at
+        FROM warehouse w
+        LEFT JOIN warehouse_storage_health h ON h.warehouse_id = w.warehouse_id
+        LEFT JOIN LATERAL warehouse_summary(w.warehouse_id) a ON true
+        WHERE w.warehouse_id = $1
         "#,
         *warehouse_id
     )
@@ -381,6 +763,19 @@ pub(crate) async fn get_warehouse(
             storage_secret_id: warehouse.storage_secret_id.map(std::convert::Into::into),
             status: warehouse.status,
             tabular_delete_profile,
+            storage_health: warehouse.storage_health,
+            namespace_defaults: warehouse.namespace_defaults.0,
+            lifecycle: warehouse.lifecycle.0,
+            allowed_client_networks: warehouse.allowed_client_networks.0,
+            table_limits: warehouse.table_limits.0,
+            snapshot_summary_enrichment: warehouse.snapshot_summary_enrichment.0,
+            wasm_hooks: warehouse.wasm_hooks,
+            summary: WarehouseSummary {
+                number_of_namespaces: warehouse.number_of_namespaces,
+                number_of_tables: warehouse.number_of_tables,
+                number_of_views: warehouse.number_of_views,
+                last_activity_at: warehouse.last_activity_at,
+            },
         }))
     } else {
         Ok(None)
@@ -533,6 +928,167 @@ pub(crate) async fn update_storage_profile(
     Ok(())
 }
 
+/// Fold committed per-transaction deltas of the warehouse summaries into their totals.
+/// Deltas of transactions that are still running are not visible and stay in place.
+/// Returns the number of folded deltas.
+pub(crate) async fn fold_warehouse_aggregate_deltas(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<u64> {
+    let folded = sqlx::query_scalar!(
+        r#"
+        WITH deltas AS (
+            DELETE FROM warehouse_aggregate_deltas
+            RETURNING warehouse_id, number_of_namespaces, number_of_tables, number_of_views, last_activity_at
+        ),
+        summed AS (
+            SELECT
+                warehouse_id,
+                sum(number_of_namespaces)::bigint AS number_of_namespaces,
+                sum(number_of_tables)::bigint AS number_of_tables,
+                sum(number_of_views)::bigint AS number_of_views,
+                max(last_activity_at) AS last_activity_at
+            FROM deltas
+            GROUP BY warehouse_id
+        ),
+        updated AS (
+            UPDATE warehouse_aggregates a
+            SET number_of_namespaces = a.number_of_namespaces + s.number_of_namespaces,
+                number_of_tables = a.number_of_tables + s.number_of_tables,
+                number_of_views = a.number_of_views + s.number_of_views,
+                last_activity_at = GREATEST(a.last_activity_at, s.last_activity_at)
+            FROM summed s
+            WHERE a.warehouse_id = s.warehouse_id
+        )
+        SELECT count(*) AS "count!" FROM deltas
+        "#
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error folding warehouse aggregate deltas"))?;
+
+    Ok(u64::try_from(folded).unwrap_or_default())
+}
+
+pub(crate) async fn get_warehouse_statistics(
+    warehouse_id: WarehouseIdent,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<WarehouseStatistics> {
+    // Staged tables (without metadata location) are not counted.
+    // The size of soft-deleted tables is taken from the summary of the snapshot
+    // `main` points to, so files only referenced by older snapshots are not included.
+    // `sum` yields `numeric` for integer input anyway, so the summary values are
+    // read as `numeric` and only the total is converted to `bigint`.
+    let stats = sqlx::query!(
+        r#"
+        SELECT
+            count(*) FILTER (WHERE t.typ = 'table' AND t.deleted_at IS NULL) AS "number_of_tables!",
+            count(*) FILTER (WHERE t.typ = 'view' AND t.deleted_at IS NULL) AS "number_of_views!",
+            count(*) FILTER (WHERE t.typ = 'table' AND t.deleted_at IS NOT NULL) AS "number_of_soft_deleted_tables!",
+            count(*) FILTER (WHERE t.typ = 'view' AND t.deleted_at IS NOT NULL) AS "number_of_soft_deleted_views!",
+            COALESCE(
+                sum((s.summary->>'total-files-size')::numeric) FILTER (WHERE t.deleted_at IS NOT NULL),
+                0
+            )::bigint AS "soft_deleted_bytes!",
+            count(*) FILTER (
+                WHERE t.deleted_at IS NOT NULL
+                AND s.snapshot_id IS NOT NULL
+                AND s.summary->>'total-files-size' IS NULL
+            ) AS "soft_deleted_tables_without_size!"
+        FROM tabular t
+        INNER JOIN namespace n ON t.namespace_id = n.namespace_id
+        LEFT JOIN table_refs r ON r.table_id = t.tabular_id AND r.table_ref_name = 'main'
+        LEFT JOIN table_snapshot s ON s.table_id = r.table_id AND s.snapshot_id = r.snapshot_id
+        WHERE n.warehouse_id = $1
+        AND t.metadata_location IS NOT NULL
+        "#,
+        *warehouse_id
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error fetching warehouse statistics"))?;
+
+    Ok(WarehouseStatistics {
+        number_of_tables: stats.number_of_tables,
+        number_of_views: stats.number_of_views,
+        number_of_soft_deleted_tables: stats.number_of_soft_deleted_tables,
+        number_of_soft_deleted_views: stats.number_of_soft_deleted_views,
+        soft_deleted_bytes: stats.soft_deleted_bytes,
+        soft_deleted_tables_without_size: stats.soft_deleted_tables_without_size,
+    })
+}
+
+pub(crate) async fn claim_warehouses_for_storage_check(
+    min_interval: chrono::Duration,
+    limit: i64,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Vec<WarehouseIdent>> {
+    #[allow(clippy::cast_precision_loss)]
+    let min_interval_seconds = min_interval.num_seconds() as f64;
+    let warehouse_ids = sqlx::query_scalar!(
+        r#"
+        INSERT INTO warehouse_storage_health (warehouse_id, checked_at)
+        SELECT w.warehouse_id, now()
+        FROM warehouse w
+        LEFT JOIN warehouse_storage_health h ON h.warehouse_id = w.warehouse_id
+        WHERE w.status = 'active'
+        AND (h.checked_at IS NULL OR h.checked_at < now() - make_interval(secs => $1))
+        ORDER BY h.checked_at ASC NULLS FIRST
+        LIMIT $2
+        -- Another instance might have claimed the warehouse concurrently
+        ON CONFLICT (warehouse_id) DO UPDATE SET checked_at = EXCLUDED.checked_at
+        WHERE warehouse_storage_health.checked_at < now() - make_interval(secs => $1)
+        RETURNING warehouse_id
+        "#,
+        min_interval_seconds,
+        limit
+    )
+    .fetch_all(&mut **transaction)
+    .await
+    .map_err(|e| e.into_error_model("Error claiming warehouses for storage check"))?;
+
+    Ok(warehouse_ids.into_iter().map(Into::into).collect())
+}
+
+pub(crate) async fn set_warehouse_storage_health(
+    warehouse_id: WarehouseIdent,
+    storage_health: WarehouseStorageHealth,
+    failed_checks: Option<serde_json::Value>,
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+) -> Result<Option<WarehouseStorageHealth>> {
+    let previous = sqlx::query_scalar!(
+        r#"
+        WITH previous AS (
+            SELECT storage_health FROM warehouse_storage_health
+            WHERE warehouse_id = $1
+            FOR UPDATE
+        )
+        INSERT INTO warehouse_storage_health (warehouse_id, storage_health, checked_at, failed_checks)
+        VALUES ($1, $2, now(), $3)
+        ON CONFLICT (warehouse_id) DO UPDATE SET
+            storage_health = EXCLUDED.storage_health,
+            checked_at = EXCLUDED.checked_at,
+            failed_checks = EXCLUDED.failed_checks
+        RETURNING (SELECT storage_health FROM previous) AS "previous_health: WarehouseStorageHealth"
+        "#,
+        *warehouse_id,
+        storage_health as _,
+        failed_checks
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| match &e {
+        sqlx::Error::Database(db_err) => match db_err.constraint() {
+            Some("warehouse_storage_health_warehouse_id_fkey") => {
+                ErrorModel::not_found("Warehouse not found", "WarehouseNotFound", Some(Box::new(e)))
+            }
+            _ => e.into_error_model("Error setting warehouse storage health"),
+        },
+        _ => e.into_error_model("Error setting warehouse storage health"),
+    })?;
+
+    Ok(previous)
+}
+
 fn map_select_warehouse_err(e: Error) -> ErrorModel {
     ErrorModel::internal(
         "Error fetching warehouse",
@@ -560,10 +1116,14 @@ impl From<TabularDeleteProfile> for DbTabularDeleteProfile {
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
+    use crate::implementations::postgres::tabular::table::tests::initialize_table;
     use crate::implementations::postgres::PostgresCatalog;
     use crate::service::storage::S3Flavor;
     use crate::service::{Catalog as _, Transaction};
-    use crate::{implementations::postgres::PostgresTransaction, service::storage::S3Profile};
+    use crate::{
+        implementations::postgres::PostgresTransaction,
+        service::storage::{LocationPlacement, MetadataNaming, S3Profile},
+    };
     use http::StatusCode;
 
     pub(crate) async fn initialize_warehouse(
@@ -599,8 +1159,19 @@ pub(crate) mod test {
             path_style_access: None,
             key_prefix: None,
             sts_role_arn: None,
+            sts_endpoint: None,
+            sts_base_role_arn: None,
+            sts_external_id: None,
+            sts_session_tags: None,
+            sts_principal_identity: false,
+            object_tags: None,
+            metadata_storage_class: None,
+            skip_archived_on_purge: false,
+            metadata_naming: MetadataNaming::default(),
             sts_enabled: false,
             flavor: S3Flavor::S3Compat,
+            additional_buckets: vec![],
+            location_placement: LocationPlacement::default(),
         }));
 
         let warehouse_id = PostgresCatalog::create_warehouse(
@@ -787,6 +1358,35 @@ pub(crate) mod test {
         assert_eq!(warehouse.unwrap().name, "new_name");
     }
 
+    #[sqlx::test]
+    async fn test_warehouse_namespace_defaults(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let defaults = HashMap::from([("owner".to_string(), "data-platform".to_string())]);
+
+        let mut transaction = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let warehouse = PostgresCatalog::get_warehouse(warehouse_id, transaction.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(warehouse.namespace_defaults.is_empty());
+        PostgresCatalog::set_warehouse_namespace_defaults(
+            warehouse_id,
+            &defaults,
+            transaction.transaction(),
+        )
+        .await
+        .unwrap();
+        let warehouse = PostgresCatalog::get_warehouse(warehouse_id, transaction.transaction())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(warehouse.namespace_defaults, defaults);
+        transaction.commit().await.unwrap();
+    }
+
     #[sqlx::test]
     async fn test_rename_project(pool: sqlx::PgPool) {
         let state = CatalogState::from_pools(pool.clone(), pool.clone());
@@ -837,4 +1437,167 @@ pub(crate) mod test {
         assert_eq!(err.error.code, StatusCode::CONFLICT);
         t.commit().await.unwrap();
     }
+
+    #[sqlx::test]
+    async fn test_warehouse_storage_health(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let claimed = PostgresCatalog::claim_warehouses_for_storage_check(
+            chrono::Duration::hours(1),
+            10,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(claimed, vec![warehouse_id]);
+
+        // Already claimed within the interval
+        let claimed = PostgresCatalog::claim_warehouses_for_storage_check(
+            chrono::Duration::hours(1),
+            10,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert!(claimed.is_empty());
+
+        let previous = PostgresCatalog::set_warehouse_storage_health(
+            warehouse_id,
+            WarehouseStorageHealth::DegradedStorage,
+            Some(serde_json::json!([{"check": "bucket-reachable"}])),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(previous, Some(WarehouseStorageHealth::Healthy));
+
+        let warehouse = PostgresCatalog::require_warehouse(warehouse_id, t.transaction())
+            .await
+            .unwrap();
+        assert_eq!(
+            warehouse.storage_health,
+            WarehouseStorageHealth::DegradedStorage
+        );
+
+        let previous = PostgresCatalog::set_warehouse_storage_health(
+            warehouse_id,
+            WarehouseStorageHealth::Healthy,
+            None,
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(previous, Some(WarehouseStorageHealth::DegradedStorage));
+        t.commit().await.unwrap();
+    }
+
+    #[sqlx::test]
+    async fn test_warehouse_statistics(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+        let _active = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let deleted = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let _staged = initialize_table(warehouse_id, state.clone(), true, None, None).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        PostgresCatalog::mark_tabular_as_deleted(
+            crate::service::TabularIdentUuid::Table(*deleted.table_id),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        let statistics = PostgresCatalog::get_warehouse_statistics(warehouse_id, t.transaction())
+            .await
+            .unwrap();
+        t.commit().await.unwrap();
+
+        assert_eq!(
+            statistics,
+            WarehouseStatistics {
+                number_of_tables: 1,
+                number_of_views: 0,
+                number_of_soft_deleted_tables: 1,
+                number_of_soft_deleted_views: 0,
+                soft_deleted_bytes: 0,
+                soft_deleted_tables_without_size: 0,
+            }
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_warehouse_summary(pool: sqlx::PgPool) {
+        let state = CatalogState::from_pools(pool.clone(), pool.clone());
+        let warehouse_id = initialize_warehouse(state.clone(), None, None, None, true).await;
+
+        let mut t = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let summary = PostgresCatalog::get_warehouse(warehouse_id, t.transaction())
+            .await
+            .unwrap()
+            .unwrap()
+            .summary;
+        t.commit().await.unwrap();
+        assert_eq!(summary.number_of_namespaces, 0);
+        assert_eq!(summary.number_of_tables, 0);
+        assert!(summary.last_activity_at.is_none());
+
+        // Each table is created in a new namespace
+        let _active = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let deleted = initialize_table(warehouse_id, state.clone(), false, None, None).await;
+        let _staged = initialize_table(warehouse_id, state.clone(), true, None, None).await;
+
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        PostgresCatalog::mark_tabular_as_deleted(
+            crate::service::TabularIdentUuid::Table(*deleted.table_id),
+            t.transaction(),
+        )
+        .await
+        .unwrap();
+        t.commit().await.unwrap();
+
+        let mut t = PostgresTransaction::begin_read(state.clone())
+            .await
+            .unwrap();
+        let summary = PostgresCatalog::get_warehouse(warehouse_id, t.transaction())
+            .await
+            .unwrap()
+            .unwrap()
+            .summary;
+        t.commit().await.unwrap();
+        assert_eq!(summary.number_of_namespaces, 3);
+        assert_eq!(summary.number_of_tables, 1);
+        assert_eq!(summary.number_of_views, 0);
+        assert!(summary.last_activity_at.is_some());
+
+        // Folding the deltas into the totals does not change the summary
+        let mut t = PostgresTransaction::begin_write(state.clone())
+            .await
+            .unwrap();
+        let folded = fold_warehouse_aggregate_deltas(t.transaction())
+            .await
+            .unwrap();
+        assert!(folded > 0);
+        let folded_summary = PostgresCatalog::get_warehouse(warehouse_id, t.transaction())
+            .await
+            .unwrap()
+            .unwrap()
+            .summary;
+        assert_eq!(folded_summary, summary);
+        assert_eq!(
+            fold_warehouse_aggregate_deltas(t.transaction())
+                .await
+                .unwrap(),
+            0
+        );
+        t.commit().await.unwrap();
+    }
 }
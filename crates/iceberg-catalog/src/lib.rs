@@ -9,16 +9,23 @@
 pub mod catalog;
 mod config;
 pub mod service;
-pub use service::{ProjectIdent, SecretIdent, WarehouseIdent};
+pub use service::{
+    NamespaceIdentUuid, ProjectIdent, SecretIdent, TableIdentUuid, TabularIdentUuid, ViewIdentUuid,
+    WarehouseIdent,
+};
 
-pub use config::{AuthZBackend, OpenFGAAuth, SecretBackend, CONFIG, DEFAULT_PROJECT_ID};
+pub use config::{AuthZBackend, LogFormat, OpenFGAAuth, SecretBackend, CONFIG, DEFAULT_PROJECT_ID};
 
 pub mod implementations;
 
+mod client_ip;
 mod request_metadata;
 
 pub mod api;
 
+#[cfg(feature = "router")]
+pub mod embedded;
+
 #[cfg(feature = "router")]
 pub mod metrics;
 mod retry;
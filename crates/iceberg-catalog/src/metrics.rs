@@ -18,16 +18,23 @@ pub type ExporterFuture = Pin<Box<dyn Future<Output = Result<(), anyhow::Error>>
 pub fn get_axum_layer_and_install_recorder(
     metrics_port: u16,
 ) -> anyhow::Result<(PrometheusMetricLayer<'static>, ExporterFuture)> {
-    let (recorder, exporter) = PrometheusBuilder::new()
-        .set_buckets_for_metric(
-            Matcher::Full(
-                PREFIXED_HTTP_REQUESTS_DURATION_SECONDS
-                    .get()
-                    .map_or(AXUM_HTTP_REQUESTS_DURATION_SECONDS, |s| s.as_str())
-                    .to_string(),
-            ),
-            utils::SECONDS_DURATION_BUCKETS,
-        )?
+    let builder = PrometheusBuilder::new().set_buckets_for_metric(
+        Matcher::Full(
+            PREFIXED_HTTP_REQUESTS_DURATION_SECONDS
+                .get()
+                .map_or(AXUM_HTTP_REQUESTS_DURATION_SECONDS, |s| s.as_str())
+                .to_string(),
+        ),
+        utils::SECONDS_DURATION_BUCKETS,
+    )?;
+    #[cfg(feature = "sqlx-postgres")]
+    let builder = builder.set_buckets_for_metric(
+        Matcher::Full(
+            crate::implementations::postgres::pool_metrics::POOL_ACQUIRE_SECONDS.to_string(),
+        ),
+        utils::SECONDS_DURATION_BUCKETS,
+    )?;
+    let (recorder, exporter) = builder
         .with_http_listener(([0, 0, 0, 0], metrics_port))
         .build()?;
     let handle = recorder.handle();
@@ -1,10 +1,34 @@
-use crate::service::authn::{Actor, AuthDetails};
+use crate::service::authn::{Actor, AuthDetails, UserId};
+use crate::service::ProjectIdent;
+use crate::CONFIG;
 use axum::middleware::Next;
 use axum::response::Response;
 use http::HeaderMap;
+use iceberg_ext::catalog::rest::ErrorModel;
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
+/// Headers carrying the id of the query or job that sent a request, in order of precedence.
+/// Clients set them via `header.<name>` catalog properties.
+const ENGINE_QUERY_ID_HEADERS: [&str; 4] = [
+    "x-engine-query-id",
+    "x-trino-query-id",
+    "x-spark-app-id",
+    "x-flink-job-id",
+];
+
+/// Snapshot summary properties written by engines that identify the query or job
+/// that created the snapshot, in order of precedence.
+const ENGINE_QUERY_ID_PROPERTIES: [&str; 4] = [
+    "engine-query-id",
+    "trino_query_id",
+    "spark.app.id",
+    "flink.job-id",
+];
+
 /// A struct to hold metadata about a request.
 ///
 /// Currently, it only holds the `request_id`, later it can be expanded to hold more metadata for
@@ -13,15 +37,45 @@ use uuid::Uuid;
 pub struct RequestMetadata {
     pub request_id: Uuid,
     pub auth_details: AuthDetails,
+    /// Engine profile matching the client that sent the request.
+    pub engine: Option<String>,
+    /// `User-Agent` header of the request.
+    pub user_agent: Option<String>,
+    /// Id of the query or job of the engine that sent the request, if the engine provided it.
+    pub engine_query_id: Option<String>,
+    /// Address of the client, as reported by trusted proxies.
+    pub client_ip: Option<IpAddr>,
+    /// Warnings returned to the client in `Warning` headers of the response.
+    pub warnings: Arc<Mutex<Vec<String>>>,
 }
 
 impl RequestMetadata {
+    /// Metadata for a request that was not received via HTTP, for example when an
+    /// application embedding the catalog calls its services directly.
+    #[must_use]
+    pub fn new(auth_details: AuthDetails) -> Self {
+        Self {
+            request_id: Uuid::now_v7(),
+            auth_details,
+            engine: None,
+            user_agent: None,
+            engine_query_id: None,
+            client_ip: None,
+            warnings: Arc::default(),
+        }
+    }
+
     #[cfg(test)]
     #[must_use]
     pub fn new_random() -> Self {
         Self {
             request_id: Uuid::new_v4(),
             auth_details: AuthDetails::Unauthenticated,
+            engine: None,
+            user_agent: None,
+            engine_query_id: None,
+            client_ip: None,
+            warnings: Arc::default(),
         }
     }
 
@@ -33,6 +87,11 @@ impl RequestMetadata {
         Self {
             request_id: Uuid::now_v7(),
             auth_details: AuthDetails::Principal(Principal::random_human(user_id)),
+            engine: None,
+            user_agent: None,
+            engine_query_id: None,
+            client_ip: None,
+            warnings: Arc::default(),
         }
     }
 
@@ -40,6 +99,66 @@ impl RequestMetadata {
     pub fn actor(&self) -> &Actor {
         self.auth_details.actor()
     }
+
+    #[must_use]
+    pub fn engine(&self) -> Option<&str> {
+        self.engine.as_deref()
+    }
+
+    #[must_use]
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+
+    #[must_use]
+    pub fn engine_query_id(&self) -> Option<&str> {
+        self.engine_query_id.as_deref()
+    }
+
+    #[must_use]
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
+    /// Add a warning for the client to the response of this request. Warnings of
+    /// requests that were not received via HTTP are dropped.
+    pub fn add_warning(&self, warning: impl Into<String>) {
+        self.warnings
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .push(warning.into());
+    }
+
+    /// Trusted principal that sent this request on behalf of the actor, if any.
+    #[must_use]
+    pub fn impersonated_by(&self) -> Option<&UserId> {
+        match &self.auth_details {
+            AuthDetails::Principal(principal) => principal.impersonated_by(),
+            AuthDetails::Unauthenticated => None,
+        }
+    }
+
+    /// Ensure that this request may target `project_id`. Principals authenticated by the
+    /// identity provider of a project may only access this project.
+    ///
+    /// # Errors
+    /// - the principal is bound to another project
+    pub fn require_project(&self, project_id: ProjectIdent) -> crate::api::Result<()> {
+        match self.auth_details.project_id() {
+            Some(principal_project) if principal_project != project_id => {
+                Err(ErrorModel::forbidden(
+                    format!(
+                        "Principal was authenticated by the identity provider of project \
+                        {principal_project} and cannot access project {project_id}"
+                    ),
+                    "ProjectNotAccessible",
+                    None,
+                )
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
 }
 #[cfg(feature = "router")]
 pub(crate) async fn create_request_metadata_with_trace_id_fn(
@@ -58,9 +177,96 @@ pub(crate) async fn create_request_metadata_with_trace_id_fn(
                 .flatten()
         })
         .unwrap_or(Uuid::now_v7());
+    let header_str = |name: &str| headers.get(name).and_then(|hv| hv.to_str().ok());
+    let engine = CONFIG
+        .engine_profiles
+        .resolve(header_str("x-iceberg-client"), header_str("user-agent"))
+        .map(str::to_string);
+    let user_agent = header_str("user-agent").map(str::to_string);
+    let engine_query_id = engine_query_id_from_headers(&headers).map(str::to_string);
+    let client_ip = crate::client_ip::client_ip(&request);
+    let warnings = Arc::<Mutex<Vec<String>>>::default();
     request.extensions_mut().insert(RequestMetadata {
         request_id,
         auth_details: AuthDetails::Unauthenticated,
+        engine,
+        user_agent,
+        engine_query_id,
+        client_ip,
+        warnings: warnings.clone(),
     });
-    next.run(request).await
+    let mut response = next.run(request).await;
+
+    let warnings = std::mem::take(
+        &mut *warnings
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner),
+    );
+    for warning in warnings {
+        // `199` is the code for miscellaneous warnings, the text must be a quoted string.
+        let text = warning.replace(['"', '\\'], "'");
+        match http::HeaderValue::from_str(&format!("199 lakekeeper \"{text}\"")) {
+            Ok(value) => {
+                response.headers_mut().append(http::header::WARNING, value);
+            }
+            Err(e) => tracing::debug!("Dropping warning that is not a valid header value: {e}"),
+        }
+    }
+    response
+}
+
+/// Id of the engine query or job from the request headers.
+pub(crate) fn engine_query_id_from_headers(headers: &HeaderMap) -> Option<&str> {
+    ENGINE_QUERY_ID_HEADERS.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|hv| hv.to_str().ok())
+            .filter(|v| !v.is_empty())
+    })
+}
+
+/// Id of the engine query or job from the summary of a snapshot.
+pub(crate) fn engine_query_id_from_properties(
+    properties: &HashMap<String, String>,
+) -> Option<&str> {
+    ENGINE_QUERY_ID_PROPERTIES.iter().find_map(|key| {
+        properties
+            .get(*key)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_engine_query_id_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(engine_query_id_from_headers(&headers), None);
+        headers.insert("x-spark-app-id", "app-20250101-0001".parse().unwrap());
+        assert_eq!(
+            engine_query_id_from_headers(&headers),
+            Some("app-20250101-0001")
+        );
+        headers.insert("x-engine-query-id", "custom".parse().unwrap());
+        assert_eq!(engine_query_id_from_headers(&headers), Some("custom"));
+    }
+
+    #[test]
+    fn test_engine_query_id_from_properties() {
+        let properties = HashMap::from_iter([
+            ("added-records".to_string(), "10".to_string()),
+            (
+                "trino_query_id".to_string(),
+                "20250101_120000_00001_abcde".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            engine_query_id_from_properties(&properties),
+            Some("20250101_120000_00001_abcde")
+        );
+        assert_eq!(engine_query_id_from_properties(&HashMap::new()), None);
+    }
 }
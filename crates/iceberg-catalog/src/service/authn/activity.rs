@@ -0,0 +1,72 @@
+use crate::request_metadata::RequestMetadata;
+use crate::service::housekeeping::evict_expired;
+use crate::service::{AuthDetails, Catalog, UserActivity, UserId};
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Activity of users that was recorded recently by this instance. Users are only
+/// marked as seen again once their entry expired, to avoid a write for every request.
+/// Deactivations and reactivations by other instances take effect once the entry expires.
+static USER_ACTIVITY_CACHE: LazyLock<moka::sync::Cache<UserId, UserActivity>> =
+    LazyLock::new(|| {
+        moka::sync::Cache::builder()
+            .max_capacity(10_000)
+            .time_to_live(Duration::from_secs(300))
+            .build()
+    });
+
+pub(crate) fn evict_expired_user_activity() -> u64 {
+    evict_expired(&USER_ACTIVITY_CACHE)
+}
+
+/// Forget the cached activity of a user, so that the next request re-checks its status.
+pub(crate) fn invalidate_user_activity(user_id: &UserId) {
+    USER_ACTIVITY_CACHE.invalidate(user_id);
+}
+
+/// Records when authenticated users were last seen and rejects deactivated users.
+/// Must run after the authentication middleware.
+pub(crate) async fn user_activity_middleware_fn<C: Catalog>(
+    State(catalog_state): State<C::State>,
+    Extension(metadata): Extension<RequestMetadata>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let AuthDetails::Principal(principal) = &metadata.auth_details else {
+        return next.run(request).await;
+    };
+    let user_id = principal.user_id();
+
+    let activity = if let Some(activity) = USER_ACTIVITY_CACHE.get(user_id) {
+        activity
+    } else {
+        match C::update_user_last_seen(user_id, catalog_state).await {
+            // Users are registered by their first request, which must not be cached.
+            Ok(UserActivity::Unknown) => UserActivity::Unknown,
+            Ok(activity) => {
+                USER_ACTIVITY_CACHE.insert(user_id.clone(), activity);
+                activity
+            }
+            Err(e) => {
+                tracing::warn!("Failed to record activity of user {user_id}: {:?}", e.error);
+                UserActivity::Unknown
+            }
+        }
+    };
+
+    if activity == UserActivity::Deactivated {
+        return IcebergErrorResponse::from(ErrorModel::forbidden(
+            format!("User {user_id} is deactivated. Ask an administrator to reactivate it."),
+            "UserDeactivated",
+            None,
+        ))
+        .into_response();
+    }
+
+    next.run(request).await
+}
@@ -1,113 +1,9 @@
 use crate::api;
-use crate::api::management::v1::user::UserType;
-use crate::service::Actor;
-use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
-use serde::{Deserialize, Serialize};
-
-/// Unique identifier of a user in the system.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, utoipa::ToSchema)]
-pub enum UserId {
-    /// OIDC principal
-    OIDC(String),
-    /// K8s principal
-    Kubernetes(String),
-}
-
-impl TryFrom<String> for UserId {
-    type Error = IcebergErrorResponse;
-
-    fn try_from(s: String) -> Result<Self, Self::Error> {
-        match s.split_once('~') {
-            Some(("oidc", user_id)) => Ok(UserId::oidc(user_id)?),
-            Some(("kubernetes", user_id)) => Ok(UserId::kubernetes(user_id)?),
-            _ => Err(ErrorModel::bad_request(
-                format!("Invalid user id format: {s}"),
-                "InvalidUserId",
-                None,
-            )
-            .into()),
-        }
-    }
-}
-
-impl std::fmt::Display for UserId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            UserId::OIDC(user_id) => write!(f, "oidc~{user_id}"),
-            UserId::Kubernetes(user_id) => write!(f, "kubernetes~{user_id}"),
-        }
-    }
-}
-
-impl UserId {
-    fn validate_subject(subject: &str) -> api::Result<()> {
-        Self::validate_len(subject)?;
-        Self::no_illegal_chars(subject)?;
-        Ok(())
-    }
-
-    pub(crate) fn oidc(subject: &str) -> api::Result<Self> {
-        Self::validate_subject(subject)?;
-        Ok(Self::OIDC(subject.to_string()))
-    }
-
-    pub(crate) fn kubernetes(subject: &str) -> api::Result<Self> {
-        Self::validate_subject(subject)?;
-        Ok(Self::Kubernetes(subject.to_string()))
-    }
-
-    fn validate_len(subject: &str) -> api::Result<()> {
-        if subject.len() >= 128 {
-            return Err(ErrorModel::bad_request(
-                "user id must be shorter than 128 chars",
-                "UserIdTooLongError",
-                None,
-            )
-            .into());
-        }
-        Ok(())
-    }
-
-    fn no_illegal_chars(subject: &str) -> api::Result<()> {
-        if subject
-            .chars()
-            .any(|c| !(c.is_alphanumeric() || c == '-' || c == '_'))
-        {
-            return Err(ErrorModel::bad_request(
-                "sub or oid claim contain illegal characters. Only alphanumeric + - are legal.",
-                "InvalidUserIdError",
-                None,
-            )
-            .into());
-        }
-        Ok(())
-    }
-}
-
-impl From<UserId> for String {
-    fn from(user_id: UserId) -> Self {
-        user_id.to_string()
-    }
-}
+use crate::api::management::v1::user::{User, UserType};
+use crate::service::{Actor, ProjectIdent};
+use iceberg_ext::catalog::rest::ErrorModel;
 
-impl<'de> Deserialize<'de> for UserId {
-    fn deserialize<D>(deserializer: D) -> api::Result<UserId, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        UserId::try_from(s).map_err(|e| serde::de::Error::custom(e.error))
-    }
-}
-
-impl Serialize for UserId {
-    fn serialize<S>(&self, serializer: S) -> api::Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str(self.to_string().as_str())
-    }
-}
+pub use iceberg_ext::management::UserId;
 
 #[derive(Debug, Clone)]
 pub struct Principal {
@@ -119,6 +15,10 @@ pub struct Principal {
     pub(super) issuer: String,
     pub(super) email: Option<String>,
     pub(super) idtyp: Option<String>,
+    /// Trusted principal that sent the request on behalf of this principal.
+    pub(super) impersonated_by: Option<UserId>,
+    /// Project whose identity provider authenticated this principal.
+    pub(super) project_id: Option<ProjectIdent>,
 }
 
 impl Principal {
@@ -134,9 +34,47 @@ impl Principal {
             issuer: "test-issuer".to_string(),
             email: None,
             idtyp: None,
+            impersonated_by: None,
+            project_id: None,
+        }
+    }
+
+    /// Principal for `user`, acting through this principal.
+    ///
+    /// Name and email are taken from the user record, as no token of the user is available.
+    #[must_use]
+    pub fn impersonate(self, user: User) -> Self {
+        Self {
+            actor: Actor::Principal(user.id.clone()),
+            user_id: user.id,
+            name: Some(user.name.clone()),
+            display_name: Some(user.name),
+            application_id: None,
+            issuer: self.issuer,
+            email: user.email,
+            idtyp: None,
+            impersonated_by: Some(self.user_id),
+            project_id: self.project_id,
         }
     }
 
+    /// Bind this principal to the project whose identity provider authenticated it.
+    ///
+    /// Subjects are only unique per identity provider, so the user id is prefixed with
+    /// the project to keep users of different projects apart.
+    ///
+    /// # Errors
+    /// - the prefixed user id is too long
+    pub(super) fn scope_to_project(self, project_id: ProjectIdent) -> api::Result<Self> {
+        let user_id = UserId::for_project(project_id, &self.user_id)?;
+        Ok(Self {
+            actor: Actor::Principal(user_id.clone()),
+            user_id,
+            project_id: Some(project_id),
+            ..self
+        })
+    }
+
     /// Best effort to determine the name of this principal from the claims.
     ///
     /// # Errors
@@ -215,6 +153,11 @@ impl Principal {
     pub fn email(&self) -> Option<&str> {
         self.email.as_deref()
     }
+
+    #[must_use]
+    pub fn impersonated_by(&self) -> Option<&UserId> {
+        self.impersonated_by.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -239,4 +182,23 @@ mod tests {
 
         serde_json::from_str::<UserId>(r#""nonexistant~123""#).unwrap_err();
     }
+
+    #[test]
+    fn test_user_id_for_project() {
+        let project_id = ProjectIdent::from(uuid::Uuid::nil());
+        let user_id = UserId::for_project(project_id, &UserId::OIDC("123".to_string())).unwrap();
+        assert_eq!(
+            user_id.to_string(),
+            "oidc~00000000-0000-0000-0000-000000000000_123"
+        );
+        assert_ne!(
+            user_id,
+            UserId::for_project(
+                ProjectIdent::from(uuid::Uuid::now_v7()),
+                &UserId::OIDC("123".to_string())
+            )
+            .unwrap()
+        );
+        UserId::for_project(project_id, &UserId::OIDC("a".repeat(100))).unwrap_err();
+    }
 }
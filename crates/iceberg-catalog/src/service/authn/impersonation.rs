@@ -0,0 +1,254 @@
+use crate::api::iceberg::v1::{PageToken, PaginationQuery};
+use crate::api::management::v1::user::{User, UserListFilter};
+use crate::request_metadata::RequestMetadata;
+use crate::service::authn::{Principal, UserId};
+use crate::service::authz::{Authorizer, CatalogServerAction};
+use crate::service::event_publisher::{CloudEventsPublisher, UserEventMetadata};
+use crate::service::housekeeping::evict_expired;
+use crate::service::{AuthDetails, Catalog};
+use crate::CONFIG;
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use std::sync::LazyLock;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Header a trusted principal sets to act on behalf of another user.
+pub(crate) const IMPERSONATE_USER_HEADER: &str = "x-lakekeeper-impersonate-user";
+/// Type of the event emitted for every impersonated request.
+const IMPERSONATE_USER_EVENT: &str = "impersonateUser";
+
+/// Users that were impersonated recently. Changes of their name or email take effect
+/// once the entry expired.
+static IMPERSONATED_USER_CACHE: LazyLock<moka::sync::Cache<UserId, User>> = LazyLock::new(|| {
+    moka::sync::Cache::builder()
+        .max_capacity(10_000)
+        .time_to_live(Duration::from_secs(300))
+        .build()
+});
+
+pub(crate) fn evict_expired_impersonated_users() -> u64 {
+    evict_expired(&IMPERSONATED_USER_CACHE)
+}
+
+/// Replaces the authenticated principal by the user in the `x-lakekeeper-impersonate-user`
+/// header if the principal may impersonate other users. Only users known to the catalog
+/// can be impersonated, so that the request carries their name and email.
+///
+/// Each impersonated request is audited with an `impersonateUser` event.
+/// Must run after the authentication middleware and before the user activity is recorded.
+pub(crate) async fn impersonation_middleware_fn<C: Catalog, A: Authorizer + Clone>(
+    State((authorizer, catalog_state, publisher)): State<(A, C::State, CloudEventsPublisher)>,
+    Extension(mut metadata): Extension<RequestMetadata>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(user) = request.headers().get(IMPERSONATE_USER_HEADER) else {
+        return next.run(request).await;
+    };
+    let user = user.to_str().unwrap_or_default().to_string();
+
+    let (principal, user_id) = match authorize_impersonation(&authorizer, &metadata, &user).await {
+        Ok(authorized) => authorized,
+        Err(e) => return e.into_response(),
+    };
+    let user = match load_impersonated_user::<C>(&user_id, catalog_state).await {
+        Ok(user) => user,
+        Err(e) => return IcebergErrorResponse::from(e).into_response(),
+    };
+
+    let operator = principal.user_id().clone();
+    let request_id = metadata.request_id;
+    let method = request.method().clone();
+    let uri = request.uri().clone();
+    metadata.auth_details = AuthDetails::Principal(principal.impersonate(user));
+    request.extensions_mut().insert(metadata);
+
+    let response = next.run(request).await;
+    let status = response.status().as_u16();
+    tracing::info!(
+        %request_id,
+        %operator,
+        impersonated_user = %user_id,
+        %method,
+        %uri,
+        status,
+        "Request sent by {operator} on behalf of user {user_id}"
+    );
+    let _ = publisher
+        .publish_user_event(
+            Uuid::now_v7(),
+            IMPERSONATE_USER_EVENT,
+            serde_json::json!({
+                "user-id": user_id,
+                "impersonated-by": operator,
+                "request-id": request_id,
+                "method": method.as_str(),
+                "path": uri.path(),
+                "status": status,
+            }),
+            UserEventMetadata {
+                user_id,
+                trace_id: request_id,
+            },
+        )
+        .await;
+    response
+}
+
+/// Check that the authenticated principal may impersonate `user` and parse its id.
+///
+/// Principals listed in `impersonation_trusted_principals` and principals granted
+/// [`CatalogServerAction::CanImpersonateUsers`] may impersonate any user.
+async fn authorize_impersonation<A: Authorizer>(
+    authorizer: &A,
+    metadata: &RequestMetadata,
+    user: &str,
+) -> Result<(Principal, UserId), IcebergErrorResponse> {
+    let AuthDetails::Principal(principal) = &metadata.auth_details else {
+        return Err(ErrorModel::unauthorized(
+            "Impersonation requires an authenticated principal",
+            "ImpersonationNotAllowed",
+            None,
+        )
+        .into());
+    };
+    let trusted = CONFIG
+        .impersonation_trusted_principals
+        .contains(principal.user_id());
+    if !trusted
+        && !authorizer
+            .is_allowed_server_action(metadata, &CatalogServerAction::CanImpersonateUsers)
+            .await?
+    {
+        tracing::info!(
+            "Principal {} is not allowed to impersonate other users",
+            principal.user_id()
+        );
+        return Err(ErrorModel::forbidden(
+            format!("Principal is not allowed to set the `{IMPERSONATE_USER_HEADER}` header"),
+            "ImpersonationNotAllowed",
+            None,
+        )
+        .into());
+    }
+    let user_id = parse_impersonated_user(principal, IMPERSONATE_USER_HEADER, user)?;
+    Ok((principal.clone(), user_id))
+}
+
+/// Load the user record of an impersonated user.
+///
+/// # Errors
+/// Fails with `403` if the user is not known to the catalog.
+async fn load_impersonated_user<C: Catalog>(
+    user_id: &UserId,
+    catalog_state: C::State,
+) -> Result<User, ErrorModel> {
+    if let Some(user) = IMPERSONATED_USER_CACHE.get(user_id) {
+        return Ok(user);
+    }
+    let user = C::list_user(
+        Some(vec![user_id.clone()]),
+        None,
+        UserListFilter::default(),
+        PaginationQuery {
+            page_size: Some(1),
+            page_token: PageToken::NotSpecified,
+        },
+        catalog_state,
+    )
+    .await
+    .map_err(|e| e.error)?
+    .users
+    .into_iter()
+    .next()
+    .ok_or_else(|| {
+        ErrorModel::forbidden(
+            format!("User {user_id} is not known to the catalog and cannot be impersonated"),
+            "ImpersonatedUserNotFound",
+            None,
+        )
+    })?;
+    IMPERSONATED_USER_CACHE.insert(user_id.clone(), user.clone());
+    Ok(user)
+}
+
+/// Parse the user id sent in an impersonation `header`.
+pub(super) fn parse_impersonated_user(
+    principal: &Principal,
+    header: &str,
+    user: &str,
+) -> Result<UserId, ErrorModel> {
+    if user.is_empty() {
+        return Err(ErrorModel::bad_request(
+            format!("`{header}` header must be a non-empty user id"),
+            "InvalidImpersonationHeader",
+            None,
+        ));
+    }
+    // Users may be passed with or without provider prefix. Without prefix, the
+    // provider and project of the acting principal are assumed.
+    if user.contains('~') {
+        UserId::try_from(user.to_string())
+    } else {
+        match principal.user_id() {
+            UserId::OIDC(_) => UserId::oidc(user),
+            UserId::Kubernetes(_) => UserId::kubernetes(user),
+        }
+        .and_then(|user_id| match principal.project_id {
+            Some(project_id) => UserId::for_project(project_id, &user_id),
+            None => Ok(user_id),
+        })
+    }
+    .map_err(|e| {
+        ErrorModel::bad_request(
+            format!("Invalid `{header}` header: {}", e.error.message),
+            "InvalidImpersonationHeader",
+            None,
+        )
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::service::authz::AllowAllAuthorizer;
+
+    #[tokio::test]
+    async fn test_impersonation_requires_authenticated_principal() {
+        let metadata = RequestMetadata::new(AuthDetails::Unauthenticated);
+        let err = authorize_impersonation(&AllowAllAuthorizer, &metadata, "end-user")
+            .await
+            .unwrap_err();
+        assert_eq!(err.error.code, 401);
+
+        let metadata = RequestMetadata::new(AuthDetails::Principal(Principal::random_human(
+            UserId::OIDC("operator".to_string()),
+        )));
+        let (principal, user_id) =
+            authorize_impersonation(&AllowAllAuthorizer, &metadata, "end-user")
+                .await
+                .unwrap();
+        assert_eq!(principal.user_id(), &UserId::OIDC("operator".to_string()));
+        assert_eq!(user_id, UserId::OIDC("end-user".to_string()));
+    }
+
+    #[test]
+    fn test_parse_impersonated_user() {
+        let principal = Principal::random_human(UserId::OIDC("operator".to_string()));
+        assert_eq!(
+            parse_impersonated_user(&principal, "x-test", "end-user").unwrap(),
+            UserId::OIDC("end-user".to_string())
+        );
+        assert_eq!(
+            parse_impersonated_user(&principal, "x-test", "kubernetes~end-user").unwrap(),
+            UserId::Kubernetes("end-user".to_string())
+        );
+        let err = parse_impersonated_user(&principal, "x-test", "").unwrap_err();
+        assert_eq!(err.code, 400);
+        assert!(err.message.contains("x-test"));
+    }
+}
@@ -5,11 +5,19 @@ use k8s_openapi::api::authentication::v1::TokenReviewStatus;
 use serde::Deserialize;
 use std::fmt::Debug;
 
+mod activity;
 mod identities;
+mod impersonation;
+mod project_scope;
 mod verification;
 
+pub(crate) use activity::{
+    evict_expired_user_activity, invalidate_user_activity, user_activity_middleware_fn,
+};
 pub use identities::{Principal, UserId};
-pub(crate) use verification::{auth_middleware_fn, VerifierChain};
+pub(crate) use impersonation::{evict_expired_impersonated_users, impersonation_middleware_fn};
+pub(crate) use project_scope::{evict_expired_owning_projects, project_scope_middleware_fn};
+pub(crate) use verification::{auth_middleware_fn, VerifierChain, PROJECT_ID_HEADER};
 pub use verification::{IdpVerifier, K8sVerifier};
 
 #[derive(Debug, Clone)]
@@ -40,6 +48,8 @@ impl AuthDetails {
                     email: None,
                     application_id: Some(uid),
                     idtyp: Some("app".to_string()),
+                    impersonated_by: None,
+                    project_id: None,
                 }));
             }
         };
@@ -100,6 +110,8 @@ impl AuthDetails {
             idtyp: claims
                 .idtyp
                 .or(claims.family_name.map(|_| "user".to_string())),
+            impersonated_by: None,
+            project_id: None,
         };
 
         Ok(Self::Principal(principal))
@@ -113,9 +125,14 @@ impl AuthDetails {
         }
     }
 
+    /// Project of the identity provider that authenticated the principal,
+    /// if it is a project-scoped provider.
     #[must_use]
     pub fn project_id(&self) -> Option<ProjectIdent> {
-        None
+        match self {
+            Self::Principal(principal) => principal.project_id,
+            Self::Unauthenticated => None,
+        }
     }
 
     #[must_use]
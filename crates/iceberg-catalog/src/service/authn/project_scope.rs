@@ -0,0 +1,157 @@
+use crate::api::iceberg::v1::{PageToken, PaginationQuery};
+use crate::api::management::v1::role::RoleListFilter;
+use crate::request_metadata::RequestMetadata;
+use crate::service::housekeeping::evict_expired;
+use crate::service::{Catalog, DomainId, ProjectIdent, RoleId, Transaction};
+use crate::WarehouseIdent;
+use axum::extract::{MatchedPath, RawPathParams, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Path parameter entity that belongs to exactly one project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum_macros::Display)]
+enum ProjectEntity {
+    #[strum(to_string = "Warehouse {0}")]
+    Warehouse(WarehouseIdent),
+    #[strum(to_string = "Role {0}")]
+    Role(RoleId),
+    #[strum(to_string = "Domain {0}")]
+    Domain(DomainId),
+}
+
+/// Project of warehouses, roles and domains. None of them move between projects,
+/// entries only expire to release deleted entities.
+static OWNING_PROJECT_CACHE: LazyLock<moka::sync::Cache<ProjectEntity, ProjectIdent>> =
+    LazyLock::new(|| {
+        moka::sync::Cache::builder()
+            .max_capacity(10_000)
+            .time_to_live(Duration::from_secs(3600))
+            .build()
+    });
+
+pub(crate) fn evict_expired_owning_projects() -> u64 {
+    evict_expired(&OWNING_PROJECT_CACHE)
+}
+
+/// Rejects requests of principals authenticated by the identity provider of a project
+/// if the project, warehouse, catalog prefix, role or domain in the path belongs to
+/// another project.
+///
+/// Namespaces, tables and views are only addressed within a warehouse or domain in the
+/// path, and the catalog only finds them within it, so their project is that of the
+/// warehouse or domain. Users are not bound to a project.
+/// Must be applied as route layer so that the path parameters are decoded.
+pub(crate) async fn project_scope_middleware_fn<C: Catalog>(
+    State(catalog_state): State<C::State>,
+    Extension(metadata): Extension<RequestMetadata>,
+    matched_path: MatchedPath,
+    params: RawPathParams,
+    request: Request,
+    next: Next,
+) -> Response {
+    if metadata.auth_details.project_id().is_none() {
+        return next.run(request).await;
+    }
+
+    // `{id}` is the id of a role on role routes and of a user on user routes.
+    let is_role_path = matched_path.as_str().contains("/role/{id}");
+    for (key, value) in &params {
+        // Invalid ids are rejected by the handlers.
+        let Ok(id) = uuid::Uuid::parse_str(value) else {
+            continue;
+        };
+        let entity = match key {
+            "project_id" => {
+                if let Err(e) = metadata.require_project(ProjectIdent::from(id)) {
+                    return e.into_response();
+                }
+                continue;
+            }
+            "prefix" | "warehouse_id" => ProjectEntity::Warehouse(WarehouseIdent::from(id)),
+            "id" | "member_id" if is_role_path => ProjectEntity::Role(RoleId::new(id)),
+            "domain_id" => ProjectEntity::Domain(DomainId::new(id)),
+            _ => continue,
+        };
+        if let Err(e) = require_entity_project::<C>(&metadata, entity, catalog_state.clone()).await
+        {
+            return e.into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Ensure that `entity` belongs to the project of the principal, if its identity
+/// provider is bound to a project.
+///
+/// # Errors
+/// - the entity belongs to another project
+/// - the project of the entity cannot be loaded
+async fn require_entity_project<C: Catalog>(
+    metadata: &RequestMetadata,
+    entity: ProjectEntity,
+    catalog_state: C::State,
+) -> Result<(), IcebergErrorResponse> {
+    if metadata.auth_details.project_id().is_none() {
+        return Ok(());
+    }
+
+    let project_id = if let Some(project_id) = OWNING_PROJECT_CACHE.get(&entity) {
+        project_id
+    } else {
+        // Unknown entities are rejected by the handlers.
+        let Some(project_id) = load_owning_project::<C>(entity, catalog_state).await? else {
+            return Ok(());
+        };
+        OWNING_PROJECT_CACHE.insert(entity, project_id);
+        project_id
+    };
+
+    metadata.require_project(project_id).map_err(|_| {
+        ErrorModel::forbidden(
+            format!("{entity} belongs to another project"),
+            "ProjectNotAccessible",
+            None,
+        )
+        .into()
+    })
+}
+
+/// Project of `entity`, `None` if it does not exist.
+async fn load_owning_project<C: Catalog>(
+    entity: ProjectEntity,
+    catalog_state: C::State,
+) -> Result<Option<ProjectIdent>, IcebergErrorResponse> {
+    let project_id = match entity {
+        ProjectEntity::Warehouse(warehouse_id) => {
+            let mut t = C::Transaction::begin_read(catalog_state).await?;
+            let warehouse = C::get_warehouse(warehouse_id, t.transaction()).await?;
+            t.commit().await?;
+            warehouse.map(|w| w.project_id)
+        }
+        ProjectEntity::Role(role_id) => C::list_roles(
+            None,
+            Some(vec![role_id]),
+            None,
+            RoleListFilter::default(),
+            PaginationQuery {
+                page_size: Some(1),
+                page_token: PageToken::NotSpecified,
+            },
+            catalog_state,
+        )
+        .await?
+        .roles
+        .into_iter()
+        .next()
+        .map(|role| role.project_id),
+        ProjectEntity::Domain(domain_id) => C::get_domain(domain_id, catalog_state)
+            .await?
+            .map(|domain| domain.project_id),
+    };
+    Ok(project_id)
+}
@@ -1,7 +1,8 @@
 use crate::api;
+use crate::config::ProjectOpenIdProvider;
 use crate::service::authn::verification::Verifier;
 use crate::service::authn::Claims;
-use crate::service::AuthDetails;
+use crate::service::{AuthDetails, ProjectIdent};
 use anyhow::Context;
 use http::StatusCode;
 use iceberg_ext::catalog::rest::ErrorModel;
@@ -18,8 +19,17 @@ use url::Url;
 #[async_trait::async_trait]
 impl Verifier for IdpVerifier {
     async fn decode(&self, token: &str) -> api::Result<AuthDetails, ErrorModel> {
-        let claims = IdpVerifier::decode::<Claims>(self, token).await?;
-        AuthDetails::try_from_jwt_claims(claims).map_err(|e| e.error)
+        let claims = IdpVerifier::decode::<RawClaims>(self, token)
+            .await?
+            .into_claims(&self.claim_mapping)?;
+        let details = AuthDetails::try_from_jwt_claims(claims).map_err(|e| e.error)?;
+        match (details, self.project_id) {
+            (AuthDetails::Principal(principal), Some(project_id)) => principal
+                .scope_to_project(project_id)
+                .map(AuthDetails::Principal)
+                .map_err(|e| e.error),
+            (details, _) => Ok(details),
+        }
     }
 
     fn typ(&self) -> &str {
@@ -37,6 +47,55 @@ pub struct IdpVerifier {
     audience: Option<Vec<String>>,
     /// This scope must be present in the scopes of the token.
     scope: Option<String>,
+    claim_mapping: ClaimMapping,
+    /// Set for providers that only authenticate requests targeting this project.
+    project_id: Option<ProjectIdent>,
+}
+
+/// Claims to read instead of the default ones.
+#[derive(Debug, Clone, Default)]
+struct ClaimMapping {
+    subject: Option<String>,
+    name: Option<String>,
+}
+
+/// Claims of a token before the [`ClaimMapping`] is applied.
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct RawClaims(serde_json::Map<String, serde_json::Value>);
+
+impl RawClaims {
+    fn into_claims(mut self, mapping: &ClaimMapping) -> api::Result<Claims, ErrorModel> {
+        if let Some(claim) = &mapping.subject {
+            let subject = match self.0.get(claim) {
+                Some(serde_json::Value::String(s)) => s.clone(),
+                Some(serde_json::Value::Number(n)) => n.to_string(),
+                _ => {
+                    return Err(ErrorModel::unauthorized(
+                        format!("Token does not contain the claim `{claim}` used as user id"),
+                        "UnauthorizedError",
+                        None,
+                    ))
+                }
+            };
+            // `oid` takes precedence over `sub` and must not shadow the mapped claim
+            self.0.remove("oid");
+            self.0.insert("sub".to_string(), subject.into());
+        }
+        if let Some(claim) = &mapping.name {
+            if let Some(name) = self.0.get(claim).cloned() {
+                self.0.insert("name".to_string(), name);
+            }
+        }
+
+        serde_json::from_value(serde_json::Value::Object(self.0)).map_err(|e| {
+            ErrorModel::unauthorized(
+                "Failed to parse token claims.",
+                "UnauthorizedError",
+                Some(Box::new(e)),
+            )
+        })
+    }
 }
 
 trait Scope {
@@ -55,6 +114,12 @@ impl Scope for Claims {
     }
 }
 
+impl Scope for RawClaims {
+    fn scope(&self) -> Option<&str> {
+        self.0.get("scope").and_then(serde_json::Value::as_str)
+    }
+}
+
 impl IdpVerifier {
     const WELL_KNOWN_CONFIG: &'static str = ".well-known/openid-configuration";
     /// Create a new verifier with the given openid configuration url and audience.
@@ -95,9 +160,34 @@ impl IdpVerifier {
             main_issuer,
             audience,
             scope,
+            claim_mapping: ClaimMapping::default(),
+            project_id: None,
         })
     }
 
+    /// Create a verifier for a provider that only authenticates requests targeting `project_id`.
+    ///
+    /// # Errors
+    /// Same as [`IdpVerifier::new`].
+    pub async fn for_project(
+        project_id: ProjectIdent,
+        provider: &ProjectOpenIdProvider,
+    ) -> anyhow::Result<Self> {
+        let mut verifier = Self::new(
+            provider.provider_uri.clone(),
+            provider.audience.clone(),
+            provider.additional_issuers.clone(),
+            provider.scope.clone(),
+        )
+        .await?;
+        verifier.claim_mapping = ClaimMapping {
+            subject: provider.subject_claim.clone(),
+            name: provider.name_claim.clone(),
+        };
+        verifier.project_id = Some(project_id);
+        Ok(verifier)
+    }
+
     // this function is mostly lifted out of jwks_client_rs which is incompatible with azure jwks.
     async fn decode<O: DeserializeOwned + Scope>(&self, token: &str) -> api::Result<O, ErrorModel> {
         let header: Header = jsonwebtoken::decode_header(token).map_err(|e| {
@@ -234,3 +324,51 @@ pub(crate) struct WellKnownConfig {
     pub jwks_uri: Url,
     pub issuer: String,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::service::authn::UserId;
+
+    fn raw_claims() -> RawClaims {
+        serde_json::from_value(serde_json::json!({
+            "iss": "https://idp.example.com/realms/tenant-a",
+            "sub": "f621fc83-4ec9-4bf8-bc8d-1234567890ab",
+            "oid": "a1b2c3d4-4ec9-4bf8-bc8d-1234567890ab",
+            "employee_id": 4711,
+            "display": "Peter Cold",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_claim_mapping() {
+        let mapping = ClaimMapping {
+            subject: Some("employee_id".to_string()),
+            name: Some("display".to_string()),
+        };
+        let claims = raw_claims().into_claims(&mapping).unwrap();
+        let AuthDetails::Principal(principal) = AuthDetails::try_from_jwt_claims(claims).unwrap()
+        else {
+            panic!("Expected principal");
+        };
+        assert_eq!(principal.user_id(), &UserId::OIDC("4711".to_string()));
+        assert_eq!(principal.name(), Some("Peter Cold"));
+
+        let claims = raw_claims().into_claims(&ClaimMapping::default()).unwrap();
+        let AuthDetails::Principal(principal) = AuthDetails::try_from_jwt_claims(claims).unwrap()
+        else {
+            panic!("Expected principal");
+        };
+        assert_eq!(
+            principal.user_id(),
+            &UserId::OIDC("a1b2c3d4-4ec9-4bf8-bc8d-1234567890ab".to_string())
+        );
+
+        let missing = ClaimMapping {
+            subject: Some("tenant_user".to_string()),
+            name: None,
+        };
+        assert_eq!(raw_claims().into_claims(&missing).unwrap_err().code, 401);
+    }
+}
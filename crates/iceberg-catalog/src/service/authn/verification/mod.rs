@@ -1,6 +1,6 @@
 use crate::api;
 use crate::request_metadata::RequestMetadata;
-use crate::service::AuthDetails;
+use crate::service::{AuthDetails, ProjectIdent};
 use axum::extract::{Request, State};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
@@ -9,45 +9,60 @@ use axum_extra::headers::authorization::Bearer;
 use axum_extra::headers::Authorization;
 use axum_extra::TypedHeader;
 use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
 mod idp;
 mod kubernetes;
 
+/// Header selecting the project a request targets. Tokens of project-scoped identity
+/// providers are only accepted if it is set to their project.
+pub(crate) const PROJECT_ID_HEADER: &str = "x-project-id";
+
 pub use idp::IdpVerifier;
 pub use kubernetes::K8sVerifier;
 
-/// `VerifierChain` chains idp and k8s verifier.
+/// `VerifierChain` chains project-scoped idp, idp and k8s verifier.
 #[derive(Clone, Debug)]
 pub(crate) struct VerifierChain {
     idp_verifier: Option<IdpVerifier>,
     k8s_verifier: Option<K8sVerifier>,
+    project_verifiers: HashMap<ProjectIdent, IdpVerifier>,
 }
 
 impl VerifierChain {
     /// Create a new verifier chain with the idp and k8s verifier
     ///
     /// You must provide at least one verifier. The authentication middleware will first try to
-    /// decode the token using the idp provider and then the k8s provider.
+    /// decode the token using the provider of the targeted project, then the idp provider
+    /// and then the k8s provider.
     ///
     /// # Errors
-    /// - If neither `idp_verifier` nor `k8s_verifier` is provided
+    /// - If no verifier is provided
     pub(crate) fn try_new(
         idp_verifier: Option<IdpVerifier>,
         k8s_verifier: Option<K8sVerifier>,
+        project_verifiers: HashMap<ProjectIdent, IdpVerifier>,
     ) -> anyhow::Result<Self> {
-        if idp_verifier.is_none() && k8s_verifier.is_none() {
+        if idp_verifier.is_none() && k8s_verifier.is_none() && project_verifiers.is_empty() {
             return Err(anyhow::anyhow!("At least one verifier must be provided"));
         }
         Ok(Self {
             idp_verifier,
             k8s_verifier,
+            project_verifiers,
         })
     }
 
-    fn into_vec(self) -> Vec<Arc<dyn Verifier + Send + Sync>> {
+    fn into_vec(
+        mut self,
+        project_id: Option<ProjectIdent>,
+    ) -> Vec<Arc<dyn Verifier + Send + Sync>> {
         let mut verifiers = vec![];
+        if let Some(verifier) = project_id.and_then(|p| self.project_verifiers.remove(&p)) {
+            verifiers.push(Arc::new(verifier) as _);
+        }
         if let Some(verifier) = self.idp_verifier {
             verifiers.push(Arc::new(verifier) as _);
         }
@@ -75,7 +90,14 @@ pub(crate) async fn auth_middleware_fn(
         .into_response();
     };
 
-    for verifier in verifiers.into_vec() {
+    let project_id = request
+        .headers()
+        .get(PROJECT_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| uuid::Uuid::parse_str(value.trim()).ok())
+        .map(ProjectIdent::from);
+
+    for verifier in verifiers.into_vec(project_id) {
         let Ok(details) = verifier.decode(authorization.token()).await.map_err(|e| {
             tracing::debug!(
                 ?e,
@@ -104,3 +126,33 @@ trait Verifier: Debug {
     async fn decode(&self, token: &str) -> api::Result<AuthDetails, ErrorModel>;
     fn typ(&self) -> &str;
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::service::authn::impersonation::parse_impersonated_user;
+    use crate::service::authn::{Principal, UserId};
+
+    #[test]
+    fn test_project_principal() {
+        let project_id = ProjectIdent::from(uuid::Uuid::now_v7());
+        let principal = Principal::random_human(UserId::OIDC("operator".to_string()))
+            .scope_to_project(project_id)
+            .unwrap();
+        assert_eq!(
+            principal.user_id(),
+            &UserId::OIDC(format!("{project_id}_operator"))
+        );
+        assert_eq!(
+            parse_impersonated_user(&principal, "x-test", "end-user").unwrap(),
+            UserId::OIDC(format!("{project_id}_end-user"))
+        );
+
+        let metadata = RequestMetadata::new(AuthDetails::Principal(principal));
+        metadata.require_project(project_id).unwrap();
+        let err = metadata
+            .require_project(ProjectIdent::from(uuid::Uuid::now_v7()))
+            .unwrap_err();
+        assert_eq!(err.error.code, 403);
+    }
+}
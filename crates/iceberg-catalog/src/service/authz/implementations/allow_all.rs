@@ -3,14 +3,15 @@ use crate::api::ApiContext;
 use crate::request_metadata::RequestMetadata;
 use crate::service::authn::UserId;
 use crate::service::authz::{
-    Authorizer, CatalogNamespaceAction, CatalogProjectAction, CatalogRoleAction,
-    CatalogServerAction, CatalogTableAction, CatalogUserAction, CatalogViewAction,
-    CatalogWarehouseAction, ListProjectsResponse, NamespaceParent,
+    nested_roles_not_supported, Authorizer, CatalogDomainAction, CatalogNamespaceAction,
+    CatalogProjectAction, CatalogRoleAction, CatalogServerAction, CatalogTableAction,
+    CatalogUserAction, CatalogViewAction, CatalogWarehouseAction, ListProjectsResponse,
+    NamespaceParent,
 };
 use crate::service::health::{Health, HealthExt};
 use crate::service::{
-    Catalog, NamespaceIdentUuid, ProjectIdent, RoleId, SecretStore, State, TableIdentUuid,
-    ViewIdentUuid, WarehouseIdent,
+    Catalog, DomainId, NamespaceIdentUuid, ProjectIdent, RoleId, SecretStore, State,
+    TableIdentUuid, ViewIdentUuid, WarehouseIdent,
 };
 use async_trait::async_trait;
 use axum::Router;
@@ -77,6 +78,15 @@ impl Authorizer for AllowAllAuthorizer {
         Ok(true)
     }
 
+    async fn is_allowed_domain_action(
+        &self,
+        _metadata: &RequestMetadata,
+        _domain_id: DomainId,
+        _action: &CatalogDomainAction,
+    ) -> Result<bool> {
+        Ok(true)
+    }
+
     async fn is_allowed_server_action(
         &self,
         _metadata: &RequestMetadata,
@@ -147,6 +157,45 @@ impl Authorizer for AllowAllAuthorizer {
         Ok(())
     }
 
+    async fn add_role_member(
+        &self,
+        _metadata: &RequestMetadata,
+        _role_id: RoleId,
+        _member_role_id: RoleId,
+    ) -> Result<()> {
+        Err(nested_roles_not_supported())
+    }
+
+    async fn remove_role_member(
+        &self,
+        _metadata: &RequestMetadata,
+        _role_id: RoleId,
+        _member_role_id: RoleId,
+    ) -> Result<()> {
+        Err(nested_roles_not_supported())
+    }
+
+    async fn list_role_members(&self, _role_id: RoleId) -> Result<Option<Vec<RoleId>>> {
+        Ok(None)
+    }
+
+    async fn list_role_assignee_users(&self, _role_id: RoleId) -> Result<Option<Vec<UserId>>> {
+        Ok(None)
+    }
+
+    async fn create_domain(
+        &self,
+        _metadata: &RequestMetadata,
+        _domain_id: DomainId,
+        _parent_project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_domain(&self, _metadata: &RequestMetadata, _domain_id: DomainId) -> Result<()> {
+        Ok(())
+    }
+
     async fn create_project(
         &self,
         _metadata: &RequestMetadata,
@@ -197,6 +246,16 @@ impl Authorizer for AllowAllAuthorizer {
         Ok(())
     }
 
+    async fn move_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _previous_parent: NamespaceParent,
+        _new_parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     async fn create_table(
         &self,
         _metadata: &RequestMetadata,
@@ -10,6 +10,8 @@ pub(super) mod allow_all;
 
 pub mod openfga;
 
+pub mod webhook;
+
 /// Get the default authorizer from the configuration
 ///
 /// # Errors
@@ -20,6 +22,7 @@ pub async fn get_default_authorizer_from_config() -> Result<Authorizers, ErrorMo
     match &CONFIG.authz_backend {
         AuthZBackend::AllowAll => Ok(allow_all::AllowAllAuthorizer.into()),
         AuthZBackend::OpenFGA => Ok(openfga::new_authorizer_from_config().await?),
+        AuthZBackend::Webhook => Ok(webhook::new_authorizer_from_config()?.into()),
     }
 }
 
@@ -30,7 +33,7 @@ pub async fn get_default_authorizer_from_config() -> Result<Authorizers, ErrorMo
 /// Authorizer implementation
 pub async fn migrate_default_authorizer() -> std::result::Result<(), ErrorModel> {
     match &CONFIG.authz_backend {
-        AuthZBackend::AllowAll => Ok(()),
+        AuthZBackend::AllowAll | AuthZBackend::Webhook => Ok(()),
         AuthZBackend::OpenFGA => {
             let mut client = openfga::new_client_from_config().await?;
             let store_name = None;
@@ -49,6 +52,7 @@ pub enum FgaType {
     Role,
     Server,
     Project,
+    Domain,
     Warehouse,
     Namespace,
     Table,
@@ -61,6 +65,7 @@ pub enum FgaType {
 pub enum Authorizers {
     AllowAll(allow_all::AllowAllAuthorizer),
     OpenFGA(openfga::OpenFGAAuthorizer),
+    Webhook(webhook::WebhookAuthorizer),
 }
 
 impl From<allow_all::AllowAllAuthorizer> for Authorizers {
@@ -69,12 +74,19 @@ impl From<allow_all::AllowAllAuthorizer> for Authorizers {
     }
 }
 
+impl From<webhook::WebhookAuthorizer> for Authorizers {
+    fn from(authorizer: webhook::WebhookAuthorizer) -> Self {
+        Self::Webhook(authorizer)
+    }
+}
+
 #[async_trait::async_trait]
 impl HealthExt for Authorizers {
     async fn health(&self) -> Vec<Health> {
         match self {
             Self::AllowAll(authorizer) => authorizer.health().await,
             Self::OpenFGA(authorizer) => authorizer.health().await,
+            Self::Webhook(authorizer) => authorizer.health().await,
         }
     }
 
@@ -82,6 +94,7 @@ impl HealthExt for Authorizers {
         match self {
             Self::AllowAll(authorizer) => authorizer.update_health().await,
             Self::OpenFGA(authorizer) => authorizer.update_health().await,
+            Self::Webhook(authorizer) => authorizer.update_health().await,
         }
     }
 }
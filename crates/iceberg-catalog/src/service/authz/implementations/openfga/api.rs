@@ -1,12 +1,14 @@
 use super::check::{__path_check, check};
 use super::relations::{
+    APIDomainAction as DomainAction, APIDomainRelation as DomainRelation,
     APINamespaceAction as NamespaceAction, APINamespaceRelation as NamespaceRelation,
     APIProjectAction as ProjectAction, APIProjectRelation as ProjectRelation,
     APIRoleAction as RoleAction, APIRoleRelation as RoleRelation, APIServerAction as ServerAction,
     APIServerRelation as ServerRelation, APITableAction as TableAction,
     APITableRelation as TableRelation, APIViewAction as ViewAction,
     APIViewRelation as ViewRelation, APIWarehouseAction as WarehouseAction,
-    APIWarehouseRelation as WarehouseRelation, Assignment, GrantableRelation, NamespaceAssignment,
+    APIWarehouseRelation as WarehouseRelation, Assignment, DomainAssignment,
+    DomainRelation as AllDomainRelations, GrantableRelation, NamespaceAssignment,
     NamespaceRelation as AllNamespaceRelations, ProjectAssignment,
     ProjectRelation as AllProjectRelations, ReducedRelation, RoleAssignment,
     RoleRelation as AllRoleRelations, ServerAssignment, ServerRelation as AllServerAction,
@@ -15,6 +17,7 @@ use super::relations::{
     WarehouseRelation as AllWarehouseRelation,
 };
 use super::OPENFGA_SERVER;
+use crate::api::management::v1::role::require_project_id_or_else;
 use crate::api::ApiContext;
 use crate::request_metadata::RequestMetadata;
 use crate::service::authz::implementations::openfga::entities::OpenFgaEntity;
@@ -23,10 +26,10 @@ use crate::service::authz::implementations::openfga::{
     OpenFGAAuthorizer, OpenFGAError, OpenFGAResult,
 };
 use crate::service::{
-    Actor, Catalog, NamespaceIdentUuid, Result, RoleId, SecretStore, State, TableIdentUuid,
-    ViewIdentUuid,
+    Actor, Catalog, DomainId, NamespaceIdentUuid, Result, RoleId, SecretStore, State,
+    TableIdentUuid, ViewIdentUuid,
 };
-use crate::{ProjectIdent, WarehouseIdent, DEFAULT_PROJECT_ID};
+use crate::{ProjectIdent, WarehouseIdent};
 use axum::extract::{Path, Query, State as AxumState};
 use axum::routing::{get, post};
 use axum::{Extension, Json, Router};
@@ -71,6 +74,12 @@ struct GetProjectAccessResponse {
     allowed_actions: Vec<ProjectAction>,
 }
 
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct GetDomainAccessResponse {
+    allowed_actions: Vec<DomainAction>,
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct GetWarehouseAccessResponse {
@@ -142,6 +151,21 @@ struct GetProjectAssignmentsResponse {
     project_id: ProjectIdent,
 }
 
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GetDomainAssignmentsQuery {
+    /// Relations to be loaded. If not specified, all relations are returned.
+    #[serde(default)]
+    #[param(nullable = false, required = false)]
+    relations: Option<Vec<DomainRelation>>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct GetDomainAssignmentsResponse {
+    assignments: Vec<DomainAssignment>,
+}
+
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 #[serde(rename_all = "camelCase")]
 pub(super) struct GetWarehouseAssignmentsQuery {
@@ -202,6 +226,42 @@ struct GetViewAssignmentsResponse {
     assignments: Vec<ViewAssignment>,
 }
 
+/// A relationship tuple as stored in `OpenFGA`.
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct Relationship {
+    /// User of the tuple, i.e. `user:<id>`, `role:<id>#assignee` or a parent object.
+    user: String,
+    relation: String,
+    object: String,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct GetRelationshipsResponse {
+    /// All tuples stored for the object, including those linking it to its parent.
+    relationships: Vec<Relationship>,
+}
+
+/// A relationship of the object given in the path.
+#[derive(Debug, Clone, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct RelationshipKey {
+    /// User of the tuple, i.e. `user:<id>` or `role:<id>#assignee`.
+    user: String,
+    /// Relation that can be assigned on the object, i.e. `ownership` or `select`.
+    relation: String,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct UpdateRelationshipsRequest {
+    #[serde(default)]
+    writes: Vec<RelationshipKey>,
+    #[serde(default)]
+    deletes: Vec<RelationshipKey>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct UpdateServerAssignmentsRequest {
@@ -220,6 +280,15 @@ struct UpdateProjectAssignmentsRequest {
     deletes: Vec<ProjectAssignment>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+struct UpdateDomainAssignmentsRequest {
+    #[serde(default)]
+    writes: Vec<DomainAssignment>,
+    #[serde(default)]
+    deletes: Vec<DomainAssignment>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 struct UpdateWarehouseAssignmentsRequest {
@@ -368,11 +437,8 @@ async fn get_project_access<C: Catalog, S: SecretStore>(
     Query(query): Query<GetAccessQuery>,
 ) -> Result<(StatusCode, Json<GetProjectAccessResponse>)> {
     let authorizer = api_context.v1_state.authz;
-    let project_id = metadata
-        .auth_details
-        .project_id()
-        .or(*DEFAULT_PROJECT_ID)
-        .ok_or(OpenFGAError::NoProjectId)?;
+    let project_id =
+        require_project_id_or_else(None, &metadata, || OpenFGAError::NoProjectId.into())?;
     let relations = get_allowed_actions(
         authorizer,
         metadata.actor(),
@@ -640,6 +706,42 @@ async fn get_namespace_access_by_id<C: Catalog, S: SecretStore>(
     ))
 }
 
+/// Get my access to a domain
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/domain/{domain_id}/access",
+    params(
+        GetAccessQuery,
+        ("domain_id" = uuid::Uuid, Path, description = "Domain ID")
+    ),
+    responses(
+            (status = 200, body = GetDomainAccessResponse),
+    )
+)]
+async fn get_domain_access_by_id<C: Catalog, S: SecretStore>(
+    Path(domain_id): Path<DomainId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<GetAccessQuery>,
+) -> Result<(StatusCode, Json<GetDomainAccessResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let relations = get_allowed_actions(
+        authorizer,
+        metadata.actor(),
+        &domain_id.to_openfga(),
+        query.principal.as_ref(),
+    )
+    .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(GetDomainAccessResponse {
+            allowed_actions: relations,
+        }),
+    ))
+}
+
 /// Get my access to a table
 #[utoipa::path(
     get,
@@ -747,6 +849,41 @@ async fn get_role_assignments_by_id<C: Catalog, S: SecretStore>(
     ))
 }
 
+/// Get user and role assignments of a domain
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/domain/{domain_id}/assignments",
+    params(
+        GetDomainAssignmentsQuery,
+        ("domain_id" = uuid::Uuid, Path, description = "Domain ID"),
+    ),
+    responses(
+            (status = 200, body = GetDomainAssignmentsResponse),
+    )
+)]
+async fn get_domain_assignments_by_id<C: Catalog, S: SecretStore>(
+    Path(domain_id): Path<DomainId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Query(query): Query<GetDomainAssignmentsQuery>,
+) -> Result<(StatusCode, Json<GetDomainAssignmentsResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    authorizer
+        .require_action(
+            &metadata,
+            AllDomainRelations::CanReadAssignments,
+            &domain_id.to_openfga(),
+        )
+        .await?;
+    let assignments = get_relations(authorizer, query.relations, &domain_id.to_openfga()).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(GetDomainAssignmentsResponse { assignments }),
+    ))
+}
+
 /// Get user and role assignments of the server
 #[utoipa::path(
     get,
@@ -794,11 +931,8 @@ async fn get_project_assignments<C: Catalog, S: SecretStore>(
     Query(query): Query<GetProjectAssignmentsQuery>,
 ) -> Result<(StatusCode, Json<GetProjectAssignmentsResponse>)> {
     let authorizer = api_context.v1_state.authz;
-    let project_id = metadata
-        .auth_details
-        .project_id()
-        .or(*DEFAULT_PROJECT_ID)
-        .ok_or(OpenFGAError::NoProjectId)?;
+    let project_id =
+        require_project_id_or_else(None, &metadata, || OpenFGAError::NoProjectId.into())?;
     authorizer
         .require_action(
             &metadata,
@@ -987,6 +1121,278 @@ async fn get_view_assignments_by_id<C: Catalog, S: SecretStore>(
     ))
 }
 
+/// Get the raw relationship tuples of a warehouse
+///
+/// Unlike the assignments endpoint, this also returns structural tuples
+/// and tuples that do not match the current authorization model.
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/relationships",
+    params(
+        ("warehouse_id" = uuid::Uuid, Path, description = "Warehouse ID"),
+    ),
+    responses(
+            (status = 200, body = GetRelationshipsResponse),
+    )
+)]
+async fn get_warehouse_relationships_by_id<C: Catalog, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseIdent>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<GetRelationshipsResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let object = warehouse_id.to_openfga();
+    authorizer
+        .require_action(&metadata, AllWarehouseRelation::CanReadAssignments, &object)
+        .await?;
+    let relationships = get_relationships(authorizer, &object).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(GetRelationshipsResponse { relationships }),
+    ))
+}
+
+/// Get the raw relationship tuples of a namespace
+///
+/// Unlike the assignments endpoint, this also returns structural tuples
+/// and tuples that do not match the current authorization model.
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/namespace/{namespace_id}/relationships",
+    params(
+        ("namespace_id" = uuid::Uuid, Path, description = "Namespace ID"),
+    ),
+    responses(
+            (status = 200, body = GetRelationshipsResponse),
+    )
+)]
+async fn get_namespace_relationships_by_id<C: Catalog, S: SecretStore>(
+    Path(namespace_id): Path<NamespaceIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<GetRelationshipsResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let object = namespace_id.to_openfga();
+    authorizer
+        .require_action(
+            &metadata,
+            AllNamespaceRelations::CanReadAssignments,
+            &object,
+        )
+        .await?;
+    let relationships = get_relationships(authorizer, &object).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(GetRelationshipsResponse { relationships }),
+    ))
+}
+
+/// Get the raw relationship tuples of a table
+///
+/// Unlike the assignments endpoint, this also returns structural tuples
+/// and tuples that do not match the current authorization model.
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/table/{table_id}/relationships",
+    params(
+        ("table_id" = uuid::Uuid, Path, description = "Table ID"),
+    ),
+    responses(
+            (status = 200, body = GetRelationshipsResponse),
+    )
+)]
+async fn get_table_relationships_by_id<C: Catalog, S: SecretStore>(
+    Path(table_id): Path<TableIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<GetRelationshipsResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let object = table_id.to_openfga();
+    authorizer
+        .require_action(&metadata, AllTableRelations::CanReadAssignments, &object)
+        .await?;
+    let relationships = get_relationships(authorizer, &object).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(GetRelationshipsResponse { relationships }),
+    ))
+}
+
+/// Get the raw relationship tuples of a view
+///
+/// Unlike the assignments endpoint, this also returns structural tuples
+/// and tuples that do not match the current authorization model.
+#[utoipa::path(
+    get,
+    tag = "permissions",
+    path = "/management/v1/permissions/view/{view_id}/relationships",
+    params(
+        ("view_id" = uuid::Uuid, Path, description = "View ID"),
+    ),
+    responses(
+            (status = 200, body = GetRelationshipsResponse),
+    )
+)]
+async fn get_view_relationships_by_id<C: Catalog, S: SecretStore>(
+    Path(view_id): Path<ViewIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+) -> Result<(StatusCode, Json<GetRelationshipsResponse>)> {
+    let authorizer = api_context.v1_state.authz;
+    let object = view_id.to_openfga();
+    authorizer
+        .require_action(&metadata, AllViewRelations::CanReadAssignments, &object)
+        .await?;
+    let relationships = get_relationships(authorizer, &object).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(GetRelationshipsResponse { relationships }),
+    ))
+}
+
+/// Add or remove relationship tuples of a warehouse
+///
+/// Only relations that can be assigned on the warehouse are accepted, structural tuples
+/// cannot be changed. Requires the same permissions as updating assignments.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/warehouse/{warehouse_id}/relationships",
+    request_body = UpdateRelationshipsRequest,
+    params(
+        ("warehouse_id" = uuid::Uuid, Path, description = "Warehouse ID"),
+    ),
+    responses(
+            (status = 204, description = "Relationships updated successfully"),
+    )
+)]
+async fn update_warehouse_relationships_by_id<C: Catalog, S: SecretStore>(
+    Path(warehouse_id): Path<WarehouseIdent>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Json(request): Json<UpdateRelationshipsRequest>,
+) -> Result<StatusCode> {
+    let authorizer = api_context.v1_state.authz;
+    update_relationships::<WarehouseAssignment>(
+        authorizer,
+        metadata.actor(),
+        request,
+        &warehouse_id.to_openfga(),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add or remove relationship tuples of a namespace
+///
+/// Only relations that can be assigned on the namespace are accepted, structural tuples
+/// cannot be changed. Requires the same permissions as updating assignments.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/namespace/{namespace_id}/relationships",
+    request_body = UpdateRelationshipsRequest,
+    params(
+        ("namespace_id" = uuid::Uuid, Path, description = "Namespace ID"),
+    ),
+    responses(
+            (status = 204, description = "Relationships updated successfully"),
+    )
+)]
+async fn update_namespace_relationships_by_id<C: Catalog, S: SecretStore>(
+    Path(namespace_id): Path<NamespaceIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Json(request): Json<UpdateRelationshipsRequest>,
+) -> Result<StatusCode> {
+    let authorizer = api_context.v1_state.authz;
+    update_relationships::<NamespaceAssignment>(
+        authorizer,
+        metadata.actor(),
+        request,
+        &namespace_id.to_openfga(),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add or remove relationship tuples of a table
+///
+/// Only relations that can be assigned on the table are accepted, structural tuples
+/// cannot be changed. Requires the same permissions as updating assignments.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/table/{table_id}/relationships",
+    request_body = UpdateRelationshipsRequest,
+    params(
+        ("table_id" = uuid::Uuid, Path, description = "Table ID"),
+    ),
+    responses(
+            (status = 204, description = "Relationships updated successfully"),
+    )
+)]
+async fn update_table_relationships_by_id<C: Catalog, S: SecretStore>(
+    Path(table_id): Path<TableIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Json(request): Json<UpdateRelationshipsRequest>,
+) -> Result<StatusCode> {
+    let authorizer = api_context.v1_state.authz;
+    update_relationships::<TableAssignment>(
+        authorizer,
+        metadata.actor(),
+        request,
+        &table_id.to_openfga(),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Add or remove relationship tuples of a view
+///
+/// Only relations that can be assigned on the view are accepted, structural tuples
+/// cannot be changed. Requires the same permissions as updating assignments.
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/view/{view_id}/relationships",
+    request_body = UpdateRelationshipsRequest,
+    params(
+        ("view_id" = uuid::Uuid, Path, description = "View ID"),
+    ),
+    responses(
+            (status = 204, description = "Relationships updated successfully"),
+    )
+)]
+async fn update_view_relationships_by_id<C: Catalog, S: SecretStore>(
+    Path(view_id): Path<ViewIdentUuid>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Json(request): Json<UpdateRelationshipsRequest>,
+) -> Result<StatusCode> {
+    let authorizer = api_context.v1_state.authz;
+    update_relationships::<ViewAssignment>(
+        authorizer,
+        metadata.actor(),
+        request,
+        &view_id.to_openfga(),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Update permissions for this server
 #[utoipa::path(
     post,
@@ -1031,11 +1437,8 @@ async fn update_project_assignments<C: Catalog, S: SecretStore>(
     Json(request): Json<UpdateProjectAssignmentsRequest>,
 ) -> Result<StatusCode> {
     let authorizer = api_context.v1_state.authz;
-    let project_id = metadata
-        .auth_details
-        .project_id()
-        .or(*DEFAULT_PROJECT_ID)
-        .ok_or(OpenFGAError::NoProjectId)?;
+    let project_id =
+        require_project_id_or_else(None, &metadata, || OpenFGAError::NoProjectId.into())?;
     checked_write(
         authorizer,
         metadata.actor(),
@@ -1144,6 +1547,38 @@ async fn update_namespace_assignments_by_id<C: Catalog, S: SecretStore>(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// Update permissions for a domain
+#[utoipa::path(
+    post,
+    tag = "permissions",
+    path = "/management/v1/permissions/domain/{domain_id}/assignments",
+    request_body = UpdateDomainAssignmentsRequest,
+    params(
+        ("domain_id" = uuid::Uuid, Path, description = "Domain ID"),
+    ),
+    responses(
+            (status = 204, description = "Permissions updated successfully"),
+    )
+)]
+async fn update_domain_assignments_by_id<C: Catalog, S: SecretStore>(
+    Path(domain_id): Path<DomainId>,
+    AxumState(api_context): AxumState<ApiContext<State<OpenFGAAuthorizer, C, S>>>,
+    Extension(metadata): Extension<RequestMetadata>,
+    Json(request): Json<UpdateDomainAssignmentsRequest>,
+) -> Result<StatusCode> {
+    let authorizer = api_context.v1_state.authz;
+    checked_write(
+        authorizer,
+        metadata.actor(),
+        request.writes,
+        request.deletes,
+        &domain_id.to_openfga(),
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// Update permissions for a table
 #[utoipa::path(
     post,
@@ -1236,6 +1671,11 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
         if assignee == &UserOrRole::Role(role_id.into_assignees()) {
             return Err(OpenFGAError::SelfAssignment(role_id.to_string()).into());
         }
+        if let RoleAssignment::Assignee(UserOrRole::Role(member)) = assignment {
+            authorizer
+                .require_no_role_cycle(role_id, *member.role())
+                .await?;
+        }
     }
     checked_write(
         authorizer,
@@ -1267,9 +1707,12 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
     ),
     paths(
         check,
+        get_domain_access_by_id,
+        get_domain_assignments_by_id,
         get_namespace_access_by_id,
         get_namespace_assignments_by_id,
         get_namespace_by_id,
+        get_namespace_relationships_by_id,
         get_project_access_by_id,
         get_project_access,
         get_project_assignments_by_id,
@@ -1280,24 +1723,33 @@ async fn update_role_assignments_by_id<C: Catalog, S: SecretStore>(
         get_server_assignments,
         get_table_access_by_id,
         get_table_assignments_by_id,
+        get_table_relationships_by_id,
         get_view_access_by_id,
         get_view_assignments_by_id,
+        get_view_relationships_by_id,
         get_warehouse_access_by_id,
         get_warehouse_assignments_by_id,
         get_warehouse_by_id,
+        get_warehouse_relationships_by_id,
         set_namespace_managed_access,
         set_warehouse_managed_access,
+        update_domain_assignments_by_id,
         update_namespace_assignments_by_id,
+        update_namespace_relationships_by_id,
         update_project_assignments_by_id,
         update_project_assignments,
         update_role_assignments_by_id,
         update_server_assignments,
         update_table_assignments_by_id,
+        update_table_relationships_by_id,
         update_view_assignments_by_id,
+        update_view_relationships_by_id,
         update_warehouse_assignments_by_id,
+        update_warehouse_relationships_by_id,
     ),
     // auto-discovery seems to be broken for these
-    components(schemas(NamespaceRelation,
+    components(schemas(DomainRelation,
+                       NamespaceRelation,
                        ProjectRelation,
                        RoleRelation,
                        ServerRelation,
@@ -1344,6 +1796,10 @@ pub(super) fn new_v1_router<C: Catalog, S: SecretStore>(
             "/permissions/namespace/{namespace_id}/managed-access",
             post(set_namespace_managed_access),
         )
+        .route(
+            "/permissions/domain/{domain_id}/access",
+            get(get_domain_access_by_id),
+        )
         .route(
             "/permissions/table/{table_id}/access",
             get(get_table_access_by_id),
@@ -1368,6 +1824,10 @@ pub(super) fn new_v1_router<C: Catalog, S: SecretStore>(
             "/permissions/project/{project_id}/assignments",
             get(get_project_assignments_by_id).post(update_project_assignments_by_id),
         )
+        .route(
+            "/permissions/domain/{domain_id}/assignments",
+            get(get_domain_assignments_by_id).post(update_domain_assignments_by_id),
+        )
         .route(
             "/permissions/warehouse/{warehouse_id}/assignments",
             get(get_warehouse_assignments_by_id).post(update_warehouse_assignments_by_id),
@@ -1384,9 +1844,76 @@ pub(super) fn new_v1_router<C: Catalog, S: SecretStore>(
             "/permissions/view/{view_id}/assignments",
             get(get_view_assignments_by_id).post(update_view_assignments_by_id),
         )
+        .route(
+            "/permissions/warehouse/{warehouse_id}/relationships",
+            get(get_warehouse_relationships_by_id).post(update_warehouse_relationships_by_id),
+        )
+        .route(
+            "/permissions/namespace/{namespace_id}/relationships",
+            get(get_namespace_relationships_by_id).post(update_namespace_relationships_by_id),
+        )
+        .route(
+            "/permissions/table/{table_id}/relationships",
+            get(get_table_relationships_by_id).post(update_table_relationships_by_id),
+        )
+        .route(
+            "/permissions/view/{view_id}/relationships",
+            get(get_view_relationships_by_id).post(update_view_relationships_by_id),
+        )
         .route("/permissions/check", post(check))
 }
 
+async fn get_relationships(
+    authorizer: OpenFGAAuthorizer,
+    object: &str,
+) -> Result<Vec<Relationship>> {
+    let relationships = authorizer
+        .read_all(ReadRequestTupleKey {
+            user: String::new(),
+            relation: String::new(),
+            object: object.to_string(),
+        })
+        .await?
+        .into_iter()
+        .filter_map(|t| t.key)
+        .map(|t| Relationship {
+            user: t.user,
+            relation: t.relation,
+            object: t.object,
+        })
+        .collect();
+
+    Ok(relationships)
+}
+
+async fn update_relationships<RA: Assignment>(
+    authorizer: OpenFGAAuthorizer,
+    actor: &Actor,
+    request: UpdateRelationshipsRequest,
+    object: &str,
+) -> OpenFGAResult<()> {
+    let parse = |keys: Vec<RelationshipKey>| {
+        keys.into_iter()
+            .map(|key| parse_relationship::<RA>(key, object))
+            .collect::<OpenFGAResult<Vec<_>>>()
+    };
+    let writes = parse(request.writes)?;
+    let deletes = parse(request.deletes)?;
+    checked_write(authorizer, actor, writes, deletes, object).await
+}
+
+/// Validate a raw relationship against the relations that can be assigned on `object`.
+fn parse_relationship<RA: Assignment>(key: RelationshipKey, object: &str) -> OpenFGAResult<RA> {
+    RA::Relation::iter()
+        .find(|relation| relation.to_openfga().to_string() == key.relation)
+        .and_then(|relation| RA::try_from_user(&key.user, &relation).ok())
+        .ok_or_else(|| OpenFGAError::InvalidRelationship {
+            user: key.user,
+            relation: key.relation,
+            object: object.to_string(),
+        })
+}
+
 async fn get_relations<RA: Assignment>(
     authorizer: OpenFGAAuthorizer,
     query_relations: Option<Vec<RA::Relation>>,
@@ -1619,6 +2146,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_relationship() {
+        let user = crate::service::authn::UserId::oidc("peter").unwrap();
+        let object = WarehouseIdent::from(uuid::Uuid::now_v7()).to_openfga();
+
+        let assignment = parse_relationship::<WarehouseAssignment>(
+            RelationshipKey {
+                user: user.to_openfga(),
+                relation: "ownership".to_string(),
+            },
+            &object,
+        )
+        .unwrap();
+        assert_eq!(
+            assignment,
+            WarehouseAssignment::Ownership(UserOrRole::User(user))
+        );
+
+        // Structural relations cannot be assigned
+        let err = parse_relationship::<WarehouseAssignment>(
+            RelationshipKey {
+                user: ProjectIdent::from(uuid::Uuid::now_v7()).to_openfga(),
+                relation: AllWarehouseRelation::Project.to_string(),
+            },
+            &object,
+        )
+        .unwrap_err();
+        assert!(matches!(err, OpenFGAError::InvalidRelationship { .. }));
+
+        let err = parse_relationship::<WarehouseAssignment>(
+            RelationshipKey {
+                user: "peter".to_string(),
+                relation: "ownership".to_string(),
+            },
+            &object,
+        )
+        .unwrap_err();
+        assert!(matches!(err, OpenFGAError::InvalidRelationship { .. }));
+    }
+
     #[needs_env_var(TEST_OPENFGA = 1)]
     mod openfga {
         use super::super::*;
@@ -1689,6 +2256,43 @@ mod tests {
             assert_eq!(relations, vec![ServerAssignment::Admin(user_id.into())]);
         }
 
+        #[tokio::test]
+        async fn test_get_relationships() {
+            let (_, authorizer) = authorizer_for_empty_store().await;
+            let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+            let project_id = ProjectIdent::from(uuid::Uuid::now_v7());
+            let user_id = UserId::oidc(&uuid::Uuid::now_v7().to_string()).unwrap();
+            let object = warehouse_id.to_openfga();
+
+            let parent = TupleKey {
+                user: project_id.to_openfga(),
+                relation: AllWarehouseRelation::Project.to_string(),
+                object: object.clone(),
+                condition: None,
+            };
+            let owner = TupleKey {
+                user: user_id.to_openfga(),
+                relation: AllWarehouseRelation::Ownership.to_string(),
+                object: object.clone(),
+                condition: None,
+            };
+            authorizer
+                .write(Some(vec![parent.clone(), owner.clone()]), None)
+                .await
+                .unwrap();
+
+            let mut relationships = get_relationships(authorizer.clone(), &object)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|r| (r.user, r.relation))
+                .collect::<Vec<_>>();
+            relationships.sort();
+            let mut expected = vec![(parent.user, parent.relation), (owner.user, owner.relation)];
+            expected.sort();
+            assert_eq!(relationships, expected);
+        }
+
         #[test]
         fn test_can_read_assignments_identical() {
             let role_assignment = RoleAction::ReadAssignments.to_openfga().to_string();
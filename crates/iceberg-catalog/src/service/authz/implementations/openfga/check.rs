@@ -7,6 +7,7 @@ use super::relations::{
     UserOrRole, ViewRelation as AllViewRelations, WarehouseRelation as AllWarehouseRelation,
 };
 use super::{OpenFGAAuthorizer, OpenFGAError, OPENFGA_SERVER};
+use crate::api::management::v1::role::require_project_id_or_else;
 use crate::catalog::namespace::authorized_namespace_ident_to_id;
 use crate::catalog::tables::authorized_table_ident_to_id;
 use crate::catalog::views::authorized_view_ident_to_id;
@@ -17,8 +18,8 @@ use crate::service::{
     Catalog, NamespaceIdentUuid, Result, SecretStore, State, TableIdentUuid, ViewIdentUuid,
 };
 use crate::service::{ListFlags, Transaction};
+use crate::WarehouseIdent;
 use crate::{api::ApiContext, ProjectIdent};
-use crate::{WarehouseIdent, DEFAULT_PROJECT_ID};
 use axum::extract::State as AxumState;
 use axum::{Extension, Json};
 use http::StatusCode;
@@ -156,11 +157,10 @@ async fn check_project(
     action: &APIProjectAction,
     project_id: Option<&ProjectIdent>,
 ) -> Result<(String, String)> {
-    let project_id = project_id
-        .or(metadata.auth_details.project_id().as_ref())
-        .or(DEFAULT_PROJECT_ID.as_ref())
-        .ok_or(OpenFGAError::NoProjectId)?
-        .to_openfga();
+    let project_id = require_project_id_or_else(project_id.copied(), metadata, || {
+        OpenFGAError::NoProjectId.into()
+    })?
+    .to_openfga();
     authorizer
         .require_action(
             metadata,
@@ -8,13 +8,16 @@ use crate::{service::authz::implementations::Authorizers, OpenFGAAuth};
 use http::{HeaderMap, Request};
 use openfga_rs::tonic::body::BoxBody;
 use openfga_rs::tonic::transport::{Channel, Endpoint};
+use openfga_rs::tonic::Status;
 use openfga_rs::{
     authentication::{ClientCredentials, RefreshConfiguration},
     open_fga_service_client::OpenFgaServiceClient,
 };
 use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tower::ServiceBuilder;
 
@@ -68,6 +71,43 @@ pub(crate) async fn new_client_from_config(
     Ok(OpenFgaServiceClient::new(c))
 }
 
+/// Fail `request` with `DEADLINE_EXCEEDED` if `OpenFGA` does not answer within `timeout_ms`.
+pub(super) async fn with_deadline<T>(
+    timeout_ms: u64,
+    request: impl Future<Output = Result<T, Status>>,
+) -> Result<T, Status> {
+    tokio::time::timeout(Duration::from_millis(timeout_ms), request)
+        .await
+        .unwrap_or_else(|_| {
+            Err(Status::deadline_exceeded(format!(
+                "OpenFGA did not respond within {timeout_ms}ms"
+            )))
+        })
+}
+
+/// Send the request created by `request`. If no response arrived after `delay`,
+/// send it a second time and return whichever response arrives first.
+/// Must only be used for requests without side effects.
+pub(super) async fn hedged<T, F, Fut>(delay: Duration, request: F) -> T
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = T>,
+{
+    let first = request();
+    tokio::pin!(first);
+    tokio::select! {
+        response = &mut first => return response,
+        () = tokio::time::sleep(delay) => {}
+    }
+
+    let second = request();
+    tokio::pin!(second);
+    tokio::select! {
+        response = &mut first => response,
+        response = &mut second => response,
+    }
+}
+
 /// Create a new `OpenFGA` authorizer from the configuration.
 ///
 /// # Errors
@@ -150,3 +190,54 @@ where
         self.inner.call(req)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn respond_after(ms: u64, response: &'static str) -> &'static str {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        response
+    }
+
+    #[tokio::test]
+    async fn test_with_deadline() {
+        let response = with_deadline(200, async { Ok::<_, Status>(1) }).await;
+        assert_eq!(response.unwrap(), 1);
+
+        let status = with_deadline(10, async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            Ok::<_, Status>(1)
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(status.code(), openfga_rs::tonic::Code::DeadlineExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_hedged_request() {
+        let sent = std::sync::atomic::AtomicUsize::new(0);
+        // The first request is slow, the hedged request answers first.
+        let response = hedged(Duration::from_millis(20), || {
+            let n = sent.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if n == 0 {
+                respond_after(1000, "first")
+            } else {
+                respond_after(0, "second")
+            }
+        })
+        .await;
+        assert_eq!(response, "second");
+        assert_eq!(sent.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // Fast responses are not hedged.
+        let sent = std::sync::atomic::AtomicUsize::new(0);
+        let response = hedged(Duration::from_millis(500), || {
+            sent.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            respond_after(0, "first")
+        })
+        .await;
+        assert_eq!(response, "first");
+        assert_eq!(sent.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
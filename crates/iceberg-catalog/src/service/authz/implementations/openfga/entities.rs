@@ -3,7 +3,7 @@ use crate::service::authn::Actor;
 use crate::service::authn::UserId;
 use crate::service::authz::implementations::openfga::{OpenFGAError, OpenFGAResult};
 use crate::service::authz::implementations::FgaType;
-use crate::service::{NamespaceIdentUuid, RoleId, TableIdentUuid, ViewIdentUuid};
+use crate::service::{DomainId, NamespaceIdentUuid, RoleId, TableIdentUuid, ViewIdentUuid};
 use crate::{ProjectIdent, WarehouseIdent};
 use std::str::FromStr;
 
@@ -157,6 +157,16 @@ impl ParseOpenFgaEntity for ProjectIdent {
     }
 }
 
+impl OpenFgaEntity for DomainId {
+    fn to_openfga(&self) -> String {
+        format!("{}:{self}", self.openfga_type())
+    }
+
+    fn openfga_type(&self) -> FgaType {
+        FgaType::Domain
+    }
+}
+
 impl OpenFgaEntity for WarehouseIdent {
     fn to_openfga(&self) -> String {
         format!("{}:{self}", self.openfga_type())
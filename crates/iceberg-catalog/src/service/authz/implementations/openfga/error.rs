@@ -66,7 +66,7 @@ pub enum OpenFGAError {
     },
     #[error("Too many writes and deletes in single Authorization transaction (actual) {actual} > {max} (max)")]
     TooManyWrites { actual: i32, max: i32 },
-    #[error("Project ID could not be inferred from request. Please specify it explicitly.")]
+    #[error("Project ID could not be inferred from request and the default project is disabled. Please specify it explicitly.")]
     NoProjectId,
     #[error("Authentication required")]
     AuthenticationRequired,
@@ -78,6 +78,16 @@ pub enum OpenFGAError {
     },
     #[error("Cannot assign {0} to itself")]
     SelfAssignment(String),
+    #[error("`{user}` cannot be assigned `{relation}` on `{object}`")]
+    InvalidRelationship {
+        user: String,
+        relation: String,
+        object: String,
+    },
+    #[error(
+        "Cannot add role {member} to role {role}: role {role} is already a member of role {member}"
+    )]
+    RoleCycle { role: String, member: String },
 }
 
 impl OpenFGAError {
@@ -140,6 +150,12 @@ impl From<OpenFGAError> for ErrorModel {
             e @ OpenFGAError::Unauthorized { .. } => {
                 ErrorModel::unauthorized(err_msg, "Unauthorized", Some(Box::new(e)))
             }
+            e @ OpenFGAError::InvalidRelationship { .. } => {
+                ErrorModel::bad_request(err_msg, "InvalidRelationship", Some(Box::new(e)))
+            }
+            e @ OpenFGAError::RoleCycle { .. } => {
+                ErrorModel::conflict(err_msg, "RoleMembershipCycle", Some(Box::new(e)))
+            }
             e @ OpenFGAError::WriteFailed { .. } => {
                 if status_msg
                     .as_deref()
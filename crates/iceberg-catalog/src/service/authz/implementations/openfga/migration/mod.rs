@@ -56,7 +56,9 @@ pub(crate) async fn migrate(
             "Applying OpenFGA Migration: Rolling up from {max_applied} to {}",
             ModelVersion::active()
         );
-        for int_id in max_applied.as_monotonic_int()..ModelVersion::active().as_monotonic_int() {
+        for int_id in
+            (max_applied.as_monotonic_int() + 1)..=ModelVersion::active().as_monotonic_int()
+        {
             let model_version = ModelVersion::from_monotonic_int(int_id)
                 .ok_or(OpenFGAError::UnknownModelVersionApplied(int_id))?;
 
@@ -70,6 +72,9 @@ pub(crate) async fn migrate(
                     // no migration to be done, we start at v1
                 }
                 ModelVersion::V2 => v2::migrate(client, &written_model.auth_model_id, &store).await,
+                ModelVersion::V3 => {
+                    // only adds computed relations, no tuples need to be rewritten
+                }
             }
             tracing::info!("Marking model version {} as applied", model_version);
             mark_as_applied(client, &store, written_model).await?;
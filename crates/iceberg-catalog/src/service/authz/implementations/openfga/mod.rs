@@ -45,9 +45,11 @@ use crate::service::authn::UserId;
 use crate::service::authz::implementations::openfga::client::ClientConnection;
 use crate::service::authz::implementations::openfga::relations::OpenFgaRelation;
 use crate::service::authz::implementations::FgaType;
-use crate::service::authz::{CatalogRoleAction, CatalogUserAction, NamespaceParent};
+use crate::service::authz::{
+    CatalogDomainAction, CatalogRoleAction, CatalogUserAction, NamespaceParent,
+};
 use crate::service::health::Health;
-use crate::service::{AuthDetails, Catalog, RoleId, SecretStore, State, ViewIdentUuid};
+use crate::service::{AuthDetails, Catalog, DomainId, RoleId, SecretStore, State, ViewIdentUuid};
 pub(crate) use client::new_client_from_config;
 pub use client::{
     new_authorizer_from_config, BearerOpenFGAAuthorizer, ClientCredentialsOpenFGAAuthorizer,
@@ -59,8 +61,8 @@ use iceberg_ext::catalog::rest::IcebergErrorResponse;
 pub(crate) use migration::migrate;
 pub(crate) use models::{ModelVersion, OpenFgaType, RoleAssignee};
 use relations::{
-    NamespaceRelation, ProjectRelation, RoleRelation, ServerRelation, TableRelation, ViewRelation,
-    WarehouseRelation,
+    DomainRelation, NamespaceRelation, ProjectRelation, RoleRelation, ServerRelation,
+    TableRelation, ViewRelation, WarehouseRelation,
 };
 pub(crate) use service_ext::ClientHelper;
 use service_ext::MAX_TUPLES_PER_WRITE;
@@ -279,6 +281,25 @@ impl Authorizer for OpenFGAAuthorizer {
         check.map_err(Into::into)
     }
 
+    async fn is_allowed_domain_action(
+        &self,
+        metadata: &RequestMetadata,
+        domain_id: DomainId,
+        action: &CatalogDomainAction,
+    ) -> Result<bool> {
+        let actor = metadata.actor();
+        let check_actor_fut = self.check_actor(actor);
+        let check_fut = self.check(CheckRequestTupleKey {
+            user: actor.to_openfga(),
+            relation: action.to_string(),
+            object: domain_id.to_openfga(),
+        });
+
+        let (check_actor, check) = futures::join!(check_actor_fut, check_fut);
+        check_actor?;
+        check.map_err(Into::into)
+    }
+
     async fn is_allowed_warehouse_action(
         &self,
         metadata: &RequestMetadata,
@@ -399,6 +420,126 @@ impl Authorizer for OpenFGAAuthorizer {
         self.delete_all_relations(&role_id).await
     }
 
+    async fn add_role_member(
+        &self,
+        _metadata: &RequestMetadata,
+        role_id: RoleId,
+        member_role_id: RoleId,
+    ) -> Result<()> {
+        if self.is_direct_role_member(role_id, member_role_id).await? {
+            return Ok(());
+        }
+        self.require_no_role_cycle(role_id, member_role_id).await?;
+        self.write(
+            Some(vec![TupleKey {
+                user: member_role_id.into_assignees().to_openfga(),
+                relation: RoleRelation::Assignee.to_string(),
+                object: role_id.to_openfga(),
+                condition: None,
+            }]),
+            None,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn remove_role_member(
+        &self,
+        _metadata: &RequestMetadata,
+        role_id: RoleId,
+        member_role_id: RoleId,
+    ) -> Result<()> {
+        if !self.is_direct_role_member(role_id, member_role_id).await? {
+            return Err(ErrorModel::not_found(
+                format!("Role {member_role_id} is not a member of role {role_id}."),
+                "RoleMemberNotFound",
+                None,
+            )
+            .into());
+        }
+        self.write(
+            None,
+            Some(vec![TupleKeyWithoutCondition {
+                user: member_role_id.into_assignees().to_openfga(),
+                relation: RoleRelation::Assignee.to_string(),
+                object: role_id.to_openfga(),
+            }]),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn list_role_members(&self, role_id: RoleId) -> Result<Option<Vec<RoleId>>> {
+        let role_prefix = format!("{}:", FgaType::Role);
+        let members = self
+            .read_all(ReadRequestTupleKey {
+                user: String::new(),
+                relation: RoleRelation::Assignee.to_string(),
+                object: role_id.to_openfga(),
+            })
+            .await?
+            .into_iter()
+            .filter_map(|t| t.key)
+            .filter(|k| k.user.starts_with(&role_prefix))
+            .map(|k| RoleAssignee::parse_from_openfga(&k.user).map(|member| *member.role()))
+            .collect::<OpenFGAResult<Vec<_>>>()?;
+        Ok(Some(members))
+    }
+
+    async fn list_role_assignee_users(&self, role_id: RoleId) -> Result<Option<Vec<UserId>>> {
+        let user_prefix = format!("{}:", FgaType::User);
+        let users = self
+            .read_all(ReadRequestTupleKey {
+                user: String::new(),
+                relation: RoleRelation::Assignee.to_string(),
+                object: role_id.to_openfga(),
+            })
+            .await?
+            .into_iter()
+            .filter_map(|t| t.key)
+            .filter(|k| k.user.starts_with(&user_prefix))
+            .map(|k| UserId::parse_from_openfga(&k.user))
+            .collect::<OpenFGAResult<Vec<_>>>()?;
+        Ok(Some(users))
+    }
+
+    async fn create_domain(
+        &self,
+        metadata: &RequestMetadata,
+        domain_id: DomainId,
+        parent_project_id: ProjectIdent,
+    ) -> Result<()> {
+        let actor = metadata.actor();
+
+        self.require_no_relations(&domain_id, ConsistencyPreference::MinimizeLatency)
+            .await?;
+        let parent_id = parent_project_id.to_openfga();
+        let this_id = domain_id.to_openfga();
+        self.write(
+            Some(vec![
+                TupleKey {
+                    user: actor.to_openfga(),
+                    relation: DomainRelation::Ownership.to_string(),
+                    object: this_id.clone(),
+                    condition: None,
+                },
+                TupleKey {
+                    user: parent_id,
+                    relation: DomainRelation::Project.to_string(),
+                    object: this_id,
+                    condition: None,
+                },
+            ]),
+            None,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn delete_domain(&self, _metadata: &RequestMetadata, domain_id: DomainId) -> Result<()> {
+        self.delete_all_relations(&domain_id).await
+    }
+
     async fn create_project(
         &self,
         metadata: &RequestMetadata,
@@ -503,16 +644,7 @@ impl Authorizer for OpenFGAAuthorizer {
         self.require_no_relations(&namespace_id, ConsistencyPreference::MinimizeLatency)
             .await?;
 
-        let (parent_id, parent_child_relation) = match parent {
-            NamespaceParent::Warehouse(warehouse_id) => (
-                warehouse_id.to_openfga(),
-                WarehouseRelation::Namespace.to_string(),
-            ),
-            NamespaceParent::Namespace(parent_namespace_id) => (
-                parent_namespace_id.to_openfga(),
-                NamespaceRelation::Child.to_string(),
-            ),
-        };
+        let (parent_id, parent_child_relation) = namespace_parent_tuple_parts(&parent);
         let this_id = namespace_id.to_openfga();
 
         self.write(
@@ -550,6 +682,50 @@ impl Authorizer for OpenFGAAuthorizer {
         self.delete_all_relations(&namespace_id).await
     }
 
+    async fn move_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        previous_parent: NamespaceParent,
+        new_parent: NamespaceParent,
+    ) -> Result<()> {
+        let this_id = namespace_id.to_openfga();
+        let (previous_parent_id, previous_parent_child_relation) =
+            namespace_parent_tuple_parts(&previous_parent);
+        let (parent_id, parent_child_relation) = namespace_parent_tuple_parts(&new_parent);
+
+        self.write(
+            Some(vec![
+                TupleKey {
+                    user: parent_id.clone(),
+                    relation: NamespaceRelation::Parent.to_string(),
+                    object: this_id.clone(),
+                    condition: None,
+                },
+                TupleKey {
+                    user: this_id.clone(),
+                    relation: parent_child_relation,
+                    object: parent_id,
+                    condition: None,
+                },
+            ]),
+            Some(vec![
+                TupleKeyWithoutCondition {
+                    user: previous_parent_id.clone(),
+                    relation: NamespaceRelation::Parent.to_string(),
+                    object: this_id.clone(),
+                },
+                TupleKeyWithoutCondition {
+                    user: this_id,
+                    relation: previous_parent_child_relation,
+                    object: previous_parent_id,
+                },
+            ]),
+        )
+        .await
+        .map_err(Into::into)
+    }
+
     async fn create_table(
         &self,
         metadata: &RequestMetadata,
@@ -768,6 +944,45 @@ impl OpenFGAAuthorizer {
             .map(|response| response.get_ref().allowed)
     }
 
+    async fn is_direct_role_member(
+        &self,
+        role_id: RoleId,
+        member_role_id: RoleId,
+    ) -> OpenFGAResult<bool> {
+        let tuples = self
+            .read_all(ReadRequestTupleKey {
+                user: member_role_id.into_assignees().to_openfga(),
+                relation: RoleRelation::Assignee.to_string(),
+                object: role_id.to_openfga(),
+            })
+            .await?;
+        Ok(!tuples.is_empty())
+    }
+
+    /// Fails if `role_id` is already (transitively) a member of `member_role_id`,
+    /// as making `member_role_id` a member of `role_id` would then close a cycle.
+    async fn require_no_role_cycle(
+        &self,
+        role_id: RoleId,
+        member_role_id: RoleId,
+    ) -> OpenFGAResult<()> {
+        let is_member = self
+            .check(CheckRequestTupleKey {
+                user: role_id.into_assignees().to_openfga(),
+                relation: RoleRelation::Assignee.to_string(),
+                object: member_role_id.to_openfga(),
+            })
+            .await?;
+
+        if is_member {
+            return Err(OpenFGAError::RoleCycle {
+                role: role_id.to_string(),
+                member: member_role_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
     async fn require_action(
         &self,
         metadata: &RequestMetadata,
@@ -1108,6 +1323,20 @@ pub trait Client {
     ) -> std::result::Result<Response<CheckResponse>, tonic::Status>;
 }
 
+/// Object of the parent and the relation the namespace has as its child.
+fn namespace_parent_tuple_parts(parent: &NamespaceParent) -> (String, String) {
+    match parent {
+        NamespaceParent::Warehouse(warehouse_id) => (
+            warehouse_id.to_openfga(),
+            WarehouseRelation::Namespace.to_string(),
+        ),
+        NamespaceParent::Namespace(parent_namespace_id) => (
+            parent_namespace_id.to_openfga(),
+            NamespaceRelation::Child.to_string(),
+        ),
+    }
+}
+
 fn suffixes_for_user(user: &FgaType) -> Vec<String> {
     user.usersets()
         .iter()
@@ -1119,21 +1348,33 @@ fn suffixes_for_user(user: &FgaType) -> Vec<String> {
 #[async_trait]
 impl Client for OpenFgaServiceClient<ClientConnection> {
     async fn write(&self, request: WriteRequest) -> Result<Response<WriteResponse>, Status> {
-        Self::write(&mut self.clone(), request).await
+        client::with_deadline(
+            AUTH_CONFIG.write_timeout_ms,
+            Self::write(&mut self.clone(), request),
+        )
+        .await
     }
 
     async fn list_objects(
         &self,
         request: ListObjectsRequest,
     ) -> Result<Response<ListObjectsResponse>, Status> {
-        Self::list_objects(&mut self.clone(), request).await
+        client::with_deadline(
+            AUTH_CONFIG.read_timeout_ms,
+            Self::list_objects(&mut self.clone(), request),
+        )
+        .await
     }
 
     async fn read(
         &self,
         request: ReadRequest,
     ) -> std::result::Result<Response<ReadResponse>, Status> {
-        Self::read(&mut self.clone(), request).await
+        client::with_deadline(
+            AUTH_CONFIG.read_timeout_ms,
+            Self::read(&mut self.clone(), request),
+        )
+        .await
     }
 
     async fn read_all_pages(
@@ -1148,7 +1389,23 @@ impl Client for OpenFgaServiceClient<ClientConnection> {
         &self,
         request: CheckRequest,
     ) -> std::result::Result<Response<CheckResponse>, Status> {
-        Self::check(&mut self.clone(), request).await
+        let send = || {
+            let mut service_client = self.clone();
+            let request = request.clone();
+            async move {
+                client::with_deadline(
+                    AUTH_CONFIG.check_timeout_ms,
+                    Self::check(&mut service_client, request),
+                )
+                .await
+            }
+        };
+        match AUTH_CONFIG.check_hedge_delay_ms {
+            Some(delay_ms) => {
+                client::hedged(std::time::Duration::from_millis(delay_ms), send).await
+            }
+            None => send().await,
+        }
     }
 }
 #[cfg(test)]
@@ -1165,22 +1422,29 @@ pub(crate) mod tests {
     ///
     /// Create via `ObjectHidingMock::new()`, use `ObjectHidingMock::to_authorizer` to create an authorizer.
     /// Hide objects via `ObjectHidingMock::hide`. Objects that have been hidden will return `allowed: false`
-    /// for any check request.
+    /// for any check request. Single relations on an object are denied via `ObjectHidingMock::deny`.
     pub(crate) struct ObjectHidingMock {
         pub hidden: Arc<RwLock<HashSet<String>>>,
+        pub denied: Arc<RwLock<HashSet<(String, String)>>>,
         pub mock: Arc<MockClient>,
     }
 
     impl ObjectHidingMock {
         pub(crate) fn new() -> Self {
             let hidden: Arc<RwLock<HashSet<String>>> = Arc::default();
+            let denied: Arc<RwLock<HashSet<(String, String)>>> = Arc::default();
             let hidden_clone = hidden.clone();
+            let denied_clone = denied.clone();
             let mut mock = MockClient::default();
             mock.expect_check().returning(move |r| {
                 let hidden = hidden_clone.clone();
                 let hidden = hidden.read().unwrap();
+                let denied = denied_clone.read().unwrap();
+                let tuple_key = r.tuple_key.unwrap();
 
-                if hidden.contains(&r.tuple_key.unwrap().object) {
+                if hidden.contains(&tuple_key.object)
+                    || denied.contains(&(tuple_key.object, tuple_key.relation))
+                {
                     return Ok(openfga_rs::tonic::Response::new(CheckResponse {
                         allowed: false,
                         resolution: String::new(),
@@ -1203,6 +1467,7 @@ pub(crate) mod tests {
 
             Self {
                 hidden,
+                denied,
                 mock: Arc::new(mock),
             }
         }
@@ -1212,6 +1477,14 @@ pub(crate) mod tests {
             self.hidden.write().unwrap().insert(object.to_string());
         }
 
+        #[cfg(test)]
+        pub(crate) fn deny(&self, object: &str, relation: &str) {
+            self.denied
+                .write()
+                .unwrap()
+                .insert((object.to_string(), relation.to_string()));
+        }
+
         #[cfg(test)]
         pub(crate) fn to_authorizer(&self) -> OpenFGAAuthorizer {
             OpenFGAAuthorizer {
@@ -1558,6 +1831,108 @@ pub(crate) mod tests {
             assert_eq!(ErrorModel::from(result).code, StatusCode::CONFLICT.as_u16());
         }
 
+        #[tokio::test]
+        async fn test_role_members() {
+            let authorizer = new_authorizer_in_empty_store().await;
+            let metadata = RequestMetadata::new_random();
+            let [a, b, c] = [RoleId::default(), RoleId::default(), RoleId::default()];
+
+            authorizer.add_role_member(&metadata, a, b).await.unwrap();
+            authorizer.add_role_member(&metadata, b, c).await.unwrap();
+            // Adding an existing member is a no-op
+            authorizer.add_role_member(&metadata, a, b).await.unwrap();
+            let err = authorizer
+                .add_role_member(&metadata, c, a)
+                .await
+                .unwrap_err();
+            assert_eq!(err.error.r#type, "RoleMembershipCycle");
+
+            assert_eq!(
+                authorizer.list_role_members(a).await.unwrap(),
+                Some(vec![b])
+            );
+            assert_eq!(
+                authorizer.list_role_members(b).await.unwrap(),
+                Some(vec![c])
+            );
+
+            authorizer
+                .remove_role_member(&metadata, a, b)
+                .await
+                .unwrap();
+            assert_eq!(authorizer.list_role_members(a).await.unwrap(), Some(vec![]));
+            let err = authorizer
+                .remove_role_member(&metadata, a, b)
+                .await
+                .unwrap_err();
+            assert_eq!(err.error.code, StatusCode::NOT_FOUND.as_u16());
+        }
+
+        #[tokio::test]
+        async fn test_move_namespace() {
+            let authorizer = new_authorizer_in_empty_store().await;
+            let user_id = UserId::oidc("this_user").unwrap();
+            let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+            let parent_id = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+            let namespace_id = NamespaceIdentUuid::from(uuid::Uuid::now_v7());
+
+            let parent_tuples = |object: String, parent: String, child_relation: String| {
+                vec![
+                    TupleKey {
+                        user: parent.clone(),
+                        relation: NamespaceRelation::Parent.to_string(),
+                        object: object.clone(),
+                        condition: None,
+                    },
+                    TupleKey {
+                        user: object,
+                        relation: child_relation,
+                        object: parent,
+                        condition: None,
+                    },
+                ]
+            };
+            let mut tuples = parent_tuples(
+                parent_id.to_openfga(),
+                warehouse_id.to_openfga(),
+                WarehouseRelation::Namespace.to_string(),
+            );
+            tuples.extend(parent_tuples(
+                namespace_id.to_openfga(),
+                parent_id.to_openfga(),
+                NamespaceRelation::Child.to_string(),
+            ));
+            tuples.push(TupleKey {
+                user: user_id.to_openfga(),
+                relation: NamespaceRelation::Modify.to_string(),
+                object: parent_id.to_openfga(),
+                condition: None,
+            });
+            authorizer.write(Some(tuples), None).await.unwrap();
+
+            let can_delete = || {
+                authorizer.check(CheckRequestTupleKey {
+                    user: user_id.to_openfga(),
+                    relation: NamespaceRelation::CanDelete.to_string(),
+                    object: namespace_id.to_openfga(),
+                })
+            };
+            assert!(can_delete().await.unwrap());
+
+            authorizer
+                .move_namespace(
+                    &RequestMetadata::new_random(),
+                    namespace_id,
+                    NamespaceParent::Namespace(parent_id),
+                    NamespaceParent::Warehouse(warehouse_id),
+                )
+                .await
+                .unwrap();
+
+            // Permissions on the previous parent are no longer inherited
+            assert!(!can_delete().await.unwrap());
+        }
+
         #[tokio::test]
         async fn test_delete_user_relations_empty() {
             let authorizer = new_authorizer_in_empty_store().await;
@@ -6,13 +6,15 @@ use std::sync::LazyLock;
 
 const V1_MODEL: &str = include_str!("../../../../../../../authz/openfga/v1/schema.json");
 const V2_MODEL: &str = include_str!("../../../../../../../authz/openfga/v2/schema.json");
+const V3_MODEL: &str = include_str!("../../../../../../../authz/openfga/v3/schema.json");
 
 static MODEL: LazyLock<CollaborationModels> = LazyLock::new(|| CollaborationModels {
     v1: serde_json::from_str(V1_MODEL).expect("Failed to parse OpenFGA model V1 as JSON"),
     v2: serde_json::from_str(V2_MODEL).expect("Failed to parse OpenFGA model V2 as JSON"),
+    v3: serde_json::from_str(V3_MODEL).expect("Failed to parse OpenFGA model V3 as JSON"),
 });
 
-const ACTIVE_MODEL: ModelVersion = ModelVersion::V2;
+const ACTIVE_MODEL: ModelVersion = ModelVersion::V3;
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(transparent)]
@@ -51,12 +53,14 @@ impl OpenFgaType for FgaType {
                 FgaType::Role,
                 FgaType::Server,
                 FgaType::Project,
+                FgaType::Domain,
                 FgaType::Warehouse,
                 FgaType::Namespace,
                 FgaType::Table,
                 FgaType::View,
             ],
-            FgaType::Project => &[FgaType::Server, FgaType::Warehouse],
+            FgaType::Project => &[FgaType::Server, FgaType::Domain, FgaType::Warehouse],
+            FgaType::Domain => &[],
             FgaType::Warehouse => &[FgaType::Project, FgaType::Namespace],
             FgaType::Namespace => &[
                 FgaType::Warehouse,
@@ -83,6 +87,7 @@ impl OpenFgaType for FgaType {
 pub(crate) struct CollaborationModels {
     v1: AuthorizationModel,
     v2: AuthorizationModel,
+    v3: AuthorizationModel,
 }
 
 impl CollaborationModels {
@@ -91,6 +96,7 @@ impl CollaborationModels {
         match version {
             ModelVersion::V1 => &self.v1,
             ModelVersion::V2 => &self.v2,
+            ModelVersion::V3 => &self.v3,
         }
     }
 }
@@ -110,6 +116,7 @@ impl CollaborationModels {
 pub(crate) enum ModelVersion {
     V1 = 1,
     V2 = 2,
+    V3 = 3,
 }
 
 impl ModelVersion {
@@ -847,6 +854,14 @@ mod test {
                         serde_json::from_str::<serde_json::Value>(V2_MODEL).unwrap()
                     );
                 }
+                ModelVersion::V3 => {
+                    let ser_model: ser_de::AuthorizationModel =
+                        serde_json::from_str(V3_MODEL).unwrap();
+                    assert_eq!(
+                        serde_json::to_value(ser_model).unwrap(),
+                        serde_json::from_str::<serde_json::Value>(V3_MODEL).unwrap()
+                    );
+                }
             }
         }
     }
@@ -877,6 +892,12 @@ mod test {
                         serde_json::from_str::<serde_json::Value>(V2_MODEL).unwrap()
                     );
                 }
+                ModelVersion::V3 => {
+                    assert_eq!(
+                        value,
+                        serde_json::from_str::<serde_json::Value>(V3_MODEL).unwrap()
+                    );
+                }
             }
         }
     }
@@ -8,7 +8,8 @@ use super::{
     OpenFGAError, OpenFGAResult, RoleAssignee,
 };
 use crate::service::authz::{
-    CatalogNamespaceAction, CatalogRoleAction, CatalogTableAction, CatalogViewAction,
+    CatalogDomainAction, CatalogNamespaceAction, CatalogRoleAction, CatalogTableAction,
+    CatalogViewAction,
 };
 use crate::service::{authn::UserId, Actor};
 use crate::service::{
@@ -260,6 +261,8 @@ pub(super) enum ServerRelation {
     CanProvisionUsers,
     CanUpdateUsers,
     CanDeleteUsers,
+    CanManageNotices,
+    CanImpersonateUsers,
     CanReadAssignments,
     CanGrantAdmin,
     CanGrantOperator,
@@ -339,6 +342,10 @@ pub(super) enum APIServerAction {
     ProvisionUsers,
     /// Can read assignments
     ReadAssignments,
+    /// Can create and delete server notices
+    ManageNotices,
+    /// Can act as other users
+    ImpersonateUsers,
 }
 
 impl ReducedRelation for APIServerRelation {
@@ -362,6 +369,8 @@ impl ReducedRelation for CatalogServerAction {
             CatalogServerAction::CanDeleteUsers => ServerRelation::CanDeleteUsers,
             CatalogServerAction::CanListUsers => ServerRelation::CanListAllProjects,
             CatalogServerAction::CanProvisionUsers => ServerRelation::CanProvisionUsers,
+            CatalogServerAction::CanManageNotices => ServerRelation::CanManageNotices,
+            CatalogServerAction::CanImpersonateUsers => ServerRelation::CanImpersonateUsers,
         }
     }
 }
@@ -377,6 +386,8 @@ impl ReducedRelation for APIServerAction {
             APIServerAction::ListUsers => ServerRelation::CanListUsers,
             APIServerAction::ProvisionUsers => ServerRelation::CanProvisionUsers,
             APIServerAction::ReadAssignments => ServerRelation::CanReadAssignments,
+            APIServerAction::ManageNotices => ServerRelation::CanManageNotices,
+            APIServerAction::ImpersonateUsers => ServerRelation::CanImpersonateUsers,
             APIServerAction::GrantAdmin => ServerRelation::CanGrantAdmin,
         }
     }
@@ -407,6 +418,8 @@ pub(super) enum ProjectRelation {
     CanCreateRole,
     CanListRoles,
     CanSearchRoles,
+    CanCreateDomain,
+    CanListDomains,
     CanReadAssignments,
     CanGrantRoleCreator,
     CanGrantCreate,
@@ -540,6 +553,8 @@ pub(super) enum APIProjectAction {
     CreateRole,
     ListRoles,
     SearchRoles,
+    CreateDomain,
+    ListDomains,
     ReadAssignments,
     GrantRoleCreator,
     GrantCreate,
@@ -580,6 +595,8 @@ impl ReducedRelation for APIProjectAction {
             APIProjectAction::CreateRole => ProjectRelation::CanCreateRole,
             APIProjectAction::ListRoles => ProjectRelation::CanListRoles,
             APIProjectAction::SearchRoles => ProjectRelation::CanSearchRoles,
+            APIProjectAction::CreateDomain => ProjectRelation::CanCreateDomain,
+            APIProjectAction::ListDomains => ProjectRelation::CanListDomains,
             APIProjectAction::ReadAssignments => ProjectRelation::CanReadAssignments,
             APIProjectAction::GrantRoleCreator => ProjectRelation::CanGrantRoleCreator,
             APIProjectAction::GrantCreate => ProjectRelation::CanGrantCreate,
@@ -607,6 +624,151 @@ impl ReducedRelation for CatalogProjectAction {
             CatalogProjectAction::CanCreateRole => ProjectRelation::CanCreateRole,
             CatalogProjectAction::CanListRoles => ProjectRelation::CanListRoles,
             CatalogProjectAction::CanSearchRoles => ProjectRelation::CanSearchRoles,
+            CatalogProjectAction::CanCreateDomain => ProjectRelation::CanCreateDomain,
+            CatalogProjectAction::CanListDomains => ProjectRelation::CanListDomains,
+        }
+    }
+}
+
+/// Domain Relations in the `OpenFGA` schema
+#[derive(Copy, Debug, Clone, strum_macros::Display, Hash, Eq, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub(super) enum DomainRelation {
+    // -- Hierarchical relations --
+    Project,
+    // -- Direct relations --
+    Ownership,
+    Describe,
+    Modify,
+    // -- Actions --
+    CanRead,
+    CanUpdate,
+    CanDelete,
+    CanManageNamespaces,
+    CanReadAssignments,
+    CanGrantDescribe,
+    CanGrantModify,
+    CanChangeOwnership,
+}
+
+impl OpenFgaRelation for DomainRelation {}
+
+#[derive(Debug, Clone, Deserialize, Copy, Eq, PartialEq, ToSchema, EnumIter)]
+#[serde(rename_all = "snake_case")]
+#[schema(as=DomainRelation)]
+pub(super) enum APIDomainRelation {
+    Ownership,
+    Describe,
+    Modify,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(super) enum DomainAssignment {
+    #[schema(title = "DomainAssignmentOwnership")]
+    Ownership(UserOrRole),
+    #[schema(title = "DomainAssignmentDescribe")]
+    Describe(UserOrRole),
+    #[schema(title = "DomainAssignmentModify")]
+    Modify(UserOrRole),
+}
+
+impl GrantableRelation for APIDomainRelation {
+    fn grant_relation(&self) -> DomainRelation {
+        match self {
+            APIDomainRelation::Ownership => DomainRelation::CanChangeOwnership,
+            APIDomainRelation::Describe => DomainRelation::CanGrantDescribe,
+            APIDomainRelation::Modify => DomainRelation::CanGrantModify,
+        }
+    }
+}
+
+impl Assignment for DomainAssignment {
+    type Relation = APIDomainRelation;
+
+    fn try_from_user(user: &str, relation: &Self::Relation) -> OpenFGAResult<Self> {
+        match relation {
+            APIDomainRelation::Ownership => {
+                UserOrRole::parse_from_openfga(user).map(DomainAssignment::Ownership)
+            }
+            APIDomainRelation::Describe => {
+                UserOrRole::parse_from_openfga(user).map(DomainAssignment::Describe)
+            }
+            APIDomainRelation::Modify => {
+                UserOrRole::parse_from_openfga(user).map(DomainAssignment::Modify)
+            }
+        }
+    }
+
+    fn openfga_user(&self) -> String {
+        match self {
+            DomainAssignment::Ownership(user)
+            | DomainAssignment::Describe(user)
+            | DomainAssignment::Modify(user) => user.to_openfga(),
+        }
+    }
+
+    fn relation(&self) -> Self::Relation {
+        match self {
+            DomainAssignment::Ownership(_) => APIDomainRelation::Ownership,
+            DomainAssignment::Describe(_) => APIDomainRelation::Describe,
+            DomainAssignment::Modify(_) => APIDomainRelation::Modify,
+        }
+    }
+}
+
+#[derive(Copy, Debug, Clone, Eq, PartialEq, Serialize, Deserialize, ToSchema, EnumIter)]
+#[serde(rename_all = "snake_case")]
+#[schema(as=DomainAction)]
+pub(super) enum APIDomainAction {
+    Read,
+    Update,
+    Delete,
+    ManageNamespaces,
+    ReadAssignments,
+    GrantDescribe,
+    GrantModify,
+    ChangeOwnership,
+}
+
+impl ReducedRelation for APIDomainRelation {
+    type OpenFgaRelation = DomainRelation;
+
+    fn to_openfga(&self) -> Self::OpenFgaRelation {
+        match self {
+            APIDomainRelation::Ownership => DomainRelation::Ownership,
+            APIDomainRelation::Describe => DomainRelation::Describe,
+            APIDomainRelation::Modify => DomainRelation::Modify,
+        }
+    }
+}
+
+impl ReducedRelation for APIDomainAction {
+    type OpenFgaRelation = DomainRelation;
+
+    fn to_openfga(&self) -> Self::OpenFgaRelation {
+        match self {
+            APIDomainAction::Read => DomainRelation::CanRead,
+            APIDomainAction::Update => DomainRelation::CanUpdate,
+            APIDomainAction::Delete => DomainRelation::CanDelete,
+            APIDomainAction::ManageNamespaces => DomainRelation::CanManageNamespaces,
+            APIDomainAction::ReadAssignments => DomainRelation::CanReadAssignments,
+            APIDomainAction::GrantDescribe => DomainRelation::CanGrantDescribe,
+            APIDomainAction::GrantModify => DomainRelation::CanGrantModify,
+            APIDomainAction::ChangeOwnership => DomainRelation::CanChangeOwnership,
+        }
+    }
+}
+
+impl ReducedRelation for CatalogDomainAction {
+    type OpenFgaRelation = DomainRelation;
+
+    fn to_openfga(&self) -> Self::OpenFgaRelation {
+        match self {
+            CatalogDomainAction::CanRead => DomainRelation::CanRead,
+            CatalogDomainAction::CanUpdate => DomainRelation::CanUpdate,
+            CatalogDomainAction::CanDelete => DomainRelation::CanDelete,
+            CatalogDomainAction::CanManageNamespaces => DomainRelation::CanManageNamespaces,
         }
     }
 }
@@ -627,15 +789,26 @@ pub(super) enum WarehouseRelation {
     Select,
     Create,
     Modify,
+    StorageRotator,
+    StorageCredentialReader,
     // -- Actions --
     CanCreateNamespace,
     CanDelete,
     CanUpdateStorage,
     CanUpdateStorageCredential,
+    CanGetStorageProfile,
+    CanGetStorageCredentialId,
+    CanRotateStorageCredential,
     CanGetMetadata,
     CanGetConfig,
     CanListNamespaces,
     CanModifySoftDeletion,
+    CanUpdateNamespaceDefaults,
+    CanUpdateNetworkPolicy,
+    CanUpdateTableLimits,
+    CanUpdateSnapshotSummaryEnrichment,
+    CanUpdateWasmHooks,
+    CanSetExpirationExemption,
     CanUse,
     CanIncludeInList,
     CanDeactivate,
@@ -647,6 +820,8 @@ pub(super) enum WarehouseRelation {
     CanGrantDescribe,
     CanGrantModify,
     CanGrantSelect,
+    CanGrantStorageRotator,
+    CanGrantStorageCredentialReader,
     CanGrantPassGrants,
     CanGrantManageGrants,
     CanChangeOwnership,
@@ -666,6 +841,8 @@ pub(super) enum APIWarehouseRelation {
     Select,
     Create,
     Modify,
+    StorageRotator,
+    StorageCredentialReader,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
@@ -685,6 +862,10 @@ pub(super) enum WarehouseAssignment {
     Create(UserOrRole),
     #[schema(title = "WarehouseAssignmentModify")]
     Modify(UserOrRole),
+    #[schema(title = "WarehouseAssignmentStorageRotator")]
+    StorageRotator(UserOrRole),
+    #[schema(title = "WarehouseAssignmentStorageCredentialReader")]
+    StorageCredentialReader(UserOrRole),
 }
 
 impl GrantableRelation for APIWarehouseRelation {
@@ -697,6 +878,10 @@ impl GrantableRelation for APIWarehouseRelation {
             APIWarehouseRelation::Select => WarehouseRelation::CanGrantSelect,
             APIWarehouseRelation::Create => WarehouseRelation::CanGrantCreate,
             APIWarehouseRelation::Modify => WarehouseRelation::CanGrantModify,
+            APIWarehouseRelation::StorageRotator => WarehouseRelation::CanGrantStorageRotator,
+            APIWarehouseRelation::StorageCredentialReader => {
+                WarehouseRelation::CanGrantStorageCredentialReader
+            }
         }
     }
 }
@@ -727,6 +912,11 @@ impl Assignment for WarehouseAssignment {
             APIWarehouseRelation::Modify => {
                 UserOrRole::parse_from_openfga(user).map(WarehouseAssignment::Modify)
             }
+            APIWarehouseRelation::StorageRotator => {
+                UserOrRole::parse_from_openfga(user).map(WarehouseAssignment::StorageRotator)
+            }
+            APIWarehouseRelation::StorageCredentialReader => UserOrRole::parse_from_openfga(user)
+                .map(WarehouseAssignment::StorageCredentialReader),
         }
     }
 
@@ -738,6 +928,8 @@ impl Assignment for WarehouseAssignment {
             | WarehouseAssignment::Select(user)
             | WarehouseAssignment::Create(user)
             | WarehouseAssignment::Modify(user)
+            | WarehouseAssignment::StorageRotator(user)
+            | WarehouseAssignment::StorageCredentialReader(user)
             | WarehouseAssignment::ManageGrants(user) => user.to_openfga(),
         }
     }
@@ -751,6 +943,10 @@ impl Assignment for WarehouseAssignment {
             WarehouseAssignment::Select { .. } => APIWarehouseRelation::Select,
             WarehouseAssignment::Create { .. } => APIWarehouseRelation::Create,
             WarehouseAssignment::Modify { .. } => APIWarehouseRelation::Modify,
+            WarehouseAssignment::StorageRotator { .. } => APIWarehouseRelation::StorageRotator,
+            WarehouseAssignment::StorageCredentialReader { .. } => {
+                APIWarehouseRelation::StorageCredentialReader
+            }
         }
     }
 }
@@ -763,6 +959,9 @@ pub(super) enum APIWarehouseAction {
     Delete,
     ModifyStorage,
     ModifyStorageCredential,
+    GetStorageProfile,
+    GetStorageCredentialId,
+    RotateStorageCredential,
     GetConfig,
     GetMetadata,
     ListNamespaces,
@@ -776,6 +975,8 @@ pub(super) enum APIWarehouseAction {
     GrantDescribe,
     GrantModify,
     GrantSelect,
+    GrantStorageRotator,
+    GrantStorageCredentialReader,
     GrantPassGrants,
     GrantManageGrants,
     ChangeOwnership,
@@ -793,6 +994,10 @@ impl ReducedRelation for APIWarehouseRelation {
             APIWarehouseRelation::Select => WarehouseRelation::Select,
             APIWarehouseRelation::Create => WarehouseRelation::Create,
             APIWarehouseRelation::Modify => WarehouseRelation::Modify,
+            APIWarehouseRelation::StorageRotator => WarehouseRelation::StorageRotator,
+            APIWarehouseRelation::StorageCredentialReader => {
+                WarehouseRelation::StorageCredentialReader
+            }
         }
     }
 }
@@ -808,6 +1013,13 @@ impl ReducedRelation for APIWarehouseAction {
             APIWarehouseAction::ModifyStorageCredential => {
                 WarehouseRelation::CanUpdateStorageCredential
             }
+            APIWarehouseAction::GetStorageProfile => WarehouseRelation::CanGetStorageProfile,
+            APIWarehouseAction::GetStorageCredentialId => {
+                WarehouseRelation::CanGetStorageCredentialId
+            }
+            APIWarehouseAction::RotateStorageCredential => {
+                WarehouseRelation::CanRotateStorageCredential
+            }
             APIWarehouseAction::GetMetadata => WarehouseRelation::CanGetMetadata,
             APIWarehouseAction::GetConfig => WarehouseRelation::CanGetConfig,
             APIWarehouseAction::ListNamespaces => WarehouseRelation::CanListNamespaces,
@@ -821,6 +1033,10 @@ impl ReducedRelation for APIWarehouseAction {
             APIWarehouseAction::GrantDescribe => WarehouseRelation::CanGrantDescribe,
             APIWarehouseAction::GrantModify => WarehouseRelation::CanGrantModify,
             APIWarehouseAction::GrantSelect => WarehouseRelation::CanGrantSelect,
+            APIWarehouseAction::GrantStorageRotator => WarehouseRelation::CanGrantStorageRotator,
+            APIWarehouseAction::GrantStorageCredentialReader => {
+                WarehouseRelation::CanGrantStorageCredentialReader
+            }
             APIWarehouseAction::GrantPassGrants => WarehouseRelation::CanGrantPassGrants,
             APIWarehouseAction::GrantManageGrants => WarehouseRelation::CanGrantManageGrants,
             APIWarehouseAction::ChangeOwnership => WarehouseRelation::CanChangeOwnership,
@@ -839,12 +1055,33 @@ impl ReducedRelation for CatalogWarehouseAction {
             CatalogWarehouseAction::CanUpdateStorageCredential => {
                 WarehouseRelation::CanUpdateStorageCredential
             }
+            CatalogWarehouseAction::CanGetStorageProfile => WarehouseRelation::CanGetStorageProfile,
+            CatalogWarehouseAction::CanGetStorageCredentialId => {
+                WarehouseRelation::CanGetStorageCredentialId
+            }
+            CatalogWarehouseAction::CanRotateStorageCredential => {
+                WarehouseRelation::CanRotateStorageCredential
+            }
             CatalogWarehouseAction::CanGetMetadata => WarehouseRelation::CanGetMetadata,
             CatalogWarehouseAction::CanGetConfig => WarehouseRelation::CanGetConfig,
             CatalogWarehouseAction::CanListNamespaces => WarehouseRelation::CanListNamespaces,
             CatalogWarehouseAction::CanModifySoftDeletion => {
                 WarehouseRelation::CanModifySoftDeletion
             }
+            CatalogWarehouseAction::CanUpdateNamespaceDefaults => {
+                WarehouseRelation::CanUpdateNamespaceDefaults
+            }
+            CatalogWarehouseAction::CanUpdateNetworkPolicy => {
+                WarehouseRelation::CanUpdateNetworkPolicy
+            }
+            CatalogWarehouseAction::CanUpdateTableLimits => WarehouseRelation::CanUpdateTableLimits,
+            CatalogWarehouseAction::CanUpdateSnapshotSummaryEnrichment => {
+                WarehouseRelation::CanUpdateSnapshotSummaryEnrichment
+            }
+            CatalogWarehouseAction::CanUpdateWasmHooks => WarehouseRelation::CanUpdateWasmHooks,
+            CatalogWarehouseAction::CanSetExpirationExemption => {
+                WarehouseRelation::CanSetExpirationExemption
+            }
             CatalogWarehouseAction::CanUse => WarehouseRelation::CanUse,
             CatalogWarehouseAction::CanIncludeInList => WarehouseRelation::CanIncludeInList,
             CatalogWarehouseAction::CanDeactivate => WarehouseRelation::CanDeactivate,
@@ -880,6 +1117,7 @@ pub(super) enum NamespaceRelation {
     CanCreateNamespace,
     CanDelete,
     CanUpdateProperties,
+    CanRename,
     CanGetMetadata,
     CanListTables,
     CanListViews,
@@ -1077,6 +1315,7 @@ impl ReducedRelation for CatalogNamespaceAction {
             CatalogNamespaceAction::CanCreateNamespace => NamespaceRelation::CanCreateNamespace,
             CatalogNamespaceAction::CanDelete => NamespaceRelation::CanDelete,
             CatalogNamespaceAction::CanUpdateProperties => NamespaceRelation::CanUpdateProperties,
+            CatalogNamespaceAction::CanRename => NamespaceRelation::CanRename,
             CatalogNamespaceAction::CanGetMetadata => NamespaceRelation::CanGetMetadata,
             CatalogNamespaceAction::CanListTables => NamespaceRelation::CanListTables,
             CatalogNamespaceAction::CanListViews => NamespaceRelation::CanListViews,
@@ -1099,6 +1338,7 @@ pub(super) enum TableRelation {
     Modify,
     // -- Actions --
     CanDrop,
+    CanForceDrop,
     CanWriteData,
     CanReadData,
     CanGetMetadata,
@@ -1282,6 +1522,7 @@ impl ReducedRelation for CatalogTableAction {
     fn to_openfga(&self) -> Self::OpenFgaRelation {
         match self {
             CatalogTableAction::CanDrop => TableRelation::CanDrop,
+            CatalogTableAction::CanForceDrop => TableRelation::CanForceDrop,
             CatalogTableAction::CanWriteData => TableRelation::CanWriteData,
             CatalogTableAction::CanReadData => TableRelation::CanReadData,
             CatalogTableAction::CanGetMetadata => TableRelation::CanGetMetadata,
@@ -0,0 +1,565 @@
+//! Delegates every authorization decision to an external policy decision point,
+//! for example OPA or an in-house service.
+//!
+//! For each decision the authorizer sends a `POST` request with a JSON body to the
+//! configured URL:
+//!
+//! ```json
+//! {
+//!   "principal": "oidc~4c1f...",
+//!   "assumed-role": null,
+//!   "impersonated-by": null,
+//!   "engine": "trino",
+//!   "action": "can_drop",
+//!   "object": {"type": "table", "id": "0194..."},
+//!   "request-id": "0194..."
+//! }
+//! ```
+//!
+//! The service must answer with `{"allowed": true | false}`. Any other response, an error
+//! status or a timeout denies the request.
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use utoipa::OpenApi;
+
+use super::FgaType;
+use crate::api::iceberg::v1::Result;
+use crate::api::ApiContext;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authn::{Actor, UserId};
+use crate::service::authz::{
+    nested_roles_not_supported, Authorizer, CatalogDomainAction, CatalogNamespaceAction,
+    CatalogProjectAction, CatalogRoleAction, CatalogServerAction, CatalogTableAction,
+    CatalogUserAction, CatalogViewAction, CatalogWarehouseAction, ErrorModel, ListProjectsResponse,
+    NamespaceParent,
+};
+use crate::service::health::{Health, HealthExt, HealthStatus};
+use crate::service::{
+    Catalog, DomainId, NamespaceIdentUuid, ProjectIdent, RoleId, SecretStore, State,
+    TableIdentUuid, ViewIdentUuid, WarehouseIdent,
+};
+use crate::CONFIG;
+
+const HEALTH_NAME: &str = "authz-webhook";
+/// Server level action that allows a principal to list all projects. Projects can't be
+/// enumerated by the policy service, so principals without it see no projects.
+const LIST_ALL_PROJECTS_ACTION: &str = "can_list_all_projects";
+/// Server level action that allows a principal to search users.
+const SEARCH_USERS_ACTION: &str = "can_search_users";
+
+#[derive(Clone)]
+pub struct WebhookAuthorizer {
+    client: reqwest::Client,
+    url: url::Url,
+    token: Option<String>,
+    cache: Option<moka::sync::Cache<Decision, bool>>,
+    last_request_failed: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for WebhookAuthorizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookAuthorizer")
+            .field("url", &self.url)
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .field("cache_enabled", &self.cache.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Identifies a decision. Decisions are cached by this key, so it must contain
+/// everything that is sent to the policy service except for the request id.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct Decision {
+    principal: Option<String>,
+    assumed_role: Option<String>,
+    impersonated_by: Option<String>,
+    engine: Option<String>,
+    action: String,
+    object: DecisionObject,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize)]
+struct DecisionObject {
+    #[serde(rename = "type")]
+    object_type: String,
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct DecisionRequest<'a> {
+    #[serde(flatten)]
+    decision: &'a Decision,
+    request_id: uuid::Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+struct DecisionResponse {
+    allowed: bool,
+}
+
+/// Create the webhook authorizer from `LAKEKEEPER__AUTHZ_WEBHOOK__*`.
+///
+/// # Errors
+/// Fails if the webhook is not configured or the HTTP client cannot be built.
+pub fn new_authorizer_from_config() -> std::result::Result<WebhookAuthorizer, ErrorModel> {
+    let config = CONFIG.authz_webhook.as_ref().ok_or_else(|| {
+        ErrorModel::internal(
+            "`LAKEKEEPER__AUTHZ_WEBHOOK__URL` is required if the `webhook` authorization backend is used",
+            "AuthorizationWebhookNotConfigured",
+            None,
+        )
+    })?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(config.timeout_ms))
+        .build()
+        .map_err(|e| {
+            ErrorModel::internal(
+                "Failed to build HTTP client for authorization webhook",
+                "AuthorizationWebhookError",
+                Some(Box::new(e)),
+            )
+        })?;
+    let cache = (config.cache_ttl_seconds > 0).then(|| {
+        moka::sync::Cache::builder()
+            .max_capacity(100_000)
+            .time_to_live(Duration::from_secs(config.cache_ttl_seconds))
+            .build()
+    });
+
+    Ok(WebhookAuthorizer {
+        client,
+        url: config.url.clone(),
+        token: config.token.clone(),
+        cache,
+        last_request_failed: Arc::new(AtomicBool::new(false)),
+    })
+}
+
+impl Decision {
+    fn new(
+        metadata: &RequestMetadata,
+        action: impl std::fmt::Display,
+        object_type: &FgaType,
+        id: impl std::fmt::Display,
+    ) -> Self {
+        let (principal, assumed_role) = match metadata.actor() {
+            Actor::Anonymous => (None, None),
+            Actor::Principal(user_id) => (Some(user_id.to_string()), None),
+            Actor::Role {
+                principal,
+                assumed_role,
+            } => (Some(principal.to_string()), Some(assumed_role.to_string())),
+        };
+        Self {
+            principal,
+            assumed_role,
+            impersonated_by: metadata.impersonated_by().map(ToString::to_string),
+            engine: metadata.engine().map(ToString::to_string),
+            action: action.to_string(),
+            object: DecisionObject {
+                object_type: object_type.to_string(),
+                id: id.to_string(),
+            },
+        }
+    }
+}
+
+impl WebhookAuthorizer {
+    async fn is_allowed(&self, metadata: &RequestMetadata, decision: Decision) -> Result<bool> {
+        if let Some(allowed) = self.cache.as_ref().and_then(|c| c.get(&decision)) {
+            return Ok(allowed);
+        }
+
+        let allowed = self
+            .request_decision(metadata, &decision)
+            .await
+            .inspect_err(|e| {
+                tracing::warn!("Authorization webhook failed, denying request: {e:?}");
+            });
+        self.last_request_failed
+            .store(allowed.is_err(), Ordering::Relaxed);
+        let allowed = allowed.map_err(|e| {
+            ErrorModel::failed_dependency(
+                "Authorization service did not return a decision",
+                "AuthorizationWebhookError",
+                Some(Box::new(e)),
+            )
+        })?;
+
+        if let Some(cache) = &self.cache {
+            cache.insert(decision, allowed);
+        }
+        Ok(allowed)
+    }
+
+    async fn request_decision(
+        &self,
+        metadata: &RequestMetadata,
+        decision: &Decision,
+    ) -> reqwest::Result<bool> {
+        let request = self.client.post(self.url.clone());
+        let request = match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+        let response = request
+            .json(&DecisionRequest {
+                decision,
+                request_id: metadata.request_id,
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<DecisionResponse>()
+            .await?;
+        Ok(response.allowed)
+    }
+}
+
+#[async_trait]
+impl HealthExt for WebhookAuthorizer {
+    async fn health(&self) -> Vec<Health> {
+        let status = if self.last_request_failed.load(Ordering::Relaxed) {
+            HealthStatus::Unhealthy
+        } else {
+            HealthStatus::Healthy
+        };
+        vec![Health::now(HEALTH_NAME, status)]
+    }
+    async fn update_health(&self) {
+        // Health is derived from the most recent decision request
+    }
+}
+
+#[derive(Debug, OpenApi)]
+#[openapi()]
+pub(super) struct ApiDoc;
+
+#[async_trait]
+impl Authorizer for WebhookAuthorizer {
+    fn api_doc() -> utoipa::openapi::OpenApi {
+        ApiDoc::openapi()
+    }
+
+    fn new_router<C: Catalog, S: SecretStore>(&self) -> Router<ApiContext<State<Self, C, S>>> {
+        Router::new()
+    }
+
+    async fn can_bootstrap(&self, metadata: &RequestMetadata) -> Result<()> {
+        if matches!(metadata.actor(), Actor::Anonymous) {
+            return Err(ErrorModel::unauthorized(
+                "Anonymous users cannot bootstrap the catalog",
+                "AnonymousBootstrap",
+                None,
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    async fn bootstrap(&self, _metadata: &RequestMetadata, _is_operator: bool) -> Result<()> {
+        // Permissions are managed in the policy service
+        Ok(())
+    }
+
+    async fn list_projects(&self, metadata: &RequestMetadata) -> Result<ListProjectsResponse> {
+        let decision = Decision::new(
+            metadata,
+            LIST_ALL_PROJECTS_ACTION,
+            &FgaType::Server,
+            CONFIG.server_id,
+        );
+        if self.is_allowed(metadata, decision).await? {
+            Ok(ListProjectsResponse::All)
+        } else {
+            Ok(ListProjectsResponse::Projects(
+                std::collections::HashSet::new(),
+            ))
+        }
+    }
+
+    async fn can_search_users(&self, metadata: &RequestMetadata) -> Result<bool> {
+        let decision = Decision::new(
+            metadata,
+            SEARCH_USERS_ACTION,
+            &FgaType::Server,
+            CONFIG.server_id,
+        );
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_user_action(
+        &self,
+        metadata: &RequestMetadata,
+        user_id: &UserId,
+        action: &CatalogUserAction,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::User, user_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_role_action(
+        &self,
+        metadata: &RequestMetadata,
+        role_id: RoleId,
+        action: &CatalogRoleAction,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::Role, role_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_domain_action(
+        &self,
+        metadata: &RequestMetadata,
+        domain_id: DomainId,
+        action: &CatalogDomainAction,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::Domain, domain_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_server_action(
+        &self,
+        metadata: &RequestMetadata,
+        action: &CatalogServerAction,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::Server, CONFIG.server_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_project_action(
+        &self,
+        metadata: &RequestMetadata,
+        project_id: ProjectIdent,
+        action: &CatalogProjectAction,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::Project, project_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_warehouse_action(
+        &self,
+        metadata: &RequestMetadata,
+        warehouse_id: WarehouseIdent,
+        action: &CatalogWarehouseAction,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::Warehouse, warehouse_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_namespace_action(
+        &self,
+        metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        action: impl From<&CatalogNamespaceAction> + std::fmt::Display + Send,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::Namespace, namespace_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_table_action(
+        &self,
+        metadata: &RequestMetadata,
+        table_id: TableIdentUuid,
+        action: impl From<&CatalogTableAction> + std::fmt::Display + Send,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::Table, table_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn is_allowed_view_action(
+        &self,
+        metadata: &RequestMetadata,
+        view_id: ViewIdentUuid,
+        action: impl From<&CatalogViewAction> + std::fmt::Display + Send,
+    ) -> Result<bool> {
+        let decision = Decision::new(metadata, action, &FgaType::View, view_id);
+        self.is_allowed(metadata, decision).await
+    }
+
+    async fn delete_user(&self, _metadata: &RequestMetadata, _user_id: UserId) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_role(
+        &self,
+        _metadata: &RequestMetadata,
+        _role_id: RoleId,
+        _parent_project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_role(&self, _metadata: &RequestMetadata, _role_id: RoleId) -> Result<()> {
+        Ok(())
+    }
+
+    async fn add_role_member(
+        &self,
+        _metadata: &RequestMetadata,
+        _role_id: RoleId,
+        _member_role_id: RoleId,
+    ) -> Result<()> {
+        Err(nested_roles_not_supported())
+    }
+
+    async fn remove_role_member(
+        &self,
+        _metadata: &RequestMetadata,
+        _role_id: RoleId,
+        _member_role_id: RoleId,
+    ) -> Result<()> {
+        Err(nested_roles_not_supported())
+    }
+
+    async fn list_role_members(&self, _role_id: RoleId) -> Result<Option<Vec<RoleId>>> {
+        Ok(None)
+    }
+
+    async fn list_role_assignee_users(&self, _role_id: RoleId) -> Result<Option<Vec<UserId>>> {
+        Ok(None)
+    }
+
+    async fn create_domain(
+        &self,
+        _metadata: &RequestMetadata,
+        _domain_id: DomainId,
+        _parent_project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_domain(&self, _metadata: &RequestMetadata, _domain_id: DomainId) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_project(
+        &self,
+        _metadata: &RequestMetadata,
+        _project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_project(
+        &self,
+        _metadata: &RequestMetadata,
+        _project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_warehouse(
+        &self,
+        _metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+        _parent_project_id: ProjectIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_warehouse(
+        &self,
+        _metadata: &RequestMetadata,
+        _warehouse_id: WarehouseIdent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn move_namespace(
+        &self,
+        _metadata: &RequestMetadata,
+        _namespace_id: NamespaceIdentUuid,
+        _previous_parent: NamespaceParent,
+        _new_parent: NamespaceParent,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_table(
+        &self,
+        _metadata: &RequestMetadata,
+        _table_id: TableIdentUuid,
+        _parent: NamespaceIdentUuid,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_table(&self, _table_id: TableIdentUuid) -> Result<()> {
+        Ok(())
+    }
+
+    async fn create_view(
+        &self,
+        _metadata: &RequestMetadata,
+        _view_id: ViewIdentUuid,
+        _parent: NamespaceIdentUuid,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_view(&self, _view_id: ViewIdentUuid) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decision_request_body() {
+        let table_id = TableIdentUuid::from(uuid::Uuid::now_v7());
+        let mut metadata = RequestMetadata::random_human(UserId::OIDC("user-1".to_string()));
+        metadata.engine = Some("trino".to_string());
+        let decision = Decision::new(
+            &metadata,
+            CatalogTableAction::CanDrop,
+            &FgaType::Table,
+            table_id,
+        );
+        let body = serde_json::to_value(DecisionRequest {
+            decision: &decision,
+            request_id: metadata.request_id,
+        })
+        .unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "principal": "oidc~user-1",
+                "assumed-role": null,
+                "impersonated-by": null,
+                "engine": "trino",
+                "action": "can_drop",
+                "object": {"type": "table", "id": table_id.to_string()},
+                "request-id": metadata.request_id.to_string(),
+            })
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use super::health::HealthExt;
 use super::{
-    Catalog, NamespaceIdentUuid, ProjectIdent, RoleId, SecretStore, State, TableIdentUuid,
-    TabularDetails, ViewIdentUuid, WarehouseIdent,
+    Catalog, DomainId, NamespaceIdentUuid, ProjectIdent, RoleId, SecretStore, State,
+    TableIdentUuid, TabularDetails, ViewIdentUuid, WarehouseIdent,
 };
 use crate::api::iceberg::v1::Result;
 use crate::request_metadata::RequestMetadata;
@@ -40,6 +40,10 @@ pub enum CatalogServerAction {
     CanListUsers,
     /// Can provision user
     CanProvisionUsers,
+    /// Can create and delete server notices.
+    CanManageNotices,
+    /// Can act as other users to troubleshoot their permissions.
+    CanImpersonateUsers,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
@@ -54,6 +58,8 @@ pub enum CatalogProjectAction {
     CanCreateRole,
     CanListRoles,
     CanSearchRoles,
+    CanCreateDomain,
+    CanListDomains,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
@@ -64,13 +70,30 @@ pub enum CatalogRoleAction {
     CanRead,
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
+#[strum(serialize_all = "snake_case")]
+pub enum CatalogDomainAction {
+    CanRead,
+    CanUpdate,
+    CanDelete,
+    /// Add namespaces to or remove namespaces from the domain.
+    CanManageNamespaces,
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
 #[strum(serialize_all = "snake_case")]
 pub enum CatalogWarehouseAction {
     CanCreateNamespace,
     CanDelete,
     CanUpdateStorage,
+    /// Set or remove the storage credential of a warehouse.
     CanUpdateStorageCredential,
+    /// View the storage profile of a warehouse. Never includes credentials.
+    CanGetStorageProfile,
+    /// View the id of the secret holding the storage credential.
+    CanGetStorageCredentialId,
+    /// Replace an existing storage credential with a new one.
+    CanRotateStorageCredential,
     CanGetMetadata,
     CanGetConfig,
     CanListNamespaces,
@@ -81,6 +104,18 @@ pub enum CatalogWarehouseAction {
     CanRename,
     CanListDeletedTabulars,
     CanModifySoftDeletion,
+    /// Set the properties applied to namespaces created without them.
+    CanUpdateNamespaceDefaults,
+    /// Restrict the client networks the warehouse can be accessed from.
+    CanUpdateNetworkPolicy,
+    /// Set the limits on the metadata of tables in the warehouse.
+    CanUpdateTableLimits,
+    /// Configure the provenance added to snapshot summaries of the warehouse.
+    CanUpdateSnapshotSummaryEnrichment,
+    /// Set the WASM hooks run as contract verifiers for the warehouse.
+    CanUpdateWasmHooks,
+    /// Exempt soft-deleted tabulars from expiration, for example for a legal hold.
+    CanSetExpirationExemption,
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, strum_macros::Display, EnumIter)]
@@ -91,6 +126,8 @@ pub enum CatalogNamespaceAction {
     CanCreateNamespace,
     CanDelete,
     CanUpdateProperties,
+    /// Rename the namespace. Moving it to another parent is not supported.
+    CanRename,
     CanGetMetadata,
     CanListTables,
     CanListViews,
@@ -101,6 +138,8 @@ pub enum CatalogNamespaceAction {
 #[strum(serialize_all = "snake_case")]
 pub enum CatalogTableAction {
     CanDrop,
+    /// Drop a table that views depend on
+    CanForceDrop,
     CanWriteData,
     CanReadData,
     CanGetMetadata,
@@ -151,6 +190,16 @@ pub enum NamespaceParent {
     Namespace(NamespaceIdentUuid),
 }
 
+/// Error for authorizers that cannot store role memberships.
+pub(crate) fn nested_roles_not_supported() -> crate::service::IcebergErrorResponse {
+    ErrorModel::bad_request(
+        "Nested roles require an authorization backend that stores role memberships.",
+        "NestedRolesNotSupported",
+        None,
+    )
+    .into()
+}
+
 #[async_trait::async_trait]
 /// Interface to provide AuthZ functions to the catalog.
 pub trait Authorizer
@@ -193,6 +242,15 @@ where
         action: &CatalogRoleAction,
     ) -> Result<bool>;
 
+    /// Return Ok(true) if the action is allowed, otherwise return Ok(false).
+    /// Return Err for internal errors.
+    async fn is_allowed_domain_action(
+        &self,
+        metadata: &RequestMetadata,
+        domain_id: DomainId,
+        action: &CatalogDomainAction,
+    ) -> Result<bool>;
+
     /// Return Ok(true) if the action is allowed, otherwise return Ok(false).
     /// Return Err for internal errors.
     async fn is_allowed_server_action(
@@ -262,6 +320,49 @@ where
     /// This is used to clean up permissions for the role.
     async fn delete_role(&self, metadata: &RequestMetadata, role_id: RoleId) -> Result<()>;
 
+    /// Make `member_role_id` a member of `role_id`.
+    /// Assignees of `member_role_id` must receive all privileges of `role_id`.
+    /// The authorizer is the only store of memberships, so it must reject
+    /// memberships that would close a cycle. Adding an existing membership succeeds.
+    /// Callers hold the lock of [`Catalog::lock_role_members`](crate::service::Catalog::lock_role_members)
+    /// of the project, so that the cycle check does not race with other additions.
+    async fn add_role_member(
+        &self,
+        metadata: &RequestMetadata,
+        role_id: RoleId,
+        member_role_id: RoleId,
+    ) -> Result<()>;
+
+    /// Remove `member_role_id` from `role_id`.
+    /// Fails with 404 if `member_role_id` is not a direct member of `role_id`.
+    async fn remove_role_member(
+        &self,
+        metadata: &RequestMetadata,
+        role_id: RoleId,
+        member_role_id: RoleId,
+    ) -> Result<()>;
+
+    /// Roles that are direct members of the role.
+    /// Returns `None` if role memberships are not managed by this authorizer.
+    async fn list_role_members(&self, role_id: RoleId) -> Result<Option<Vec<RoleId>>>;
+
+    /// Users that are directly assigned to the role.
+    /// Returns `None` if role assignments are not managed by this authorizer.
+    async fn list_role_assignee_users(&self, role_id: RoleId) -> Result<Option<Vec<UserId>>>;
+
+    /// Hook that is called when a new domain is created.
+    /// This is used to set up the initial permissions for the domain.
+    async fn create_domain(
+        &self,
+        metadata: &RequestMetadata,
+        domain_id: DomainId,
+        parent_project_id: ProjectIdent,
+    ) -> Result<()>;
+
+    /// Hook that is called when a domain is deleted.
+    /// This is used to clean up permissions for the domain.
+    async fn delete_domain(&self, metadata: &RequestMetadata, domain_id: DomainId) -> Result<()>;
+
     /// Hook that is called when a new project is created.
     /// This is used to set up the initial permissions for the project.
     async fn create_project(
@@ -312,6 +413,17 @@ where
         namespace_id: NamespaceIdentUuid,
     ) -> Result<()>;
 
+    /// Hook that is called after moving a namespace to a different parent has been committed.
+    /// Permissions inherited from the previous parent no longer apply.
+    /// If the hook fails, the move is reverted.
+    async fn move_namespace(
+        &self,
+        metadata: &RequestMetadata,
+        namespace_id: NamespaceIdentUuid,
+        previous_parent: NamespaceParent,
+        new_parent: NamespaceParent,
+    ) -> Result<()>;
+
     /// Hook that is called when a new table is created.
     /// This is used to set up the initial permissions for the table.
     async fn create_table(
@@ -393,6 +505,27 @@ where
         }
     }
 
+    async fn require_domain_action(
+        &self,
+        metadata: &RequestMetadata,
+        domain_id: DomainId,
+        action: &CatalogDomainAction,
+    ) -> Result<()> {
+        if self
+            .is_allowed_domain_action(metadata, domain_id, action)
+            .await?
+        {
+            Ok(())
+        } else {
+            Err(ErrorModel::forbidden(
+                format!("Forbidden action {action} on domain {domain_id}"),
+                "DomainActionForbidden",
+                None,
+            )
+            .into())
+        }
+    }
+
     async fn require_server_action(
         &self,
         metadata: &RequestMetadata,
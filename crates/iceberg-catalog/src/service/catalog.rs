@@ -1,30 +1,40 @@
 use super::authz::TableUuid;
 use super::{
-    storage::StorageProfile, NamespaceIdentUuid, ProjectIdent, RoleId, TableIdentUuid,
-    TabularDetails, ViewIdentUuid, WarehouseIdent, WarehouseStatus,
+    storage::StorageProfile, DomainId, NamespaceIdentUuid, ProjectIdent, RoleId, TableIdentUuid,
+    TabularDetails, ViewIdentUuid, WarehouseIdent, WarehouseStatus, WarehouseStorageHealth,
 };
 pub use crate::api::iceberg::v1::{
     CreateNamespaceRequest, CreateNamespaceResponse, ListNamespacesQuery, NamespaceIdent, Result,
     TableIdent, UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
 };
-use crate::api::iceberg::v1::{PaginatedMapping, PaginationQuery};
+use crate::api::iceberg::v1::{PaginatedMapping, PaginationQuery, ReturnTotal};
 use crate::service::health::HealthExt;
 use crate::SecretIdent;
 
-use crate::api::management::v1::role::{ListRolesResponse, Role, SearchRoleResponse};
+use crate::api::management::v1::domain::{Domain, DomainNamespace};
+use crate::api::management::v1::notice::{Notice, NoticeSeverity};
+use crate::api::management::v1::property_history::PropertyVersion;
+use crate::api::management::v1::role::{
+    ListRolesResponse, Role, RoleListFilter, SearchRoleResponse,
+};
+use crate::api::management::v1::table::TableOverview;
 use crate::api::management::v1::user::{
-    ListUsersResponse, SearchUserResponse, User, UserLastUpdatedWith, UserType,
+    ListUsersResponse, SearchUserResponse, User, UserLastUpdatedWith, UserListFilter, UserType,
+};
+use crate::api::management::v1::warehouse::{
+    DeletedTabularListFilter, SnapshotSummaryEnrichment, TableLimits, TabularDeleteProfile,
+    WarehouseLifecycle, WarehouseSummary,
 };
-use crate::api::management::v1::warehouse::TabularDeleteProfile;
 use crate::service::tabular_idents::{TabularIdentOwned, TabularIdentUuid};
-use iceberg::spec::{TableMetadata, ViewMetadata};
+use iceberg::spec::{Schema, TableMetadata, ViewMetadata};
 use iceberg_ext::catalog::rest::{CatalogConfig, ErrorModel};
-pub use iceberg_ext::catalog::rest::{CommitTableResponse, CreateTableRequest};
+pub use iceberg_ext::catalog::rest::{CommitTableResponse, CreateTableRequest, TabularAccess};
 use iceberg_ext::configs::Location;
 
 use crate::catalog::tables::TableMetadataDiffs;
+use crate::config::StaleUserAction;
 use crate::service::authn::UserId;
-use crate::service::task_queue::TaskId;
+use crate::service::task_queue::{Task, TaskId};
 use iceberg::TableUpdate;
 use std::collections::{HashMap, HashSet};
 
@@ -119,6 +129,126 @@ pub struct GetWarehouseResponse {
     pub status: WarehouseStatus,
     /// Tabular delete profile used for the warehouse.
     pub tabular_delete_profile: TabularDeleteProfile,
+    /// Result of the most recent background storage check.
+    pub storage_health: WarehouseStorageHealth,
+    /// Properties applied to namespaces created without them.
+    pub namespace_defaults: HashMap<String, String>,
+    /// Whether the warehouse is torn down automatically.
+    pub lifecycle: WarehouseLifecycle,
+    /// Networks clients must connect from. Empty if access is not restricted.
+    pub allowed_client_networks: Vec<ipnet::IpNet>,
+    /// Limits on the metadata of tables in the warehouse.
+    pub table_limits: TableLimits,
+    /// Provenance added to the summaries of committed snapshots.
+    pub snapshot_summary_enrichment: SnapshotSummaryEnrichment,
+    /// Names of the WASM hooks run as contract verifiers for tables and views of the warehouse.
+    pub wasm_hooks: Vec<String>,
+    /// Counts and last activity, maintained on every change of the warehouse.
+    pub summary: WarehouseSummary,
+}
+
+/// Metrics reported by an engine for a single scan or commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableMetricsReport {
+    Scan {
+        /// Number of data files in the scan result.
+        data_files: i64,
+        /// Size of the data files in the scan result.
+        bytes: i64,
+        /// Time spent planning the scan in milliseconds.
+        planning_duration_ms: i64,
+    },
+    Commit {
+        /// Number of data files added by the commit.
+        data_files: i64,
+        /// Size of the data files added by the commit.
+        bytes: i64,
+    },
+}
+
+/// Engine metrics of a table aggregated over all reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TableMetrics {
+    pub scan_count: i64,
+    pub scanned_data_files: i64,
+    pub scanned_bytes: i64,
+    pub total_planning_duration_ms: i64,
+    pub commit_count: i64,
+    pub committed_data_files: i64,
+    pub committed_bytes: i64,
+    pub last_scan_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_commit_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Access to a table or view that is tracked by the catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TabularAccessKind {
+    /// The table or view was loaded.
+    Read,
+    /// A commit to the table or view succeeded.
+    Commit,
+}
+
+/// Namespace, table or view whose description matches a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogSearchMatch {
+    Namespace {
+        id: NamespaceIdentUuid,
+        namespace: NamespaceIdent,
+        description: String,
+    },
+    Tabular {
+        id: TabularIdentUuid,
+        ident: TableIdent,
+        description: String,
+    },
+}
+
+/// Storage used by the tables of a namespace on a day, estimated from snapshot summaries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceStorageUsage {
+    pub warehouse_id: WarehouseIdent,
+    pub namespace_id: NamespaceIdentUuid,
+    pub namespace: NamespaceIdent,
+    pub day: chrono::NaiveDate,
+    /// Number of tables, including soft-deleted ones.
+    pub number_of_tables: i64,
+    /// Sum of `total-files-size` of the snapshot `main` of each table points to.
+    pub total_bytes: i64,
+    /// Number of tables whose snapshot summary does not report a size.
+    pub tables_without_size: i64,
+}
+
+/// Storage used by a table on a day, estimated from its snapshot summary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableStorageUsage {
+    pub warehouse_id: WarehouseIdent,
+    pub namespace_id: NamespaceIdentUuid,
+    pub namespace: NamespaceIdent,
+    pub table_id: TableIdentUuid,
+    pub name: String,
+    pub day: chrono::NaiveDate,
+    /// `total-files-size` of the snapshot `main` points to. `None` if the
+    /// snapshot summary does not report a size.
+    pub total_bytes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct WarehouseStatistics {
+    /// Number of active tables.
+    pub number_of_tables: i64,
+    /// Number of active views.
+    pub number_of_views: i64,
+    /// Number of soft-deleted tables awaiting expiration.
+    pub number_of_soft_deleted_tables: i64,
+    /// Number of soft-deleted views awaiting expiration.
+    pub number_of_soft_deleted_views: i64,
+    /// Bytes of data files referenced by the current snapshot of soft-deleted tables,
+    /// as reported by the snapshot summaries.
+    pub soft_deleted_bytes: i64,
+    /// Number of soft-deleted tables whose current snapshot does not report its size.
+    /// These tables are not included in `soft_deleted_bytes`.
+    pub soft_deleted_tables_without_size: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -129,20 +259,46 @@ pub struct GetProjectResponse {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrincipalDefaultWarehouse {
+    /// ID of the default warehouse.
+    pub warehouse_id: WarehouseIdent,
+    /// Project ID of the default warehouse.
+    pub project_id: ProjectIdent,
+}
+
 #[derive(Debug, Clone)]
 pub struct TableCommit {
     pub new_metadata: TableMetadata,
     pub new_metadata_location: Location,
+    /// Metadata location the commit is based on, `None` for staged tables.
+    /// The commit fails with a conflict if the table was changed since.
+    pub previous_metadata_location: Option<Location>,
     pub updates: Vec<TableUpdate>,
     pub(crate) diffs: TableMetadataDiffs,
 }
 
+/// Switch of a table to a new location after all files have been copied.
+#[derive(Debug, Clone)]
+pub struct TableRelocation {
+    pub table_id: TableIdentUuid,
+    pub source_location: Location,
+    pub target_location: Location,
+    /// Metadata location the copy is based on.
+    pub previous_metadata_location: Option<Location>,
+    /// Metadata file written to the target location.
+    pub new_metadata_location: Location,
+}
+
 #[derive(Debug, Clone)]
 pub struct TableCreation<'c> {
     pub(crate) namespace_id: NamespaceIdentUuid,
     pub(crate) table_ident: &'c TableIdent,
     pub(crate) metadata_location: Option<&'c Location>,
     pub(crate) table_metadata: TableMetadata,
+    /// Property that expires soft-deleted tabulars blocking the location.
+    /// Named in the error if such a tabular is found.
+    pub(crate) force_expire_property: Option<&'c str>,
 }
 
 #[derive(Debug, Clone)]
@@ -151,6 +307,40 @@ pub enum CreateOrUpdateUserResponse {
     Updated(User),
 }
 
+/// Outcome of recording that a user sent a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserActivity {
+    /// The user is not registered in the catalog.
+    Unknown,
+    Active,
+    Deactivated,
+}
+
+/// User affected by the stale user policy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleUser {
+    pub user_id: UserId,
+    pub name: String,
+    /// `None` if the user was never seen.
+    pub last_seen_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Table in a namespace with a `table-ttl` property.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableTtlCandidate {
+    pub table_id: TableIdentUuid,
+    pub warehouse_id: WarehouseIdent,
+    pub namespace: NamespaceIdent,
+    pub name: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Time of the last commit, `None` if the table was never updated.
+    pub last_commit_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Value of the `table-ttl` namespace property.
+    pub ttl: String,
+    /// Value of the `table-ttl-basis` namespace property.
+    pub basis: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StartupValidationData {
     /// Catalog is not bootstrapped
@@ -172,6 +362,9 @@ where
     type Transaction: Transaction<Self::State>;
     type State: Clone + Send + Sync + 'static + HealthExt;
 
+    /// Name of the backend storing the catalog, reported by the server info endpoint.
+    fn backend_name() -> &'static str;
+
     /// Get data required for startup validations and server info endpoint
     async fn get_server_info(
         catalog_state: Self::State,
@@ -219,6 +412,13 @@ where
         catalog_state: Self::State,
     ) -> Result<Option<CatalogConfig>>;
 
+    /// Networks clients of the warehouse must connect from. `None` if the
+    /// warehouse does not exist.
+    async fn get_warehouse_allowed_client_networks(
+        warehouse_id: WarehouseIdent,
+        catalog_state: Self::State,
+    ) -> Result<Option<Vec<ipnet::IpNet>>>;
+
     /// Wrapper around get_config_for_warehouse that returns
     /// not found error if the warehouse does not exist.
     async fn require_config_for_warehouse(
@@ -244,6 +444,15 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<PaginatedMapping<NamespaceIdentUuid, NamespaceIdent>>;
 
+    /// Count the namespaces `list_namespaces` returns for `parent`.
+    /// Authorization is not taken into account.
+    async fn count_namespaces<'a>(
+        warehouse_id: WarehouseIdent,
+        parent: Option<&NamespaceIdent>,
+        mode: ReturnTotal,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<i64>;
+
     async fn create_namespace<'a>(
         warehouse_id: WarehouseIdent,
         namespace_id: NamespaceIdentUuid,
@@ -286,11 +495,45 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Properties the namespace had before its previous updates, most recent first.
+    /// At most `LAKEKEEPER__PROPERTY_HISTORY_LENGTH` versions are kept.
+    async fn list_namespace_property_history(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<PropertyVersion>>;
+
+    /// Properties the table had before commits changed them, most recent first.
+    async fn list_table_property_history(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<PropertyVersion>>;
+
+    /// Rename the namespace `source` to `destination`, including all of its children.
+    /// `destination` may have a different parent, which moves the namespace.
+    /// Tables and views stay in the renamed namespaces.
+    async fn rename_namespace<'a>(
+        warehouse_id: WarehouseIdent,
+        source: &NamespaceIdent,
+        destination: &NamespaceIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     async fn create_table<'a>(
         table_creation: TableCreation<'_>,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<CreateTableResponse>;
 
+    /// Current schema of the table a new table with the given name replaces,
+    /// i.e. a staged table or the most recently soft-deleted table of that name.
+    /// Return Ok(None) if there is no such table.
+    async fn get_replaced_table_schema<'a>(
+        namespace_id: NamespaceIdentUuid,
+        table_name: &str,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Schema>>;
+
     async fn list_tables<'a>(
         warehouse_id: WarehouseIdent,
         namespace: &NamespaceIdent,
@@ -330,6 +573,17 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<HashMap<TableIdentUuid, LoadTableResponse>>;
 
+    /// Compact view of a table for browsing: current schema, default partition spec,
+    /// properties and the `snapshot_limit` most recent snapshots.
+    /// Does not assemble the full table metadata.
+    /// Return Ok(None) if the table does not exist or is staged.
+    async fn get_table_overview(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        snapshot_limit: u32,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Option<TableOverview>>;
+
     /// Get table metadata by table id.
     /// If include_staged is true, also return staged tables,
     /// i.e. tables with no metadata file yet.
@@ -373,7 +627,11 @@ where
     /// Undrop a table.
     ///
     /// Undrops a soft-deleted table. Does not work if the table was hard-deleted.
-    /// Returns the task id of the expiration task associated with the soft-deletion.
+    /// Cancels the expiration tasks associated with the soft-deletion in the same
+    /// transaction and returns their ids.
+    ///
+    /// # Errors
+    /// Fails with a conflict if an expiration task is already running.
     async fn undrop_tabulars(
         table_id: &[TableIdentUuid],
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
@@ -384,14 +642,100 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<()>;
 
+    /// List active tables in namespaces with a `table-ttl` property, except tables
+    /// with the `table-ttl-exempt` property set to `true`.
+    async fn list_table_ttl_candidates(
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<TableTtlCandidate>>;
+
+    /// Add a metrics report of an engine to the aggregated metrics of a table.
+    async fn record_table_metrics<'a>(
+        table_id: TableIdentUuid,
+        report: TableMetricsReport,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Get the aggregated engine metrics of a table.
+    /// Returns zeroed metrics if no report has been received yet.
+    async fn get_table_metrics(
+        table_id: TableIdentUuid,
+        catalog_state: Self::State,
+    ) -> Result<TableMetrics>;
+
+    /// Record an access of a table or view. Does nothing if an access of the same kind
+    /// was recorded within `min_interval` or if the tabular does not exist (anymore).
+    async fn record_tabular_access(
+        tabular_id: TabularIdentUuid,
+        kind: TabularAccessKind,
+        min_interval: chrono::Duration,
+        catalog_state: Self::State,
+    ) -> Result<()>;
+
+    /// Get the recorded access of tables or views.
+    /// Tabulars without any recorded access are not contained in the result.
+    async fn get_tabular_access<'a>(
+        tabular_ids: &[uuid::Uuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<HashMap<uuid::Uuid, TabularAccess>>;
+
+    /// Set or, if `None`, remove the description of a namespace.
+    async fn set_namespace_description<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_id: NamespaceIdentUuid,
+        description: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set or, if `None`, remove the description of a table or view that is not deleted.
+    async fn set_tabular_description<'a>(
+        warehouse_id: WarehouseIdent,
+        tabular_id: TabularIdentUuid,
+        description: Option<&str>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Get the descriptions of namespaces.
+    /// Namespaces without a description are not contained in the result.
+    async fn get_namespace_descriptions<'a>(
+        namespace_ids: &[uuid::Uuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<HashMap<uuid::Uuid, String>>;
+
+    /// Get the descriptions of tables or views.
+    /// Tabulars without a description are not contained in the result.
+    async fn get_tabular_descriptions<'a>(
+        tabular_ids: &[uuid::Uuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<HashMap<uuid::Uuid, String>>;
+
+    /// Search the descriptions of namespaces, tables and views of a warehouse.
+    /// Returns at most `limit` matches, best matches first.
+    /// Authorization is not taken into account.
+    async fn search_descriptions<'a>(
+        warehouse_id: WarehouseIdent,
+        search: &str,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<CatalogSearchMatch>>;
+
     /// Commit changes to a table.
     /// The table might be staged or not.
+    /// Fails with a conflict if a table is not at the `previous_metadata_location` of its commit anymore.
     async fn commit_table_transaction<'a>(
         warehouse_id: WarehouseIdent,
         commits: impl IntoIterator<Item = TableCommit> + Send,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Point a table to its new location.
+    /// Paths of snapshots, statistics and the metadata log are moved along.
+    /// Fails with 409 if the metadata location of the table
+    /// is no longer `previous_metadata_location`.
+    async fn relocate_table<'a>(
+        relocation: &TableRelocation,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     // ---------------- Role Management API ----------------
     async fn create_role<'a>(
         role_id: RoleId,
@@ -413,6 +757,7 @@ where
         filter_project_id: Option<ProjectIdent>,
         filter_role_id: Option<Vec<RoleId>>,
         filter_name: Option<String>,
+        filter: RoleListFilter,
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListRolesResponse>;
@@ -428,6 +773,110 @@ where
         catalog_state: Self::State,
     ) -> Result<SearchRoleResponse>;
 
+    /// Get the default warehouse of a role.
+    /// Return Ok(None) if no default is set or the warehouse is not active.
+    async fn get_role_default_warehouse(
+        role_id: RoleId,
+        catalog_state: Self::State,
+    ) -> Result<Option<PrincipalDefaultWarehouse>>;
+
+    /// Set the default warehouse of a role. `None` removes the default.
+    async fn set_role_default_warehouse<'a>(
+        role_id: RoleId,
+        warehouse_id: Option<WarehouseIdent>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Serialize role membership changes of `project_id` until `transaction` ends.
+    /// Membership cycles are only detected reliably while the lock is held, as concurrent
+    /// changes could otherwise close a cycle that each change on its own would not.
+    async fn lock_role_members<'a>(
+        project_id: ProjectIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    // ---------------- Domain Management API ----------------
+    async fn create_domain<'a>(
+        domain_id: DomainId,
+        project_id: ProjectIdent,
+        name: &str,
+        description: Option<&str>,
+        tags: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Domain>;
+
+    /// Return Ok(None) if the domain does not exist.
+    async fn get_domain(domain_id: DomainId, catalog_state: Self::State) -> Result<Option<Domain>>;
+
+    /// List all domains of a project, optionally only those with the given tag.
+    async fn list_domains(
+        project_id: ProjectIdent,
+        tag: Option<&str>,
+        catalog_state: Self::State,
+    ) -> Result<Vec<Domain>>;
+
+    /// Return Ok(None) if the domain does not exist.
+    async fn update_domain<'a>(
+        domain_id: DomainId,
+        name: &str,
+        description: Option<&str>,
+        tags: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<Domain>>;
+
+    /// Return Ok(None) if the domain does not exist.
+    /// Namespaces of the domain are not affected.
+    async fn delete_domain<'a>(
+        domain_id: DomainId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>>;
+
+    /// Add a namespace to a domain. A namespace can belong to at most one domain.
+    /// Returns `false` if the namespace already was part of the domain.
+    ///
+    /// # Errors
+    /// Fails with a conflict if the namespace belongs to a different domain.
+    async fn add_domain_namespace<'a>(
+        domain_id: DomainId,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<bool>;
+
+    /// Return Ok(None) if the namespace is not part of the domain.
+    async fn remove_domain_namespace<'a>(
+        domain_id: DomainId,
+        namespace_id: NamespaceIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>>;
+
+    /// List namespaces of a domain in active warehouses.
+    async fn list_domain_namespaces(
+        domain_id: DomainId,
+        catalog_state: Self::State,
+    ) -> Result<Vec<DomainNamespace>>;
+
+    // ---------------- Notices API ----------------
+    async fn create_notice<'a>(
+        notice_id: uuid::Uuid,
+        message: &str,
+        severity: NoticeSeverity,
+        starts_at: Option<chrono::DateTime<chrono::Utc>>,
+        ends_at: Option<chrono::DateTime<chrono::Utc>>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Notice>;
+
+    /// List notices that are currently shown, or all notices if `include_inactive` is set.
+    async fn list_notices(
+        include_inactive: bool,
+        catalog_state: Self::State,
+    ) -> Result<Vec<Notice>>;
+
+    /// Return Ok(None) if the notice does not exist.
+    async fn delete_notice<'a>(
+        notice_id: uuid::Uuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>>;
+
     // ---------------- User Management API ----------------
     async fn create_or_update_user<'a>(
         user_id: &UserId,
@@ -448,15 +897,51 @@ where
     async fn list_user(
         filter_user_id: Option<Vec<UserId>>,
         filter_name: Option<String>,
+        filter: UserListFilter,
         pagination: PaginationQuery,
         catalog_state: Self::State,
     ) -> Result<ListUsersResponse>;
 
+    /// Record that the user was active. Lifts a stale flag, but not a deactivation.
+    async fn update_user_last_seen(
+        user_id: &UserId,
+        catalog_state: Self::State,
+    ) -> Result<UserActivity>;
+
+    /// Flag or deactivate all users that were not seen within `stale_after`,
+    /// except for `exempt` users. Returns the users that changed.
+    async fn apply_stale_user_policy<'a>(
+        stale_after: chrono::Duration,
+        action: StaleUserAction,
+        exempt: &[UserId],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<StaleUser>>;
+
+    /// Lift the deactivation of a user. Returns `None` if the user does not exist.
+    async fn reactivate_user<'a>(
+        user_id: &UserId,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<()>>;
+
     async fn delete_user<'a>(
         user_id: UserId,
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<Option<()>>;
 
+    /// Get the default warehouse of a user.
+    /// Return Ok(None) if no default is set or the warehouse is not active.
+    async fn get_user_default_warehouse(
+        user_id: &UserId,
+        catalog_state: Self::State,
+    ) -> Result<Option<PrincipalDefaultWarehouse>>;
+
+    /// Set the default warehouse of a user. `None` removes the default.
+    async fn set_user_default_warehouse<'a>(
+        user_id: &UserId,
+        warehouse_id: Option<WarehouseIdent>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
     // ---------------- Warehouse Management API ----------------
 
     /// Create a warehouse.
@@ -548,6 +1033,67 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Replace the namespace defaults of a warehouse.
+    async fn set_warehouse_namespace_defaults<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace_defaults: &HashMap<String, String>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Replace the networks clients of a warehouse must connect from.
+    async fn set_warehouse_allowed_client_networks<'a>(
+        warehouse_id: WarehouseIdent,
+        allowed_client_networks: &[ipnet::IpNet],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Replace the limits on the metadata of tables in a warehouse.
+    async fn set_warehouse_table_limits<'a>(
+        warehouse_id: WarehouseIdent,
+        table_limits: &TableLimits,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Replace the provenance added to snapshot summaries of a warehouse.
+    async fn set_warehouse_snapshot_summary_enrichment<'a>(
+        warehouse_id: WarehouseIdent,
+        snapshot_summary_enrichment: &SnapshotSummaryEnrichment,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Replace the WASM hooks of a warehouse.
+    async fn set_warehouse_wasm_hooks<'a>(
+        warehouse_id: WarehouseIdent,
+        wasm_hooks: &[String],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Set the lifecycle of a warehouse.
+    async fn set_warehouse_lifecycle<'a>(
+        warehouse_id: WarehouseIdent,
+        lifecycle: &WarehouseLifecycle,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Ephemeral warehouses whose lifetime or idle period has passed, regardless of their status.
+    async fn list_expired_ephemeral_warehouses<'a>(
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<WarehouseIdent>>;
+
+    /// Delete all tables and views of a warehouse, including soft-deleted ones.
+    /// Returns the deleted tabulars with their locations.
+    /// Fails while any tabular of the warehouse is exempt from expiration.
+    async fn clear_warehouse_tabulars<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<(TabularIdentUuid, String)>>;
+
+    /// Delete all namespaces of a warehouse. Fails if the warehouse still contains tabulars.
+    async fn clear_warehouse_namespaces<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceIdentUuid>>;
+
     /// Rename a project.
     async fn rename_project<'a>(
         project_id: ProjectIdent,
@@ -569,6 +1115,67 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
     ) -> Result<()>;
 
+    /// Fold the changes of the warehouse summaries recorded per transaction into their
+    /// totals, so that loading a summary does not need to sum all changes.
+    /// Returns the number of folded changes.
+    async fn fold_warehouse_aggregate_deltas<'a>(
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<u64>;
+
+    /// Get statistics of all tabulars in a warehouse, including soft-deleted ones.
+    async fn get_warehouse_statistics<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<WarehouseStatistics>;
+
+    /// Compute the storage usage of all tables and namespaces in active warehouses and
+    /// store it as rollup of `day`, replacing an existing rollup of the same day.
+    /// Rollups of days before `retain_from` are removed.
+    async fn rollup_storage_usage<'a>(
+        day: chrono::NaiveDate,
+        retain_from: chrono::NaiveDate,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceStorageUsage>>;
+
+    /// Get the storage usage rollup of all namespaces of a warehouse for `day`,
+    /// or for the most recent day if not specified.
+    async fn list_storage_usage<'a>(
+        warehouse_id: WarehouseIdent,
+        day: Option<chrono::NaiveDate>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<NamespaceStorageUsage>>;
+
+    /// Get the storage usage rollup of the tables of a warehouse for `day`,
+    /// or for the most recent day if not specified. Optionally limited to the
+    /// tables of a single namespace.
+    async fn list_table_storage_usage<'a>(
+        warehouse_id: WarehouseIdent,
+        day: Option<chrono::NaiveDate>,
+        namespace_id: Option<NamespaceIdentUuid>,
+        pagination_query: PaginationQuery,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<PaginatedMapping<TableIdentUuid, TableStorageUsage>>;
+
+    /// Claim up to `limit` active warehouses whose storage has not been checked
+    /// within `min_interval`. Claimed warehouses are marked as checked immediately,
+    /// so that concurrent catalog instances don't check the same warehouse.
+    async fn claim_warehouses_for_storage_check<'a>(
+        min_interval: chrono::Duration,
+        limit: i64,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<WarehouseIdent>>;
+
+    /// Record the result of a storage check.
+    ///
+    /// Returns the storage health before this update, or `None` if the
+    /// warehouse has never been checked.
+    async fn set_warehouse_storage_health<'a>(
+        warehouse_id: WarehouseIdent,
+        storage_health: WarehouseStorageHealth,
+        failed_checks: Option<serde_json::Value>,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Option<WarehouseStorageHealth>>;
+
     /// Return Err only on unexpected errors, not if the table does not exist.
     /// If include_staged is true, also return staged tables.
     /// If the table does not exist, return Ok(None).
@@ -629,6 +1236,28 @@ where
         transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
     ) -> Result<()>;
 
+    /// Replace the tables the view depends on.
+    async fn set_view_dependencies(
+        view_id: ViewIdentUuid,
+        table_ids: &[TableIdentUuid],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<()>;
+
+    /// Views that are not deleted and depend on the table.
+    async fn list_view_dependents(
+        warehouse_id: WarehouseIdent,
+        table_id: TableIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<ViewDependent>>;
+
+    /// Tables that are not deleted and the view depends on, with their snapshot when
+    /// the view was last committed and their current snapshot.
+    async fn list_view_dependencies(
+        warehouse_id: WarehouseIdent,
+        view_id: ViewIdentUuid,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<ViewDependency>>;
+
     async fn list_tabulars(
         warehouse_id: WarehouseIdent,
         namespace_id: Option<NamespaceIdentUuid>, // Filter by namespace
@@ -637,6 +1266,84 @@ where
         pagination_query: PaginationQuery,
     ) -> Result<PaginatedMapping<TabularIdentUuid, (TabularIdentOwned, Option<DeletionDetails>)>>;
 
+    /// Soft-deleted tables and views of the warehouse matching `filter`.
+    /// Deletion details are always `Some`.
+    async fn list_deleted_tabulars(
+        warehouse_id: WarehouseIdent,
+        filter: DeletedTabularListFilter,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+        pagination_query: PaginationQuery,
+    ) -> Result<PaginatedMapping<TabularIdentUuid, (TabularIdentOwned, Option<DeletionDetails>)>>;
+
+    /// Count the tables or views in `namespace` matching `list_flags`.
+    /// Authorization is not taken into account.
+    async fn count_tabulars<'a>(
+        warehouse_id: WarehouseIdent,
+        namespace: &NamespaceIdent,
+        typ: crate::api::management::v1::TabularType,
+        list_flags: ListFlags,
+        mode: ReturnTotal,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<i64>;
+
+    /// List tabulars of the warehouse, including staged and soft-deleted ones,
+    /// whose location equals `location`, is a parent of it, or is inside of it.
+    async fn list_location_usage(
+        warehouse_id: WarehouseIdent,
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+        pagination_query: PaginationQuery,
+    ) -> Result<PaginatedMapping<TabularIdentUuid, TabularLocationUsage>>;
+
+    /// Soft-deleted tabulars of the warehouse that block `location` until they expire.
+    /// Uses the same notion of overlapping locations as [`Catalog::list_location_usage`].
+    async fn list_deleted_tabulars_at_location(
+        warehouse_id: WarehouseIdent,
+        location: &Location,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'_>,
+    ) -> Result<Vec<DeletedTabularAtLocation>>;
+
+    /// Soft-deleted tabulars of the warehouse whose expiration is still pending,
+    /// ordered by expiration date. The expiration tasks are locked until the
+    /// transaction ends.
+    async fn list_pending_expirations<'a>(
+        warehouse_id: WarehouseIdent,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<Vec<PendingExpiration>>;
+
+    /// Lock a soft-deleted tabular for its running expiration `task` before it is dropped.
+    /// The lock is held until the transaction ends, so that concurrent undrops wait
+    /// for the expiration to finish and then fail.
+    async fn claim_tabular_expiration<'a>(
+        tabular_id: uuid::Uuid,
+        task: &Task,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<TabularExpirationClaim>;
+
+    /// Move pending expiration tasks, identified by their task id, to a new date.
+    /// Tasks that are no longer pending are left untouched.
+    async fn reschedule_expirations<'a>(
+        expirations: &[(uuid::Uuid, chrono::DateTime<chrono::Utc>)],
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Exempt a soft-deleted tabular from expiration, or lift the exemption.
+    /// Fails with 404 if the tabular is not soft-deleted in the warehouse.
+    async fn set_tabular_expiration_exempt<'a>(
+        warehouse_id: WarehouseIdent,
+        tabular_id: uuid::Uuid,
+        exempt: bool,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<()>;
+
+    /// Free the idempotency keys of tasks that are done or failed for longer than
+    /// `older_than`, so that the same work can be queued again. The tasks are kept.
+    /// Returns the number of released keys.
+    async fn release_idempotency_keys<'a>(
+        older_than: chrono::Duration,
+        transaction: <Self::Transaction as Transaction<Self::State>>::Transaction<'a>,
+    ) -> Result<u64>;
+
     async fn load_storage_profile(
         warehouse_id: WarehouseIdent,
         tabular_id: TableIdentUuid,
@@ -708,16 +1415,93 @@ impl DropFlags {
     }
 }
 
+/// A view depending on a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewDependent {
+    pub view_id: ViewIdentUuid,
+    pub view: TableIdent,
+}
+
+/// A table a view depends on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewDependency {
+    pub table_id: TableIdentUuid,
+    pub table: TableIdent,
+    /// Snapshot of the `main` branch when the view was last committed.
+    pub snapshot_id_at_view_commit: Option<i64>,
+    /// Current snapshot of the `main` branch.
+    pub current_snapshot_id: Option<i64>,
+    pub current_snapshot_timestamp_ms: Option<i64>,
+    /// Time the dependency was recorded. `None` if it was recorded before
+    /// snapshots were tracked, so `snapshot_id_at_view_commit` is unknown.
+    pub recorded_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl ViewDependency {
+    /// Whether the table changed since the view was last committed.
+    /// `None` if unknown.
+    #[must_use]
+    pub fn changed_since_view_commit(&self) -> Option<bool> {
+        self.recorded_at
+            .map(|_| self.snapshot_id_at_view_commit != self.current_snapshot_id)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ViewMetadataWithLocation {
     pub metadata_location: String,
     pub metadata: ViewMetadata,
 }
 
+/// A tabular whose location overlaps with a requested location.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TabularLocationUsage {
+    pub tabular: TabularIdentOwned,
+    pub location: String,
+    /// The tabular has been created but no metadata has been committed yet.
+    pub staged: bool,
+    pub deleted_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeletedTabularAtLocation {
+    pub tabular_id: TabularIdentUuid,
+    pub location: String,
+    /// Pending task that removes the tabular once the expiration delay passed
+    pub expiration_task_id: uuid::Uuid,
+    /// The files of the tabular are deleted on expiration
+    pub purge: bool,
+    /// The tabular must not expire, for example because it is under a legal hold
+    pub expiration_exempt: bool,
+}
+
+/// Outcome of [`Catalog::claim_tabular_expiration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TabularExpirationClaim {
+    /// The tabular is soft-deleted and locked, the expiration can proceed.
+    Claimed,
+    /// The tabular was undropped or does not exist anymore. There is nothing to expire.
+    NotDeleted,
+    /// The task was cancelled or picked up again by another worker.
+    TaskNotOwned,
+    /// The tabular is exempt from expiration. The task was postponed and is checked again later.
+    Exempt,
+}
+
+/// A soft-deleted tabular whose expiration task has not started yet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingExpiration {
+    pub tabular_id: TabularIdentUuid,
+    pub tabular: TabularIdentOwned,
+    pub deletion_details: DeletionDetails,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DeletionDetails {
     pub expiration_task_id: uuid::Uuid,
     pub expiration_date: chrono::DateTime<chrono::Utc>,
     pub deleted_at: chrono::DateTime<chrono::Utc>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// The tabular is held back from expiration, for example for a legal hold.
+    pub expiration_exempt: bool,
 }
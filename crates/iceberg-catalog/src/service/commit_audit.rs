@@ -0,0 +1,713 @@
+//! Publishes facts about table commits to a designated audit table.
+//!
+//! Facts are extracted from `updateTable` events, buffered in memory and periodically
+//! appended to the audit table as a Parquet data file, in a snapshot committed by the
+//! catalog itself. Buffered facts are written once more when the process shuts down.
+use crate::api::Result;
+use crate::catalog::commit_tables::apply_commit;
+use crate::catalog::compression_codec::CompressionCodec;
+use crate::catalog::io::{new_output, write_file};
+use crate::catalog::maybe_get_secret;
+use crate::catalog::tables::{extract_count_from_metadata_location, CommitContext};
+use crate::service::event_publisher::CloudEventBackend;
+use crate::service::storage::StorageLocations as _;
+use crate::service::{Catalog, ListFlags, LoadTableResponse, SecretStore, Transaction};
+use crate::WarehouseIdent;
+use arrow_array::{new_null_array, ArrayRef, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, SchemaRef};
+use async_trait::async_trait;
+use cloudevents::{AttributesReader, Data, Event};
+use iceberg::io::FileIO;
+use iceberg::spec::{
+    DataContentType, DataFileBuilder, DataFileFormat, FormatVersion, Manifest, ManifestContentType,
+    ManifestEntry, ManifestListWriter, ManifestMetadata, ManifestStatus, ManifestWriter, Operation,
+    Snapshot, SnapshotReference, SnapshotRetention, Struct, Summary, TableMetadata,
+    TableMetadataBuildResult, MAIN_BRANCH,
+};
+use iceberg::{TableIdent, TableRequirement, TableUpdate};
+use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::configs::Location;
+use parquet::arrow::ArrowWriter;
+use rand::Rng;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Oldest facts are dropped if more than this many could not be written yet.
+const MAX_BUFFERED_FACTS: usize = 100_000;
+const UPDATE_TABLE_EVENT: &str = "updateTable";
+
+/// A snapshot added by a commit.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommitFact {
+    pub warehouse_id: Uuid,
+    pub table_id: Uuid,
+    /// Namespace levels joined by `.`
+    pub namespace: String,
+    pub table_name: String,
+    pub snapshot_id: i64,
+    pub parent_snapshot_id: Option<i64>,
+    pub sequence_number: Option<i64>,
+    pub timestamp_ms: Option<i64>,
+    pub operation: Option<String>,
+    pub added_data_files: Option<i64>,
+    pub removed_data_files: Option<i64>,
+    pub added_records: Option<i64>,
+    pub removed_records: Option<i64>,
+    pub request_id: Option<Uuid>,
+    /// Id of the Spark application, Trino query or other engine job that made the commit.
+    pub engine_query_id: Option<String>,
+    /// Address of the client that made the commit.
+    pub client_ip: Option<String>,
+}
+
+/// Event sink that appends [`CommitFact`]s to the audit table.
+pub struct CommitAuditBackend<C: Catalog, S: SecretStore> {
+    warehouse_id: WarehouseIdent,
+    table: TableIdent,
+    catalog_state: C::State,
+    secret_state: S,
+    buffer: tokio::sync::Mutex<Vec<CommitFact>>,
+}
+
+impl<C: Catalog, S: SecretStore> Debug for CommitAuditBackend<C, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommitAuditBackend")
+            .field("warehouse_id", &self.warehouse_id)
+            .field("table", &self.table)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<C: Catalog, S: SecretStore> CommitAuditBackend<C, S> {
+    /// Create a new backend writing to `table` in `warehouse_id`. Namespace levels
+    /// and the table name are separated by `.`, for example `system.commits`.
+    ///
+    /// # Errors
+    /// Fails if `table` does not contain a namespace.
+    pub fn try_new(
+        warehouse_id: WarehouseIdent,
+        table: &str,
+        catalog_state: C::State,
+        secret_state: S,
+    ) -> anyhow::Result<Self> {
+        let parts = table.split('.').collect::<Vec<_>>();
+        if parts.len() < 2 || parts.iter().any(|p| p.is_empty()) {
+            anyhow::bail!(
+                "Commit audit table must be given as `<namespace>.<table>`, got `{table}`"
+            );
+        }
+        Ok(Self {
+            warehouse_id,
+            table: TableIdent::from_strs(parts)?,
+            catalog_state,
+            secret_state,
+            buffer: tokio::sync::Mutex::new(vec![]),
+        })
+    }
+
+    /// Write buffered facts every `interval` until `shutdown` resolves, then write
+    /// the remaining facts once more. Facts that could not be written are retried
+    /// with the next flush.
+    pub async fn flush_task(
+        self: Arc<Self>,
+        interval: Duration,
+        shutdown: impl std::future::Future<Output = ()> + Send,
+    ) {
+        tokio::pin!(shutdown);
+        loop {
+            tokio::select! {
+                () = tokio::time::sleep(interval) => self.flush().await,
+                () = &mut shutdown => {
+                    self.flush().await;
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn flush(&self) {
+        let facts = std::mem::take(&mut *self.buffer.lock().await);
+        if facts.is_empty() {
+            return;
+        }
+        if let Err(e) = self.write_facts(&facts).await {
+            tracing::warn!(
+                "Failed to append {} commit facts to audit table {}: {}",
+                facts.len(),
+                self.table,
+                e.error
+            );
+            let mut buffer = self.buffer.lock().await;
+            let newer = std::mem::replace(&mut *buffer, facts);
+            buffer.extend(newer);
+            truncate_buffer(&mut buffer);
+        }
+    }
+
+    async fn write_facts(&self, facts: &[CommitFact]) -> Result<()> {
+        let not_found = || {
+            ErrorModel::not_found(
+                format!(
+                    "Commit audit table {} does not exist in warehouse {}",
+                    self.table, self.warehouse_id
+                ),
+                "CommitAuditTableNotFound",
+                None,
+            )
+        };
+        // The metadata file is written before the transaction is committed, like for
+        // commits received via the REST API.
+        let mut t = C::Transaction::begin_write(self.catalog_state.clone()).await?;
+        let table_id = C::table_to_id(
+            self.warehouse_id,
+            &self.table,
+            ListFlags::default(),
+            t.transaction(),
+        )
+        .await?
+        .ok_or_else(not_found)?;
+        let table = C::load_tables(self.warehouse_id, [table_id], false, t.transaction())
+            .await?
+            .remove(&table_id)
+            .ok_or_else(not_found)?;
+
+        // Commits to the audit table itself would otherwise be recorded in a loop.
+        let facts = facts
+            .iter()
+            .filter(|f| f.table_id != *table_id)
+            .collect::<Vec<_>>();
+        if facts.is_empty() {
+            return t.commit().await;
+        }
+
+        let secret = maybe_get_secret(table.storage_secret_ident, &self.secret_state).await?;
+        let file_io = table.storage_profile.file_io(secret.as_ref())?;
+        let snapshot = write_snapshot(&file_io, &table.table_metadata, &facts).await?;
+        let commit = append_commit(&table, snapshot)?;
+
+        C::commit_table_transaction(self.warehouse_id, [commit.commit()], t.transaction()).await?;
+        table
+            .storage_profile
+            .write_metadata_file(
+                &file_io,
+                secret.as_ref(),
+                Some(self.warehouse_id),
+                &commit.new_metadata_location,
+                &commit.new_metadata,
+                commit.new_compression_codec,
+            )
+            .await?;
+        t.commit().await?;
+        tracing::debug!(
+            "Appended {} commit facts to {} in {}",
+            facts.len(),
+            self.table,
+            commit.new_metadata_location
+        );
+        Ok(())
+    }
+}
+
+fn write_error<E: std::error::Error + Send + Sync + 'static>(
+    message: &'static str,
+) -> impl FnOnce(E) -> ErrorModel {
+    move |e| ErrorModel::internal(message, "CommitAuditWriteError", Some(Box::new(e)))
+}
+
+/// Write `facts` as a Parquet data file of the table, along with a manifest and a
+/// manifest list that carries over the manifests of the current snapshot.
+/// Returns the snapshot appending the file, which is not committed yet.
+async fn write_snapshot(
+    file_io: &FileIO,
+    metadata: &TableMetadata,
+    facts: &[&CommitFact],
+) -> Result<Snapshot> {
+    let schema = metadata.current_schema();
+    let partition_spec = metadata.default_partition_spec();
+    if !partition_spec.fields().is_empty() {
+        return Err(ErrorModel::bad_request(
+            "Commit audit table must not be partitioned",
+            "CommitAuditTablePartitioned",
+            None,
+        )
+        .into());
+    }
+    let mut table_location = Location::from_str(metadata.location()).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to parse location of commit audit table",
+            "ParseError",
+            Some(Box::new(e)),
+        )
+    })?;
+    table_location.without_trailing_slash();
+    let file_location = |directory: &str, name: String| {
+        let mut location = table_location.clone();
+        location.extend(&[directory, &name]);
+        location
+    };
+
+    let arrow_schema = Arc::new(iceberg::arrow::schema_to_arrow_schema(schema).map_err(
+        write_error("Failed to convert schema of commit audit table"),
+    )?);
+    let batch = fact_batch(arrow_schema.clone(), facts)?;
+    let mut writer = ArrowWriter::try_new(Vec::new(), arrow_schema, None)
+        .map_err(write_error("Failed to create Parquet writer"))?;
+    writer
+        .write(&batch)
+        .map_err(write_error("Failed to write commit facts"))?;
+    let content = writer
+        .into_inner()
+        .map_err(write_error("Failed to write commit facts"))?;
+    let file_size = content.len() as u64;
+    let data_location = file_location("data", format!("commit-facts-{}.parquet", Uuid::now_v7()));
+    write_file(file_io, &data_location, content).await?;
+
+    let snapshot_id = loop {
+        let id = rand::thread_rng().gen_range(1..i64::MAX);
+        if metadata.snapshot_by_id(id).is_none() {
+            break id;
+        }
+    };
+    let data_file = DataFileBuilder::default()
+        .content(DataContentType::Data)
+        .file_path(data_location.to_string())
+        .file_format(DataFileFormat::Parquet)
+        .partition(Struct::empty())
+        .record_count(facts.len() as u64)
+        .file_size_in_bytes(file_size)
+        .build()
+        .map_err(write_error("Failed to describe data file of commit facts"))?;
+    let manifest = Manifest::new(
+        ManifestMetadata::builder()
+            .schema((**schema).clone())
+            .schema_id(schema.schema_id())
+            .partition_spec((**partition_spec).clone())
+            .format_version(metadata.format_version())
+            .content(ManifestContentType::Data)
+            .build(),
+        vec![ManifestEntry::builder()
+            .status(ManifestStatus::Added)
+            .snapshot_id(snapshot_id)
+            .data_file(data_file)
+            .build()],
+    );
+    let manifest_location = file_location("metadata", format!("{}-m0.avro", Uuid::now_v7()));
+    let mut manifest_file = ManifestWriter::new(
+        new_output(file_io, &manifest_location)?,
+        snapshot_id,
+        vec![],
+    )
+    .write(manifest)
+    .await
+    .map_err(write_error("Failed to write manifest of commit facts"))?;
+    manifest_file.manifest_path = manifest_location.to_string();
+
+    let parent = metadata.current_snapshot();
+    let mut manifests = vec![manifest_file];
+    if let Some(parent) = parent {
+        let manifest_list =
+            parent
+                .load_manifest_list(file_io, metadata)
+                .await
+                .map_err(write_error(
+                    "Failed to read manifest list of commit audit table",
+                ))?;
+        manifests.extend(manifest_list.entries().iter().cloned());
+    }
+    let sequence_number = metadata.next_sequence_number();
+    let manifest_list_location = file_location(
+        "metadata",
+        format!("snap-{snapshot_id}-1-{}.avro", Uuid::now_v7()),
+    );
+    let output = new_output(file_io, &manifest_list_location)?;
+    let parent_snapshot_id = parent.map(|parent| parent.snapshot_id());
+    let mut writer = if metadata.format_version() == FormatVersion::V1 {
+        ManifestListWriter::v1(output, snapshot_id, parent_snapshot_id)
+    } else {
+        ManifestListWriter::v2(output, snapshot_id, parent_snapshot_id, sequence_number)
+    };
+    writer
+        .add_manifests(manifests.into_iter())
+        .map_err(write_error("Failed to write manifest list of commit facts"))?;
+    writer
+        .close()
+        .await
+        .map_err(write_error("Failed to write manifest list of commit facts"))?;
+
+    Ok(Snapshot::builder()
+        .with_snapshot_id(snapshot_id)
+        .with_parent_snapshot_id(parent_snapshot_id)
+        .with_sequence_number(sequence_number)
+        .with_timestamp_ms(chrono::Utc::now().timestamp_millis())
+        .with_manifest_list(manifest_list_location.to_string())
+        .with_schema_id(schema.schema_id())
+        .with_summary(Summary {
+            operation: Operation::Append,
+            additional_properties: HashMap::from([
+                ("added-data-files".to_string(), "1".to_string()),
+                ("added-records".to_string(), facts.len().to_string()),
+                ("added-files-size".to_string(), file_size.to_string()),
+            ]),
+        })
+        .build())
+}
+
+/// Commit that sets `snapshot` as the head of `main` of `table`.
+/// Fails if `main` moved since the snapshot was written.
+fn append_commit(table: &LoadTableResponse, snapshot: Snapshot) -> Result<CommitContext> {
+    let snapshot_id = snapshot.snapshot_id();
+    let requirements = vec![TableRequirement::RefSnapshotIdMatch {
+        r#ref: MAIN_BRANCH.to_string(),
+        snapshot_id: snapshot.parent_snapshot_id(),
+    }];
+    let updates = vec![
+        TableUpdate::AddSnapshot { snapshot },
+        TableUpdate::SetSnapshotRef {
+            ref_name: MAIN_BRANCH.to_string(),
+            reference: SnapshotReference {
+                snapshot_id,
+                retention: SnapshotRetention::Branch {
+                    min_snapshots_to_keep: None,
+                    max_snapshot_age_ms: None,
+                    max_ref_age_ms: None,
+                },
+            },
+        },
+    ];
+    let TableMetadataBuildResult {
+        metadata: new_metadata,
+        expired_metadata_logs,
+        ..
+    } = apply_commit(
+        table.table_metadata.clone(),
+        table.metadata_location.as_ref(),
+        &requirements,
+        updates.clone(),
+    )?;
+
+    let next_metadata_count = table
+        .metadata_location
+        .as_ref()
+        .and_then(|location| {
+            extract_count_from_metadata_location(location, table.storage_profile.metadata_naming())
+        })
+        .map_or(0, |v| v + 1);
+    let table_location = Location::from_str(new_metadata.location()).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to parse location of commit audit table",
+            "ParseError",
+            Some(Box::new(e)),
+        )
+    })?;
+    let new_compression_codec = CompressionCodec::try_from_metadata(&new_metadata)?;
+    let new_metadata_location = table.storage_profile.default_metadata_location(
+        &table_location,
+        &new_compression_codec,
+        Uuid::now_v7(),
+        next_metadata_count,
+    );
+    let number_added_metadata_log_entries = (new_metadata.metadata_log().len()
+        + expired_metadata_logs.len())
+    .saturating_sub(table.table_metadata.metadata_log().len());
+
+    Ok(CommitContext {
+        new_metadata,
+        new_metadata_location,
+        previous_metadata: table.table_metadata.clone(),
+        previous_metadata_location: table.metadata_location.clone(),
+        updates,
+        new_compression_codec,
+        number_expired_metadata_log_entries: expired_metadata_logs.len(),
+        number_added_metadata_log_entries,
+    })
+}
+
+/// One row per fact. Columns are filled with the field of the fact of the same name,
+/// columns without such a field with nulls.
+fn fact_batch(schema: SchemaRef, facts: &[&CommitFact]) -> Result<RecordBatch> {
+    let rows = facts
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(write_error("Failed to serialize commit fact"))?;
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| -> Result<ArrayRef> {
+            let name = field.name().as_str();
+            if rows.first().map_or(true, |row| row.get(name).is_none()) {
+                return Ok(new_null_array(field.data_type(), rows.len()));
+            }
+            let values = rows.iter().map(|row| row.get(name));
+            let column: ArrayRef = match field.data_type() {
+                DataType::Int64 => Arc::new(
+                    values
+                        .map(|v| v.and_then(Value::as_i64))
+                        .collect::<Int64Array>(),
+                ),
+                DataType::Utf8 => Arc::new(
+                    values
+                        .map(|v| v.and_then(Value::as_str))
+                        .collect::<StringArray>(),
+                ),
+                other => {
+                    return Err(ErrorModel::bad_request(
+                        format!("Column `{name}` of the commit audit table has type {other}, expected long or string"),
+                        "CommitAuditColumnTypeUnsupported",
+                        None,
+                    )
+                    .into())
+                }
+            };
+            Ok(column)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    RecordBatch::try_new(schema, columns).map_err(|e| {
+        ErrorModel::bad_request(
+            "Commit facts do not match the schema of the commit audit table",
+            "CommitAuditSchemaMismatch",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+#[async_trait]
+impl<C: Catalog, S: SecretStore> CloudEventBackend for CommitAuditBackend<C, S> {
+    async fn publish(&self, event: Event) -> anyhow::Result<()> {
+        let facts = commit_facts(&event);
+        if facts.is_empty() {
+            return Ok(());
+        }
+        let mut buffer = self.buffer.lock().await;
+        buffer.extend(facts);
+        truncate_buffer(&mut buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "commit-audit-publisher"
+    }
+}
+
+fn truncate_buffer(buffer: &mut Vec<CommitFact>) {
+    if buffer.len() > MAX_BUFFERED_FACTS {
+        let dropped = buffer.len() - MAX_BUFFERED_FACTS;
+        buffer.drain(..dropped);
+        tracing::warn!("Commit audit buffer is full, dropped {dropped} commit facts");
+    }
+}
+
+/// Extract one fact per snapshot added by an `updateTable` event.
+fn commit_facts(event: &Event) -> Vec<CommitFact> {
+    if event.ty() != UPDATE_TABLE_EVENT {
+        return vec![];
+    }
+    let Some(Data::Json(body)) = event.data() else {
+        return vec![];
+    };
+    let extension = |name: &str| event.extension(name).map(ToString::to_string);
+    let uuid_extension = |name: &str| extension(name).and_then(|v| Uuid::from_str(&v).ok());
+    let (Some(warehouse_id), Some(table_id)) =
+        (uuid_extension("warehouse-id"), uuid_extension("tabular-id"))
+    else {
+        return vec![];
+    };
+    let namespace = extension("namespace")
+        .unwrap_or_default()
+        .replace('\u{1f}', ".");
+    let table_name = extension("name").unwrap_or_default();
+    let request_id = uuid_extension("trace-id");
+    let engine_query_id = extension("engine-query-id");
+    let client_ip = extension("client-ip");
+
+    body.get("updates")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter(|update| update.get("action").and_then(Value::as_str) == Some("add-snapshot"))
+        .filter_map(|update| update.get("snapshot"))
+        .filter_map(|snapshot| {
+            let summary = snapshot.get("summary");
+            let summary_number = |key: &str| {
+                summary
+                    .and_then(|s| s.get(key))
+                    .and_then(Value::as_str)
+                    .and_then(|v| v.parse().ok())
+            };
+            Some(CommitFact {
+                warehouse_id,
+                table_id,
+                namespace: namespace.clone(),
+                table_name: table_name.clone(),
+                snapshot_id: snapshot.get("snapshot-id")?.as_i64()?,
+                parent_snapshot_id: snapshot.get("parent-snapshot-id").and_then(Value::as_i64),
+                sequence_number: snapshot.get("sequence-number").and_then(Value::as_i64),
+                timestamp_ms: snapshot.get("timestamp-ms").and_then(Value::as_i64),
+                operation: summary
+                    .and_then(|s| s.get("operation"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+                added_data_files: summary_number("added-data-files"),
+                removed_data_files: summary_number("deleted-data-files"),
+                added_records: summary_number("added-records"),
+                removed_records: summary_number("deleted-records"),
+                request_id,
+                engine_query_id: engine_query_id.clone(),
+                client_ip: client_ip.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cloudevents::{EventBuilder, EventBuilderV10};
+
+    fn event(typ: &str, data: Value) -> Event {
+        EventBuilderV10::new()
+            .id(Uuid::now_v7().to_string())
+            .source("uri:test")
+            .ty(typ)
+            .data("application/json", data)
+            .extension("tabular-id", "0194685d-3f2a-7c21-9b52-1d0a2e7a3c10")
+            .extension("warehouse-id", "0194685d-3f2a-7c21-9b52-1d0a2e7a3c11")
+            .extension("name", "orders")
+            .extension("namespace", "sales\u{1f}eu")
+            .extension("trace-id", "0194685d-3f2a-7c21-9b52-1d0a2e7a3c12")
+            .extension("engine-query-id", "20250101_120000_00001_abcde")
+            .extension("client-ip", "198.51.100.1")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_facts_are_extracted_from_added_snapshots() {
+        let body = serde_json::json!({
+            "updates": [
+                {"action": "set-properties", "updates": {"a": "b"}},
+                {
+                    "action": "add-snapshot",
+                    "snapshot": {
+                        "snapshot-id": 2,
+                        "parent-snapshot-id": 1,
+                        "sequence-number": 2,
+                        "timestamp-ms": 1_737_000_000_000_i64,
+                        "manifest-list": "s3://bucket/snap-2.avro",
+                        "summary": {
+                            "operation": "overwrite",
+                            "added-data-files": "3",
+                            "deleted-data-files": "1",
+                            "added-records": "300",
+                            "deleted-records": "100"
+                        }
+                    }
+                }
+            ]
+        });
+        let facts = commit_facts(&event(UPDATE_TABLE_EVENT, body));
+        assert_eq!(
+            facts,
+            vec![CommitFact {
+                warehouse_id: Uuid::from_str("0194685d-3f2a-7c21-9b52-1d0a2e7a3c11").unwrap(),
+                table_id: Uuid::from_str("0194685d-3f2a-7c21-9b52-1d0a2e7a3c10").unwrap(),
+                namespace: "sales.eu".to_string(),
+                table_name: "orders".to_string(),
+                snapshot_id: 2,
+                parent_snapshot_id: Some(1),
+                sequence_number: Some(2),
+                timestamp_ms: Some(1_737_000_000_000),
+                operation: Some("overwrite".to_string()),
+                added_data_files: Some(3),
+                removed_data_files: Some(1),
+                added_records: Some(300),
+                removed_records: Some(100),
+                request_id: Some(Uuid::from_str("0194685d-3f2a-7c21-9b52-1d0a2e7a3c12").unwrap()),
+                engine_query_id: Some("20250101_120000_00001_abcde".to_string()),
+                client_ip: Some("198.51.100.1".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_fact_batch_fills_columns_by_name() {
+        use arrow_array::Array;
+        use arrow_schema::{Field, Schema};
+
+        let fact = CommitFact {
+            warehouse_id: Uuid::now_v7(),
+            table_id: Uuid::now_v7(),
+            namespace: "sales".to_string(),
+            table_name: "orders".to_string(),
+            snapshot_id: 2,
+            parent_snapshot_id: None,
+            sequence_number: Some(2),
+            timestamp_ms: None,
+            operation: Some("append".to_string()),
+            added_data_files: None,
+            removed_data_files: None,
+            added_records: Some(10),
+            removed_records: None,
+            request_id: None,
+            engine_query_id: None,
+            client_ip: None,
+        };
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("table_id", DataType::Utf8, false),
+            Field::new("snapshot_id", DataType::Int64, false),
+            Field::new("parent_snapshot_id", DataType::Int64, true),
+            Field::new("comment", DataType::Utf8, true),
+        ]));
+        let batch = fact_batch(schema.clone(), &[&fact]).unwrap();
+        assert_eq!(batch.num_rows(), 1);
+        let table_ids = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(table_ids.value(0), fact.table_id.to_string());
+        let snapshot_ids = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(snapshot_ids.value(0), 2);
+        assert!(batch.column(2).is_null(0));
+        assert!(batch.column(3).is_null(0));
+
+        // Required columns cannot be filled with missing values
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "timestamp_ms",
+            DataType::Int64,
+            false,
+        )]));
+        assert!(fact_batch(schema, &[&fact]).is_err());
+        // Unsupported types are rejected
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "snapshot_id",
+            DataType::Int32,
+            false,
+        )]));
+        assert!(fact_batch(schema, &[&fact]).is_err());
+    }
+
+    #[test]
+    fn test_other_events_are_ignored() {
+        let body = serde_json::json!({
+            "updates": [{"action": "add-snapshot", "snapshot": {"snapshot-id": 2}}]
+        });
+        assert!(commit_facts(&event("createTable", body)).is_empty());
+        assert!(commit_facts(&event(
+            UPDATE_TABLE_EVENT,
+            serde_json::json!({"updates": []})
+        ))
+        .is_empty());
+    }
+}
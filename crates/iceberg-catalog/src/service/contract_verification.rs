@@ -5,7 +5,9 @@ use iceberg::spec::{TableMetadata, ViewMetadata};
 use iceberg::{TableIdent, TableUpdate};
 use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
 use std::fmt::Debug;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// A trait for checking if a table change is allowed.
 ///
@@ -130,6 +132,17 @@ pub trait ContractVerification: Debug {
         _current_metadata: &ViewMetadata,
     ) -> Result<ContractVerificationOutcome, ErrorModel>;
 
+    /// Check if a table may be created or registered with the given metadata.
+    ///
+    /// Defaults to allowing every table.
+    async fn check_table_create(
+        &self,
+        _table: &TableIdent,
+        _metadata: &TableMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        Ok(ContractVerificationOutcome::Clear {})
+    }
+
     async fn check_drop(
         &self,
         table_ident_uuid: TabularIdentUuid,
@@ -183,15 +196,132 @@ impl ContractVerificationOutcome {
     }
 }
 
+/// Resolves contract verifiers that warehouses reference by name, such as WASM hooks.
+pub trait WarehouseContractVerifiers: Debug + Sync + Send {
+    /// Returns `None` if no verifier with this name exists.
+    ///
+    /// # Errors
+    /// Fails if the verifier exists but cannot be loaded.
+    fn resolve(
+        &self,
+        name: &str,
+    ) -> Result<Option<Arc<dyn ContractVerification + Sync + Send>>, ErrorModel>;
+}
+
 #[derive(Debug, Clone)]
 pub struct ContractVerifiers {
     checkers: Vec<Arc<dyn ContractVerification + Sync + Send>>,
+    warehouse_verifiers: Option<Arc<dyn WarehouseContractVerifiers>>,
+    timeout: Option<Duration>,
 }
 
 impl ContractVerifiers {
     #[must_use]
     pub fn new(checkers: Vec<Arc<dyn ContractVerification + Sync + Send>>) -> Self {
-        Self { checkers }
+        Self {
+            checkers,
+            warehouse_verifiers: None,
+            timeout: None,
+        }
+    }
+
+    /// Resolve the verifiers referenced by warehouses with `resolver`.
+    #[must_use]
+    pub fn with_warehouse_verifiers(
+        mut self,
+        resolver: Arc<dyn WarehouseContractVerifiers>,
+    ) -> Self {
+        self.warehouse_verifiers = Some(resolver);
+        self
+    }
+
+    /// Verifiers to run for changes in a warehouse: the verifiers of all warehouses
+    /// followed by the verifiers the warehouse references by name.
+    ///
+    /// # Errors
+    /// Fails if a referenced verifier cannot be resolved.
+    pub fn for_warehouse(&self, names: &[String]) -> Result<Self, ErrorModel> {
+        if names.is_empty() {
+            return Ok(self.clone());
+        }
+        let mut checkers = self.checkers.clone();
+        for name in names {
+            let checker = self.resolve(name)?.ok_or_else(|| {
+                ErrorModel::internal(
+                    format!("Contract verifier '{name}' of the warehouse does not exist"),
+                    "ContractVerifierNotFound",
+                    None,
+                )
+            })?;
+            checkers.push(checker);
+        }
+        Ok(Self {
+            checkers,
+            warehouse_verifiers: self.warehouse_verifiers.clone(),
+            timeout: self.timeout,
+        })
+    }
+
+    /// Check that warehouses may reference the verifiers `names`.
+    ///
+    /// # Errors
+    /// Fails with `400` if a verifier does not exist.
+    pub fn validate_warehouse_verifiers(&self, names: &[String]) -> Result<(), ErrorModel> {
+        for name in names {
+            if self.resolve(name)?.is_none() {
+                return Err(ErrorModel::bad_request(
+                    format!("Contract verifier '{name}' does not exist"),
+                    "ContractVerifierNotFound",
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve(
+        &self,
+        name: &str,
+    ) -> Result<Option<Arc<dyn ContractVerification + Sync + Send>>, ErrorModel> {
+        let Some(resolver) = &self.warehouse_verifiers else {
+            return Err(ErrorModel::bad_request(
+                "Warehouse specific contract verifiers are not enabled",
+                "WarehouseContractVerifiersDisabled",
+                None,
+            ));
+        };
+        resolver.resolve(name)
+    }
+
+    /// Limit the time a single checker may take for a single check.
+    /// Checks that exceed the limit fail the request.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    async fn run(
+        &self,
+        checker: &(dyn ContractVerification + Sync + Send),
+        check: impl Future<Output = Result<ContractVerificationOutcome, ErrorModel>> + Send,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        let Some(timeout) = self.timeout else {
+            return check.await;
+        };
+        tokio::time::timeout(timeout, check)
+            .await
+            .unwrap_or_else(|_| {
+                tracing::warn!(
+                    "ContractVerifier '{}' timed out after {timeout:?}",
+                    checker.name()
+                );
+                Err(ErrorModel::internal(
+                    format!("ContractVerifier '{}' timed out", checker.name()),
+                    "ContractVerificationTimeout",
+                    None,
+                ))
+            })
     }
 }
 
@@ -207,8 +337,11 @@ impl ContractVerification for ContractVerifiers {
         current_metadata: &TableMetadata,
     ) -> Result<ContractVerificationOutcome, ErrorModel> {
         for checker in &self.checkers {
-            match checker
-                .check_table_updates(table_updates, current_metadata)
+            match self
+                .run(
+                    checker.as_ref(),
+                    checker.check_table_updates(table_updates, current_metadata),
+                )
                 .await
             {
                 Ok(ContractVerificationOutcome::Clear {}) => {}
@@ -236,8 +369,11 @@ impl ContractVerification for ContractVerifiers {
         current_metadata: &ViewMetadata,
     ) -> Result<ContractVerificationOutcome, ErrorModel> {
         for checker in &self.checkers {
-            match checker
-                .check_view_updates(view_updates, current_metadata)
+            match self
+                .run(
+                    checker.as_ref(),
+                    checker.check_view_updates(view_updates, current_metadata),
+                )
                 .await
             {
                 Ok(ContractVerificationOutcome::Clear {}) => {}
@@ -259,12 +395,46 @@ impl ContractVerification for ContractVerifiers {
         Ok(ContractVerificationOutcome::Clear {})
     }
 
+    async fn check_table_create(
+        &self,
+        table: &TableIdent,
+        metadata: &TableMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        for checker in &self.checkers {
+            match self
+                .run(
+                    checker.as_ref(),
+                    checker.check_table_create(table, metadata),
+                )
+                .await
+            {
+                Ok(ContractVerificationOutcome::Clear {}) => {}
+                Ok(block_result @ ContractVerificationOutcome::Violation { error_model: _ }) => {
+                    tracing::info!(
+                        "ContractVerifier '{}' blocked creation of table '{:?}'",
+                        checker.name(),
+                        table
+                    );
+                    return Ok(block_result);
+                }
+                Err(error) => {
+                    tracing::warn!("ContractVerifier '{}' failed", checker.name());
+                    return Err(error);
+                }
+            }
+        }
+        Ok(ContractVerificationOutcome::Clear {})
+    }
+
     async fn check_drop(
         &self,
         table_ident_uuid: TabularIdentUuid,
     ) -> Result<ContractVerificationOutcome, ErrorModel> {
         for checker in &self.checkers {
-            match checker.check_drop(table_ident_uuid).await {
+            match self
+                .run(checker.as_ref(), checker.check_drop(table_ident_uuid))
+                .await
+            {
                 Ok(ContractVerificationOutcome::Clear {}) => {}
                 Ok(block_result @ ContractVerificationOutcome::Violation { error_model: _ }) => {
                     tracing::info!(
@@ -289,7 +459,10 @@ impl ContractVerification for ContractVerifiers {
         destination: &TableIdent,
     ) -> Result<ContractVerificationOutcome, ErrorModel> {
         for checker in &self.checkers {
-            match checker.check_rename(source, destination).await {
+            match self
+                .run(checker.as_ref(), checker.check_rename(source, destination))
+                .await
+            {
                 Ok(ContractVerificationOutcome::Clear {}) => {}
                 Ok(block_result @ ContractVerificationOutcome::Violation { error_model: _ }) => {
                     tracing::info!(
@@ -309,3 +482,114 @@ impl ContractVerification for ContractVerifiers {
         Ok(ContractVerificationOutcome::Clear {})
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug)]
+    struct SlowChecker;
+
+    #[async_trait]
+    impl ContractVerification for SlowChecker {
+        fn name(&self) -> &'static str {
+            "SlowChecker"
+        }
+
+        async fn check_table_updates(
+            &self,
+            _table_updates: &[TableUpdate],
+            _current_metadata: &TableMetadata,
+        ) -> Result<ContractVerificationOutcome, ErrorModel> {
+            Ok(ContractVerificationOutcome::Clear {})
+        }
+
+        async fn check_view_updates(
+            &self,
+            _view_updates: &[ViewUpdate],
+            _current_metadata: &ViewMetadata,
+        ) -> Result<ContractVerificationOutcome, ErrorModel> {
+            Ok(ContractVerificationOutcome::Clear {})
+        }
+
+        async fn check_drop(
+            &self,
+            _table_ident_uuid: TabularIdentUuid,
+        ) -> Result<ContractVerificationOutcome, ErrorModel> {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            Ok(ContractVerificationOutcome::Clear {})
+        }
+
+        async fn check_rename(
+            &self,
+            _source: TabularIdentUuid,
+            _destination: &TableIdent,
+        ) -> Result<ContractVerificationOutcome, ErrorModel> {
+            Ok(ContractVerificationOutcome::Clear {})
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checker_timeout() {
+        let verifiers = ContractVerifiers::new(vec![Arc::new(SlowChecker)])
+            .with_timeout(Duration::from_millis(10));
+        let err = verifiers
+            .check_drop(TabularIdentUuid::Table(uuid::Uuid::now_v7()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.r#type, "ContractVerificationTimeout");
+
+        let outcome = verifiers
+            .check_rename(
+                TabularIdentUuid::Table(uuid::Uuid::now_v7()),
+                &TableIdent::from_strs(["ns", "tbl"]).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(outcome, ContractVerificationOutcome::Clear {}));
+    }
+
+    #[derive(Debug)]
+    struct SlowCheckers;
+
+    impl WarehouseContractVerifiers for SlowCheckers {
+        fn resolve(
+            &self,
+            name: &str,
+        ) -> Result<Option<Arc<dyn ContractVerification + Sync + Send>>, ErrorModel> {
+            Ok((name == "slow").then(|| Arc::new(SlowChecker) as _))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_warehouse_verifiers() {
+        let verifiers = ContractVerifiers::new(vec![]).with_timeout(Duration::from_millis(10));
+        let err = verifiers
+            .validate_warehouse_verifiers(&["slow".to_string()])
+            .unwrap_err();
+        assert_eq!(err.r#type, "WarehouseContractVerifiersDisabled");
+        assert!(verifiers.for_warehouse(&[]).is_ok());
+
+        let verifiers = verifiers.with_warehouse_verifiers(Arc::new(SlowCheckers));
+        verifiers
+            .validate_warehouse_verifiers(&["slow".to_string()])
+            .unwrap();
+        let err = verifiers
+            .validate_warehouse_verifiers(&["missing".to_string()])
+            .unwrap_err();
+        assert_eq!(err.r#type, "ContractVerifierNotFound");
+
+        // Only warehouses referencing the verifier run it.
+        verifiers
+            .check_drop(TabularIdentUuid::Table(uuid::Uuid::now_v7()))
+            .await
+            .unwrap();
+        let err = verifiers
+            .for_warehouse(&["slow".to_string()])
+            .unwrap()
+            .check_drop(TabularIdentUuid::Table(uuid::Uuid::now_v7()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.r#type, "ContractVerificationTimeout");
+    }
+}
@@ -0,0 +1,128 @@
+use crate::api::management::v1::TabularType;
+use crate::api::Result;
+use crate::catalog::config::invalidate_warehouse_config;
+use crate::request_metadata::RequestMetadata;
+use crate::service::authn::AuthDetails;
+use crate::service::authz::Authorizer;
+use crate::service::task_queue::tabular_purge_queue::TabularPurgeInput;
+use crate::service::task_queue::TaskQueues;
+use crate::service::{
+    Catalog, TableIdentUuid, TabularIdentUuid, Transaction, ViewIdentUuid, WarehouseStatus,
+};
+use crate::WarehouseIdent;
+
+/// Periodically tear down ephemeral warehouses whose lifetime or idle period has passed.
+///
+/// Teardown takes several runs: the warehouse is deactivated, all of its tables and views
+/// are deleted and their locations are queued for purging. Once no purge of the warehouse
+/// is pending or running anymore, its namespaces and the warehouse itself are deleted.
+pub async fn ephemeral_warehouse_task<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: A,
+    queues: TaskQueues,
+    interval: std::time::Duration,
+) {
+    loop {
+        if let Err(err) =
+            teardown_expired_warehouses::<C, A>(catalog_state.clone(), &authorizer, &queues).await
+        {
+            tracing::error!("Failed to tear down ephemeral warehouses: {:?}", err.error);
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn teardown_expired_warehouses<C: Catalog, A: Authorizer>(
+    catalog_state: C::State,
+    authorizer: &A,
+    queues: &TaskQueues,
+) -> Result<()> {
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let warehouse_ids = C::list_expired_ephemeral_warehouses(t.transaction()).await?;
+    t.commit().await?;
+
+    for warehouse_id in warehouse_ids {
+        if let Err(err) =
+            teardown_warehouse::<C, A>(warehouse_id, catalog_state.clone(), authorizer, queues)
+                .await
+        {
+            tracing::error!(
+                "Failed to tear down ephemeral warehouse {warehouse_id}: {:?}",
+                err.error
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn teardown_warehouse<C: Catalog, A: Authorizer>(
+    warehouse_id: WarehouseIdent,
+    catalog_state: C::State,
+    authorizer: &A,
+    queues: &TaskQueues,
+) -> Result<()> {
+    // Purges started in an earlier run need the warehouse to resolve its storage.
+    if queues.has_unfinished_tabular_purges(warehouse_id).await? {
+        tracing::debug!("Waiting for purges of ephemeral warehouse {warehouse_id}");
+        return Ok(());
+    }
+
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    if warehouse.status == WarehouseStatus::Active {
+        C::set_warehouse_status(warehouse_id, WarehouseStatus::Inactive, t.transaction()).await?;
+    }
+    let tabulars = C::clear_warehouse_tabulars(warehouse_id, t.transaction()).await?;
+    if !tabulars.is_empty() {
+        // Purges are queued before the deletion is committed: if queuing fails, the
+        // tabulars are kept and cleared again in a later run instead of leaking their files.
+        // Purges are idempotent per tabular, so queuing them twice is harmless.
+        for (tabular_id, tabular_location) in &tabulars {
+            queues
+                .queue_tabular_purge(TabularPurgeInput {
+                    tabular_id: **tabular_id,
+                    warehouse_ident: warehouse_id,
+                    tabular_type: match tabular_id {
+                        TabularIdentUuid::Table(_) => TabularType::Table,
+                        TabularIdentUuid::View(_) => TabularType::View,
+                    },
+                    parent_id: None,
+                    tabular_location: tabular_location.clone(),
+                })
+                .await?;
+        }
+        t.commit().await?;
+        // Clients must not keep using the cached config of the deactivated warehouse.
+        invalidate_warehouse_config(warehouse_id);
+        tracing::info!(
+            "Deleted {} tabulars of expired ephemeral warehouse {warehouse_id}",
+            tabulars.len()
+        );
+        for (tabular_id, _) in tabulars {
+            match tabular_id {
+                TabularIdentUuid::Table(id) => {
+                    authorizer.delete_table(TableIdentUuid::from(id)).await?;
+                }
+                TabularIdentUuid::View(id) => {
+                    authorizer.delete_view(ViewIdentUuid::from(id)).await?;
+                }
+            }
+        }
+        // The warehouse is deleted in a later run, once the purges are done.
+        return Ok(());
+    }
+
+    let namespace_ids = C::clear_warehouse_namespaces(warehouse_id, t.transaction()).await?;
+    C::delete_warehouse(warehouse_id, t.transaction()).await?;
+    t.commit().await?;
+    invalidate_warehouse_config(warehouse_id);
+
+    let metadata = RequestMetadata::new(AuthDetails::Unauthenticated);
+    for namespace_id in namespace_ids {
+        authorizer.delete_namespace(&metadata, namespace_id).await?;
+    }
+    authorizer.delete_warehouse(&metadata, warehouse_id).await?;
+    tracing::info!("Deleted expired ephemeral warehouse {warehouse_id}");
+    Ok(())
+}
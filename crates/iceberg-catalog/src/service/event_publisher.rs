@@ -1,11 +1,17 @@
+use crate::config::RedactionAction;
 use crate::service::tabular_idents::TabularIdentUuid;
+use crate::CONFIG;
 use async_trait::async_trait;
-use cloudevents::Event;
+use cloudevents::{Data, Event};
+use rand::Rng;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use super::WarehouseIdent;
+use super::{UserId, WarehouseIdent};
 
 #[derive(Debug, Clone)]
 pub struct CloudEventsPublisher {
@@ -37,16 +43,61 @@ impl CloudEventsPublisher {
         data: serde_json::Value,
         metadata: EventMetadata,
     ) -> anyhow::Result<()> {
+        self.send(Payload {
+            id,
+            typ: typ.to_string(),
+            data,
+            metadata: PayloadMetadata::Tabular(metadata),
+        })
+        .await
+    }
+
+    /// Publish an event that concerns a warehouse as a whole rather than a single tabular.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be sent to the channel due to capacity / timeout.
+    pub async fn publish_warehouse_event(
+        &self,
+        id: Uuid,
+        typ: &str,
+        data: serde_json::Value,
+        metadata: WarehouseEventMetadata,
+    ) -> anyhow::Result<()> {
+        self.send(Payload {
+            id,
+            typ: typ.to_string(),
+            data,
+            metadata: PayloadMetadata::Warehouse(metadata),
+        })
+        .await
+    }
+
+    /// Publish an event that concerns a user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the event cannot be sent to the channel due to capacity / timeout.
+    pub async fn publish_user_event(
+        &self,
+        id: Uuid,
+        typ: &str,
+        data: serde_json::Value,
+        metadata: UserEventMetadata,
+    ) -> anyhow::Result<()> {
+        self.send(Payload {
+            id,
+            typ: typ.to_string(),
+            data,
+            metadata: PayloadMetadata::User(metadata),
+        })
+        .await
+    }
+
+    async fn send(&self, payload: Payload) -> anyhow::Result<()> {
+        let id = payload.id;
         self.tx
-            .send_timeout(
-                Message::Event(Payload {
-                    id,
-                    typ: typ.to_string(),
-                    data,
-                    metadata,
-                }),
-                self.timeout,
-            )
+            .send_timeout(Message::Event(payload), self.timeout)
             .await
             .map_err(|e| {
                 tracing::warn!("Failed to emit event with id: '{}' due to: '{}'.", id, e);
@@ -66,6 +117,29 @@ pub struct EventMetadata {
     pub num_events: usize,
     pub sequence_number: usize,
     pub trace_id: Uuid,
+    /// Id of the engine query or job that caused the event, if known.
+    pub engine_query_id: Option<String>,
+    /// Address of the client that sent the request causing the event, if known.
+    pub client_ip: Option<std::net::IpAddr>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WarehouseEventMetadata {
+    pub warehouse_id: WarehouseIdent,
+    pub trace_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserEventMetadata {
+    pub user_id: UserId,
+    pub trace_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub enum PayloadMetadata {
+    Tabular(EventMetadata),
+    Warehouse(WarehouseEventMetadata),
+    User(UserEventMetadata),
 }
 
 #[derive(Debug)]
@@ -73,7 +147,7 @@ pub struct Payload {
     pub id: Uuid,
     pub typ: String,
     pub data: serde_json::Value,
-    pub metadata: EventMetadata,
+    pub metadata: PayloadMetadata,
 }
 
 #[derive(Debug)]
@@ -83,6 +157,107 @@ pub enum Message {
     Shutdown,
 }
 
+/// Redaction and sampling applied to events before they are logged by the [`TracingPublisher`].
+/// Other sinks, such as Nats or the commit audit, receive events unchanged.
+#[derive(Debug, Clone, Default)]
+pub struct EventPolicy {
+    redaction: BTreeMap<String, RedactionAction>,
+    salt: String,
+    sampling: BTreeMap<String, f64>,
+}
+
+impl EventPolicy {
+    #[must_use]
+    pub fn new(
+        redaction: BTreeMap<String, RedactionAction>,
+        salt: Option<String>,
+        sampling: BTreeMap<String, f64>,
+    ) -> Self {
+        Self {
+            redaction,
+            salt: salt.unwrap_or_default(),
+            sampling,
+        }
+    }
+
+    /// Policy configured via `event_redaction`, `event_redaction_salt` and `event_sampling`.
+    #[must_use]
+    pub fn from_config() -> Self {
+        Self::new(
+            CONFIG.event_redaction.clone(),
+            CONFIG.event_redaction_salt.clone(),
+            CONFIG.event_sampling.clone(),
+        )
+    }
+
+    /// Whether an event of type `typ` should be emitted.
+    fn sample(&self, typ: &str) -> bool {
+        self.sampling
+            .get(typ)
+            .map_or(true, |rate| rand::thread_rng().gen_bool(*rate))
+    }
+
+    /// Drop or hash the configured fields in the extensions and data of `event`.
+    fn redact(&self, event: &mut Event) {
+        if self.redaction.is_empty() {
+            return;
+        }
+
+        for (field, action) in &self.redaction {
+            let Some(value) = event.extension(field).map(ToString::to_string) else {
+                continue;
+            };
+            match action {
+                RedactionAction::Drop => {
+                    event.remove_extension(field);
+                }
+                RedactionAction::Hash => {
+                    let hashed = self.hash(&value);
+                    event.set_extension(field, hashed);
+                }
+            }
+        }
+
+        if !matches!(event.data(), Some(Data::Json(_))) {
+            return;
+        }
+        if let (content_type, _, Some(Data::Json(mut data))) = event.take_data() {
+            self.redact_value(&mut data);
+            event.set_data(content_type.unwrap_or("application/json".to_string()), data);
+        }
+    }
+
+    fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (field, action) in &self.redaction {
+                    match action {
+                        RedactionAction::Drop => {
+                            map.remove(field);
+                        }
+                        RedactionAction::Hash => {
+                            if let Some(value) = map.get_mut(field) {
+                                let hashed = match value {
+                                    Value::String(s) => self.hash(s),
+                                    other => self.hash(&other.to_string()),
+                                };
+                                *value = Value::String(hashed);
+                            }
+                        }
+                    }
+                }
+                map.values_mut().for_each(|v| self.redact_value(v));
+            }
+            Value::Array(values) => values.iter_mut().for_each(|v| self.redact_value(v)),
+            _ => {}
+        }
+    }
+
+    fn hash(&self, value: &str) -> String {
+        format!("{:x}", Sha256::digest(format!("{}{value}", self.salt)))
+    }
+}
+
 #[derive(Debug)]
 pub struct CloudEventsPublisherBackgroundTask {
     pub source: tokio::sync::mpsc::Receiver<Message>,
@@ -113,34 +288,61 @@ impl CloudEventsPublisherBackgroundTask {
                 .ty(typ)
                 .data("application/json", data);
 
-            let EventMetadata {
-                tabular_id,
-                warehouse_id,
-                name,
-                namespace,
-                prefix,
-                num_events,
-                sequence_number,
-                trace_id,
-            } = metadata;
-            // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
-            let event = event_builder
-                .extension("tabular-type", tabular_id.typ_str())
-                .extension("tabular-id", tabular_id.to_string())
-                .extension("warehouse-id", warehouse_id.to_string())
-                .extension("name", name.to_string())
-                .extension("namespace", namespace.to_string())
-                .extension("prefix", prefix.to_string())
-                // TODO: decide what to do with these numbers, likely they are never anywhere close to
-                // saturating the respective int types, so probably a non-issue. Still we are converting
-                // the numbers to_string here to avoid usize -> i64 which is what EventBuilderV10
-                // uses to represent integers. The CloudEvents spec states i32 would be the correct int
-                // type.
-                .extension("num-events", num_events.to_string())
-                .extension("sequence-number", sequence_number.to_string())
-                // Implement distributed tracing: https://github.com/lakekeeper/lakekeeper/issues/63
-                .extension("trace-id", trace_id.to_string())
-                .build()?;
+            let event = match metadata {
+                PayloadMetadata::Tabular(EventMetadata {
+                    tabular_id,
+                    warehouse_id,
+                    name,
+                    namespace,
+                    prefix,
+                    num_events,
+                    sequence_number,
+                    trace_id,
+                    engine_query_id,
+                    client_ip,
+                }) => {
+                    // TODO: this could be more elegant with a proc macro to give us IntoIter for EventMetadata
+                    let event_builder = event_builder
+                        .extension("tabular-type", tabular_id.typ_str())
+                        .extension("tabular-id", tabular_id.to_string())
+                        .extension("warehouse-id", warehouse_id.to_string())
+                        .extension("name", name.to_string())
+                        .extension("namespace", namespace.to_string())
+                        .extension("prefix", prefix.to_string())
+                        // TODO: decide what to do with these numbers, likely they are never anywhere close to
+                        // saturating the respective int types, so probably a non-issue. Still we are converting
+                        // the numbers to_string here to avoid usize -> i64 which is what EventBuilderV10
+                        // uses to represent integers. The CloudEvents spec states i32 would be the correct int
+                        // type.
+                        .extension("num-events", num_events.to_string())
+                        .extension("sequence-number", sequence_number.to_string())
+                        // Implement distributed tracing: https://github.com/lakekeeper/lakekeeper/issues/63
+                        .extension("trace-id", trace_id.to_string());
+                    let event_builder = match engine_query_id {
+                        Some(engine_query_id) => {
+                            event_builder.extension("engine-query-id", engine_query_id)
+                        }
+                        None => event_builder,
+                    };
+                    match client_ip {
+                        Some(client_ip) => event_builder
+                            .extension("client-ip", client_ip.to_string())
+                            .build()?,
+                        None => event_builder.build()?,
+                    }
+                }
+                PayloadMetadata::Warehouse(WarehouseEventMetadata {
+                    warehouse_id,
+                    trace_id,
+                }) => event_builder
+                    .extension("warehouse-id", warehouse_id.to_string())
+                    .extension("trace-id", trace_id.to_string())
+                    .build()?,
+                PayloadMetadata::User(UserEventMetadata { user_id, trace_id }) => event_builder
+                    .extension("user-id", user_id.to_string())
+                    .extension("trace-id", trace_id.to_string())
+                    .build()?,
+            };
 
             for sink in &self.sinks {
                 if let Err(e) = sink.publish(event.clone()).await {
@@ -186,12 +388,18 @@ impl CloudEventBackend for NatsBackend {
     }
 }
 
-#[derive(Clone, Debug)]
-pub struct TracingPublisher;
+#[derive(Clone, Debug, Default)]
+pub struct TracingPublisher {
+    pub policy: EventPolicy,
+}
 
 #[async_trait::async_trait]
 impl CloudEventBackend for TracingPublisher {
-    async fn publish(&self, event: Event) -> anyhow::Result<()> {
+    async fn publish(&self, mut event: Event) -> anyhow::Result<()> {
+        if !self.policy.sample(event.ty()) {
+            return Ok(());
+        }
+        self.policy.redact(&mut event);
         let data = serde_json::to_string(&event).unwrap_or("Serialization failed".to_string());
         tracing::info!("Received event: {data}'");
         Ok(())
@@ -201,3 +409,65 @@ impl CloudEventBackend for TracingPublisher {
         "tracing-publisher"
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use cloudevents::{EventBuilder, EventBuilderV10};
+
+    #[test]
+    fn test_redact_event() {
+        let policy = EventPolicy::new(
+            BTreeMap::from([
+                ("name".to_string(), RedactionAction::Hash),
+                ("email".to_string(), RedactionAction::Drop),
+            ]),
+            Some("salt".to_string()),
+            BTreeMap::new(),
+        );
+        let mut event = EventBuilderV10::new()
+            .id(Uuid::now_v7().to_string())
+            .source("uri:test")
+            .ty("createUser")
+            .data(
+                "application/json",
+                serde_json::json!({"user": {"name": "Peter", "email": "peter@example.com"}, "id": 1}),
+            )
+            .extension("name", "orders")
+            .extension("email", "peter@example.com")
+            .extension("namespace", "sales")
+            .build()
+            .unwrap();
+
+        policy.redact(&mut event);
+
+        assert_eq!(
+            event.extension("name").unwrap().to_string(),
+            policy.hash("orders")
+        );
+        assert!(event.extension("email").is_none());
+        assert_eq!(event.extension("namespace").unwrap().to_string(), "sales");
+        assert_eq!(
+            event.data(),
+            Some(&Data::Json(
+                serde_json::json!({"user": {"name": policy.hash("Peter")}, "id": 1})
+            ))
+        );
+        assert_ne!(policy.hash("orders"), EventPolicy::default().hash("orders"));
+    }
+
+    #[test]
+    fn test_sample_event() {
+        let policy = EventPolicy::new(
+            BTreeMap::new(),
+            None,
+            BTreeMap::from([
+                ("loadTable".to_string(), 0.0),
+                ("updateTable".to_string(), 1.0),
+            ]),
+        );
+        assert!(!policy.sample("loadTable"));
+        assert!(policy.sample("updateTable"));
+        assert!(policy.sample("createTable"));
+    }
+}
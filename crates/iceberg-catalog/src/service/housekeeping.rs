@@ -0,0 +1,246 @@
+use std::collections::BTreeSet;
+use std::hash::Hash;
+use std::time::Duration;
+
+use axum_prometheus::metrics::counter;
+use futures::TryStreamExt;
+use iceberg_ext::configs::Location;
+use uuid::Uuid;
+
+use crate::api::Result;
+use crate::catalog::io::{list_location, remove_all};
+use crate::catalog::maybe_get_secret;
+use crate::service::storage::VALIDATION_PREFIX;
+use crate::service::{Catalog, GetWarehouseResponse, SecretStore, Transaction};
+
+const CACHE_ENTRIES_EVICTED_TOTAL: &str = "lakekeeper_housekeeping_cache_entries_evicted_total";
+const VALIDATION_DIRECTORIES_REMOVED_TOTAL: &str =
+    "lakekeeper_housekeeping_validation_directories_removed_total";
+const IDEMPOTENCY_KEYS_RELEASED_TOTAL: &str =
+    "lakekeeper_housekeeping_idempotency_keys_released_total";
+
+/// Validation files younger than this might still be in use by a running validation.
+const VALIDATION_FILE_MIN_AGE: chrono::Duration = chrono::Duration::days(1);
+/// Idempotency keys of finished tasks are kept for this long, so that work that was
+/// just completed is not queued again by a retried request.
+const IDEMPOTENCY_KEY_RETENTION: chrono::Duration = chrono::Duration::days(7);
+
+/// Periodically drop expired entries of the in-memory caches of this instance.
+///
+/// Caches only remove expired entries while they are used, so entries of warehouses,
+/// users or tables that are no longer accessed would otherwise stay in memory.
+/// Must run on every replica.
+pub async fn cache_housekeeping_task(interval: Duration) {
+    loop {
+        tokio::time::sleep(interval).await;
+        evict_expired_cache_entries();
+    }
+}
+
+/// Periodically remove artifacts that accumulate in long-running deployments:
+///
+/// * Test files of storage validations that were interrupted before their cleanup,
+///   once they are older than a day.
+/// * Idempotency keys of tasks that finished more than a week ago. The tasks are kept.
+/// * Per-transaction changes of the warehouse summaries, which are folded into their totals.
+///
+/// Must only run on a single replica.
+pub async fn housekeeping_task<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: S,
+    interval: Duration,
+) {
+    loop {
+        tokio::time::sleep(interval).await;
+
+        if let Err(err) = release_idempotency_keys::<C>(catalog_state.clone()).await {
+            tracing::error!("Failed to release idempotency keys: {:?}", err.error);
+        }
+
+        if let Err(err) = fold_warehouse_aggregate_deltas::<C>(catalog_state.clone()).await {
+            tracing::error!("Failed to fold warehouse summaries: {:?}", err.error);
+        }
+
+        let warehouses = match list_active_warehouses::<C>(catalog_state.clone()).await {
+            Ok(warehouses) => warehouses,
+            Err(err) => {
+                tracing::error!(
+                    "Failed to list warehouses for housekeeping: {:?}",
+                    err.error
+                );
+                continue;
+            }
+        };
+        for warehouse in warehouses {
+            if let Err(err) = remove_stale_validation_files(&warehouse, &secret_state).await {
+                tracing::warn!(
+                    "Failed to remove validation files of warehouse {}: {:?}",
+                    warehouse.id,
+                    err.error
+                );
+            }
+        }
+    }
+}
+
+/// Remove expired entries from `cache`. Returns the number of removed entries.
+pub(crate) fn evict_expired<K, V>(cache: &moka::sync::Cache<K, V>) -> u64
+where
+    K: Hash + Eq + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    let before = cache.entry_count();
+    cache.run_pending_tasks();
+    before.saturating_sub(cache.entry_count())
+}
+
+fn evict_expired_cache_entries() {
+    for (cache, evicted) in [
+        (
+            "warehouse-config",
+            crate::catalog::config::evict_expired_warehouse_configs(),
+        ),
+        (
+            "user-activity",
+            crate::service::authn::evict_expired_user_activity(),
+        ),
+        (
+            "impersonated-users",
+            crate::service::authn::evict_expired_impersonated_users(),
+        ),
+        (
+            "owning-projects",
+            crate::service::authn::evict_expired_owning_projects(),
+        ),
+        (
+            "allowed-client-networks",
+            crate::service::network_policy::evict_expired_allowed_client_networks(),
+        ),
+        (
+            "tabular-access",
+            crate::service::tabular_access::evict_expired_recent_access(),
+        ),
+        (
+            "missing-idents",
+            crate::service::missing_idents::evict_expired_missing_idents(),
+        ),
+    ] {
+        counter!(CACHE_ENTRIES_EVICTED_TOTAL, "cache" => cache).increment(evicted);
+    }
+}
+
+async fn release_idempotency_keys<C: Catalog>(catalog_state: C::State) -> Result<()> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let released = C::release_idempotency_keys(IDEMPOTENCY_KEY_RETENTION, t.transaction()).await?;
+    t.commit().await?;
+    if released > 0 {
+        tracing::info!("Released idempotency keys of {released} finished tasks");
+    }
+    counter!(IDEMPOTENCY_KEYS_RELEASED_TOTAL).increment(released);
+    Ok(())
+}
+
+async fn fold_warehouse_aggregate_deltas<C: Catalog>(catalog_state: C::State) -> Result<()> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let folded = C::fold_warehouse_aggregate_deltas(t.transaction()).await?;
+    t.commit().await?;
+    tracing::debug!("Folded {folded} changes of warehouse summaries");
+    Ok(())
+}
+
+async fn list_active_warehouses<C: Catalog>(
+    catalog_state: C::State,
+) -> Result<Vec<GetWarehouseResponse>> {
+    let mut t = C::Transaction::begin_read(catalog_state).await?;
+    let mut warehouses = Vec::new();
+    for project in C::list_projects(None, t.transaction()).await? {
+        warehouses.extend(C::list_warehouses(project.project_id, None, t.transaction()).await?);
+    }
+    t.commit().await?;
+    Ok(warehouses)
+}
+
+async fn remove_stale_validation_files<S: SecretStore>(
+    warehouse: &GetWarehouseResponse,
+    secret_state: &S,
+) -> Result<()> {
+    let secret = maybe_get_secret(warehouse.storage_secret_id, secret_state).await?;
+    let file_io = warehouse.storage_profile.file_io(secret.as_ref())?;
+    let min_created_at = chrono::Utc::now() - VALIDATION_FILE_MIN_AGE;
+
+    for mut validation_root in warehouse.storage_profile.base_locations()? {
+        validation_root
+            .without_trailing_slash()
+            .push(VALIDATION_PREFIX);
+
+        // Each validation writes below a directory named after a v7 uuid,
+        // which tells us when the validation started.
+        let mut directories = BTreeSet::new();
+        let mut pages = list_location(&file_io, &validation_root, None).await?;
+        while let Some(page) = pages.try_next().await? {
+            directories.extend(
+                page.iter()
+                    .filter_map(|path| validation_directory(path))
+                    .filter(|(_, created_at)| *created_at < min_created_at)
+                    .map(|(id, _)| id),
+            );
+        }
+
+        for id in directories {
+            let directory: Location = validation_root.cloning_push(&id.to_string());
+            remove_all(&file_io, &directory).await?;
+            tracing::info!(
+                "Removed leftover validation files at {directory} of warehouse {}",
+                warehouse.id
+            );
+            counter!(VALIDATION_DIRECTORIES_REMOVED_TOTAL).increment(1);
+        }
+    }
+    Ok(())
+}
+
+/// Directory of a single validation that contains `path`, and the time it was created.
+fn validation_directory(path: &str) -> Option<(Uuid, chrono::DateTime<chrono::Utc>)> {
+    let (_, relative) = path.split_once(&format!("/{VALIDATION_PREFIX}/"))?;
+    let id = Uuid::parse_str(relative.split('/').next()?).ok()?;
+    let (seconds, nanos) = id.get_timestamp()?.to_unix();
+    let created_at = chrono::DateTime::from_timestamp(i64::try_from(seconds).ok()?, nanos)?;
+    Some((id, created_at))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_directory() {
+        let id = Uuid::now_v7();
+        let path = format!("s3://bucket/prefix/{VALIDATION_PREFIX}/{id}/0190/metadata/test.json");
+        let (found, created_at) = validation_directory(&path).unwrap();
+        assert_eq!(found, id);
+        assert!((chrono::Utc::now() - created_at).num_seconds() < 60);
+
+        // Namespaces outside of the validation prefix are never touched
+        assert!(
+            validation_directory(&format!("s3://bucket/prefix/{id}/table/data.parquet")).is_none()
+        );
+        // Only v7 uuids carry a timestamp
+        let v4 = Uuid::new_v4();
+        assert!(
+            validation_directory(&format!("s3://bucket/{VALIDATION_PREFIX}/{v4}/file")).is_none()
+        );
+    }
+
+    #[test]
+    fn test_evict_expired() {
+        let cache = moka::sync::Cache::builder()
+            .time_to_live(Duration::from_millis(10))
+            .build();
+        cache.insert(1, ());
+        cache.insert(2, ());
+        cache.run_pending_tasks();
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(evict_expired(&cache), 2);
+        assert_eq!(cache.entry_count(), 0);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::api::{ErrorModel, Result};
+use crate::CONFIG;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::sync::{Arc, OnceLock};
+use uuid::Uuid;
+
+static GENERATOR: OnceLock<Arc<dyn IdGenerator>> = OnceLock::new();
+
+/// Property of namespaces, tables and views holding the id of the entity in an external
+/// asset registry. Set on creation, it is persisted alongside the entity and cannot be
+/// changed afterwards.
+pub const PROPERTY_EXTERNAL_ID: &str = "lakekeeper.external-id";
+
+/// Kind of entity an id is generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdKind {
+    Namespace,
+    Table,
+    View,
+}
+
+/// Input for generating the id of a new namespace, table or view.
+#[derive(Debug, Clone, Copy)]
+pub struct IdRequest<'a> {
+    pub kind: IdKind,
+    /// Name of the entity. For namespaces, the last level of the namespace.
+    pub name: &'a str,
+    /// Value of the `lakekeeper.external-id` property, if provided by the client.
+    pub external_id: Option<&'a str>,
+}
+
+/// Generates the ids of new namespaces, tables and views.
+///
+/// Ids must be unique. Registered tables keep the uuid of their metadata.
+pub trait IdGenerator: Debug + Send + Sync + 'static {
+    fn generate_id(&self, request: IdRequest<'_>) -> Uuid;
+}
+
+/// Built-in id generators, configured with `LAKEKEEPER__ID_GENERATION`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum IdGeneration {
+    /// Time-ordered UUIDv7, which keeps database indexes compact.
+    #[default]
+    UuidV7,
+    /// Random UUIDv4, which does not reveal the creation time.
+    UuidV4,
+}
+
+impl IdGenerator for IdGeneration {
+    fn generate_id(&self, _request: IdRequest<'_>) -> Uuid {
+        match self {
+            IdGeneration::UuidV7 => Uuid::now_v7(),
+            IdGeneration::UuidV4 => Uuid::new_v4(),
+        }
+    }
+}
+
+/// Replace the built-in generator configured by `LAKEKEEPER__ID_GENERATION` with a custom
+/// generator, for example to derive ids from the external ids of an asset registry.
+/// Must be called before the first entity is created.
+///
+/// # Errors
+/// Returns the generator if a generator has already been set or used.
+pub fn set_id_generator(
+    generator: Arc<dyn IdGenerator>,
+) -> std::result::Result<(), Arc<dyn IdGenerator>> {
+    GENERATOR.set(generator)
+}
+
+/// Reject property updates or removals touching [`PROPERTY_EXTERNAL_ID`].
+pub(crate) fn external_id_may_not_change<'a>(
+    properties: impl IntoIterator<Item = &'a String>,
+) -> Result<()> {
+    if properties
+        .into_iter()
+        .any(|property| property == PROPERTY_EXTERNAL_ID)
+    {
+        return Err(ErrorModel::conflict(
+            format!("Property `{PROPERTY_EXTERNAL_ID}` can only be set on creation."),
+            "ExternalIdCannotBeChanged",
+            None,
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Generate the id of a new namespace, table or view.
+pub(crate) fn generate_id(request: IdRequest<'_>) -> Uuid {
+    GENERATOR
+        .get_or_init(|| Arc::new(CONFIG.id_generation))
+        .generate_id(request)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_builtin_generators() {
+        let request = IdRequest {
+            kind: IdKind::Table,
+            name: "my_table",
+            external_id: None,
+        };
+        assert_eq!(
+            IdGeneration::UuidV7.generate_id(request).get_version_num(),
+            7
+        );
+        assert_eq!(
+            IdGeneration::UuidV4.generate_id(request).get_version_num(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_external_id_may_not_change() {
+        let err = external_id_may_not_change(&[PROPERTY_EXTERNAL_ID.to_string()]).unwrap_err();
+        assert_eq!(err.error.r#type, "ExternalIdCannotBeChanged");
+        external_id_may_not_change(&["owner".to_string()]).unwrap();
+    }
+}
@@ -0,0 +1,305 @@
+#![allow(clippy::module_name_repetitions)]
+use crate::service::TableIdentUuid;
+use crate::WarehouseIdent;
+use async_trait::async_trait;
+use iceberg::spec::TableMetadata;
+use iceberg_ext::catalog::rest::ErrorModel;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Submits maintenance jobs of tables to an external system.
+///
+/// Lakekeeper does not rewrite data files itself. Instead, once a table exceeds the
+/// configured [`CompactionThresholds`], a compaction task is queued and handed to the
+/// executor. The task stays running until the executor reports the job as finished.
+#[async_trait]
+pub trait MaintenanceExecutor: Debug + Send + Sync + 'static {
+    fn name(&self) -> &'static str;
+
+    /// Submit a compaction job. Returns the ID of the job in the external system.
+    ///
+    /// `job.task_id` is stable across retries of the same task and can be used by the
+    /// external system to deduplicate submissions.
+    async fn submit_compaction(&self, job: &MaintenanceJob) -> Result<String, ErrorModel>;
+
+    /// Get the status of a previously submitted job.
+    async fn job_status(&self, job_id: &str) -> Result<MaintenanceJobStatus, ErrorModel>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct MaintenanceJob {
+    pub task_id: Uuid,
+    pub warehouse_id: WarehouseIdent,
+    pub table_id: TableIdentUuid,
+    pub namespace: Vec<String>,
+    pub table_name: String,
+    pub location: String,
+    pub metadata_location: Option<String>,
+    /// Why the job was triggered
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "status")]
+pub enum MaintenanceJobStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed {
+        #[serde(default)]
+        message: Option<String>,
+    },
+}
+
+impl MaintenanceJobStatus {
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Succeeded | Self::Failed { .. })
+    }
+}
+
+/// Table statistics above which a compaction is requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionThresholds {
+    /// Minimum number of data files in the current snapshot.
+    pub min_data_files: u64,
+    /// Compaction is requested if the average data file is smaller than this size.
+    pub target_file_size_bytes: u64,
+}
+
+impl CompactionThresholds {
+    /// Returns the reason for a compaction if the current snapshot of the table exceeds
+    /// the thresholds. Statistics are taken from the snapshot summary, tables without
+    /// a summary containing `total-data-files` and `total-files-size` are never compacted.
+    #[must_use]
+    pub fn compaction_reason(&self, metadata: &TableMetadata) -> Option<String> {
+        let summary = &metadata.current_snapshot()?.summary().additional_properties;
+        let data_files = summary.get("total-data-files")?.parse::<u64>().ok()?;
+        let files_size = summary.get("total-files-size")?.parse::<u64>().ok()?;
+
+        if data_files == 0 || data_files < self.min_data_files {
+            return None;
+        }
+        let average_file_size = files_size / data_files;
+        (average_file_size < self.target_file_size_bytes).then(|| {
+            format!(
+                "{data_files} data files with an average size of {average_file_size} bytes (target: {} bytes)",
+                self.target_file_size_bytes
+            )
+        })
+    }
+}
+
+/// Submits jobs to an HTTP job runner.
+///
+/// Jobs are submitted via `POST {url}` with a [`MaintenanceJob`] as JSON body. The runner is
+/// expected to answer with `{"job-id": "<id>"}`. The status of a job is polled via
+/// `GET {url}/{job-id}` which must return `{"status": "pending" | "running" | "succeeded" | "failed", "message": ...}`.
+#[derive(Debug, Clone)]
+pub struct HttpMaintenanceExecutor {
+    client: reqwest::Client,
+    url: url::Url,
+    token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct SubmitJobResponse {
+    job_id: String,
+}
+
+impl HttpMaintenanceExecutor {
+    #[must_use]
+    pub fn new(url: url::Url, token: Option<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+            token,
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, url: url::Url) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, url);
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    fn job_url(&self, job_id: &str) -> Result<url::Url, ErrorModel> {
+        let mut url = self.url.clone();
+        url.path_segments_mut()
+            .map_err(|()| {
+                ErrorModel::internal(
+                    format!("Maintenance executor URL {} cannot be a base", self.url),
+                    "MaintenanceExecutorError",
+                    None,
+                )
+            })?
+            .pop_if_empty()
+            .push(job_id);
+        Ok(url)
+    }
+}
+
+fn executor_error(e: reqwest::Error) -> ErrorModel {
+    ErrorModel::failed_dependency(
+        "Request to maintenance executor failed",
+        "MaintenanceExecutorError",
+        Some(Box::new(e)),
+    )
+}
+
+#[async_trait]
+impl MaintenanceExecutor for HttpMaintenanceExecutor {
+    fn name(&self) -> &'static str {
+        "HttpMaintenanceExecutor"
+    }
+
+    async fn submit_compaction(&self, job: &MaintenanceJob) -> Result<String, ErrorModel> {
+        let response = self
+            .request(reqwest::Method::POST, self.url.clone())
+            .json(job)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(executor_error)?
+            .json::<SubmitJobResponse>()
+            .await
+            .map_err(executor_error)?;
+        Ok(response.job_id)
+    }
+
+    async fn job_status(&self, job_id: &str) -> Result<MaintenanceJobStatus, ErrorModel> {
+        self.request(reqwest::Method::GET, self.job_url(job_id)?)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(executor_error)?
+            .json::<MaintenanceJobStatus>()
+            .await
+            .map_err(executor_error)
+    }
+}
+
+pub type MaintenanceExecutorRef = Arc<dyn MaintenanceExecutor>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iceberg::spec::{
+        FormatVersion, NestedField, Operation, PrimitiveType, Schema, Snapshot, SnapshotReference,
+        SnapshotRetention, SortOrder, Summary, TableMetadataBuilder, Type, UnboundPartitionSpec,
+        MAIN_BRANCH,
+    };
+    use std::collections::HashMap;
+
+    fn metadata_with_summary(summary: HashMap<String, String>) -> TableMetadata {
+        let schema = Schema::builder()
+            .with_fields(vec![NestedField::required(
+                1,
+                "id",
+                Type::Primitive(PrimitiveType::Int),
+            )
+            .into()])
+            .build()
+            .unwrap();
+        TableMetadataBuilder::new(
+            schema,
+            UnboundPartitionSpec::builder().build(),
+            SortOrder::unsorted_order(),
+            "s3://bucket/table".to_string(),
+            FormatVersion::V2,
+            HashMap::new(),
+        )
+        .unwrap()
+        .add_snapshot(
+            Snapshot::builder()
+                .with_snapshot_id(1)
+                .with_sequence_number(1)
+                .with_timestamp_ms(chrono::Utc::now().timestamp_millis())
+                .with_manifest_list("s3://bucket/table/metadata/snap-1.avro")
+                .with_schema_id(0)
+                .with_summary(Summary {
+                    operation: Operation::Append,
+                    additional_properties: summary,
+                })
+                .build(),
+        )
+        .unwrap()
+        .set_ref(
+            MAIN_BRANCH,
+            SnapshotReference {
+                snapshot_id: 1,
+                retention: SnapshotRetention::Branch {
+                    min_snapshots_to_keep: None,
+                    max_snapshot_age_ms: None,
+                    max_ref_age_ms: None,
+                },
+            },
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .metadata
+    }
+
+    #[test]
+    fn test_compaction_reason() {
+        let thresholds = CompactionThresholds {
+            min_data_files: 10,
+            target_file_size_bytes: 100,
+        };
+
+        let small_files = metadata_with_summary(HashMap::from_iter([
+            ("total-data-files".to_string(), "20".to_string()),
+            ("total-files-size".to_string(), "200".to_string()),
+        ]));
+        assert!(thresholds.compaction_reason(&small_files).is_some());
+
+        let few_files = metadata_with_summary(HashMap::from_iter([
+            ("total-data-files".to_string(), "5".to_string()),
+            ("total-files-size".to_string(), "50".to_string()),
+        ]));
+        assert!(thresholds.compaction_reason(&few_files).is_none());
+
+        let large_files = metadata_with_summary(HashMap::from_iter([
+            ("total-data-files".to_string(), "20".to_string()),
+            ("total-files-size".to_string(), "20000".to_string()),
+        ]));
+        assert!(thresholds.compaction_reason(&large_files).is_none());
+
+        let no_summary = metadata_with_summary(HashMap::new());
+        assert!(thresholds.compaction_reason(&no_summary).is_none());
+    }
+
+    #[test]
+    fn test_job_status_deserialization() {
+        let status: MaintenanceJobStatus =
+            serde_json::from_str(r#"{"status": "failed", "message": "out of memory"}"#).unwrap();
+        assert_eq!(
+            status,
+            MaintenanceJobStatus::Failed {
+                message: Some("out of memory".to_string())
+            }
+        );
+        assert!(status.is_finished());
+
+        let status: MaintenanceJobStatus =
+            serde_json::from_str(r#"{"status": "running"}"#).unwrap();
+        assert!(!status.is_finished());
+    }
+
+    #[test]
+    fn test_job_url() {
+        let executor =
+            HttpMaintenanceExecutor::new("https://runner.example.com/jobs/".parse().unwrap(), None);
+        assert_eq!(
+            executor.job_url("abc").unwrap().as_str(),
+            "https://runner.example.com/jobs/abc"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::future::Future;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use iceberg::{NamespaceIdent, TableIdent};
+
+use crate::api::Result;
+use crate::service::housekeeping::evict_expired;
+use crate::{WarehouseIdent, CONFIG};
+
+/// Identifier that was looked up by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum LookupIdent {
+    Namespace(NamespaceIdent),
+    Table(TableIdent),
+    View(TableIdent),
+}
+
+/// Identifiers recently found not to exist. Engines probe the same missing tables over
+/// and over, for example optional feature tables, so the lookup is answered from memory
+/// until the entry expires or something is created in the warehouse on this instance.
+/// Only misses are cached, existing identifiers are always resolved from the database.
+struct MissingIdentCache(moka::sync::Cache<(WarehouseIdent, LookupIdent), ()>);
+
+impl MissingIdentCache {
+    fn new(ttl: Duration) -> Self {
+        Self(
+            moka::sync::Cache::builder()
+                .max_capacity(100_000)
+                .time_to_live(ttl)
+                .support_invalidation_closures()
+                .build(),
+        )
+    }
+
+    async fn lookup<T>(
+        &self,
+        warehouse_id: WarehouseIdent,
+        ident: LookupIdent,
+        lookup: impl Future<Output = Result<Option<T>>>,
+    ) -> Result<Option<T>> {
+        let key = (warehouse_id, ident);
+        if self.0.contains_key(&key) {
+            return Ok(None);
+        }
+        let result = lookup.await?;
+        if result.is_none() {
+            self.0.insert(key, ());
+        }
+        Ok(result)
+    }
+
+    fn invalidate_warehouse(&self, warehouse_id: WarehouseIdent) {
+        self.0
+            .invalidate_entries_if(move |(warehouse, _), ()| *warehouse == warehouse_id)
+            .expect("Invalidation closures are supported");
+    }
+}
+
+static MISSING_IDENTS: LazyLock<Option<MissingIdentCache>> = LazyLock::new(|| {
+    (CONFIG.missing_ident_cache_ttl_seconds > 0).then(|| {
+        MissingIdentCache::new(Duration::from_secs(CONFIG.missing_ident_cache_ttl_seconds))
+    })
+});
+
+pub(crate) fn evict_expired_missing_idents() -> u64 {
+    MISSING_IDENTS
+        .as_ref()
+        .map_or(0, |cache| evict_expired(&cache.0))
+}
+
+/// Resolve `ident` with `lookup`, unless it was recently found not to exist.
+pub(crate) async fn lookup_unless_missing<T>(
+    warehouse_id: WarehouseIdent,
+    ident: impl FnOnce() -> LookupIdent,
+    lookup: impl Future<Output = Result<Option<T>>>,
+) -> Result<Option<T>> {
+    match MISSING_IDENTS.as_ref() {
+        Some(cache) => cache.lookup(warehouse_id, ident(), lookup).await,
+        None => lookup.await,
+    }
+}
+
+/// Forget all missing identifiers of a warehouse. Must be called after namespaces, tables
+/// or views were created, renamed or restored in the warehouse and the change is committed.
+pub(crate) fn invalidate_missing_idents(warehouse_id: WarehouseIdent) {
+    if let Some(cache) = MISSING_IDENTS.as_ref() {
+        cache.invalidate_warehouse(warehouse_id);
+    }
+}
+
+/// Forget the missing identifiers of all warehouses, for changes that are not scoped to a
+/// single warehouse.
+pub(crate) fn invalidate_all_missing_idents() {
+    if let Some(cache) = MISSING_IDENTS.as_ref() {
+        cache.0.invalidate_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_missing_ident_cache() {
+        let cache = MissingIdentCache::new(Duration::from_secs(60));
+        let warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let other_warehouse_id = WarehouseIdent::from(uuid::Uuid::now_v7());
+        let table = || LookupIdent::Table(TableIdent::from_strs(["ns", "missing"]).unwrap());
+
+        let lookup = cache.lookup(warehouse_id, table(), async { Ok(None::<u8>) });
+        assert_eq!(lookup.await.unwrap(), None);
+        // Answered from the cache without running the lookup
+        let lookup = cache.lookup(warehouse_id, table(), async { Ok(Some(0)) });
+        assert_eq!(lookup.await.unwrap(), None);
+        // Views and other warehouses are cached separately
+        let view = LookupIdent::View(TableIdent::from_strs(["ns", "missing"]).unwrap());
+        let lookup = cache.lookup(warehouse_id, view, async { Ok(Some(1)) });
+        assert_eq!(lookup.await.unwrap(), Some(1));
+        let lookup = cache.lookup(other_warehouse_id, table(), async { Ok(Some(2)) });
+        assert_eq!(lookup.await.unwrap(), Some(2));
+
+        cache.invalidate_warehouse(warehouse_id);
+        let lookup = cache.lookup(warehouse_id, table(), async { Ok(Some(3)) });
+        assert_eq!(lookup.await.unwrap(), Some(3));
+        // Existing identifiers are not cached
+        let lookup = cache.lookup(warehouse_id, table(), async { Ok(None) });
+        assert_eq!(lookup.await.unwrap(), None);
+    }
+}
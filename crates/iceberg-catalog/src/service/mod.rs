@@ -1,23 +1,41 @@
 pub mod authn;
 pub mod authz;
 mod catalog;
+pub mod commit_audit;
 pub mod contract_verification;
+pub mod ephemeral_warehouses;
 pub mod event_publisher;
 pub mod health;
+pub mod housekeeping;
+pub mod id_generation;
+pub mod maintenance;
+pub(crate) mod missing_idents;
+pub mod network_policy;
 pub mod secrets;
+pub mod stale_users;
 pub mod storage;
+pub mod storage_health;
+pub mod storage_usage;
+pub mod table_ttl;
+pub(crate) mod tabular_access;
 mod tabular_idents;
 pub mod task_queue;
+#[cfg(feature = "wasm-hooks")]
+pub mod wasm_hooks;
 
 pub use authn::{Actor, AuthDetails};
 pub use catalog::{
-    Catalog, CommitTableResponse, CreateNamespaceRequest, CreateNamespaceResponse,
-    CreateOrUpdateUserResponse, CreateTableRequest, CreateTableResponse, DeletionDetails,
-    DropFlags, GetNamespaceResponse, GetProjectResponse, GetStorageConfigResponse,
-    GetTableMetadataResponse, GetWarehouseResponse, ListFlags, ListNamespacesQuery,
-    ListNamespacesResponse, LoadTableResponse, NamespaceIdent, Result, StartupValidationData,
-    TableCommit, TableCreation, TableIdent, Transaction, UpdateNamespacePropertiesRequest,
-    UpdateNamespacePropertiesResponse, ViewMetadataWithLocation,
+    Catalog, CatalogSearchMatch, CommitTableResponse, CreateNamespaceRequest,
+    CreateNamespaceResponse, CreateOrUpdateUserResponse, CreateTableRequest, CreateTableResponse,
+    DeletedTabularAtLocation, DeletionDetails, DropFlags, GetNamespaceResponse, GetProjectResponse,
+    GetStorageConfigResponse, GetTableMetadataResponse, GetWarehouseResponse, ListFlags,
+    ListNamespacesQuery, ListNamespacesResponse, LoadTableResponse, NamespaceIdent,
+    NamespaceStorageUsage, PendingExpiration, PrincipalDefaultWarehouse, Result, StaleUser,
+    StartupValidationData, TableCommit, TableCreation, TableIdent, TableMetrics,
+    TableMetricsReport, TableRelocation, TableStorageUsage, TableTtlCandidate, TabularAccess,
+    TabularAccessKind, TabularExpirationClaim, TabularLocationUsage, Transaction,
+    UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse, UserActivity,
+    ViewDependency, ViewDependent, ViewMetadataWithLocation, WarehouseStatistics,
 };
 use std::ops::Deref;
 pub(crate) use tabular_idents::TabularIdentBorrowed;
@@ -35,6 +53,7 @@ use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 pub use authn::{IdpVerifier, K8sVerifier, UserId};
+pub use iceberg_ext::management::{ProjectIdent, WarehouseIdent, WarehouseStatus};
 pub use secrets::{SecretIdent, SecretStore};
 
 // ---------------- State ----------------
@@ -50,7 +69,22 @@ pub struct State<A: Authorizer + Clone, C: Catalog, S: SecretStore> {
 
 impl<A: Authorizer + Clone, C: Catalog, S: SecretStore> ServiceState for State<A, C, S> {}
 
-#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
+// Identifiers of entities. They are transparent wrappers around their uuid in serde,
+// sqlx and OpenAPI, and are re-exported at the crate root.
+
+#[derive(
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Copy,
+    utoipa::ToSchema,
+)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
 #[cfg_attr(feature = "sqlx", sqlx(transparent))]
 #[serde(transparent)]
@@ -62,25 +96,43 @@ impl From<uuid::Uuid> for ViewIdentUuid {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
+#[derive(
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Copy,
+    utoipa::ToSchema,
+)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
 #[cfg_attr(feature = "sqlx", sqlx(transparent))]
 #[serde(transparent)]
 pub struct NamespaceIdentUuid(uuid::Uuid);
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
+#[derive(
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Copy,
+    utoipa::ToSchema,
+)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
 #[cfg_attr(feature = "sqlx", sqlx(transparent))]
 #[serde(transparent)]
 pub struct TableIdentUuid(uuid::Uuid);
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
-#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
-#[cfg_attr(feature = "sqlx", sqlx(transparent))]
-#[serde(transparent)]
-pub struct WarehouseIdent(pub(crate) uuid::Uuid);
-
-/// Status of a warehouse
+/// Result of the most recent background storage check of a warehouse
 #[derive(
     Debug,
     Clone,
@@ -88,8 +140,7 @@ pub struct WarehouseIdent(pub(crate) uuid::Uuid);
     PartialEq,
     Eq,
     Hash,
-    PartialOrd,
-    Ord,
+    Default,
     strum_macros::Display,
     serde::Serialize,
     serde::Deserialize,
@@ -100,49 +151,14 @@ pub struct WarehouseIdent(pub(crate) uuid::Uuid);
 #[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
 #[cfg_attr(
     feature = "sqlx",
-    sqlx(type_name = "warehouse_status", rename_all = "kebab-case")
-)]
-pub enum WarehouseStatus {
-    /// The warehouse is active and can be used
-    Active,
-    /// The warehouse is inactive and cannot be used.
-    Inactive,
-}
-
-#[derive(
-    Debug,
-    serde::Serialize,
-    serde::Deserialize,
-    Clone,
-    PartialEq,
-    Eq,
-    Hash,
-    PartialOrd,
-    Ord,
-    Copy,
-    utoipa::ToSchema,
+    sqlx(type_name = "storage_health_status", rename_all = "kebab-case")
 )]
-#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
-#[cfg_attr(feature = "sqlx", sqlx(transparent))]
-#[serde(transparent)]
-pub struct ProjectIdent(uuid::Uuid);
-
-impl Default for ProjectIdent {
-    fn default() -> Self {
-        Self(uuid::Uuid::now_v7())
-    }
-}
-impl From<ProjectIdent> for uuid::Uuid {
-    fn from(ident: ProjectIdent) -> Self {
-        ident.0
-    }
-}
-
-impl ProjectIdent {
-    #[must_use]
-    pub fn new(id: uuid::Uuid) -> Self {
-        Self(id)
-    }
+pub enum WarehouseStorageHealth {
+    /// The last storage check passed or no check has been performed yet
+    #[default]
+    Healthy,
+    /// The last storage check failed. Requests accessing storage are likely to fail.
+    DegradedStorage,
 }
 
 #[derive(Debug, Serialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
@@ -207,6 +223,90 @@ impl From<RoleId> for uuid::Uuid {
     }
 }
 
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Copy)]
+#[serde(transparent)]
+pub struct DomainId(uuid::Uuid);
+
+impl<'de> serde::Deserialize<'de> for DomainId {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<DomainId, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        DomainId::from_str(&s).map_err(|e| serde::de::Error::custom(e.error.message))
+    }
+}
+
+impl DomainId {
+    #[must_use]
+    pub fn new(id: uuid::Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl Default for DomainId {
+    fn default() -> Self {
+        Self(uuid::Uuid::now_v7())
+    }
+}
+
+impl FromStr for DomainId {
+    type Err = IcebergErrorResponse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(DomainId(uuid::Uuid::from_str(s).map_err(|e| {
+            ErrorModel::builder()
+                .code(StatusCode::BAD_REQUEST.into())
+                .message("Provided domain id is not a valid UUID".to_string())
+                .r#type("DomainIDIsNotUUID".to_string())
+                .source(Some(Box::new(e)))
+                .build()
+        })?))
+    }
+}
+
+impl std::fmt::Display for DomainId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Deref for DomainId {
+    type Target = uuid::Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<DomainId> for uuid::Uuid {
+    fn from(ident: DomainId) -> Self {
+        ident.0
+    }
+}
+
+impl From<ViewIdentUuid> for uuid::Uuid {
+    fn from(ident: ViewIdentUuid) -> Self {
+        ident.0
+    }
+}
+
+impl TryFrom<TabularIdentUuid> for ViewIdentUuid {
+    type Error = IcebergErrorResponse;
+
+    fn try_from(value: TabularIdentUuid) -> Result<Self, Self::Error> {
+        match value {
+            TabularIdentUuid::View(value) => Ok(value.into()),
+            TabularIdentUuid::Table(_) => Err(ErrorModel::internal(
+                "Provided identifier is not a view id",
+                "IdentifierIsNotViewID",
+                None,
+            )
+            .into()),
+        }
+    }
+}
+
 impl Deref for ViewIdentUuid {
     type Target = uuid::Uuid;
 
@@ -285,6 +385,12 @@ impl From<&uuid::Uuid> for NamespaceIdentUuid {
     }
 }
 
+impl From<NamespaceIdentUuid> for uuid::Uuid {
+    fn from(ident: NamespaceIdentUuid) -> Self {
+        ident.0
+    }
+}
+
 impl Default for TableIdentUuid {
     fn default() -> Self {
         Self(uuid::Uuid::now_v7())
@@ -350,88 +456,6 @@ impl TryFrom<TabularIdentUuid> for TableIdentUuid {
 
 // ---------------- Identifier ----------------
 
-impl Deref for ProjectIdent {
-    type Target = uuid::Uuid;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl std::fmt::Display for ProjectIdent {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl FromStr for ProjectIdent {
-    type Err = IcebergErrorResponse;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(ProjectIdent(uuid::Uuid::from_str(s).map_err(|e| {
-            ErrorModel::builder()
-                .code(StatusCode::BAD_REQUEST.into())
-                .message("Provided project id is not a valid UUID".to_string())
-                .r#type("ProjectIDIsNotUUID".to_string())
-                .source(Some(Box::new(e)))
-                .build()
-        })?))
-    }
-}
-
-impl WarehouseIdent {
-    #[must_use]
-    pub fn to_uuid(&self) -> uuid::Uuid {
-        **self
-    }
-
-    #[must_use]
-    pub fn as_uuid(&self) -> &uuid::Uuid {
-        self
-    }
-}
-
-impl Deref for WarehouseIdent {
-    type Target = uuid::Uuid;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl From<uuid::Uuid> for WarehouseIdent {
-    fn from(uuid: uuid::Uuid) -> Self {
-        Self(uuid)
-    }
-}
-
-impl std::fmt::Display for WarehouseIdent {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
-}
-
-impl FromStr for WarehouseIdent {
-    type Err = IcebergErrorResponse;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(WarehouseIdent(uuid::Uuid::from_str(s).map_err(|e| {
-            ErrorModel::builder()
-                .code(StatusCode::BAD_REQUEST.into())
-                .message("Provided warehouse id is not a valid UUID".to_string())
-                .r#type("WarehouseIDIsNotUUID".to_string())
-                .source(Some(Box::new(e)))
-                .build()
-        })?))
-    }
-}
-
-impl From<uuid::Uuid> for ProjectIdent {
-    fn from(uuid: uuid::Uuid) -> Self {
-        Self(uuid)
-    }
-}
-
 impl TryFrom<Prefix> for WarehouseIdent {
     type Error = IcebergErrorResponse;
 
@@ -447,7 +471,7 @@ impl TryFrom<Prefix> for WarehouseIdent {
                 .source(Some(Box::new(e)))
                 .build()
         })?;
-        Ok(WarehouseIdent(prefix))
+        Ok(WarehouseIdent::new(prefix))
     }
 }
 
@@ -456,3 +480,41 @@ pub struct TabularDetails {
     pub ident: TableIdentUuid,
     pub location: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifiers_are_transparent() {
+        let id = uuid::Uuid::now_v7();
+        let json = serde_json::json!(id);
+        assert_eq!(serde_json::json!(WarehouseIdent::from(id)), json);
+        assert_eq!(serde_json::json!(NamespaceIdentUuid::from(id)), json);
+        assert_eq!(serde_json::json!(TableIdentUuid::from(id)), json);
+        assert_eq!(serde_json::json!(ViewIdentUuid::from(id)), json);
+        assert_eq!(
+            serde_json::from_value::<ViewIdentUuid>(json).unwrap(),
+            ViewIdentUuid::from(id)
+        );
+
+        assert_eq!(uuid::Uuid::from(WarehouseIdent::from(id)), id);
+        assert_eq!(uuid::Uuid::from(NamespaceIdentUuid::from(id)), id);
+        assert_eq!(uuid::Uuid::from(ViewIdentUuid::from(id)), id);
+    }
+
+    #[test]
+    fn test_tabular_ident_conversion() {
+        let id = uuid::Uuid::now_v7();
+        let tabular = TabularIdentUuid::from(ViewIdentUuid::from(id));
+        assert_eq!(
+            serde_json::to_value(tabular).unwrap(),
+            serde_json::json!({"type": "view", "id": id})
+        );
+        assert_eq!(
+            ViewIdentUuid::try_from(tabular).unwrap(),
+            ViewIdentUuid::from(id)
+        );
+        assert!(TableIdentUuid::try_from(tabular).is_err());
+    }
+}
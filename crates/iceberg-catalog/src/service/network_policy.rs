@@ -0,0 +1,146 @@
+use crate::request_metadata::RequestMetadata;
+use crate::service::housekeeping::evict_expired;
+use crate::service::Catalog;
+use crate::WarehouseIdent;
+use axum::extract::{RawPathParams, Request, State};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Extension;
+use iceberg_ext::catalog::rest::{ErrorModel, IcebergErrorResponse};
+use ipnet::IpNet;
+use std::net::IpAddr;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+/// Allowed client networks per warehouse. Changes made through other instances
+/// take effect once the entry expired.
+static ALLOWED_NETWORKS_CACHE: LazyLock<moka::sync::Cache<WarehouseIdent, Arc<Vec<IpNet>>>> =
+    LazyLock::new(|| {
+        moka::sync::Cache::builder()
+            .max_capacity(10_000)
+            .time_to_live(Duration::from_secs(60))
+            .build()
+    });
+
+pub(crate) fn evict_expired_allowed_client_networks() -> u64 {
+    evict_expired(&ALLOWED_NETWORKS_CACHE)
+}
+
+/// Drop the cached allowed networks of a warehouse. Must be called after changes are committed.
+pub(crate) fn invalidate_allowed_client_networks(warehouse_id: WarehouseIdent) {
+    ALLOWED_NETWORKS_CACHE.invalidate(&warehouse_id);
+}
+
+fn is_allowed(client: Option<IpAddr>, allowed_networks: &[IpNet]) -> bool {
+    allowed_networks.is_empty()
+        || client.is_some_and(|ip| allowed_networks.iter().any(|net| net.contains(&ip)))
+}
+
+/// Ensure that the client of the request may access `warehouse_id`. Must be called
+/// by every handler that resolves a warehouse other than from its path.
+///
+/// # Errors
+/// - the warehouse has allowed client networks that do not contain the client address,
+///   or the client address cannot be determined
+/// - the allowed networks cannot be loaded
+pub(crate) async fn require_allowed_client_network<C: Catalog>(
+    metadata: &RequestMetadata,
+    warehouse_id: WarehouseIdent,
+    catalog_state: C::State,
+) -> Result<(), IcebergErrorResponse> {
+    let allowed_networks = if let Some(networks) = ALLOWED_NETWORKS_CACHE.get(&warehouse_id) {
+        networks
+    } else {
+        match C::get_warehouse_allowed_client_networks(warehouse_id, catalog_state).await? {
+            Some(networks) => {
+                let networks = Arc::new(networks);
+                ALLOWED_NETWORKS_CACHE.insert(warehouse_id, networks.clone());
+                networks
+            }
+            // Unknown warehouses are rejected by the handlers.
+            None => return Ok(()),
+        }
+    };
+
+    let client = metadata.client_ip();
+    if is_allowed(client, &allowed_networks) {
+        return Ok(());
+    }
+    tracing::info!(
+        "Rejected request to warehouse {warehouse_id} from {}",
+        client.map_or_else(|| "unknown address".to_string(), |ip| ip.to_string())
+    );
+    Err(ErrorModel::forbidden(
+        format!("Warehouse {warehouse_id} cannot be accessed from this network"),
+        "ClientNetworkNotAllowed",
+        None,
+    )
+    .into())
+}
+
+/// Warehouses addressed by the path parameters of a request, either as prefix of the
+/// catalog API or as warehouse of the management API.
+fn warehouses_of_path_params<'a>(
+    params: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Vec<WarehouseIdent> {
+    params
+        .into_iter()
+        .filter(|(key, _)| matches!(*key, "prefix" | "warehouse_id"))
+        .filter_map(|(_, value)| uuid::Uuid::parse_str(value).ok())
+        .map(WarehouseIdent::from)
+        .collect()
+}
+
+/// Rejects requests to warehouses with allowed client networks from other addresses.
+/// Requests whose client address cannot be determined are rejected as well.
+///
+/// Must be applied as route layer, so that the warehouse is taken from the decoded
+/// path parameters of the matched route.
+pub(crate) async fn network_policy_middleware_fn<C: Catalog>(
+    State(catalog_state): State<C::State>,
+    Extension(metadata): Extension<RequestMetadata>,
+    params: RawPathParams,
+    request: Request,
+    next: Next,
+) -> Response {
+    for warehouse_id in warehouses_of_path_params(&params) {
+        if let Err(e) =
+            require_allowed_client_network::<C>(&metadata, warehouse_id, catalog_state.clone())
+                .await
+        {
+            return e.into_response();
+        }
+    }
+
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_warehouses_of_path_params() {
+        let id = uuid::Uuid::now_v7();
+        let encoded = id.to_string();
+        assert_eq!(
+            warehouses_of_path_params([("prefix", encoded.as_str()), ("namespace", "ns")]),
+            vec![WarehouseIdent::from(id)]
+        );
+        assert_eq!(
+            warehouses_of_path_params([("warehouse_id", encoded.as_str())]),
+            vec![WarehouseIdent::from(id)]
+        );
+        assert!(warehouses_of_path_params([("table_id", encoded.as_str())]).is_empty());
+        assert!(warehouses_of_path_params([("prefix", "aws")]).is_empty());
+    }
+
+    #[test]
+    fn test_is_allowed() {
+        let networks: Vec<IpNet> = vec!["10.1.0.0/16".parse().unwrap()];
+        assert!(is_allowed("10.1.2.3".parse().ok(), &networks));
+        assert!(!is_allowed("10.2.2.3".parse().ok(), &networks));
+        assert!(!is_allowed(None, &networks));
+        assert!(is_allowed(None, &[]));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::api::Result;
+use crate::config::StaleUserAction;
+use crate::service::authn::invalidate_user_activity;
+use crate::service::event_publisher::{CloudEventsPublisher, UserEventMetadata};
+use crate::service::{Catalog, StaleUser, Transaction, UserId};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Interval in which the policy is applied.
+const POLL_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Periodically flag or deactivate users that were not seen for `stale_after`.
+///
+/// Users are seen whenever they send an authenticated request. Users that were never
+/// seen are judged by the time they were registered. For every affected user a
+/// `userFlaggedStale` or `userDeactivated` event is emitted.
+pub async fn stale_user_task<C: Catalog>(
+    catalog_state: C::State,
+    publisher: CloudEventsPublisher,
+    stale_after: Duration,
+    action: StaleUserAction,
+    exempt: Vec<UserId>,
+) {
+    let Ok(stale_after) = chrono::Duration::from_std(stale_after) else {
+        tracing::error!("Invalid stale user threshold: {stale_after:?}");
+        return;
+    };
+
+    loop {
+        match apply_policy::<C>(catalog_state.clone(), stale_after, action, &exempt).await {
+            Ok(users) => {
+                for user in users {
+                    publish_event(&publisher, action, user).await;
+                }
+            }
+            Err(err) => {
+                tracing::error!("Failed to apply stale user policy: {:?}", err.error);
+            }
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn apply_policy<C: Catalog>(
+    catalog_state: C::State,
+    stale_after: chrono::Duration,
+    action: StaleUserAction,
+    exempt: &[UserId],
+) -> Result<Vec<StaleUser>> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let users = C::apply_stale_user_policy(stale_after, action, exempt, t.transaction()).await?;
+    t.commit().await?;
+    if action == StaleUserAction::Deactivate {
+        // Deactivated users must not keep access until their cached activity expires.
+        for user in &users {
+            invalidate_user_activity(&user.user_id);
+        }
+    }
+    if !users.is_empty() {
+        tracing::info!(
+            "Stale user policy ({action:?}) affected {} users",
+            users.len()
+        );
+    }
+    Ok(users)
+}
+
+async fn publish_event(publisher: &CloudEventsPublisher, action: StaleUserAction, user: StaleUser) {
+    let event_type = match action {
+        StaleUserAction::Flag => "userFlaggedStale",
+        StaleUserAction::Deactivate => "userDeactivated",
+    };
+    let event_id = Uuid::now_v7();
+    let _ = publisher
+        .publish_user_event(
+            event_id,
+            event_type,
+            serde_json::json!({
+                "user-id": user.user_id,
+                "user-name": user.name,
+                "last-seen-at": user.last_seen_at,
+            }),
+            UserEventMetadata {
+                user_id: user.user_id,
+                trace_id: event_id,
+            },
+        )
+        .await;
+}
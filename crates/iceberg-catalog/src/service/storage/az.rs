@@ -6,7 +6,9 @@ use crate::service::storage::error::{
     CredentialsError, FileIoError, TableConfigError, UpdateError, ValidationError,
 };
 use crate::service::storage::path_utils::reduce_scheme_string;
-use crate::service::storage::{StoragePermissions, StorageProfile, StorageType, TableConfig};
+use crate::service::storage::{
+    MetadataNaming, StoragePermissions, StorageProfile, StorageType, TableConfig,
+};
 use azure_storage::prelude::{BlobSasPermissions, BlobSignedResource};
 use azure_storage::shared_access_signature::service_sas::BlobSharedAccessSignature;
 use azure_storage::shared_access_signature::SasToken;
@@ -44,6 +46,10 @@ pub struct AdlsProfile {
     pub host: Option<String>,
     /// The validity of the sas token in seconds. Default: 3600.
     pub sas_token_validity_seconds: Option<u64>,
+    /// Naming of metadata files written for tables and views.
+    /// Defaults to `<count>-<uuid>.metadata.json`.
+    #[serde(default)]
+    pub metadata_naming: MetadataNaming,
 }
 
 const DEFAULT_HOST: &str = "dfs.core.windows.net";
@@ -66,6 +72,7 @@ impl AdlsProfile {
         self.host = self.host.take().map(normalize_host).transpose()?.flatten();
         self.normalize_key_prefix()?;
         validate_account_name(&self.account_name)?;
+        self.metadata_naming.validate()?;
 
         Ok(())
     }
@@ -155,22 +162,7 @@ impl AdlsProfile {
         creds: &AzCredential,
         permissions: StoragePermissions,
     ) -> Result<TableConfig, TableConfigError> {
-        let AzCredential::ClientCredentials {
-            client_id,
-            tenant_id,
-            client_secret,
-        } = creds;
-        let http_client = STS_CLIENT.get_or_init(reqwest::Client::new).clone();
-        let token = azure_identity::ClientSecretCredential::new(
-            Arc::new(http_client),
-            self.authority_host
-                .clone()
-                .unwrap_or(DEFAULT_AUTHORITY_HOST.clone()),
-            tenant_id.clone(),
-            client_id.clone(),
-            client_secret.clone(),
-        );
-        let cred = azure_storage::StorageCredentials::token_credential(Arc::new(token));
+        let cred = self.storage_credentials(creds);
         let mut creds = TableProperties::default();
 
         let sas = self
@@ -189,6 +181,95 @@ impl AdlsProfile {
         })
     }
 
+    /// Generate a URL including a short-lived SAS token to download the
+    /// file at `location` without any further credentials.
+    ///
+    /// # Errors
+    /// Fails if the location is invalid or the sas token cannot be generated.
+    pub async fn presign_download_url(
+        &self,
+        creds: &AzCredential,
+        location: &Location,
+        expires_in: std::time::Duration,
+    ) -> Result<Url, TableConfigError> {
+        let location = AdlsLocation::try_from(location.clone()).map_err(|e| {
+            TableConfigError::Internal("Invalid ADLS location".to_string(), Some(Box::new(e)))
+        })?;
+        let client =
+            blob_service_client(self.account_name.as_str(), self.storage_credentials(creds));
+
+        let start = time::OffsetDateTime::now_utc();
+        let expiry = i64::try_from(expires_in.as_secs())
+            .ok()
+            .and_then(|seconds| start.checked_add(time::Duration::seconds(seconds)))
+            .ok_or_else(|| {
+                TableConfigError::Misconfiguration(format!(
+                    "Cannot issue a URL valid for {} seconds",
+                    expires_in.as_secs()
+                ))
+            })?;
+        let delegation_key = client
+            .get_user_deligation_key(start, expiry)
+            .await
+            .map_err(|e| CredentialsError::ShortTermCredential {
+                reason: "Error getting azure user delegation key.".to_string(),
+                source: Some(Box::new(e)),
+            })?;
+
+        let path = location.key().join("/");
+        let canonical_resource = format!(
+            "/blob/{}/{}/{path}",
+            location.account_name(),
+            location.filesystem(),
+        );
+        let sas = BlobSharedAccessSignature::new(
+            delegation_key.user_deligation_key.clone(),
+            canonical_resource,
+            StoragePermissions::Read.into(),
+            delegation_key.user_deligation_key.signed_expiry,
+            BlobSignedResource::Blob,
+        )
+        .token()
+        .map_err(|e| CredentialsError::ShortTermCredential {
+            reason: "Error getting azure sas token.".to_string(),
+            source: Some(Box::new(e)),
+        })?;
+
+        let mut url = Url::parse(&format!(
+            "https://{}.{}",
+            location.account_name(),
+            location.endpoint_suffix()
+        ))
+        .map_err(|e| {
+            TableConfigError::Internal("Invalid ADLS endpoint".to_string(), Some(Box::new(e)))
+        })?;
+        url.path_segments_mut()
+            .map_err(|()| TableConfigError::Internal("Invalid ADLS endpoint".to_string(), None))?
+            .push(location.filesystem())
+            .extend(location.key());
+        url.set_query(Some(&sas));
+        Ok(url)
+    }
+
+    fn storage_credentials(&self, creds: &AzCredential) -> StorageCredentials {
+        let AzCredential::ClientCredentials {
+            client_id,
+            tenant_id,
+            client_secret,
+        } = creds;
+        let http_client = STS_CLIENT.get_or_init(reqwest::Client::new).clone();
+        let token = azure_identity::ClientSecretCredential::new(
+            Arc::new(http_client),
+            self.authority_host
+                .clone()
+                .unwrap_or(DEFAULT_AUTHORITY_HOST.clone()),
+            tenant_id.clone(),
+            client_id.clone(),
+            client_secret.clone(),
+        );
+        azure_storage::StorageCredentials::token_credential(Arc::new(token))
+    }
+
     /// Create a new `FileIO` instance for Adls.
     ///
     /// # Errors
@@ -408,6 +489,11 @@ impl AdlsLocation {
     pub fn endpoint_suffix(&self) -> &str {
         &self.endpoint_suffix
     }
+
+    #[must_use]
+    pub fn key(&self) -> &Vec<String> {
+        &self.key
+    }
 }
 
 impl std::fmt::Display for AdlsLocation {
@@ -771,7 +857,7 @@ mod test {
 
     #[needs_env_var(TEST_AZURE = 1)]
     mod azure_tests {
-        use crate::service::storage::{AdlsProfile, AzCredential};
+        use crate::service::storage::{AdlsProfile, AzCredential, MetadataNaming};
         use crate::service::storage::{StorageCredential, StorageProfile};
 
         fn azure_profile() -> (AdlsProfile, AzCredential) {
@@ -788,6 +874,7 @@ mod test {
                 authority_host: None,
                 host: None,
                 sas_token_validity_seconds: None,
+                metadata_naming: MetadataNaming::default(),
             };
 
             let cred = AzCredential::ClientCredentials {
@@ -842,6 +929,7 @@ mod test {
             authority_host: None,
             host: None,
             sas_token_validity_seconds: None,
+            metadata_naming: MetadataNaming::default(),
         };
 
         let sp: StorageProfile = profile.clone().into();
@@ -37,7 +37,9 @@ impl From<TableConfigError> for ValidationError {
     fn from(value: TableConfigError) -> Self {
         match value {
             TableConfigError::Credentials(e) => e.into(),
-            TableConfigError::FailedDependency(_) | TableConfigError::Misconfiguration(_) => {
+            TableConfigError::FailedDependency(_)
+            | TableConfigError::Misconfiguration(_)
+            | TableConfigError::Unsupported(_) => {
                 let reason = value.to_string();
                 ValidationError::InvalidProfile {
                     source: Some(Box::new(value)),
@@ -141,6 +143,8 @@ pub enum TableConfigError {
     FailedDependency(String),
     #[error("Misconfiguration: {0}")]
     Misconfiguration(String),
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
     #[error("Internal error: {0}")]
     Internal(
         String,
@@ -159,6 +163,12 @@ impl From<TableConfigError> for IcebergErrorResponse {
             e @ TableConfigError::Misconfiguration(_) => {
                 ErrorModel::bad_request(e.to_string(), "Misconfiguration", Some(Box::new(e))).into()
             }
+            e @ TableConfigError::Unsupported(_) => ErrorModel::not_implemented(
+                e.to_string(),
+                "UnsupportedOperation",
+                Some(Box::new(e)),
+            )
+            .into(),
             e @ TableConfigError::Internal(_, _) => {
                 ErrorModel::internal(e.to_string(), "StsError", Some(Box::new(e))).into()
             }
@@ -195,6 +205,8 @@ pub enum CredentialsError {
     Mismatch(#[from] ConversionError),
     #[error("Failed to serialize credential.")]
     SerializationError(#[from] serde_json::Error),
+    #[error("None of the signing keys of the credential is currently valid.")]
+    NoValidSigningKey,
 }
 
 impl From<CredentialsError> for IcebergErrorResponse {
@@ -219,6 +231,9 @@ impl From<CredentialsError> for IcebergErrorResponse {
             CredentialsError::SerializationError(_) => {
                 ErrorModel::internal(message, "SerializationError", Some(boxed)).into()
             }
+            CredentialsError::NoValidSigningKey => {
+                ErrorModel::precondition_failed(message, "NoValidSigningKey", Some(boxed)).into()
+            }
         }
     }
 }
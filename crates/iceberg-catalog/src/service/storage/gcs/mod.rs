@@ -3,23 +3,29 @@
 use crate::WarehouseIdent;
 
 use crate::api::{iceberg::v1::DataAccess, CatalogConfig};
+use crate::catalog::io::IoError;
+use crate::retry::retry_fn;
 use crate::service::storage::error::{
     CredentialsError, FileIoError, TableConfigError, UpdateError, ValidationError,
 };
-use crate::service::storage::{StoragePermissions, TableConfig};
+use crate::service::storage::{MetadataNaming, StoragePermissions, TableConfig};
 
 use super::StorageType;
 use crate::api::iceberg::supported_endpoints;
 use base64::Engine;
+use bytes::Bytes;
 use iceberg_ext::configs::table::{gcs, TableProperties};
 use iceberg_ext::configs::Location;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::LazyLock;
 use veil::Redact;
 
 mod sts;
 
+static GCS_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(reqwest::Client::new);
+
 #[derive(Debug, Eq, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "kebab-case")]
 pub struct GcsProfile {
@@ -28,13 +34,22 @@ pub struct GcsProfile {
     /// Subpath in the bucket to use.
     /// The same prefix can be used for multiple warehouses.
     pub key_prefix: Option<String>,
+    /// Optional Cloud KMS key used to encrypt files written by Lakekeeper.
+    /// The key is also passed to engines as `gcs.kms-key-name`.
+    /// Example: `projects/my-project/locations/europe-west3/keyRings/my-ring/cryptoKeys/my-key`
+    #[serde(default)]
+    pub kms_key_name: Option<String>,
+    /// Naming of metadata files written for tables and views.
+    /// Defaults to `<count>-<uuid>.metadata.json`.
+    #[serde(default)]
+    pub metadata_naming: MetadataNaming,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(tag = "credential-type", rename_all = "kebab-case")]
 /// GCS Credentials
 ///
-/// Currently only supports Service Account Key
+/// Supports a Service Account Key or Workload Identity Federation.
 /// Example of a key:
 /// ```json
 ///     {
@@ -55,8 +70,133 @@ pub enum GcsCredential {
     /// Service Account Key
     ///
     /// The key is the JSON object obtained when creating a service account key in the GCP console.
+    /// Lakekeeper always uses the key to access storage itself. If `token-exchange` is set,
+    /// credentials vended to engines are minted via workload identity federation instead of
+    /// being downscoped from the service account.
     #[schema(title = "GcsCredentialServiceAccountKey")]
-    ServiceAccountKey { key: GcsServiceKey },
+    #[serde(rename_all = "kebab-case")]
+    ServiceAccountKey {
+        key: GcsServiceKey,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_exchange: Option<GcsTokenExchange>,
+    },
+    /// Workload Identity Federation
+    ///
+    /// Credentials vended to engines are minted via workload identity federation without
+    /// a service account key. Lakekeeper accesses storage itself with the application
+    /// default credentials of its environment, for example GKE workload identity.
+    #[schema(title = "GcsCredentialWorkloadIdentityFederation")]
+    #[serde(rename_all = "kebab-case")]
+    WorkloadIdentityFederation { token_exchange: GcsTokenExchange },
+}
+
+impl GcsCredential {
+    /// Validate the credential.
+    ///
+    /// # Errors
+    /// Fails if the token exchange is invalid.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            GcsCredential::ServiceAccountKey {
+                token_exchange: Some(token_exchange),
+                ..
+            }
+            | GcsCredential::WorkloadIdentityFederation { token_exchange } => {
+                token_exchange.validate()
+            }
+            GcsCredential::ServiceAccountKey {
+                token_exchange: None,
+                ..
+            } => Ok(()),
+        }
+    }
+}
+
+/// Mint vended credentials by exchanging a token of an `OpenID` provider at the Google STS.
+///
+/// Lakekeeper obtains a token for itself from `token-endpoint` using the client credentials
+/// grant, exchanges it (RFC 8693) for a federated access token of `workload-identity-provider`
+/// and restricts it to the table location with a credential access boundary.
+#[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct GcsTokenExchange {
+    /// Token endpoint of the `OpenID` provider.
+    /// Example: `https://keycloak.local/realms/lakekeeper/protocol/openid-connect/token`
+    pub token_endpoint: String,
+    /// Client id of Lakekeeper at the `OpenID` provider.
+    pub client_id: String,
+    #[redact(partial)]
+    pub client_secret: String,
+    /// Scope to request from the `OpenID` provider.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Audience to request from the `OpenID` provider, if it differs from the default
+    /// audience of the client.
+    #[serde(default)]
+    pub audience: Option<String>,
+    /// Full resource name of the workload identity pool provider trusting the `OpenID` provider.
+    /// Example: `//iam.googleapis.com/projects/<project-number>/locations/global/workloadIdentityPools/<pool-id>/providers/<provider-id>`
+    pub workload_identity_provider: String,
+}
+
+impl GcsTokenExchange {
+    const WORKLOAD_IDENTITY_PROVIDER_PREFIX: &'static str = "//iam.googleapis.com/projects/";
+
+    /// Validate the token exchange.
+    ///
+    /// # Errors
+    /// Fails if the token endpoint is not an http(s) URL, the client id, scope or audience
+    /// is empty or the workload identity provider is not a full resource name.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let invalid = |reason: &str| ValidationError::InvalidProfile {
+            source: None,
+            reason: reason.to_string(),
+            entity: "TokenExchange".to_string(),
+        };
+
+        if !url::Url::parse(&self.token_endpoint)
+            .is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+        {
+            return Err(invalid("`token-endpoint` must be an http(s) URL."));
+        }
+        if self.client_id.trim().is_empty() {
+            return Err(invalid("`client-id` must not be empty."));
+        }
+        if self.scope.as_ref().is_some_and(|s| s.trim().is_empty()) {
+            return Err(invalid("`scope` must not be empty if set."));
+        }
+        if self.audience.as_ref().is_some_and(|a| a.trim().is_empty()) {
+            return Err(invalid("`audience` must not be empty if set."));
+        }
+        if !self.is_valid_workload_identity_provider() {
+            return Err(invalid(
+                "`workload-identity-provider` must have the format `//iam.googleapis.com/projects/<project-number>/locations/global/workloadIdentityPools/<pool-id>/providers/<provider-id>`.",
+            ));
+        }
+        Ok(())
+    }
+
+    fn is_valid_workload_identity_provider(&self) -> bool {
+        let Some(rest) = self
+            .workload_identity_provider
+            .strip_prefix(Self::WORKLOAD_IDENTITY_PROVIDER_PREFIX)
+        else {
+            return false;
+        };
+        let is_id = |s: &str| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        };
+        matches!(
+            rest.split('/').collect::<Vec<_>>().as_slice(),
+            [project_number, "locations", "global", "workloadIdentityPools", pool, "providers", provider]
+                if !project_number.is_empty()
+                    && project_number.chars().all(|c| c.is_ascii_digit())
+                    && is_id(pool)
+                    && is_id(provider)
+        )
+    }
 }
 
 #[derive(Redact, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
@@ -87,7 +227,8 @@ impl GcsProfile {
     ) -> Result<iceberg::io::FileIO, FileIoError> {
         let mut builder = iceberg::io::FileIOBuilder::new("gcs");
 
-        if let Some(GcsCredential::ServiceAccountKey { key }) = credential {
+        // Without a service account key, the application default credentials are used.
+        if let Some(GcsCredential::ServiceAccountKey { key, .. }) = credential {
             builder = builder.with_prop(
                 iceberg::io::GCS_CREDENTIALS_JSON,
                 // guess we're doing base64 now ¯\_(._.)_/¯
@@ -110,6 +251,8 @@ impl GcsProfile {
     pub(super) fn normalize(&mut self) -> Result<(), ValidationError> {
         validate_bucket_name(&self.bucket)?;
         self.normalize_key_prefix()?;
+        self.normalize_kms_key_name()?;
+        self.metadata_naming.validate()?;
 
         Ok(())
     }
@@ -175,17 +318,39 @@ impl GcsProfile {
         storage_permissions: StoragePermissions,
     ) -> Result<TableConfig, TableConfigError> {
         let mut creds = TableProperties::default();
-        if let Some(GcsCredential::ServiceAccountKey { key }) = cred {
-            let token = sts::downscope(
-                key,
-                &self.bucket,
-                table_location.clone(),
-                storage_permissions,
-            )
-            .await?;
+        if let Some(cred) = cred {
+            let token = match cred {
+                GcsCredential::ServiceAccountKey {
+                    token_exchange: Some(token_exchange),
+                    ..
+                }
+                | GcsCredential::WorkloadIdentityFederation { token_exchange } => {
+                    sts::exchange(
+                        token_exchange,
+                        &self.bucket,
+                        table_location.clone(),
+                        storage_permissions,
+                    )
+                    .await?
+                }
+                GcsCredential::ServiceAccountKey {
+                    key,
+                    token_exchange: None,
+                } => {
+                    sts::downscope(
+                        key,
+                        &self.bucket,
+                        table_location.clone(),
+                        storage_permissions,
+                    )
+                    .await?
+                }
+            };
 
             creds.insert(&gcs::Token(token.access_token));
-            creds.insert(&gcs::ProjectId(key.project_id.clone()));
+            if let GcsCredential::ServiceAccountKey { key, .. } = cred {
+                creds.insert(&gcs::ProjectId(key.project_id.clone()));
+            }
 
             if let Some(expiry) = token.expires_in {
                 creds.insert(&gcs::TokenExpiresAt(
@@ -199,11 +364,94 @@ impl GcsProfile {
             }
         }
 
-        Ok(TableConfig {
-            // Due to backwards compat reasons we still return creds within config too
-            config: creds.clone(),
-            creds,
+        // Due to backwards compat reasons we still return creds within config too
+        let mut config = creds.clone();
+        if let Some(kms_key_name) = &self.kms_key_name {
+            config.insert(&gcs::KmsKeyName(kms_key_name.clone()));
+        }
+
+        Ok(TableConfig { config, creds })
+    }
+
+    /// Upload `content` to `location`, encrypted with the configured KMS key.
+    ///
+    /// `FileIO` cannot pass a key, so files written by Lakekeeper are uploaded through the
+    /// JSON API with `kmsKeyName` instead. This way no copy encrypted with the bucket default
+    /// is ever stored.
+    ///
+    /// # Errors
+    /// Fails if no key is configured, no service account key is available or the upload
+    /// is rejected, e.g. because the Cloud Storage service agent may not use the key.
+    pub(crate) async fn upload_with_kms_key(
+        &self,
+        credential: Option<&GcsCredential>,
+        location: &Location,
+        content: Bytes,
+    ) -> Result<(), IoError> {
+        let Some(kms_key_name) = &self.kms_key_name else {
+            return Err(IoError::EncryptionKey(
+                "No KMS key is configured for the storage profile".into(),
+            ));
+        };
+        let Some(GcsCredential::ServiceAccountKey { key, .. }) = credential else {
+            return Err(IoError::EncryptionKey(
+                "Writing with a KMS key requires a service account key".into(),
+            ));
+        };
+        let token = sts::access_token(key)
+            .await
+            .map_err(|e| IoError::EncryptionKey(Box::new(e)))?;
+
+        let object = location
+            .as_str()
+            .strip_prefix(&format!("gs://{}/", self.bucket))
+            .ok_or_else(|| {
+                IoError::EncryptionKey(
+                    format!("Location {location} is not in bucket {}", self.bucket).into(),
+                )
+            })?;
+        let url = format!(
+            "https://storage.googleapis.com/upload/storage/v1/b/{}/o",
+            self.bucket
+        );
+
+        retry_fn(|| async {
+            GCS_CLIENT
+                .post(&url)
+                .bearer_auth(&token.access_token)
+                .query(&[
+                    ("uploadType", "media"),
+                    ("name", object),
+                    ("kmsKeyName", kms_key_name),
+                ])
+                .header(http::header::CONTENT_TYPE, "application/octet-stream")
+                .body(content.clone())
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map(|_| ())
+                .map_err(|e| IoError::EncryptionKey(Box::new(e)))
         })
+        .await
+    }
+
+    fn normalize_kms_key_name(&mut self) -> Result<(), ValidationError> {
+        if let Some(kms_key_name) = self.kms_key_name.as_mut() {
+            *kms_key_name = kms_key_name.trim().to_string();
+            if kms_key_name.is_empty() {
+                self.kms_key_name = None;
+            } else if !lazy_regex::regex_is_match!(
+                r"^projects/[^/]+/locations/[^/]+/keyRings/[^/]+/cryptoKeys/[^/]+$",
+                kms_key_name
+            ) {
+                return Err(ValidationError::InvalidProfile {
+                    source: None,
+                    reason: "Storage Profile `kms_key_name` must have the format `projects/<project>/locations/<location>/keyRings/<key-ring>/cryptoKeys/<key>`.".to_string(),
+                    entity: "kms_key_name".to_string(),
+                });
+            }
+        }
+        Ok(())
     }
 
     fn normalize_key_prefix(&mut self) -> Result<(), ValidationError> {
@@ -328,7 +576,8 @@ fn validate_bucket_name(bucket: &str) -> Result<(), ValidationError> {
 
 #[cfg(test)]
 mod test {
-    use crate::service::storage::gcs::validate_bucket_name;
+    use crate::service::storage::gcs::{validate_bucket_name, GcsProfile, GcsTokenExchange};
+    use crate::service::storage::MetadataNaming;
     use needs_env_var::needs_env_var;
 
     // Bucket names: Your bucket names must meet the following requirements:
@@ -363,17 +612,93 @@ mod test {
         assert!(validate_bucket_name("a".repeat(64).as_str()).is_err()); // More than 63 characters
     }
 
+    #[test]
+    fn test_kms_key_name_is_validated() {
+        let profile = |kms_key_name: &str| GcsProfile {
+            bucket: "my-bucket".to_string(),
+            key_prefix: None,
+            kms_key_name: Some(kms_key_name.to_string()),
+            metadata_naming: MetadataNaming::default(),
+        };
+
+        let mut valid = profile(
+            " projects/my-project/locations/europe-west3/keyRings/my-ring/cryptoKeys/my-key ",
+        );
+        valid.normalize().unwrap();
+        assert_eq!(
+            valid.kms_key_name.as_deref(),
+            Some("projects/my-project/locations/europe-west3/keyRings/my-ring/cryptoKeys/my-key")
+        );
+
+        let mut empty = profile("  ");
+        empty.normalize().unwrap();
+        assert_eq!(empty.kms_key_name, None);
+
+        assert!(profile("my-key").normalize().is_err());
+        assert!(
+            profile("projects/my-project/locations/europe-west3/keyRings/my-ring")
+                .normalize()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_token_exchange_is_validated() {
+        let exchange = GcsTokenExchange {
+            token_endpoint:
+                "https://keycloak.local/realms/lakekeeper/protocol/openid-connect/token"
+                    .to_string(),
+            client_id: "lakekeeper".to_string(),
+            client_secret: "secret".to_string(),
+            scope: None,
+            audience: Some("sts.googleapis.com".to_string()),
+            workload_identity_provider: "//iam.googleapis.com/projects/123456/locations/global/workloadIdentityPools/my-pool/providers/keycloak".to_string(),
+        };
+        exchange.validate().unwrap();
+
+        let invalid = [
+            GcsTokenExchange {
+                token_endpoint: "keycloak.local/token".to_string(),
+                ..exchange.clone()
+            },
+            GcsTokenExchange {
+                audience: Some(" ".to_string()),
+                ..exchange.clone()
+            },
+            GcsTokenExchange {
+                scope: Some(String::new()),
+                ..exchange.clone()
+            },
+            GcsTokenExchange {
+                workload_identity_provider: "projects/123456/locations/global/workloadIdentityPools/my-pool/providers/keycloak".to_string(),
+                ..exchange.clone()
+            },
+            GcsTokenExchange {
+                workload_identity_provider: "//iam.googleapis.com/projects/my-project/locations/global/workloadIdentityPools/my-pool/providers/keycloak".to_string(),
+                ..exchange.clone()
+            },
+            GcsTokenExchange {
+                workload_identity_provider: "//iam.googleapis.com/projects/123456/locations/global/workloadIdentityPools/my-pool".to_string(),
+                ..exchange.clone()
+            },
+        ];
+        for exchange in invalid {
+            assert!(exchange.validate().is_err());
+        }
+    }
+
     #[needs_env_var(TEST_GCS = 1)]
     mod cloud_tests {
         use crate::service::storage::gcs::{GcsCredential, GcsProfile, GcsServiceKey};
         use crate::service::storage::StorageCredential;
-        use crate::service::storage::StorageProfile;
+        use crate::service::storage::{MetadataNaming, StorageProfile};
 
         #[tokio::test]
         async fn test_can_validate() {
             let cred: StorageCredential = std::env::var("GCS_CREDENTIAL")
                 .map(|s| GcsCredential::ServiceAccountKey {
                     key: serde_json::from_str::<GcsServiceKey>(&s).unwrap(),
+                    token_exchange: None,
                 })
                 .map_err(|_| ())
                 .expect("Missing cred")
@@ -386,6 +711,8 @@ mod test {
             let mut profile: StorageProfile = GcsProfile {
                 bucket,
                 key_prefix: Some("test_prefix".to_string()),
+                kms_key_name: None,
+                metadata_naming: MetadataNaming::default(),
             }
             .into();
 
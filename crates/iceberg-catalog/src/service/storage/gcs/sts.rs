@@ -1,5 +1,5 @@
 use crate::service::storage::error::TableConfigError;
-use crate::service::storage::gcs::GcsServiceKey;
+use crate::service::storage::gcs::{GcsServiceKey, GcsTokenExchange};
 use crate::service::storage::StoragePermissions;
 use google_cloud_auth::credentials::CredentialsFile;
 use iceberg_ext::configs::Location;
@@ -11,6 +11,8 @@ static STS_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
 static STS_URL: OnceLock<Url> = OnceLock::new();
 const STS_URL_STR: &str = "https://sts.googleapis.com/v1/token";
 const GOOGLE_CLOUD_PLATFORM_SCOPE: &str = "https://www.googleapis.com/auth/cloud-platform";
+const TOKEN_TYPE_ACCESS_TOKEN: &str = "urn:ietf:params:oauth:token-type:access_token";
+const TOKEN_TYPE_JWT: &str = "urn:ietf:params:oauth:token-type:jwt";
 
 pub(crate) async fn downscope(
     cred: impl Into<CredentialsFile>,
@@ -27,23 +29,7 @@ pub(crate) async fn downscope(
         })
         .clone();
 
-    let c =
-        google_cloud_auth::project::Config::default().with_scopes(&[GOOGLE_CLOUD_PLATFORM_SCOPE]);
-    let source = google_cloud_auth::project::create_token_source_from_credentials(&cred.into(), &c)
-        .await
-        .map_err(|e| {
-            tracing::error!(
-                "Failed to create gcp token source from credentials: {:?}",
-                e
-            );
-            TableConfigError::FailedDependency(
-                "Failed to create gcp token source from credentials".to_string(),
-            )
-        })?;
-    let token = source.token().await.map_err(|e| {
-        tracing::error!("Failed to get token from token source: {:?}", e);
-        TableConfigError::FailedDependency("Failed to get gcp token from token source".to_string())
-    })?;
+    let token = access_token(cred).await?;
 
     client
         .post(sts_url)
@@ -66,6 +52,120 @@ pub(crate) async fn downscope(
         })
 }
 
+/// Mint a token restricted to `table_location` by exchanging a token of the `OpenID`
+/// provider configured in `exchange` for a federated access token.
+pub(crate) async fn exchange(
+    exchange: &GcsTokenExchange,
+    bucket: &str,
+    table_location: Location,
+    storage_permissions: StoragePermissions,
+) -> Result<STSResponse, TableConfigError> {
+    let client = STS_CLIENT.get_or_init(reqwest::Client::new);
+    let sts_url = STS_URL
+        .get_or_init(|| {
+            STS_URL_STR
+                .parse::<Url>()
+                .expect("failed to parse a constant to a url")
+        })
+        .clone();
+
+    let subject_token = idp_token(client, exchange).await?;
+
+    client
+        .post(sts_url)
+        .header("Content-Type", "application/json")
+        .json(&STSRequest::from_idp_token_and_options(
+            &subject_token,
+            &exchange.workload_identity_provider,
+            &Options::from_location_and_permissions(bucket, &table_location, storage_permissions),
+        )?)
+        .send()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to send token exchange request: {:?}", e);
+            TableConfigError::FailedDependency("Failed to send token exchange request".to_string())
+        })?
+        .error_for_status()
+        .map_err(|e| {
+            tracing::error!("Token exchange was rejected: {:?}", e);
+            TableConfigError::FailedDependency("Token exchange was rejected".to_string())
+        })?
+        .json::<STSResponse>()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to parse token exchange response: {:?}", e);
+            TableConfigError::FailedDependency("Failed to exchange token.".to_string())
+        })
+}
+
+/// Get a token for Lakekeeper itself from the `OpenID` provider.
+async fn idp_token(
+    client: &reqwest::Client,
+    exchange: &GcsTokenExchange,
+) -> Result<String, TableConfigError> {
+    #[derive(Deserialize)]
+    struct TokenResponse {
+        access_token: String,
+    }
+
+    let mut form = vec![
+        ("grant_type", "client_credentials"),
+        ("client_id", exchange.client_id.as_str()),
+        ("client_secret", exchange.client_secret.as_str()),
+    ];
+    if let Some(scope) = &exchange.scope {
+        form.push(("scope", scope.as_str()));
+    }
+    if let Some(audience) = &exchange.audience {
+        form.push(("audience", audience.as_str()));
+    }
+
+    client
+        .post(&exchange.token_endpoint)
+        .form(&form)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| {
+            tracing::error!("Failed to get token from OpenID provider: {:?}", e);
+            TableConfigError::FailedDependency(
+                "Failed to get token from OpenID provider".to_string(),
+            )
+        })?
+        .json::<TokenResponse>()
+        .await
+        .map(|r| r.access_token)
+        .map_err(|e| {
+            tracing::error!("Failed to parse token of OpenID provider: {:?}", e);
+            TableConfigError::FailedDependency(
+                "Failed to parse token of OpenID provider".to_string(),
+            )
+        })
+}
+
+/// Get an access token with the `cloud-platform` scope for the service account.
+pub(crate) async fn access_token(
+    cred: impl Into<CredentialsFile>,
+) -> Result<google_cloud_auth::token::Token, TableConfigError> {
+    let c =
+        google_cloud_auth::project::Config::default().with_scopes(&[GOOGLE_CLOUD_PLATFORM_SCOPE]);
+    let source = google_cloud_auth::project::create_token_source_from_credentials(&cred.into(), &c)
+        .await
+        .map_err(|e| {
+            tracing::error!(
+                "Failed to create gcp token source from credentials: {:?}",
+                e
+            );
+            TableConfigError::FailedDependency(
+                "Failed to create gcp token source from credentials".to_string(),
+            )
+        })?;
+    source.token().await.map_err(|e| {
+        tracing::error!("Failed to get token from token source: {:?}", e);
+        TableConfigError::FailedDependency("Failed to get gcp token from token source".to_string())
+    })
+}
+
 #[derive(Deserialize, veil::Redact)]
 pub(crate) struct STSResponse {
     #[redact(partial)]
@@ -108,14 +208,27 @@ impl STSRequest {
             grant_type: "urn:ietf:params:oauth:grant-type:token-exchange".to_string(),
             audience: None,
             scope: None,
-            requested_token_type: "urn:ietf:params:oauth:token-type:access_token".to_string(),
+            requested_token_type: TOKEN_TYPE_ACCESS_TOKEN.to_string(),
             subject_token: token.to_string(),
-            subject_token_type: "urn:ietf:params:oauth:token-type:access_token".to_string(),
+            subject_token_type: TOKEN_TYPE_ACCESS_TOKEN.to_string(),
             // A string with JSON-format Credential Access Boundary, encoded with percent encoding.
             options: percent_encoding::utf8_percent_encode(&op, percent_encoding::NON_ALPHANUMERIC)
                 .to_string(),
         })
     }
+
+    fn from_idp_token_and_options(
+        token: &str,
+        workload_identity_provider: &str,
+        options: &Options,
+    ) -> Result<Self, TableConfigError> {
+        Ok(Self {
+            audience: Some(workload_identity_provider.to_string()),
+            scope: Some(GOOGLE_CLOUD_PLATFORM_SCOPE.to_string()),
+            subject_token_type: TOKEN_TYPE_JWT.to_string(),
+            ..Self::from_token_and_options(token, options)?
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -221,3 +334,27 @@ impl From<&GcsServiceKey> for CredentialsFile {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_idp_token_request_targets_workload_identity_provider() {
+        let provider = "//iam.googleapis.com/projects/123/locations/global/workloadIdentityPools/pool/providers/idp";
+        let options = Options::from_location_and_permissions(
+            "my-bucket",
+            &"gs://my-bucket/prefix/table".parse().unwrap(),
+            StoragePermissions::Read,
+        );
+        let request =
+            STSRequest::from_idp_token_and_options("idp-token", provider, &options).unwrap();
+
+        assert_eq!(request.audience.as_deref(), Some(provider));
+        assert_eq!(request.scope.as_deref(), Some(GOOGLE_CLOUD_PLATFORM_SCOPE));
+        assert_eq!(request.subject_token, "idp-token");
+        assert_eq!(request.subject_token_type, TOKEN_TYPE_JWT);
+        assert_eq!(request.requested_token_type, TOKEN_TYPE_ACCESS_TOKEN);
+        assert!(request.options.contains("prefix%2Ftable%2F"));
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use uuid::Uuid;
+
+static ROUND_ROBIN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Strategy used to pick one of multiple base locations of a storage profile
+/// for the default location of a new namespace.
+///
+/// Tables and views inherit the location of their namespace, so all tabulars of a
+/// namespace with default locations share a base location.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum LocationPlacement {
+    /// Derive the base location from the namespace id. The same namespace always
+    /// maps to the same base location.
+    #[default]
+    HashNamespace,
+    /// Cycle through the base locations for every new namespace.
+    RoundRobin,
+}
+
+impl LocationPlacement {
+    /// Index of the base location to use for `namespace_id` among `members` base locations.
+    #[must_use]
+    pub fn pick(self, namespace_id: Uuid, members: usize) -> usize {
+        if members <= 1 {
+            return 0;
+        }
+        match self {
+            LocationPlacement::HashNamespace => {
+                // UUIDv7 starts with a timestamp, the trailing bits are random.
+                #[allow(clippy::cast_possible_truncation)]
+                let hash = namespace_id.as_u64_pair().1 as usize;
+                hash % members
+            }
+            LocationPlacement::RoundRobin => {
+                ROUND_ROBIN_COUNTER.fetch_add(1, Ordering::Relaxed) % members
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hash_namespace_is_stable() {
+        let namespace_id = Uuid::now_v7();
+        let first = LocationPlacement::HashNamespace.pick(namespace_id, 3);
+        assert!(first < 3);
+        for _ in 0..10 {
+            assert_eq!(
+                LocationPlacement::HashNamespace.pick(namespace_id, 3),
+                first
+            );
+        }
+        assert_eq!(LocationPlacement::HashNamespace.pick(namespace_id, 1), 0);
+    }
+
+    #[test]
+    fn test_round_robin_uses_all_members() {
+        let mut picked = [false; 3];
+        for _ in 0..3 {
+            picked[LocationPlacement::RoundRobin.pick(Uuid::now_v7(), 3)] = true;
+        }
+        assert!(picked.iter().all(|p| *p));
+    }
+}
@@ -0,0 +1,282 @@
+use super::ValidationError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const METADATA_FILE_SUFFIX: &str = ".metadata.json";
+const PLACEHOLDER_COUNT: &str = "{count}";
+const PLACEHOLDER_VERSION: &str = "{version}";
+const PLACEHOLDER_UUID: &str = "{uuid}";
+
+/// Naming of metadata files written for tables and views of a warehouse.
+///
+/// The extension of the compression codec (for example `.gz`) and `.metadata.json`
+/// are always appended to the name.
+#[derive(Debug, Clone, Eq, PartialEq, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum MetadataNaming {
+    /// `<count>-<uuid>.metadata.json`, for example `00001-0194685d-3f2a-7c21-9b52-1d0a2e7a3c10.metadata.json`.
+    #[default]
+    #[schema(title = "MetadataNamingUuid")]
+    Uuid,
+    /// Hadoop catalog style `v<version>.metadata.json`, starting with `v1.metadata.json`.
+    #[schema(title = "MetadataNamingSequential")]
+    Sequential,
+    /// Custom name built from a template, for example `{version}-{uuid}`.
+    /// Supported placeholders are `{version}` (starting at 1), `{count}` (starting at 0)
+    /// and `{uuid}`. At least one placeholder is required and placeholders must be
+    /// separated by at least one other character.
+    #[serde(rename_all = "kebab-case")]
+    #[schema(title = "MetadataNamingTemplate")]
+    Template { template: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemplatePart<'a> {
+    Literal(&'a str),
+    Count,
+    Version,
+    Uuid,
+}
+
+impl MetadataNaming {
+    /// File name of a new metadata file. `metadata_count` is the number of metadata
+    /// files written for the tabular before.
+    #[must_use]
+    pub fn file_name(
+        &self,
+        metadata_id: Uuid,
+        metadata_count: usize,
+        compression_extension: &str,
+    ) -> String {
+        let version = metadata_count + 1;
+        let stem = match self {
+            MetadataNaming::Uuid => format!("{metadata_count:05}-{metadata_id}"),
+            MetadataNaming::Sequential => format!("v{version}"),
+            MetadataNaming::Template { template } => template
+                .replace(PLACEHOLDER_COUNT, &metadata_count.to_string())
+                .replace(PLACEHOLDER_VERSION, &version.to_string())
+                .replace(PLACEHOLDER_UUID, &metadata_id.to_string()),
+        };
+        format!("{stem}{compression_extension}{METADATA_FILE_SUFFIX}")
+    }
+
+    /// Number of metadata files written before the file named `file_name`, if
+    /// `file_name` was created by this naming strategy.
+    #[must_use]
+    pub fn count_from_file_name(&self, file_name: &str) -> Option<usize> {
+        match self {
+            MetadataNaming::Uuid => lazy_regex::regex_captures!(
+                r"^(\d+)-([\w-]{36})(?:\.\w+)?\.metadata\.json",
+                file_name
+            )
+            .and_then(|(_whole, count, _metadata_id)| count.parse().ok()),
+            MetadataNaming::Sequential => {
+                lazy_regex::regex_captures!(r"^v(\d+)(?:\.\w+)?\.metadata\.json$", file_name)
+                    .and_then(|(_whole, version)| version.parse::<usize>().ok())
+                    .and_then(|version| version.checked_sub(1))
+            }
+            MetadataNaming::Template { template } => {
+                let parts = parse_template(template).ok()?;
+                let stem = file_name.strip_suffix(METADATA_FILE_SUFFIX)?;
+                // The stem may end with the extension of the compression codec
+                count_from_stem(&parts, stem).or_else(|| {
+                    let (stem, extension) = stem.rsplit_once('.')?;
+                    if extension.is_empty() || !extension.chars().all(|c| c.is_ascii_alphanumeric())
+                    {
+                        return None;
+                    }
+                    count_from_stem(&parts, stem)
+                })
+            }
+        }
+    }
+
+    /// Validate the template of [`MetadataNaming::Template`].
+    ///
+    /// # Errors
+    /// Fails if the template contains unknown placeholders, no placeholder at all,
+    /// adjacent placeholders or characters that are not allowed in file names.
+    pub(super) fn validate(&self) -> Result<(), ValidationError> {
+        let MetadataNaming::Template { template } = self else {
+            return Ok(());
+        };
+        parse_template(template).map_err(|reason| ValidationError::InvalidProfile {
+            source: None,
+            reason: format!("Invalid `metadata-naming` template `{template}`: {reason}"),
+            entity: "metadata_naming".to_string(),
+        })?;
+        Ok(())
+    }
+}
+
+fn parse_template(template: &str) -> Result<Vec<TemplatePart<'_>>, String> {
+    if template.contains('/') || template.contains('\\') {
+        return Err("Template must not contain path separators.".to_string());
+    }
+
+    let mut parts = vec![];
+    let mut rest = template;
+    while !rest.is_empty() {
+        let Some(start) = rest.find('{') else {
+            parts.push(TemplatePart::Literal(rest));
+            break;
+        };
+        if start > 0 {
+            parts.push(TemplatePart::Literal(&rest[..start]));
+        }
+        let placeholder_len = rest[start..]
+            .find('}')
+            .ok_or_else(|| "Unclosed `{` in template.".to_string())?
+            + 1;
+        let part = match &rest[start..start + placeholder_len] {
+            PLACEHOLDER_COUNT => TemplatePart::Count,
+            PLACEHOLDER_VERSION => TemplatePart::Version,
+            PLACEHOLDER_UUID => TemplatePart::Uuid,
+            other => {
+                return Err(format!(
+                    "Unknown placeholder `{other}`. Supported placeholders are {PLACEHOLDER_COUNT}, {PLACEHOLDER_VERSION} and {PLACEHOLDER_UUID}."
+                ))
+            }
+        };
+        if parts
+            .last()
+            .is_some_and(|last| !matches!(last, TemplatePart::Literal(_)))
+        {
+            return Err("Placeholders must be separated by at least one character.".to_string());
+        }
+        parts.push(part);
+        rest = &rest[start + placeholder_len..];
+    }
+
+    if parts
+        .iter()
+        .any(|part| matches!(part, TemplatePart::Literal(literal) if literal.contains('}')))
+    {
+        return Err("Unmatched `}` in template.".to_string());
+    }
+    if parts
+        .iter()
+        .all(|part| matches!(part, TemplatePart::Literal(_)))
+    {
+        return Err(format!(
+            "Template must contain at least one of {PLACEHOLDER_COUNT}, {PLACEHOLDER_VERSION} or {PLACEHOLDER_UUID}, otherwise all metadata files would have the same name."
+        ));
+    }
+    Ok(parts)
+}
+
+/// Match `stem` against the parsed template. Returns `None` if it doesn't match or
+/// the template only contains a `{uuid}`.
+fn count_from_stem(parts: &[TemplatePart<'_>], stem: &str) -> Option<usize> {
+    let mut rest = stem;
+    let mut count = None;
+    let mut version = None;
+    for part in parts {
+        match part {
+            TemplatePart::Literal(literal) => rest = rest.strip_prefix(literal)?,
+            TemplatePart::Count | TemplatePart::Version => {
+                let digits = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let number = rest[..digits].parse::<usize>().ok()?;
+                if *part == TemplatePart::Count {
+                    count = Some(number);
+                } else {
+                    version = Some(number);
+                }
+                rest = &rest[digits..];
+            }
+            TemplatePart::Uuid => {
+                let uuid = rest.get(..36)?;
+                Uuid::parse_str(uuid).ok()?;
+                rest = &rest[36..];
+            }
+        }
+    }
+    if !rest.is_empty() {
+        return None;
+    }
+    count.or_else(|| version.and_then(|v| v.checked_sub(1)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_uuid_naming_roundtrip() {
+        let id = Uuid::now_v7();
+        let name = MetadataNaming::Uuid.file_name(id, 3, ".gz");
+        assert_eq!(name, format!("00003-{id}.gz.metadata.json"));
+        assert_eq!(MetadataNaming::Uuid.count_from_file_name(&name), Some(3));
+    }
+
+    #[test]
+    fn test_sequential_naming_roundtrip() {
+        let naming = MetadataNaming::Sequential;
+        assert_eq!(naming.file_name(Uuid::now_v7(), 0, ""), "v1.metadata.json");
+        assert_eq!(
+            naming.file_name(Uuid::now_v7(), 41, ".gz"),
+            "v42.gz.metadata.json"
+        );
+        assert_eq!(naming.count_from_file_name("v1.metadata.json"), Some(0));
+        assert_eq!(
+            naming.count_from_file_name("v42.gz.metadata.json"),
+            Some(41)
+        );
+        assert_eq!(naming.count_from_file_name("v0.metadata.json"), None);
+        assert_eq!(naming.count_from_file_name("00001-abc.metadata.json"), None);
+    }
+
+    #[test]
+    fn test_template_naming_roundtrip() {
+        let id = Uuid::now_v7();
+        let naming = MetadataNaming::Template {
+            template: "snapshot-{version}_{uuid}".to_string(),
+        };
+        naming.validate().unwrap();
+        let name = naming.file_name(id, 6, ".gz");
+        assert_eq!(name, format!("snapshot-7_{id}.gz.metadata.json"));
+        assert_eq!(naming.count_from_file_name(&name), Some(6));
+
+        let name = naming.file_name(id, 6, "");
+        assert_eq!(naming.count_from_file_name(&name), Some(6));
+        assert_eq!(naming.count_from_file_name("v7.metadata.json"), None);
+    }
+
+    #[test]
+    fn test_invalid_templates_are_rejected() {
+        for template in [
+            "",
+            "metadata",
+            "{version}{uuid}",
+            "{unknown}-{uuid}",
+            "nested/{version}",
+            "{version",
+            "{version}}",
+        ] {
+            let naming = MetadataNaming::Template {
+                template: template.to_string(),
+            };
+            assert!(naming.validate().is_err(), "{template} should be invalid");
+        }
+    }
+
+    #[test]
+    fn test_serde() {
+        let naming: MetadataNaming = serde_json::from_value(serde_json::json!({
+            "type": "template",
+            "template": "v{version}-{uuid}"
+        }))
+        .unwrap();
+        assert_eq!(
+            naming,
+            MetadataNaming::Template {
+                template: "v{version}-{uuid}".to_string()
+            }
+        );
+        let naming: MetadataNaming =
+            serde_json::from_value(serde_json::json!({"type": "sequential"})).unwrap();
+        assert_eq!(naming, MetadataNaming::Sequential);
+    }
+}
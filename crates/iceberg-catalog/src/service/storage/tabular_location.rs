@@ -0,0 +1,147 @@
+use crate::service::tabular_idents::TabularIdentUuid;
+use crate::CONFIG;
+use iceberg_ext::configs::Location;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use std::sync::{Arc, OnceLock};
+
+static STRATEGY: OnceLock<Arc<dyn TabularLocationStrategy>> = OnceLock::new();
+
+/// Input for deriving the default location of a new table or view.
+#[derive(Debug, Clone, Copy)]
+pub struct TabularLocationRequest<'a> {
+    /// Location of the namespace the tabular is created in.
+    pub namespace_location: &'a Location,
+    pub tabular_id: TabularIdentUuid,
+    /// Name of the tabular.
+    pub name: &'a str,
+}
+
+/// Derives the default location of new tables and views that are created without a location.
+///
+/// The returned location must be a sublocation of the namespace location and must be
+/// unique for every tabular id, as tabulars can be re-created with the same name while
+/// the files of the dropped tabular are still being purged.
+pub trait TabularLocationStrategy: Debug + Send + Sync + 'static {
+    fn tabular_location(&self, request: TabularLocationRequest<'_>) -> Location;
+}
+
+/// Built-in layouts of default tabular locations, configured with
+/// `LAKEKEEPER__TABULAR_LOCATION_LAYOUT`.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum TabularLocationLayout {
+    /// `<namespace-location>/<tabular-id>`
+    #[default]
+    Uuid,
+    /// `<namespace-location>/<ab>/<cd>/<tabular-id>`, where `ab` and `cd` are taken from the
+    /// random part of the tabular id. Spreads keys over many prefixes, which raises the
+    /// request rate S3 can serve.
+    HashedPrefix,
+    /// `<namespace-location>/<yyyy>/<mm>/<dd>/<tabular-id>` with the UTC creation date.
+    DatePrefix,
+    /// `<namespace-location>/<name>-<tabular-id>`. Characters of the name that are not
+    /// alphanumeric, `-`, `_` or `.` are replaced by `_`.
+    Named,
+}
+
+impl TabularLocationStrategy for TabularLocationLayout {
+    fn tabular_location(&self, request: TabularLocationRequest<'_>) -> Location {
+        let tabular_id = *request.tabular_id;
+        let mut location = request.namespace_location.clone();
+        location.without_trailing_slash();
+        match self {
+            TabularLocationLayout::Uuid => {
+                location.push(&tabular_id.to_string());
+            }
+            TabularLocationLayout::HashedPrefix => {
+                // UUIDv7 starts with a timestamp, the last bytes are random.
+                let bytes = tabular_id.as_bytes();
+                location
+                    .push(&format!("{:02x}", bytes[15]))
+                    .push(&format!("{:02x}", bytes[14]))
+                    .push(&tabular_id.to_string());
+            }
+            TabularLocationLayout::DatePrefix => {
+                let today = chrono::Utc::now().date_naive();
+                location
+                    .push(&today.format("%Y").to_string())
+                    .push(&today.format("%m").to_string())
+                    .push(&today.format("%d").to_string())
+                    .push(&tabular_id.to_string());
+            }
+            TabularLocationLayout::Named => {
+                let name: String = request
+                    .name
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect();
+                location.push(&format!("{name}-{tabular_id}"));
+            }
+        }
+        location
+    }
+}
+
+/// Replace the built-in layout configured by `LAKEKEEPER__TABULAR_LOCATION_LAYOUT` with a
+/// custom strategy. Must be called before the first tabular is created.
+///
+/// # Errors
+/// Returns the strategy if a strategy has already been set or used.
+pub fn set_tabular_location_strategy(
+    strategy: Arc<dyn TabularLocationStrategy>,
+) -> Result<(), Arc<dyn TabularLocationStrategy>> {
+    STRATEGY.set(strategy)
+}
+
+/// The strategy used for default locations of new tabulars.
+pub(crate) fn tabular_location_strategy() -> &'static dyn TabularLocationStrategy {
+    STRATEGY
+        .get_or_init(|| Arc::new(CONFIG.tabular_location_layout))
+        .as_ref()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    fn location(layout: TabularLocationLayout, name: &str) -> String {
+        let namespace_location = Location::from_str("s3://bucket/prefix/ns-id/").unwrap();
+        layout
+            .tabular_location(TabularLocationRequest {
+                namespace_location: &namespace_location,
+                tabular_id: TabularIdentUuid::Table(uuid::uuid!(
+                    "0194685d-3f2a-7c21-9b52-1d0a2e7a3c10"
+                )),
+                name,
+            })
+            .to_string()
+    }
+
+    #[test]
+    fn test_builtin_layouts() {
+        assert_eq!(
+            location(TabularLocationLayout::Uuid, "my_table"),
+            "s3://bucket/prefix/ns-id/0194685d-3f2a-7c21-9b52-1d0a2e7a3c10"
+        );
+        assert_eq!(
+            location(TabularLocationLayout::HashedPrefix, "my_table"),
+            "s3://bucket/prefix/ns-id/10/3c/0194685d-3f2a-7c21-9b52-1d0a2e7a3c10"
+        );
+        assert!(lazy_regex::regex_is_match!(
+            r"^s3://bucket/prefix/ns-id/\d{4}/\d{2}/\d{2}/0194685d-3f2a-7c21-9b52-1d0a2e7a3c10$",
+            &location(TabularLocationLayout::DatePrefix, "my_table")
+        ),);
+        assert_eq!(
+            location(TabularLocationLayout::Named, "My Table/1"),
+            "s3://bucket/prefix/ns-id/My_Table_1-0194685d-3f2a-7c21-9b52-1d0a2e7a3c10"
+        );
+    }
+}
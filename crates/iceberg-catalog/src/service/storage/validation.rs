@@ -0,0 +1,572 @@
+use super::{
+    check_location_is_empty, StorageCredential, StorageLocations, StoragePermissions,
+    StorageProfile, ValidationError,
+};
+use crate::api::iceberg::v1::DataAccess;
+use crate::catalog::compression_codec::CompressionCodec;
+use crate::catalog::io::{self, list_location, IoError};
+use crate::service::authn::Actor;
+use futures::StreamExt;
+use iceberg::io::FileIO;
+use iceberg_ext::configs::Location;
+use serde::Serialize;
+
+/// Individual check performed while validating a storage profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema, strum_macros::Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum StorageValidationCheckKind {
+    /// The storage credential can be used to construct a client for the storage profile.
+    Credentials,
+    /// The base location of the storage profile can be listed.
+    BucketReachable,
+    /// A test file can be written below the base location.
+    WriteAllowed,
+    /// The test file can be read back.
+    ReadAllowed,
+    /// The test file shows up when listing its parent location.
+    ListAllowed,
+    /// The test file can be deleted.
+    DeleteAllowed,
+    /// Files written by Lakekeeper can be moved to the configured storage class.
+    StorageClassAllowed,
+    /// Files written by Lakekeeper can be tagged with the configured object tags.
+    TaggingAllowed,
+    /// Files written by Lakekeeper can be encrypted with the configured customer-managed key.
+    EncryptionKeyUsable,
+    /// Vended credentials can be generated and used to read, write and delete files.
+    VendedCredentials,
+    /// A configuration for remote signing can be generated.
+    RemoteSigning,
+    /// All files created during validation have been removed.
+    Cleanup,
+}
+
+/// Outcome of a single validation check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema, strum_macros::Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum StorageValidationCheckStatus {
+    Passed,
+    Failed,
+    /// The check was not executed, either because it does not apply to the
+    /// storage profile or because a previous check failed.
+    Skipped,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageValidationCheck {
+    /// The check that was performed
+    pub check: StorageValidationCheckKind,
+    /// Outcome of the check
+    pub status: StorageValidationCheckStatus,
+    /// Error or reason for skipping the check
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Suggestion on how to fix a failed check
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+    /// Location the check was performed at, for checks that are performed
+    /// for each base location of the storage profile.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
+/// Result of validating a storage profile.
+/// In contrast to `validate_access`, all checks are reported instead of only the first error.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub struct StorageValidationReport {
+    /// `true` if no check failed
+    pub success: bool,
+    /// Checks in the order they have been executed
+    pub checks: Vec<StorageValidationCheck>,
+}
+
+impl StorageValidationReport {
+    /// Checks that failed
+    #[must_use]
+    pub fn failed_checks(&self) -> impl Iterator<Item = &StorageValidationCheck> {
+        self.checks
+            .iter()
+            .filter(|c| c.status == StorageValidationCheckStatus::Failed)
+    }
+}
+
+#[derive(Debug, Default)]
+struct ReportBuilder {
+    checks: Vec<StorageValidationCheck>,
+    /// Location recorded with subsequent checks
+    location: Option<String>,
+}
+
+impl ReportBuilder {
+    fn at(&mut self, location: Option<&Location>) {
+        self.location = location.map(ToString::to_string);
+    }
+
+    fn record<T, E: std::error::Error>(
+        &mut self,
+        check: StorageValidationCheckKind,
+        result: Result<T, E>,
+    ) -> Option<T> {
+        match result {
+            Ok(v) => {
+                self.checks.push(StorageValidationCheck {
+                    check,
+                    status: StorageValidationCheckStatus::Passed,
+                    message: None,
+                    hint: None,
+                    location: self.location.clone(),
+                });
+                Some(v)
+            }
+            Err(e) => {
+                tracing::debug!("Storage validation check {check} failed: {e}");
+                self.checks.push(StorageValidationCheck {
+                    check,
+                    status: StorageValidationCheckStatus::Failed,
+                    message: Some(error_chain(&e)),
+                    hint: Some(hint(check).to_string()),
+                    location: self.location.clone(),
+                });
+                None
+            }
+        }
+    }
+
+    fn fail(&mut self, check: StorageValidationCheckKind, message: impl Into<String>) {
+        self.checks.push(StorageValidationCheck {
+            check,
+            status: StorageValidationCheckStatus::Failed,
+            message: Some(message.into()),
+            hint: Some(hint(check).to_string()),
+            location: self.location.clone(),
+        });
+    }
+
+    fn skip(&mut self, check: StorageValidationCheckKind, reason: impl Into<String>) {
+        self.checks.push(StorageValidationCheck {
+            check,
+            status: StorageValidationCheckStatus::Skipped,
+            message: Some(reason.into()),
+            hint: None,
+            location: self.location.clone(),
+        });
+    }
+
+    fn skip_remaining(
+        &mut self,
+        checks: &[StorageValidationCheckKind],
+        failed: StorageValidationCheckKind,
+    ) {
+        for check in checks {
+            self.skip(*check, format!("Skipped because check '{failed}' failed."));
+        }
+    }
+
+    fn build(self) -> StorageValidationReport {
+        StorageValidationReport {
+            success: !self
+                .checks
+                .iter()
+                .any(|c| c.status == StorageValidationCheckStatus::Failed),
+            checks: self.checks,
+        }
+    }
+}
+
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut message = e.to_string();
+    let mut source = e.source();
+    while let Some(s) = source {
+        message.push_str(": ");
+        message.push_str(&s.to_string());
+        source = s.source();
+    }
+    message
+}
+
+fn hint(check: StorageValidationCheckKind) -> &'static str {
+    match check {
+        StorageValidationCheckKind::Credentials => {
+            "Check that the storage credential matches the type of the storage profile and is complete."
+        }
+        StorageValidationCheckKind::BucketReachable => {
+            "Check that the bucket / container exists, the endpoint and region are correct and reachable from Lakekeeper, and that the credential may list objects."
+        }
+        StorageValidationCheckKind::WriteAllowed => {
+            "Grant the credential permission to create objects below the key prefix of the storage profile."
+        }
+        StorageValidationCheckKind::ReadAllowed => {
+            "Grant the credential permission to read objects below the key prefix of the storage profile."
+        }
+        StorageValidationCheckKind::ListAllowed => {
+            "Grant the credential permission to list objects below the key prefix of the storage profile."
+        }
+        StorageValidationCheckKind::DeleteAllowed => {
+            "Grant the credential permission to delete objects below the key prefix of the storage profile. Lakekeeper needs to delete files when tables are dropped with purge."
+        }
+        StorageValidationCheckKind::StorageClassAllowed => {
+            "Grant the credential the `s3:GetObject` and `s3:PutObject` permissions below the key prefix of the storage profile and check that the storage supports the configured `metadata-storage-class`."
+        }
+        StorageValidationCheckKind::TaggingAllowed => {
+            "Grant the credential the `s3:PutObjectTagging` permission below the key prefix of the storage profile or remove the object tags."
+        }
+        StorageValidationCheckKind::EncryptionKeyUsable => {
+            "Grant the Cloud Storage service agent of the project the `roles/cloudkms.cryptoKeyEncrypterDecrypter` role on the KMS key and check that the key is enabled and in a location compatible with the bucket."
+        }
+        StorageValidationCheckKind::VendedCredentials => {
+            "Check the STS / SAS / downscoping configuration: the role to assume must exist, be assumable by the credential and allow access to the key prefix. Disable vended credentials if they are not required."
+        }
+        StorageValidationCheckKind::RemoteSigning => {
+            "Check that the storage profile supports remote signing and that the region of the profile is valid."
+        }
+        StorageValidationCheckKind::Cleanup => {
+            "Files created during validation could not be removed. Grant the credential permission to delete and list objects and remove left-over files manually."
+        }
+    }
+}
+
+const CHECKS_AFTER_CREDENTIALS: &[StorageValidationCheckKind] = &[
+    StorageValidationCheckKind::BucketReachable,
+    StorageValidationCheckKind::WriteAllowed,
+    StorageValidationCheckKind::ReadAllowed,
+    StorageValidationCheckKind::ListAllowed,
+    StorageValidationCheckKind::DeleteAllowed,
+    StorageValidationCheckKind::StorageClassAllowed,
+    StorageValidationCheckKind::TaggingAllowed,
+    StorageValidationCheckKind::EncryptionKeyUsable,
+    StorageValidationCheckKind::VendedCredentials,
+    StorageValidationCheckKind::RemoteSigning,
+    StorageValidationCheckKind::Cleanup,
+];
+
+impl StorageProfile {
+    /// Validate physical access and report the outcome of every individual check.
+    ///
+    /// Never fails - errors are recorded in the returned report.
+    /// If location is not provided, a dummy table location below [`super::VALIDATION_PREFIX`]
+    /// is used in every base location
+    /// and the checks following [`StorageValidationCheckKind::BucketReachable`] are reported
+    /// once per base location.
+    pub async fn validate_access_report(
+        &self,
+        credential: Option<&StorageCredential>,
+        location: Option<&Location>,
+    ) -> StorageValidationReport {
+        use StorageValidationCheckKind as K;
+        let mut report = ReportBuilder::default();
+
+        let Some(file_io) = report.record(K::Credentials, self.file_io(credential)) else {
+            report.skip_remaining(CHECKS_AFTER_CREDENTIALS, K::Credentials);
+            return report.build();
+        };
+
+        let Some(reachable) =
+            check_reachable(&mut report, self, &file_io, &CHECKS_AFTER_CREDENTIALS[1..]).await
+        else {
+            return report.build();
+        };
+
+        let test_locations = if let Some(location) = location {
+            report.at(None);
+            vec![location.clone()]
+        } else {
+            reachable
+                .into_iter()
+                .map(|base_location| self.validation_location(base_location))
+                .collect()
+        };
+        for test_location in &test_locations {
+            if location.is_none() {
+                report.at(Some(test_location));
+            }
+            self.validate_access_report_at(&mut report, &file_io, credential, test_location)
+                .await;
+        }
+
+        report.build()
+    }
+
+    /// Run the checks following [`StorageValidationCheckKind::BucketReachable`] at `test_location`.
+    async fn validate_access_report_at(
+        &self,
+        report: &mut ReportBuilder,
+        file_io: &FileIO,
+        credential: Option<&StorageCredential>,
+        test_location: &Location,
+    ) {
+        use StorageValidationCheckKind as K;
+
+        // ------------- Direct access -------------
+        let file_name = format!("validation-{}", uuid::Uuid::now_v7());
+        let mut test_file = self.default_metadata_location(
+            test_location,
+            &CompressionCodec::Gzip,
+            uuid::Uuid::now_v7(),
+            0,
+        );
+        test_file.pop().push(&file_name);
+
+        let written =
+            io::write_metadata_file(&test_file, "test", CompressionCodec::Gzip, file_io).await;
+        if report.record(K::WriteAllowed, written).is_some() {
+            report.record(K::ReadAllowed, io::read_file(file_io, &test_file).await);
+            match list_contains(file_io, test_location, &file_name).await {
+                Ok(true) => {
+                    report.record(K::ListAllowed, Ok::<_, IoError>(()));
+                }
+                Ok(false) => report.fail(
+                    K::ListAllowed,
+                    format!("Test file {test_file} was not returned when listing {test_location}."),
+                ),
+                Err(e) => {
+                    report.record(K::ListAllowed, Err::<(), _>(e));
+                }
+            }
+            report.record(K::DeleteAllowed, io::delete_file(file_io, &test_file).await);
+        } else {
+            report.skip_remaining(
+                &[K::ReadAllowed, K::ListAllowed, K::DeleteAllowed],
+                K::WriteAllowed,
+            );
+        }
+
+        // ------------- Storage class, object tagging & encryption -------------
+        // Storage classes and object tags are only supported for S3, encryption keys only
+        // for GCS. Each check applies all settings of the profile to its test file.
+        if self.has_metadata_storage_class() {
+            let result = self
+                .validate_written_file_finalization(file_io, credential, test_location)
+                .await;
+            report.record(K::StorageClassAllowed, result);
+        } else {
+            report.skip(
+                K::StorageClassAllowed,
+                "No metadata storage class is configured for this storage profile.",
+            );
+        }
+        if self.has_object_tags() {
+            let result = self
+                .validate_written_file_finalization(file_io, credential, test_location)
+                .await;
+            report.record(K::TaggingAllowed, result);
+        } else {
+            report.skip(
+                K::TaggingAllowed,
+                "No object tags are configured for this storage profile.",
+            );
+        }
+        if self.has_encryption_key() {
+            let result = self
+                .validate_written_file_finalization(file_io, credential, test_location)
+                .await;
+            report.record(K::EncryptionKeyUsable, result);
+        } else {
+            report.skip(
+                K::EncryptionKeyUsable,
+                "No encryption key is configured for this storage profile.",
+            );
+        }
+
+        // ------------- Vended credentials -------------
+        if self.supports_vended_credentials() {
+            let result = self
+                .validate_vended_credentials(credential, test_location)
+                .await;
+            report.record(K::VendedCredentials, result);
+        } else {
+            report.skip(
+                K::VendedCredentials,
+                "Vended credentials are not enabled for this storage profile.",
+            );
+        }
+
+        // ------------- Remote signing -------------
+        if matches!(self, StorageProfile::S3(_)) {
+            let result = self
+                .generate_table_config(
+                    &DataAccess {
+                        remote_signing: true,
+                        vended_credentials: false,
+                    },
+                    credential,
+                    test_location,
+                    StoragePermissions::ReadWriteDelete,
+                    &Actor::Anonymous,
+                )
+                .await;
+            report.record(K::RemoteSigning, result);
+        } else {
+            report.skip(
+                K::RemoteSigning,
+                format!(
+                    "Remote signing is not supported for storage type {}.",
+                    self.storage_type()
+                ),
+            );
+        }
+
+        // ------------- Cleanup -------------
+        let cleanup = async {
+            io::remove_all(file_io, test_location)
+                .await
+                .map_err(|e| ValidationError::IoOperationFailed(e, Box::new(self.clone())))?;
+            check_location_is_empty(file_io, test_location, self, || {
+                ValidationError::InvalidLocation {
+                    reason: "Files are left after remove_all on test location".to_string(),
+                    source: None,
+                    location: test_location.to_string(),
+                    storage_type: self.storage_type(),
+                }
+            })
+            .await
+        }
+        .await;
+        report.record(K::Cleanup, cleanup);
+    }
+}
+
+impl StorageProfile {
+    /// Lightweight check used by the periodic storage health check.
+    ///
+    /// Only verifies that the credential is accepted, every base location can be listed
+    /// and - if enabled - vended credentials can still be issued. No files are written.
+    pub async fn check_health_report(
+        &self,
+        credential: Option<&StorageCredential>,
+    ) -> StorageValidationReport {
+        use StorageValidationCheckKind as K;
+        let mut report = ReportBuilder::default();
+
+        let Some(file_io) = report.record(K::Credentials, self.file_io(credential)) else {
+            report.skip_remaining(&[K::BucketReachable, K::VendedCredentials], K::Credentials);
+            return report.build();
+        };
+
+        let Some(reachable) =
+            check_reachable(&mut report, self, &file_io, &[K::VendedCredentials]).await
+        else {
+            return report.build();
+        };
+
+        for base_location in &reachable {
+            report.at(Some(base_location));
+            if self.supports_vended_credentials() {
+                let result = self
+                    .generate_table_config(
+                        &DataAccess {
+                            remote_signing: false,
+                            vended_credentials: true,
+                        },
+                        credential,
+                        base_location,
+                        StoragePermissions::Read,
+                        &Actor::Anonymous,
+                    )
+                    .await;
+                report.record(K::VendedCredentials, result);
+            } else {
+                report.skip(
+                    K::VendedCredentials,
+                    "Vended credentials are not enabled for this storage profile.",
+                );
+            }
+        }
+
+        report.build()
+    }
+}
+
+/// List every base location of the profile and record the outcome per location.
+/// Returns the reachable base locations, the `remaining` checks of unreachable ones are
+/// skipped. Returns `None` if the base locations cannot be determined.
+async fn check_reachable(
+    report: &mut ReportBuilder,
+    profile: &StorageProfile,
+    file_io: &FileIO,
+    remaining: &[StorageValidationCheckKind],
+) -> Option<Vec<Location>> {
+    use StorageValidationCheckKind as K;
+
+    let base_locations = match profile.base_locations() {
+        Ok(base_locations) => base_locations,
+        Err(e) => {
+            report.record(K::BucketReachable, Err::<(), _>(e));
+            report.skip_remaining(remaining, K::BucketReachable);
+            return None;
+        }
+    };
+
+    let mut reachable = Vec::with_capacity(base_locations.len());
+    for base_location in base_locations {
+        report.at(Some(&base_location));
+        let listed = list_first_page(file_io, &base_location)
+            .await
+            .map_err(|e| ValidationError::IoOperationFailed(e, Box::new(profile.clone())));
+        if report.record(K::BucketReachable, listed).is_some() {
+            reachable.push(base_location);
+        } else {
+            report.skip_remaining(remaining, K::BucketReachable);
+        }
+    }
+    Some(reachable)
+}
+
+async fn list_first_page(file_io: &FileIO, location: &Location) -> Result<Vec<String>, IoError> {
+    let mut entries = list_location(file_io, location, Some(1)).await?;
+    entries
+        .next()
+        .await
+        .transpose()
+        .map(Option::unwrap_or_default)
+}
+
+async fn list_contains(
+    file_io: &FileIO,
+    location: &Location,
+    file_name: &str,
+) -> Result<bool, IoError> {
+    let mut entries = list_location(file_io, location, None).await?;
+    while let Some(page) = entries.next().await {
+        if page?
+            .iter()
+            .any(|entry| entry.trim_end_matches('/').ends_with(file_name))
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_builder_skips_and_fails() {
+        let mut report = ReportBuilder::default();
+        report.record(
+            StorageValidationCheckKind::Credentials,
+            Ok::<_, IoError>(()),
+        );
+        report.fail(StorageValidationCheckKind::BucketReachable, "unreachable");
+        report.skip_remaining(
+            &CHECKS_AFTER_CREDENTIALS[1..],
+            StorageValidationCheckKind::BucketReachable,
+        );
+        let report = report.build();
+
+        assert!(!report.success);
+        assert_eq!(report.checks.len(), 1 + CHECKS_AFTER_CREDENTIALS.len());
+        let failed = report.failed_checks().collect::<Vec<_>>();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].check, StorageValidationCheckKind::BucketReachable);
+        assert!(failed[0].hint.is_some());
+        assert!(report.checks[2..]
+            .iter()
+            .all(|c| c.status == StorageValidationCheckStatus::Skipped));
+    }
+}
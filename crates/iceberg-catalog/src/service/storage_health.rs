@@ -0,0 +1,146 @@
+use crate::api::Result;
+use crate::catalog::maybe_get_secret;
+use crate::service::event_publisher::{CloudEventsPublisher, WarehouseEventMetadata};
+use crate::service::{Catalog, SecretStore, Transaction, WarehouseIdent, WarehouseStorageHealth};
+use std::time::Duration;
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// Number of warehouses claimed per iteration.
+const CHECK_BATCH_SIZE: i64 = 10;
+/// Interval in which we look for warehouses that are due for a check.
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically re-validate the storage of all active warehouses.
+///
+/// Only lightweight checks are performed (credential, bucket listing, vended credentials).
+/// If checks start failing, the warehouse is marked as `degraded-storage` and a
+/// `warehouseStorageDegraded` event is emitted. Once checks pass again, the warehouse is
+/// marked `healthy` and `warehouseStorageRecovered` is emitted.
+///
+/// Each warehouse is checked at most once per `check_interval` across all catalog instances.
+pub async fn storage_health_task<C: Catalog, S: SecretStore>(
+    catalog_state: C::State,
+    secret_state: S,
+    publisher: CloudEventsPublisher,
+    check_interval: Duration,
+) {
+    let Ok(min_interval) = chrono::Duration::from_std(check_interval) else {
+        tracing::error!("Invalid storage health check interval: {check_interval:?}");
+        return;
+    };
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL.min(check_interval) + Duration::from_millis(17)).await;
+
+        let warehouses = match claim_warehouses::<C>(catalog_state.clone(), min_interval).await {
+            Ok(warehouses) => warehouses,
+            Err(err) => {
+                tracing::error!("Failed to claim warehouses for storage check: {:?}", err);
+                continue;
+            }
+        };
+
+        for warehouse_id in warehouses {
+            let span = tracing::debug_span!("storage_health_check", warehouse_id = %warehouse_id);
+            if let Err(err) = check_warehouse::<C, S>(
+                warehouse_id,
+                catalog_state.clone(),
+                &secret_state,
+                &publisher,
+            )
+            .instrument(span.or_current())
+            .await
+            {
+                tracing::error!(
+                    "Failed to check storage of warehouse {warehouse_id}: {:?}",
+                    err.error
+                );
+            }
+        }
+    }
+}
+
+async fn claim_warehouses<C: Catalog>(
+    catalog_state: C::State,
+    min_interval: chrono::Duration,
+) -> Result<Vec<WarehouseIdent>> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let warehouses =
+        C::claim_warehouses_for_storage_check(min_interval, CHECK_BATCH_SIZE, t.transaction())
+            .await?;
+    t.commit().await?;
+    Ok(warehouses)
+}
+
+async fn check_warehouse<C: Catalog, S: SecretStore>(
+    warehouse_id: WarehouseIdent,
+    catalog_state: C::State,
+    secret_state: &S,
+    publisher: &CloudEventsPublisher,
+) -> Result<()> {
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let warehouse = C::require_warehouse(warehouse_id, t.transaction()).await?;
+    t.commit().await?;
+
+    let secret = maybe_get_secret(warehouse.storage_secret_id, secret_state).await?;
+    let report = warehouse
+        .storage_profile
+        .check_health_report(secret.as_ref())
+        .await;
+
+    let (storage_health, failed_checks) = if report.success {
+        (WarehouseStorageHealth::Healthy, None)
+    } else {
+        let failed_checks = report.failed_checks().collect::<Vec<_>>();
+        tracing::warn!(
+            "Storage check of warehouse {warehouse_id} failed: {:?}",
+            failed_checks
+        );
+        (
+            WarehouseStorageHealth::DegradedStorage,
+            Some(serde_json::to_value(&failed_checks).unwrap_or_default()),
+        )
+    };
+
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let previous = C::set_warehouse_storage_health(
+        warehouse_id,
+        storage_health,
+        failed_checks.clone(),
+        t.transaction(),
+    )
+    .await?;
+    t.commit().await?;
+
+    let event_type = match (previous.unwrap_or_default(), storage_health) {
+        (WarehouseStorageHealth::Healthy, WarehouseStorageHealth::DegradedStorage) => {
+            "warehouseStorageDegraded"
+        }
+        (WarehouseStorageHealth::DegradedStorage, WarehouseStorageHealth::Healthy) => {
+            "warehouseStorageRecovered"
+        }
+        _ => return Ok(()),
+    };
+
+    let event_id = Uuid::now_v7();
+    let _ = publisher
+        .publish_warehouse_event(
+            event_id,
+            event_type,
+            serde_json::json!({
+                "warehouse-id": warehouse_id,
+                "warehouse-name": warehouse.name,
+                "project-id": warehouse.project_id,
+                "storage-health": storage_health,
+                "failed-checks": failed_checks,
+            }),
+            WarehouseEventMetadata {
+                warehouse_id,
+                trace_id: event_id,
+            },
+        )
+        .await;
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use crate::api::Result;
+use crate::service::{Catalog, NamespaceStorageUsage, Transaction};
+use axum_prometheus::metrics::gauge;
+use chrono::NaiveDate;
+use std::time::Duration;
+
+/// Bytes used by the tables of a namespace according to the latest rollup.
+const NAMESPACE_STORAGE_BYTES: &str = "lakekeeper_namespace_storage_bytes";
+/// Interval in which we check whether the rollup of the current day is due.
+const POLL_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Compute a rollup of the storage used by each namespace once per day (UTC).
+///
+/// Sizes are taken from the snapshot summaries of the tables, so no bucket is listed.
+/// Rollups are kept for `retention_days` and served by the management API. If
+/// `export_metrics` is set, the latest rollup is also exported as
+/// `lakekeeper_namespace_storage_bytes` gauge.
+pub async fn storage_usage_task<C: Catalog>(
+    catalog_state: C::State,
+    retention_days: u32,
+    export_metrics: bool,
+) {
+    let mut last_rollup: Option<NaiveDate> = None;
+
+    loop {
+        let today = chrono::Utc::now().date_naive();
+        if last_rollup != Some(today) {
+            let retain_from = today
+                .checked_sub_days(chrono::Days::new(u64::from(retention_days)))
+                .unwrap_or(NaiveDate::MIN);
+            match rollup::<C>(catalog_state.clone(), today, retain_from).await {
+                Ok(usage) => {
+                    tracing::info!("Computed storage usage of {} namespaces", usage.len());
+                    if export_metrics {
+                        export_metrics_for(&usage);
+                    }
+                    last_rollup = Some(today);
+                }
+                Err(err) => {
+                    tracing::error!("Failed to compute storage usage: {:?}", err.error);
+                }
+            }
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn rollup<C: Catalog>(
+    catalog_state: C::State,
+    day: NaiveDate,
+    retain_from: NaiveDate,
+) -> Result<Vec<NamespaceStorageUsage>> {
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let usage = C::rollup_storage_usage(day, retain_from, t.transaction()).await?;
+    t.commit().await?;
+    Ok(usage)
+}
+
+fn export_metrics_for(usage: &[NamespaceStorageUsage]) {
+    for namespace in usage {
+        #[allow(clippy::cast_precision_loss)]
+        gauge!(
+            NAMESPACE_STORAGE_BYTES,
+            "warehouse_id" => namespace.warehouse_id.to_string(),
+            "namespace" => namespace.namespace.to_url_string()
+        )
+        .set(namespace.total_bytes as f64);
+    }
+}
@@ -0,0 +1,274 @@
+use crate::api::management::v1::warehouse::TabularDeleteProfile;
+use crate::api::management::v1::TabularType;
+use crate::api::Result;
+use crate::service::contract_verification::{
+    ContractVerification, ContractVerificationOutcome, ContractVerifiers,
+};
+use crate::service::event_publisher::{CloudEventsPublisher, EventMetadata};
+use crate::service::task_queue::tabular_expiration_queue::TabularExpirationInput;
+use crate::service::task_queue::TaskQueues;
+use crate::service::{Catalog, TableTtlCandidate, TabularIdentUuid, Transaction};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Namespace property holding the TTL of tables in the namespace, for example `7d`.
+pub const TABLE_TTL_PROPERTY: &str = "table-ttl";
+/// Namespace property selecting the time the TTL is counted from:
+/// `created` (default) or `last-commit`.
+pub const TABLE_TTL_BASIS_PROPERTY: &str = "table-ttl-basis";
+/// Table property exempting a table from the TTL of its namespace if set to `true`.
+pub const TABLE_TTL_EXEMPT_PROPERTY: &str = "table-ttl-exempt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum TtlBasis {
+    #[default]
+    Created,
+    LastCommit,
+}
+
+impl TtlBasis {
+    fn parse(value: Option<&str>) -> Option<Self> {
+        match value {
+            None | Some("created") => Some(TtlBasis::Created),
+            Some("last-commit") => Some(TtlBasis::LastCommit),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Parse a TTL such as `30m`, `12h`, `7d` or `2w`.
+fn parse_ttl(value: &str) -> Option<chrono::Duration> {
+    let (amount, unit) = lazy_regex::regex_captures!(r"^(\d+)([smhdw])$", value.trim())
+        .map(|(_, amount, unit)| (amount, unit))?;
+    let amount: i64 = amount.parse().ok()?;
+    match unit {
+        "s" => chrono::Duration::try_seconds(amount),
+        "m" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }
+    .filter(|ttl| *ttl > chrono::Duration::zero())
+}
+
+/// Reject invalid values of the TTL namespace properties.
+///
+/// # Errors
+/// Fails if `table-ttl` or `table-ttl-basis` cannot be parsed.
+pub(crate) fn validate_table_ttl_properties(properties: &HashMap<String, String>) -> Result<()> {
+    if let Some(ttl) = properties.get(TABLE_TTL_PROPERTY) {
+        if parse_ttl(ttl).is_none() {
+            return Err(ErrorModel::bad_request(
+                format!("Invalid `{TABLE_TTL_PROPERTY}` '{ttl}'. Expected a positive number followed by one of s, m, h, d, w, for example `7d`."),
+                "InvalidTableTtl",
+                None,
+            )
+            .into());
+        }
+    }
+    if let Some(basis) = properties.get(TABLE_TTL_BASIS_PROPERTY) {
+        if TtlBasis::parse(Some(basis)).is_none() {
+            return Err(ErrorModel::bad_request(
+                format!("Invalid `{TABLE_TTL_BASIS_PROPERTY}` '{basis}'. Expected `created` or `last-commit`."),
+                "InvalidTableTtlBasis",
+                None,
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn is_expired(candidate: &TableTtlCandidate, now: chrono::DateTime<chrono::Utc>) -> bool {
+    let (Some(ttl), Some(basis)) = (
+        parse_ttl(&candidate.ttl),
+        TtlBasis::parse(candidate.basis.as_deref()),
+    ) else {
+        tracing::warn!(
+            "Ignoring invalid table TTL '{}' / basis '{:?}' of namespace {:?}",
+            candidate.ttl,
+            candidate.basis,
+            candidate.namespace
+        );
+        return false;
+    };
+    let since = match basis {
+        TtlBasis::Created => candidate.created_at,
+        TtlBasis::LastCommit => candidate.last_commit_at.unwrap_or(candidate.created_at),
+    };
+    since + ttl < now
+}
+
+/// Periodically drop tables that outlived the `table-ttl` of their namespace.
+///
+/// Tables are dropped like a `DROP TABLE` with purge: they are soft-deleted according to
+/// the delete profile of their warehouse and purged once the expiration task runs.
+/// Tables with the `table-ttl-exempt` property set to `true` are kept, as are tables whose
+/// drop is rejected by the `contract_verifiers`.
+pub async fn table_ttl_task<C: Catalog>(
+    catalog_state: C::State,
+    queues: TaskQueues,
+    publisher: CloudEventsPublisher,
+    contract_verifiers: ContractVerifiers,
+    interval: std::time::Duration,
+) {
+    loop {
+        match drop_expired_tables::<C>(catalog_state.clone(), &queues, &contract_verifiers).await {
+            Ok(dropped) => {
+                for table in dropped {
+                    publish_event(&publisher, table).await;
+                }
+            }
+            Err(err) => {
+                tracing::error!("Failed to apply table TTLs: {:?}", err.error);
+            }
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+async fn drop_expired_tables<C: Catalog>(
+    catalog_state: C::State,
+    queues: &TaskQueues,
+    contract_verifiers: &ContractVerifiers,
+) -> Result<Vec<TableTtlCandidate>> {
+    let now = chrono::Utc::now();
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    let candidates = C::list_table_ttl_candidates(t.transaction())
+        .await?
+        .into_iter()
+        .filter(|candidate| is_expired(candidate, now));
+
+    let mut warehouses: HashMap<WarehouseIdent, (TabularDeleteProfile, ContractVerifiers)> =
+        HashMap::new();
+    let mut expired = vec![];
+    for candidate in candidates {
+        if !warehouses.contains_key(&candidate.warehouse_id) {
+            let warehouse = C::require_warehouse(candidate.warehouse_id, t.transaction()).await?;
+            let verifiers = contract_verifiers.for_warehouse(&warehouse.wasm_hooks)?;
+            warehouses.insert(
+                candidate.warehouse_id,
+                (warehouse.tabular_delete_profile, verifiers),
+            );
+        }
+        let (_, verifiers) = &warehouses[&candidate.warehouse_id];
+        match verifiers
+            .check_drop(TabularIdentUuid::Table(*candidate.table_id))
+            .await
+            .and_then(ContractVerificationOutcome::into_result)
+        {
+            Ok(()) => expired.push(candidate),
+            Err(e) => tracing::info!(
+                "Keeping table {} that outlived its TTL, as its drop was rejected: {}",
+                candidate.table_id,
+                e.message
+            ),
+        }
+    }
+
+    let mut expirations = Vec::with_capacity(expired.len());
+    for table in &expired {
+        let delete_profile = warehouses[&table.warehouse_id].0;
+        C::mark_tabular_as_deleted(TabularIdentUuid::Table(*table.table_id), t.transaction())
+            .await?;
+        let expire_at = match delete_profile {
+            TabularDeleteProfile::Hard {} => now,
+            TabularDeleteProfile::Soft { expiration_seconds } => now + expiration_seconds,
+        };
+        expirations.push(TabularExpirationInput {
+            tabular_id: *table.table_id,
+            warehouse_ident: table.warehouse_id,
+            tabular_type: TabularType::Table,
+            purge: true,
+            expire_at,
+        });
+    }
+    t.commit().await?;
+
+    for expiration in expirations {
+        queues.queue_tabular_expiration(expiration).await?;
+    }
+    if !expired.is_empty() {
+        tracing::info!("Dropped {} tables that outlived their TTL", expired.len());
+    }
+    Ok(expired)
+}
+
+async fn publish_event(publisher: &CloudEventsPublisher, table: TableTtlCandidate) {
+    let trace_id = Uuid::now_v7();
+    let _ = publisher
+        .publish(
+            Uuid::now_v7(),
+            "dropTable",
+            serde_json::json!({ "reason": "table-ttl", "table-ttl": table.ttl }),
+            EventMetadata {
+                tabular_id: TabularIdentUuid::Table(*table.table_id),
+                warehouse_id: table.warehouse_id,
+                name: table.name,
+                namespace: table.namespace.to_url_string(),
+                prefix: table.warehouse_id.to_string(),
+                num_events: 1,
+                sequence_number: 0,
+                trace_id,
+                engine_query_id: None,
+                client_ip: None,
+            },
+        )
+        .await;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn candidate(ttl: &str, basis: Option<&str>, age_days: i64) -> TableTtlCandidate {
+        let now = chrono::Utc::now();
+        TableTtlCandidate {
+            table_id: Uuid::now_v7().into(),
+            warehouse_id: Uuid::now_v7().into(),
+            namespace: crate::service::NamespaceIdent::new("scratch".to_string()),
+            name: "tmp".to_string(),
+            created_at: now - chrono::Duration::days(age_days),
+            last_commit_at: Some(now - chrono::Duration::hours(1)),
+            ttl: ttl.to_string(),
+            basis: basis.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_ttl() {
+        assert_eq!(parse_ttl("30m"), Some(chrono::Duration::minutes(30)));
+        assert_eq!(parse_ttl(" 7d "), Some(chrono::Duration::days(7)));
+        assert_eq!(parse_ttl("2w"), Some(chrono::Duration::weeks(2)));
+        assert_eq!(parse_ttl("0d"), None);
+        assert_eq!(parse_ttl("7"), None);
+        assert_eq!(parse_ttl("7 days"), None);
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = chrono::Utc::now();
+        assert!(is_expired(&candidate("7d", None, 8), now));
+        assert!(!is_expired(&candidate("7d", None, 6), now));
+        assert!(!is_expired(&candidate("7d", Some("last-commit"), 8), now));
+        assert!(is_expired(&candidate("30m", Some("last-commit"), 8), now));
+        assert!(!is_expired(&candidate("invalid", None, 8), now));
+    }
+
+    #[test]
+    fn test_validate_table_ttl_properties() {
+        let props = |k: &str, v: &str| HashMap::from([(k.to_string(), v.to_string())]);
+        assert!(validate_table_ttl_properties(&props(TABLE_TTL_PROPERTY, "7d")).is_ok());
+        assert!(validate_table_ttl_properties(&props(TABLE_TTL_PROPERTY, "7")).is_err());
+        assert!(
+            validate_table_ttl_properties(&props(TABLE_TTL_BASIS_PROPERTY, "last-commit")).is_ok()
+        );
+        assert!(
+            validate_table_ttl_properties(&props(TABLE_TTL_BASIS_PROPERTY, "updated")).is_err()
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::service::housekeeping::evict_expired;
+use crate::service::{Catalog, TabularAccessKind, TabularIdentUuid};
+use crate::CONFIG;
+
+/// Accesses that were recorded recently by this instance. Loads of the same table are
+/// frequent, so we only go to the database once the entry expired.
+static RECENT_ACCESS: LazyLock<moka::sync::Cache<(Uuid, TabularAccessKind), ()>> =
+    LazyLock::new(|| {
+        moka::sync::Cache::builder()
+            .max_capacity(100_000)
+            .time_to_live(Duration::from_secs(
+                CONFIG.tabular_access_interval_seconds.max(1),
+            ))
+            .build()
+    });
+
+pub(crate) fn evict_expired_recent_access() -> u64 {
+    evict_expired(&RECENT_ACCESS)
+}
+
+/// Record a read or commit of a table or view in the background.
+///
+/// Each kind of access is recorded at most once per `tabular_access_interval_seconds`,
+/// also across instances. Failures are only logged, they must not fail the request.
+pub(crate) fn record_tabular_access<C: Catalog>(
+    tabular_id: TabularIdentUuid,
+    kind: TabularAccessKind,
+    catalog_state: C::State,
+) {
+    let interval = CONFIG.tabular_access_interval_seconds;
+    if interval == 0 {
+        return;
+    }
+    let key = (*tabular_id, kind);
+    if RECENT_ACCESS.contains_key(&key) {
+        return;
+    }
+    RECENT_ACCESS.insert(key, ());
+
+    let min_interval =
+        chrono::Duration::from_std(Duration::from_secs(interval)).unwrap_or(chrono::Duration::MAX);
+    tokio::spawn(async move {
+        if let Err(e) =
+            C::record_tabular_access(tabular_id, kind, min_interval, catalog_state).await
+        {
+            tracing::warn!(
+                "Failed to record {kind:?} access of tabular {tabular_id}: {:?}",
+                e.error
+            );
+        }
+    });
+}
@@ -1,8 +1,18 @@
+use crate::service::maintenance::MaintenanceExecutorRef;
+use crate::service::task_queue::table_inventory_export_queue::{
+    InventoryExportDetails, TableInventoryExportInput,
+};
+use crate::service::task_queue::tabular_compaction_queue::TabularCompactionInput;
 use crate::service::task_queue::tabular_expiration_queue::TabularExpirationInput;
-use crate::service::task_queue::tabular_purge_queue::TabularPurgeInput;
-use crate::service::{Catalog, SecretStore};
+use crate::service::task_queue::tabular_purge_queue::{
+    PurgeAwaitingApproval, PurgeTaskDetails, TabularPurgeInput,
+};
+use crate::service::task_queue::tabular_relocation_queue::TabularRelocationInput;
+use crate::service::{Catalog, SecretStore, TableIdentUuid};
+use crate::CONFIG;
 use async_trait::async_trait;
 use chrono::Utc;
+use iceberg::spec::TableMetadata;
 use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::FromRow;
 use std::fmt::Debug;
@@ -14,13 +24,20 @@ use uuid::Uuid;
 use super::authz::Authorizer;
 use super::WarehouseIdent;
 
+pub mod table_inventory_export_queue;
+pub mod tabular_compaction_queue;
 pub mod tabular_expiration_queue;
 pub mod tabular_purge_queue;
+pub mod tabular_relocation_queue;
 
 #[derive(Debug, Clone)]
 pub struct TaskQueues {
     tabular_expiration: tabular_expiration_queue::ExpirationQueue,
     tabular_purge: tabular_purge_queue::TabularPurgeQueue,
+    tabular_relocation: tabular_relocation_queue::TabularRelocationQueue,
+    tabular_compaction: tabular_compaction_queue::TabularCompactionQueue,
+    table_inventory_export: table_inventory_export_queue::TableInventoryExportQueue,
+    maintenance_executor: Option<MaintenanceExecutorRef>,
 }
 
 impl TaskQueues {
@@ -28,13 +45,42 @@ impl TaskQueues {
     pub fn new(
         expiration: tabular_expiration_queue::ExpirationQueue,
         purge: tabular_purge_queue::TabularPurgeQueue,
+        relocation: tabular_relocation_queue::TabularRelocationQueue,
+        compaction: tabular_compaction_queue::TabularCompactionQueue,
+        inventory_export: table_inventory_export_queue::TableInventoryExportQueue,
     ) -> Self {
         Self {
             tabular_expiration: expiration,
             tabular_purge: purge,
+            tabular_relocation: relocation,
+            tabular_compaction: compaction,
+            table_inventory_export: inventory_export,
+            maintenance_executor: None,
         }
     }
 
+    /// Enable compaction of tables. Without an executor, no compactions are queued.
+    #[must_use]
+    pub fn with_maintenance_executor(mut self, executor: MaintenanceExecutorRef) -> Self {
+        self.maintenance_executor = Some(executor);
+        self
+    }
+
+    /// Names of the queues processed by the workers of this replica.
+    #[must_use]
+    pub fn queue_names(&self) -> Vec<&'static str> {
+        let mut names = vec![
+            self.tabular_expiration.queue_name(),
+            self.tabular_purge.queue_name(),
+            self.tabular_relocation.queue_name(),
+            self.table_inventory_export.queue_name(),
+        ];
+        if self.maintenance_executor.is_some() {
+            names.push(self.tabular_compaction.queue_name());
+        }
+        names
+    }
+
     #[tracing::instrument(skip(self))]
     pub(crate) async fn queue_tabular_expiration(
         &self,
@@ -59,6 +105,100 @@ impl TaskQueues {
         self.tabular_purge.enqueue(task).await
     }
 
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn approve_tabular_purge(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        task_id: Uuid,
+    ) -> crate::api::Result<bool> {
+        self.tabular_purge.approve(warehouse_ident, task_id).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn list_purges_awaiting_approval(
+        &self,
+        warehouse_ident: WarehouseIdent,
+    ) -> crate::api::Result<Vec<PurgeAwaitingApproval>> {
+        self.tabular_purge
+            .list_awaiting_approval(warehouse_ident)
+            .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn has_unfinished_tabular_purges(
+        &self,
+        warehouse_ident: WarehouseIdent,
+    ) -> crate::api::Result<bool> {
+        self.tabular_purge.has_unfinished(warehouse_ident).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn get_tabular_purge(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        task_id: Uuid,
+    ) -> crate::api::Result<Option<PurgeTaskDetails>> {
+        self.tabular_purge.get_purge(warehouse_ident, task_id).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn queue_tabular_relocation(
+        &self,
+        task: TabularRelocationInput,
+    ) -> crate::api::Result<()> {
+        self.tabular_relocation.enqueue(task).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn queue_table_inventory_export(
+        &self,
+        task: TableInventoryExportInput,
+    ) -> crate::api::Result<()> {
+        self.table_inventory_export.enqueue(task).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub(crate) async fn get_table_inventory_export(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        table_id: TableIdentUuid,
+        export_id: Uuid,
+    ) -> crate::api::Result<Option<InventoryExportDetails>> {
+        self.table_inventory_export
+            .get_export(warehouse_ident, table_id, export_id)
+            .await
+    }
+
+    /// Queue a compaction if the current snapshot of the table exceeds the configured
+    /// thresholds. Errors are logged and not returned, as a missed compaction must not
+    /// fail the commit that triggered it.
+    #[tracing::instrument(skip(self, metadata))]
+    pub(crate) async fn queue_compaction_if_needed(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        metadata: &TableMetadata,
+    ) {
+        if self.maintenance_executor.is_none() {
+            return;
+        }
+        let Some(snapshot_id) = metadata.current_snapshot_id() else {
+            return;
+        };
+        let Some(reason) = CONFIG.compaction_thresholds().compaction_reason(metadata) else {
+            return;
+        };
+
+        let input = TabularCompactionInput {
+            table_id: TableIdentUuid::from(metadata.uuid()),
+            warehouse_ident,
+            snapshot_id,
+            reason,
+        };
+        if let Err(e) = self.tabular_compaction.enqueue(input).await {
+            tracing::warn!("Failed to queue compaction: {:?}", e.error);
+        }
+    }
+
     pub async fn spawn_queues<C, S, A>(
         &self,
         catalog_state: C::State,
@@ -81,9 +221,40 @@ impl TaskQueues {
         let purge_queue_handler = tokio::task::spawn(tabular_purge_queue::purge_task::<C, S>(
             self.tabular_purge.clone(),
             catalog_state.clone(),
-            secret_store,
+            secret_store.clone(),
         ));
 
+        let relocation_queue_handler =
+            tokio::task::spawn(tabular_relocation_queue::relocation_task::<C, S>(
+                self.tabular_relocation.clone(),
+                catalog_state.clone(),
+                secret_store.clone(),
+            ));
+
+        let inventory_export_queue_handler =
+            tokio::task::spawn(table_inventory_export_queue::inventory_export_task::<C, S>(
+                self.table_inventory_export.clone(),
+                catalog_state.clone(),
+                secret_store,
+            ));
+
+        let compaction_queue = self.tabular_compaction.clone();
+        let maintenance_executor = self.maintenance_executor.clone();
+        let compaction_queue_handler = tokio::task::spawn(async move {
+            match maintenance_executor {
+                Some(executor) => {
+                    tabular_compaction_queue::compaction_task::<C>(
+                        compaction_queue,
+                        executor,
+                        catalog_state,
+                        CONFIG.maintenance_executor_max_concurrent_jobs,
+                    )
+                    .await;
+                }
+                None => std::future::pending().await,
+            }
+        });
+
         tokio::select!(
             _ = expiration_queue_handler => {
                 tracing::error!("Tabular expiration queue handler exited unexpectedly");
@@ -93,6 +264,18 @@ impl TaskQueues {
                 tracing::error!("Tabular purge queue handler exited unexpectedly");
                 Err(anyhow::anyhow!("Tabular purge queue handler exited unexpectedly"))
             },
+            _ = relocation_queue_handler => {
+                tracing::error!("Tabular relocation queue handler exited unexpectedly");
+                Err(anyhow::anyhow!("Tabular relocation queue handler exited unexpectedly"))
+            },
+            _ = inventory_export_queue_handler => {
+                tracing::error!("Table inventory export queue handler exited unexpectedly");
+                Err(anyhow::anyhow!("Table inventory export queue handler exited unexpectedly"))
+            },
+            _ = compaction_queue_handler => {
+                tracing::error!("Tabular compaction queue handler exited unexpectedly");
+                Err(anyhow::anyhow!("Tabular compaction queue handler exited unexpectedly"))
+            },
         )?;
         Ok(())
     }
@@ -298,6 +481,28 @@ mod test {
         );
         let purge_queue = Arc::new(
             crate::implementations::postgres::task_queues::TabularPurgeQueue::from_config(
+                rw.clone(),
+                config.clone(),
+            )
+            .unwrap(),
+        );
+
+        let relocation_queue = Arc::new(
+            crate::implementations::postgres::task_queues::TabularRelocationQueue::from_config(
+                rw.clone(),
+                config.clone(),
+            )
+            .unwrap(),
+        );
+        let compaction_queue = Arc::new(
+            crate::implementations::postgres::task_queues::TabularCompactionQueue::from_config(
+                rw.clone(),
+                config.clone(),
+            )
+            .unwrap(),
+        );
+        let inventory_export_queue = Arc::new(
+            crate::implementations::postgres::task_queues::TableInventoryExportQueue::from_config(
                 rw.clone(),
                 config,
             )
@@ -306,8 +511,13 @@ mod test {
 
         let catalog_state = CatalogState::from_pools(pool.clone(), pool.clone());
 
-        let queues =
-            crate::service::task_queue::TaskQueues::new(expiration_queue.clone(), purge_queue);
+        let queues = crate::service::task_queue::TaskQueues::new(
+            expiration_queue.clone(),
+            purge_queue,
+            relocation_queue,
+            compaction_queue,
+            inventory_export_queue,
+        );
         let secrets =
             crate::implementations::postgres::SecretsState::from_pools(pool.clone(), pool);
         let cloned = queues.clone();
@@ -0,0 +1,833 @@
+use crate::api::management::v1::table::{InventoryExportStatus, InventoryFormat};
+use crate::api::Result;
+use crate::catalog::io::{new_output, write_file, IoError};
+use crate::catalog::manifest_cache::read_manifest;
+use crate::catalog::maybe_get_secret;
+use crate::service::task_queue::{Task, TaskQueue};
+use crate::service::{Catalog, SecretStore, TableIdentUuid, Transaction};
+use crate::WarehouseIdent;
+use apache_avro::types::Value;
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field, Schema};
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+use iceberg::io::{FileIO, FileWrite};
+use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::configs::{Location, ParseFromStr};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+use uuid::Uuid;
+
+/// Number of data files per inventory file.
+const ROWS_PER_FILE: usize = 1_000_000;
+/// Number of rows encoded and uploaded at once. Each batch is a row group in parquet files.
+const ROWS_PER_BATCH: usize = 100_000;
+/// Number of manifests read concurrently.
+const MANIFEST_READ_PARALLELISM: usize = 16;
+/// Name of the file listing the inventory files, written once all of them are written.
+const INVENTORY_MANIFEST_NAME: &str = "inventory.json";
+/// Status of manifest entries whose file was removed by the snapshot of the manifest.
+const ENTRY_STATUS_DELETED: i32 = 2;
+
+pub type TableInventoryExportQueue = Arc<dyn InventoryExportQueue + Send + Sync + 'static>;
+
+/// Queue of data file inventory exports of tables.
+#[async_trait]
+pub trait InventoryExportQueue:
+    TaskQueue<Task = TableInventoryExportTask, Input = TableInventoryExportInput>
+{
+    /// Store the result of a finished export.
+    async fn record_result(&self, id: Uuid, result: &InventoryExportResult) -> Result<()>;
+
+    /// Get status and result of an export of the table.
+    async fn get_export(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        table_id: TableIdentUuid,
+        export_id: Uuid,
+    ) -> Result<Option<InventoryExportDetails>>;
+}
+
+// TODO: concurrent workers
+pub async fn inventory_export_task<C: Catalog, S: SecretStore>(
+    fetcher: TableInventoryExportQueue,
+    catalog_state: C::State,
+    secret_state: S,
+) {
+    loop {
+        tokio::time::sleep(fetcher.config().poll_interval + Duration::from_millis(23)).await;
+
+        let export = match fetcher.pick_new_task().await {
+            Ok(export) => export,
+            Err(err) => {
+                tracing::error!("Failed to fetch inventory export: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(export) = export else {
+            continue;
+        };
+
+        let span = tracing::debug_span!(
+            "table_inventory_export",
+            export_id = %export.export_id,
+            table_id = %export.table_id,
+            warehouse_id = %export.warehouse_ident,
+            target_location = %export.target_location,
+            format = ?export.format,
+            queue_name = %export.task.queue_name,
+            task = ?export.task,
+        );
+
+        instrumented_export::<C, S>(
+            fetcher.clone(),
+            catalog_state.clone(),
+            &secret_state,
+            &export,
+        )
+        .instrument(span.or_current())
+        .await;
+    }
+}
+
+async fn instrumented_export<C: Catalog, S: SecretStore>(
+    fetcher: TableInventoryExportQueue,
+    catalog_state: C::State,
+    secret_state: &S,
+    export: &TableInventoryExportTask,
+) {
+    let result = match export_inventory::<C, S>(export, secret_state, catalog_state).await {
+        Ok(result) => fetcher
+            .record_result(export.task.task_id, &result)
+            .await
+            .map(|()| result),
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(result) => {
+            fetcher.retrying_record_success(&export.task).await;
+            tracing::info!(
+                "Exported inventory of {} data files of table {} to {}",
+                result.data_file_count,
+                export.table_id,
+                export.target_location
+            );
+        }
+        Err(err) => {
+            tracing::error!(
+                "Failed to export inventory of table {}: {}",
+                export.table_id,
+                err.error
+            );
+            fetcher
+                .retrying_record_failure(&export.task, &err.error.to_string())
+                .await;
+        }
+    }
+}
+
+/// Write an inventory of all data and delete files referenced by any snapshot of a table.
+///
+/// Files are read from the manifests of all snapshots and deduplicated by path, so the
+/// inventory can be compared with a listing of the storage to find orphaned or missing
+/// files. The inventory is split into files of at most [`ROWS_PER_FILE`] rows, which are
+/// uploaded in batches while the manifests are read. The `inventory.json` listing them is
+/// written last, its presence marks a complete export.
+async fn export_inventory<C, S>(
+    TableInventoryExportTask {
+        export_id: _,
+        table_id,
+        warehouse_ident,
+        target_location,
+        format,
+        task: _,
+    }: &TableInventoryExportTask,
+    secret_state: &S,
+    catalog_state: C::State,
+) -> Result<InventoryExportResult>
+where
+    C: Catalog,
+    S: SecretStore,
+{
+    let target_location = parse_location(target_location)?;
+
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let mut tables = C::load_tables(*warehouse_ident, [*table_id], false, t.transaction()).await?;
+    t.commit().await?;
+    let table = tables.remove(table_id).ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("Table {table_id} to export the inventory of does not exist anymore"),
+            "TableNotFound",
+            None,
+        )
+    })?;
+
+    let secret = maybe_get_secret(table.storage_secret_ident, secret_state).await?;
+    let file_io = table.storage_profile.file_io(secret.as_ref())?;
+
+    // ------------------- Manifests -------------------
+    let mut manifests = HashMap::new();
+    for snapshot in table.table_metadata.snapshots() {
+        let manifest_list = snapshot.manifest_list();
+        let content = read_manifest(
+            *warehouse_ident,
+            &file_io,
+            &parse_location(manifest_list)?,
+            None,
+        )
+        .await?;
+        for manifest in list_manifest_files(&content, manifest_list)? {
+            manifests.entry(manifest.path.clone()).or_insert(manifest);
+        }
+    }
+
+    // ------------------- Inventory -------------------
+    // Rows are uploaded as the manifests are read. Only the paths are kept, to skip
+    // files that are listed by the manifests of several snapshots.
+    let mut writer = InventoryWriter::new(&file_io, &target_location, *format);
+    let mut written_paths = HashSet::new();
+    let mut entries = futures::stream::iter(manifests.values())
+        .map(|manifest| {
+            let file_io = &file_io;
+            async move {
+                let content = read_manifest(
+                    *warehouse_ident,
+                    file_io,
+                    &parse_location(&manifest.path)?,
+                    manifest.length,
+                )
+                .await?;
+                manifest_entries(&content, manifest)
+            }
+        })
+        .buffer_unordered(MANIFEST_READ_PARALLELISM);
+    while let Some(files) = entries.try_next().await? {
+        for entry in files {
+            if written_paths.insert(entry.file_path.clone()) {
+                writer.push(entry).await?;
+            }
+        }
+    }
+    let result = writer.finish().await?;
+
+    let manifest = serde_json::to_vec_pretty(&InventoryManifest {
+        table_id: **table_id,
+        format: *format,
+        data_file_count: result.data_file_count,
+        files: &result.inventory_files,
+        created_at: chrono::Utc::now(),
+    })
+    .map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize inventory manifest",
+            "SerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+    write_file(
+        &file_io,
+        &target_location.cloning_push(INVENTORY_MANIFEST_NAME),
+        manifest,
+    )
+    .await?;
+
+    Ok(result)
+}
+
+fn parse_location(location: &str) -> Result<Location> {
+    Location::parse_value(location).map_err(|e| {
+        ErrorModel::internal(
+            format!("Failed to parse location {location}"),
+            "ParseError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn avro_error(file: &str, e: apache_avro::Error) -> crate::api::IcebergErrorResponse {
+    ErrorModel::internal(
+        format!("Failed to read avro file {file}"),
+        "AvroReadError",
+        Some(Box::new(e)),
+    )
+    .into()
+}
+
+/// Optional fields are written as union with `null`.
+fn unwrap_union(value: Value) -> Value {
+    match value {
+        Value::Union(_, value) => *value,
+        value => value,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct ManifestFile {
+    path: String,
+    length: Option<u64>,
+    /// Inherited by entries without snapshot id
+    added_snapshot_id: Option<i64>,
+}
+
+fn list_manifest_files(manifest_list: &[u8], file: &str) -> Result<Vec<ManifestFile>> {
+    let reader = apache_avro::Reader::new(manifest_list).map_err(|e| avro_error(file, e))?;
+    let mut manifests = Vec::new();
+    for entry in reader {
+        let Value::Record(fields) = entry.map_err(|e| avro_error(file, e))? else {
+            continue;
+        };
+        let mut path = None;
+        let mut length = None;
+        let mut added_snapshot_id = None;
+        for (name, value) in fields {
+            match (name.as_str(), unwrap_union(value)) {
+                ("manifest_path", Value::String(p)) => path = Some(p),
+                ("manifest_length", Value::Long(l)) => length = u64::try_from(l).ok(),
+                ("added_snapshot_id", Value::Long(id)) => added_snapshot_id = Some(id),
+                _ => {}
+            }
+        }
+        if let Some(path) = path {
+            manifests.push(ManifestFile {
+                path,
+                length,
+                added_snapshot_id,
+            });
+        }
+    }
+    Ok(manifests)
+}
+
+/// A data or delete file of the inventory.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct InventoryEntry {
+    file_path: String,
+    content: &'static str,
+    file_format: String,
+    /// Partition values as JSON object
+    partition: String,
+    record_count: i64,
+    file_size_in_bytes: i64,
+    /// Snapshot that added the file
+    snapshot_id: Option<i64>,
+}
+
+/// Files of a manifest that are not deleted by its snapshot.
+fn manifest_entries(manifest: &[u8], file: &ManifestFile) -> Result<Vec<InventoryEntry>> {
+    let reader = apache_avro::Reader::new(manifest).map_err(|e| avro_error(&file.path, e))?;
+    let mut entries = Vec::new();
+    for entry in reader {
+        let Value::Record(fields) = entry.map_err(|e| avro_error(&file.path, e))? else {
+            continue;
+        };
+        let mut status = 0;
+        let mut snapshot_id = None;
+        let mut data_file = None;
+        for (name, value) in fields {
+            match (name.as_str(), unwrap_union(value)) {
+                ("status", Value::Int(s)) => status = s,
+                ("snapshot_id", Value::Long(id)) => snapshot_id = Some(id),
+                ("data_file", Value::Record(fields)) => data_file = Some(fields),
+                _ => {}
+            }
+        }
+        let Some(data_file) = data_file else {
+            continue;
+        };
+        if status == ENTRY_STATUS_DELETED {
+            continue;
+        }
+
+        // Format version 1 has no content field, all files are data files.
+        let mut entry = InventoryEntry {
+            content: "data",
+            snapshot_id: snapshot_id.or(file.added_snapshot_id),
+            ..InventoryEntry::default()
+        };
+        for (name, value) in data_file {
+            match (name.as_str(), unwrap_union(value)) {
+                ("content", Value::Int(content)) => entry.content = content_name(content),
+                ("file_path", Value::String(path)) => entry.file_path = path,
+                ("file_format", Value::String(format)) => entry.file_format = format,
+                ("partition", partition) => {
+                    entry.partition = serde_json::Value::try_from(partition)
+                        .map(|v| v.to_string())
+                        .unwrap_or_default();
+                }
+                ("record_count", Value::Long(count)) => entry.record_count = count,
+                ("file_size_in_bytes", Value::Long(size)) => entry.file_size_in_bytes = size,
+                _ => {}
+            }
+        }
+        if !entry.file_path.is_empty() {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+fn content_name(content: i32) -> &'static str {
+    match content {
+        1 => "position-deletes",
+        2 => "equality-deletes",
+        _ => "data",
+    }
+}
+
+const COLUMNS: [&str; 7] = [
+    "file_path",
+    "content",
+    "file_format",
+    "partition",
+    "record_count",
+    "file_size_in_bytes",
+    "snapshot_id",
+];
+
+/// Splits the inventory into files of at most [`ROWS_PER_FILE`] rows and uploads every
+/// [`ROWS_PER_BATCH`] rows, so that memory does not grow with the number of files of the table.
+struct InventoryWriter<'a> {
+    file_io: &'a FileIO,
+    target_location: &'a Location,
+    format: InventoryFormat,
+    batch: Vec<InventoryEntry>,
+    file: Option<InventoryFile>,
+    inventory_files: Vec<String>,
+    row_count: usize,
+}
+
+struct InventoryFile {
+    writer: Box<dyn FileWrite>,
+    encoder: InventoryEncoder,
+    row_count: usize,
+}
+
+impl<'a> InventoryWriter<'a> {
+    fn new(file_io: &'a FileIO, target_location: &'a Location, format: InventoryFormat) -> Self {
+        Self {
+            file_io,
+            target_location,
+            format,
+            batch: Vec::with_capacity(ROWS_PER_BATCH),
+            file: None,
+            inventory_files: Vec::new(),
+            row_count: 0,
+        }
+    }
+
+    async fn push(&mut self, entry: InventoryEntry) -> Result<()> {
+        self.batch.push(entry);
+        let file_row_count = self.file.as_ref().map_or(0, |file| file.row_count);
+        if self.batch.len() >= ROWS_PER_BATCH || file_row_count + self.batch.len() >= ROWS_PER_FILE
+        {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Upload the buffered rows, closing the current file once it is full.
+    async fn flush(&mut self) -> Result<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        let mut file = match self.file.take() {
+            Some(file) => file,
+            None => self.create_file().await?,
+        };
+
+        let content = file.encoder.encode(&self.batch).map_err(parquet_error)?;
+        write_chunk(&mut file.writer, content).await?;
+        file.row_count += self.batch.len();
+        self.row_count += self.batch.len();
+        self.batch.clear();
+
+        if file.row_count >= ROWS_PER_FILE {
+            close_file(file).await?;
+        } else {
+            self.file = Some(file);
+        }
+        Ok(())
+    }
+
+    async fn create_file(&mut self) -> Result<InventoryFile> {
+        let location = self.target_location.cloning_push(&format!(
+            "inventory-{:05}.{}",
+            self.inventory_files.len(),
+            self.format.extension()
+        ));
+        let mut writer = new_output(self.file_io, &location)?
+            .writer()
+            .await
+            .map_err(IoError::FileWriterCreation)?;
+        let (encoder, header) = InventoryEncoder::new(self.format).map_err(parquet_error)?;
+        write_chunk(&mut writer, header).await?;
+        self.inventory_files.push(location.to_string());
+        Ok(InventoryFile {
+            writer,
+            encoder,
+            row_count: 0,
+        })
+    }
+
+    async fn finish(mut self) -> Result<InventoryExportResult> {
+        self.flush().await?;
+        if let Some(file) = self.file.take() {
+            close_file(file).await?;
+        }
+        Ok(InventoryExportResult {
+            data_file_count: i64::try_from(self.row_count).unwrap_or(i64::MAX),
+            inventory_files: self.inventory_files,
+        })
+    }
+}
+
+async fn write_chunk(writer: &mut Box<dyn FileWrite>, content: Vec<u8>) -> Result<()> {
+    if content.is_empty() {
+        return Ok(());
+    }
+    FileWrite::write(&mut **writer, content.into())
+        .await
+        .map_err(|e| IoError::FileWrite(Box::new(e)).into())
+}
+
+async fn close_file(mut file: InventoryFile) -> Result<()> {
+    let footer = file.encoder.finish().map_err(parquet_error)?;
+    write_chunk(&mut file.writer, footer).await?;
+    FileWrite::close(&mut *file.writer)
+        .await
+        .map_err(|e| IoError::FileWrite(Box::new(e)).into())
+}
+
+fn parquet_error(e: parquet::errors::ParquetError) -> crate::api::IcebergErrorResponse {
+    ErrorModel::internal(
+        "Failed to write parquet inventory",
+        "ParquetWriteError",
+        Some(Box::new(e)),
+    )
+    .into()
+}
+
+/// Encodes batches of rows. Returns the bytes to append to the file for each step.
+enum InventoryEncoder {
+    Csv,
+    Parquet(Box<ArrowWriter<Vec<u8>>>),
+}
+
+impl InventoryEncoder {
+    fn new(format: InventoryFormat) -> Result<(Self, Vec<u8>), parquet::errors::ParquetError> {
+        match format {
+            InventoryFormat::Csv => {
+                Ok((Self::Csv, format!("{}\n", COLUMNS.join(",")).into_bytes()))
+            }
+            InventoryFormat::Parquet => {
+                let properties = WriterProperties::builder()
+                    .set_compression(Compression::ZSTD(ZstdLevel::default()))
+                    .build();
+                let writer = ArrowWriter::try_new(Vec::new(), parquet_schema(), Some(properties))?;
+                Ok((Self::Parquet(Box::new(writer)), Vec::new()))
+            }
+        }
+    }
+
+    fn encode(
+        &mut self,
+        rows: &[InventoryEntry],
+    ) -> Result<Vec<u8>, parquet::errors::ParquetError> {
+        match self {
+            Self::Csv => Ok(csv_rows(rows)),
+            Self::Parquet(writer) => {
+                writer.write(&record_batch(rows)?)?;
+                // Closes the row group. The writer tracks its offset itself, so the
+                // encoded bytes can be taken from the buffer.
+                writer.flush()?;
+                Ok(std::mem::take(writer.inner_mut()))
+            }
+        }
+    }
+
+    fn finish(self) -> Result<Vec<u8>, parquet::errors::ParquetError> {
+        match self {
+            Self::Csv => Ok(Vec::new()),
+            Self::Parquet(writer) => writer.into_inner(),
+        }
+    }
+}
+
+fn csv_rows(rows: &[InventoryEntry]) -> Vec<u8> {
+    let mut csv = String::new();
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            csv_field(&row.file_path),
+            row.content,
+            csv_field(&row.file_format),
+            csv_field(&row.partition),
+            row.record_count,
+            row.file_size_in_bytes,
+            row.snapshot_id.map(|id| id.to_string()).unwrap_or_default()
+        ));
+    }
+    csv.into_bytes()
+}
+
+fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\"")).into()
+    } else {
+        value.into()
+    }
+}
+
+fn parquet_schema() -> Arc<Schema> {
+    Arc::new(Schema::new(vec![
+        Field::new(COLUMNS[0], DataType::Utf8, false),
+        Field::new(COLUMNS[1], DataType::Utf8, false),
+        Field::new(COLUMNS[2], DataType::Utf8, false),
+        Field::new(COLUMNS[3], DataType::Utf8, false),
+        Field::new(COLUMNS[4], DataType::Int64, false),
+        Field::new(COLUMNS[5], DataType::Int64, false),
+        Field::new(COLUMNS[6], DataType::Int64, true),
+    ]))
+}
+
+fn record_batch(rows: &[InventoryEntry]) -> Result<RecordBatch, parquet::errors::ParquetError> {
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.file_path.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.content),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.file_format.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.partition.as_str()),
+        )),
+        Arc::new(Int64Array::from_iter_values(
+            rows.iter().map(|r| r.record_count),
+        )),
+        Arc::new(Int64Array::from_iter_values(
+            rows.iter().map(|r| r.file_size_in_bytes),
+        )),
+        Arc::new(rows.iter().map(|r| r.snapshot_id).collect::<Int64Array>()),
+    ];
+    Ok(RecordBatch::try_new(parquet_schema(), columns)?)
+}
+
+/// Content of `inventory.json`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct InventoryManifest<'a> {
+    table_id: Uuid,
+    format: InventoryFormat,
+    data_file_count: i64,
+    files: &'a [String],
+    created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TableInventoryExportInput {
+    pub export_id: Uuid,
+    pub table_id: TableIdentUuid,
+    pub warehouse_ident: WarehouseIdent,
+    pub target_location: String,
+    pub format: InventoryFormat,
+}
+
+#[derive(Debug)]
+pub struct TableInventoryExportTask {
+    pub export_id: Uuid,
+    pub table_id: TableIdentUuid,
+    pub warehouse_ident: WarehouseIdent,
+    pub target_location: String,
+    pub format: InventoryFormat,
+    pub task: Task,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryExportResult {
+    /// Number of data and delete files in the inventory
+    pub data_file_count: i64,
+    pub inventory_files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InventoryExportDetails {
+    pub export_id: Uuid,
+    pub table_id: TableIdentUuid,
+    pub target_location: String,
+    pub format: InventoryFormat,
+    pub status: InventoryExportStatus,
+    pub attempt: i32,
+    pub last_error: Option<String>,
+    /// Set once the export is done
+    pub result: Option<InventoryExportResult>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_schema() -> apache_avro::Schema {
+        apache_avro::Schema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "manifest_entry",
+                "fields": [
+                    {"name": "status", "type": "int"},
+                    {"name": "snapshot_id", "type": ["null", "long"]},
+                    {"name": "data_file", "type": {
+                        "type": "record",
+                        "name": "r2",
+                        "fields": [
+                            {"name": "content", "type": "int"},
+                            {"name": "file_path", "type": "string"},
+                            {"name": "file_format", "type": "string"},
+                            {"name": "partition", "type": {
+                                "type": "record",
+                                "name": "r102",
+                                "fields": [{"name": "day", "type": ["null", "int"]}]
+                            }},
+                            {"name": "record_count", "type": "long"},
+                            {"name": "file_size_in_bytes", "type": "long"}
+                        ]
+                    }}
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn entry(status: i32, snapshot_id: Option<i64>, path: &str) -> Value {
+        Value::Record(vec![
+            ("status".to_string(), Value::Int(status)),
+            (
+                "snapshot_id".to_string(),
+                match snapshot_id {
+                    Some(id) => Value::Union(1, Box::new(Value::Long(id))),
+                    None => Value::Union(0, Box::new(Value::Null)),
+                },
+            ),
+            (
+                "data_file".to_string(),
+                Value::Record(vec![
+                    ("content".to_string(), Value::Int(0)),
+                    ("file_path".to_string(), Value::String(path.to_string())),
+                    (
+                        "file_format".to_string(),
+                        Value::String("PARQUET".to_string()),
+                    ),
+                    (
+                        "partition".to_string(),
+                        Value::Record(vec![(
+                            "day".to_string(),
+                            Value::Union(1, Box::new(Value::Int(19_000))),
+                        )]),
+                    ),
+                    ("record_count".to_string(), Value::Long(10)),
+                    ("file_size_in_bytes".to_string(), Value::Long(1024)),
+                ]),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_manifest_entries() {
+        let mut writer = apache_avro::Writer::new(&manifest_schema(), Vec::new());
+        writer
+            .append(entry(1, None, "s3://b/t/data/1.parquet"))
+            .unwrap();
+        writer
+            .append(entry(0, Some(7), "s3://b/t/data/2.parquet"))
+            .unwrap();
+        writer
+            .append(entry(
+                ENTRY_STATUS_DELETED,
+                Some(9),
+                "s3://b/t/data/3.parquet",
+            ))
+            .unwrap();
+        let content = writer.into_inner().unwrap();
+
+        let manifest = ManifestFile {
+            path: "s3://b/t/metadata/m.avro".to_string(),
+            length: None,
+            added_snapshot_id: Some(9),
+        };
+        let entries = manifest_entries(&content, &manifest).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                InventoryEntry {
+                    file_path: "s3://b/t/data/1.parquet".to_string(),
+                    content: "data",
+                    file_format: "PARQUET".to_string(),
+                    partition: r#"{"day":19000}"#.to_string(),
+                    record_count: 10,
+                    file_size_in_bytes: 1024,
+                    snapshot_id: Some(9),
+                },
+                InventoryEntry {
+                    file_path: "s3://b/t/data/2.parquet".to_string(),
+                    content: "data",
+                    file_format: "PARQUET".to_string(),
+                    partition: r#"{"day":19000}"#.to_string(),
+                    record_count: 10,
+                    file_size_in_bytes: 1024,
+                    snapshot_id: Some(7),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_encode_inventory() {
+        let rows = vec![InventoryEntry {
+            file_path: "s3://b/t/data/a,b.parquet".to_string(),
+            content: "data",
+            file_format: "PARQUET".to_string(),
+            partition: r#"{"day":19000,"region":"eu"}"#.to_string(),
+            record_count: 10,
+            file_size_in_bytes: 1024,
+            snapshot_id: None,
+        }];
+
+        let (mut encoder, mut csv) = InventoryEncoder::new(InventoryFormat::Csv).unwrap();
+        csv.extend(encoder.encode(&rows).unwrap());
+        csv.extend(encoder.finish().unwrap());
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "file_path,content,file_format,partition,record_count,file_size_in_bytes,snapshot_id\n\
+             \"s3://b/t/data/a,b.parquet\",data,PARQUET,\"{\"\"day\"\":19000,\"\"region\"\":\"\"eu\"\"}\",10,1024,\n"
+        );
+
+        // Batches are taken from the writer as they are encoded, concatenated they
+        // form a valid file.
+        let (mut encoder, mut parquet) = InventoryEncoder::new(InventoryFormat::Parquet).unwrap();
+        parquet.extend(encoder.encode(&rows).unwrap());
+        parquet.extend(encoder.encode(&rows).unwrap());
+        parquet.extend(encoder.finish().unwrap());
+        let path = std::env::temp_dir().join(format!("inventory-{}.parquet", Uuid::now_v7()));
+        std::fs::write(&path, parquet).unwrap();
+        let reader =
+            parquet::file::reader::SerializedFileReader::new(std::fs::File::open(&path).unwrap())
+                .unwrap();
+        let metadata = parquet::file::reader::FileReader::metadata(&reader);
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        assert_eq!(metadata.num_row_groups(), 2);
+        assert_eq!(metadata.file_metadata().schema_descr().num_columns(), 7);
+        std::fs::remove_file(path).unwrap();
+    }
+}
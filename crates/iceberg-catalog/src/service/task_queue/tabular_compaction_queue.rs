@@ -0,0 +1,189 @@
+use crate::api::Result;
+use crate::service::maintenance::{MaintenanceExecutorRef, MaintenanceJob, MaintenanceJobStatus};
+use crate::service::task_queue::{Task, TaskQueue};
+use crate::service::{Catalog, ListFlags, TableIdentUuid};
+use crate::WarehouseIdent;
+use iceberg_ext::catalog::rest::ErrorModel;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tracing::Instrument;
+
+pub type TabularCompactionQueue = Arc<
+    dyn TaskQueue<Task = TabularCompactionTask, Input = TabularCompactionInput>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+/// Picks up compaction tasks and hands them to the maintenance executor.
+///
+/// Each task is tracked in its own tokio task, as jobs typically run for much longer
+/// than the poll interval of the queue. At most `max_concurrent_jobs` jobs are tracked
+/// at a time, further tasks stay in the queue until a job finishes.
+pub async fn compaction_task<C: Catalog>(
+    fetcher: TabularCompactionQueue,
+    executor: MaintenanceExecutorRef,
+    catalog_state: C::State,
+    max_concurrent_jobs: usize,
+) {
+    let semaphore = Arc::new(Semaphore::new(max_concurrent_jobs.max(1)));
+    loop {
+        tokio::time::sleep(fetcher.config().poll_interval).await;
+
+        let Ok(permit) = semaphore.clone().acquire_owned().await else {
+            tracing::error!("Compaction semaphore closed");
+            return;
+        };
+
+        let compaction = match fetcher.pick_new_task().await {
+            Ok(compaction) => compaction,
+            Err(err) => {
+                tracing::error!("Failed to fetch compaction: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(compaction) = compaction else {
+            continue;
+        };
+
+        let span = tracing::debug_span!(
+            "tabular_compaction",
+            table_id = %compaction.table_id,
+            warehouse_id = %compaction.warehouse_ident,
+            snapshot_id = %compaction.snapshot_id,
+            executor = %executor.name(),
+            queue_name = %compaction.task.queue_name,
+            task = ?compaction.task,
+        );
+
+        let fetcher = fetcher.clone();
+        let executor = executor.clone();
+        let catalog_state = catalog_state.clone();
+        tokio::task::spawn(
+            async move {
+                instrumented_compact::<C>(fetcher, executor, catalog_state, compaction).await;
+                drop(permit);
+            }
+            .instrument(span.or_current()),
+        );
+    }
+}
+
+async fn instrumented_compact<C: Catalog>(
+    fetcher: TabularCompactionQueue,
+    executor: MaintenanceExecutorRef,
+    catalog_state: C::State,
+    compaction: TabularCompactionTask,
+) {
+    match compact::<C>(&fetcher, &executor, catalog_state, &compaction).await {
+        Ok(()) => {
+            fetcher.retrying_record_success(&compaction.task).await;
+            tracing::info!("Successfully compacted table {}", compaction.table_id);
+        }
+        Err(err) => {
+            tracing::error!(
+                "Failed to compact table {}: {}",
+                compaction.table_id,
+                err.error
+            );
+            fetcher
+                .retrying_record_failure(&compaction.task, &err.error.to_string())
+                .await;
+        }
+    }
+}
+
+async fn compact<C: Catalog>(
+    fetcher: &TabularCompactionQueue,
+    executor: &MaintenanceExecutorRef,
+    catalog_state: C::State,
+    TabularCompactionTask {
+        table_id,
+        warehouse_ident,
+        snapshot_id: _,
+        reason,
+        task,
+    }: &TabularCompactionTask,
+) -> Result<()> {
+    let Some(table) = C::get_table_metadata_by_id(
+        *warehouse_ident,
+        *table_id,
+        ListFlags::default(),
+        catalog_state,
+    )
+    .await?
+    else {
+        tracing::info!("Table {table_id} does not exist anymore, skipping compaction");
+        return Ok(());
+    };
+
+    let job = MaintenanceJob {
+        task_id: task.task_id,
+        warehouse_id: *warehouse_ident,
+        table_id: *table_id,
+        namespace: table.table.namespace.inner(),
+        table_name: table.table.name,
+        location: table.location,
+        metadata_location: table.metadata_location,
+        reason: reason.clone(),
+    };
+
+    let job_id = executor.submit_compaction(&job).await?;
+    tracing::debug!("Submitted compaction job {job_id} to {}", executor.name());
+
+    let started = tokio::time::Instant::now();
+    let max_age = fetcher
+        .config()
+        .max_age
+        .to_std()
+        .unwrap_or(Duration::from_secs(3600));
+    loop {
+        match executor.job_status(&job_id).await? {
+            MaintenanceJobStatus::Succeeded => return Ok(()),
+            MaintenanceJobStatus::Failed { message } => {
+                return Err(ErrorModel::failed_dependency(
+                    format!(
+                        "Compaction job {job_id} failed: {}",
+                        message.as_deref().unwrap_or("no details provided")
+                    ),
+                    "CompactionFailed",
+                    None,
+                )
+                .into());
+            }
+            MaintenanceJobStatus::Pending | MaintenanceJobStatus::Running => {}
+        }
+
+        if started.elapsed() > max_age {
+            // Retries resubmit the job with the same task id, so the executor can deduplicate it.
+            return Err(ErrorModel::failed_dependency(
+                format!("Compaction job {job_id} did not finish within {max_age:?}"),
+                "CompactionTimeout",
+                None,
+            )
+            .into());
+        }
+        tokio::time::sleep(fetcher.config().poll_interval).await;
+    }
+}
+
+#[derive(Debug)]
+pub struct TabularCompactionTask {
+    pub table_id: TableIdentUuid,
+    pub warehouse_ident: WarehouseIdent,
+    pub snapshot_id: i64,
+    pub reason: String,
+    pub task: Task,
+}
+
+#[derive(Debug, Clone)]
+pub struct TabularCompactionInput {
+    pub table_id: TableIdentUuid,
+    pub warehouse_ident: WarehouseIdent,
+    /// Snapshot that triggered the compaction
+    pub snapshot_id: i64,
+    pub reason: String,
+}
@@ -1,7 +1,7 @@
 use crate::api::management::v1::{DeleteKind, TabularType};
 use crate::api::Result;
 use crate::service::task_queue::{Task, TaskQueue};
-use crate::service::{Catalog, TableIdentUuid, Transaction, ViewIdentUuid};
+use crate::service::{Catalog, TableIdentUuid, TabularExpirationClaim, Transaction, ViewIdentUuid};
 use crate::WarehouseIdent;
 use std::sync::Arc;
 
@@ -73,10 +73,21 @@ async fn instrumented_expire<C: Catalog, A: Authorizer>(
     expiration: &TabularExpirationTask,
 ) {
     match handle_table::<C, A>(catalog_state.clone(), authorizer, cleaner, expiration).await {
-        Ok(()) => {
+        Ok(TabularExpirationClaim::TaskNotOwned) => {
+            // Another worker owns the task now, its state must not be touched.
+            tracing::info!("Expiration task is no longer owned by this worker, skipping");
+        }
+        Ok(TabularExpirationClaim::NotDeleted) => {
+            fetcher.retrying_record_success(&expiration.task).await;
+            tracing::info!("Tabular is no longer soft-deleted, nothing to expire");
+        }
+        Ok(TabularExpirationClaim::Claimed) => {
             fetcher.retrying_record_success(&expiration.task).await;
             tracing::info!("Successfully handled table expiration");
         }
+        Ok(TabularExpirationClaim::Exempt) => {
+            tracing::info!("Tabular is exempt from expiration, postponed");
+        }
         Err(e) => {
             tracing::error!("Failed to handle table expiration: {:?}", e);
             fetcher
@@ -91,7 +102,7 @@ async fn handle_table<C, A>(
     authorizer: A,
     delete_queue: &TabularPurgeQueue,
     expiration: &TabularExpirationTask,
-) -> Result<()>
+) -> Result<TabularExpirationClaim>
 where
     C: Catalog,
     A: Authorizer,
@@ -103,6 +114,24 @@ where
             e
         })?;
 
+    // Nothing irreversible happens before the tabular is locked, so an undrop
+    // either cancels the task before this point or fails afterwards.
+    let claim =
+        C::claim_tabular_expiration(expiration.tabular_id, &expiration.task, trx.transaction())
+            .await?;
+    match claim {
+        TabularExpirationClaim::Claimed => {}
+        // Commit to keep the postponed task.
+        TabularExpirationClaim::Exempt => {
+            trx.commit().await?;
+            return Ok(claim);
+        }
+        TabularExpirationClaim::NotDeleted | TabularExpirationClaim::TaskNotOwned => {
+            trx.rollback().await?;
+            return Ok(claim);
+        }
+    }
+
     let tabular_location = match expiration.tabular_type {
         TabularType::Table => {
             let table_id = TableIdentUuid::from(expiration.tabular_id);
@@ -148,7 +177,7 @@ where
         e
     })?;
 
-    Ok(())
+    Ok(TabularExpirationClaim::Claimed)
 }
 
 #[derive(Debug)]
@@ -1,10 +1,16 @@
+use crate::api::iceberg::v1::{PageToken, PaginationQuery};
+use crate::api::management::v1::task::PurgeTaskStatus;
 use crate::api::management::v1::TabularType;
 use crate::api::Result;
-use crate::catalog::io::remove_all;
+use crate::catalog::io::{list_location, IoError};
 use crate::catalog::maybe_get_secret;
 use crate::service::task_queue::{Task, TaskQueue};
-use crate::service::{Catalog, SecretStore, Transaction};
-use crate::WarehouseIdent;
+use crate::service::{Catalog, SecretStore, TabularIdentUuid, Transaction};
+use crate::{WarehouseIdent, CONFIG};
+use async_trait::async_trait;
+use axum_prometheus::metrics::counter;
+use futures::{StreamExt, TryStreamExt};
+use iceberg::io::FileIO;
 use std::sync::Arc;
 
 use iceberg_ext::catalog::rest::ErrorModel;
@@ -13,8 +19,43 @@ use std::time::Duration;
 use tracing::Instrument;
 use uuid::Uuid;
 
-pub type TabularPurgeQueue =
-    Arc<dyn TaskQueue<Task = TabularPurgeTask, Input = TabularPurgeInput> + Send + Sync + 'static>;
+/// Objects in archive storage classes that were skipped by purges.
+const PURGE_SKIPPED_ARCHIVED_OBJECTS_TOTAL: &str =
+    "lakekeeper_purge_skipped_archived_objects_total";
+
+pub type TabularPurgeQueue = Arc<dyn PurgeQueue + Send + Sync + 'static>;
+
+/// Queue of purges. If `purge_approval_threshold` is configured, purges of locations
+/// with more objects are held back until an operator approves them.
+#[async_trait]
+pub trait PurgeQueue: TaskQueue<Task = TabularPurgeTask, Input = TabularPurgeInput> {
+    /// Hold back the purge until it is approved. The task is not picked up before.
+    async fn request_approval(&self, id: Uuid, object_count: i64) -> Result<()>;
+
+    /// Approve a purge that waits for approval. Returns `false` if the warehouse has no
+    /// pending purge with this id that waits for approval.
+    async fn approve(&self, warehouse_ident: WarehouseIdent, task_id: Uuid) -> Result<bool>;
+
+    /// List the purges of a warehouse that wait for approval, oldest first.
+    async fn list_awaiting_approval(
+        &self,
+        warehouse_ident: WarehouseIdent,
+    ) -> Result<Vec<PurgeAwaitingApproval>>;
+
+    /// Whether the warehouse has purges that are pending, running or waiting for approval.
+    async fn has_unfinished(&self, warehouse_ident: WarehouseIdent) -> Result<bool>;
+
+    /// Persist the progress of a running purge. A purge that is picked up again
+    /// after a crash continues from this progress.
+    async fn record_progress(&self, id: Uuid, progress: &PurgeProgress) -> Result<()>;
+
+    /// Get status and progress of a purge of the warehouse.
+    async fn get_purge(
+        &self,
+        warehouse_ident: WarehouseIdent,
+        task_id: Uuid,
+    ) -> Result<Option<PurgeTaskDetails>>;
+}
 
 // TODO: concurrent workers
 pub async fn purge_task<C: Catalog, S: SecretStore>(
@@ -63,13 +104,38 @@ pub async fn purge_task<C: Catalog, S: SecretStore>(
 }
 
 async fn instrumented_purge<S: SecretStore, C: Catalog>(
-    fetcher: Arc<dyn TaskQueue<Task = TabularPurgeTask, Input = TabularPurgeInput> + Send + Sync>,
+    fetcher: TabularPurgeQueue,
     catalog_state: C::State,
     secret_state: &S,
     purge_task: &TabularPurgeTask,
 ) {
-    match purge::<C, S>(purge_task, secret_state, catalog_state.clone()).await {
-        Ok(()) => {
+    match purge::<C, S>(&fetcher, purge_task, secret_state, catalog_state.clone()).await {
+        Ok(PurgeOutcome::ApprovalRequired { object_count }) => {
+            tracing::warn!(
+                "Location {} of tabular {} contains at least {object_count} objects. The purge waits for approval.",
+                purge_task.tabular_location,
+                purge_task.tabular_id,
+            );
+            let object_count = i64::try_from(object_count).unwrap_or(i64::MAX);
+            if let Err(err) = fetcher
+                .request_approval(purge_task.task.task_id, object_count)
+                .await
+            {
+                tracing::error!("Failed to request approval for purge: {}", err.error);
+                fetcher
+                    .retrying_record_failure(&purge_task.task, &err.error.to_string())
+                    .await;
+            }
+        }
+        Ok(PurgeOutcome::LocationInUse) => {
+            fetcher.retrying_record_success(&purge_task.task).await;
+            tracing::warn!(
+                "Skipped purge of tabular {}: location {} is used by another tabular",
+                purge_task.tabular_id,
+                purge_task.tabular_location
+            );
+        }
+        Ok(PurgeOutcome::Purged) => {
             fetcher.retrying_record_success(&purge_task.task).await;
             tracing::info!(
                 "Successfully cleaned up tabular {} at location {}",
@@ -90,17 +156,32 @@ async fn instrumented_purge<S: SecretStore, C: Catalog>(
     };
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PurgeOutcome {
+    Purged,
+    /// Nothing was deleted because the location contains more than `object_count` objects.
+    ApprovalRequired {
+        object_count: usize,
+    },
+    /// Nothing was deleted because another tabular uses the location, for example
+    /// one created after a soft-deleted tabular was force-expired.
+    LocationInUse,
+}
+
 async fn purge<C, S>(
+    fetcher: &TabularPurgeQueue,
     TabularPurgeTask {
         tabular_id,
         tabular_location,
         warehouse_ident,
-        tabular_type: _,
-        task: _,
+        tabular_type,
+        approved,
+        progress,
+        task,
     }: &TabularPurgeTask,
     secret_state: &S,
     catalog_state: C::State,
-) -> Result<()>
+) -> Result<PurgeOutcome>
 where
     C: Catalog,
     S: SecretStore,
@@ -119,11 +200,44 @@ where
             e
         })?;
 
+    let tabular_location = Location::parse_value(tabular_location).map_err(|e| {
+        tracing::error!(
+            "Failed delete tabular - to parse location {}: {:?}",
+            tabular_location,
+            e
+        );
+        ErrorModel::internal(
+            "Failed to parse table location of deleted tabular.",
+            "ParseError",
+            Some(Box::new(e)),
+        )
+    })?;
+    let usage = C::list_location_usage(
+        *warehouse_ident,
+        &tabular_location,
+        trx.transaction(),
+        PaginationQuery {
+            page_token: PageToken::Empty,
+            page_size: Some(2),
+        },
+    )
+    .await?;
+    // The expiration queues the purge before it commits, the tabular may still be listed.
+    let own_id = match tabular_type {
+        TabularType::Table => TabularIdentUuid::Table(*tabular_id),
+        TabularType::View => TabularIdentUuid::View(*tabular_id),
+    };
+    let location_in_use = usage.len() > usize::from(usage.get(&own_id).is_some());
+
     trx.commit().await.map_err(|e| {
         tracing::error!("Failed to commit transaction: {:?}", e);
         e
     })?;
 
+    if location_in_use {
+        return Ok(PurgeOutcome::LocationInUse);
+    }
+
     let secret = maybe_get_secret(warehouse.storage_secret_id, secret_state)
         .await
         .map_err(|e| {
@@ -139,19 +253,24 @@ where
             e
         })?;
 
-    let tabular_location = Location::parse_value(tabular_location).map_err(|e| {
-        tracing::error!(
-            "Failed delete tabular - to parse location {}: {:?}",
-            tabular_location,
-            e
-        );
-        ErrorModel::internal(
-            "Failed to parse table location of deleted tabular.",
-            "ParseError",
-            Some(Box::new(e)),
-        )
-    })?;
-    remove_all(&file_io, &tabular_location).await.map_err(|e| {
+    // A purge that already deleted files was approved or below the threshold.
+    let needs_check = !approved && progress.deleted_count == 0;
+    if let Some(threshold) = CONFIG.purge_approval_threshold.filter(|_| needs_check) {
+        let object_count = count_objects(&file_io, &tabular_location, threshold)
+            .await
+            .map_err(|e| {
+                ErrorModel::internal(
+                    "Failed to count objects in location.",
+                    "FileIOError",
+                    Some(Box::new(e)),
+                )
+            })?;
+        if object_count > threshold {
+            return Ok(PurgeOutcome::ApprovalRequired { object_count });
+        }
+    }
+
+    let purge_error = |e: IoError| {
         tracing::error!(
             ?e,
             "Failed to purge '{tabular_id}' at location: '{tabular_location}'",
@@ -161,9 +280,64 @@ where
             "FileIOError",
             Some(Box::new(e)),
         )
-    })?;
+    };
+
+    let resume_after = progress
+        .checkpoint
+        .as_deref()
+        .and_then(|checkpoint| Location::parse_value(checkpoint).ok());
+    if let Some(resume_after) = &resume_after {
+        tracing::info!(
+            "Resuming purge of '{tabular_id}' after '{resume_after}', {} objects deleted so far",
+            progress.deleted_count
+        );
+    }
+
+    let mut progress = progress.clone();
+    let mut pages = warehouse
+        .storage_profile
+        .purge_location(&file_io, secret.as_ref(), &tabular_location, resume_after)
+        .await
+        .map_err(purge_error)?;
+    while let Some(page) = pages.try_next().await.map_err(purge_error)? {
+        if !page.skipped_archived.is_empty() {
+            counter!(PURGE_SKIPPED_ARCHIVED_OBJECTS_TOTAL)
+                .increment(page.skipped_archived.len() as u64);
+            tracing::warn!(
+                skipped = ?page.skipped_archived,
+                "Skipped {} archived objects while purging '{tabular_id}' at location '{tabular_location}'. They must be removed manually.",
+                page.skipped_archived.len(),
+            );
+        }
 
-    Ok(())
+        progress.deleted_count += i64::try_from(page.deleted).unwrap_or(i64::MAX);
+        if let Some(checkpoint) = page.checkpoint {
+            progress.checkpoint = Some(checkpoint.to_string());
+        }
+        // Losing progress only means that a restarted purge lists more objects again.
+        if let Err(err) = fetcher.record_progress(task.task_id, &progress).await {
+            tracing::warn!("Failed to record progress of purge: {}", err.error);
+        }
+    }
+
+    Ok(PurgeOutcome::Purged)
+}
+
+/// Count the objects below `location`. Stops as soon as more than `limit` objects are found.
+async fn count_objects(
+    file_io: &FileIO,
+    location: &Location,
+    limit: usize,
+) -> std::result::Result<usize, IoError> {
+    let mut pages = list_location(file_io, location, None).await?;
+    let mut count = 0;
+    while let Some(page) = pages.next().await {
+        count += page?.len();
+        if count > limit {
+            break;
+        }
+    }
+    Ok(count)
 }
 
 #[derive(Debug)]
@@ -172,9 +346,45 @@ pub struct TabularPurgeTask {
     pub tabular_location: String,
     pub warehouse_ident: WarehouseIdent,
     pub tabular_type: TabularType,
+    /// The purge was approved by an operator and skips the object count check.
+    pub approved: bool,
+    /// Progress of previous attempts
+    pub progress: PurgeProgress,
     pub task: Task,
 }
 
+/// Progress of a purge, recorded after every listing page.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PurgeProgress {
+    /// Number of objects deleted so far, summed over all attempts
+    pub deleted_count: i64,
+    /// Last object of the most recently handled listing page
+    pub checkpoint: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurgeTaskDetails {
+    pub task_id: Uuid,
+    pub tabular_id: Uuid,
+    pub tabular_type: TabularType,
+    pub tabular_location: String,
+    pub status: PurgeTaskStatus,
+    pub attempt: i32,
+    pub progress: PurgeProgress,
+    pub progress_updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PurgeAwaitingApproval {
+    pub task_id: Uuid,
+    pub tabular_id: Uuid,
+    pub tabular_type: TabularType,
+    pub tabular_location: String,
+    /// Number of objects found before counting stopped
+    pub object_count: i64,
+    pub approval_requested_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(Debug, Clone)]
 pub struct TabularPurgeInput {
     pub tabular_id: Uuid,
@@ -0,0 +1,664 @@
+use crate::api::Result;
+use crate::catalog::compression_codec::CompressionCodec;
+use crate::catalog::io::{copy_file, list_location, remove_all, write_file};
+use crate::catalog::manifest_cache::read_manifest;
+use crate::catalog::maybe_get_secret;
+use crate::catalog::tables::extract_count_from_metadata_location;
+use crate::service::storage::{path_utils, StorageLocations as _};
+use crate::service::task_queue::{Task, TaskQueue};
+use crate::service::{Catalog, SecretStore, TableIdentUuid, TableRelocation, Transaction};
+use crate::{WarehouseIdent, CONFIG};
+use apache_avro::types::Value;
+use futures::{StreamExt, TryStreamExt};
+use iceberg::spec::TableMetadata;
+use iceberg_ext::catalog::rest::ErrorModel;
+use iceberg_ext::configs::{Location, ParseFromStr};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::Instrument;
+use uuid::Uuid;
+
+pub type TabularRelocationQueue = Arc<
+    dyn TaskQueue<Task = TabularRelocationTask, Input = TabularRelocationInput>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+// TODO: concurrent workers
+pub async fn relocation_task<C: Catalog, S: SecretStore>(
+    fetcher: TabularRelocationQueue,
+    catalog_state: C::State,
+    secret_state: S,
+) {
+    loop {
+        tokio::time::sleep(fetcher.config().poll_interval + Duration::from_millis(19)).await;
+
+        let relocation = match fetcher.pick_new_task().await {
+            Ok(relocation) => relocation,
+            Err(err) => {
+                tracing::error!("Failed to fetch relocation: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let Some(relocation) = relocation else {
+            continue;
+        };
+
+        let span = tracing::debug_span!(
+            "tabular_relocation",
+            table_id = %relocation.table_id,
+            warehouse_id = %relocation.warehouse_ident,
+            source_location = %relocation.source_location,
+            target_location = %relocation.target_location,
+            queue_name = %relocation.task.queue_name,
+            task = ?relocation.task,
+        );
+
+        instrumented_relocate::<C, S>(
+            fetcher.clone(),
+            catalog_state.clone(),
+            &secret_state,
+            &relocation,
+        )
+        .instrument(span.or_current())
+        .await;
+    }
+}
+
+async fn instrumented_relocate<C: Catalog, S: SecretStore>(
+    fetcher: TabularRelocationQueue,
+    catalog_state: C::State,
+    secret_state: &S,
+    relocation: &TabularRelocationTask,
+) {
+    match relocate::<C, S>(relocation, secret_state, catalog_state).await {
+        Ok(()) => {
+            fetcher.retrying_record_success(&relocation.task).await;
+            tracing::info!(
+                "Successfully relocated table {} from {} to {}",
+                relocation.table_id,
+                relocation.source_location,
+                relocation.target_location
+            );
+        }
+        Err(err) => {
+            tracing::error!(
+                "Failed to relocate table {}: {}",
+                relocation.table_id,
+                err.error
+            );
+            fetcher
+                .retrying_record_failure(&relocation.task, &err.error.to_string())
+                .await;
+        }
+    }
+}
+
+/// Move all files of a table to a new location.
+///
+/// 1. All files below the source location are copied to the target location.
+///    Manifest lists and manifests are rewritten to point to the new paths.
+/// 2. A new metadata file is written to the target location.
+/// 3. The table is switched to the target location in a single transaction.
+///    If the table was committed to in the meantime, the task fails and is retried.
+/// 4. The source location is purged.
+///
+/// If the table already points to the target location (i.e. a previous attempt failed
+/// while purging), only the purge is repeated.
+async fn relocate<C, S>(
+    TabularRelocationTask {
+        table_id,
+        warehouse_ident,
+        source_location,
+        target_location,
+        task: _,
+    }: &TabularRelocationTask,
+    secret_state: &S,
+    catalog_state: C::State,
+) -> Result<()>
+where
+    C: Catalog,
+    S: SecretStore,
+{
+    let source_location = parse_task_location(source_location)?;
+    let target_location = parse_task_location(target_location)?;
+
+    let mut t = C::Transaction::begin_read(catalog_state.clone()).await?;
+    let mut tables = C::load_tables(*warehouse_ident, [*table_id], false, t.transaction()).await?;
+    t.commit().await?;
+    let table = tables.remove(table_id).ok_or_else(|| {
+        ErrorModel::not_found(
+            format!("Table {table_id} to relocate does not exist anymore"),
+            "TableNotFound",
+            None,
+        )
+    })?;
+
+    let secret = maybe_get_secret(table.storage_secret_ident, secret_state).await?;
+    let file_io = table.storage_profile.file_io(secret.as_ref())?;
+
+    let current_location = table.table_metadata.location().trim_end_matches('/');
+    if current_location == target_location.as_str().trim_end_matches('/') {
+        tracing::info!("Table {table_id} already relocated, purging source location");
+        return purge_source(&file_io, &source_location).await;
+    }
+    if current_location != source_location.as_str().trim_end_matches('/') {
+        return Err(ErrorModel::conflict(
+            format!(
+                "Table {table_id} is located at {current_location}, expected {source_location}"
+            ),
+            "TableLocationChanged",
+            None,
+        )
+        .into());
+    }
+
+    let rewriter = PathRewriter::new(&source_location, &target_location);
+    let parallelism = CONFIG.table_relocation_parallelism.max(1);
+
+    // ------------------- Manifests & Manifest Lists -------------------
+    let manifest_lists = table
+        .table_metadata
+        .snapshots()
+        .map(|s| s.manifest_list().to_string())
+        .collect::<HashSet<_>>();
+
+    let mut manifest_list_contents = HashMap::with_capacity(manifest_lists.len());
+    let mut manifests = HashMap::new();
+    for manifest_list in manifest_lists {
+        let content = read_manifest(
+            *warehouse_ident,
+            &file_io,
+            &parse_file_location(&manifest_list)?,
+            None,
+        )
+        .await?;
+        manifests.extend(list_manifests(&content, &manifest_list)?);
+        manifest_list_contents.insert(manifest_list, content);
+    }
+
+    let manifest_lengths = futures::stream::iter(manifests.iter())
+        .map(|(manifest, length)| {
+            let file_io = &file_io;
+            let rewriter = &rewriter;
+            async move {
+                let content = read_manifest(
+                    *warehouse_ident,
+                    file_io,
+                    &parse_file_location(manifest)?,
+                    *length,
+                )
+                .await?;
+                let rewritten = rewrite_avro(&content, rewriter, &HashMap::new())
+                    .map_err(|e| avro_error(manifest, e))?;
+                let length = i64::try_from(rewritten.len()).unwrap_or(i64::MAX);
+                let target = rewriter.require_rewrite(manifest)?;
+                write_file(file_io, &target, rewritten).await?;
+                // Keyed by the rewritten path as it appears in the rewritten manifest list
+                let key = rewriter.rewrite(manifest).unwrap_or_default();
+                Result::<_>::Ok((key, length))
+            }
+        })
+        .buffer_unordered(parallelism)
+        .try_collect::<HashMap<_, _>>()
+        .await?;
+
+    for (manifest_list, content) in &manifest_list_contents {
+        let rewritten = rewrite_avro(content, &rewriter, &manifest_lengths)
+            .map_err(|e| avro_error(manifest_list, e))?;
+        write_file(
+            &file_io,
+            &rewriter.require_rewrite(manifest_list)?,
+            rewritten,
+        )
+        .await?;
+    }
+
+    // ------------------- Remaining Files -------------------
+    let rewritten_files = manifests
+        .keys()
+        .chain(manifest_list_contents.keys())
+        .filter_map(|path| rewriter.rewrite(path))
+        .collect::<HashSet<_>>();
+
+    let mut listing = list_location(&file_io, &source_location, None).await?;
+    while let Some(page) = listing.next().await {
+        let files = page?
+            .into_iter()
+            .filter(|path| !path.ends_with('/'))
+            .filter(|path| {
+                rewriter
+                    .rewrite(path)
+                    .map_or(true, |target| !rewritten_files.contains(&target))
+            })
+            .map(|path| {
+                let target = rewriter.require_rewrite(&path)?;
+                Result::<_>::Ok((path, target))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        futures::stream::iter(files)
+            .map(|(source, target)| {
+                let file_io = &file_io;
+                async move {
+                    copy_file(file_io, &parse_file_location(&source)?, &target).await?;
+                    Result::<_>::Ok(())
+                }
+            })
+            .buffer_unordered(parallelism)
+            .try_collect::<()>()
+            .await?;
+    }
+
+    // ------------------- Metadata -------------------
+    let new_metadata = rewrite_table_metadata(&table.table_metadata, &rewriter)?;
+    let compression_codec = CompressionCodec::try_from_metadata(&new_metadata)?;
+    let next_metadata_count = table
+        .metadata_location
+        .as_ref()
+        .and_then(|location| {
+            extract_count_from_metadata_location(location, table.storage_profile.metadata_naming())
+        })
+        .map_or(0, |v| v + 1);
+    let new_metadata_location = table.storage_profile.default_metadata_location(
+        &target_location,
+        &compression_codec,
+        Uuid::now_v7(),
+        next_metadata_count,
+    );
+    table
+        .storage_profile
+        .write_metadata_file(
+            &file_io,
+            secret.as_ref(),
+            Some(*warehouse_ident),
+            &new_metadata_location,
+            &new_metadata,
+            compression_codec,
+        )
+        .await?;
+
+    // ------------------- Switch -------------------
+    let mut t = C::Transaction::begin_write(catalog_state).await?;
+    C::relocate_table(
+        &TableRelocation {
+            table_id: *table_id,
+            source_location: source_location.clone(),
+            target_location,
+            previous_metadata_location: table.metadata_location,
+            new_metadata_location,
+        },
+        t.transaction(),
+    )
+    .await?;
+    t.commit().await?;
+
+    purge_source(&file_io, &source_location).await
+}
+
+async fn purge_source(file_io: &iceberg::io::FileIO, source_location: &Location) -> Result<()> {
+    remove_all(file_io, source_location).await.map_err(|e| {
+        tracing::error!(?e, "Failed to purge source location '{source_location}'");
+        ErrorModel::internal(
+            "Failed to remove source location.",
+            "FileIOError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn parse_task_location(location: &str) -> Result<Location> {
+    Location::from_str(location).map_err(|e| {
+        ErrorModel::internal(
+            format!("Failed to parse location {location} of relocation task"),
+            "ParseError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn parse_file_location(location: &str) -> Result<Location> {
+    Location::parse_value(location).map_err(|e| {
+        ErrorModel::internal(
+            format!("Failed to parse file location {location}"),
+            "ParseError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+fn avro_error(file: &str, e: apache_avro::Error) -> crate::api::IcebergErrorResponse {
+    ErrorModel::internal(
+        format!("Failed to rewrite avro file {file}"),
+        "AvroRewriteError",
+        Some(Box::new(e)),
+    )
+    .into()
+}
+
+/// Replaces the source location prefix of paths with the target location.
+#[derive(Debug)]
+struct PathRewriter {
+    source: String,
+    /// Source location as returned by listings (i.e. without the `abfss` account part)
+    reduced_source: String,
+    target: String,
+}
+
+impl PathRewriter {
+    fn new(source: &Location, target: &Location) -> Self {
+        let source = source.as_str().trim_end_matches('/').to_string();
+        Self {
+            reduced_source: path_utils::reduce_scheme_string(&source, false),
+            source,
+            target: target.as_str().trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn rewrite(&self, path: &str) -> Option<String> {
+        let rest = path
+            .strip_prefix(&self.source)
+            .or_else(|| path.strip_prefix(&self.reduced_source))?;
+        (rest.is_empty() || rest.starts_with('/')).then(|| format!("{}{rest}", self.target))
+    }
+
+    fn require_rewrite(&self, path: &str) -> Result<Location> {
+        let rewritten = self.rewrite(path).ok_or_else(|| {
+            ErrorModel::bad_request(
+                format!(
+                    "File {path} is not stored below the table location {}. Only tables whose files are all stored below the table location can be relocated.",
+                    self.source
+                ),
+                "FileOutsideTableLocation",
+                None,
+            )
+        })?;
+        parse_file_location(&rewritten)
+    }
+
+    fn rewrite_json(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(s) => {
+                if let Some(rewritten) = self.rewrite(s) {
+                    *s = rewritten;
+                }
+            }
+            serde_json::Value::Array(items) => {
+                items.iter_mut().for_each(|v| self.rewrite_json(v));
+            }
+            serde_json::Value::Object(map) => {
+                map.values_mut().for_each(|v| self.rewrite_json(v));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn rewrite_table_metadata(
+    metadata: &TableMetadata,
+    rewriter: &PathRewriter,
+) -> Result<TableMetadata> {
+    let mut value = serde_json::to_value(metadata).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to serialize table metadata",
+            "SerializationError",
+            Some(Box::new(e)),
+        )
+    })?;
+    rewriter.rewrite_json(&mut value);
+    serde_json::from_value(value).map_err(|e| {
+        ErrorModel::internal(
+            "Failed to deserialize rewritten table metadata",
+            "SerializationError",
+            Some(Box::new(e)),
+        )
+        .into()
+    })
+}
+
+/// Paths of all manifests referenced by a manifest list.
+/// Fails for manifest lists that reference delete manifests, as position delete files
+/// contain absolute paths of data files which we cannot rewrite.
+/// Paths of the manifests in a manifest list with their length in bytes.
+fn list_manifests(manifest_list: &[u8], file: &str) -> Result<Vec<(String, Option<u64>)>> {
+    let reader = apache_avro::Reader::new(manifest_list).map_err(|e| avro_error(file, e))?;
+    let mut manifests = Vec::new();
+    for entry in reader {
+        let Value::Record(fields) = entry.map_err(|e| avro_error(file, e))? else {
+            continue;
+        };
+        let mut path = None;
+        let mut length = None;
+        for (name, value) in fields {
+            match (name.as_str(), value) {
+                ("manifest_path", Value::String(p)) => path = Some(p),
+                ("manifest_length", Value::Long(l)) => length = u64::try_from(l).ok(),
+                ("content", Value::Int(content)) if content != 0 => {
+                    return Err(ErrorModel::bad_request(
+                        "Tables with delete files cannot be relocated",
+                        "RelocationNotSupported",
+                        None,
+                    )
+                    .into());
+                }
+                _ => {}
+            }
+        }
+        if let Some(path) = path {
+            manifests.push((path, length));
+        }
+    }
+    Ok(manifests)
+}
+
+/// Rewrite all paths in an avro file (manifest or manifest list).
+/// `manifest_lengths` maps rewritten manifest paths to their new size in bytes.
+fn rewrite_avro(
+    content: &[u8],
+    rewriter: &PathRewriter,
+    manifest_lengths: &HashMap<String, i64>,
+) -> Result<Vec<u8>, apache_avro::Error> {
+    let reader = apache_avro::Reader::new(content)?;
+    let schema = reader.writer_schema().clone();
+    let user_metadata = reader.user_metadata().clone();
+
+    let mut writer =
+        apache_avro::Writer::with_codec(&schema, Vec::new(), apache_avro::Codec::Deflate);
+    for (key, value) in user_metadata {
+        writer.add_user_metadata(key, value)?;
+    }
+    for value in reader {
+        let mut value = value?;
+        rewrite_avro_value(&mut value, rewriter, manifest_lengths);
+        writer.append(value)?;
+    }
+    writer.into_inner()
+}
+
+fn rewrite_avro_value(
+    value: &mut Value,
+    rewriter: &PathRewriter,
+    manifest_lengths: &HashMap<String, i64>,
+) {
+    match value {
+        Value::String(s) => {
+            if let Some(rewritten) = rewriter.rewrite(s) {
+                *s = rewritten;
+            }
+        }
+        Value::Record(fields) => {
+            fields
+                .iter_mut()
+                .for_each(|(_, v)| rewrite_avro_value(v, rewriter, manifest_lengths));
+            let new_length = fields.iter().find_map(|(name, v)| match v {
+                Value::String(path) if name == "manifest_path" => {
+                    manifest_lengths.get(path).copied()
+                }
+                _ => None,
+            });
+            if let Some(new_length) = new_length {
+                if let Some((_, length)) = fields.iter_mut().find(|(n, _)| n == "manifest_length") {
+                    *length = Value::Long(new_length);
+                }
+            }
+        }
+        Value::Union(_, inner) => rewrite_avro_value(inner, rewriter, manifest_lengths),
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|v| rewrite_avro_value(v, rewriter, manifest_lengths)),
+        Value::Map(map) => map
+            .values_mut()
+            .for_each(|v| rewrite_avro_value(v, rewriter, manifest_lengths)),
+        _ => {}
+    }
+}
+
+#[derive(Debug)]
+pub struct TabularRelocationTask {
+    pub table_id: TableIdentUuid,
+    pub warehouse_ident: WarehouseIdent,
+    pub source_location: String,
+    pub target_location: String,
+    pub task: Task,
+}
+
+#[derive(Debug, Clone)]
+pub struct TabularRelocationInput {
+    pub table_id: TableIdentUuid,
+    pub warehouse_ident: WarehouseIdent,
+    pub source_location: String,
+    pub target_location: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rewriter() -> PathRewriter {
+        PathRewriter::new(
+            &Location::from_str("s3://old-bucket/wh/table").unwrap(),
+            &Location::from_str("s3://new-bucket/wh/table/").unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_path_rewriter() {
+        let rewriter = rewriter();
+        assert_eq!(
+            rewriter.rewrite("s3://old-bucket/wh/table/data/1.parquet"),
+            Some("s3://new-bucket/wh/table/data/1.parquet".to_string())
+        );
+        assert_eq!(
+            rewriter.rewrite("s3://old-bucket/wh/table"),
+            Some("s3://new-bucket/wh/table".to_string())
+        );
+        assert_eq!(rewriter.rewrite("s3://old-bucket/wh/table-2/x"), None);
+        assert_eq!(rewriter.rewrite("s3://other-bucket/wh/table/x"), None);
+    }
+
+    #[test]
+    fn test_rewrite_manifest_list() {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "manifest_file",
+                "fields": [
+                    {"name": "manifest_path", "type": "string"},
+                    {"name": "manifest_length", "type": "long"},
+                    {"name": "content", "type": "int"},
+                    {"name": "key_metadata", "type": ["null", "string"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, Vec::new());
+        writer
+            .add_user_metadata("format-version".to_string(), "2")
+            .unwrap();
+        writer
+            .append(Value::Record(vec![
+                (
+                    "manifest_path".to_string(),
+                    Value::String("s3://old-bucket/wh/table/metadata/m1.avro".to_string()),
+                ),
+                ("manifest_length".to_string(), Value::Long(100)),
+                ("content".to_string(), Value::Int(0)),
+                (
+                    "key_metadata".to_string(),
+                    Value::Union(0, Box::new(Value::Null)),
+                ),
+            ]))
+            .unwrap();
+        let content = writer.into_inner().unwrap();
+
+        assert_eq!(
+            list_manifests(&content, "m.avro").unwrap(),
+            vec![(
+                "s3://old-bucket/wh/table/metadata/m1.avro".to_string(),
+                Some(100)
+            )]
+        );
+
+        let lengths =
+            HashMap::from_iter([("s3://new-bucket/wh/table/metadata/m1.avro".to_string(), 120)]);
+        let rewritten = rewrite_avro(&content, &rewriter(), &lengths).unwrap();
+
+        let reader = apache_avro::Reader::new(&rewritten[..]).unwrap();
+        assert_eq!(
+            reader.user_metadata().get("format-version"),
+            Some(&b"2".to_vec())
+        );
+        let records = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(
+            records,
+            vec![Value::Record(vec![
+                (
+                    "manifest_path".to_string(),
+                    Value::String("s3://new-bucket/wh/table/metadata/m1.avro".to_string()),
+                ),
+                ("manifest_length".to_string(), Value::Long(120)),
+                ("content".to_string(), Value::Int(0)),
+                (
+                    "key_metadata".to_string(),
+                    Value::Union(0, Box::new(Value::Null)),
+                ),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_list_manifests_rejects_delete_manifests() {
+        let schema = apache_avro::Schema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "manifest_file",
+                "fields": [
+                    {"name": "manifest_path", "type": "string"},
+                    {"name": "content", "type": "int"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let mut writer = apache_avro::Writer::new(&schema, Vec::new());
+        writer
+            .append(Value::Record(vec![
+                (
+                    "manifest_path".to_string(),
+                    Value::String("s3://old-bucket/wh/table/metadata/d1.avro".to_string()),
+                ),
+                ("content".to_string(), Value::Int(1)),
+            ]))
+            .unwrap();
+        let content = writer.into_inner().unwrap();
+
+        assert!(list_manifests(&content, "m.avro").is_err());
+    }
+}
@@ -0,0 +1,458 @@
+//! Contract verifiers implemented as WebAssembly modules.
+//!
+//! Platform teams can enforce their own rules (naming conventions, partitioning
+//! rules, ...) by compiling them to WebAssembly instead of patching Lakekeeper.
+//! A module must not import anything and must export:
+//!
+//! - `memory`: the linear memory used to exchange data.
+//! - `alloc(len: i32) -> i32`: returns a pointer to `len` bytes the catalog may write to.
+//! - `check(ptr: i32, len: i32) -> i64`: receives a JSON encoded hook event, i.e.
+//!   `{"hook": "table-create", "table": {...}, "metadata": {...}}`. Returns 0 to allow
+//!   the change. Any other value rejects it: the upper 32 bits are a pointer to and the
+//!   lower 32 bits the length of a UTF-8 message explaining the rejection.
+//!
+//! Every check runs in a fresh instance, so modules cannot keep state between checks.
+//! Instances are limited in the number of instructions (fuel) and memory they may use.
+//! A module that traps or exceeds its limits fails the request.
+//!
+//! Modules are placed in a hook directory and referenced by warehouses by their
+//! file name without extension. See [`WasmHookRegistry`].
+#![allow(clippy::module_name_repetitions)]
+use crate::service::contract_verification::{
+    ContractVerification, ContractVerificationOutcome, WarehouseContractVerifiers,
+};
+use crate::service::TabularIdentUuid;
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use iceberg::spec::{TableMetadata, ViewMetadata};
+use iceberg::{TableIdent, TableUpdate};
+use iceberg_ext::catalog::rest::{ErrorModel, ViewUpdate};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use wasmtime::{Config, Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Rejection messages are truncated to this length.
+const MAX_MESSAGE_LENGTH: usize = 4096;
+
+#[derive(Debug, Clone, Copy)]
+pub struct WasmHookLimits {
+    /// Number of instructions a single check may execute (roughly).
+    pub fuel: u64,
+    /// Maximum size of the linear memory of a module in bytes.
+    pub memory_bytes: usize,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "hook", rename_all = "kebab-case")]
+enum HookEvent<'a> {
+    TableCreate {
+        table: &'a TableIdent,
+        metadata: &'a TableMetadata,
+    },
+    TableCommit {
+        updates: &'a [TableUpdate],
+        metadata: &'a TableMetadata,
+    },
+    ViewCommit {
+        updates: &'a [ViewUpdate],
+        metadata: &'a ViewMetadata,
+    },
+    Drop {
+        tabular: TabularIdentUuid,
+    },
+    Rename {
+        source: TabularIdentUuid,
+        destination: &'a TableIdent,
+    },
+}
+
+#[derive(Clone)]
+pub struct WasmContractVerifier {
+    name: String,
+    engine: Engine,
+    module: Module,
+    limits: WasmHookLimits,
+}
+
+impl std::fmt::Debug for WasmContractVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmContractVerifier")
+            .field("name", &self.name)
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl WasmContractVerifier {
+    /// Compile a module from its binary (or text) representation.
+    ///
+    /// # Errors
+    /// Fails if the module cannot be compiled, imports anything or
+    /// does not export the expected functions.
+    pub fn new(
+        name: impl Into<String>,
+        wasm: &[u8],
+        limits: WasmHookLimits,
+    ) -> anyhow::Result<Self> {
+        let name = name.into();
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, wasm)
+            .with_context(|| format!("Failed to compile WASM hook '{name}'"))?;
+
+        if let Some(import) = module.imports().next() {
+            return Err(anyhow!(
+                "WASM hook '{name}' imports '{}::{}'. Hooks must not import anything.",
+                import.module(),
+                import.name()
+            ));
+        }
+        for export in ["memory", "alloc", "check"] {
+            if module.get_export(export).is_none() {
+                return Err(anyhow!("WASM hook '{name}' does not export '{export}'"));
+            }
+        }
+
+        Ok(Self {
+            name,
+            engine,
+            module,
+            limits,
+        })
+    }
+
+    /// Compile the module stored at `path`.
+    ///
+    /// # Errors
+    /// Fails if the file cannot be read or [`WasmContractVerifier::new`] fails.
+    pub fn from_file(path: &Path, limits: WasmHookLimits) -> anyhow::Result<Self> {
+        let wasm = std::fs::read(path)
+            .with_context(|| format!("Failed to read WASM hook {}", path.display()))?;
+        Self::new(path.display().to_string(), &wasm, limits)
+    }
+
+    async fn check(
+        &self,
+        event: &HookEvent<'_>,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        let input = serde_json::to_vec(event).map_err(|e| {
+            ErrorModel::internal(
+                "Failed to serialize WASM hook event",
+                "WasmHookFailed",
+                Some(Box::new(e)),
+            )
+        })?;
+        let verifier = self.clone();
+        // Modules run synchronously until they return or run out of fuel.
+        let result = tokio::task::spawn_blocking(move || verifier.run(&input))
+            .await
+            .map_err(|e| {
+                ErrorModel::internal("WASM hook panicked", "WasmHookFailed", Some(Box::new(e)))
+            })?;
+
+        match result {
+            Ok(None) => Ok(ContractVerificationOutcome::Clear {}),
+            Ok(Some(message)) => Ok(ContractVerificationOutcome::Violation {
+                error_model: ErrorModel::conflict(message, "ContractViolation", None),
+            }),
+            Err(e) => {
+                tracing::warn!("WASM hook '{}' failed: {e:#}", self.name);
+                Err(ErrorModel::internal(
+                    format!("WASM hook '{}' failed", self.name),
+                    "WasmHookFailed",
+                    Some(e.into()),
+                ))
+            }
+        }
+    }
+
+    /// Returns the rejection message, if the module rejected the event.
+    fn run(&self, input: &[u8]) -> anyhow::Result<Option<String>> {
+        let mut store = Store::new(
+            &self.engine,
+            StoreLimitsBuilder::new()
+                .memory_size(self.limits.memory_bytes)
+                .instances(1)
+                .build(),
+        );
+        store.limiter(|limits: &mut StoreLimits| limits);
+        store.set_fuel(self.limits.fuel)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| anyhow!("`memory` is not a memory"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+        let check = instance.get_typed_func::<(i32, i32), i64>(&mut store, "check")?;
+
+        let len = i32::try_from(input.len()).context("Hook event too large")?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, usize::try_from(ptr)?, input)?;
+
+        let result = u64::from_ne_bytes(check.call(&mut store, (ptr, len))?.to_ne_bytes());
+        if result == 0 {
+            return Ok(None);
+        }
+        let message_ptr = usize::try_from(result >> 32)?;
+        let message_len = usize::try_from(result & 0xffff_ffff)?.min(MAX_MESSAGE_LENGTH);
+        let mut message = vec![0; message_len];
+        memory.read(&store, message_ptr, &mut message)?;
+        Ok(Some(String::from_utf8_lossy(&message).into_owned()))
+    }
+}
+
+/// Hooks of a hook directory, compiled on first use.
+///
+/// A warehouse referencing the hook `naming` runs the module `<dir>/naming.wasm`
+/// (or `<dir>/naming.wat`). Compiled modules are cached until the server restarts.
+#[derive(Debug)]
+pub struct WasmHookRegistry {
+    dir: PathBuf,
+    limits: WasmHookLimits,
+    modules: moka::sync::Cache<String, Arc<WasmContractVerifier>>,
+}
+
+impl WasmHookRegistry {
+    #[must_use]
+    pub fn new(dir: PathBuf, limits: WasmHookLimits) -> Self {
+        Self {
+            dir,
+            limits,
+            modules: moka::sync::Cache::new(1000),
+        }
+    }
+
+    fn module_path(&self, name: &str) -> Option<PathBuf> {
+        // Names must not escape the hook directory.
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return None;
+        }
+        ["wasm", "wat"]
+            .into_iter()
+            .map(|extension| self.dir.join(format!("{name}.{extension}")))
+            .find(|path| path.is_file())
+    }
+}
+
+impl WarehouseContractVerifiers for WasmHookRegistry {
+    fn resolve(
+        &self,
+        name: &str,
+    ) -> Result<Option<Arc<dyn ContractVerification + Sync + Send>>, ErrorModel> {
+        if let Some(verifier) = self.modules.get(name) {
+            return Ok(Some(verifier));
+        }
+        let Some(path) = self.module_path(name) else {
+            return Ok(None);
+        };
+        let verifier = Arc::new(WasmContractVerifier::from_file(&path, self.limits).map_err(
+            |e| {
+                tracing::warn!("Failed to load WASM hook '{name}': {e:#}");
+                ErrorModel::internal(
+                    format!("Failed to load WASM hook '{name}'"),
+                    "WasmHookFailed",
+                    Some(e.into()),
+                )
+            },
+        )?);
+        tracing::info!("Loaded WASM hook '{name}' from {}", path.display());
+        self.modules.insert(name.to_string(), verifier.clone());
+        Ok(Some(verifier))
+    }
+}
+
+#[async_trait]
+impl ContractVerification for WasmContractVerifier {
+    fn name(&self) -> &'static str {
+        "WasmContractVerifier"
+    }
+
+    async fn check_table_updates(
+        &self,
+        table_updates: &[TableUpdate],
+        current_metadata: &TableMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.check(&HookEvent::TableCommit {
+            updates: table_updates,
+            metadata: current_metadata,
+        })
+        .await
+    }
+
+    async fn check_view_updates(
+        &self,
+        view_updates: &[ViewUpdate],
+        current_metadata: &ViewMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.check(&HookEvent::ViewCommit {
+            updates: view_updates,
+            metadata: current_metadata,
+        })
+        .await
+    }
+
+    async fn check_table_create(
+        &self,
+        table: &TableIdent,
+        metadata: &TableMetadata,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.check(&HookEvent::TableCreate { table, metadata })
+            .await
+    }
+
+    async fn check_drop(
+        &self,
+        table_ident_uuid: TabularIdentUuid,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.check(&HookEvent::Drop {
+            tabular: table_ident_uuid,
+        })
+        .await
+    }
+
+    async fn check_rename(
+        &self,
+        source: TabularIdentUuid,
+        destination: &TableIdent,
+    ) -> Result<ContractVerificationOutcome, ErrorModel> {
+        self.check(&HookEvent::Rename {
+            source,
+            destination,
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LIMITS: WasmHookLimits = WasmHookLimits {
+        fuel: 1_000_000,
+        memory_bytes: 1 << 20,
+    };
+
+    /// Rejects every event whose JSON contains the byte sequence `"drop"`.
+    const DENY_DROP: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (data (i32.const 0) "drops are not allowed")
+          (func (export "alloc") (param i32) (result i32) (i32.const 1024))
+          (func (export "check") (param $ptr i32) (param $len i32) (result i64)
+            (local $i i32)
+            (block $done
+              (loop $next
+                (br_if $done (i32.gt_s (i32.add (local.get $i) (i32.const 6)) (local.get $len)))
+                (if (i32.and
+                      (i32.eq (i32.load (i32.add (local.get $ptr) (local.get $i))) (i32.const 0x6f726422))
+                      (i32.eq (i32.load16_u (i32.add (local.get $ptr) (i32.add (local.get $i) (i32.const 4)))) (i32.const 0x2270)))
+                  (then (return (i64.const 21))))
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $next)))
+            (i64.const 0)))
+    "#;
+
+    const LOOP: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "check") (param i32 i32) (result i64)
+            (loop $forever (br $forever))
+            (i64.const 0)))
+    "#;
+
+    const GROW: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "alloc") (param i32) (result i32) (i32.const 0))
+          (func (export "check") (param i32 i32) (result i64)
+            (if (i32.eq (memory.grow (i32.const 100)) (i32.const -1)) (then unreachable))
+            (i64.const 0)))
+    "#;
+
+    #[tokio::test]
+    async fn test_wasm_hook_rejects_with_message() {
+        let verifier =
+            WasmContractVerifier::new("deny-drop", DENY_DROP.as_bytes(), LIMITS).unwrap();
+
+        let outcome = verifier
+            .check_drop(TabularIdentUuid::Table(uuid::Uuid::now_v7()))
+            .await
+            .unwrap();
+        let ContractVerificationOutcome::Violation { error_model } = outcome else {
+            panic!("Expected violation, got {outcome:?}");
+        };
+        assert_eq!(error_model.message, "drops are not allowed");
+        assert_eq!(error_model.code, 409);
+
+        let outcome = verifier
+            .check_rename(
+                TabularIdentUuid::Table(uuid::Uuid::now_v7()),
+                &TableIdent::from_strs(["ns", "tbl"]).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(matches!(outcome, ContractVerificationOutcome::Clear {}));
+    }
+
+    #[tokio::test]
+    async fn test_wasm_hook_limits() {
+        let ident = TabularIdentUuid::Table(uuid::Uuid::now_v7());
+
+        let verifier = WasmContractVerifier::new("loop", LOOP.as_bytes(), LIMITS).unwrap();
+        let err = verifier.check_drop(ident).await.unwrap_err();
+        assert_eq!(err.r#type, "WasmHookFailed");
+
+        let verifier = WasmContractVerifier::new("grow", GROW.as_bytes(), LIMITS).unwrap();
+        let err = verifier.check_drop(ident).await.unwrap_err();
+        assert_eq!(err.r#type, "WasmHookFailed");
+    }
+
+    #[test]
+    fn test_wasm_hook_must_not_import() {
+        let module = r#"
+            (module
+              (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32)))
+              (memory (export "memory") 1)
+              (func (export "alloc") (param i32) (result i32) (i32.const 0))
+              (func (export "check") (param i32 i32) (result i64) (i64.const 0)))
+        "#;
+        let err = WasmContractVerifier::new("imports", module.as_bytes(), LIMITS).unwrap_err();
+        assert!(err.to_string().contains("must not import"), "{err}");
+
+        let module = r#"(module (memory (export "memory") 1))"#;
+        let err = WasmContractVerifier::new("exports", module.as_bytes(), LIMITS).unwrap_err();
+        assert!(err.to_string().contains("does not export 'alloc'"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_wasm_hook_registry() {
+        let dir = std::env::temp_dir().join(format!("lakekeeper-hooks-{}", uuid::Uuid::now_v7()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("deny-drop.wat"), DENY_DROP).unwrap();
+        let registry = WasmHookRegistry::new(dir.clone(), LIMITS);
+
+        let verifier = registry.resolve("deny-drop").unwrap().unwrap();
+        let outcome = verifier
+            .check_drop(TabularIdentUuid::Table(uuid::Uuid::now_v7()))
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            ContractVerificationOutcome::Violation { .. }
+        ));
+        // Served from the cache, even if the file is gone.
+        std::fs::remove_file(dir.join("deny-drop.wat")).unwrap();
+        assert!(registry.resolve("deny-drop").unwrap().is_some());
+
+        assert!(registry.resolve("missing").unwrap().is_none());
+        assert!(registry.resolve("../deny-drop").unwrap().is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -38,7 +38,11 @@ impl<B> MakeSpan<B> for RestMakeSpan {
                                     .headers()
                                     .get("x-request-id")
                                     .and_then(|v| v.to_str().ok())
-                                    .unwrap_or("MISSING-REQUEST-ID")
+                                    .unwrap_or("MISSING-REQUEST-ID"),
+                        engine_query_id = ?crate::request_metadata::engine_query_id_from_headers(
+                            request.headers()
+                        ),
+                        client_ip = ?crate::client_ip::client_ip(request)
                     )
             }
         }
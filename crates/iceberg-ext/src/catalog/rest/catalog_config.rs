@@ -30,4 +30,13 @@ mod test {
         let c: CatalogConfig = serde_json::from_value(j.clone()).unwrap();
         assert_eq!(serde_json::to_value(c).unwrap(), j);
     }
+
+    #[test]
+    fn test_empty_catalog_config_serialization() {
+        // `defaults` and `overrides` are required by the spec and must be serialized even if empty.
+        assert_eq!(
+            serde_json::to_value(CatalogConfig::default()).unwrap(),
+            serde_json::json!({"overrides": {}, "defaults": {}, "endpoints": []})
+        );
+    }
 }
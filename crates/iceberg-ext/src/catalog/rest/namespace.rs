@@ -125,6 +125,13 @@ pub struct ListNamespacesResponse {
     /// The order of the IDs in this list corresponds to the order of the namespaces in the `namespaces` field.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub namespace_uuids: Option<Vec<uuid::Uuid>>,
+    /// Total number of namespaces, only set if requested via `returnTotal`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i64>,
+    /// Descriptions of the namespaces maintained via the Management API,
+    /// in the order of `namespaces`. Only set if requested via `returnDescriptions`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descriptions: Option<Vec<Option<String>>>,
 }
 
 #[cfg(feature = "axum")]
@@ -63,6 +63,26 @@ pub struct ListTablesResponse {
     /// Lakekeeper IDs of the tables.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub table_uuids: Option<Vec<uuid::Uuid>>,
+    /// Total number of tables or views, only set if requested via `returnTotal`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<i64>,
+    /// Last access of the tables or views, in the order of `identifiers`.
+    /// Only set if requested via `returnAccessTimes`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_times: Option<Vec<TabularAccess>>,
+    /// Descriptions of the tables or views maintained via the Management API,
+    /// in the order of `identifiers`. Only set if requested via `returnDescriptions`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descriptions: Option<Vec<Option<String>>>,
+}
+
+/// Reads and commits of a table or view observed by the catalog.
+/// Accesses are sampled, timestamps may lag behind by the configured interval.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TabularAccess {
+    pub last_read_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_commit_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
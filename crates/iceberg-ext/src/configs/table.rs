@@ -80,6 +80,7 @@ pub mod gcs {
             Bucket, String, "gcs.bucket", "gcs_bucket";
             Token, String, "gcs.oauth2.token", "gcs_oauth2_token";
             TokenExpiresAt, String, "gcs.oauth2.token-expires-at", "gcs_oauth2_token_expires_at";
+            KmsKeyName, String, "gcs.kms-key-name", "gcs_kms_key_name";
         }
     );
 }
@@ -9,6 +9,7 @@
 
 pub mod catalog;
 pub mod configs;
+pub mod management;
 pub mod spec;
 pub mod validation;
 
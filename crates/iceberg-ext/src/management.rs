@@ -0,0 +1,350 @@
+//! Models of the Lakekeeper Management API shared by the server and its clients.
+
+use std::ops::Deref;
+use std::str::FromStr;
+
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::rest::{ErrorModel, IcebergErrorResponse};
+
+#[derive(
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Copy,
+    utoipa::ToSchema,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "sqlx", sqlx(transparent))]
+#[serde(transparent)]
+pub struct WarehouseIdent(uuid::Uuid);
+
+impl WarehouseIdent {
+    #[must_use]
+    pub const fn new(id: uuid::Uuid) -> Self {
+        Self(id)
+    }
+
+    #[must_use]
+    pub fn to_uuid(&self) -> uuid::Uuid {
+        **self
+    }
+
+    #[must_use]
+    pub fn as_uuid(&self) -> &uuid::Uuid {
+        self
+    }
+}
+
+impl Deref for WarehouseIdent {
+    type Target = uuid::Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<uuid::Uuid> for WarehouseIdent {
+    fn from(uuid: uuid::Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<WarehouseIdent> for uuid::Uuid {
+    fn from(ident: WarehouseIdent) -> Self {
+        ident.0
+    }
+}
+
+impl std::fmt::Display for WarehouseIdent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for WarehouseIdent {
+    type Err = IcebergErrorResponse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(WarehouseIdent(uuid::Uuid::from_str(s).map_err(|e| {
+            ErrorModel::builder()
+                .code(StatusCode::BAD_REQUEST.into())
+                .message("Provided warehouse id is not a valid UUID".to_string())
+                .r#type("WarehouseIDIsNotUUID".to_string())
+                .source(Some(Box::new(e)))
+                .build()
+        })?))
+    }
+}
+
+/// Status of a warehouse
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    strum_macros::Display,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(
+    feature = "sqlx",
+    sqlx(type_name = "warehouse_status", rename_all = "kebab-case")
+)]
+pub enum WarehouseStatus {
+    /// The warehouse is active and can be used
+    Active,
+    /// The warehouse is inactive and cannot be used.
+    Inactive,
+}
+
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Copy,
+    utoipa::ToSchema,
+)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+#[cfg_attr(feature = "sqlx", sqlx(transparent))]
+#[serde(transparent)]
+pub struct ProjectIdent(uuid::Uuid);
+
+impl Default for ProjectIdent {
+    fn default() -> Self {
+        Self(uuid::Uuid::now_v7())
+    }
+}
+
+impl ProjectIdent {
+    #[must_use]
+    pub const fn new(id: uuid::Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl Deref for ProjectIdent {
+    type Target = uuid::Uuid;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<uuid::Uuid> for ProjectIdent {
+    fn from(uuid: uuid::Uuid) -> Self {
+        Self(uuid)
+    }
+}
+
+impl From<ProjectIdent> for uuid::Uuid {
+    fn from(ident: ProjectIdent) -> Self {
+        ident.0
+    }
+}
+
+impl std::fmt::Display for ProjectIdent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for ProjectIdent {
+    type Err = IcebergErrorResponse;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(ProjectIdent(uuid::Uuid::from_str(s).map_err(|e| {
+            ErrorModel::builder()
+                .code(StatusCode::BAD_REQUEST.into())
+                .message("Provided project id is not a valid UUID".to_string())
+                .r#type("ProjectIDIsNotUUID".to_string())
+                .source(Some(Box::new(e)))
+                .build()
+        })?))
+    }
+}
+
+/// Unique identifier of a user in the system.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, utoipa::ToSchema)]
+pub enum UserId {
+    /// OIDC principal
+    OIDC(String),
+    /// K8s principal
+    Kubernetes(String),
+}
+
+impl TryFrom<String> for UserId {
+    type Error = IcebergErrorResponse;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        match s.split_once('~') {
+            Some(("oidc", user_id)) => Ok(UserId::oidc(user_id)?),
+            Some(("kubernetes", user_id)) => Ok(UserId::kubernetes(user_id)?),
+            _ => Err(ErrorModel::bad_request(
+                format!("Invalid user id format: {s}"),
+                "InvalidUserId",
+                None,
+            )
+            .into()),
+        }
+    }
+}
+
+impl std::fmt::Display for UserId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserId::OIDC(user_id) => write!(f, "oidc~{user_id}"),
+            UserId::Kubernetes(user_id) => write!(f, "kubernetes~{user_id}"),
+        }
+    }
+}
+
+impl UserId {
+    fn validate_subject(subject: &str) -> Result<(), IcebergErrorResponse> {
+        Self::validate_len(subject)?;
+        Self::no_illegal_chars(subject)?;
+        Ok(())
+    }
+
+    /// User authenticated by an `OpenID` provider.
+    ///
+    /// # Errors
+    /// - the subject is too long or contains illegal characters
+    pub fn oidc(subject: &str) -> Result<Self, IcebergErrorResponse> {
+        Self::validate_subject(subject)?;
+        Ok(Self::OIDC(subject.to_string()))
+    }
+
+    /// User authenticated by a Kubernetes service account token.
+    ///
+    /// # Errors
+    /// - the subject is too long or contains illegal characters
+    pub fn kubernetes(subject: &str) -> Result<Self, IcebergErrorResponse> {
+        Self::validate_subject(subject)?;
+        Ok(Self::Kubernetes(subject.to_string()))
+    }
+
+    /// User id of `user_id` authenticated by the identity provider of `project_id`.
+    ///
+    /// # Errors
+    /// - the prefixed subject is too long
+    pub fn for_project(
+        project_id: ProjectIdent,
+        user_id: &UserId,
+    ) -> Result<Self, IcebergErrorResponse> {
+        match user_id {
+            UserId::OIDC(subject) => Self::oidc(&format!("{project_id}_{subject}")),
+            UserId::Kubernetes(subject) => Self::kubernetes(&format!("{project_id}_{subject}")),
+        }
+    }
+
+    fn validate_len(subject: &str) -> Result<(), IcebergErrorResponse> {
+        if subject.len() >= 128 {
+            return Err(ErrorModel::bad_request(
+                "user id must be shorter than 128 chars",
+                "UserIdTooLongError",
+                None,
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn no_illegal_chars(subject: &str) -> Result<(), IcebergErrorResponse> {
+        if subject
+            .chars()
+            .any(|c| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        {
+            return Err(ErrorModel::bad_request(
+                "sub or oid claim contain illegal characters. Only alphanumeric + - are legal.",
+                "InvalidUserIdError",
+                None,
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+impl From<UserId> for String {
+    fn from(user_id: UserId) -> Self {
+        user_id.to_string()
+    }
+}
+
+impl<'de> Deserialize<'de> for UserId {
+    fn deserialize<D>(deserializer: D) -> Result<UserId, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        UserId::try_from(s).map_err(|e| serde::de::Error::custom(e.error))
+    }
+}
+
+impl Serialize for UserId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_str())
+    }
+}
+
+/// Type of a User
+#[derive(Copy, Debug, PartialEq, Deserialize, Serialize, utoipa::ToSchema, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub enum UserType {
+    /// Human User
+    Human,
+    /// Application / Technical User
+    Application,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuthZBackend {
+    AllowAll,
+    #[serde(rename = "openfga")]
+    OpenFGA,
+    Webhook,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifiers_are_transparent() {
+        let id = uuid::Uuid::now_v7();
+        let json = serde_json::json!(id);
+        assert_eq!(serde_json::json!(WarehouseIdent::from(id)), json);
+        assert_eq!(serde_json::json!(ProjectIdent::from(id)), json);
+        assert_eq!(
+            serde_json::from_value::<ProjectIdent>(json).unwrap(),
+            ProjectIdent::new(id)
+        );
+        assert_eq!(uuid::Uuid::from(WarehouseIdent::new(id)), id);
+    }
+}
@@ -0,0 +1,153 @@
+use std::time::{Duration, Instant};
+
+use iceberg_ext::catalog::rest::OAuthTokenResponse;
+use tokio::sync::Mutex;
+
+use crate::error::{Error, Result};
+
+/// Tokens are refreshed this long before they expire, so that they don't
+/// expire while a request is in flight.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(30);
+
+/// How requests to Lakekeeper are authenticated.
+#[derive(Clone, Default)]
+pub enum Authentication {
+    /// Send requests without an `Authorization` header.
+    #[default]
+    None,
+    /// Send a static bearer token, e.g. a Kubernetes service account token.
+    BearerToken(String),
+    /// Obtain tokens from an `OpenID` provider using the client credentials flow.
+    /// Tokens are cached until shortly before they expire.
+    ClientCredentials(ClientCredentials),
+}
+
+impl std::fmt::Debug for Authentication {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Authentication::None => write!(f, "None"),
+            Authentication::BearerToken(_) => write!(f, "BearerToken(<redacted>)"),
+            Authentication::ClientCredentials(credentials) => f
+                .debug_tuple("ClientCredentials")
+                .field(credentials)
+                .finish(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ClientCredentials {
+    /// Token endpoint of the `OpenID` provider.
+    pub token_endpoint: url::Url,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Scope to request, e.g. `lakekeeper`.
+    pub scope: Option<String>,
+}
+
+impl std::fmt::Debug for ClientCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientCredentials")
+            .field("token_endpoint", &self.token_endpoint.as_str())
+            .field("client_id", &self.client_id)
+            .field("client_secret", &"<redacted>")
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+struct CachedToken {
+    token: String,
+    refresh_at: Option<Instant>,
+}
+
+/// Adds credentials to requests, fetching and caching tokens as needed.
+#[derive(Debug)]
+pub(crate) struct Authenticator {
+    authentication: Authentication,
+    cached: Mutex<Option<CachedToken>>,
+}
+
+impl Authenticator {
+    pub(crate) fn new(authentication: Authentication) -> Self {
+        Self {
+            authentication,
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub(crate) async fn authenticate(
+        &self,
+        http: &reqwest::Client,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder> {
+        match &self.authentication {
+            Authentication::None => Ok(request),
+            Authentication::BearerToken(token) => Ok(request.bearer_auth(token)),
+            Authentication::ClientCredentials(credentials) => {
+                let token = self.client_credentials_token(http, credentials).await?;
+                Ok(request.bearer_auth(token))
+            }
+        }
+    }
+
+    /// Drop the cached token, e.g. after the server rejected it.
+    pub(crate) async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+
+    async fn client_credentials_token(
+        &self,
+        http: &reqwest::Client,
+        credentials: &ClientCredentials,
+    ) -> Result<String> {
+        let mut cached = self.cached.lock().await;
+        if let Some(token) = cached.as_ref() {
+            if token.refresh_at.map_or(true, |at| Instant::now() < at) {
+                return Ok(token.token.clone());
+            }
+        }
+
+        tracing::debug!(
+            "Requesting access token from {}",
+            credentials.token_endpoint
+        );
+        let mut form = vec![
+            ("grant_type", "client_credentials"),
+            ("client_id", credentials.client_id.as_str()),
+            ("client_secret", credentials.client_secret.as_str()),
+        ];
+        if let Some(scope) = &credentials.scope {
+            form.push(("scope", scope.as_str()));
+        }
+        let response = http
+            .post(credentials.token_endpoint.clone())
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| Error::Authentication(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::Authentication(format!(
+                "Token endpoint responded with {status}: {body}"
+            )));
+        }
+        let token: OAuthTokenResponse = response
+            .json()
+            .await
+            .map_err(|e| Error::Authentication(e.to_string()))?;
+
+        let refresh_at = token.expires_in.map(|expires_in| {
+            Instant::now() + Duration::from_secs(expires_in).saturating_sub(TOKEN_REFRESH_MARGIN)
+        });
+        let access_token = token.access_token;
+        *cached = Some(CachedToken {
+            token: access_token.clone(),
+            refresh_at,
+        });
+        Ok(access_token)
+    }
+}
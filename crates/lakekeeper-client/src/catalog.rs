@@ -0,0 +1,271 @@
+use http::Method;
+use iceberg_ext::catalog::rest::{
+    CatalogConfig, CommitTableRequest, CommitTableResponse, CreateNamespaceRequest,
+    CreateNamespaceResponse, CreateTableRequest, GetNamespaceResponse, ListNamespacesResponse,
+    ListTablesResponse, LoadTableResult, LoadViewResult, RenameTableRequest,
+    UpdateNamespacePropertiesRequest, UpdateNamespacePropertiesResponse,
+};
+use iceberg_ext::{NamespaceIdent, TableIdent};
+
+use crate::client::{encode_segment, LakekeeperClient};
+use crate::error::Result;
+
+/// Client for the Iceberg REST Catalog API of a single warehouse.
+///
+/// Obtained via [`LakekeeperClient::catalog`].
+#[derive(Debug, Clone)]
+pub struct CatalogClient {
+    client: LakekeeperClient,
+    config: CatalogConfig,
+    /// `catalog/v1/{prefix}/`
+    base_path: String,
+}
+
+impl CatalogClient {
+    pub(crate) fn new(client: LakekeeperClient, config: CatalogConfig) -> Self {
+        let base_path = match config
+            .overrides
+            .get("prefix")
+            .or_else(|| config.defaults.get("prefix"))
+        {
+            Some(prefix) => format!("catalog/v1/{}/", encode_segment(prefix)),
+            None => "catalog/v1/".to_string(),
+        };
+        Self {
+            client,
+            config,
+            base_path,
+        }
+    }
+
+    /// Config returned by the server for this warehouse.
+    #[must_use]
+    pub fn config(&self) -> &CatalogConfig {
+        &self.config
+    }
+
+    /// List namespaces below `parent`, or top-level namespaces if `parent` is `None`.
+    ///
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn list_namespaces(
+        &self,
+        parent: Option<&NamespaceIdent>,
+        page_token: Option<&str>,
+    ) -> Result<ListNamespacesResponse> {
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        if let Some(parent) = parent {
+            query.append_pair("parent", &parent.to_url_string());
+        }
+        if let Some(page_token) = page_token {
+            query.append_pair("pageToken", page_token);
+        }
+        let path = format!("{}namespaces?{}", self.base_path, query.finish());
+        self.client.request(Method::GET, &path, None::<&()>).await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn create_namespace(
+        &self,
+        request: &CreateNamespaceRequest,
+    ) -> Result<CreateNamespaceResponse> {
+        let path = format!("{}namespaces", self.base_path);
+        self.client
+            .request(Method::POST, &path, Some(request))
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn load_namespace(&self, namespace: &NamespaceIdent) -> Result<GetNamespaceResponse> {
+        self.client
+            .request(Method::GET, &self.namespace_path(namespace), None::<&()>)
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn drop_namespace(&self, namespace: &NamespaceIdent) -> Result<()> {
+        self.client
+            .request_empty(Method::DELETE, &self.namespace_path(namespace), None::<&()>)
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn update_namespace_properties(
+        &self,
+        namespace: &NamespaceIdent,
+        request: &UpdateNamespacePropertiesRequest,
+    ) -> Result<UpdateNamespacePropertiesResponse> {
+        let path = format!("{}/properties", self.namespace_path(namespace));
+        self.client
+            .request(Method::POST, &path, Some(request))
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn list_tables(
+        &self,
+        namespace: &NamespaceIdent,
+        page_token: Option<&str>,
+    ) -> Result<ListTablesResponse> {
+        let path = with_page_token(
+            format!("{}/tables", self.namespace_path(namespace)),
+            page_token,
+        );
+        self.client.request(Method::GET, &path, None::<&()>).await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn create_table(
+        &self,
+        namespace: &NamespaceIdent,
+        request: &CreateTableRequest,
+    ) -> Result<LoadTableResult> {
+        let path = format!("{}/tables", self.namespace_path(namespace));
+        self.client
+            .request(Method::POST, &path, Some(request))
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn load_table(&self, table: &TableIdent) -> Result<LoadTableResult> {
+        self.client
+            .request(
+                Method::GET,
+                &self.tabular_path(table, "tables"),
+                None::<&()>,
+            )
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn commit_table(
+        &self,
+        table: &TableIdent,
+        request: &CommitTableRequest,
+    ) -> Result<CommitTableResponse> {
+        self.client
+            .request(
+                Method::POST,
+                &self.tabular_path(table, "tables"),
+                Some(request),
+            )
+            .await
+    }
+
+    /// Drop a table. If `purge` is set, its data is deleted as well.
+    ///
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn drop_table(&self, table: &TableIdent, purge: bool) -> Result<()> {
+        let path = format!(
+            "{}?purgeRequested={purge}",
+            self.tabular_path(table, "tables")
+        );
+        self.client
+            .request_empty(Method::DELETE, &path, None::<&()>)
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn rename_table(&self, request: &RenameTableRequest) -> Result<()> {
+        let path = format!("{}tables/rename", self.base_path);
+        self.client
+            .request_empty(Method::POST, &path, Some(request))
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn list_views(
+        &self,
+        namespace: &NamespaceIdent,
+        page_token: Option<&str>,
+    ) -> Result<ListTablesResponse> {
+        let path = with_page_token(
+            format!("{}/views", self.namespace_path(namespace)),
+            page_token,
+        );
+        self.client.request(Method::GET, &path, None::<&()>).await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn load_view(&self, view: &TableIdent) -> Result<LoadViewResult> {
+        self.client
+            .request(Method::GET, &self.tabular_path(view, "views"), None::<&()>)
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn drop_view(&self, view: &TableIdent) -> Result<()> {
+        self.client
+            .request_empty(
+                Method::DELETE,
+                &self.tabular_path(view, "views"),
+                None::<&()>,
+            )
+            .await
+    }
+
+    fn namespace_path(&self, namespace: &NamespaceIdent) -> String {
+        format!(
+            "{}namespaces/{}",
+            self.base_path,
+            encode_segment(&namespace.to_url_string())
+        )
+    }
+
+    fn tabular_path(&self, tabular: &TableIdent, kind: &str) -> String {
+        format!(
+            "{}/{kind}/{}",
+            self.namespace_path(&tabular.namespace),
+            encode_segment(&tabular.name)
+        )
+    }
+}
+
+fn with_page_token(path: String, page_token: Option<&str>) -> String {
+    match page_token {
+        Some(token) => format!("{path}?pageToken={}", encode_segment(token)),
+        None => path,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn catalog(prefix: &str) -> CatalogClient {
+        let client = LakekeeperClient::builder("https://example.com".parse().unwrap())
+            .build()
+            .unwrap();
+        let config = CatalogConfig {
+            overrides: [("prefix".to_string(), prefix.to_string())].into(),
+            ..CatalogConfig::default()
+        };
+        CatalogClient::new(client, config)
+    }
+
+    #[test]
+    fn test_paths_are_encoded() {
+        let catalog = catalog("0192f3a4-prefix");
+        let table = TableIdent::new(
+            NamespaceIdent::from_vec(vec!["a".to_string(), "b c".to_string()]).unwrap(),
+            "my/table".to_string(),
+        );
+        assert_eq!(
+            catalog.tabular_path(&table, "tables"),
+            "catalog/v1/0192f3a4-prefix/namespaces/a%1Fb%20c/tables/my%2Ftable"
+        );
+    }
+}
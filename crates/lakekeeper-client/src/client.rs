@@ -0,0 +1,401 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use http::{Method, StatusCode};
+use iceberg_ext::catalog::rest::{CatalogConfig, IcebergErrorResponse};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::auth::{Authentication, Authenticator};
+use crate::catalog::CatalogClient;
+use crate::error::{Error, Result};
+use crate::management::ManagementClient;
+use crate::retry::{is_idempotent, RetryConfig};
+
+/// Client for a Lakekeeper deployment. Cheap to clone.
+#[derive(Debug, Clone)]
+pub struct LakekeeperClient {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    http: reqwest::Client,
+    base_url: url::Url,
+    authenticator: Authenticator,
+    retry: RetryConfig,
+}
+
+#[derive(Debug)]
+pub struct LakekeeperClientBuilder {
+    base_url: url::Url,
+    authentication: Authentication,
+    retry: RetryConfig,
+    timeout: Duration,
+    user_agent: String,
+}
+
+impl LakekeeperClientBuilder {
+    #[must_use]
+    pub fn authentication(mut self, authentication: Authentication) -> Self {
+        self.authentication = authentication;
+        self
+    }
+
+    #[must_use]
+    pub fn retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Timeout of a single attempt. Default: 30 seconds
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Build the client.
+    ///
+    /// # Errors
+    /// If the underlying HTTP client cannot be initialized, e.g. because no TLS roots are available.
+    pub fn build(self) -> Result<LakekeeperClient> {
+        let http = reqwest::Client::builder()
+            .timeout(self.timeout)
+            .user_agent(self.user_agent)
+            .build()
+            .map_err(Error::ClientBuild)?;
+
+        // Without a trailing slash, `join` would replace the last path segment.
+        let mut base_url = self.base_url;
+        let path = base_url.path().trim_end_matches('/').to_string();
+        base_url.set_path(&format!("{path}/"));
+
+        Ok(LakekeeperClient {
+            inner: Arc::new(Inner {
+                http,
+                base_url,
+                authenticator: Authenticator::new(self.authentication),
+                retry: self.retry,
+            }),
+        })
+    }
+}
+
+impl LakekeeperClient {
+    /// Start building a client for the Lakekeeper deployment reachable at `base_url`,
+    /// i.e. the configured `LAKEKEEPER__BASE_URI`.
+    #[must_use]
+    pub fn builder(base_url: url::Url) -> LakekeeperClientBuilder {
+        LakekeeperClientBuilder {
+            base_url,
+            authentication: Authentication::None,
+            retry: RetryConfig::default(),
+            timeout: Duration::from_secs(30),
+            user_agent: format!("lakekeeper-client/{}", env!("CARGO_PKG_VERSION")),
+        }
+    }
+
+    #[must_use]
+    pub fn base_url(&self) -> &url::Url {
+        &self.inner.base_url
+    }
+
+    /// Client for the Management API.
+    #[must_use]
+    pub fn management(&self) -> ManagementClient {
+        ManagementClient::new(self.clone())
+    }
+
+    /// Client for the Iceberg REST Catalog API of a warehouse.
+    ///
+    /// # Errors
+    /// If the catalog config of the warehouse cannot be loaded.
+    pub async fn catalog(&self, warehouse: &str) -> Result<CatalogClient> {
+        let config: CatalogConfig = self
+            .request(
+                Method::GET,
+                &format!(
+                    "catalog/v1/config?warehouse={}",
+                    urlencoding::encode(warehouse)
+                ),
+                None::<&()>,
+            )
+            .await?;
+        Ok(CatalogClient::new(self.clone(), config))
+    }
+
+    /// Send a request and deserialize the JSON response.
+    pub(crate) async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&impl Serialize>,
+    ) -> Result<T> {
+        let url = self.inner.base_url.join(path)?;
+        let response = self.send(method, url.clone(), body).await?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(|source| Error::Request {
+            url: url.clone(),
+            source,
+        })?;
+        serde_json::from_slice(&body).map_err(|e| {
+            tracing::debug!("Failed to parse response from {url}: {e}");
+            Error::UnexpectedResponse {
+                url,
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }
+        })
+    }
+
+    /// Send a request whose response has no body.
+    pub(crate) async fn request_empty(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&impl Serialize>,
+    ) -> Result<()> {
+        let url = self.inner.base_url.join(path)?;
+        self.send(method, url, body).await.map(|_| ())
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        url: url::Url,
+        body: Option<&impl Serialize>,
+    ) -> Result<reqwest::Response> {
+        let body = body
+            .map(serde_json::to_vec)
+            .transpose()
+            .map_err(Error::Serialization)?;
+        let retry = self.inner.retry;
+        let retries = if is_idempotent(&method) {
+            retry.max_retries
+        } else {
+            0
+        };
+
+        tryhard::retry_fn(|| self.send_once(method.clone(), url.clone(), body.clone()))
+            .retries(retries)
+            .custom_backoff(move |attempt, error: &Error| {
+                if error.is_retryable() {
+                    tracing::debug!("Retrying request (attempt {attempt}): {error}");
+                    tryhard::RetryPolicy::Delay(retry.backoff(attempt))
+                } else {
+                    tryhard::RetryPolicy::Break
+                }
+            })
+            .await
+    }
+
+    async fn send_once(
+        &self,
+        method: Method,
+        url: url::Url,
+        body: Option<Vec<u8>>,
+    ) -> Result<reqwest::Response> {
+        let mut request = self.inner.http.request(method, url.clone());
+        if let Some(body) = body {
+            request = request
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(body);
+        }
+        let request = self
+            .inner
+            .authenticator
+            .authenticate(&self.inner.http, request)
+            .await?;
+
+        let response = request.send().await.map_err(|source| Error::Request {
+            url: url.clone(),
+            source,
+        })?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        if status == StatusCode::UNAUTHORIZED {
+            // The token might have been revoked before it expired
+            self.inner.authenticator.invalidate().await;
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(match serde_json::from_str::<IcebergErrorResponse>(&body) {
+            Ok(IcebergErrorResponse { error }) => Error::Api { status, error },
+            Err(_) => Error::UnexpectedResponse { url, status, body },
+        })
+    }
+}
+
+/// Percent-encode a single path segment.
+pub(crate) fn encode_segment(segment: &str) -> String {
+    urlencoding::encode(segment).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::ClientCredentials;
+    use axum::http::HeaderMap;
+    use axum::routing::{get, post};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    const SERVER_INFO: &str = r#"{
+        "version": "0.6.2",
+        "bootstrapped": true,
+        "server-id": "00000000-0000-0000-0000-000000000000",
+        "default-project-id": null,
+        "authz-backend": "allow-all"
+    }"#;
+
+    /// Serve `router` on a random local port and return its base url.
+    async fn serve(router: axum::Router) -> url::Url {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        format!("http://{addr}/").parse().unwrap()
+    }
+
+    fn immediate_retries() -> RetryConfig {
+        RetryConfig {
+            max_retries: 3,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_unavailable_server() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = axum::Router::new().route(
+            "/management/v1/info",
+            get({
+                let calls = calls.clone();
+                move || async move {
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        (StatusCode::SERVICE_UNAVAILABLE, String::new())
+                    } else {
+                        (StatusCode::OK, SERVER_INFO.to_string())
+                    }
+                }
+            }),
+        );
+        let client = LakekeeperClient::builder(serve(router).await)
+            .retry(immediate_retries())
+            .build()
+            .unwrap();
+
+        let info = client.management().server_info().await.unwrap();
+        assert!(info.bootstrapped);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_non_idempotent_requests() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = axum::Router::new().fallback({
+            let calls = calls.clone();
+            move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+        });
+        let client = LakekeeperClient::builder(serve(router).await)
+            .retry(immediate_retries())
+            .build()
+            .unwrap();
+
+        let err = client
+            .request_empty(Method::POST, "management/v1/bootstrap", Some(&()))
+            .await
+            .unwrap_err();
+        assert!(err.is_retryable());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_token_is_cached_and_refreshed_after_rejection() {
+        let issued = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let router = axum::Router::new()
+            .route(
+                "/oauth/token",
+                post({
+                    let issued = issued.clone();
+                    move || async move {
+                        let n = issued.fetch_add(1, Ordering::SeqCst) + 1;
+                        format!(
+                            r#"{{"access_token": "token-{n}", "token_type": "bearer", "expires_in": 3600}}"#
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/management/v1/info",
+                get({
+                    let seen = seen.clone();
+                    move |headers: HeaderMap| async move {
+                        let token = headers[http::header::AUTHORIZATION]
+                            .to_str()
+                            .unwrap()
+                            .to_string();
+                        let revoked = token == "Bearer token-1" && seen.lock().unwrap().len() == 1;
+                        seen.lock().unwrap().push(token);
+                        if revoked {
+                            (StatusCode::UNAUTHORIZED, String::new())
+                        } else {
+                            (StatusCode::OK, SERVER_INFO.to_string())
+                        }
+                    }
+                }),
+            );
+        let base_url = serve(router).await;
+        let client = LakekeeperClient::builder(base_url.clone())
+            .authentication(Authentication::ClientCredentials(ClientCredentials {
+                token_endpoint: base_url.join("oauth/token").unwrap(),
+                client_id: "client".to_string(),
+                client_secret: "secret".to_string(),
+                scope: None,
+            }))
+            .retry(immediate_retries())
+            .build()
+            .unwrap();
+        let management = client.management();
+
+        // The first token is reused until the server rejects it.
+        management.server_info().await.unwrap();
+        let err = management.server_info().await.unwrap_err();
+        assert!(!err.is_retryable());
+        management.server_info().await.unwrap();
+
+        assert_eq!(issued.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["Bearer token-1", "Bearer token-1", "Bearer token-2"]
+        );
+    }
+
+    #[test]
+    fn test_base_url_keeps_path_prefix() {
+        let client = LakekeeperClient::builder("https://example.com/lakekeeper".parse().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(
+            client
+                .base_url()
+                .join("management/v1/info")
+                .unwrap()
+                .as_str(),
+            "https://example.com/lakekeeper/management/v1/info"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use http::StatusCode;
+use iceberg_ext::catalog::rest::ErrorModel;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Request to {url} failed")]
+    Request {
+        url: url::Url,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("Lakekeeper responded with {status}: {} ({})", .error.message, .error.r#type)]
+    Api {
+        status: StatusCode,
+        error: ErrorModel,
+    },
+    #[error("Unexpected response with status {status} from {url}")]
+    UnexpectedResponse {
+        url: url::Url,
+        status: StatusCode,
+        body: String,
+    },
+    #[error("Failed to obtain an access token: {0}")]
+    Authentication(String),
+    #[error("Invalid URL")]
+    InvalidUrl(#[from] url::ParseError),
+    #[error("Failed to build HTTP client")]
+    ClientBuild(#[source] reqwest::Error),
+    #[error("Failed to serialize request body")]
+    Serialization(#[source] serde_json::Error),
+}
+
+impl Error {
+    /// HTTP status returned by the server, if a response was received.
+    #[must_use]
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Api { status, .. } | Error::UnexpectedResponse { status, .. } => Some(*status),
+            Error::Request { source, .. } => source.status(),
+            _ => None,
+        }
+    }
+
+    /// Whether the request may succeed if it is sent again.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Request { source, .. } => source.is_connect() || source.is_timeout(),
+            Error::Api { status, .. } | Error::UnexpectedResponse { status, .. } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            Error::Authentication(_)
+            | Error::InvalidUrl(_)
+            | Error::ClientBuild(_)
+            | Error::Serialization(_) => false,
+        }
+    }
+}
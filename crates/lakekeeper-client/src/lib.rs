@@ -0,0 +1,46 @@
+//! Async client for [Lakekeeper](https://github.com/lakekeeper/lakekeeper).
+//!
+//! The client covers the Iceberg REST Catalog API and the most common operations of the
+//! Lakekeeper Management API, using the models of [`iceberg_ext`]. Requests are
+//! authenticated with a static bearer token or via the `OAuth2` client credentials flow,
+//! and idempotent requests are retried with exponential backoff if the server is
+//! temporarily unavailable.
+//!
+//! ```no_run
+//! # async fn example() -> lakekeeper_client::Result<()> {
+//! use lakekeeper_client::{Authentication, LakekeeperClient};
+//!
+//! let client = LakekeeperClient::builder("https://lakekeeper.example.com".parse().unwrap())
+//!     .authentication(Authentication::BearerToken("my-token".to_string()))
+//!     .build()?;
+//!
+//! let info = client.management().server_info().await?;
+//! let catalog = client.catalog("my-warehouse").await?;
+//! let namespaces = catalog.list_namespaces(None, None).await?;
+//! # Ok(())
+//! # }
+//! ```
+#![warn(
+    missing_debug_implementations,
+    rust_2018_idioms,
+    unreachable_pub,
+    clippy::pedantic
+)]
+#![forbid(unsafe_code)]
+#![allow(clippy::module_name_repetitions)]
+
+mod auth;
+mod catalog;
+mod client;
+mod error;
+pub mod management;
+mod retry;
+
+pub use auth::{Authentication, ClientCredentials};
+pub use catalog::CatalogClient;
+pub use client::{LakekeeperClient, LakekeeperClientBuilder};
+pub use error::{Error, Result};
+pub use iceberg_ext::catalog::rest as models;
+pub use iceberg_ext::management::{ProjectIdent, WarehouseIdent};
+pub use management::ManagementClient;
+pub use retry::RetryConfig;
@@ -0,0 +1,227 @@
+//! Management API client and models.
+//!
+//! Models only contain the fields most integrations need. Fields added to the API
+//! later are ignored, so that older clients keep working against newer servers.
+
+use http::Method;
+use iceberg_ext::management::{AuthZBackend, ProjectIdent, UserId, WarehouseIdent};
+pub use iceberg_ext::management::{UserType, WarehouseStatus};
+use serde::{Deserialize, Serialize};
+
+use crate::client::LakekeeperClient;
+use crate::error::Result;
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ServerInfo {
+    pub version: String,
+    pub bootstrapped: bool,
+    pub server_id: uuid::Uuid,
+    pub default_project_id: Option<ProjectIdent>,
+    pub authz_backend: AuthZBackend,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct User {
+    pub id: UserId,
+    pub name: String,
+    #[serde(default)]
+    pub email: Option<String>,
+    pub user_type: UserType,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Project {
+    pub project_id: ProjectIdent,
+    pub project_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListProjectsResponse {
+    pub projects: Vec<Project>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Warehouse {
+    pub id: WarehouseIdent,
+    pub name: String,
+    pub project_id: ProjectIdent,
+    pub status: WarehouseStatus,
+    /// Kept untyped, so that profiles of storage types added later still deserialize.
+    /// `null` if the caller may not view the storage profile.
+    pub storage_profile: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ListWarehousesResponse {
+    pub warehouses: Vec<Warehouse>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct RenameWarehouseRequest<'a> {
+    new_name: &'a str,
+}
+
+/// Client for the Management API.
+///
+/// Obtained via [`LakekeeperClient::management`].
+#[derive(Debug, Clone)]
+pub struct ManagementClient {
+    client: LakekeeperClient,
+}
+
+impl ManagementClient {
+    pub(crate) fn new(client: LakekeeperClient) -> Self {
+        Self { client }
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn server_info(&self) -> Result<ServerInfo> {
+        self.client
+            .request(Method::GET, "management/v1/info", None::<&()>)
+            .await
+    }
+
+    /// The user the client is authenticated as.
+    ///
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn whoami(&self) -> Result<User> {
+        self.client
+            .request(Method::GET, "management/v1/whoami", None::<&()>)
+            .await
+    }
+
+    /// Projects the caller has access to.
+    ///
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn list_projects(&self) -> Result<ListProjectsResponse> {
+        self.client
+            .request(Method::GET, "management/v1/project-list", None::<&()>)
+            .await
+    }
+
+    /// Active warehouses of a project. Uses the default project if `project_id` is `None`.
+    ///
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn list_warehouses(
+        &self,
+        project_id: Option<ProjectIdent>,
+    ) -> Result<ListWarehousesResponse> {
+        let path = match project_id {
+            Some(project_id) => format!("management/v1/warehouse?projectId={project_id}"),
+            None => "management/v1/warehouse".to_string(),
+        };
+        self.client.request(Method::GET, &path, None::<&()>).await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn get_warehouse(&self, warehouse_id: WarehouseIdent) -> Result<Warehouse> {
+        self.client
+            .request(
+                Method::GET,
+                &format!("management/v1/warehouse/{warehouse_id}"),
+                None::<&()>,
+            )
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn rename_warehouse(
+        &self,
+        warehouse_id: WarehouseIdent,
+        new_name: &str,
+    ) -> Result<()> {
+        self.client
+            .request_empty(
+                Method::POST,
+                &format!("management/v1/warehouse/{warehouse_id}/rename"),
+                Some(&RenameWarehouseRequest { new_name }),
+            )
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn activate_warehouse(&self, warehouse_id: WarehouseIdent) -> Result<()> {
+        self.client
+            .request_empty(
+                Method::POST,
+                &format!("management/v1/warehouse/{warehouse_id}/activate"),
+                None::<&()>,
+            )
+            .await
+    }
+
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn deactivate_warehouse(&self, warehouse_id: WarehouseIdent) -> Result<()> {
+        self.client
+            .request_empty(
+                Method::POST,
+                &format!("management/v1/warehouse/{warehouse_id}/deactivate"),
+                None::<&()>,
+            )
+            .await
+    }
+
+    /// Look up a warehouse of a project by name.
+    ///
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn warehouse_by_name(
+        &self,
+        project_id: Option<ProjectIdent>,
+        name: &str,
+    ) -> Result<Option<Warehouse>> {
+        let warehouses = self.list_warehouses(project_id).await?;
+        Ok(warehouses.warehouses.into_iter().find(|w| w.name == name))
+    }
+
+    /// Delete a warehouse. The warehouse must be empty.
+    ///
+    /// # Errors
+    /// If the request fails or the server responds with an error.
+    pub async fn delete_warehouse(&self, warehouse_id: WarehouseIdent) -> Result<()> {
+        self.client
+            .request_empty(
+                Method::DELETE,
+                &format!("management/v1/warehouse/{warehouse_id}"),
+                None::<&()>,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warehouse_deserializes() {
+        let warehouse: Warehouse = serde_json::from_value(serde_json::json!({
+            "id": "0192f3a4-5b6c-7d8e-9f00-112233445566",
+            "name": "my-warehouse",
+            "project-id": "00000000-0000-0000-0000-000000000000",
+            "status": "active",
+            "storage-profile": {"type": "s3", "bucket": "my-bucket"},
+            "delete-profile": {"type": "hard"},
+            "storage-health": "healthy"
+        }))
+        .unwrap();
+        assert_eq!(warehouse.status, WarehouseStatus::Active);
+        assert_eq!(warehouse.project_id, ProjectIdent::from(uuid::Uuid::nil()));
+        assert_eq!(warehouse.storage_profile["bucket"], "my-bucket");
+    }
+}
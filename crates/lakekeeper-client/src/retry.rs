@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use http::Method;
+
+/// Retry behavior for requests that failed with a transient error,
+/// such as a connection error or a `503 Service Unavailable`.
+/// Only idempotent requests are retried: a `POST` that timed out might
+/// have been applied by the server, so retrying it could apply it twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Number of retries after the initial attempt. `0` disables retries.
+    pub max_retries: u32,
+    /// Delay before the first retry. Doubled for every subsequent retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between two attempts.
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryConfig {
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before the given retry, starting at 1.
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Whether sending the request twice has the same effect as sending it once.
+pub(crate) fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_is_exponential_and_capped() {
+        let config = RetryConfig {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+        };
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(2), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(400));
+        assert_eq!(config.backoff(4), Duration::from_millis(500));
+        assert_eq!(config.backoff(64), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_only_idempotent_methods_are_retried() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(is_idempotent(&Method::PUT));
+        assert!(is_idempotent(&Method::DELETE));
+        assert!(!is_idempotent(&Method::POST));
+        assert!(!is_idempotent(&Method::PATCH));
+    }
+}
@@ -0,0 +1,82 @@
+#!/bin/bash
+# Runs the Iceberg REST Compatibility Kit against the `server` of docker-compose.yaml.
+# This script is meant to run in maven:3.9-eclipse-temurin-17 like docker images.
+set -eo pipefail
+
+: "${LAKEKEEPER_TEST__MANAGEMENT_URL:?}"
+: "${LAKEKEEPER_TEST__CATALOG_URL:?}"
+: "${LAKEKEEPER_TEST__OPENID_PROVIDER_URI:?}"
+: "${LAKEKEEPER_TEST__OPENID_CLIENT_ID:?}"
+: "${LAKEKEEPER_TEST__OPENID_CLIENT_SECRET:?}"
+: "${LAKEKEEPER_TEST__OPENID_SCOPE:=lakekeeper}"
+
+MANAGEMENT_URL="${LAKEKEEPER_TEST__MANAGEMENT_URL%/}"
+TOKEN_ENDPOINT="${LAKEKEEPER_TEST__OPENID_PROVIDER_URI%/}/protocol/openid-connect/token"
+
+# Extract a string field from a flat JSON object without requiring jq
+function json_field() {
+  grep -o "\"$1\" *: *\"[^\"]*\"" | head -n1 | sed -E 's/.*: *"([^"]*)"/\1/'
+}
+
+echo "Fetching access token ..."
+ACCESS_TOKEN=$(curl -sSf "$TOKEN_ENDPOINT" \
+  -d grant_type=client_credentials \
+  -d client_id="$LAKEKEEPER_TEST__OPENID_CLIENT_ID" \
+  -d client_secret="$LAKEKEEPER_TEST__OPENID_CLIENT_SECRET" \
+  -d scope="$LAKEKEEPER_TEST__OPENID_SCOPE" | json_field access_token)
+
+function management() {
+  curl -sSf -X "$1" "$MANAGEMENT_URL/$2" \
+    -H "Authorization: Bearer $ACCESS_TOKEN" \
+    -H "Content-Type: application/json" \
+    ${3:+-d "$3"}
+}
+
+if management GET v1/info | grep -q '"bootstrapped" *: *false'; then
+  echo "Bootstrapping server ..."
+  management POST v1/bootstrap '{"accept-terms-of-use": true}' >/dev/null
+fi
+
+TEST_ID=$(cat /proc/sys/kernel/random/uuid)
+PROJECT_ID=$(management POST v1/project "{\"project-name\": \"rck-$TEST_ID\"}" | json_field project-id)
+WAREHOUSE_NAME="rck-$TEST_ID"
+echo "Creating warehouse $WAREHOUSE_NAME in project $PROJECT_ID ..."
+management POST v1/warehouse "{
+  \"project-id\": \"$PROJECT_ID\",
+  \"warehouse-name\": \"$WAREHOUSE_NAME\",
+  \"storage-profile\": {
+    \"type\": \"s3\",
+    \"bucket\": \"$LAKEKEEPER_TEST__S3_BUCKET\",
+    \"region\": \"$LAKEKEEPER_TEST__S3_REGION\",
+    \"endpoint\": \"$LAKEKEEPER_TEST__S3_ENDPOINT\",
+    \"path-style-access\": true,
+    \"flavor\": \"minio\",
+    \"sts-enabled\": false
+  },
+  \"storage-credential\": {
+    \"type\": \"s3\",
+    \"credential-type\": \"access-key\",
+    \"aws-access-key-id\": \"$LAKEKEEPER_TEST__S3_ACCESS_KEY\",
+    \"aws-secret-access-key\": \"$LAKEKEEPER_TEST__S3_SECRET_KEY\"
+  },
+  \"delete-profile\": {\"type\": \"hard\"}
+}" >/dev/null
+
+# The RCK reads catalog properties from CATALOG_* env vars:
+# the prefix is stripped, `__` becomes `-` and `_` becomes `.`.
+export CATALOG_URI="$LAKEKEEPER_TEST__CATALOG_URL"
+export CATALOG_WAREHOUSE="$PROJECT_ID/$WAREHOUSE_NAME"
+export CATALOG_CREDENTIAL="$LAKEKEEPER_TEST__OPENID_CLIENT_ID:$LAKEKEEPER_TEST__OPENID_CLIENT_SECRET"
+export CATALOG_SCOPE="$LAKEKEEPER_TEST__OPENID_SCOPE"
+export CATALOG_OAUTH2__SERVER__URI="$TOKEN_ENDPOINT"
+export CATALOG_IO__IMPL=org.apache.iceberg.aws.s3.S3FileIO
+export CATALOG_S3_ENDPOINT="$LAKEKEEPER_TEST__S3_ENDPOINT"
+export CATALOG_S3_PATH__STYLE__ACCESS=true
+export CATALOG_S3_ACCESS__KEY__ID="$LAKEKEEPER_TEST__S3_ACCESS_KEY"
+export CATALOG_S3_SECRET__ACCESS__KEY="$LAKEKEEPER_TEST__S3_SECRET_KEY"
+export CATALOG_CLIENT_REGION="$LAKEKEEPER_TEST__S3_REGION"
+export AWS_REGION="$LAKEKEEPER_TEST__S3_REGION"
+
+echo "Running REST Compatibility Kit ..."
+cd "$(dirname "$0")"
+mvn -B -q test